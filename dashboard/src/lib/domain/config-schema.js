@@ -11,6 +11,12 @@ export const advancedConfigTemplatePaths = Object.freeze([
     'rate_limit',
     'honeypot_enabled',
     'honeypots',
+    'honeypot_rotation_enabled',
+    'honeypot_rotation_interval_seconds',
+    'honeypot_rotation_grace_seconds',
+    'form_honeypot_enabled',
+    'form_honeypot_min_submit_seconds',
+    'form_honeypot_auto_ban',
     'browser_block',
     'browser_whitelist',
     'geo_risk',
@@ -62,6 +68,15 @@ export const advancedConfigTemplatePaths = Object.freeze([
     'ai_policy_block_search',
     'ai_policy_allow_search_engines',
     'robots_crawl_delay',
+    'ai_crawler_action',
+    'crawler_catalog',
+    'crawl_payment_price_micros',
+    'crawl_payment_currency',
+    'crawl_payment_issuers',
+    'llms_txt_title',
+    'llms_txt_summary',
+    'llms_txt_sections',
+    'tdm_policy_url',
     'cdp_detection_enabled',
     'cdp_auto_ban',
     'cdp_detection_threshold',
@@ -78,19 +93,41 @@ export const advancedConfigTemplatePaths = Object.freeze([
     'fingerprint_family_cap_temporal',
     'fingerprint_family_cap_persistence',
     'fingerprint_family_cap_behavior',
+    'fingerprint_tls_custom_rules',
+    'fingerprint_tls_managed_policies',
+    'behavior_signal_enabled',
+    'behavior_family_cap',
+    'privacy_pass_enabled',
+    'privacy_pass_issuers',
+    'web_bot_auth_enabled',
+    'web_bot_auth_agents',
     'js_required_enforced',
     'pow_enabled',
     'pow_difficulty',
     'pow_ttl_seconds',
+    'pow_algorithm',
+    'pow_scrypt_memory_kib',
+    'pow_scrypt_difficulty',
+    'pow_adaptive_enabled',
+    'pow_adaptive_max_extra_bits',
+    'pow_adaptive_load_threshold_per_minute',
     'challenge_puzzle_enabled',
     'challenge_puzzle_transform_count',
     'challenge_puzzle_risk_threshold',
+    'locale_overrides',
     'botness_maze_threshold',
     'botness_weights.js_required',
     'botness_weights.geo_risk',
     'botness_weights.rate_medium',
     'botness_weights.rate_high',
     'botness_weights.maze_behavior',
+    'botness_weights.behavior_timing_regular',
+    'botness_weights.behavior_asset_ratio',
+    'botness_weights.behavior_referrer_chain',
+    'botness_weights.behavior_breadth_first',
+    'botness_weights.behavior_sitemap_order',
+    'botness_weights.privacy_pass_attested',
+    'botness_weights.robots_violation',
     'defence_modes.rate',
     'defence_modes.geo',
     'defence_modes.js',
@@ -65,6 +65,12 @@ const VAR_MEANINGS = Object.freeze({
     rate_limit: 'Requests-per-minute threshold used by rate limiting.',
     honeypot_enabled: 'Enables/disables honeypot trap handling and enforcement for configured honeypot paths.',
     honeypots: 'Trap paths that are treated as high-confidence bot traffic.',
+    honeypot_rotation_enabled: 'Adds secret-derived trap paths that change every rotation interval, linked from HTML pages and listed in robots.txt.',
+    honeypot_rotation_interval_seconds: 'How often (seconds) a new rotating honeypot path generation starts.',
+    honeypot_rotation_grace_seconds: 'How long (seconds) paths from earlier generations are still treated as honeypots.',
+    form_honeypot_enabled: 'Injects decoy fields and a time-to-submit token into POST forms and blocks submissions that trip them.',
+    form_honeypot_min_submit_seconds: 'Form submissions sooner than this many seconds after the page was served are treated as bots.',
+    form_honeypot_auto_ban: 'Bans the client (honeypot ban duration) on a form honeypot trip instead of only blocking the submission.',
     browser_block: 'Minimum browser-version policy used for blocking suspicious automation stacks.',
     browser_whitelist: 'Browser-version exceptions allowed past browser policy blocks.',
     geo_risk: 'Country codes that add GEO botness score.',
@@ -131,6 +137,15 @@ const VAR_MEANINGS = Object.freeze({
     ai_policy_block_search: 'First-class AI policy alias for AI-search-bot blocking.',
     ai_policy_allow_search_engines: 'First-class AI policy alias for mainstream search allowance.',
     robots_crawl_delay: 'Crawl-delay value emitted in robots.txt.',
+    ai_crawler_action: 'Request-time action for declared AI crawlers that robots.txt disallows (allow, block, maze, payment_required).',
+    crawler_catalog: 'Known crawlers with UA patterns, category, verification, per-bot action and crawl-delay.',
+    crawl_payment_price_micros: 'Pay-per-crawl price per request, in millionths of the currency.',
+    crawl_payment_currency: 'Three-letter currency code for pay-per-crawl offers and tokens.',
+    crawl_payment_issuers: 'Payment providers whose Ed25519-signed crawl-payment tokens are accepted.',
+    llms_txt_title: 'Title heading for /llms.txt (defaults to "llms.txt").',
+    llms_txt_summary: 'One-line summary quoted under the /llms.txt title.',
+    llms_txt_sections: 'Curated /llms.txt link sections, withheld when AI search is blocked.',
+    tdm_policy_url: 'TDMRep policy URL advertised alongside a text-and-data-mining reservation.',
     cdp_detection_enabled: 'Enables client CDP automation-signal collection and scoring.',
     cdp_auto_ban: 'Auto-bans only on strong CDP automation outcomes.',
     cdp_detection_threshold: 'CDP score threshold when hard automation checks are absent.',
@@ -156,14 +171,28 @@ const VAR_MEANINGS = Object.freeze({
       'Per-family cap for persistence-abuse fingerprint contributions.',
     fingerprint_family_cap_behavior:
       'Per-family cap for flow/behavioral fingerprint contributions.',
-    js_required_enforced: 'Requires valid js_verified cookie for normal request flow.',
+    behavior_signal_enabled:
+      'Enables the cross-request behavioural session model (timing, assets, referrers, traversal order).',
+    behavior_family_cap: 'Total botness contribution cap for behavioural session signals.',
+    privacy_pass_enabled: 'Sends PrivateToken challenges on challenge pages and redeems Privacy Pass tokens.',
+    privacy_pass_issuers: 'Trusted Privacy Pass issuers (name and base64url RSA token key).',
+    web_bot_auth_enabled: 'Verifies Web Bot Auth message signatures from configured agents.',
+    web_bot_auth_agents: 'Trusted signing agents (name, directory origin, pinned keys, action and rate limit).',
+    js_required_enforced: 'Requires valid shuma_clearance cookie for normal request flow.',
     pow_enabled: 'Enables PoW in JS verification flow.',
     pow_difficulty: 'PoW difficulty (leading-zero bits).',
     pow_ttl_seconds: 'PoW seed lifetime in seconds.',
+    pow_algorithm: 'PoW hash function (sha256 or memory-hard scrypt).',
+    pow_scrypt_memory_kib: 'Memory per scrypt PoW attempt in KiB (power of two).',
+    pow_scrypt_difficulty: 'Scrypt PoW difficulty (leading-zero bits).',
+    pow_adaptive_enabled: 'Raises PoW difficulty per seed from botness, recent failures and issue load.',
+    pow_adaptive_max_extra_bits: 'Maximum leading-zero bits adaptive PoW adds to the base difficulty.',
+    pow_adaptive_load_threshold_per_minute: 'Seeds issued per minute before adaptive PoW adds load bits (0 disables).',
     challenge_puzzle_enabled: 'Enables/disables challenge puzzle routing at the challenge escalation step.',
     challenge_puzzle_transform_count: 'Challenge puzzle transform-option count.',
     challenge_puzzle_risk_threshold: 'Botness threshold for challenge step-up routing.',
     challenge_puzzle_risk_threshold_default: 'Default challenge threshold derived from environment seed.',
+    locale_overrides: 'Per-locale replacements for visitor-facing challenge and block page strings.',
     not_a_bot_enabled: 'Enables/disables Not-a-Bot checkbox routing at medium botness certainty.',
     not_a_bot_risk_threshold: 'Botness threshold for serving the Not-a-Bot checkbox step.',
     not_a_bot_score_pass_min: 'Minimum Not-a-Bot score required to pass and return to normal flow.',
@@ -179,6 +208,13 @@ const VAR_MEANINGS = Object.freeze({
     'botness_weights.rate_medium': 'Botness points at medium rate pressure.',
     'botness_weights.rate_high': 'Botness points at high rate pressure.',
     'botness_weights.maze_behavior': 'Botness points for suspicious maze traversal behavior.',
+    'botness_weights.behavior_timing_regular': 'Botness points for machine-regular page request timing.',
+    'botness_weights.behavior_asset_ratio': 'Botness points for pages fetched without subresources.',
+    'botness_weights.behavior_referrer_chain': 'Botness points for missing or unknown same-site referrers.',
+    'botness_weights.behavior_breadth_first': 'Botness points for breadth-first crawl traversal.',
+    'botness_weights.behavior_sitemap_order': 'Botness points for lexically ordered (sitemap-order) traversal.',
+    'botness_weights.privacy_pass_attested': 'Botness points subtracted for a redeemed Privacy Pass token.',
+    'botness_weights.robots_violation': 'Botness points for clients that fetched robots.txt and then requested a path it disallows for them.',
     'defence_modes.rate': 'Configured composability mode for rate module.',
     'defence_modes.geo': 'Configured composability mode for GEO module.',
     'defence_modes.js': 'Configured composability mode for JS module.',
@@ -242,6 +278,8 @@ const VAR_GROUP_DEFINITIONS = Object.freeze([
       title: 'Signals and Bypass Lists',
       matches: path => (
         path === 'honeypots' ||
+        path.startsWith('honeypot_rotation_') ||
+        path.startsWith('form_honeypot_') ||
         path.startsWith('browser_') ||
         path === 'whitelist' ||
         path === 'path_whitelist' ||
@@ -508,7 +546,7 @@ const STATUS_DEFINITIONS = [
     {
       title: 'Proof-of-Work (PoW)',
       description: snapshot => (
-        `PoW is applied in the JS verification flow and increases bot cost before <code>js_verified</code> is issued. ` +
+        `PoW is applied in the JS verification flow and increases bot cost before <code>shuma_clearance</code> is issued. ` +
         `Primary controls are ${envVar('SHUMA_POW_ENABLED')}, ${envVar('SHUMA_POW_DIFFICULTY')}, and ${envVar('SHUMA_POW_TTL_SECONDS')}. ` +
         `Runtime updates are available only when ${envVar('SHUMA_ADMIN_CONFIG_WRITE_ENABLED')} is enabled. ` +
         `If ${envVar('SHUMA_JS_REQUIRED_ENFORCED')} is disabled, normal visitor requests bypass this flow.`
@@ -556,8 +594,8 @@ const STATUS_DEFINITIONS = [
     {
       title: 'JS Required',
       description: snapshot => (
-        `When ${envVar('SHUMA_JS_REQUIRED_ENFORCED')} is true, requests without a valid <code>js_verified</code> cookie are sent to the JS verification page. ` +
-        `That flow writes <code>js_verified</code>, reloads the original path, and re-evaluates access. ` +
+        `When ${envVar('SHUMA_JS_REQUIRED_ENFORCED')} is true, requests without a valid <code>shuma_clearance</code> cookie are sent to the JS verification page. ` +
+        `That flow writes <code>shuma_clearance</code>, reloads the original path, and re-evaluates access. ` +
         `If ${envVar('SHUMA_POW_ENABLED')} is true, this step includes PoW before the cookie is issued. ` +
         `Disabling ${envVar('SHUMA_JS_REQUIRED_ENFORCED')} allows non-JS clients but removes PoW from the normal request path. ` +
         `Its botness contribution is weighted separately by ${envVar('SHUMA_BOTNESS_WEIGHT_JS_REQUIRED')} ` +
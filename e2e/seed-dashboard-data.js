@@ -1,5 +1,8 @@
+const crypto = require("crypto");
+
 const DEFAULT_BASE_URL = "http://127.0.0.1:3000";
 const API_KEY = (process.env.SHUMA_API_KEY || "").trim();
+const SEED_USER_AGENT = "ShumaDashboardSeed/1.0";
 
 function authHeaders() {
   return {
@@ -48,6 +51,20 @@ async function request(baseURL, path, options = {}) {
   }
 }
 
+// Report checks are per-request probe codes derived from the salt inside the signed nonce.
+function cdpProbeCodes(nonce, checks) {
+  let salt = "";
+  try {
+    const payload = JSON.parse(Buffer.from(nonce.split(".")[0], "base64url").toString("utf8"));
+    salt = payload.probe_salt || "";
+  } catch {
+    // Missing or malformed nonce; the report is rejected server-side either way.
+  }
+  return checks.map((check) =>
+    crypto.createHash("sha256").update(`${salt}:${check}`).digest("hex").slice(0, 10)
+  );
+}
+
 async function safeUnban(baseURL, ip) {
   try {
     await request(baseURL, `/admin/unban?ip=${encodeURIComponent(ip)}`, {
@@ -98,16 +115,27 @@ async function seedDashboardData() {
       })
     });
 
+    // CDP reports are only accepted with the nonce from the interstitial served to this client.
+    const interstitial = await request(baseURL, "/", {
+      headers: {
+        ...forwardedHeaders(cdpIp),
+        "User-Agent": SEED_USER_AGENT
+      }
+    });
+    const nonceMatch = /nonce: "([^"]+)"/.exec(typeof interstitial === "string" ? interstitial : "");
+    const cdpNonce = nonceMatch ? nonceMatch[1] : "";
     await request(baseURL, "/cdp-report", {
       method: "POST",
       headers: {
         ...forwardedHeaders(cdpIp),
+        "User-Agent": SEED_USER_AGENT,
         "Content-Type": "application/json"
       },
       body: JSON.stringify({
+        nonce: cdpNonce,
         cdp_detected: true,
         score: 0.92,
-        checks: ["webdriver", "runtime_enable"]
+        checks: cdpProbeCodes(cdpNonce, ["webdriver", "cdp_timing"])
       })
     });
 
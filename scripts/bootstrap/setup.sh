@@ -109,11 +109,13 @@ ensure_env_local_file() {
 SHUMA_API_KEY=${SHUMA_API_KEY:-}
 SHUMA_ADMIN_READONLY_API_KEY=${SHUMA_ADMIN_READONLY_API_KEY:-}
 SHUMA_JS_SECRET=${SHUMA_JS_SECRET:-}
+SHUMA_JS_SECRET_PREVIOUS=${SHUMA_JS_SECRET_PREVIOUS:-}
 SHUMA_POW_SECRET=${SHUMA_POW_SECRET:-}
 SHUMA_CHALLENGE_SECRET=${SHUMA_CHALLENGE_SECRET:-}
 SHUMA_MAZE_PREVIEW_SECRET=${SHUMA_MAZE_PREVIEW_SECRET:-}
 SHUMA_FORWARDED_IP_SECRET=${SHUMA_FORWARDED_IP_SECRET:-}
 SHUMA_HEALTH_SECRET=${SHUMA_HEALTH_SECRET:-}
+SHUMA_CRAWL_PAYMENT_SECRET=${SHUMA_CRAWL_PAYMENT_SECRET:-}
 SHUMA_ADMIN_IP_ALLOWLIST=${SHUMA_ADMIN_IP_ALLOWLIST:-}
 SHUMA_ADMIN_AUTH_FAILURE_LIMIT_PER_MINUTE=${SHUMA_ADMIN_AUTH_FAILURE_LIMIT_PER_MINUTE:-}
 SHUMA_EVENT_LOG_RETENTION_HOURS=${SHUMA_EVENT_LOG_RETENTION_HOURS:-}
@@ -127,6 +129,12 @@ SHUMA_RATE_LIMITER_REDIS_URL=${SHUMA_RATE_LIMITER_REDIS_URL:-}
 SHUMA_BAN_STORE_REDIS_URL=${SHUMA_BAN_STORE_REDIS_URL:-}
 SHUMA_RATE_LIMITER_OUTAGE_MODE_MAIN=${SHUMA_RATE_LIMITER_OUTAGE_MODE_MAIN:-}
 SHUMA_RATE_LIMITER_OUTAGE_MODE_ADMIN_AUTH=${SHUMA_RATE_LIMITER_OUTAGE_MODE_ADMIN_AUTH:-}
+SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT=${SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT:-}
+SHUMA_OTEL_EXPORTER_OTLP_HEADERS=${SHUMA_OTEL_EXPORTER_OTLP_HEADERS:-}
+SHUMA_OTEL_TRACES_SAMPLE_RATIO=${SHUMA_OTEL_TRACES_SAMPLE_RATIO:-}
+SHUMA_OTEL_METRICS_EXPORT_INTERVAL_SECONDS=${SHUMA_OTEL_METRICS_EXPORT_INTERVAL_SECONDS:-}
+SHUMA_ALERT_WEBHOOK_URL=${SHUMA_ALERT_WEBHOOK_URL:-}
+SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS=${SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS:-}
 EOF
     fi
     chmod 600 "$ENV_LOCAL_FILE" 2>/dev/null || true
@@ -406,11 +414,13 @@ ensure_env_local_file
 ensure_local_dev_secret "SHUMA_API_KEY" 32
 ensure_env_local_default_from_defaults "SHUMA_ADMIN_READONLY_API_KEY"
 ensure_local_dev_secret "SHUMA_JS_SECRET" 32
+ensure_env_local_default_from_defaults "SHUMA_JS_SECRET_PREVIOUS"
 ensure_local_dev_secret "SHUMA_FORWARDED_IP_SECRET" 32
 ensure_env_local_default_from_defaults "SHUMA_POW_SECRET"
 ensure_env_local_default_from_defaults "SHUMA_CHALLENGE_SECRET"
 ensure_env_local_default_from_defaults "SHUMA_MAZE_PREVIEW_SECRET"
 ensure_env_local_default_from_defaults "SHUMA_HEALTH_SECRET"
+ensure_env_local_default_from_defaults "SHUMA_CRAWL_PAYMENT_SECRET"
 ensure_env_local_default_from_defaults "SHUMA_ADMIN_IP_ALLOWLIST"
 ensure_env_local_default_from_defaults "SHUMA_ADMIN_AUTH_FAILURE_LIMIT_PER_MINUTE"
 ensure_env_local_default_from_defaults "SHUMA_EVENT_LOG_RETENTION_HOURS"
@@ -424,6 +434,12 @@ ensure_env_local_default_from_defaults "SHUMA_RATE_LIMITER_REDIS_URL"
 ensure_env_local_default_from_defaults "SHUMA_BAN_STORE_REDIS_URL"
 ensure_env_local_default_from_defaults "SHUMA_RATE_LIMITER_OUTAGE_MODE_MAIN"
 ensure_env_local_default_from_defaults "SHUMA_RATE_LIMITER_OUTAGE_MODE_ADMIN_AUTH"
+ensure_env_local_default_from_defaults "SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT"
+ensure_env_local_default_from_defaults "SHUMA_OTEL_EXPORTER_OTLP_HEADERS"
+ensure_env_local_default_from_defaults "SHUMA_OTEL_TRACES_SAMPLE_RATIO"
+ensure_env_local_default_from_defaults "SHUMA_OTEL_METRICS_EXPORT_INTERVAL_SECONDS"
+ensure_env_local_default_from_defaults "SHUMA_ALERT_WEBHOOK_URL"
+ensure_env_local_default_from_defaults "SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS"
 normalize_env_local_unquoted_style
 success "Local dev secrets are ready in $ENV_LOCAL_FILE"
 
@@ -82,6 +82,12 @@ cat > "${tmp_json}" <<EOF
   "rate_limit": ${SHUMA_RATE_LIMIT},
   "honeypot_enabled": $(bool_norm "${SHUMA_HONEYPOT_ENABLED}"),
   "honeypots": ${SHUMA_HONEYPOTS},
+  "honeypot_rotation_enabled": $(bool_norm "${SHUMA_HONEYPOT_ROTATION_ENABLED}"),
+  "honeypot_rotation_interval_seconds": ${SHUMA_HONEYPOT_ROTATION_INTERVAL_SECONDS},
+  "honeypot_rotation_grace_seconds": ${SHUMA_HONEYPOT_ROTATION_GRACE_SECONDS},
+  "form_honeypot_enabled": $(bool_norm "${SHUMA_FORM_HONEYPOT_ENABLED}"),
+  "form_honeypot_min_submit_seconds": ${SHUMA_FORM_HONEYPOT_MIN_SUBMIT_SECONDS},
+  "form_honeypot_auto_ban": $(bool_norm "${SHUMA_FORM_HONEYPOT_AUTO_BAN}"),
   "browser_block": ${SHUMA_BROWSER_BLOCK},
   "browser_whitelist": ${SHUMA_BROWSER_WHITELIST},
   "geo_risk": ${SHUMA_GEO_RISK_COUNTRIES},
@@ -137,13 +143,36 @@ cat > "${tmp_json}" <<EOF
   "ai_policy_block_search": $(bool_norm "${SHUMA_AI_POLICY_BLOCK_SEARCH}"),
   "ai_policy_allow_search_engines": $(bool_norm "${SHUMA_AI_POLICY_ALLOW_SEARCH_ENGINES}"),
   "robots_crawl_delay": ${SHUMA_ROBOTS_CRAWL_DELAY},
+  "ai_crawler_action": "${SHUMA_AI_CRAWLER_ACTION}",
+  "crawler_catalog": ${SHUMA_CRAWLER_CATALOG},
+  "crawl_payment_price_micros": ${SHUMA_CRAWL_PAYMENT_PRICE_MICROS},
+  "crawl_payment_currency": "${SHUMA_CRAWL_PAYMENT_CURRENCY}",
+  "crawl_payment_issuers": ${SHUMA_CRAWL_PAYMENT_ISSUERS},
+  "llms_txt_title": "${SHUMA_LLMS_TXT_TITLE}",
+  "llms_txt_summary": "${SHUMA_LLMS_TXT_SUMMARY}",
+  "llms_txt_sections": ${SHUMA_LLMS_TXT_SECTIONS},
+  "tdm_policy_url": "${SHUMA_TDM_POLICY_URL}",
   "cdp_detection_enabled": $(bool_norm "${SHUMA_CDP_DETECTION_ENABLED}"),
   "cdp_auto_ban": $(bool_norm "${SHUMA_CDP_AUTO_BAN}"),
   "cdp_detection_threshold": ${SHUMA_CDP_DETECTION_THRESHOLD},
+  "fingerprint_tls_custom_rules": ${SHUMA_FINGERPRINT_TLS_CUSTOM_RULES},
+  "fingerprint_tls_managed_policies": ${SHUMA_FINGERPRINT_TLS_MANAGED_POLICIES},
+  "behavior_signal_enabled": $(bool_norm "${SHUMA_BEHAVIOR_SIGNAL_ENABLED}"),
+  "behavior_family_cap": ${SHUMA_BEHAVIOR_FAMILY_CAP},
+  "privacy_pass_enabled": $(bool_norm "${SHUMA_PRIVACY_PASS_ENABLED}"),
+  "privacy_pass_issuers": ${SHUMA_PRIVACY_PASS_ISSUERS},
+  "web_bot_auth_enabled": $(bool_norm "${SHUMA_WEB_BOT_AUTH_ENABLED}"),
+  "web_bot_auth_agents": ${SHUMA_WEB_BOT_AUTH_AGENTS},
   "js_required_enforced": $(bool_norm "${SHUMA_JS_REQUIRED_ENFORCED}"),
   "pow_enabled": $(bool_norm "${SHUMA_POW_ENABLED}"),
   "pow_difficulty": ${SHUMA_POW_DIFFICULTY},
   "pow_ttl_seconds": ${SHUMA_POW_TTL_SECONDS},
+  "pow_algorithm": "${SHUMA_POW_ALGORITHM}",
+  "pow_scrypt_memory_kib": ${SHUMA_POW_SCRYPT_MEMORY_KIB},
+  "pow_scrypt_difficulty": ${SHUMA_POW_SCRYPT_DIFFICULTY},
+  "pow_adaptive_enabled": $(bool_norm "${SHUMA_POW_ADAPTIVE_ENABLED}"),
+  "pow_adaptive_max_extra_bits": ${SHUMA_POW_ADAPTIVE_MAX_EXTRA_BITS},
+  "pow_adaptive_load_threshold_per_minute": ${SHUMA_POW_ADAPTIVE_LOAD_THRESHOLD_PER_MINUTE},
   "challenge_puzzle_enabled": $(bool_norm "${SHUMA_CHALLENGE_PUZZLE_ENABLED}"),
   "challenge_puzzle_transform_count": ${SHUMA_CHALLENGE_PUZZLE_TRANSFORM_COUNT},
   "challenge_puzzle_risk_threshold": ${SHUMA_CHALLENGE_PUZZLE_RISK_THRESHOLD},
@@ -155,13 +184,21 @@ cat > "${tmp_json}" <<EOF
   "not_a_bot_marker_ttl_seconds": ${SHUMA_NOT_A_BOT_MARKER_TTL_SECONDS},
   "not_a_bot_attempt_limit_per_window": ${SHUMA_NOT_A_BOT_ATTEMPT_LIMIT_PER_WINDOW},
   "not_a_bot_attempt_window_seconds": ${SHUMA_NOT_A_BOT_ATTEMPT_WINDOW_SECONDS},
+  "locale_overrides": ${SHUMA_LOCALE_OVERRIDES},
   "botness_maze_threshold": ${SHUMA_BOTNESS_MAZE_THRESHOLD},
   "botness_weights": {
     "js_required": ${SHUMA_BOTNESS_WEIGHT_JS_REQUIRED},
     "geo_risk": ${SHUMA_BOTNESS_WEIGHT_GEO_RISK},
     "rate_medium": ${SHUMA_BOTNESS_WEIGHT_RATE_MEDIUM},
     "rate_high": ${SHUMA_BOTNESS_WEIGHT_RATE_HIGH},
-    "maze_behavior": ${SHUMA_BOTNESS_WEIGHT_MAZE_BEHAVIOR}
+    "maze_behavior": ${SHUMA_BOTNESS_WEIGHT_MAZE_BEHAVIOR},
+    "behavior_timing_regular": ${SHUMA_BOTNESS_WEIGHT_BEHAVIOR_TIMING_REGULAR},
+    "behavior_asset_ratio": ${SHUMA_BOTNESS_WEIGHT_BEHAVIOR_ASSET_RATIO},
+    "behavior_referrer_chain": ${SHUMA_BOTNESS_WEIGHT_BEHAVIOR_REFERRER_CHAIN},
+    "behavior_breadth_first": ${SHUMA_BOTNESS_WEIGHT_BEHAVIOR_BREADTH_FIRST},
+    "behavior_sitemap_order": ${SHUMA_BOTNESS_WEIGHT_BEHAVIOR_SITEMAP_ORDER},
+    "privacy_pass_attested": ${SHUMA_BOTNESS_WEIGHT_PRIVACY_PASS_ATTESTED},
+    "robots_violation": ${SHUMA_BOTNESS_WEIGHT_ROBOTS_VIOLATION}
   },
   "defence_modes": {
     "rate": "${SHUMA_MODE_RATE}",
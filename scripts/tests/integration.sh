@@ -27,7 +27,7 @@
 #   15. GEO policy block route
 #   16. Legacy /maze/* and /trap/* route rejection (no direct maze surface)
 #   17. Prometheus metrics endpoint
-#   18. CDP report endpoint (POST /cdp-report)
+#   18. CDP report endpoint (POST /cdp-report) and report nonce replay rejection
 #   19. CDP auto-ban with high score
 #   20. CDP config via admin API
 #   21. CDP stats counters reflect reports
@@ -409,7 +409,7 @@ fi
 info "Testing root endpoint..."
 
 root_resp=$(curl -s "${FORWARDED_SECRET_HEADER[@]}" -H "X-Forwarded-For: ${TEST_HONEYPOT_IP}" "$BASE_URL/")
-if echo "$root_resp" | grep -qE '(js_verified|JavaScript|Verifying|pow|Proof-of-work)'; then
+if echo "$root_resp" | grep -qE '(shuma_clearance|JavaScript|Verifying|pow|Proof-of-work)'; then
   pass "/ returns JS challenge (PoW or standard)"
 else
   fail "/ did not return expected JS challenge page"
@@ -498,8 +498,8 @@ else
     -X POST "$BASE_URL/challenge/not-a-bot-checkbox")
   if [[ "$not_a_bot_status" == "303" ]] \
     && grep -qi '^Location: /' "$not_a_bot_headers" \
-    && grep -qi '^Set-Cookie: shuma_not_a_bot=' "$not_a_bot_headers"; then
-    pass "POST /challenge/not-a-bot-checkbox returns redirect + marker cookie on pass"
+    && grep -qi '^Set-Cookie: shuma_clearance=' "$not_a_bot_headers"; then
+    pass "POST /challenge/not-a-bot-checkbox returns redirect + clearance cookie on pass"
   else
     fail "POST /challenge/not-a-bot-checkbox did not return expected pass response"
     echo -e "${YELLOW}DEBUG not-a-bot status:${NC} $not_a_bot_status"
@@ -873,10 +873,28 @@ else
   fail "/metrics monitoring parity metric families missing or label vocabularies drifted"
 fi
 
+# CDP reports must carry the nonce embedded in the JS interstitial served to the same client,
+# with checks encoded as that suite's per-request probe codes (derived from the nonce salt).
+cdp_report_body() {
+  local page
+  page=$(curl -s "${FORWARDED_SECRET_HEADER[@]}" -H "X-Forwarded-For: $1" -H "User-Agent: ${INTEGRATION_USER_AGENT}" "$BASE_URL/")
+  python3 -c '
+import base64, hashlib, json, re, sys
+m = re.search(r"nonce: \"([^\"]+)\"", sys.stdin.read())
+nonce = m.group(1) if m else ""
+salt = ""
+if nonce:
+    payload = nonce.split(".")[0]
+    salt = json.loads(base64.urlsafe_b64decode(payload + "=" * (-len(payload) % 4))).get("probe_salt", "")
+checks = [hashlib.sha256(f"{salt}:{c}".encode()).hexdigest()[:10] for c in sys.argv[1].split(",")]
+print(json.dumps({"nonce": nonce, "cdp_detected": True, "score": float(sys.argv[2]), "checks": checks}))
+' "$2" "$3" <<< "$page"
+}
+
 # Test 17: CDP report endpoint exists
 info "Testing POST /cdp-report endpoint..."
-cdp_report='{"cdp_detected":true,"score":0.5,"checks":["webdriver"]}'
-cdp_resp=$(curl -s "${FORWARDED_SECRET_HEADER[@]}" -X POST -H "Content-Type: application/json" -H "X-Forwarded-For: 10.0.0.200" -d "$cdp_report" "$BASE_URL/cdp-report")
+cdp_report=$(cdp_report_body 10.0.0.200 webdriver 0.5)
+cdp_resp=$(curl -s "${FORWARDED_SECRET_HEADER[@]}" -X POST -H "Content-Type: application/json" -H "X-Forwarded-For: 10.0.0.200" -H "User-Agent: ${INTEGRATION_USER_AGENT}" -d "$cdp_report" "$BASE_URL/cdp-report")
 if echo "$cdp_resp" | grep -qiE 'received|disabled|detected'; then
   pass "/cdp-report endpoint accepts detection reports"
 else
@@ -884,10 +902,19 @@ else
   echo -e "${YELLOW}DEBUG cdp response:${NC} $cdp_resp"
 fi
 
+# Test 17b: CDP report nonce is single-use
+info "Testing CDP report replay rejection..."
+cdp_replay_status=$(curl -s -o /dev/null -w "%{http_code}" "${FORWARDED_SECRET_HEADER[@]}" -X POST -H "Content-Type: application/json" -H "X-Forwarded-For: 10.0.0.200" -H "User-Agent: ${INTEGRATION_USER_AGENT}" -d "$cdp_report" "$BASE_URL/cdp-report")
+if echo "$cdp_resp" | grep -qi 'disabled' || [[ "$cdp_replay_status" == "403" ]]; then
+  pass "/cdp-report rejects a replayed report nonce"
+else
+  fail "/cdp-report accepted a replayed report nonce (status=${cdp_replay_status})"
+fi
+
 # Test 18: CDP report with high score triggers action (when enabled)
 info "Testing CDP auto-ban with high score..."
-cdp_high='{"cdp_detected":true,"score":0.95,"checks":["webdriver","automation_props","cdp_timing"]}'
-cdp_high_resp=$(curl -s "${FORWARDED_SECRET_HEADER[@]}" -X POST -H "Content-Type: application/json" -H "X-Forwarded-For: 10.0.0.201" -d "$cdp_high" "$BASE_URL/cdp-report")
+cdp_high=$(cdp_report_body 10.0.0.201 webdriver,automation_props,cdp_timing 0.95)
+cdp_high_resp=$(curl -s "${FORWARDED_SECRET_HEADER[@]}" -X POST -H "Content-Type: application/json" -H "X-Forwarded-For: 10.0.0.201" -H "User-Agent: ${INTEGRATION_USER_AGENT}" -d "$cdp_high" "$BASE_URL/cdp-report")
 if echo "$cdp_high_resp" | grep -qiE 'banned|received|disabled'; then
   pass "/cdp-report handles high-score detection"
 else
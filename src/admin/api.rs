@@ -3,7 +3,6 @@ use rand::random;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashSet};
 use std::sync::Mutex;
-use std::time::{SystemTime, UNIX_EPOCH};
 /// Event types for activity logging
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum EventType {
@@ -27,11 +26,17 @@ pub struct EventLogEntry {
 
 /// Event log storage notes:
 /// - v2 format stores immutable records per event: eventlog:v2:<hour>:<ts>-<nonce>
-const EVENTLOG_V2_PREFIX: &str = "eventlog:v2";
+pub(crate) const EVENTLOG_V2_PREFIX: &str = "eventlog:v2";
 const POW_DIFFICULTY_MIN: u8 = crate::config::POW_DIFFICULTY_MIN;
 const POW_DIFFICULTY_MAX: u8 = crate::config::POW_DIFFICULTY_MAX;
 const POW_TTL_MIN: u64 = crate::config::POW_TTL_MIN;
 const POW_TTL_MAX: u64 = crate::config::POW_TTL_MAX;
+const POW_SCRYPT_MEMORY_KIB_MIN: u32 = crate::config::POW_SCRYPT_MEMORY_KIB_MIN;
+const POW_SCRYPT_MEMORY_KIB_MAX: u32 = crate::config::POW_SCRYPT_MEMORY_KIB_MAX;
+const POW_SCRYPT_DIFFICULTY_MIN: u8 = crate::config::POW_SCRYPT_DIFFICULTY_MIN;
+const POW_SCRYPT_DIFFICULTY_MAX: u8 = crate::config::POW_SCRYPT_DIFFICULTY_MAX;
+const POW_ADAPTIVE_MAX_EXTRA_BITS_MAX: u8 = crate::config::POW_ADAPTIVE_MAX_EXTRA_BITS_MAX;
+const POW_ADAPTIVE_LOAD_THRESHOLD_MAX: u32 = crate::config::POW_ADAPTIVE_LOAD_THRESHOLD_MAX;
 const CHALLENGE_TRANSFORM_COUNT_MIN: u64 = 4;
 const CHALLENGE_TRANSFORM_COUNT_MAX: u64 = 8;
 const NOT_A_BOT_THRESHOLD_MIN: u64 = 1;
@@ -53,17 +58,29 @@ const IP_RANGE_CUSTOM_MESSAGE_MAX_CHARS: usize = 280;
 const IP_RANGE_REDIRECT_URL_MAX_CHARS: usize = 512;
 const IP_RANGE_MANAGED_MAX_STALENESS_HOURS_MIN: u64 = 1;
 const IP_RANGE_MANAGED_MAX_STALENESS_HOURS_MAX: u64 = 24 * 90;
-const CONFIG_EXPORT_SECRET_KEYS: [&str; 10] = [
+const TLS_FINGERPRINT_MAX_RULES: usize = 64;
+const CRAWL_PAYMENT_PRICE_MICROS_MAX: u64 = 1_000_000_000;
+const FORM_HONEYPOT_MIN_SUBMIT_SECONDS_MAX: u64 = 60;
+const HONEYPOT_ROTATION_INTERVAL_SECONDS_MIN: u64 = 300;
+const HONEYPOT_ROTATION_INTERVAL_SECONDS_MAX: u64 = 30 * 24 * 60 * 60;
+const HONEYPOT_ROTATION_GRACE_SECONDS_MAX: u64 = 30 * 24 * 60 * 60;
+const TLS_FINGERPRINT_MAX_VALUES_PER_RULE: usize = 256;
+const CONFIG_EXPORT_SECRET_KEYS: [&str; 15] = [
     "SHUMA_API_KEY",
     "SHUMA_ADMIN_READONLY_API_KEY",
     "SHUMA_JS_SECRET",
+    "SHUMA_JS_SECRET_PREVIOUS",
     "SHUMA_POW_SECRET",
     "SHUMA_CHALLENGE_SECRET",
     "SHUMA_MAZE_PREVIEW_SECRET",
     "SHUMA_FORWARDED_IP_SECRET",
     "SHUMA_HEALTH_SECRET",
+    "SHUMA_CRAWL_PAYMENT_SECRET",
     "SHUMA_RATE_LIMITER_REDIS_URL",
     "SHUMA_BAN_STORE_REDIS_URL",
+    "SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT",
+    "SHUMA_OTEL_EXPORTER_OTLP_HEADERS",
+    "SHUMA_ALERT_WEBHOOK_URL",
 ];
 
 static LAST_EVENTLOG_CLEANUP_HOUR: Lazy<Mutex<u64>> = Lazy::new(|| Mutex::new(0));
@@ -118,7 +135,10 @@ fn parse_v2_event_hour(key: &str) -> Option<u64> {
     }
 }
 
-pub fn log_event<S: crate::challenge::KeyValueStore>(store: &S, entry: &EventLogEntry) {
+pub fn log_event<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, entry: &EventLogEntry) {
+    if let Some(reason) = entry.reason.as_deref() {
+        crate::observability::decision_trace::note_reason(reason);
+    }
     // Write each event to a distinct immutable key to avoid read-modify-write races.
     let hour = entry.ts / 3600;
     let key = make_v2_event_key(hour, entry.ts);
@@ -444,16 +464,6 @@ mod admin_config_tests {
         }
     }
 
-    impl crate::maze::state::MazeStateStore for TestStore {
-        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
-            crate::challenge::KeyValueStore::get(self, key)
-        }
-
-        fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
-            crate::challenge::KeyValueStore::set(self, key, value)
-        }
-    }
-
     fn clear_env(keys: &[&str]) {
         for key in keys {
             std::env::remove_var(key);
@@ -590,12 +600,16 @@ mod admin_config_tests {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_API_KEY", "admin-key-secret");
         std::env::set_var("SHUMA_JS_SECRET", "js-secret");
+        std::env::set_var("SHUMA_JS_SECRET_PREVIOUS", "old-js-secret");
         std::env::set_var("SHUMA_POW_SECRET", "pow-secret");
         std::env::set_var("SHUMA_CHALLENGE_SECRET", "challenge-secret");
         std::env::set_var("SHUMA_FORWARDED_IP_SECRET", "forwarded-secret");
         std::env::set_var("SHUMA_HEALTH_SECRET", "health-secret");
         std::env::set_var("SHUMA_RATE_LIMITER_REDIS_URL", "redis://secret@redis:6379");
         std::env::set_var("SHUMA_BAN_STORE_REDIS_URL", "redis://secret@redis:6379");
+        std::env::set_var("SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT", "https://user:pw@otel:4318");
+        std::env::set_var("SHUMA_OTEL_EXPORTER_OTLP_HEADERS", "authorization=Bearer secret");
+        std::env::set_var("SHUMA_ALERT_WEBHOOK_URL", "https://hooks.example.com/secret-token");
 
         let store = TestStore::default();
         let req = make_request(Method::Get, "/admin/config/export", Vec::new());
@@ -626,12 +640,16 @@ mod admin_config_tests {
         clear_env(&[
             "SHUMA_API_KEY",
             "SHUMA_JS_SECRET",
+            "SHUMA_JS_SECRET_PREVIOUS",
             "SHUMA_POW_SECRET",
             "SHUMA_CHALLENGE_SECRET",
             "SHUMA_FORWARDED_IP_SECRET",
             "SHUMA_HEALTH_SECRET",
             "SHUMA_RATE_LIMITER_REDIS_URL",
             "SHUMA_BAN_STORE_REDIS_URL",
+            "SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT",
+            "SHUMA_OTEL_EXPORTER_OTLP_HEADERS",
+            "SHUMA_ALERT_WEBHOOK_URL",
         ]);
     }
 
@@ -810,6 +828,84 @@ mod admin_config_tests {
         assert!(sanitize_path("/admin/maze/preview"));
     }
 
+    #[test]
+    fn admin_alerts_round_trip_rules_and_reject_invalid_payloads() {
+        let _lock = crate::test_support::lock_env();
+        let store = TestStore::default();
+        let post_req = make_request(
+            Method::Post,
+            "/admin/alerts",
+            br#"{"rules":[{"id":"bans-per-hour","metric":"bans","comparator":"gt","threshold":50}]}"#
+                .to_vec(),
+        );
+        let resp = handle_admin_alerts(&post_req, &store);
+        assert_eq!(*resp.status(), 200u16);
+
+        let get_req = make_request(Method::Get, "/admin/alerts", Vec::new());
+        let resp = handle_admin_alerts(&get_req, &store);
+        assert_eq!(*resp.status(), 200u16);
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["rules"][0]["id"], "bans-per-hour");
+        assert_eq!(body["rules"][0]["window_minutes"], 60);
+        assert_eq!(body["rules"][0]["enabled"], true);
+        assert!(body["state"]["rules"].as_array().unwrap().is_empty());
+
+        let bad_metric = make_request(
+            Method::Post,
+            "/admin/alerts",
+            br#"{"rules":[{"id":"x","metric":"nope","comparator":"gt","threshold":1}]}"#.to_vec(),
+        );
+        assert_eq!(*handle_admin_alerts(&bad_metric, &store).status(), 400u16);
+        let bad_window = make_request(
+            Method::Post,
+            "/admin/alerts",
+            br#"{"rules":[{"id":"x","metric":"bans","comparator":"gt","threshold":1,"window_minutes":0}]}"#
+                .to_vec(),
+        );
+        assert_eq!(*handle_admin_alerts(&bad_window, &store).status(), 400u16);
+        assert!(request_requires_admin_write("/admin/alerts", &Method::Post));
+        assert!(!request_requires_admin_write("/admin/alerts", &Method::Get));
+    }
+
+    #[test]
+    fn admin_decision_lookup_returns_persisted_trace() {
+        let _lock = crate::test_support::lock_env();
+        let store = TestStore::default();
+        crate::observability::decision_trace::begin();
+        crate::observability::decision_trace::note_policy_match(
+            &crate::runtime::policy_taxonomy::resolve_policy_match(
+                crate::runtime::policy_taxonomy::PolicyTransition::HoneypotHit,
+            ),
+        );
+        let draft = crate::observability::decision_trace::take_draft().unwrap();
+        let incident_id = crate::observability::decision_trace::persist(
+            &store,
+            draft,
+            "GET",
+            "/instaban",
+            403,
+            now_ts(),
+        )
+        .unwrap();
+
+        let path = format!("/admin/decisions/{}", incident_id);
+        assert!(sanitize_path(path.as_str()));
+        let req = make_request(Method::Get, path.as_str(), Vec::new());
+        let resp = handle_admin_decision(&req, &store, path.as_str());
+        assert_eq!(*resp.status(), 200u16);
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["incident_id"], incident_id);
+        assert_eq!(body["status"], 403);
+        assert!(body["transitions"][0]["detection"].as_str().is_some());
+
+        let missing = "/admin/decisions/1-00000000";
+        let req = make_request(Method::Get, missing, Vec::new());
+        assert_eq!(*handle_admin_decision(&req, &store, missing).status(), 404u16);
+        let req = make_request(Method::Post, path.as_str(), Vec::new());
+        assert_eq!(*handle_admin_decision(&req, &store, path.as_str()).status(), 405u16);
+        assert!(!sanitize_path("/admin/decisions/not-an-id"));
+    }
+
     #[test]
     fn admin_monitoring_returns_structured_summary_shape() {
         let _lock = crate::test_support::lock_env();
@@ -1152,6 +1248,36 @@ mod admin_config_tests {
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
 
+    #[test]
+    fn admin_config_validates_ai_crawler_action() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let bad_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"ai_crawler_action":"tarpit"}"#.to_vec(),
+        );
+        let bad_resp = handle_admin_config(&bad_req, &store, "default");
+        assert_eq!(*bad_resp.status(), 400u16);
+        let msg = String::from_utf8_lossy(bad_resp.body());
+        assert!(msg.contains("ai_crawler_action must be one of"));
+
+        let post_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"ai_crawler_action":"maze"}"#.to_vec(),
+        );
+        let post_resp = handle_admin_config(&post_req, &store, "default");
+        assert_eq!(*post_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(saved_cfg.ai_crawler_action, crate::config::AiCrawlerAction::Maze);
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
     #[test]
     fn admin_config_updates_lists_and_cdp_ban_duration() {
         let _lock = crate::test_support::lock_env();
@@ -1606,237 +1732,793 @@ mod admin_config_tests {
     }
 
     #[test]
-    fn admin_config_rejects_invalid_ip_range_payloads() {
+    fn admin_config_validates_tls_fingerprint_reputation_payloads() {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
         let store = TestStore::default();
 
-        let invalid_cidr = make_request(
+        let valid = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"ip_range_custom_rules":[{"id":"bad","enabled":true,"cidrs":["invalid"],"action":"forbidden_403"}]}"#
+            br#"{"fingerprint_tls_custom_rules":[{"id":"scraper","enabled":true,"ja3":["B32309A26951912BE7DBA376398ABC3B"],"action":"block"}],"fingerprint_tls_managed_policies":[{"set_id":"go_net_http","enabled":true}]}"#
                 .to_vec(),
         );
-        let invalid_cidr_resp = handle_admin_config(&invalid_cidr, &store, "default");
-        assert_eq!(*invalid_cidr_resp.status(), 400u16);
-        assert!(String::from_utf8_lossy(invalid_cidr_resp.body()).contains("invalid"));
-
-        let missing_redirect = make_request(
-            Method::Post,
-            "/admin/config",
-            br#"{"ip_range_custom_rules":[{"id":"redir","enabled":true,"cidrs":["203.0.113.0/24"],"action":"redirect_308"}]}"#
-                .to_vec(),
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(
+            saved_cfg.fingerprint_tls_custom_rules[0].ja3,
+            vec!["b32309a26951912be7dba376398abc3b".to_string()]
+        );
+        assert_eq!(
+            saved_cfg.fingerprint_tls_custom_rules[0].action,
+            crate::config::TlsFingerprintAction::Block
+        );
+        assert_eq!(
+            saved_cfg.fingerprint_tls_managed_policies[0].action,
+            crate::config::TlsFingerprintAction::Score
         );
-        let missing_redirect_resp = handle_admin_config(&missing_redirect, &store, "default");
-        assert_eq!(*missing_redirect_resp.status(), 400u16);
-        assert!(String::from_utf8_lossy(missing_redirect_resp.body()).contains("redirect_url"));
 
-        let unknown_managed = make_request(
+        let empty_rule = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"ip_range_managed_policies":[{"set_id":"deepseek","enabled":true,"action":"forbidden_403"}]}"#
+            br#"{"fingerprint_tls_custom_rules":[{"id":"empty","enabled":true,"action":"score"}]}"#
                 .to_vec(),
         );
-        let unknown_managed_resp = handle_admin_config(&unknown_managed, &store, "default");
-        assert_eq!(*unknown_managed_resp.status(), 400u16);
-        assert!(
-            String::from_utf8_lossy(unknown_managed_resp.body())
-                .contains("official source unavailable")
-        );
+        let empty_rule_resp = handle_admin_config(&empty_rule, &store, "default");
+        assert_eq!(*empty_rule_resp.status(), 400u16);
+        assert!(String::from_utf8_lossy(empty_rule_resp.body()).contains("at least one ja3 or ja4"));
 
-        let invalid_staleness = make_request(
+        let bad_action = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"ip_range_managed_max_staleness_hours":0}"#.to_vec(),
-        );
-        let invalid_staleness_resp = handle_admin_config(&invalid_staleness, &store, "default");
-        assert_eq!(*invalid_staleness_resp.status(), 400u16);
-        assert!(
-            String::from_utf8_lossy(invalid_staleness_resp.body())
-                .contains("ip_range_managed_max_staleness_hours out of range")
+            br#"{"fingerprint_tls_custom_rules":[{"id":"x","ja4":["t13d190900_9dc949149365_97f8aa674fd9"],"action":"maze"}]}"#
+                .to_vec(),
         );
+        let bad_action_resp = handle_admin_config(&bad_action, &store, "default");
+        assert_eq!(*bad_action_resp.status(), 400u16);
 
-        let invalid_allow_flag = make_request(
+        let unknown_set = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"ip_range_allow_stale_managed_enforce":"sometimes"}"#.to_vec(),
-        );
-        let invalid_allow_flag_resp = handle_admin_config(&invalid_allow_flag, &store, "default");
-        assert_eq!(*invalid_allow_flag_resp.status(), 400u16);
-        assert!(
-            String::from_utf8_lossy(invalid_allow_flag_resp.body())
-                .contains("ip_range_allow_stale_managed_enforce must be true or false")
+            br#"{"fingerprint_tls_managed_policies":[{"set_id":"headless_unknown","action":"block"}]}"#
+                .to_vec(),
         );
+        let unknown_set_resp = handle_admin_config(&unknown_set, &store, "default");
+        assert_eq!(*unknown_set_resp.status(), 400u16);
+        assert!(String::from_utf8_lossy(unknown_set_resp.body()).contains("unknown"));
 
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
 
     #[test]
-    fn admin_config_rejects_invalid_defence_mode_value() {
+    fn admin_config_validates_privacy_pass_issuers() {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
         let store = TestStore::default();
 
-        let post_req = make_request(
+        let valid = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"defence_modes":{"rate":"invalid"}}"#.to_vec(),
+            serde_json::to_vec(&serde_json::json!({
+                "privacy_pass_enabled": true,
+                "privacy_pass_issuers": [{
+                    "name": " Issuer.Example ",
+                    "token_key": crate::test_support::PRIVACY_PASS_TOKEN_KEY
+                }],
+                "botness_weights": { "privacy_pass_attested": 6 }
+            }))
+            .unwrap(),
         );
-        let post_resp = handle_admin_config(&post_req, &store, "default");
-        assert_eq!(*post_resp.status(), 400u16);
-        let msg = String::from_utf8_lossy(post_resp.body());
-        assert!(msg.contains("defence_modes.rate must be one of"));
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert!(saved_cfg.privacy_pass_enabled);
+        assert_eq!(saved_cfg.privacy_pass_issuers[0].name, "issuer.example");
+        assert_eq!(saved_cfg.botness_weights.privacy_pass_attested, 6);
+
+        for (payload, expected) in [
+            (
+                serde_json::json!({"privacy_pass_issuers": [{"name": "issuer.example", "token_key": "AAAA"}]}),
+                "token_key must be",
+            ),
+            (
+                serde_json::json!({"privacy_pass_issuers": [{"token_key": crate::test_support::PRIVACY_PASS_TOKEN_KEY}]}),
+                "name is required",
+            ),
+            (
+                serde_json::json!({"privacy_pass_issuers": [
+                    {"name": "issuer.example", "token_key": crate::test_support::PRIVACY_PASS_TOKEN_KEY},
+                    {"name": "ISSUER.example", "token_key": crate::test_support::PRIVACY_PASS_TOKEN_KEY}
+                ]}),
+                "duplicate name",
+            ),
+            (
+                serde_json::json!({"botness_weights": {"privacy_pass_attested": 11}}),
+                "privacy_pass_attested out of range",
+            ),
+        ] {
+            let req = make_request(
+                Method::Post,
+                "/admin/config",
+                serde_json::to_vec(&payload).unwrap(),
+            );
+            let resp = handle_admin_config(&req, &store, "default");
+            assert_eq!(*resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(resp.body()).contains(expected));
+        }
 
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
 
     #[test]
-    fn admin_config_rejects_unknown_defence_mode_key() {
+    fn admin_config_updates_form_honeypot_settings() {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
         let store = TestStore::default();
 
-        let post_req = make_request(
+        let valid = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"defence_modes":{"rate":"both","foo":"off"}}"#.to_vec(),
+            br#"{
+                "form_honeypot_enabled": false,
+                "form_honeypot_min_submit_seconds": 5,
+                "form_honeypot_auto_ban": true
+            }"#
+            .to_vec(),
         );
-        let post_resp = handle_admin_config(&post_req, &store, "default");
-        assert_eq!(*post_resp.status(), 400u16);
-        let msg = String::from_utf8_lossy(post_resp.body());
-        assert!(msg.contains("defence_modes.foo is not supported"));
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert!(!saved_cfg.form_honeypot_enabled);
+        assert_eq!(saved_cfg.form_honeypot_min_submit_seconds, 5);
+        assert!(saved_cfg.form_honeypot_auto_ban);
+
+        let invalid = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"form_honeypot_min_submit_seconds": 61}"#.to_vec(),
+        );
+        let invalid_resp = handle_admin_config(&invalid, &store, "default");
+        assert_eq!(*invalid_resp.status(), 400u16);
+        assert!(String::from_utf8_lossy(invalid_resp.body())
+            .contains("form_honeypot_min_submit_seconds out of range"));
 
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
 
     #[test]
-    fn admin_config_updates_provider_backends_and_edge_mode() {
+    fn admin_config_updates_honeypot_rotation_settings() {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
         let store = TestStore::default();
 
-        let post_req = make_request(
+        let valid = make_request(
             Method::Post,
             "/admin/config",
             br#"{
-                "provider_backends": {
-                    "rate_limiter": "external",
-                    "ban_store": "external",
-                    "fingerprint_signal": "external"
-                },
-                "edge_integration_mode": "advisory"
+                "honeypot_rotation_enabled": false,
+                "honeypot_rotation_interval_seconds": 3600,
+                "honeypot_rotation_grace_seconds": 0
             }"#
             .to_vec(),
         );
-        let post_resp = handle_admin_config(&post_req, &store, "default");
-        assert_eq!(*post_resp.status(), 200u16);
-        let post_json: serde_json::Value = serde_json::from_slice(post_resp.body()).unwrap();
-        let cfg = post_json.get("config").unwrap();
-        assert_eq!(
-            cfg.get("provider_backends"),
-            Some(&serde_json::json!({
-                "rate_limiter": "external",
-                "ban_store": "external",
-                "challenge_engine": "internal",
-                "maze_tarpit": "internal",
-                "fingerprint_signal": "external"
-            }))
-        );
-        assert_eq!(
-            cfg.get("edge_integration_mode"),
-            Some(&serde_json::json!("advisory"))
-        );
-
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
         let saved_bytes = store.get("config:default").unwrap().unwrap();
         let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
-        assert_eq!(
-            saved_cfg.provider_backends.rate_limiter,
-            crate::config::ProviderBackend::External
-        );
-        assert_eq!(
-            saved_cfg.provider_backends.ban_store,
-            crate::config::ProviderBackend::External
-        );
-        assert_eq!(
-            saved_cfg.provider_backends.fingerprint_signal,
-            crate::config::ProviderBackend::External
-        );
-        assert_eq!(
-            saved_cfg.edge_integration_mode,
-            crate::config::EdgeIntegrationMode::Advisory
-        );
+        assert!(!saved_cfg.honeypot_rotation_enabled);
+        assert_eq!(saved_cfg.honeypot_rotation_interval_seconds, 3600);
+        assert_eq!(saved_cfg.honeypot_rotation_grace_seconds, 0);
+
+        for (body, expected) in [
+            (
+                br#"{"honeypot_rotation_interval_seconds": 60}"#.to_vec(),
+                "honeypot_rotation_interval_seconds out of range",
+            ),
+            (
+                br#"{"honeypot_rotation_grace_seconds": 2592001}"#.to_vec(),
+                "honeypot_rotation_grace_seconds out of range",
+            ),
+        ] {
+            let invalid = make_request(Method::Post, "/admin/config", body);
+            let invalid_resp = handle_admin_config(&invalid, &store, "default");
+            assert_eq!(*invalid_resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(invalid_resp.body()).contains(expected));
+        }
 
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
 
     #[test]
-    fn admin_config_rejects_invalid_provider_backend_value() {
+    fn admin_config_validates_crawl_payment_settings() {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
         let store = TestStore::default();
 
-        let post_req = make_request(
+        let valid = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"provider_backends":{"rate_limiter":"invalid"}}"#.to_vec(),
+            serde_json::to_vec(&serde_json::json!({
+                "crawl_payment_price_micros": 2500,
+                "crawl_payment_currency": "eur",
+                "crawl_payment_issuers": [{
+                    "name": "Pay.Example",
+                    "key": "iojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SIAbQPb1w"
+                }]
+            }))
+            .unwrap(),
         );
-        let post_resp = handle_admin_config(&post_req, &store, "default");
-        assert_eq!(*post_resp.status(), 400u16);
-        let msg = String::from_utf8_lossy(post_resp.body());
-        assert!(msg.contains("provider_backends.rate_limiter must be one of"));
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(saved_cfg.crawl_payment_price_micros, 2500);
+        assert_eq!(saved_cfg.crawl_payment_currency, "EUR");
+        assert_eq!(saved_cfg.crawl_payment_issuers[0].name, "pay.example");
+
+        for (payload, expected) in [
+            (
+                serde_json::json!({"crawl_payment_price_micros": 0}),
+                "crawl_payment_price_micros out of range",
+            ),
+            (
+                serde_json::json!({"crawl_payment_currency": "dollars"}),
+                "three-letter currency code",
+            ),
+            (
+                serde_json::json!({"crawl_payment_issuers": [{"name": "pay.example", "key": "AAAA"}]}),
+                "Ed25519 public key",
+            ),
+        ] {
+            let req = make_request(
+                Method::Post,
+                "/admin/config",
+                serde_json::to_vec(&payload).unwrap(),
+            );
+            let resp = handle_admin_config(&req, &store, "default");
+            assert_eq!(*resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(resp.body()).contains(expected));
+        }
 
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
 
     #[test]
-    fn admin_config_rejects_invalid_edge_integration_mode() {
+    fn admin_config_validates_crawler_catalog() {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
         let store = TestStore::default();
 
-        let post_req = make_request(
+        let valid = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"edge_integration_mode":"invalid"}"#.to_vec(),
+            serde_json::to_vec(&serde_json::json!({
+                "crawler_catalog": [
+                    {"name": "GPTBot", "category": "ai_training", "verification": "ip_range", "ip_range_set": "OpenAI_GPTBot"},
+                    {"name": " NewsBot ", "ua_patterns": ["newsbot-ai"], "category": "ai_search", "action": "block", "crawl_delay": 10},
+                    {"name": "Googlebot", "category": "search_engine"}
+                ]
+            }))
+            .unwrap(),
         );
-        let post_resp = handle_admin_config(&post_req, &store, "default");
-        assert_eq!(*post_resp.status(), 400u16);
-        let msg = String::from_utf8_lossy(post_resp.body());
-        assert!(msg.contains("edge_integration_mode must be one of"));
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(saved_cfg.crawler_catalog.len(), 3);
+        assert_eq!(saved_cfg.crawler_catalog[0].ip_range_set, "openai_gptbot");
+        assert_eq!(saved_cfg.crawler_catalog[1].name, "NewsBot");
+        assert_eq!(
+            saved_cfg.crawler_catalog[1].action,
+            Some(crate::config::AiCrawlerAction::Block)
+        );
+        assert_eq!(saved_cfg.crawler_catalog[1].crawl_delay, Some(10));
+
+        for (payload, expected) in [
+            (
+                serde_json::json!({"crawler_catalog": [{"name": "GPTBot", "category": "ai_chat"}]}),
+                "crawler_catalog must be an array",
+            ),
+            (
+                serde_json::json!({"crawler_catalog": [
+                    {"name": "GPTBot", "category": "ai_training"},
+                    {"name": "gptbot", "category": "ai_search"}
+                ]}),
+                "duplicate name",
+            ),
+            (
+                serde_json::json!({"crawler_catalog": [{"name": "GPTBot", "verification": "ip_range", "ip_range_set": "nope"}]}),
+                "crawler_catalog[0].ip_range_set",
+            ),
+        ] {
+            let req = make_request(
+                Method::Post,
+                "/admin/config",
+                serde_json::to_vec(&payload).unwrap(),
+            );
+            let resp = handle_admin_config(&req, &store, "default");
+            assert_eq!(*resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(resp.body()).contains(expected));
+        }
 
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
 
     #[test]
-    fn admin_config_rejects_unknown_provider_backend_key() {
+    fn admin_config_validates_llms_txt_and_tdm_settings() {
         let _lock = crate::test_support::lock_env();
         std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
         let store = TestStore::default();
 
-        let post_req = make_request(
+        let valid = make_request(
             Method::Post,
             "/admin/config",
-            br#"{"provider_backends":{"fingerprint_signal":"external","unknown":"external"}}"#
-                .to_vec(),
+            serde_json::to_vec(&serde_json::json!({
+                "llms_txt_title": "Example Docs",
+                "llms_txt_summary": "Reference documentation for Example.",
+                "llms_txt_sections": [{
+                    "title": "Docs",
+                    "links": [{"title": "Quick start", "url": "/docs/start.md", "notes": "Install"}]
+                }],
+                "tdm_policy_url": "https://example.com/tdm-policy.json"
+            }))
+            .unwrap(),
         );
-        let post_resp = handle_admin_config(&post_req, &store, "default");
-        assert_eq!(*post_resp.status(), 400u16);
-        let msg = String::from_utf8_lossy(post_resp.body());
-        assert!(msg.contains("provider_backends.unknown is not supported"));
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(saved_cfg.llms_txt_title, "Example Docs");
+        assert_eq!(
+            saved_cfg.llms_txt_sections[0].links[0].url,
+            "/docs/start.md"
+        );
+        assert_eq!(
+            saved_cfg.tdm_policy_url,
+            "https://example.com/tdm-policy.json"
+        );
+
+        for (payload, expected) in [
+            (
+                serde_json::json!({"llms_txt_summary": "line one\nline two"}),
+                "llms_txt_summary must be a single line",
+            ),
+            (
+                serde_json::json!({"llms_txt_sections": {"title": "Docs"}}),
+                "llms_txt_sections must be an array",
+            ),
+            (
+                serde_json::json!({"llms_txt_sections": [{
+                    "title": "Docs",
+                    "links": [{"title": "x", "url": "javascript:alert(1)"}]
+                }]}),
+                "llms_txt_sections[0].links[0].url",
+            ),
+            (
+                serde_json::json!({"tdm_policy_url": "/tdm-policy.json"}),
+                "tdm_policy_url must be empty or an absolute http(s) URL",
+            ),
+        ] {
+            let req = make_request(
+                Method::Post,
+                "/admin/config",
+                serde_json::to_vec(&payload).unwrap(),
+            );
+            let resp = handle_admin_config(&req, &store, "default");
+            assert_eq!(*resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(resp.body()).contains(expected));
+        }
 
         std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
     }
-}
 
-#[cfg(test)]
-mod admin_auth_tests {
-    use super::*;
-    use spin_sdk::http::{Method, Request};
-    use std::collections::HashMap;
-    use std::sync::Mutex;
+    #[test]
+    fn admin_config_validates_web_bot_auth_agents() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+        let key = "iojj3XQJ8ZX9UtstPLpdcspnCb8dlBIb83SIAbQPb1w";
 
-    #[derive(Default)]
-    struct TestStore {
+        let valid = make_request(
+            Method::Post,
+            "/admin/config",
+            serde_json::to_vec(&serde_json::json!({
+                "web_bot_auth_enabled": true,
+                "web_bot_auth_agents": [{
+                    "name": "Agent",
+                    "directory": "https://Agent.example/",
+                    "keys": [key],
+                    "action": "rate_limit",
+                    "rate_limit": 120
+                }]
+            }))
+            .unwrap(),
+        );
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert!(saved_cfg.web_bot_auth_enabled);
+        let agent = &saved_cfg.web_bot_auth_agents[0];
+        assert_eq!(agent.name, "agent");
+        assert_eq!(agent.directory, "https://agent.example");
+        assert_eq!(agent.action, crate::config::VerifiedAgentAction::RateLimit);
+        assert_eq!(agent.rate_limit, 120);
+
+        for (payload, expected) in [
+            (
+                serde_json::json!({"web_bot_auth_agents": [{"name": "agent", "directory": "http://agent.example"}]}),
+                "must be an https origin",
+            ),
+            (
+                serde_json::json!({"web_bot_auth_agents": [{"name": "agent", "directory": "https://agent.example", "keys": ["AAAA"]}]}),
+                "Ed25519 public key",
+            ),
+            (
+                serde_json::json!({"web_bot_auth_agents": [{"name": "agent", "directory": "https://agent.example", "action": "block"}]}),
+                "action must be one of",
+            ),
+            (
+                serde_json::json!({"web_bot_auth_agents": [{"name": "agent", "directory": "https://agent.example", "action": "rate_limit"}]}),
+                "rate_limit must be greater than 0",
+            ),
+            (
+                serde_json::json!({"web_bot_auth_agents": [
+                    {"name": "a", "directory": "https://agent.example"},
+                    {"name": "b", "directory": "https://AGENT.example/"}
+                ]}),
+                "duplicate directory",
+            ),
+        ] {
+            let req = make_request(
+                Method::Post,
+                "/admin/config",
+                serde_json::to_vec(&payload).unwrap(),
+            );
+            let resp = handle_admin_config(&req, &store, "default");
+            assert_eq!(*resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(resp.body()).contains(expected));
+        }
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_validates_locale_overrides() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let valid = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"locale_overrides":{"de":{"block.rate_limit.title":" Bitte langsamer "},"fr":{}}}"#
+                .to_vec(),
+        );
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(
+            saved_cfg.locale_overrides["de"]["block.rate_limit.title"],
+            "Bitte langsamer"
+        );
+        assert!(!saved_cfg.locale_overrides.contains_key("fr"));
+
+        for (payload, expected) in [
+            (
+                br#"{"locale_overrides":{"xx":{"puzzle.title":"Puzzle"}}}"#.to_vec(),
+                "not supported",
+            ),
+            (
+                br#"{"locale_overrides":{"en":{"puzzle.missing":"Puzzle"}}}"#.to_vec(),
+                "unknown message key",
+            ),
+            (
+                br#"{"locale_overrides":{"en":{"puzzle.title":"  "}}}"#.to_vec(),
+                "non-empty string",
+            ),
+            (br#"{"locale_overrides":[]}"#.to_vec(), "keyed by locale"),
+        ] {
+            let req = make_request(Method::Post, "/admin/config", payload);
+            let resp = handle_admin_config(&req, &store, "default");
+            assert_eq!(*resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(resp.body()).contains(expected));
+        }
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_validates_pow_algorithm_fields() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let valid = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"pow_algorithm":"scrypt","pow_scrypt_memory_kib":8192,"pow_scrypt_difficulty":3,"pow_adaptive_enabled":false,"pow_adaptive_max_extra_bits":2}"#
+                .to_vec(),
+        );
+        let valid_resp = handle_admin_config(&valid, &store, "default");
+        assert_eq!(*valid_resp.status(), 200u16);
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(saved_cfg.pow_algorithm, crate::config::PowAlgorithm::Scrypt);
+        assert_eq!(saved_cfg.pow_scrypt_memory_kib, 8192);
+        assert_eq!(saved_cfg.pow_scrypt_difficulty, 3);
+        assert!(!saved_cfg.pow_adaptive_enabled);
+        assert_eq!(saved_cfg.pow_adaptive_max_extra_bits, 2);
+
+        for (payload, expected) in [
+            (br#"{"pow_algorithm":"argon2"}"#.to_vec(), "sha256, scrypt"),
+            (
+                br#"{"pow_scrypt_memory_kib":3000}"#.to_vec(),
+                "pow_scrypt_memory_kib",
+            ),
+            (
+                br#"{"pow_scrypt_memory_kib":32768}"#.to_vec(),
+                "pow_scrypt_memory_kib",
+            ),
+            (
+                br#"{"pow_scrypt_difficulty":11}"#.to_vec(),
+                "pow_scrypt_difficulty",
+            ),
+            (
+                br#"{"pow_adaptive_max_extra_bits":9}"#.to_vec(),
+                "pow_adaptive_max_extra_bits",
+            ),
+            (
+                br#"{"pow_adaptive_load_threshold_per_minute":1000001}"#.to_vec(),
+                "pow_adaptive_load_threshold_per_minute",
+            ),
+        ] {
+            let req = make_request(Method::Post, "/admin/config", payload);
+            let resp = handle_admin_config(&req, &store, "default");
+            assert_eq!(*resp.status(), 400u16);
+            assert!(String::from_utf8_lossy(resp.body()).contains(expected));
+        }
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_rejects_invalid_ip_range_payloads() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let invalid_cidr = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"ip_range_custom_rules":[{"id":"bad","enabled":true,"cidrs":["invalid"],"action":"forbidden_403"}]}"#
+                .to_vec(),
+        );
+        let invalid_cidr_resp = handle_admin_config(&invalid_cidr, &store, "default");
+        assert_eq!(*invalid_cidr_resp.status(), 400u16);
+        assert!(String::from_utf8_lossy(invalid_cidr_resp.body()).contains("invalid"));
+
+        let missing_redirect = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"ip_range_custom_rules":[{"id":"redir","enabled":true,"cidrs":["203.0.113.0/24"],"action":"redirect_308"}]}"#
+                .to_vec(),
+        );
+        let missing_redirect_resp = handle_admin_config(&missing_redirect, &store, "default");
+        assert_eq!(*missing_redirect_resp.status(), 400u16);
+        assert!(String::from_utf8_lossy(missing_redirect_resp.body()).contains("redirect_url"));
+
+        let unknown_managed = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"ip_range_managed_policies":[{"set_id":"deepseek","enabled":true,"action":"forbidden_403"}]}"#
+                .to_vec(),
+        );
+        let unknown_managed_resp = handle_admin_config(&unknown_managed, &store, "default");
+        assert_eq!(*unknown_managed_resp.status(), 400u16);
+        assert!(
+            String::from_utf8_lossy(unknown_managed_resp.body())
+                .contains("official source unavailable")
+        );
+
+        let invalid_staleness = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"ip_range_managed_max_staleness_hours":0}"#.to_vec(),
+        );
+        let invalid_staleness_resp = handle_admin_config(&invalid_staleness, &store, "default");
+        assert_eq!(*invalid_staleness_resp.status(), 400u16);
+        assert!(
+            String::from_utf8_lossy(invalid_staleness_resp.body())
+                .contains("ip_range_managed_max_staleness_hours out of range")
+        );
+
+        let invalid_allow_flag = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"ip_range_allow_stale_managed_enforce":"sometimes"}"#.to_vec(),
+        );
+        let invalid_allow_flag_resp = handle_admin_config(&invalid_allow_flag, &store, "default");
+        assert_eq!(*invalid_allow_flag_resp.status(), 400u16);
+        assert!(
+            String::from_utf8_lossy(invalid_allow_flag_resp.body())
+                .contains("ip_range_allow_stale_managed_enforce must be true or false")
+        );
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_rejects_invalid_defence_mode_value() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let post_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"defence_modes":{"rate":"invalid"}}"#.to_vec(),
+        );
+        let post_resp = handle_admin_config(&post_req, &store, "default");
+        assert_eq!(*post_resp.status(), 400u16);
+        let msg = String::from_utf8_lossy(post_resp.body());
+        assert!(msg.contains("defence_modes.rate must be one of"));
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_rejects_unknown_defence_mode_key() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let post_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"defence_modes":{"rate":"both","foo":"off"}}"#.to_vec(),
+        );
+        let post_resp = handle_admin_config(&post_req, &store, "default");
+        assert_eq!(*post_resp.status(), 400u16);
+        let msg = String::from_utf8_lossy(post_resp.body());
+        assert!(msg.contains("defence_modes.foo is not supported"));
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_updates_provider_backends_and_edge_mode() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let post_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{
+                "provider_backends": {
+                    "rate_limiter": "external",
+                    "ban_store": "external",
+                    "fingerprint_signal": "external"
+                },
+                "edge_integration_mode": "advisory"
+            }"#
+            .to_vec(),
+        );
+        let post_resp = handle_admin_config(&post_req, &store, "default");
+        assert_eq!(*post_resp.status(), 200u16);
+        let post_json: serde_json::Value = serde_json::from_slice(post_resp.body()).unwrap();
+        let cfg = post_json.get("config").unwrap();
+        assert_eq!(
+            cfg.get("provider_backends"),
+            Some(&serde_json::json!({
+                "rate_limiter": "external",
+                "ban_store": "external",
+                "challenge_engine": "internal",
+                "maze_tarpit": "internal",
+                "fingerprint_signal": "external"
+            }))
+        );
+        assert_eq!(
+            cfg.get("edge_integration_mode"),
+            Some(&serde_json::json!("advisory"))
+        );
+
+        let saved_bytes = store.get("config:default").unwrap().unwrap();
+        let saved_cfg: crate::config::Config = serde_json::from_slice(&saved_bytes).unwrap();
+        assert_eq!(
+            saved_cfg.provider_backends.rate_limiter,
+            crate::config::ProviderBackend::External
+        );
+        assert_eq!(
+            saved_cfg.provider_backends.ban_store,
+            crate::config::ProviderBackend::External
+        );
+        assert_eq!(
+            saved_cfg.provider_backends.fingerprint_signal,
+            crate::config::ProviderBackend::External
+        );
+        assert_eq!(
+            saved_cfg.edge_integration_mode,
+            crate::config::EdgeIntegrationMode::Advisory
+        );
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_rejects_invalid_provider_backend_value() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let post_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"provider_backends":{"rate_limiter":"invalid"}}"#.to_vec(),
+        );
+        let post_resp = handle_admin_config(&post_req, &store, "default");
+        assert_eq!(*post_resp.status(), 400u16);
+        let msg = String::from_utf8_lossy(post_resp.body());
+        assert!(msg.contains("provider_backends.rate_limiter must be one of"));
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_rejects_invalid_edge_integration_mode() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let post_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"edge_integration_mode":"invalid"}"#.to_vec(),
+        );
+        let post_resp = handle_admin_config(&post_req, &store, "default");
+        assert_eq!(*post_resp.status(), 400u16);
+        let msg = String::from_utf8_lossy(post_resp.body());
+        assert!(msg.contains("edge_integration_mode must be one of"));
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+
+    #[test]
+    fn admin_config_rejects_unknown_provider_backend_key() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "true");
+        let store = TestStore::default();
+
+        let post_req = make_request(
+            Method::Post,
+            "/admin/config",
+            br#"{"provider_backends":{"fingerprint_signal":"external","unknown":"external"}}"#
+                .to_vec(),
+        );
+        let post_resp = handle_admin_config(&post_req, &store, "default");
+        assert_eq!(*post_resp.status(), 400u16);
+        let msg = String::from_utf8_lossy(post_resp.body());
+        assert!(msg.contains("provider_backends.unknown is not supported"));
+
+        std::env::remove_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED");
+    }
+}
+
+#[cfg(test)]
+mod admin_auth_tests {
+    use super::*;
+    use spin_sdk::http::{Method, Request};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct TestStore {
         map: Mutex<HashMap<String, Vec<u8>>>,
     }
 
@@ -1939,10 +2621,7 @@ mod admin_auth_tests {
 
 /// Utility to get current unix timestamp
 pub fn now_ts() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
+    crate::runtime::clock::now_ts()
 }
 // src/admin.rs
 // Admin API endpoints for WASM Bot Defence
@@ -1977,7 +2656,13 @@ fn sanitize_path(path: &str) -> bool {
             | "/admin/cdp"
             | "/admin/cdp/events"
             | "/admin/monitoring"
-    )
+            | "/admin/alerts"
+    ) || admin_decision_id_from_path(path).is_some()
+}
+
+fn admin_decision_id_from_path(path: &str) -> Option<&str> {
+    path.strip_prefix("/admin/decisions/")
+        .filter(|id| crate::observability::decision_trace::is_valid_incident_id(id))
 }
 
 fn session_cookie_value(session_id: &str) -> String {
@@ -2165,6 +2850,7 @@ fn request_requires_admin_write(path: &str, method: &Method) -> bool {
             | "/admin/config"
             | "/admin/maze/seeds"
             | "/admin/maze/seeds/refresh"
+            | "/admin/alerts"
     )
 }
 
@@ -2463,6 +3149,11 @@ fn botness_signal_definitions(cfg: &crate::config::Config) -> serde_json::Value
                 "label": "Fingerprint UA/client-hint mismatch",
                 "weight": 2
             },
+            {
+                "key": "fp_header_shape_mismatch",
+                "label": "Fingerprint header shape mismatch",
+                "weight": 2
+            },
             {
                 "key": "fp_ua_transport_mismatch",
                 "label": "Fingerprint UA/transport mismatch",
@@ -2474,27 +3165,69 @@ fn botness_signal_definitions(cfg: &crate::config::Config) -> serde_json::Value
                 "weight": 2
             },
             {
-                "key": "fp_flow_violation",
-                "label": "Fingerprint flow-window violation",
-                "weight": 2
+                "key": "fp_flow_violation",
+                "label": "Fingerprint flow-window violation",
+                "weight": 2
+            },
+            {
+                "key": "fp_persistence_marker_missing",
+                "label": "Fingerprint persistence-marker missing",
+                "weight": 1
+            },
+            {
+                "key": "fp_untrusted_transport_header",
+                "label": "Fingerprint untrusted transport header",
+                "weight": 3
+            },
+            {
+                "key": "fp_tls_reputation",
+                "label": "Fingerprint TLS reputation match",
+                "weight": 3
+            },
+            {
+                "key": "behavior_timing_regular",
+                "label": "Behavior: machine-regular request timing",
+                "weight": cfg.botness_weights.behavior_timing_regular
+            },
+            {
+                "key": "behavior_asset_ratio",
+                "label": "Behavior: pages fetched without assets",
+                "weight": cfg.botness_weights.behavior_asset_ratio
+            },
+            {
+                "key": "behavior_referrer_chain",
+                "label": "Behavior: broken referrer chain",
+                "weight": cfg.botness_weights.behavior_referrer_chain
+            },
+            {
+                "key": "behavior_breadth_first",
+                "label": "Behavior: breadth-first crawl",
+                "weight": cfg.botness_weights.behavior_breadth_first
+            },
+            {
+                "key": "behavior_sitemap_order",
+                "label": "Behavior: sitemap-order traversal",
+                "weight": cfg.botness_weights.behavior_sitemap_order
             },
             {
-                "key": "fp_persistence_marker_missing",
-                "label": "Fingerprint persistence-marker missing",
-                "weight": 1
+                "key": "privacy_pass_attested",
+                "label": "Privacy Pass attested (credit)",
+                "weight": cfg.botness_weights.privacy_pass_attested
             },
             {
-                "key": "fp_untrusted_transport_header",
-                "label": "Fingerprint untrusted transport header",
-                "weight": 3
+                "key": "robots_violation",
+                "label": "robots.txt disallow ignored",
+                "weight": cfg.botness_weights.robots_violation
             }
         ],
         "terminal_signals": [
             { "key": "honeypot", "label": "Honeypot hit", "action": "Immediate ban" },
+            { "key": "form_honeypot", "label": "Form honeypot tripped", "action": "Block page (ban if enabled)" },
             { "key": "rate_limit_exceeded", "label": "Rate limit exceeded", "action": "Immediate ban" },
             { "key": "outdated_browser", "label": "Outdated browser", "action": "Immediate ban" },
             { "key": "cdp_automation", "label": "CDP automation detected", "action": "Immediate ban (if enabled)" },
             { "key": "maze_crawler_threshold", "label": "Maze crawler threshold reached", "action": "Immediate ban (if enabled)" },
+            { "key": "tls_fingerprint_block", "label": "TLS fingerprint on block list", "action": "Block page" },
             { "key": "already_banned", "label": "Existing active ban", "action": "Block page" }
         ]
     })
@@ -2554,6 +3287,18 @@ fn config_export_env_entries(cfg: &crate::config::Config) -> Vec<(String, String
                 .as_str()
                 .to_string(),
         ),
+        (
+            "SHUMA_OTEL_TRACES_SAMPLE_RATIO".to_string(),
+            crate::config::otel_traces_sample_ratio().to_string(),
+        ),
+        (
+            "SHUMA_OTEL_METRICS_EXPORT_INTERVAL_SECONDS".to_string(),
+            crate::config::otel_metrics_export_interval_seconds().to_string(),
+        ),
+        (
+            "SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS".to_string(),
+            crate::config::alert_evaluation_interval_seconds().to_string(),
+        ),
         (
             "SHUMA_TEST_MODE".to_string(),
             bool_env(cfg.test_mode).to_string(),
@@ -2613,6 +3358,30 @@ fn config_export_env_entries(cfg: &crate::config::Config) -> Vec<(String, String
             "SHUMA_POW_TTL_SECONDS".to_string(),
             cfg.pow_ttl_seconds.to_string(),
         ),
+        (
+            "SHUMA_POW_ALGORITHM".to_string(),
+            cfg.pow_algorithm.as_str().to_string(),
+        ),
+        (
+            "SHUMA_POW_SCRYPT_MEMORY_KIB".to_string(),
+            cfg.pow_scrypt_memory_kib.to_string(),
+        ),
+        (
+            "SHUMA_POW_SCRYPT_DIFFICULTY".to_string(),
+            cfg.pow_scrypt_difficulty.to_string(),
+        ),
+        (
+            "SHUMA_POW_ADAPTIVE_ENABLED".to_string(),
+            bool_env(cfg.pow_adaptive_enabled).to_string(),
+        ),
+        (
+            "SHUMA_POW_ADAPTIVE_MAX_EXTRA_BITS".to_string(),
+            cfg.pow_adaptive_max_extra_bits.to_string(),
+        ),
+        (
+            "SHUMA_POW_ADAPTIVE_LOAD_THRESHOLD_PER_MINUTE".to_string(),
+            cfg.pow_adaptive_load_threshold_per_minute.to_string(),
+        ),
         (
             "SHUMA_CHALLENGE_PUZZLE_ENABLED".to_string(),
             bool_env(cfg.challenge_puzzle_enabled).to_string(),
@@ -2657,6 +3426,10 @@ fn config_export_env_entries(cfg: &crate::config::Config) -> Vec<(String, String
             "SHUMA_NOT_A_BOT_ATTEMPT_WINDOW_SECONDS".to_string(),
             cfg.not_a_bot_attempt_window_seconds.to_string(),
         ),
+        (
+            "SHUMA_LOCALE_OVERRIDES".to_string(),
+            json_env(&cfg.locale_overrides),
+        ),
         (
             "SHUMA_BOTNESS_MAZE_THRESHOLD".to_string(),
             cfg.botness_maze_threshold.to_string(),
@@ -2681,6 +3454,34 @@ fn config_export_env_entries(cfg: &crate::config::Config) -> Vec<(String, String
             "SHUMA_BOTNESS_WEIGHT_MAZE_BEHAVIOR".to_string(),
             cfg.botness_weights.maze_behavior.to_string(),
         ),
+        (
+            "SHUMA_BOTNESS_WEIGHT_BEHAVIOR_TIMING_REGULAR".to_string(),
+            cfg.botness_weights.behavior_timing_regular.to_string(),
+        ),
+        (
+            "SHUMA_BOTNESS_WEIGHT_BEHAVIOR_ASSET_RATIO".to_string(),
+            cfg.botness_weights.behavior_asset_ratio.to_string(),
+        ),
+        (
+            "SHUMA_BOTNESS_WEIGHT_BEHAVIOR_REFERRER_CHAIN".to_string(),
+            cfg.botness_weights.behavior_referrer_chain.to_string(),
+        ),
+        (
+            "SHUMA_BOTNESS_WEIGHT_BEHAVIOR_BREADTH_FIRST".to_string(),
+            cfg.botness_weights.behavior_breadth_first.to_string(),
+        ),
+        (
+            "SHUMA_BOTNESS_WEIGHT_BEHAVIOR_SITEMAP_ORDER".to_string(),
+            cfg.botness_weights.behavior_sitemap_order.to_string(),
+        ),
+        (
+            "SHUMA_BOTNESS_WEIGHT_PRIVACY_PASS_ATTESTED".to_string(),
+            cfg.botness_weights.privacy_pass_attested.to_string(),
+        ),
+        (
+            "SHUMA_BOTNESS_WEIGHT_ROBOTS_VIOLATION".to_string(),
+            cfg.botness_weights.robots_violation.to_string(),
+        ),
         (
             "SHUMA_BAN_DURATION".to_string(),
             cfg.ban_duration.to_string(),
@@ -2711,6 +3512,30 @@ fn config_export_env_entries(cfg: &crate::config::Config) -> Vec<(String, String
             bool_env(cfg.honeypot_enabled).to_string(),
         ),
         ("SHUMA_HONEYPOTS".to_string(), json_env(&cfg.honeypots)),
+        (
+            "SHUMA_HONEYPOT_ROTATION_ENABLED".to_string(),
+            bool_env(cfg.honeypot_rotation_enabled).to_string(),
+        ),
+        (
+            "SHUMA_HONEYPOT_ROTATION_INTERVAL_SECONDS".to_string(),
+            cfg.honeypot_rotation_interval_seconds.to_string(),
+        ),
+        (
+            "SHUMA_HONEYPOT_ROTATION_GRACE_SECONDS".to_string(),
+            cfg.honeypot_rotation_grace_seconds.to_string(),
+        ),
+        (
+            "SHUMA_FORM_HONEYPOT_ENABLED".to_string(),
+            bool_env(cfg.form_honeypot_enabled).to_string(),
+        ),
+        (
+            "SHUMA_FORM_HONEYPOT_MIN_SUBMIT_SECONDS".to_string(),
+            cfg.form_honeypot_min_submit_seconds.to_string(),
+        ),
+        (
+            "SHUMA_FORM_HONEYPOT_AUTO_BAN".to_string(),
+            bool_env(cfg.form_honeypot_auto_ban).to_string(),
+        ),
         (
             "SHUMA_BROWSER_BLOCK".to_string(),
             json_env(&cfg.browser_block),
@@ -2924,6 +3749,42 @@ fn config_export_env_entries(cfg: &crate::config::Config) -> Vec<(String, String
             "SHUMA_ROBOTS_CRAWL_DELAY".to_string(),
             cfg.robots_crawl_delay.to_string(),
         ),
+        (
+            "SHUMA_AI_CRAWLER_ACTION".to_string(),
+            cfg.ai_crawler_action.as_str().to_string(),
+        ),
+        (
+            "SHUMA_CRAWLER_CATALOG".to_string(),
+            json_env(&cfg.crawler_catalog),
+        ),
+        (
+            "SHUMA_CRAWL_PAYMENT_PRICE_MICROS".to_string(),
+            cfg.crawl_payment_price_micros.to_string(),
+        ),
+        (
+            "SHUMA_CRAWL_PAYMENT_CURRENCY".to_string(),
+            cfg.crawl_payment_currency.clone(),
+        ),
+        (
+            "SHUMA_CRAWL_PAYMENT_ISSUERS".to_string(),
+            json_env(&cfg.crawl_payment_issuers),
+        ),
+        (
+            "SHUMA_LLMS_TXT_TITLE".to_string(),
+            cfg.llms_txt_title.clone(),
+        ),
+        (
+            "SHUMA_LLMS_TXT_SUMMARY".to_string(),
+            cfg.llms_txt_summary.clone(),
+        ),
+        (
+            "SHUMA_LLMS_TXT_SECTIONS".to_string(),
+            json_env(&cfg.llms_txt_sections),
+        ),
+        (
+            "SHUMA_TDM_POLICY_URL".to_string(),
+            cfg.tdm_policy_url.clone(),
+        ),
         (
             "SHUMA_CDP_DETECTION_ENABLED".to_string(),
             bool_env(cfg.cdp_detection_enabled).to_string(),
@@ -2936,6 +3797,38 @@ fn config_export_env_entries(cfg: &crate::config::Config) -> Vec<(String, String
             "SHUMA_CDP_DETECTION_THRESHOLD".to_string(),
             cfg.cdp_detection_threshold.to_string(),
         ),
+        (
+            "SHUMA_FINGERPRINT_TLS_CUSTOM_RULES".to_string(),
+            json_env(&cfg.fingerprint_tls_custom_rules),
+        ),
+        (
+            "SHUMA_FINGERPRINT_TLS_MANAGED_POLICIES".to_string(),
+            json_env(&cfg.fingerprint_tls_managed_policies),
+        ),
+        (
+            "SHUMA_BEHAVIOR_SIGNAL_ENABLED".to_string(),
+            bool_env(cfg.behavior_signal_enabled).to_string(),
+        ),
+        (
+            "SHUMA_BEHAVIOR_FAMILY_CAP".to_string(),
+            cfg.behavior_family_cap.to_string(),
+        ),
+        (
+            "SHUMA_PRIVACY_PASS_ENABLED".to_string(),
+            bool_env(cfg.privacy_pass_enabled).to_string(),
+        ),
+        (
+            "SHUMA_PRIVACY_PASS_ISSUERS".to_string(),
+            json_env(&cfg.privacy_pass_issuers),
+        ),
+        (
+            "SHUMA_WEB_BOT_AUTH_ENABLED".to_string(),
+            bool_env(cfg.web_bot_auth_enabled).to_string(),
+        ),
+        (
+            "SHUMA_WEB_BOT_AUTH_AGENTS".to_string(),
+            json_env(&cfg.web_bot_auth_agents),
+        ),
     ]
 }
 
@@ -3143,30 +4036,228 @@ fn parse_ip_range_policy_action_json(
     })
 }
 
-fn validate_ip_range_action_params(
+fn validate_ip_range_action_params(
+    field: &str,
+    action: crate::config::IpRangePolicyAction,
+    redirect_url: Option<&str>,
+    custom_message: Option<&str>,
+) -> Result<(), String> {
+    if action == crate::config::IpRangePolicyAction::Redirect308 && redirect_url.is_none() {
+        return Err(format!("{} action redirect_308 requires redirect_url", field));
+    }
+    if action == crate::config::IpRangePolicyAction::CustomMessage && custom_message.is_none() {
+        return Err(format!("{} action custom_message requires custom_message", field));
+    }
+    Ok(())
+}
+
+fn parse_ip_range_custom_rules_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::IpRangePolicyRule>, String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array of objects", field))?;
+    if items.len() > IP_RANGE_MAX_RULES {
+        return Err(format!("{} exceeds max rules {}", field, IP_RANGE_MAX_RULES));
+    }
+
+    let mut parsed = Vec::with_capacity(items.len());
+    let mut seen_ids = HashSet::new();
+    for (index, item) in items.iter().enumerate() {
+        let obj = item
+            .as_object()
+            .ok_or_else(|| format!("{}[{}] must be an object", field, index))?;
+        let enabled = obj.get("enabled").and_then(|value| value.as_bool()).unwrap_or(true);
+        let id = obj
+            .get("id")
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string)
+            .unwrap_or_else(|| format!("custom_rule_{}", index + 1));
+        if !id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+        {
+            return Err(format!(
+                "{}[{}].id must contain only [a-zA-Z0-9_-]",
+                field, index
+            ));
+        }
+        if !seen_ids.insert(id.clone()) {
+            return Err(format!("{} contains duplicate id '{}'", field, id));
+        }
+        let cidrs = parse_cidr_list_json(
+            format!("{}[{}].cidrs", field, index).as_str(),
+            obj.get("cidrs")
+                .ok_or_else(|| format!("{}[{}].cidrs is required", field, index))?,
+            IP_RANGE_MAX_CIDRS_PER_RULE,
+        )?;
+        if enabled && cidrs.is_empty() {
+            return Err(format!(
+                "{}[{}].cidrs must not be empty when enabled=true",
+                field, index
+            ));
+        }
+        let action = parse_ip_range_policy_action_json(
+            format!("{}[{}]", field, index).as_str(),
+            obj.get("action"),
+        )?;
+        let redirect_url = sanitize_redirect_url(
+            format!("{}[{}]", field, index).as_str(),
+            obj.get("redirect_url"),
+        )?;
+        let custom_message = sanitize_custom_message(
+            format!("{}[{}]", field, index).as_str(),
+            obj.get("custom_message"),
+        )?;
+        validate_ip_range_action_params(
+            format!("{}[{}]", field, index).as_str(),
+            action,
+            redirect_url.as_deref(),
+            custom_message.as_deref(),
+        )?;
+
+        parsed.push(crate::config::IpRangePolicyRule {
+            id,
+            enabled,
+            cidrs,
+            action,
+            redirect_url,
+            custom_message,
+        });
+    }
+    Ok(parsed)
+}
+
+fn parse_ip_range_managed_policies_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::IpRangeManagedPolicy>, String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array of objects", field))?;
+    if items.len() > IP_RANGE_MAX_RULES {
+        return Err(format!("{} exceeds max rules {}", field, IP_RANGE_MAX_RULES));
+    }
+
+    let mut parsed = Vec::with_capacity(items.len());
+    let mut seen_set_ids = HashSet::new();
+    for (index, item) in items.iter().enumerate() {
+        let obj = item
+            .as_object()
+            .ok_or_else(|| format!("{}[{}] must be an object", field, index))?;
+        let set_id = obj
+            .get("set_id")
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("{}[{}].set_id is required", field, index))?
+            .to_ascii_lowercase();
+        if set_id == "deepseek" || set_id == "deepseek_bot" || set_id == "deepseek_crawler" {
+            return Err(format!(
+                "{}[{}].set_id '{}' is unavailable: official source unavailable",
+                field, index, set_id
+            ));
+        }
+        if !crate::signals::ip_range_policy::has_managed_set(set_id.as_str()) {
+            return Err(format!(
+                "{}[{}].set_id '{}' is unknown",
+                field, index, set_id
+            ));
+        }
+        if !seen_set_ids.insert(set_id.clone()) {
+            return Err(format!(
+                "{} contains duplicate set_id '{}'",
+                field, set_id
+            ));
+        }
+        let enabled = obj.get("enabled").and_then(|value| value.as_bool()).unwrap_or(true);
+        let action = parse_ip_range_policy_action_json(
+            format!("{}[{}]", field, index).as_str(),
+            obj.get("action"),
+        )?;
+        let redirect_url = sanitize_redirect_url(
+            format!("{}[{}]", field, index).as_str(),
+            obj.get("redirect_url"),
+        )?;
+        let custom_message = sanitize_custom_message(
+            format!("{}[{}]", field, index).as_str(),
+            obj.get("custom_message"),
+        )?;
+        validate_ip_range_action_params(
+            format!("{}[{}]", field, index).as_str(),
+            action,
+            redirect_url.as_deref(),
+            custom_message.as_deref(),
+        )?;
+
+        parsed.push(crate::config::IpRangeManagedPolicy {
+            set_id,
+            enabled,
+            action,
+            redirect_url,
+            custom_message,
+        });
+    }
+    Ok(parsed)
+}
+
+fn parse_tls_fingerprint_action_json(
     field: &str,
-    action: crate::config::IpRangePolicyAction,
-    redirect_url: Option<&str>,
-    custom_message: Option<&str>,
-) -> Result<(), String> {
-    if action == crate::config::IpRangePolicyAction::Redirect308 && redirect_url.is_none() {
-        return Err(format!("{} action redirect_308 requires redirect_url", field));
+    value: Option<&serde_json::Value>,
+) -> Result<crate::config::TlsFingerprintAction, String> {
+    let Some(raw_value) = value else {
+        return Ok(crate::config::TlsFingerprintAction::Score);
+    };
+    match raw_value.as_str() {
+        Some("allow") => Ok(crate::config::TlsFingerprintAction::Allow),
+        Some("score") => Ok(crate::config::TlsFingerprintAction::Score),
+        Some("block") => Ok(crate::config::TlsFingerprintAction::Block),
+        _ => Err(format!("{} action must be one of: allow, score, block", field)),
     }
-    if action == crate::config::IpRangePolicyAction::CustomMessage && custom_message.is_none() {
-        return Err(format!("{} action custom_message requires custom_message", field));
+}
+
+fn parse_tls_fingerprint_list_json(field: &str, value: Option<&serde_json::Value>) -> Result<Vec<String>, String> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array of strings", field))?;
+    if items.len() > TLS_FINGERPRINT_MAX_VALUES_PER_RULE {
+        return Err(format!(
+            "{} exceeds max length {}",
+            field, TLS_FINGERPRINT_MAX_VALUES_PER_RULE
+        ));
     }
-    Ok(())
+    let mut parsed = Vec::with_capacity(items.len());
+    let mut seen = HashSet::new();
+    for item in items {
+        let raw = item
+            .as_str()
+            .ok_or_else(|| format!("{} must contain only strings", field))?;
+        if !crate::signals::tls_reputation::is_valid_fingerprint(raw) {
+            return Err(format!("{} contains invalid fingerprint '{}'", field, raw));
+        }
+        let normalized = raw.trim().to_ascii_lowercase();
+        if seen.insert(normalized.clone()) {
+            parsed.push(normalized);
+        }
+    }
+    Ok(parsed)
 }
 
-fn parse_ip_range_custom_rules_json(
+fn parse_tls_fingerprint_custom_rules_json(
     field: &str,
     value: &serde_json::Value,
-) -> Result<Vec<crate::config::IpRangePolicyRule>, String> {
+) -> Result<Vec<crate::config::TlsFingerprintRule>, String> {
     let items = value
         .as_array()
         .ok_or_else(|| format!("{} must be an array of objects", field))?;
-    if items.len() > IP_RANGE_MAX_RULES {
-        return Err(format!("{} exceeds max rules {}", field, IP_RANGE_MAX_RULES));
+    if items.len() > TLS_FINGERPRINT_MAX_RULES {
+        return Err(format!("{} exceeds max rules {}", field, TLS_FINGERPRINT_MAX_RULES));
     }
 
     let mut parsed = Vec::with_capacity(items.len());
@@ -3182,7 +4273,7 @@ fn parse_ip_range_custom_rules_json(
             .map(str::trim)
             .filter(|value| !value.is_empty())
             .map(ToString::to_string)
-            .unwrap_or_else(|| format!("custom_rule_{}", index + 1));
+            .unwrap_or_else(|| format!("tls_rule_{}", index + 1));
         if !id
             .chars()
             .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
@@ -3195,58 +4286,95 @@ fn parse_ip_range_custom_rules_json(
         if !seen_ids.insert(id.clone()) {
             return Err(format!("{} contains duplicate id '{}'", field, id));
         }
-        let cidrs = parse_cidr_list_json(
-            format!("{}[{}].cidrs", field, index).as_str(),
-            obj.get("cidrs")
-                .ok_or_else(|| format!("{}[{}].cidrs is required", field, index))?,
-            IP_RANGE_MAX_CIDRS_PER_RULE,
+        let ja3 = parse_tls_fingerprint_list_json(
+            format!("{}[{}].ja3", field, index).as_str(),
+            obj.get("ja3"),
         )?;
-        if enabled && cidrs.is_empty() {
+        let ja4 = parse_tls_fingerprint_list_json(
+            format!("{}[{}].ja4", field, index).as_str(),
+            obj.get("ja4"),
+        )?;
+        if enabled && ja3.is_empty() && ja4.is_empty() {
             return Err(format!(
-                "{}[{}].cidrs must not be empty when enabled=true",
+                "{}[{}] must list at least one ja3 or ja4 value when enabled=true",
                 field, index
             ));
         }
-        let action = parse_ip_range_policy_action_json(
+        let action = parse_tls_fingerprint_action_json(
             format!("{}[{}]", field, index).as_str(),
             obj.get("action"),
         )?;
-        let redirect_url = sanitize_redirect_url(
-            format!("{}[{}]", field, index).as_str(),
-            obj.get("redirect_url"),
-        )?;
-        let custom_message = sanitize_custom_message(
-            format!("{}[{}]", field, index).as_str(),
-            obj.get("custom_message"),
-        )?;
-        validate_ip_range_action_params(
-            format!("{}[{}]", field, index).as_str(),
-            action,
-            redirect_url.as_deref(),
-            custom_message.as_deref(),
-        )?;
 
-        parsed.push(crate::config::IpRangePolicyRule {
+        parsed.push(crate::config::TlsFingerprintRule {
             id,
             enabled,
-            cidrs,
+            ja3,
+            ja4,
             action,
-            redirect_url,
-            custom_message,
         });
     }
     Ok(parsed)
 }
 
-fn parse_ip_range_managed_policies_json(
+fn parse_locale_overrides_json(
     field: &str,
     value: &serde_json::Value,
-) -> Result<Vec<crate::config::IpRangeManagedPolicy>, String> {
+) -> Result<BTreeMap<String, BTreeMap<String, String>>, String> {
+    let locales = value
+        .as_object()
+        .ok_or_else(|| format!("{} must be an object keyed by locale", field))?;
+    let mut parsed = BTreeMap::new();
+    for (locale, entries) in locales {
+        if !crate::i18n::is_supported_locale(locale.as_str()) {
+            return Err(format!(
+                "{} locale '{}' is not supported (expected one of {})",
+                field,
+                locale,
+                crate::i18n::SUPPORTED_LOCALES.join(", ")
+            ));
+        }
+        let entries = entries
+            .as_object()
+            .ok_or_else(|| format!("{}.{} must be an object of message keys", field, locale))?;
+        let mut messages = BTreeMap::new();
+        for (key, text) in entries {
+            if !crate::i18n::is_known_key(key.as_str()) {
+                return Err(format!("{}.{} has unknown message key '{}'", field, locale, key));
+            }
+            let text = text
+                .as_str()
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .ok_or_else(|| {
+                    format!("{}.{}.{} must be a non-empty string", field, locale, key)
+                })?;
+            if text.chars().count() > crate::i18n::LOCALE_OVERRIDE_MAX_CHARS {
+                return Err(format!(
+                    "{}.{}.{} exceeds {} characters",
+                    field,
+                    locale,
+                    key,
+                    crate::i18n::LOCALE_OVERRIDE_MAX_CHARS
+                ));
+            }
+            messages.insert(key.clone(), text.to_string());
+        }
+        if !messages.is_empty() {
+            parsed.insert(locale.clone(), messages);
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_tls_fingerprint_managed_policies_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::TlsFingerprintManagedPolicy>, String> {
     let items = value
         .as_array()
         .ok_or_else(|| format!("{} must be an array of objects", field))?;
-    if items.len() > IP_RANGE_MAX_RULES {
-        return Err(format!("{} exceeds max rules {}", field, IP_RANGE_MAX_RULES));
+    if items.len() > TLS_FINGERPRINT_MAX_RULES {
+        return Err(format!("{} exceeds max rules {}", field, TLS_FINGERPRINT_MAX_RULES));
     }
 
     let mut parsed = Vec::with_capacity(items.len());
@@ -3262,13 +4390,7 @@ fn parse_ip_range_managed_policies_json(
             .filter(|value| !value.is_empty())
             .ok_or_else(|| format!("{}[{}].set_id is required", field, index))?
             .to_ascii_lowercase();
-        if set_id == "deepseek" || set_id == "deepseek_bot" || set_id == "deepseek_crawler" {
-            return Err(format!(
-                "{}[{}].set_id '{}' is unavailable: official source unavailable",
-                field, index, set_id
-            ));
-        }
-        if !crate::signals::ip_range_policy::has_managed_set(set_id.as_str()) {
+        if !crate::signals::tls_reputation::has_managed_set(set_id.as_str()) {
             return Err(format!(
                 "{}[{}].set_id '{}' is unknown",
                 field, index, set_id
@@ -3281,31 +4403,287 @@ fn parse_ip_range_managed_policies_json(
             ));
         }
         let enabled = obj.get("enabled").and_then(|value| value.as_bool()).unwrap_or(true);
-        let action = parse_ip_range_policy_action_json(
+        let action = parse_tls_fingerprint_action_json(
             format!("{}[{}]", field, index).as_str(),
             obj.get("action"),
         )?;
-        let redirect_url = sanitize_redirect_url(
-            format!("{}[{}]", field, index).as_str(),
-            obj.get("redirect_url"),
-        )?;
-        let custom_message = sanitize_custom_message(
-            format!("{}[{}]", field, index).as_str(),
-            obj.get("custom_message"),
-        )?;
-        validate_ip_range_action_params(
-            format!("{}[{}]", field, index).as_str(),
-            action,
-            redirect_url.as_deref(),
-            custom_message.as_deref(),
-        )?;
 
-        parsed.push(crate::config::IpRangeManagedPolicy {
+        parsed.push(crate::config::TlsFingerprintManagedPolicy {
             set_id,
             enabled,
             action,
-            redirect_url,
-            custom_message,
+        });
+    }
+    Ok(parsed)
+}
+
+fn parse_privacy_pass_issuers_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::PrivacyPassIssuer>, String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array of objects", field))?;
+    if items.len() > crate::signals::privacy_pass::MAX_ISSUERS {
+        return Err(format!(
+            "{} exceeds max issuers {}",
+            field,
+            crate::signals::privacy_pass::MAX_ISSUERS
+        ));
+    }
+
+    let mut parsed = Vec::with_capacity(items.len());
+    let mut seen_names = HashSet::new();
+    for (index, item) in items.iter().enumerate() {
+        let obj = item
+            .as_object()
+            .ok_or_else(|| format!("{}[{}] must be an object", field, index))?;
+        let name = obj
+            .get("name")
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("{}[{}].name is required", field, index))?
+            .to_ascii_lowercase();
+        if !crate::signals::privacy_pass::is_valid_issuer_name(name.as_str()) {
+            return Err(format!(
+                "{}[{}].name '{}' must be an issuer host name",
+                field, index, name
+            ));
+        }
+        if !seen_names.insert(name.clone()) {
+            return Err(format!("{} contains duplicate name '{}'", field, name));
+        }
+        let token_key = obj
+            .get("token_key")
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("{}[{}].token_key is required", field, index))?;
+        if !crate::signals::privacy_pass::is_valid_token_key(token_key) {
+            return Err(format!(
+                "{}[{}].token_key must be a base64url SPKI for a 2048-bit RSA key",
+                field, index
+            ));
+        }
+
+        parsed.push(crate::config::PrivacyPassIssuer {
+            name,
+            token_key: token_key.to_string(),
+        });
+    }
+    Ok(parsed)
+}
+
+fn parse_crawler_catalog_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::CrawlerCatalogEntry>, String> {
+    let mut catalog: Vec<crate::config::CrawlerCatalogEntry> =
+        serde_json::from_value(value.clone()).map_err(|err| {
+            format!(
+                "{} must be an array of {{ name, ua_patterns, category, verification, ip_range_set, action, crawl_delay }} objects ({})",
+                field, err
+            )
+        })?;
+    for entry in catalog.iter_mut() {
+        entry.name = entry.name.trim().to_string();
+        entry.ip_range_set = entry.ip_range_set.trim().to_ascii_lowercase();
+        entry.ua_patterns = entry
+            .ua_patterns
+            .iter()
+            .map(|pattern| pattern.trim().to_string())
+            .collect();
+    }
+    crate::crawler_policy::catalog::validate_entries(&catalog)?;
+    Ok(catalog)
+}
+
+fn parse_llms_txt_sections_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::LlmsTxtSection>, String> {
+    let sections: Vec<crate::config::LlmsTxtSection> = serde_json::from_value(value.clone())
+        .map_err(|_| {
+            format!(
+                "{} must be an array of {{ title, links: [{{ title, url, notes }}] }} objects",
+                field
+            )
+        })?;
+    crate::crawler_policy::policy_files::validate_llms_txt_sections(&sections)?;
+    Ok(sections)
+}
+
+fn parse_crawl_payment_issuers_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::CrawlPaymentIssuer>, String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array of objects", field))?;
+    if items.len() > crate::crawler_policy::payment::MAX_ISSUERS {
+        return Err(format!(
+            "{} exceeds max issuers {}",
+            field,
+            crate::crawler_policy::payment::MAX_ISSUERS
+        ));
+    }
+
+    let mut parsed = Vec::with_capacity(items.len());
+    let mut seen_names = HashSet::new();
+    for (index, item) in items.iter().enumerate() {
+        let obj = item
+            .as_object()
+            .ok_or_else(|| format!("{}[{}] must be an object", field, index))?;
+        let name = obj
+            .get("name")
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("{}[{}].name is required", field, index))?
+            .to_ascii_lowercase();
+        if !seen_names.insert(name.clone()) {
+            return Err(format!("{} contains duplicate name '{}'", field, name));
+        }
+        let key = obj
+            .get("key")
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("{}[{}].key is required", field, index))?;
+        if !crate::signals::web_bot_auth::is_valid_public_key(key) {
+            return Err(format!(
+                "{}[{}].key must be a base64url Ed25519 public key",
+                field, index
+            ));
+        }
+
+        parsed.push(crate::config::CrawlPaymentIssuer {
+            name,
+            key: key.to_string(),
+        });
+    }
+    Ok(parsed)
+}
+
+fn parse_web_bot_auth_agents_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<Vec<crate::config::WebBotAuthAgent>, String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{} must be an array of objects", field))?;
+    if items.len() > crate::signals::web_bot_auth::MAX_AGENTS {
+        return Err(format!(
+            "{} exceeds max agents {}",
+            field,
+            crate::signals::web_bot_auth::MAX_AGENTS
+        ));
+    }
+
+    let mut parsed = Vec::with_capacity(items.len());
+    let mut seen_names = HashSet::new();
+    let mut seen_directories = HashSet::new();
+    for (index, item) in items.iter().enumerate() {
+        let obj = item
+            .as_object()
+            .ok_or_else(|| format!("{}[{}] must be an object", field, index))?;
+        let name = obj
+            .get("name")
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("{}[{}].name is required", field, index))?
+            .to_ascii_lowercase();
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(format!(
+                "{}[{}].name may only contain letters, digits, '-', '_' and '.'",
+                field, index
+            ));
+        }
+        if !seen_names.insert(name.clone()) {
+            return Err(format!("{} contains duplicate name '{}'", field, name));
+        }
+        let directory = obj
+            .get("directory")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| format!("{}[{}].directory is required", field, index))?;
+        if !crate::signals::web_bot_auth::is_valid_directory(directory) {
+            return Err(format!(
+                "{}[{}].directory must be an https origin such as https://agent.example",
+                field, index
+            ));
+        }
+        let directory = crate::signals::web_bot_auth::normalize_directory(directory);
+        if !seen_directories.insert(directory.clone()) {
+            return Err(format!(
+                "{} contains duplicate directory '{}'",
+                field, directory
+            ));
+        }
+        let keys = match obj.get("keys") {
+            Some(value) => {
+                parse_string_list_json(format!("{}[{}].keys", field, index).as_str(), value)?
+            }
+            None => Vec::new(),
+        };
+        if keys.len() > crate::signals::web_bot_auth::MAX_KEYS_PER_AGENT {
+            return Err(format!(
+                "{}[{}].keys exceeds max keys {}",
+                field,
+                index,
+                crate::signals::web_bot_auth::MAX_KEYS_PER_AGENT
+            ));
+        }
+        if let Some(key) = keys
+            .iter()
+            .find(|key| !crate::signals::web_bot_auth::is_valid_public_key(key))
+        {
+            return Err(format!(
+                "{}[{}].keys entry '{}' must be a base64url Ed25519 public key",
+                field, index, key
+            ));
+        }
+        let action = match obj.get("action") {
+            Some(value) => value
+                .as_str()
+                .and_then(crate::config::parse_verified_agent_action)
+                .ok_or_else(|| {
+                    format!(
+                        "{}[{}].action must be one of allow, rate_limit, payment_required",
+                        field, index
+                    )
+                })?,
+            None => crate::config::VerifiedAgentAction::Allow,
+        };
+        let rate_limit = match obj.get("rate_limit") {
+            Some(value) => value
+                .as_u64()
+                .filter(|value| *value <= u32::MAX as u64)
+                .ok_or_else(|| {
+                    format!(
+                        "{}[{}].rate_limit must be a non-negative integer",
+                        field, index
+                    )
+                })? as u32,
+            None => 0,
+        };
+        if action == crate::config::VerifiedAgentAction::RateLimit && rate_limit == 0 {
+            return Err(format!(
+                "{}[{}].rate_limit must be greater than 0 when action is rate_limit",
+                field, index
+            ));
+        }
+
+        parsed.push(crate::config::WebBotAuthAgent {
+            name,
+            directory,
+            keys,
+            action,
+            rate_limit,
         });
     }
     Ok(parsed)
@@ -3374,15 +4752,35 @@ fn parse_edge_integration_mode_json(
         .ok_or_else(|| format!("{} must be one of: off, advisory, authoritative", field))
 }
 
-fn parse_cdp_probe_family_json(
+fn parse_cdp_probe_family_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<crate::config::CdpProbeFamily, String> {
+    let raw = value
+        .as_str()
+        .ok_or_else(|| format!("{} must be one of: v1, v2, split", field))?;
+    crate::config::parse_cdp_probe_family(raw)
+        .ok_or_else(|| format!("{} must be one of: v1, v2, split", field))
+}
+
+fn parse_ai_crawler_action_json(
+    field: &str,
+    value: &serde_json::Value,
+) -> Result<crate::config::AiCrawlerAction, String> {
+    let message = || format!("{} must be one of: allow, block, maze, payment_required", field);
+    let raw = value.as_str().ok_or_else(message)?;
+    crate::config::parse_ai_crawler_action(raw).ok_or_else(message)
+}
+
+fn parse_pow_algorithm_json(
     field: &str,
     value: &serde_json::Value,
-) -> Result<crate::config::CdpProbeFamily, String> {
+) -> Result<crate::config::PowAlgorithm, String> {
     let raw = value
         .as_str()
-        .ok_or_else(|| format!("{} must be one of: v1, v2, split", field))?;
-    crate::config::parse_cdp_probe_family(raw)
-        .ok_or_else(|| format!("{} must be one of: v1, v2, split", field))
+        .ok_or_else(|| format!("{} must be one of: sha256, scrypt", field))?;
+    crate::config::parse_pow_algorithm(raw)
+        .ok_or_else(|| format!("{} must be one of: sha256, scrypt", field))
 }
 
 fn parse_maze_rollout_phase_json(
@@ -3481,6 +4879,19 @@ fn admin_config_payload(
         )
             .unwrap_or_else(|_| json!([])),
     );
+    obj.insert(
+        "fingerprint_tls_catalog_version".to_string(),
+        serde_json::Value::String(crate::signals::tls_reputation::managed_catalog_version()),
+    );
+    obj.insert(
+        "fingerprint_tls_catalog_generated_at".to_string(),
+        serde_json::Value::String(crate::signals::tls_reputation::managed_catalog_generated_at()),
+    );
+    obj.insert(
+        "fingerprint_tls_managed_sets".to_string(),
+        serde_json::to_value(crate::signals::tls_reputation::managed_set_metadata())
+            .unwrap_or_else(|_| json!([])),
+    );
     obj.insert(
         "defence_modes_effective".to_string(),
         serde_json::to_value(cfg.defence_modes_effective()).unwrap_or_else(|_| json!({})),
@@ -3640,6 +5051,77 @@ fn handle_admin_config(
                 Err(msg) => return Response::new(400, msg),
             }
         }
+        if let Some(enabled) = json
+            .get("honeypot_rotation_enabled")
+            .and_then(|v| v.as_bool())
+        {
+            cfg.honeypot_rotation_enabled = enabled;
+            changed = true;
+        }
+        if let Some(value) = json.get("honeypot_rotation_interval_seconds") {
+            match value.as_u64() {
+                Some(seconds)
+                    if (HONEYPOT_ROTATION_INTERVAL_SECONDS_MIN
+                        ..=HONEYPOT_ROTATION_INTERVAL_SECONDS_MAX)
+                        .contains(&seconds) =>
+                {
+                    cfg.honeypot_rotation_interval_seconds = seconds;
+                    changed = true;
+                }
+                _ => {
+                    return Response::new(
+                        400,
+                        format!(
+                            "honeypot_rotation_interval_seconds out of range ({}-{})",
+                            HONEYPOT_ROTATION_INTERVAL_SECONDS_MIN,
+                            HONEYPOT_ROTATION_INTERVAL_SECONDS_MAX
+                        ),
+                    )
+                }
+            }
+        }
+        if let Some(value) = json.get("honeypot_rotation_grace_seconds") {
+            match value.as_u64() {
+                Some(seconds) if seconds <= HONEYPOT_ROTATION_GRACE_SECONDS_MAX => {
+                    cfg.honeypot_rotation_grace_seconds = seconds;
+                    changed = true;
+                }
+                _ => {
+                    return Response::new(
+                        400,
+                        format!(
+                            "honeypot_rotation_grace_seconds out of range (0-{})",
+                            HONEYPOT_ROTATION_GRACE_SECONDS_MAX
+                        ),
+                    )
+                }
+            }
+        }
+        if let Some(enabled) = json.get("form_honeypot_enabled").and_then(|v| v.as_bool()) {
+            cfg.form_honeypot_enabled = enabled;
+            changed = true;
+        }
+        if let Some(value) = json.get("form_honeypot_min_submit_seconds") {
+            match value.as_u64() {
+                Some(seconds) if seconds <= FORM_HONEYPOT_MIN_SUBMIT_SECONDS_MAX => {
+                    cfg.form_honeypot_min_submit_seconds = seconds;
+                    changed = true;
+                }
+                _ => {
+                    return Response::new(
+                        400,
+                        format!(
+                            "form_honeypot_min_submit_seconds out of range (0-{})",
+                            FORM_HONEYPOT_MIN_SUBMIT_SECONDS_MAX
+                        ),
+                    )
+                }
+            }
+        }
+        if let Some(auto_ban) = json.get("form_honeypot_auto_ban").and_then(|v| v.as_bool()) {
+            cfg.form_honeypot_auto_ban = auto_ban;
+            changed = true;
+        }
         if let Some(value) = json.get("browser_block") {
             match parse_browser_rules_json("browser_block", value) {
                 Ok(rules) => {
@@ -3716,6 +5198,36 @@ fn handle_admin_config(
                 Err(msg) => return Response::new(400, msg),
             }
         }
+        if let Some(value) = json.get("fingerprint_tls_custom_rules") {
+            match parse_tls_fingerprint_custom_rules_json("fingerprint_tls_custom_rules", value) {
+                Ok(rules) => {
+                    cfg.fingerprint_tls_custom_rules = rules;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
+        if let Some(value) = json.get("fingerprint_tls_managed_policies") {
+            match parse_tls_fingerprint_managed_policies_json(
+                "fingerprint_tls_managed_policies",
+                value,
+            ) {
+                Ok(policies) => {
+                    cfg.fingerprint_tls_managed_policies = policies;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
+        if let Some(value) = json.get("locale_overrides") {
+            match parse_locale_overrides_json("locale_overrides", value) {
+                Ok(overrides) => {
+                    cfg.locale_overrides = overrides;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
         if let Some(value) = json.get("ip_range_managed_max_staleness_hours") {
             let Some(hours) = value.as_u64() else {
                 return Response::new(
@@ -4003,6 +5515,120 @@ fn handle_admin_config(
             cfg.robots_crawl_delay = robots_crawl_delay as u32;
             changed = true;
         }
+        if let Some(value) = json.get("ai_crawler_action") {
+            cfg.ai_crawler_action = match parse_ai_crawler_action_json("ai_crawler_action", value) {
+                Ok(action) => action,
+                Err(msg) => return Response::new(400, msg),
+            };
+            changed = true;
+        }
+        if let Some(value) = json.get("crawl_payment_price_micros") {
+            match value.as_u64() {
+                Some(price) if (1..=CRAWL_PAYMENT_PRICE_MICROS_MAX).contains(&price) => {
+                    cfg.crawl_payment_price_micros = price;
+                    changed = true;
+                }
+                _ => {
+                    return Response::new(
+                        400,
+                        format!(
+                            "crawl_payment_price_micros out of range (1-{})",
+                            CRAWL_PAYMENT_PRICE_MICROS_MAX
+                        ),
+                    )
+                }
+            }
+        }
+        if let Some(value) = json.get("crawl_payment_currency") {
+            match value
+                .as_str()
+                .map(|raw| raw.trim().to_ascii_uppercase())
+                .filter(|raw| crate::crawler_policy::payment::is_valid_currency(raw))
+            {
+                Some(currency) => {
+                    cfg.crawl_payment_currency = currency;
+                    changed = true;
+                }
+                None => {
+                    return Response::new(
+                        400,
+                        "crawl_payment_currency must be a three-letter currency code",
+                    )
+                }
+            }
+        }
+        if let Some(value) = json.get("crawl_payment_issuers") {
+            match parse_crawl_payment_issuers_json("crawl_payment_issuers", value) {
+                Ok(issuers) => {
+                    cfg.crawl_payment_issuers = issuers;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
+        if let Some(value) = json.get("crawler_catalog") {
+            match parse_crawler_catalog_json("crawler_catalog", value) {
+                Ok(catalog) => {
+                    cfg.crawler_catalog = catalog;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
+        for (field, target) in [
+            ("llms_txt_title", &mut cfg.llms_txt_title),
+            ("llms_txt_summary", &mut cfg.llms_txt_summary),
+        ] {
+            if let Some(value) = json.get(field) {
+                match value
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|raw| crate::crawler_policy::policy_files::is_valid_llms_txt_text(raw))
+                {
+                    Some(text) => {
+                        *target = text.to_string();
+                        changed = true;
+                    }
+                    None => {
+                        return Response::new(
+                            400,
+                            format!(
+                                "{} must be a single line of at most {} characters",
+                                field,
+                                crate::crawler_policy::policy_files::MAX_LLMS_TXT_TEXT_CHARS
+                            ),
+                        )
+                    }
+                }
+            }
+        }
+        if let Some(value) = json.get("llms_txt_sections") {
+            match parse_llms_txt_sections_json("llms_txt_sections", value) {
+                Ok(sections) => {
+                    cfg.llms_txt_sections = sections;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
+        if let Some(value) = json.get("tdm_policy_url") {
+            match value.as_str().map(str::trim).filter(|raw| {
+                raw.is_empty()
+                    || (!raw.starts_with('/')
+                        && crate::crawler_policy::policy_files::is_valid_llms_txt_url(raw))
+            }) {
+                Some(url) => {
+                    cfg.tdm_policy_url = url.to_string();
+                    changed = true;
+                }
+                None => {
+                    return Response::new(
+                        400,
+                        "tdm_policy_url must be empty or an absolute http(s) URL",
+                    )
+                }
+            }
+        }
 
         // Update CDP detection settings if provided
         if let Some(cdp_detection_enabled) =
@@ -4127,10 +5753,50 @@ fn handle_admin_config(
             cfg.fingerprint_family_cap_behavior = value as u8;
             changed = true;
         }
+        if let Some(value) = json.get("behavior_signal_enabled").and_then(|v| v.as_bool()) {
+            cfg.behavior_signal_enabled = value;
+            changed = true;
+        }
+        if let Some(value) = json.get("behavior_family_cap").and_then(|v| v.as_u64()) {
+            if value > 10 {
+                return Response::new(400, "behavior_family_cap out of range (0-10)");
+            }
+            cfg.behavior_family_cap = value as u8;
+            changed = true;
+        }
+        if let Some(value) = json.get("privacy_pass_enabled").and_then(|v| v.as_bool()) {
+            cfg.privacy_pass_enabled = value;
+            changed = true;
+        }
+        if let Some(value) = json.get("privacy_pass_issuers") {
+            match parse_privacy_pass_issuers_json("privacy_pass_issuers", value) {
+                Ok(issuers) => {
+                    cfg.privacy_pass_issuers = issuers;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
+        if let Some(value) = json.get("web_bot_auth_enabled").and_then(|v| v.as_bool()) {
+            cfg.web_bot_auth_enabled = value;
+            changed = true;
+        }
+        if let Some(value) = json.get("web_bot_auth_agents") {
+            match parse_web_bot_auth_agents_json("web_bot_auth_agents", value) {
+                Ok(agents) => {
+                    cfg.web_bot_auth_agents = agents;
+                    changed = true;
+                }
+                Err(msg) => return Response::new(400, msg),
+            }
+        }
 
         let old_pow_enabled = cfg.pow_enabled;
         let old_pow_difficulty = cfg.pow_difficulty;
         let old_pow_ttl = cfg.pow_ttl_seconds;
+        let old_pow_algorithm = cfg.pow_algorithm;
+        let old_pow_scrypt_memory_kib = cfg.pow_scrypt_memory_kib;
+        let old_pow_scrypt_difficulty = cfg.pow_scrypt_difficulty;
         let mut pow_changed = false;
 
         // Update PoW settings if provided.
@@ -4159,6 +5825,62 @@ fn handle_admin_config(
             changed = true;
             pow_changed = true;
         }
+        if let Some(value) = json.get("pow_algorithm") {
+            cfg.pow_algorithm = match parse_pow_algorithm_json("pow_algorithm", value) {
+                Ok(algorithm) => algorithm,
+                Err(msg) => return Response::new(400, msg),
+            };
+            changed = true;
+            pow_changed = true;
+        }
+        if let Some(value) = json.get("pow_scrypt_memory_kib").and_then(|v| v.as_u64()) {
+            if value < POW_SCRYPT_MEMORY_KIB_MIN as u64
+                || value > POW_SCRYPT_MEMORY_KIB_MAX as u64
+                || !value.is_power_of_two()
+            {
+                return Response::new(
+                    400,
+                    "pow_scrypt_memory_kib must be a power of two in range (1024-16384)",
+                );
+            }
+            cfg.pow_scrypt_memory_kib = value as u32;
+            changed = true;
+            pow_changed = true;
+        }
+        if let Some(value) = json.get("pow_scrypt_difficulty").and_then(|v| v.as_u64()) {
+            if value < POW_SCRYPT_DIFFICULTY_MIN as u64
+                || value > POW_SCRYPT_DIFFICULTY_MAX as u64
+            {
+                return Response::new(400, "pow_scrypt_difficulty out of range (1-10)");
+            }
+            cfg.pow_scrypt_difficulty = value as u8;
+            changed = true;
+            pow_changed = true;
+        }
+        if let Some(value) = json.get("pow_adaptive_enabled").and_then(|v| v.as_bool()) {
+            cfg.pow_adaptive_enabled = value;
+            changed = true;
+        }
+        if let Some(value) = json.get("pow_adaptive_max_extra_bits").and_then(|v| v.as_u64()) {
+            if value > POW_ADAPTIVE_MAX_EXTRA_BITS_MAX as u64 {
+                return Response::new(400, "pow_adaptive_max_extra_bits out of range (0-8)");
+            }
+            cfg.pow_adaptive_max_extra_bits = value as u8;
+            changed = true;
+        }
+        if let Some(value) = json
+            .get("pow_adaptive_load_threshold_per_minute")
+            .and_then(|v| v.as_u64())
+        {
+            if value > POW_ADAPTIVE_LOAD_THRESHOLD_MAX as u64 {
+                return Response::new(
+                    400,
+                    "pow_adaptive_load_threshold_per_minute out of range (0-1000000)",
+                );
+            }
+            cfg.pow_adaptive_load_threshold_per_minute = value as u32;
+            changed = true;
+        }
 
         if pow_changed {
             log_event(
@@ -4169,13 +5891,19 @@ fn handle_admin_config(
                     ip: None,
                     reason: Some("pow_config_update".to_string()),
                     outcome: Some(format!(
-                        "enabled:{}->{} difficulty:{}->{} ttl:{}->{}",
+                        "enabled:{}->{} difficulty:{}->{} ttl:{}->{} algorithm:{}->{} scrypt_memory_kib:{}->{} scrypt_difficulty:{}->{}",
                         old_pow_enabled,
                         cfg.pow_enabled,
                         old_pow_difficulty,
                         cfg.pow_difficulty,
                         old_pow_ttl,
-                        cfg.pow_ttl_seconds
+                        cfg.pow_ttl_seconds,
+                        old_pow_algorithm.as_str(),
+                        cfg.pow_algorithm.as_str(),
+                        old_pow_scrypt_memory_kib,
+                        cfg.pow_scrypt_memory_kib,
+                        old_pow_scrypt_difficulty,
+                        cfg.pow_scrypt_difficulty
                     )),
                     admin: Some(crate::admin::auth::get_admin_id(req)),
                 },
@@ -4566,6 +6294,62 @@ fn handle_admin_config(
                 changed = true;
                 botness_changed = true;
             }
+            if let Some(behavior_timing_regular) = weights.get("behavior_timing_regular").and_then(|v| v.as_u64()) {
+                if behavior_timing_regular > 10 {
+                    return Response::new(400, "botness_weights.behavior_timing_regular out of range (0-10)");
+                }
+                cfg.botness_weights.behavior_timing_regular = behavior_timing_regular as u8;
+                changed = true;
+                botness_changed = true;
+            }
+            if let Some(behavior_asset_ratio) = weights.get("behavior_asset_ratio").and_then(|v| v.as_u64()) {
+                if behavior_asset_ratio > 10 {
+                    return Response::new(400, "botness_weights.behavior_asset_ratio out of range (0-10)");
+                }
+                cfg.botness_weights.behavior_asset_ratio = behavior_asset_ratio as u8;
+                changed = true;
+                botness_changed = true;
+            }
+            if let Some(behavior_referrer_chain) = weights.get("behavior_referrer_chain").and_then(|v| v.as_u64()) {
+                if behavior_referrer_chain > 10 {
+                    return Response::new(400, "botness_weights.behavior_referrer_chain out of range (0-10)");
+                }
+                cfg.botness_weights.behavior_referrer_chain = behavior_referrer_chain as u8;
+                changed = true;
+                botness_changed = true;
+            }
+            if let Some(behavior_breadth_first) = weights.get("behavior_breadth_first").and_then(|v| v.as_u64()) {
+                if behavior_breadth_first > 10 {
+                    return Response::new(400, "botness_weights.behavior_breadth_first out of range (0-10)");
+                }
+                cfg.botness_weights.behavior_breadth_first = behavior_breadth_first as u8;
+                changed = true;
+                botness_changed = true;
+            }
+            if let Some(behavior_sitemap_order) = weights.get("behavior_sitemap_order").and_then(|v| v.as_u64()) {
+                if behavior_sitemap_order > 10 {
+                    return Response::new(400, "botness_weights.behavior_sitemap_order out of range (0-10)");
+                }
+                cfg.botness_weights.behavior_sitemap_order = behavior_sitemap_order as u8;
+                changed = true;
+                botness_changed = true;
+            }
+            if let Some(privacy_pass_attested) = weights.get("privacy_pass_attested").and_then(|v| v.as_u64()) {
+                if privacy_pass_attested > 10 {
+                    return Response::new(400, "botness_weights.privacy_pass_attested out of range (0-10)");
+                }
+                cfg.botness_weights.privacy_pass_attested = privacy_pass_attested as u8;
+                changed = true;
+                botness_changed = true;
+            }
+            if let Some(robots_violation) = weights.get("robots_violation").and_then(|v| v.as_u64()) {
+                if robots_violation > 10 {
+                    return Response::new(400, "botness_weights.robots_violation out of range (0-10)");
+                }
+                cfg.botness_weights.robots_violation = robots_violation as u8;
+                changed = true;
+                botness_changed = true;
+            }
         }
         if let Some(defence_modes) = json.get("defence_modes") {
             let Some(modes_obj) = defence_modes.as_object() else {
@@ -4625,7 +6409,7 @@ fn handle_admin_config(
                     ip: None,
                     reason: Some("botness_config_update".to_string()),
                     outcome: Some(format!(
-                        "challenge:{}->{} maze:{}->{} weights(js:{}->{} geo:{}->{} rate_med:{}->{} rate_high:{}->{} maze_behavior:{}->{}) modes(rate:{:?}->{:?} geo:{:?}->{:?} js:{:?}->{:?})",
+                        "challenge:{}->{} maze:{}->{} weights(js:{}->{} geo:{}->{} rate_med:{}->{} rate_high:{}->{} maze_behavior:{}->{} behavior(timing:{}->{} assets:{}->{} referrer:{}->{} breadth:{}->{} sitemap:{}->{} privacy_pass:{}->{} robots_violation:{}->{})) modes(rate:{:?}->{:?} geo:{:?}->{:?} js:{:?}->{:?})",
                         old_challenge_threshold,
                         cfg.challenge_puzzle_risk_threshold,
                         old_maze_threshold,
@@ -4640,6 +6424,20 @@ fn handle_admin_config(
                         cfg.botness_weights.rate_high,
                         old_weights.maze_behavior,
                         cfg.botness_weights.maze_behavior,
+                        old_weights.behavior_timing_regular,
+                        cfg.botness_weights.behavior_timing_regular,
+                        old_weights.behavior_asset_ratio,
+                        cfg.botness_weights.behavior_asset_ratio,
+                        old_weights.behavior_referrer_chain,
+                        cfg.botness_weights.behavior_referrer_chain,
+                        old_weights.behavior_breadth_first,
+                        cfg.botness_weights.behavior_breadth_first,
+                        old_weights.behavior_sitemap_order,
+                        cfg.botness_weights.behavior_sitemap_order,
+                        old_weights.privacy_pass_attested,
+                        cfg.botness_weights.privacy_pass_attested,
+                        old_weights.robots_violation,
+                        cfg.botness_weights.robots_violation,
                         old_modes.rate,
                         cfg.defence_modes.rate,
                         old_modes.geo,
@@ -4911,6 +6709,79 @@ where
     Response::new(200, body)
 }
 
+fn handle_admin_alerts<S>(req: &Request, store: &S) -> Response
+where
+    S: crate::challenge::KeyValueStore,
+{
+    match *req.method() {
+        Method::Get => {
+            let body = serde_json::to_string(&json!({
+                "rules": crate::observability::alerts::load_rules(store),
+                "state": crate::observability::alerts::load_state(store),
+                "webhook_configured": crate::config::alert_webhook_url().is_some(),
+                "evaluation_interval_seconds": crate::config::alert_evaluation_interval_seconds()
+            }))
+            .unwrap();
+            Response::new(200, body)
+        }
+        Method::Post => {
+            let payload = match crate::request_validation::parse_json_body(
+                req.body(),
+                crate::request_validation::MAX_ADMIN_JSON_BYTES,
+            ) {
+                Ok(payload) => payload,
+                Err(err) => return Response::new(400, err),
+            };
+            let Some(value) = payload.get("rules") else {
+                return Response::new(400, "rules field is required");
+            };
+            let rules = match serde_json::from_value::<Vec<crate::observability::alerts::AlertRule>>(
+                value.clone(),
+            ) {
+                Ok(rules) => rules,
+                Err(err) => return Response::new(400, format!("invalid alert rules: {}", err)),
+            };
+            if let Err(err) = crate::observability::alerts::save_rules(store, &rules) {
+                return Response::new(400, err);
+            }
+            log_event(
+                store,
+                &EventLogEntry {
+                    ts: now_ts(),
+                    event: EventType::AdminAction,
+                    ip: None,
+                    reason: Some("alert_rules_update".to_string()),
+                    outcome: Some(format!("rules={}", rules.len())),
+                    admin: Some(crate::admin::auth::get_admin_id(req)),
+                },
+            );
+            let body = serde_json::to_string(&json!({
+                "updated": true,
+                "rule_count": rules.len()
+            }))
+            .unwrap();
+            Response::new(200, body)
+        }
+        _ => Response::new(405, "Method Not Allowed"),
+    }
+}
+
+fn handle_admin_decision<S>(req: &Request, store: &S, path: &str) -> Response
+where
+    S: crate::challenge::KeyValueStore,
+{
+    if *req.method() != Method::Get {
+        return Response::new(405, "Method Not Allowed");
+    }
+    let Some(incident_id) = admin_decision_id_from_path(path) else {
+        return Response::new(400, "Bad Request: Invalid incident id");
+    };
+    match crate::observability::decision_trace::load(store, incident_id, now_ts()) {
+        Some(trace) => Response::new(200, serde_json::to_string(&trace).unwrap()),
+        None => Response::new(404, "Decision trace not found or expired"),
+    }
+}
+
 fn handle_admin_monitoring<S>(req: &Request, store: &S) -> Response
 where
     S: crate::challenge::KeyValueStore,
@@ -5103,11 +6974,13 @@ where
             "fingerprint_stats": {
                 "events": read_u64_counter(store, "fingerprint:events"),
                 "ua_client_hint_mismatch": read_u64_counter(store, "fingerprint:ua_ch_mismatch"),
+                "header_shape_mismatch": read_u64_counter(store, "fingerprint:header_shape_mismatch"),
                 "ua_transport_mismatch": read_u64_counter(store, "fingerprint:ua_transport_mismatch"),
                 "temporal_transition": read_u64_counter(store, "fingerprint:temporal_transition"),
                 "flow_violation": read_u64_counter(store, "fingerprint:flow_violation"),
                 "persistence_marker_missing": read_u64_counter(store, "fingerprint:persistence_marker_missing"),
-                "untrusted_transport_header": read_u64_counter(store, "fingerprint:untrusted_transport_header")
+                "untrusted_transport_header": read_u64_counter(store, "fingerprint:untrusted_transport_header"),
+                "tls_reputation": read_u64_counter(store, "fingerprint:tls_reputation")
             }
         },
         "cdp_events": {
@@ -5346,6 +7219,7 @@ pub fn handle_admin(req: &Request) -> Response {
             }
             handle_admin_monitoring(req, &store)
         }
+        "/admin/alerts" => handle_admin_alerts(req, &store),
         "/admin/ban" => {
             if *req.method() == spin_sdk::http::Method::Get
                 && (dashboard_refresh_is_limited(&store, &auth, provider_registry.as_ref())
@@ -5522,7 +7396,7 @@ pub fn handle_admin(req: &Request) -> Response {
                     admin: Some(crate::admin::auth::get_admin_id(req)),
                 },
             );
-            Response::new(200, "WASM Bot Defence Admin API. Endpoints: /admin/ban, /admin/unban?ip=IP, /admin/analytics, /admin/events, /admin/monitoring, /admin/config, /admin/config/export, /admin/maze (GET for maze stats), /admin/maze/preview (GET non-operational maze preview), /admin/maze/seeds (GET/POST seed source adapters), /admin/maze/seeds/refresh (POST manual seed refresh), /admin/robots (GET for robots.txt config & preview), /admin/cdp (GET for CDP detection config & stats), /admin/cdp/events (GET for CDP detection and auto-ban events).")
+            Response::new(200, "WASM Bot Defence Admin API. Endpoints: /admin/ban, /admin/unban?ip=IP, /admin/analytics, /admin/events, /admin/monitoring, /admin/alerts (GET alert rules & state, POST replace rules), /admin/decisions/{id} (GET decision trace for an incident ID), /admin/config, /admin/config/export, /admin/maze (GET for maze stats), /admin/maze/preview (GET non-operational maze preview), /admin/maze/seeds (GET/POST seed source adapters), /admin/maze/seeds/refresh (POST manual seed refresh), /admin/robots (GET for robots.txt config & preview), /admin/cdp (GET for CDP detection config & stats), /admin/cdp/events (GET for CDP detection and auto-ban events).")
         }
         "/admin/maze" => {
             // Return maze statistics
@@ -5601,8 +7475,17 @@ pub fn handle_admin(req: &Request) -> Response {
             };
 
             // Generate preview of robots.txt content
-            let preview = crate::crawler_policy::robots::generate_robots_txt(&cfg);
+            let preview =
+                crate::crawler_policy::robots::generate_robots_txt(&cfg, crate::admin::now_ts());
             let content_signal = crate::crawler_policy::robots::get_content_signal_header(&cfg);
+            let ai_txt_preview = crate::crawler_policy::policy_files::generate_ai_txt(&cfg);
+            let llms_txt_preview = crate::crawler_policy::policy_files::generate_llms_txt(&cfg);
+            let tdmrep_preview = crate::crawler_policy::policy_files::generate_tdmrep_json(&cfg);
+            let crawler_names = |category| {
+                crate::crawler_policy::catalog::entries_in(&cfg, category)
+                    .map(|entry| entry.name.as_str())
+                    .collect::<Vec<_>>()
+            };
 
             // Log admin action
             log_event(
@@ -5626,13 +7509,23 @@ pub fn handle_admin(req: &Request) -> Response {
                     "block_ai_training": cfg.robots_block_ai_training,
                     "block_ai_search": cfg.robots_block_ai_search,
                     "allow_search_engines": cfg.robots_allow_search_engines,
-                    "crawl_delay": cfg.robots_crawl_delay
+                    "crawl_delay": cfg.robots_crawl_delay,
+                    "ai_crawler_action": cfg.ai_crawler_action.as_str(),
+                    "llms_txt_title": cfg.llms_txt_title,
+                    "llms_txt_summary": cfg.llms_txt_summary,
+                    "llms_txt_sections": cfg.llms_txt_sections,
+                    "tdm_policy_url": cfg.tdm_policy_url
                 },
                 "content_signal_header": content_signal,
-                "ai_training_bots": crate::crawler_policy::robots::AI_TRAINING_BOTS,
-                "ai_search_bots": crate::crawler_policy::robots::AI_SEARCH_BOTS,
-                "search_engine_bots": crate::crawler_policy::robots::SEARCH_ENGINE_BOTS,
-                "preview": preview
+                "tdm_reservation_header": crate::crawler_policy::policy_files::tdm_reservation_header(&cfg),
+                "ai_training_bots": crawler_names(crate::config::CrawlerCategory::AiTraining),
+                "ai_search_bots": crawler_names(crate::config::CrawlerCategory::AiSearch),
+                "search_engine_bots": crawler_names(crate::config::CrawlerCategory::SearchEngine),
+                "crawler_catalog": cfg.crawler_catalog,
+                "preview": preview,
+                "ai_txt_preview": ai_txt_preview,
+                "llms_txt_preview": llms_txt_preview,
+                "tdmrep_preview": tdmrep_preview
             }))
             .unwrap();
             Response::new(200, body)
@@ -5710,6 +7603,20 @@ pub fn handle_admin(req: &Request) -> Response {
                 .and_then(|v| String::from_utf8(v).ok())
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(0);
+            let fingerprint_header_shape_mismatch: u64 = store
+                .get("fingerprint:header_shape_mismatch")
+                .ok()
+                .flatten()
+                .and_then(|v| String::from_utf8(v).ok())
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+            let fingerprint_tls_reputation: u64 = store
+                .get("fingerprint:tls_reputation")
+                .ok()
+                .flatten()
+                .and_then(|v| String::from_utf8(v).ok())
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
 
             // Log admin action
             log_event(
@@ -5741,7 +7648,14 @@ pub fn handle_admin(req: &Request) -> Response {
                     "fingerprint_family_cap_transport": cfg.fingerprint_family_cap_transport,
                     "fingerprint_family_cap_temporal": cfg.fingerprint_family_cap_temporal,
                     "fingerprint_family_cap_persistence": cfg.fingerprint_family_cap_persistence,
-                    "fingerprint_family_cap_behavior": cfg.fingerprint_family_cap_behavior
+                    "fingerprint_family_cap_behavior": cfg.fingerprint_family_cap_behavior,
+                    "fingerprint_tls_custom_rule_count": cfg.fingerprint_tls_custom_rules.len(),
+                    "fingerprint_tls_managed_policy_count": cfg.fingerprint_tls_managed_policies.len(),
+                    "fingerprint_tls_catalog_version": crate::signals::tls_reputation::managed_catalog_version()
+                },
+                "probe_registry": {
+                    "version": crate::signals::cdp::probes::CURRENT_PROBE_REGISTRY_VERSION,
+                    "probes": crate::signals::cdp::probes::current_registry()
                 },
                 "stats": {
                     "total_detections": cdp_detections,
@@ -5750,23 +7664,33 @@ pub fn handle_admin(req: &Request) -> Response {
                 "fingerprint_stats": {
                     "events": fingerprint_events,
                     "ua_client_hint_mismatch": fingerprint_ua_ch_mismatch,
+                    "header_shape_mismatch": fingerprint_header_shape_mismatch,
                     "ua_transport_mismatch": fingerprint_ua_transport_mismatch,
                     "temporal_transition": fingerprint_temporal_transition,
                     "flow_violation": fingerprint_flow_violation,
                     "persistence_marker_missing": fingerprint_persistence_marker_missing,
-                    "untrusted_transport_header": fingerprint_untrusted_transport_header
+                    "untrusted_transport_header": fingerprint_untrusted_transport_header,
+                    "tls_reputation": fingerprint_tls_reputation
                 },
                 "detection_methods": [
                     "Error stack timing analysis (Runtime.Enable leak)",
                     "navigator.webdriver property check",
                     "Automation-specific window properties",
                     "Chrome object consistency verification",
-                    "Plugin array anomaly detection"
+                    "Plugin array anomaly detection",
+                    "WebGL software renderer detection",
+                    "Permissions API notification quirk",
+                    "iframe contentWindow integrity",
+                    "Timezone/locale consistency",
+                    "Worker vs main-thread navigator mismatch"
                 ]
             }))
             .unwrap();
             Response::new(200, body)
         }
+        _ if admin_decision_id_from_path(path).is_some() => {
+            handle_admin_decision(req, &store, path)
+        }
         _ => Response::new(404, "Not found"),
     }
 }
@@ -1,4 +1,5 @@
 mod api;
 pub(crate) mod auth;
 
+pub(crate) use api::EVENTLOG_V2_PREFIX;
 pub use api::{handle_admin, log_event, now_ts, EventLogEntry, EventType};
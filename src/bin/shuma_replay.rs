@@ -0,0 +1,14 @@
+// Offline traffic replay CLI. Build with `--features replay-cli`; see docs/replay.md.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = shuma_gorath::replay::cli::parse_args(&args)
+        .and_then(|args| shuma_gorath::replay::cli::run(&args));
+    match result {
+        Ok(report) => println!("{}", report),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    }
+}
@@ -15,8 +15,12 @@ impl ChallengeBoundary for DefaultChallengeBoundary {
         crate::challenge::NOT_A_BOT_PATH
     }
 
-    fn render_challenge(&self, req: &Request, transform_count: usize) -> Response {
-        crate::challenge::render_challenge(req, transform_count)
+    fn puzzle_accessible_path(&self) -> &'static str {
+        crate::challenge::PUZZLE_ACCESSIBLE_PATH
+    }
+
+    fn render_challenge(&self, req: &Request, cfg: &crate::config::Config) -> Response {
+        crate::challenge::render_challenge(req, cfg)
     }
 
     fn render_not_a_bot(&self, req: &Request, cfg: &crate::config::Config) -> Response {
@@ -27,9 +31,17 @@ impl ChallengeBoundary for DefaultChallengeBoundary {
         &self,
         req: &Request,
         test_mode: bool,
-        transform_count: usize,
+        cfg: &crate::config::Config,
+    ) -> Response {
+        crate::challenge::serve_challenge_page(req, test_mode, cfg)
+    }
+
+    fn serve_accessible_challenge_page(
+        &self,
+        req: &Request,
+        cfg: &crate::config::Config,
     ) -> Response {
-        crate::challenge::serve_challenge_page(req, test_mode, transform_count)
+        crate::challenge::serve_accessible_challenge_page(req, cfg)
     }
 
     fn serve_not_a_bot_page(
@@ -41,15 +53,25 @@ impl ChallengeBoundary for DefaultChallengeBoundary {
         crate::challenge::serve_not_a_bot_page(req, test_mode, cfg)
     }
 
-    fn handle_challenge_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+    fn handle_challenge_submit_with_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
+        &self,
+        store: &S,
+        req: &Request,
+        cfg: &crate::config::Config,
+    ) -> (Response, crate::challenge::ChallengeSubmitOutcome) {
+        crate::challenge::handle_challenge_submit_with_outcome(store, req, cfg)
+    }
+
+    fn handle_accessible_submit_with_outcome<S: crate::challenge::KeyValueStore>(
         &self,
         store: &S,
         req: &Request,
+        cfg: &crate::config::Config,
     ) -> (Response, crate::challenge::ChallengeSubmitOutcome) {
-        crate::challenge::handle_challenge_submit_with_outcome(store, req)
+        crate::challenge::handle_accessible_submit_with_outcome(store, req, cfg)
     }
 
-    fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+    fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
         &self,
         store: &S,
         req: &Request,
@@ -83,8 +105,12 @@ pub(crate) fn challenge_not_a_bot_path() -> &'static str {
     CHALLENGE.not_a_bot_path()
 }
 
-pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Response {
-    CHALLENGE.render_challenge(req, transform_count)
+pub(crate) fn challenge_puzzle_accessible_path() -> &'static str {
+    CHALLENGE.puzzle_accessible_path()
+}
+
+pub(crate) fn render_challenge(req: &Request, cfg: &crate::config::Config) -> Response {
+    CHALLENGE.render_challenge(req, cfg)
 }
 
 pub(crate) fn render_not_a_bot(req: &Request, cfg: &crate::config::Config) -> Response {
@@ -94,9 +120,16 @@ pub(crate) fn render_not_a_bot(req: &Request, cfg: &crate::config::Config) -> Re
 pub(crate) fn serve_challenge_page(
     req: &Request,
     test_mode: bool,
-    transform_count: usize,
+    cfg: &crate::config::Config,
+) -> Response {
+    CHALLENGE.serve_challenge_page(req, test_mode, cfg)
+}
+
+pub(crate) fn serve_accessible_challenge_page(
+    req: &Request,
+    cfg: &crate::config::Config,
 ) -> Response {
-    CHALLENGE.serve_challenge_page(req, test_mode, transform_count)
+    CHALLENGE.serve_accessible_challenge_page(req, cfg)
 }
 
 pub(crate) fn serve_not_a_bot_page(
@@ -107,14 +140,23 @@ pub(crate) fn serve_not_a_bot_page(
     CHALLENGE.serve_not_a_bot_page(req, test_mode, cfg)
 }
 
-pub(crate) fn handle_challenge_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+pub(crate) fn handle_challenge_submit_with_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    req: &Request,
+    cfg: &crate::config::Config,
+) -> (Response, crate::challenge::ChallengeSubmitOutcome) {
+    CHALLENGE.handle_challenge_submit_with_outcome(store, req, cfg)
+}
+
+pub(crate) fn handle_accessible_submit_with_outcome<S: crate::challenge::KeyValueStore>(
     store: &S,
     req: &Request,
+    cfg: &crate::config::Config,
 ) -> (Response, crate::challenge::ChallengeSubmitOutcome) {
-    CHALLENGE.handle_challenge_submit_with_outcome(store, req)
+    CHALLENGE.handle_accessible_submit_with_outcome(store, req, cfg)
 }
 
-pub(crate) fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+pub(crate) fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
     store: &S,
     req: &Request,
     cfg: &crate::config::Config,
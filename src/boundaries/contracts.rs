@@ -3,13 +3,19 @@ use spin_sdk::http::{Request, Response};
 pub(crate) trait ChallengeBoundary {
     fn puzzle_path(&self) -> &'static str;
     fn not_a_bot_path(&self) -> &'static str;
-    fn render_challenge(&self, req: &Request, transform_count: usize) -> Response;
+    fn puzzle_accessible_path(&self) -> &'static str;
+    fn render_challenge(&self, req: &Request, cfg: &crate::config::Config) -> Response;
     fn render_not_a_bot(&self, req: &Request, cfg: &crate::config::Config) -> Response;
     fn serve_challenge_page(
         &self,
         req: &Request,
         test_mode: bool,
-        transform_count: usize,
+        cfg: &crate::config::Config,
+    ) -> Response;
+    fn serve_accessible_challenge_page(
+        &self,
+        req: &Request,
+        cfg: &crate::config::Config,
     ) -> Response;
     fn serve_not_a_bot_page(
         &self,
@@ -17,12 +23,19 @@ pub(crate) trait ChallengeBoundary {
         test_mode: bool,
         cfg: &crate::config::Config,
     ) -> Response;
-    fn handle_challenge_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+    fn handle_challenge_submit_with_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
         &self,
         store: &S,
         req: &Request,
+        cfg: &crate::config::Config,
+    ) -> (Response, crate::challenge::ChallengeSubmitOutcome);
+    fn handle_accessible_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+        &self,
+        store: &S,
+        req: &Request,
+        cfg: &crate::config::Config,
     ) -> (Response, crate::challenge::ChallengeSubmitOutcome);
-    fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+    fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
         &self,
         store: &S,
         req: &Request,
@@ -3,7 +3,8 @@ mod contracts;
 
 pub(crate) use crate::challenge::ChallengeSubmitOutcome;
 pub(crate) use adapters::{
-    challenge_not_a_bot_path, challenge_puzzle_path, handle_admin,
-    handle_challenge_submit_with_outcome, handle_not_a_bot_submit_with_outcome, is_maze_path,
-    render_challenge, render_not_a_bot, serve_challenge_page, serve_not_a_bot_page,
+    challenge_not_a_bot_path, challenge_puzzle_accessible_path, challenge_puzzle_path,
+    handle_accessible_submit_with_outcome, handle_admin, handle_challenge_submit_with_outcome,
+    handle_not_a_bot_submit_with_outcome, is_maze_path, render_challenge, render_not_a_bot,
+    serve_accessible_challenge_page, serve_challenge_page, serve_not_a_bot_page,
 };
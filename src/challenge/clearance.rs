@@ -0,0 +1,395 @@
+// src/challenge/clearance.rs
+// Signed clearance token issued by every challenge success path (JS, PoW, not-a-bot, puzzle).
+// Carries issue/expiry times, IP-bucket and UA-bucket binding, the escalation level the client
+// cleared and the ID of the signing key, so secrets can rotate without logging everyone out.
+
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub(crate) const CLEARANCE_COOKIE_NAME: &str = "shuma_clearance";
+/// v1 was the unversioned IP-only `js_verified` HMAC.
+pub(crate) const CLEARANCE_TOKEN_VERSION: u8 = 2;
+/// Lifetime for JS, PoW and puzzle clearance; not-a-bot uses `not_a_bot_marker_ttl_seconds`.
+pub(crate) const CLEARANCE_TTL_SECONDS: u64 = 86_400;
+const KEY_ID_HEX_CHARS: usize = 8;
+
+/// Escalation level a client has cleared; higher levels satisfy lower gates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) enum ClearanceLevel {
+    #[serde(rename = "L4_VERIFY_JS")]
+    VerifyJs,
+    #[serde(rename = "L5_NOT_A_BOT")]
+    NotABot,
+    /// Text alternative to the puzzle. Its questions are machine-answerable, so it clears
+    /// not-a-bot but never the puzzle gate.
+    #[serde(rename = "L6_CHALLENGE_ACCESSIBLE")]
+    ChallengeAccessible,
+    #[serde(rename = "L6_CHALLENGE_STRONG")]
+    ChallengeStrong,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ClearanceClaims {
+    pub token_version: u8,
+    pub kid: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub ip_bucket: String,
+    pub ua_bucket: String,
+    pub level: ClearanceLevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ClearanceError {
+    Malformed,
+    UnsupportedVersion,
+    UnknownKey,
+    SignatureMismatch,
+    Expired,
+    BindingMismatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ClearanceKey {
+    pub kid: String,
+    secret: String,
+}
+
+impl ClearanceKey {
+    pub(crate) fn from_secret(secret: &str) -> Self {
+        let digest = Sha256::digest(secret.as_bytes());
+        let kid = digest
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+            .chars()
+            .take(KEY_ID_HEX_CHARS)
+            .collect();
+        Self {
+            kid,
+            secret: secret.to_string(),
+        }
+    }
+
+    fn mac(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes()).unwrap();
+        mac.update(payload);
+        mac
+    }
+}
+
+/// Signing key first, then retired keys that are still accepted for verification.
+fn keyring() -> Vec<ClearanceKey> {
+    let mut keys = vec![ClearanceKey::from_secret(
+        crate::config::env_string_required("SHUMA_JS_SECRET").as_str(),
+    )];
+    for secret in crate::config::js_secret_previous() {
+        let key = ClearanceKey::from_secret(secret.as_str());
+        if !keys.iter().any(|existing| existing.kid == key.kid) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+pub(crate) fn issue_with_keyring(
+    keyring: &[ClearanceKey],
+    ip_bucket: &str,
+    ua_bucket: &str,
+    level: ClearanceLevel,
+    ttl_seconds: u64,
+    now: u64,
+) -> String {
+    let key = &keyring[0];
+    let claims = ClearanceClaims {
+        token_version: CLEARANCE_TOKEN_VERSION,
+        kid: key.kid.clone(),
+        issued_at: now,
+        expires_at: now.saturating_add(ttl_seconds),
+        ip_bucket: ip_bucket.to_string(),
+        ua_bucket: ua_bucket.to_string(),
+        level,
+    };
+    let payload_json = serde_json::to_vec(&claims).unwrap();
+    let sig = key.mac(&payload_json).finalize().into_bytes();
+    format!(
+        "{}.{}",
+        general_purpose::URL_SAFE_NO_PAD.encode(payload_json),
+        general_purpose::URL_SAFE_NO_PAD.encode(sig)
+    )
+}
+
+pub(crate) fn verify_with_keyring(
+    keyring: &[ClearanceKey],
+    token: &str,
+    ip_bucket: &str,
+    ua_bucket: &str,
+    now: u64,
+) -> Result<ClearanceClaims, ClearanceError> {
+    let (payload_b64, sig_b64) = token.split_once('.').ok_or(ClearanceError::Malformed)?;
+    let payload_json = general_purpose::URL_SAFE_NO_PAD
+        .decode(payload_b64.as_bytes())
+        .map_err(|_| ClearanceError::Malformed)?;
+    let sig = general_purpose::URL_SAFE_NO_PAD
+        .decode(sig_b64.as_bytes())
+        .map_err(|_| ClearanceError::Malformed)?;
+    let claims = serde_json::from_slice::<ClearanceClaims>(&payload_json)
+        .map_err(|_| ClearanceError::Malformed)?;
+    if claims.token_version != CLEARANCE_TOKEN_VERSION {
+        return Err(ClearanceError::UnsupportedVersion);
+    }
+    let key = keyring
+        .iter()
+        .find(|key| key.kid == claims.kid)
+        .ok_or(ClearanceError::UnknownKey)?;
+    key.mac(&payload_json)
+        .verify_slice(&sig)
+        .map_err(|_| ClearanceError::SignatureMismatch)?;
+    if now >= claims.expires_at {
+        return Err(ClearanceError::Expired);
+    }
+    if claims.ip_bucket != ip_bucket || claims.ua_bucket != ua_bucket {
+        return Err(ClearanceError::BindingMismatch);
+    }
+    Ok(claims)
+}
+
+/// Cookie for server-issued clearance. The JS-only interstitial sets the same cookie from
+/// script, so it passes `http_only = false`.
+pub(crate) fn cookie_value(token: &str, ttl_seconds: u64, http_only: bool) -> String {
+    format!(
+        "{}={}; path=/; {}SameSite=Strict; Max-Age={}",
+        CLEARANCE_COOKIE_NAME,
+        token,
+        if http_only { "HttpOnly; " } else { "" },
+        ttl_seconds
+    )
+}
+
+pub(crate) fn issue_token(
+    ip_bucket: &str,
+    ua_bucket: &str,
+    level: ClearanceLevel,
+    ttl_seconds: u64,
+) -> String {
+    issue_with_keyring(
+        &keyring(),
+        ip_bucket,
+        ua_bucket,
+        level,
+        ttl_seconds,
+        crate::admin::now_ts(),
+    )
+}
+
+/// `Set-Cookie` value granting `level` to the client identified by the IP and UA buckets.
+pub(crate) fn issue_cookie(
+    ip_bucket: &str,
+    ua_bucket: &str,
+    level: ClearanceLevel,
+    ttl_seconds: u64,
+) -> String {
+    cookie_value(
+        issue_token(ip_bucket, ua_bucket, level, ttl_seconds).as_str(),
+        ttl_seconds,
+        true,
+    )
+}
+
+fn cookie_token(cookie_header: &str) -> Option<&str> {
+    cookie_header.split(';').find_map(|part| {
+        let (name, value) = part.trim().split_once('=')?;
+        (name.trim() == CLEARANCE_COOKIE_NAME && !value.trim().is_empty()).then(|| value.trim())
+    })
+}
+
+/// Highest level the request's clearance cookie proves for this IP and user agent.
+pub(crate) fn request_clearance_level(
+    req: &spin_sdk::http::Request,
+    ip: &str,
+    user_agent: &str,
+) -> Option<ClearanceLevel> {
+    let cookie_header = req.header("cookie").and_then(|value| value.as_str())?;
+    let token = cookie_token(cookie_header)?;
+    verify_with_keyring(
+        &keyring(),
+        token,
+        crate::signals::ip_identity::bucket_ip(ip).as_str(),
+        crate::challenge::operation_envelope::user_agent_bucket(user_agent).as_str(),
+        crate::admin::now_ts(),
+    )
+    .ok()
+    .map(|claims| claims.level)
+}
+
+/// Whether a pass at `level` should replace the request's clearance cookie. A request
+/// already holding a stronger valid level keeps it, so a later JS or PoW pass never
+/// downgrades a not-a-bot or puzzle clearance.
+pub(crate) fn should_issue_level(
+    req: &spin_sdk::http::Request,
+    ip: &str,
+    user_agent: &str,
+    level: ClearanceLevel,
+) -> bool {
+    request_clearance_level(req, ip, user_agent).is_none_or(|held| held <= level)
+}
+
+pub(crate) fn has_clearance_at_least(
+    req: &spin_sdk::http::Request,
+    ip: &str,
+    user_agent: &str,
+    level: ClearanceLevel,
+) -> bool {
+    request_clearance_level(req, ip, user_agent).is_some_and(|cleared| cleared >= level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spin_sdk::http::Request;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn keys(secrets: &[&str]) -> Vec<ClearanceKey> {
+        secrets
+            .iter()
+            .map(|secret| ClearanceKey::from_secret(secret))
+            .collect()
+    }
+
+    #[test]
+    fn token_round_trips_claims_and_enforces_expiry() {
+        let keyring = keys(&["current-secret"]);
+        let token = issue_with_keyring(
+            &keyring,
+            "198.51.100.0",
+            "ua1",
+            ClearanceLevel::NotABot,
+            600,
+            NOW,
+        );
+        let claims = verify_with_keyring(&keyring, &token, "198.51.100.0", "ua1", NOW + 10)
+            .expect("fresh token should verify");
+        assert_eq!(claims.level, ClearanceLevel::NotABot);
+        assert_eq!(claims.kid, keyring[0].kid);
+        assert_eq!(claims.expires_at, NOW + 600);
+        assert_eq!(
+            verify_with_keyring(&keyring, &token, "198.51.100.0", "ua1", NOW + 600),
+            Err(ClearanceError::Expired)
+        );
+    }
+
+    #[test]
+    fn token_is_bound_to_ip_and_user_agent_buckets() {
+        let keyring = keys(&["current-secret"]);
+        let token = issue_with_keyring(
+            &keyring,
+            "198.51.100.0",
+            "ua1",
+            ClearanceLevel::VerifyJs,
+            600,
+            NOW,
+        );
+        assert_eq!(
+            verify_with_keyring(&keyring, &token, "203.0.113.0", "ua1", NOW),
+            Err(ClearanceError::BindingMismatch)
+        );
+        assert_eq!(
+            verify_with_keyring(&keyring, &token, "198.51.100.0", "ua2", NOW),
+            Err(ClearanceError::BindingMismatch)
+        );
+    }
+
+    #[test]
+    fn previous_keys_verify_during_rotation_until_removed() {
+        let old_keyring = keys(&["old-secret"]);
+        let token = issue_with_keyring(
+            &old_keyring,
+            "198.51.100.0",
+            "ua1",
+            ClearanceLevel::ChallengeStrong,
+            600,
+            NOW,
+        );
+        let rotated = keys(&["new-secret", "old-secret"]);
+        assert!(verify_with_keyring(&rotated, &token, "198.51.100.0", "ua1", NOW).is_ok());
+        let retired = keys(&["new-secret"]);
+        assert_eq!(
+            verify_with_keyring(&retired, &token, "198.51.100.0", "ua1", NOW),
+            Err(ClearanceError::UnknownKey)
+        );
+    }
+
+    #[test]
+    fn tampered_claims_fail_signature_check() {
+        let keyring = keys(&["current-secret"]);
+        let token = issue_with_keyring(
+            &keyring,
+            "198.51.100.0",
+            "ua1",
+            ClearanceLevel::VerifyJs,
+            600,
+            NOW,
+        );
+        let (payload_b64, sig_b64) = token.split_once('.').unwrap();
+        let payload = general_purpose::URL_SAFE_NO_PAD
+            .decode(payload_b64)
+            .unwrap();
+        let forged = String::from_utf8(payload)
+            .unwrap()
+            .replace("L4_VERIFY_JS", "L6_CHALLENGE_STRONG");
+        let forged_token = format!(
+            "{}.{}",
+            general_purpose::URL_SAFE_NO_PAD.encode(forged),
+            sig_b64
+        );
+        assert_eq!(
+            verify_with_keyring(&keyring, &forged_token, "198.51.100.0", "ua1", NOW),
+            Err(ClearanceError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify_with_keyring(&keyring, "not-a-token", "198.51.100.0", "ua1", NOW),
+            Err(ClearanceError::Malformed)
+        );
+    }
+
+    #[test]
+    fn request_cookie_grants_cleared_level_and_below() {
+        let _lock = crate::test_support::lock_env();
+        std::env::set_var("SHUMA_JS_SECRET", "unit-test-secret");
+        std::env::remove_var("SHUMA_JS_SECRET_PREVIOUS");
+        let ua = "Mozilla/5.0";
+        let cookie = issue_cookie(
+            crate::signals::ip_identity::bucket_ip("198.51.100.42").as_str(),
+            crate::challenge::operation_envelope::user_agent_bucket(ua).as_str(),
+            ClearanceLevel::NotABot,
+            600,
+        );
+        assert!(cookie.contains("HttpOnly"));
+        let req = Request::builder()
+            .method(spin_sdk::http::Method::Get)
+            .uri("/")
+            .header("cookie", format!("other=1; {}", cookie))
+            .header("user-agent", ua)
+            .build();
+        assert_eq!(
+            request_clearance_level(&req, "198.51.100.7", ua),
+            Some(ClearanceLevel::NotABot)
+        );
+        assert!(has_clearance_at_least(
+            &req,
+            "198.51.100.7",
+            ua,
+            ClearanceLevel::VerifyJs
+        ));
+        assert!(!has_clearance_at_least(
+            &req,
+            "198.51.100.7",
+            ua,
+            ClearanceLevel::ChallengeStrong
+        ));
+        assert_eq!(request_clearance_level(&req, "203.0.113.10", ua), None);
+    }
+}
@@ -1,15 +1,15 @@
 use spin_sdk::http::Response;
 use spin_sdk::key_value::Store;
 
+pub(crate) mod clearance;
 pub(crate) mod not_a_bot;
 pub(crate) mod operation_envelope;
 pub(crate) mod pow;
 mod puzzle;
 
 pub(crate) use not_a_bot::{
-    handle_not_a_bot_submit_with_outcome, has_valid_marker as has_valid_not_a_bot_marker,
-    render_not_a_bot, serve_not_a_bot_page, NotABotDecision, NotABotSubmitOutcome,
-    NotABotSubmitResult,
+    handle_not_a_bot_submit_with_outcome, render_not_a_bot, serve_not_a_bot_page,
+    NotABotDecision, NotABotSubmitOutcome, NotABotSubmitResult,
 };
 #[cfg(test)]
 pub use puzzle::handle_challenge_submit;
@@ -19,10 +19,13 @@ pub(crate) use puzzle::render_challenge;
 #[cfg(test)]
 pub(crate) use puzzle::{
     apply_transform, build_puzzle, generate_pair, parse_submission, parse_transform_count,
-    select_transform_pair, transforms_for_count, ChallengeSeed, Transform,
+    select_transform_pair, transforms_for_count, ChallengeSeed, ChallengeVariant, Transform,
 };
+#[cfg(test)]
+pub(crate) use puzzle::{answer_matches, build_question, AccessibleQuestion};
 pub(crate) use puzzle::{
-    handle_challenge_submit_with_outcome, serve_challenge_page, ChallengeSubmitOutcome,
+    handle_accessible_submit_with_outcome, handle_challenge_submit_with_outcome,
+    serve_accessible_challenge_page, serve_challenge_page, ChallengeSubmitOutcome,
 };
 
 pub trait KeyValueStore {
@@ -35,6 +38,7 @@ pub trait KeyValueStore {
 }
 
 pub(crate) const PUZZLE_PATH: &str = "/challenge/puzzle";
+pub(crate) const PUZZLE_ACCESSIBLE_PATH: &str = "/challenge/puzzle/accessible";
 pub(crate) const NOT_A_BOT_PATH: &str = "/challenge/not-a-bot-checkbox";
 
 impl KeyValueStore for Store {
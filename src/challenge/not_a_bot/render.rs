@@ -29,14 +29,15 @@ pub(crate) fn render_not_a_bot(
         return_to: normalize_return_to(req.uri()),
     };
     let seed_token = make_seed_token(&seed);
+    let messages = crate::i18n::Messages::for_request(req, cfg);
 
     let html = format!(
         r#"
-<html>
+<html lang="{lang}" dir="{dir}">
 <head>
   <meta charset="utf-8" />
   <meta name="viewport" content="width=device-width, initial-scale=1" />
-  <title>Please confrim you are not a bot</title>
+  <title>{title}</title>
   <style>
     :root {{
       --bg: #fffafd;
@@ -88,7 +89,7 @@ pub(crate) fn render_not_a_bot(
       gap: 12px;
       padding: 12px 14px;
       cursor: pointer;
-      text-align: left;
+      text-align: start;
       font-size: 1rem;
       line-height: 1.3;
     }}
@@ -114,7 +115,7 @@ pub(crate) fn render_not_a_bot(
     }}
     .checkbox-mark {{
       position: absolute;
-      left: 5px;
+      inset-inline-start: 5px;
       top: 0px;
       width: 6px;
       height: 12px;
@@ -135,7 +136,7 @@ pub(crate) fn render_not_a_bot(
 </head>
 <body>
   <main class="panel">
-    <h1>Please confrim you are not a bot</h1>
+    <h1>{title}</h1>
     <form id="not-a-bot-form" method="POST" action="{not_a_bot_path}">
       <input type="hidden" name="seed" value="{seed_token}" />
       <input type="hidden" id="not-a-bot-checked" name="checked" value="0" />
@@ -149,7 +150,7 @@ pub(crate) fn render_not_a_bot(
           aria-checked="false"
         >
           <span class="checkbox-box" aria-hidden="true"><span class="checkbox-mark"></span></span>
-          <span>I am not a bot</span>
+          <span>{checkbox_label}</span>
         </button>
       </div>
     </form>
@@ -293,7 +294,11 @@ pub(crate) fn render_not_a_bot(
 </html>
 "#,
         not_a_bot_path = crate::challenge::NOT_A_BOT_PATH,
-        seed_token = seed_token
+        seed_token = seed_token,
+        lang = messages.locale(),
+        dir = messages.dir(),
+        title = messages.text("not_a_bot.title"),
+        checkbox_label = messages.text("not_a_bot.checkbox"),
     );
 
     crate::challenge::challenge_response(200, html.as_str())
@@ -1,7 +1,8 @@
 use spin_sdk::http::Request;
 
 use super::render::normalize_return_to;
-use super::token::{marker_cookie_value, parse_seed_token, SeedTokenError};
+use super::token::{parse_seed_token, SeedTokenError};
+use crate::challenge::clearance::{self, ClearanceLevel};
 use super::types::{
     NotABotDecision, NotABotSubmitOutcome, NotABotSubmitResult, NotABotTelemetry,
 };
@@ -28,7 +29,7 @@ pub(crate) fn serve_not_a_bot_page(
     super::render::render_not_a_bot(req, cfg)
 }
 
-pub(crate) fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore>(
+pub(crate) fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
     store: &S,
     req: &Request,
     cfg: &crate::config::Config,
@@ -264,11 +265,20 @@ pub(crate) fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValue
             outcome: NotABotSubmitOutcome::Pass,
             decision: NotABotDecision::Pass,
             return_to,
-            marker_cookie: Some(marker_cookie_value(
-                seed.ip_bucket.as_str(),
-                seed.ua_bucket.as_str(),
-                cfg.not_a_bot_marker_ttl_seconds,
-            )),
+            clearance_cookie: clearance::should_issue_level(
+                req,
+                ip.as_str(),
+                ua,
+                ClearanceLevel::NotABot,
+            )
+            .then(|| {
+                clearance::issue_cookie(
+                    seed.ip_bucket.as_str(),
+                    seed.ua_bucket.as_str(),
+                    ClearanceLevel::NotABot,
+                    cfg.not_a_bot_marker_ttl_seconds,
+                )
+            }),
             solve_ms: Some(telemetry.interaction_elapsed_ms as u64),
         };
     }
@@ -278,7 +288,7 @@ pub(crate) fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValue
             outcome: NotABotSubmitOutcome::EscalatePuzzle,
             decision: NotABotDecision::EscalatePuzzle,
             return_to,
-            marker_cookie: None,
+            clearance_cookie: None,
             solve_ms: Some(telemetry.interaction_elapsed_ms as u64),
         };
     }
@@ -290,7 +300,7 @@ pub(crate) fn handle_not_a_bot_submit_with_outcome<S: crate::challenge::KeyValue
     )
 }
 
-fn increment_and_check_attempt_limit<S: crate::challenge::KeyValueStore>(
+fn increment_and_check_attempt_limit<S: crate::challenge::KeyValueStore + ?Sized>(
     store: &S,
     ip_bucket: &str,
     now: u64,
@@ -469,7 +479,7 @@ fn failure_result(
         outcome,
         decision,
         return_to: normalize_return_to(return_to),
-        marker_cookie: None,
+        clearance_cookie: None,
         solve_ms: None,
     }
 }
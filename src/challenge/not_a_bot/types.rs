@@ -62,6 +62,6 @@ pub(crate) struct NotABotSubmitResult {
     pub outcome: NotABotSubmitOutcome,
     pub decision: NotABotDecision,
     pub return_to: String,
-    pub marker_cookie: Option<String>,
+    pub clearance_cookie: Option<String>,
     pub solve_ms: Option<u64>,
 }
@@ -39,6 +39,13 @@ pub(crate) const TIMING_REGULARITY_SPREAD_SECONDS_JS_POW_VERIFY: u64 = 1;
 pub(crate) const TIMING_HISTORY_TTL_SECONDS_JS_POW_VERIFY: u64 = 1200;
 pub(crate) const MAX_OPERATION_REPLAY_TTL_SECONDS_JS_POW_VERIFY: u64 = 600;
 
+pub(crate) const FLOW_CDP_REPORT: &str = "cdp_report";
+pub(crate) const STEP_CDP_REPORT_SUBMIT: &str = "cdp_report_submit";
+pub(crate) const PATH_CLASS_CDP_REPORT_SUBMIT: &str = "cdp_report_submit";
+pub(crate) const STEP_INDEX_CDP_REPORT_SUBMIT: u8 = 2;
+pub(crate) const MAX_STEP_WINDOW_SECONDS_CDP_REPORT: u64 = 300;
+pub(crate) const MAX_OPERATION_REPLAY_TTL_SECONDS_CDP_REPORT: u64 = 300;
+
 const MAX_OPERATION_ID_LEN: usize = 64;
 const UA_BUCKET_HEX_LEN: usize = 16;
 const CADENCE_KEY_PREFIX: &str = "seq:cadence";
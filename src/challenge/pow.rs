@@ -1,6 +1,8 @@
 // src/challenge/pow.rs
-// Lightweight proof-of-work (PoW) challenge for JS verification
+// Proof-of-work (PoW) challenge for JS verification: SHA-256 leading-zero bits by default,
+// or memory-hard scrypt when `pow_algorithm=scrypt`.
 
+use crate::config::PowAlgorithm;
 use base64::{engine::general_purpose, Engine as _};
 use hmac::{Hmac, Mac};
 use once_cell::sync::Lazy;
@@ -26,6 +28,20 @@ struct PowPayload {
     expires_at: u64,
     token_version: u8,
     difficulty: u8,
+    #[serde(default)]
+    algorithm: PowAlgorithm,
+    #[serde(default)]
+    memory_kib: u32,
+}
+
+impl PowPayload {
+    fn params(&self) -> PowParams {
+        PowParams {
+            algorithm: self.algorithm,
+            difficulty: self.difficulty,
+            memory_kib: self.memory_kib,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,6 +49,271 @@ pub struct PowChallenge {
     pub seed: String,
     pub difficulty: u8,
     pub expires_at: u64,
+    pub algorithm: PowAlgorithm,
+    pub memory_kib: u32,
+}
+
+// scrypt block size and parallelism are fixed so `memory_kib` alone sets the per-attempt
+// memory: 128 * r * N bytes with r = 8 is exactly N KiB.
+const SCRYPT_BLOCK_SIZE: u32 = 8;
+const SCRYPT_PARALLELISM: u32 = 1;
+const SCRYPT_OUTPUT_LEN: usize = 32;
+
+/// Algorithm and cost for one seed. Resolved from config at issue time and signed into the
+/// seed, so a config change never alters the work required for seeds already handed out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowParams {
+    pub algorithm: PowAlgorithm,
+    pub difficulty: u8,
+    pub memory_kib: u32,
+}
+
+impl PowParams {
+    pub fn sha256(difficulty: u8) -> Self {
+        Self {
+            algorithm: PowAlgorithm::Sha256,
+            difficulty,
+            memory_kib: 0,
+        }
+    }
+
+    pub fn scrypt(difficulty: u8, memory_kib: u32) -> Self {
+        Self {
+            algorithm: PowAlgorithm::Scrypt,
+            difficulty,
+            memory_kib,
+        }
+    }
+
+    pub fn from_config(cfg: &crate::config::Config) -> Self {
+        match cfg.pow_algorithm {
+            PowAlgorithm::Sha256 => Self::sha256(cfg.pow_difficulty),
+            PowAlgorithm::Scrypt => {
+                Self::scrypt(cfg.pow_scrypt_difficulty, cfg.pow_scrypt_memory_kib)
+            }
+        }
+    }
+
+    /// Adds adaptive bits, staying inside the algorithm's configurable range.
+    fn with_extra_bits(self, extra_bits: u8) -> Self {
+        let max = match self.algorithm {
+            PowAlgorithm::Sha256 => crate::config::POW_DIFFICULTY_MAX,
+            PowAlgorithm::Scrypt => crate::config::POW_SCRYPT_DIFFICULTY_MAX,
+        };
+        Self {
+            difficulty: self.difficulty.saturating_add(extra_bits).min(max),
+            ..self
+        }
+    }
+
+    /// Verification runs one hash at these parameters, so the server only accepts seeds
+    /// whose cost stays inside the configurable range. Anything else is refused before
+    /// any hashing happens.
+    fn within_verification_bounds(&self) -> bool {
+        match self.algorithm {
+            PowAlgorithm::Sha256 => self.difficulty <= crate::config::POW_DIFFICULTY_MAX,
+            PowAlgorithm::Scrypt => {
+                self.memory_kib.is_power_of_two()
+                    && (crate::config::POW_SCRYPT_MEMORY_KIB_MIN
+                        ..=crate::config::POW_SCRYPT_MEMORY_KIB_MAX)
+                        .contains(&self.memory_kib)
+                    && self.difficulty <= crate::config::POW_SCRYPT_DIFFICULTY_MAX
+            }
+        }
+    }
+}
+
+const POW_ADAPTIVE_BOTNESS_POINTS_PER_BIT: u8 = 2;
+const POW_ADAPTIVE_FAILURE_BITS_MAX: u8 = 3;
+const POW_ADAPTIVE_FAILURE_WINDOW_SECONDS: u64 = 600;
+const POW_ADAPTIVE_LOAD_WINDOW_SECONDS: u64 = 60;
+/// Running total of issued seeds, bumped through the metrics buffer so issuing never
+/// writes KV itself; buffers are flushed after each response.
+const POW_ADAPTIVE_ISSUED_KEY: &str = "pow:adaptive:issued_total";
+const POW_ADAPTIVE_LOAD_KEY: &str = "pow:adaptive:load";
+const POW_ADAPTIVE_FAILURE_KEY_PREFIX: &str = "pow:adaptive:failures";
+
+/// Issue-rate estimate derived from `POW_ADAPTIVE_ISSUED_KEY`; rewritten once per window.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PowLoadWindow {
+    window_start: u64,
+    /// Issued total when this window started.
+    baseline: u64,
+    /// Seeds per minute over the previous window.
+    last_rate: u64,
+}
+
+/// Fixed-window counter for per-bucket PoW failures.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PowWindowCounter {
+    window_start: u64,
+    count: u32,
+}
+
+/// Extra leading-zero bits a seed carries on top of the configured base difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct PowDifficultyFactors {
+    pub botness_bits: u8,
+    pub failure_bits: u8,
+    pub load_bits: u8,
+}
+
+impl PowDifficultyFactors {
+    /// One bit per two botness points, one per recent failure from the same IP bucket
+    /// (capped), and one or two when seeds are being issued above the load threshold.
+    pub(crate) fn from_inputs(
+        botness_score: u8,
+        recent_failures: u32,
+        issued_this_minute: u32,
+        load_threshold_per_minute: u32,
+    ) -> Self {
+        let load_bits = if load_threshold_per_minute == 0 {
+            0
+        } else if issued_this_minute >= load_threshold_per_minute.saturating_mul(4) {
+            2
+        } else if issued_this_minute >= load_threshold_per_minute {
+            1
+        } else {
+            0
+        };
+        Self {
+            botness_bits: botness_score / POW_ADAPTIVE_BOTNESS_POINTS_PER_BIT,
+            failure_bits: recent_failures.min(POW_ADAPTIVE_FAILURE_BITS_MAX as u32) as u8,
+            load_bits,
+        }
+    }
+
+    pub(crate) fn total(&self, max_extra_bits: u8) -> u8 {
+        self.botness_bits
+            .saturating_add(self.failure_bits)
+            .saturating_add(self.load_bits)
+            .min(max_extra_bits)
+    }
+}
+
+fn load_window_counter<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    key: &str,
+    now: u64,
+    window_seconds: u64,
+) -> PowWindowCounter {
+    store
+        .get(key)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<PowWindowCounter>(raw.as_slice()).ok())
+        .filter(|counter| now.saturating_sub(counter.window_start) < window_seconds)
+        .unwrap_or(PowWindowCounter {
+            window_start: now,
+            count: 0,
+        })
+}
+
+fn increment_window_counter<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    key: &str,
+    now: u64,
+    window_seconds: u64,
+) -> u32 {
+    let mut counter = load_window_counter(store, key, now, window_seconds);
+    counter.count = counter.count.saturating_add(1);
+    if let Ok(encoded) = serde_json::to_vec(&counter) {
+        if let Err(err) = store.set(key, encoded.as_slice()) {
+            eprintln!("[pow] failed to persist counter {}: {:?}", key, err);
+        }
+    }
+    counter.count
+}
+
+/// Seeds issued per minute: the larger of the last full window's rate and the count so
+/// far in this one. Reads the flushed issue total, so it trails by whatever is still
+/// buffered in running instances. KV is written only when a window rolls over.
+fn estimate_issue_rate<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, now: u64) -> u32 {
+    let total = crate::observability::metrics::get_counter(store, POW_ADAPTIVE_ISSUED_KEY);
+    let stored = store
+        .get(POW_ADAPTIVE_LOAD_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<PowLoadWindow>(raw.as_slice()).ok());
+    let window = match stored {
+        Some(window)
+            if now.saturating_sub(window.window_start) < POW_ADAPTIVE_LOAD_WINDOW_SECONDS
+                && total >= window.baseline =>
+        {
+            window
+        }
+        previous => {
+            let last_rate = previous
+                .filter(|window| total >= window.baseline)
+                .map(|window| {
+                    let elapsed = now.saturating_sub(window.window_start).max(1);
+                    (total - window.baseline).saturating_mul(POW_ADAPTIVE_LOAD_WINDOW_SECONDS)
+                        / elapsed
+                })
+                .unwrap_or(0);
+            let window = PowLoadWindow {
+                window_start: now,
+                baseline: total,
+                last_rate,
+            };
+            if let Ok(encoded) = serde_json::to_vec(&window) {
+                let _ = store.set(POW_ADAPTIVE_LOAD_KEY, encoded.as_slice());
+            }
+            window
+        }
+    };
+    let current = total.saturating_sub(window.baseline);
+    u32::try_from(window.last_rate.max(current)).unwrap_or(u32::MAX)
+}
+
+fn adaptive_failure_key(ip: &str) -> String {
+    format!(
+        "{}:{}",
+        POW_ADAPTIVE_FAILURE_KEY_PREFIX,
+        crate::signals::ip_identity::bucket_ip(ip)
+    )
+}
+
+/// Parameters for a seed about to be issued. With `pow_adaptive_enabled`, the base
+/// difficulty grows with the request's botness score, recent PoW failures from the same IP
+/// bucket and the estimated global issue rate. The result is signed into the seed, so
+/// verify holds the client to exactly what it was given.
+pub(crate) fn pow_params_for_issue<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    cfg: &crate::config::Config,
+    ip: &str,
+    botness_score: u8,
+) -> PowParams {
+    let base = PowParams::from_config(cfg);
+    if !cfg.pow_enabled || !cfg.pow_adaptive_enabled {
+        return base;
+    }
+    let now = now_ts();
+    let recent_failures = load_window_counter(
+        store,
+        adaptive_failure_key(ip).as_str(),
+        now,
+        POW_ADAPTIVE_FAILURE_WINDOW_SECONDS,
+    )
+    .count;
+    let issued_this_minute = estimate_issue_rate(store, now);
+    crate::observability::metrics::increment_counter(store, POW_ADAPTIVE_ISSUED_KEY, 1);
+    let factors = PowDifficultyFactors::from_inputs(
+        botness_score,
+        recent_failures,
+        issued_this_minute,
+        cfg.pow_adaptive_load_threshold_per_minute,
+    );
+    base.with_extra_bits(factors.total(cfg.pow_adaptive_max_extra_bits))
+}
+
+fn record_adaptive_failure<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, ip: &str) {
+    increment_window_counter(
+        store,
+        adaptive_failure_key(ip).as_str(),
+        now_ts(),
+        POW_ADAPTIVE_FAILURE_WINDOW_SECONDS,
+    );
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -147,19 +428,53 @@ fn has_leading_zero_bits(hash: &[u8], bits: u8) -> bool {
     true
 }
 
-fn verify_pow(seed_token: &str, nonce: &str, difficulty: u8) -> bool {
+fn sha256_pow_hash(seed_token: &str, nonce: &str) -> Vec<u8> {
     let mut hasher = Sha256::new();
     hasher.update(seed_token.as_bytes());
     hasher.update(b":");
     hasher.update(nonce.as_bytes());
-    let hash = hasher.finalize();
-    has_leading_zero_bits(&hash, difficulty)
+    hasher.finalize().to_vec()
+}
+
+/// scrypt(password = seed token, salt = nonce, N = memory_kib, r = 8, p = 1, 32 bytes).
+fn scrypt_pow_hash(seed_token: &str, nonce: &str, memory_kib: u32) -> Option<Vec<u8>> {
+    let log_n = memory_kib.trailing_zeros() as u8;
+    let params = scrypt::Params::new(
+        log_n,
+        SCRYPT_BLOCK_SIZE,
+        SCRYPT_PARALLELISM,
+        SCRYPT_OUTPUT_LEN,
+    )
+    .ok()?;
+    let mut output = vec![0u8; SCRYPT_OUTPUT_LEN];
+    scrypt::scrypt(
+        seed_token.as_bytes(),
+        nonce.as_bytes(),
+        &params,
+        &mut output,
+    )
+    .ok()?;
+    Some(output)
+}
+
+fn verify_pow(seed_token: &str, nonce: &str, params: PowParams) -> bool {
+    if !params.within_verification_bounds() {
+        return false;
+    }
+    match params.algorithm {
+        PowAlgorithm::Sha256 => {
+            has_leading_zero_bits(&sha256_pow_hash(seed_token, nonce), params.difficulty)
+        }
+        PowAlgorithm::Scrypt => scrypt_pow_hash(seed_token, nonce, params.memory_kib)
+            .map(|hash| has_leading_zero_bits(&hash, params.difficulty))
+            .unwrap_or(false),
+    }
 }
 
 pub fn issue_pow_challenge(
     ip: &str,
     user_agent: &str,
-    difficulty: u8,
+    params: PowParams,
     ttl_seconds: u64,
 ) -> PowChallenge {
     let now = now_ts();
@@ -178,13 +493,17 @@ pub fn issue_pow_challenge(
         issued_at: now,
         expires_at: now + ttl,
         token_version: crate::challenge::operation_envelope::TOKEN_VERSION_V1,
-        difficulty,
+        difficulty: params.difficulty,
+        algorithm: params.algorithm,
+        memory_kib: params.memory_kib,
     };
     let seed = make_seed_token(&payload);
     PowChallenge {
         seed,
-        difficulty,
+        difficulty: params.difficulty,
         expires_at: payload.expires_at,
+        algorithm: params.algorithm,
+        memory_kib: params.memory_kib,
     }
 }
 
@@ -192,13 +511,13 @@ pub fn handle_pow_challenge(
     ip: &str,
     user_agent: &str,
     pow_enabled: bool,
-    difficulty: u8,
+    params: PowParams,
     ttl_seconds: u64,
 ) -> Response {
     if !pow_enabled {
         return Response::new(404, "PoW disabled");
     }
-    let challenge = issue_pow_challenge(ip, user_agent, difficulty, ttl_seconds);
+    let challenge = issue_pow_challenge(ip, user_agent, params, ttl_seconds);
     let body = serde_json::to_string(&challenge).unwrap();
     Response::builder()
         .status(200)
@@ -279,6 +598,11 @@ pub fn handle_pow_verify(req: &Request, ip: &str, pow_enabled: bool) -> Response
         }
     };
 
+    if !payload.params().within_verification_bounds() {
+        record_pow_failure("sequence_violation", ip);
+        return Response::new(400, "Invalid seed");
+    }
+
     let now = now_ts();
     if now > payload.expires_at {
         record_sequence_policy_violation(
@@ -417,22 +741,39 @@ pub fn handle_pow_verify(req: &Request, ip: &str, pow_enabled: bool) -> Response
         }
     }
 
-    if !verify_pow(seed, nonce, payload.difficulty) {
+    // The hash runs last: every earlier check is cheap, and the replay check above has
+    // already consumed this operation, so each signed seed buys at most one evaluation.
+    if !verify_pow(seed, nonce, payload.params()) {
         record_pow_failure("invalid_proof", ip);
         return Response::new(400, "Invalid proof");
     }
 
-    record_pow_success();
+    record_pow_success(now.saturating_sub(payload.issued_at), payload.difficulty);
 
-    Response::builder()
-        .status(200)
-        .header(
+    let mut builder = Response::builder();
+    builder.status(200).header("Cache-Control", "no-store");
+    let user_agent = req
+        .header("user-agent")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    if crate::challenge::clearance::should_issue_level(
+        req,
+        ip,
+        user_agent,
+        crate::challenge::clearance::ClearanceLevel::VerifyJs,
+    ) {
+        builder.header(
             "Set-Cookie",
-            crate::signals::js_verification::js_verified_cookie(ip).as_str(),
-        )
-        .header("Cache-Control", "no-store")
-        .body("OK")
-        .build()
+            crate::challenge::clearance::issue_cookie(
+                payload.ip_bucket.as_str(),
+                payload.ua_bucket.as_str(),
+                crate::challenge::clearance::ClearanceLevel::VerifyJs,
+                crate::challenge::clearance::CLEARANCE_TTL_SECONDS,
+            )
+            .as_str(),
+        );
+    }
+    builder.body("OK").build()
 }
 
 static FALLBACK_POW_STATE_STORE: Lazy<Mutex<HashMap<String, Vec<u8>>>> =
@@ -483,21 +824,37 @@ fn record_sequence_policy_violation(
 fn record_pow_failure(reason: &str, ip: &str) {
     if let Some(store) = try_open_default_store() {
         crate::observability::monitoring::record_pow_failure(&store, ip, reason);
+        record_adaptive_failure(&store, ip);
     }
 }
 
-fn record_pow_success() {
+fn record_pow_success(solve_seconds: u64, difficulty: u8) {
     if let Some(store) = try_open_default_store() {
         crate::observability::monitoring::record_pow_success(&store);
+        crate::observability::metrics::observe(
+            &store,
+            crate::observability::metrics::HistogramName::PowSolveSeconds,
+            None,
+            solve_seconds as f64,
+        );
+        crate::observability::metrics::observe(
+            &store,
+            crate::observability::metrics::HistogramName::PowSolveSecondsByDifficulty,
+            Some(difficulty.to_string().as_str()),
+            solve_seconds as f64,
+        );
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        handle_pow_verify, issue_pow_challenge, make_seed_token, parse_seed_token, verify_pow,
-        PowPayload, FALLBACK_POW_STATE_STORE,
+        handle_pow_verify, issue_pow_challenge, make_seed_token, parse_seed_token,
+        pow_params_for_issue, record_adaptive_failure, verify_pow, PowDifficultyFactors, PowParams,
+        PowPayload, FALLBACK_POW_STATE_STORE, POW_ADAPTIVE_FAILURE_BITS_MAX,
+        POW_ADAPTIVE_ISSUED_KEY,
     };
+    use crate::config::PowAlgorithm;
     use spin_sdk::http::{Method, Request};
     use std::sync::MutexGuard;
 
@@ -544,17 +901,18 @@ mod tests {
         ttl_seconds: u64,
         adjust: impl FnOnce(&mut PowPayload),
     ) -> (String, PowPayload) {
-        let challenge = issue_pow_challenge(ip, user_agent, difficulty, ttl_seconds);
+        let challenge =
+            issue_pow_challenge(ip, user_agent, PowParams::sha256(difficulty), ttl_seconds);
         let mut payload = parse_seed_token(&challenge.seed).expect("issued seed should parse");
         adjust(&mut payload);
         let seed = make_seed_token(&payload);
         (seed, payload)
     }
 
-    fn find_valid_nonce(seed: &str, difficulty: u8) -> String {
+    fn find_valid_nonce(seed: &str, params: PowParams) -> String {
         for attempt in 0..200_000u64 {
             let nonce = format!("{:x}", attempt);
-            if verify_pow(seed, nonce.as_str(), difficulty) {
+            if verify_pow(seed, nonce.as_str(), params) {
                 return nonce;
             }
         }
@@ -564,7 +922,7 @@ mod tests {
     #[test]
     fn issued_pow_seed_contains_valid_operation_envelope() {
         let _lock = setup_pow_test_env();
-        let challenge = issue_pow_challenge("1.2.3.4", "Mozilla/5.0", 12, 60);
+        let challenge = issue_pow_challenge("1.2.3.4", "Mozilla/5.0", PowParams::sha256(12), 60);
         let payload = parse_seed_token(&challenge.seed).expect("issued seed should parse");
         assert_eq!(
             payload.flow_id,
@@ -602,6 +960,8 @@ mod tests {
             expires_at: now + 60,
             token_version: crate::challenge::operation_envelope::TOKEN_VERSION_V1,
             difficulty: 12,
+            algorithm: PowAlgorithm::Sha256,
+            memory_kib: 0,
         };
         let seed = make_seed_token(&payload);
         assert!(parse_seed_token(seed.as_str()).is_err());
@@ -610,13 +970,14 @@ mod tests {
     #[test]
     fn pow_verify_rejects_operation_replay() {
         let _lock = setup_pow_test_env();
-        let challenge = issue_pow_challenge("198.51.100.10", "ReplayUA/1.0", 12, 120);
+        let challenge =
+            issue_pow_challenge("198.51.100.10", "ReplayUA/1.0", PowParams::sha256(12), 120);
         let mut payload = parse_seed_token(&challenge.seed).expect("seed should parse");
         let now = crate::admin::now_ts();
         payload.issued_at = now.saturating_sub(2);
         payload.expires_at = now + 120;
         let seed = make_seed_token(&payload);
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "ReplayUA/1.0");
 
         let first = handle_pow_verify(&req, "198.51.100.10", true);
@@ -630,7 +991,8 @@ mod tests {
     #[test]
     fn pow_verify_rejects_too_fast_submission() {
         let _lock = setup_pow_test_env();
-        let challenge = issue_pow_challenge("198.51.100.11", "FastUA/1.0", 12, 120);
+        let challenge =
+            issue_pow_challenge("198.51.100.11", "FastUA/1.0", PowParams::sha256(12), 120);
         let mut payload = parse_seed_token(&challenge.seed).expect("seed should parse");
         let now = crate::admin::now_ts();
         // Keep issued_at in the near future so the timing check is deterministic
@@ -638,7 +1000,7 @@ mod tests {
         payload.issued_at = now.saturating_add(2);
         payload.expires_at = now + 120;
         let seed = make_seed_token(&payload);
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "FastUA/1.0");
 
         let resp = handle_pow_verify(&req, "198.51.100.11", true);
@@ -656,25 +1018,25 @@ mod tests {
         let ua = "RegularUA/1.0";
 
         for _ in 0..3 {
-            let challenge = issue_pow_challenge(ip, ua, 12, 120);
+            let challenge = issue_pow_challenge(ip, ua, PowParams::sha256(12), 120);
             let mut payload = parse_seed_token(&challenge.seed).expect("seed should parse");
             let now = crate::admin::now_ts();
             payload.issued_at = now.saturating_sub(2);
             payload.expires_at = now + 120;
             let seed = make_seed_token(&payload);
-            let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+            let nonce = find_valid_nonce(seed.as_str(), payload.params());
             let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), ua);
             let resp = handle_pow_verify(&req, ip, true);
             assert_eq!(*resp.status(), 200u16);
         }
 
-        let challenge = issue_pow_challenge(ip, ua, 12, 120);
+        let challenge = issue_pow_challenge(ip, ua, PowParams::sha256(12), 120);
         let mut payload = parse_seed_token(&challenge.seed).expect("seed should parse");
         let now = crate::admin::now_ts();
         payload.issued_at = now.saturating_sub(2);
         payload.expires_at = now + 120;
         let seed = make_seed_token(&payload);
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), ua);
         let resp = handle_pow_verify(&req, ip, true);
         assert_eq!(*resp.status(), 400u16);
@@ -727,7 +1089,12 @@ mod tests {
     #[test]
     fn pow_verify_requires_nonce_field() {
         let _lock = setup_pow_test_env();
-        let challenge = issue_pow_challenge("198.51.100.25", "MissingNonceUA/1.0", 8, 120);
+        let challenge = issue_pow_challenge(
+            "198.51.100.25",
+            "MissingNonceUA/1.0",
+            PowParams::sha256(8),
+            120,
+        );
         let payload = serde_json::json!({ "seed": challenge.seed });
         let req = make_pow_verify_request_raw(
             serde_json::to_string(&payload)
@@ -755,7 +1122,12 @@ mod tests {
     #[test]
     fn pow_verify_rejects_invalid_nonce_format() {
         let _lock = setup_pow_test_env();
-        let challenge = issue_pow_challenge("198.51.100.27", "InvalidNonceUA/1.0", 8, 120);
+        let challenge = issue_pow_challenge(
+            "198.51.100.27",
+            "InvalidNonceUA/1.0",
+            PowParams::sha256(8),
+            120,
+        );
         let payload = serde_json::json!({ "seed": challenge.seed, "nonce": "bad nonce" });
         let req = make_pow_verify_request_raw(
             serde_json::to_string(&payload)
@@ -783,7 +1155,7 @@ mod tests {
                 p.expires_at = now + 120;
             },
         );
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "MissingOpUA/1.0");
         let resp = handle_pow_verify(&req, "198.51.100.28", true);
         assert_eq!(*resp.status(), 400u16);
@@ -805,7 +1177,7 @@ mod tests {
                 p.expires_at = now + 120;
             },
         );
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "BadEnvelopeUA/1.0");
         let resp = handle_pow_verify(&req, "198.51.100.29", true);
         assert_eq!(*resp.status(), 400u16);
@@ -815,7 +1187,8 @@ mod tests {
     #[test]
     fn pow_verify_rejects_tampered_signature_seed() {
         let _lock = setup_pow_test_env();
-        let challenge = issue_pow_challenge("198.51.100.30", "TamperUA/1.0", 8, 120);
+        let challenge =
+            issue_pow_challenge("198.51.100.30", "TamperUA/1.0", PowParams::sha256(8), 120);
         let mut tampered = challenge.seed;
         if let Some(last) = tampered.pop() {
             tampered.push(if last == 'A' { 'B' } else { 'A' });
@@ -841,7 +1214,7 @@ mod tests {
                 p.expires_at = now + 120;
             },
         );
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "StepOrderUA/1.0");
         let resp = handle_pow_verify(&req, "198.51.100.31", true);
         assert_eq!(*resp.status(), 400u16);
@@ -864,7 +1237,7 @@ mod tests {
                 p.expires_at = now + 120;
             },
         );
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "WindowUA/1.0");
         let resp = handle_pow_verify(&req, "198.51.100.32", true);
         assert_eq!(*resp.status(), 400u16);
@@ -885,7 +1258,7 @@ mod tests {
                 p.expires_at = now + 120;
             },
         );
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "DifferentUA/1.0");
         let resp = handle_pow_verify(&req, "198.51.100.33", true);
         assert_eq!(*resp.status(), 400u16);
@@ -902,7 +1275,7 @@ mod tests {
                 p.expires_at = now + 120;
             });
         let mut bad_nonce = String::from("0");
-        while verify_pow(seed.as_str(), bad_nonce.as_str(), payload.difficulty) {
+        while verify_pow(seed.as_str(), bad_nonce.as_str(), payload.params()) {
             bad_nonce.push('1');
         }
         let req = make_pow_verify_request(seed.as_str(), bad_nonce.as_str(), "InvalidProofUA/1.0");
@@ -912,7 +1285,7 @@ mod tests {
     }
 
     #[test]
-    fn pow_verify_success_sets_js_verified_cookie() {
+    fn pow_verify_success_sets_clearance_cookie() {
         let _lock = setup_pow_test_env();
         let now = crate::admin::now_ts();
         let (seed, payload) = issue_adjusted_pow_seed(
@@ -925,7 +1298,7 @@ mod tests {
                 p.expires_at = now + 120;
             },
         );
-        let nonce = find_valid_nonce(seed.as_str(), payload.difficulty);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
         let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "SuccessUA/1.0");
         let resp = handle_pow_verify(&req, "198.51.100.35", true);
         assert_eq!(*resp.status(), 200u16);
@@ -934,6 +1307,310 @@ mod tests {
             .find(|(k, _)| k.eq_ignore_ascii_case("set-cookie"))
             .and_then(|(_, v)| v.as_str())
             .unwrap_or("");
-        assert!(set_cookie.contains("js_verified="));
+        assert!(set_cookie.contains("shuma_clearance="));
+        assert!(set_cookie.contains("HttpOnly"));
+    }
+
+    #[test]
+    fn pow_verify_success_keeps_a_stronger_clearance_cookie() {
+        let _lock = setup_pow_test_env();
+        let ip = "198.51.100.37";
+        let user_agent = "StrongUA/1.0";
+        let now = crate::admin::now_ts();
+        let (seed, payload) = issue_adjusted_pow_seed(ip, user_agent, 8, 120, |p| {
+            p.issued_at = now.saturating_sub(2);
+            p.expires_at = now + 120;
+        });
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
+        let strong = crate::challenge::clearance::issue_token(
+            payload.ip_bucket.as_str(),
+            payload.ua_bucket.as_str(),
+            crate::challenge::clearance::ClearanceLevel::ChallengeStrong,
+            600,
+        );
+        let mut req = make_pow_verify_request(seed.as_str(), nonce.as_str(), user_agent);
+        req.set_header(
+            "cookie",
+            format!(
+                "{}={}",
+                crate::challenge::clearance::CLEARANCE_COOKIE_NAME,
+                strong
+            ),
+        );
+
+        let resp = handle_pow_verify(&req, ip, true);
+        assert_eq!(*resp.status(), 200u16);
+        assert!(resp.header("set-cookie").is_none());
+    }
+
+    #[test]
+    fn scrypt_pow_seed_round_trips_through_verify() {
+        let _lock = setup_pow_test_env();
+        let now = crate::admin::now_ts();
+        let challenge = issue_pow_challenge(
+            "198.51.100.36",
+            "ScryptUA/1.0",
+            PowParams::scrypt(2, crate::config::POW_SCRYPT_MEMORY_KIB_MIN),
+            120,
+        );
+        assert_eq!(challenge.algorithm, PowAlgorithm::Scrypt);
+        assert_eq!(
+            challenge.memory_kib,
+            crate::config::POW_SCRYPT_MEMORY_KIB_MIN
+        );
+        let mut payload = parse_seed_token(&challenge.seed).expect("seed should parse");
+        assert_eq!(payload.params(), PowParams::scrypt(2, 1024));
+        payload.issued_at = now.saturating_sub(2);
+        payload.expires_at = now + 120;
+        let seed = make_seed_token(&payload);
+        let nonce = find_valid_nonce(seed.as_str(), payload.params());
+        let req = make_pow_verify_request(seed.as_str(), nonce.as_str(), "ScryptUA/1.0");
+        let resp = handle_pow_verify(&req, "198.51.100.36", true);
+        assert_eq!(*resp.status(), 200u16);
+    }
+
+    #[test]
+    fn legacy_seed_without_algorithm_defaults_to_sha256() {
+        let _lock = setup_pow_test_env();
+        let challenge =
+            issue_pow_challenge("198.51.100.37", "LegacyUA/1.0", PowParams::sha256(12), 120);
+        let payload = parse_seed_token(&challenge.seed).expect("seed should parse");
+        let mut json = serde_json::to_value(&payload).expect("payload should serialize");
+        let object = json.as_object_mut().expect("payload should be an object");
+        object.remove("algorithm");
+        object.remove("memory_kib");
+        let legacy: PowPayload = serde_json::from_value(json).expect("legacy payload parses");
+        assert_eq!(legacy.params(), PowParams::sha256(12));
+    }
+
+    #[test]
+    fn pow_verify_rejects_scrypt_seed_above_memory_cap_without_hashing() {
+        let _lock = setup_pow_test_env();
+        let now = crate::admin::now_ts();
+        let (seed, payload) = issue_adjusted_pow_seed(
+            "198.51.100.38",
+            "CapUA/1.0",
+            8,
+            120,
+            |p| {
+                p.issued_at = now.saturating_sub(2);
+                p.expires_at = now + 120;
+                p.algorithm = PowAlgorithm::Scrypt;
+                p.difficulty = 1;
+                p.memory_kib = crate::config::POW_SCRYPT_MEMORY_KIB_MAX * 64;
+            },
+        );
+        assert!(!payload.params().within_verification_bounds());
+        assert!(!verify_pow(seed.as_str(), "0", payload.params()));
+        let req = make_pow_verify_request(seed.as_str(), "0", "CapUA/1.0");
+        let resp = handle_pow_verify(&req, "198.51.100.38", true);
+        assert_eq!(*resp.status(), 400u16);
+        assert_eq!(String::from_utf8_lossy(resp.body()), "Invalid seed");
+    }
+
+    #[test]
+    fn scrypt_params_outside_supported_shape_are_out_of_bounds() {
+        assert!(PowParams::scrypt(4, 4096).within_verification_bounds());
+        assert!(!PowParams::scrypt(4, 3000).within_verification_bounds());
+        assert!(!PowParams::scrypt(4, 512).within_verification_bounds());
+        assert!(
+            !PowParams::scrypt(crate::config::POW_SCRYPT_DIFFICULTY_MAX + 1, 4096)
+                .within_verification_bounds()
+        );
+        assert!(
+            !PowParams::sha256(crate::config::POW_DIFFICULTY_MAX + 1).within_verification_bounds()
+        );
+    }
+
+    /// Mean wall time of one proof attempt at `params`, measured natively.
+    fn mean_attempt_seconds(params: PowParams, samples: u32) -> f64 {
+        let started = std::time::Instant::now();
+        for attempt in 0..samples {
+            verify_pow(
+                "calibration-seed",
+                format!("{:x}", attempt).as_str(),
+                params,
+            );
+        }
+        started.elapsed().as_secs_f64() / samples as f64
+    }
+
+    /// A valid proof needs 2^difficulty attempts on average.
+    fn expected_solve_seconds(attempt_seconds: f64, difficulty: u8) -> f64 {
+        attempt_seconds * f64::from(1u32 << difficulty)
+    }
+
+    #[test]
+    fn expected_solve_time_doubles_per_difficulty_bit() {
+        let attempt = mean_attempt_seconds(
+            PowParams::scrypt(
+                crate::config::POW_SCRYPT_DIFFICULTY_MIN,
+                crate::config::POW_SCRYPT_MEMORY_KIB_MIN,
+            ),
+            2,
+        );
+        assert!(attempt > 0.0);
+        for difficulty in
+            crate::config::POW_SCRYPT_DIFFICULTY_MIN..crate::config::POW_SCRYPT_DIFFICULTY_MAX
+        {
+            assert_eq!(
+                expected_solve_seconds(attempt, difficulty + 1),
+                2.0 * expected_solve_seconds(attempt, difficulty)
+            );
+        }
+    }
+
+    // Prints the table operators use to pick `pow_scrypt_memory_kib` / `pow_scrypt_difficulty`.
+    // Timings are native; the browser worker is typically several times slower per attempt.
+    #[test]
+    #[ignore = "calibration benchmark; run with --release -- --ignored --nocapture"]
+    fn scrypt_pow_calibration_benchmark() {
+        let mut memory_kib = crate::config::POW_SCRYPT_MEMORY_KIB_MIN;
+        while memory_kib <= crate::config::POW_SCRYPT_MEMORY_KIB_MAX {
+            let attempt = mean_attempt_seconds(PowParams::scrypt(0, memory_kib), 8);
+            println!(
+                "scrypt memory_kib={} attempt={:.2}ms (server verify cost)",
+                memory_kib,
+                attempt * 1000.0
+            );
+            for difficulty in
+                crate::config::POW_SCRYPT_DIFFICULTY_MIN..=crate::config::POW_SCRYPT_DIFFICULTY_MAX
+            {
+                println!(
+                    "  difficulty={:>2} expected_attempts={:>5} expected_solve={:.2}s",
+                    difficulty,
+                    1u32 << difficulty,
+                    expected_solve_seconds(attempt, difficulty)
+                );
+            }
+            memory_kib *= 2;
+        }
+        let attempt = mean_attempt_seconds(PowParams::sha256(0), 20_000);
+        println!("sha256 attempt={:.4}ms", attempt * 1000.0);
+        for difficulty in crate::config::POW_DIFFICULTY_MIN..=crate::config::POW_DIFFICULTY_MAX {
+            println!(
+                "  difficulty={:>2} expected_attempts={:>7} expected_solve={:.2}s",
+                difficulty,
+                1u32 << difficulty,
+                expected_solve_seconds(attempt, difficulty)
+            );
+        }
+    }
+
+    #[test]
+    fn adaptive_factors_scale_with_botness_failures_and_load() {
+        assert_eq!(
+            PowDifficultyFactors::from_inputs(0, 0, 1, 600),
+            PowDifficultyFactors::default()
+        );
+        let factors = PowDifficultyFactors::from_inputs(5, 7, 600, 600);
+        assert_eq!(factors.botness_bits, 2);
+        assert_eq!(factors.failure_bits, POW_ADAPTIVE_FAILURE_BITS_MAX);
+        assert_eq!(factors.load_bits, 1);
+        assert_eq!(factors.total(8), 6);
+        assert_eq!(factors.total(4), 4);
+        let heavy_load = PowDifficultyFactors::from_inputs(0, 0, 2400, 600);
+        assert_eq!(heavy_load.load_bits, 2);
+        let load_disabled = PowDifficultyFactors::from_inputs(0, 0, 9999, 0);
+        assert_eq!(load_disabled.load_bits, 0);
+    }
+
+    #[test]
+    fn adaptive_extra_bits_stay_within_algorithm_range() {
+        assert_eq!(PowParams::sha256(18).with_extra_bits(8).difficulty, 20);
+        assert_eq!(
+            PowParams::scrypt(4, 4096).with_extra_bits(3),
+            PowParams::scrypt(7, 4096)
+        );
+        assert_eq!(PowParams::scrypt(9, 4096).with_extra_bits(4).difficulty, 10);
+    }
+
+    #[test]
+    fn pow_params_for_issue_adds_bits_for_botness_and_recent_failures() {
+        let _lock = crate::test_support::lock_env();
+        let store = crate::test_support::InMemoryStore::default();
+        let mut cfg = crate::config::defaults().clone();
+        cfg.pow_enabled = true;
+        cfg.pow_difficulty = 12;
+        cfg.pow_adaptive_enabled = true;
+        cfg.pow_adaptive_max_extra_bits = 4;
+        cfg.pow_adaptive_load_threshold_per_minute = 0;
+
+        let difficulty = |cfg: &crate::config::Config, ip: &str, botness: u8| {
+            pow_params_for_issue(&store, cfg, ip, botness).difficulty
+        };
+        assert_eq!(difficulty(&cfg, "198.51.100.40", 0), 12);
+        assert_eq!(difficulty(&cfg, "198.51.100.40", 4), 14);
+
+        record_adaptive_failure(&store, "198.51.100.40");
+        assert_eq!(difficulty(&cfg, "198.51.100.41", 4), 15);
+        assert_eq!(difficulty(&cfg, "203.0.113.9", 4), 14);
+        assert_eq!(difficulty(&cfg, "198.51.100.40", 10), 16);
+
+        cfg.pow_adaptive_enabled = false;
+        assert_eq!(difficulty(&cfg, "198.51.100.40", 10), 12);
+    }
+
+    #[test]
+    fn pow_params_for_issue_adds_bits_under_global_load() {
+        let _lock = crate::test_support::lock_env();
+        let store = crate::test_support::InMemoryStore::default();
+        // Drop issues other tests left in the shared metrics buffer.
+        crate::observability::metrics::flush_counter(
+            &crate::test_support::InMemoryStore::default(),
+            POW_ADAPTIVE_ISSUED_KEY,
+        );
+        let mut cfg = crate::config::defaults().clone();
+        cfg.pow_enabled = true;
+        cfg.pow_difficulty = 12;
+        cfg.pow_adaptive_enabled = true;
+        cfg.pow_adaptive_max_extra_bits = 8;
+        cfg.pow_adaptive_load_threshold_per_minute = 2;
+
+        // Each issue is flushed as the entrypoint would after its response.
+        let issue = |at: u64| {
+            crate::runtime::clock::with_clock(&crate::runtime::clock::FixedClock(at), || {
+                let difficulty = pow_params_for_issue(&store, &cfg, "198.51.100.42", 0).difficulty;
+                crate::observability::metrics::flush_counter(&store, POW_ADAPTIVE_ISSUED_KEY);
+                difficulty
+            })
+        };
+        let start = 1_700_000_000;
+        let issued: Vec<u8> = (0..8).map(|_| issue(start)).collect();
+        assert_eq!(issued, vec![12, 12, 13, 13, 13, 13, 13, 13]);
+        assert_eq!(issue(start + 1), 14);
+
+        // The next window starts from the previous window's rate, then decays with it.
+        assert_eq!(issue(start + 60), 14);
+        assert_eq!(issue(start + 120), 12);
+    }
+
+    #[test]
+    fn pow_verify_enforces_adaptive_difficulty_signed_into_seed() {
+        let _lock = setup_pow_test_env();
+        let now = crate::admin::now_ts();
+        let (seed, payload) = issue_adjusted_pow_seed(
+            "198.51.100.43",
+            "AdaptiveUA/1.0",
+            PowParams::sha256(8).with_extra_bits(4).difficulty,
+            120,
+            |p| {
+                p.issued_at = now.saturating_sub(2);
+                p.expires_at = now + 120;
+            },
+        );
+        assert_eq!(payload.difficulty, 12);
+        let base_only_nonce = (0..200_000u64)
+            .map(|attempt| format!("{:x}", attempt))
+            .find(|nonce| {
+                verify_pow(seed.as_str(), nonce, PowParams::sha256(8))
+                    && !verify_pow(seed.as_str(), nonce, payload.params())
+            })
+            .expect("a nonce solving only the base difficulty should exist");
+        let req =
+            make_pow_verify_request(seed.as_str(), base_only_nonce.as_str(), "AdaptiveUA/1.0");
+        let resp = handle_pow_verify(&req, "198.51.100.43", true);
+        assert_eq!(*resp.status(), 400u16);
+        assert_eq!(String::from_utf8_lossy(resp.body()), "Invalid proof");
     }
 }
@@ -0,0 +1,393 @@
+use rand::{Rng, SeedableRng};
+use spin_sdk::http::{Request, Response};
+
+use super::super::{challenge_response, KeyValueStore, PUZZLE_ACCESSIBLE_PATH};
+use super::submit::{
+    challenge_expired_response, challenge_forbidden_response, challenge_incorrect_response,
+    challenge_solved_response, validate_submission, ChallengeSubmitOutcome,
+};
+use super::token::{make_seed_token, parse_seed_token};
+use super::types::ChallengeVariant;
+use super::ChallengeSeed;
+use crate::i18n::Messages;
+
+const QUESTION_KIND_COUNT: u32 = 11;
+/// Text answers are machine-solvable, so their clearance is a weaker level, short-lived and
+/// capped per IP bucket.
+pub(crate) const ACCESSIBLE_CLEARANCE_TTL_SECONDS: u64 = 3_600;
+pub(crate) const ACCESSIBLE_SOLVES_PER_WINDOW: u32 = 3;
+const ACCESSIBLE_SOLVE_WINDOW_SECONDS: u64 = 3_600;
+const ACCESSIBLE_SOLVES_KEY_PREFIX: &str = "challenge_accessible_solves:";
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+const WORD_BANK: [&str; 48] = [
+    "garden", "window", "pencil", "orange", "planet", "silver", "bridge", "candle", "forest",
+    "rocket", "market", "button", "harbor", "lemon", "tiger", "meadow", "anchor", "basket",
+    "castle", "dragon", "engine", "falcon", "guitar", "hammer", "island", "jacket", "kettle",
+    "ladder", "magnet", "needle", "oyster", "parrot", "quartz", "rabbit", "saddle", "tunnel",
+    "velvet", "walnut", "yogurt", "zipper", "blanket", "compass", "feather", "lantern", "mirror",
+    "pepper", "thunder", "violin",
+];
+const NUMBER_WORDS: [&str; 21] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+];
+
+/// A text question as a catalog key plus its arguments, so the same seed asks the same
+/// question in every locale. Weekday arguments are themselves catalog keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AccessibleQuestion {
+    pub prompt_key: &'static str,
+    pub args: Vec<(&'static str, String)>,
+    pub answer: String,
+}
+
+impl AccessibleQuestion {
+    pub(crate) fn prompt(&self, messages: &Messages) -> String {
+        let args: Vec<(&str, String)> = self
+            .args
+            .iter()
+            .map(|(name, value)| {
+                if value.starts_with("weekday.") {
+                    (*name, messages.raw(value))
+                } else {
+                    (*name, value.clone())
+                }
+            })
+            .collect();
+        let borrowed: Vec<(&str, &str)> = args
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect();
+        messages.format(self.prompt_key, &borrowed)
+    }
+}
+
+/// Derives the text question for a seed. The bank rotates per seed, and the answer is
+/// rebuilt server-side on submit so nothing about it is carried in the page.
+pub(crate) fn build_question(seed: &ChallengeSeed) -> AccessibleQuestion {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed.seed);
+    let (prompt_key, args, answer) = match rng.random_range(0..QUESTION_KIND_COUNT) {
+        0 => {
+            let a = rng.random_range(2..=9u32);
+            let b = rng.random_range(2..=9u32);
+            (
+                "accessible.question.add",
+                vec![("a", a.to_string()), ("b", b.to_string())],
+                (a + b).to_string(),
+            )
+        }
+        1 => {
+            let a = rng.random_range(11..=19u32);
+            let b = rng.random_range(2..=9u32);
+            (
+                "accessible.question.subtract",
+                vec![("a", a.to_string()), ("b", b.to_string())],
+                (a - b).to_string(),
+            )
+        }
+        2 => {
+            let values = [
+                rng.random_range(10..=99u32),
+                rng.random_range(10..=99u32),
+                rng.random_range(10..=99u32),
+            ];
+            let largest = values.iter().copied().max().unwrap_or_default();
+            (
+                "accessible.question.largest",
+                vec![
+                    ("a", values[0].to_string()),
+                    ("b", values[1].to_string()),
+                    ("c", values[2].to_string()),
+                ],
+                largest.to_string(),
+            )
+        }
+        3 => {
+            let word = WORD_BANK[rng.random_range(0..WORD_BANK.len())];
+            (
+                "accessible.question.letters",
+                vec![("word", word.to_string())],
+                word.len().to_string(),
+            )
+        }
+        4 => {
+            let idx = rng.random_range(0..WEEKDAYS.len());
+            (
+                "accessible.question.next_day",
+                vec![("day", format!("weekday.{}", WEEKDAYS[idx]))],
+                WEEKDAYS[(idx + 1) % WEEKDAYS.len()].to_string(),
+            )
+        }
+        5 => {
+            let a = rng.random_range(2..=9u32);
+            let b = rng.random_range(2..=9u32);
+            (
+                "accessible.question.multiply",
+                vec![("a", a.to_string()), ("b", b.to_string())],
+                (a * b).to_string(),
+            )
+        }
+        6 => {
+            let values = [
+                rng.random_range(10..=99u32),
+                rng.random_range(10..=99u32),
+                rng.random_range(10..=99u32),
+            ];
+            let smallest = values.iter().copied().min().unwrap_or_default();
+            (
+                "accessible.question.smallest",
+                vec![
+                    ("a", values[0].to_string()),
+                    ("b", values[1].to_string()),
+                    ("c", values[2].to_string()),
+                ],
+                smallest.to_string(),
+            )
+        }
+        7 => {
+            let idx = rng.random_range(0..WEEKDAYS.len());
+            (
+                "accessible.question.previous_day",
+                vec![("day", format!("weekday.{}", WEEKDAYS[idx]))],
+                WEEKDAYS[(idx + WEEKDAYS.len() - 1) % WEEKDAYS.len()].to_string(),
+            )
+        }
+        8 => {
+            let word = WORD_BANK[rng.random_range(0..WORD_BANK.len())];
+            (
+                "accessible.question.first_letter",
+                vec![("word", word.to_string())],
+                word[..1].to_string(),
+            )
+        }
+        9 => {
+            let word = WORD_BANK[rng.random_range(0..WORD_BANK.len())];
+            (
+                "accessible.question.last_letter",
+                vec![("word", word.to_string())],
+                word[word.len() - 1..].to_string(),
+            )
+        }
+        _ => {
+            let word = WORD_BANK[rng.random_range(0..WORD_BANK.len())];
+            (
+                "accessible.question.backwards",
+                vec![("word", word.to_string())],
+                word.chars().rev().collect(),
+            )
+        }
+    };
+    AccessibleQuestion {
+        prompt_key,
+        args,
+        answer,
+    }
+}
+
+fn normalize_answer(raw: &str) -> String {
+    // Form encoding turns spaces into `+`, and no answer in the bank contains one.
+    let spaced = raw.replace('+', " ");
+    let collapsed = spaced
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['.', '。'])
+        .to_lowercase();
+    match NUMBER_WORDS.iter().position(|word| *word == collapsed) {
+        Some(value) => value.to_string(),
+        None => collapsed,
+    }
+}
+
+/// Compares case-insensitively, ignoring surrounding whitespace and a trailing full stop.
+/// Small numbers may be typed as words, and weekday answers are accepted in English or in
+/// the visitor's locale.
+pub(crate) fn answer_matches(
+    question: &AccessibleQuestion,
+    raw: &str,
+    messages: &Messages,
+) -> bool {
+    let answer = normalize_answer(raw);
+    if answer == question.answer {
+        return true;
+    }
+    WEEKDAYS.contains(&question.answer.as_str())
+        && answer == normalize_answer(&messages.raw(&format!("weekday.{}", question.answer)))
+}
+
+/// Swaps a grid seed for the text variant of the same operation. The operation id, issue
+/// time and bindings carry over, so switching neither extends the window nor adds a
+/// second attempt.
+pub(crate) fn serve_accessible_challenge_page(
+    req: &Request,
+    cfg: &crate::config::Config,
+) -> Response {
+    let messages = Messages::for_request(req, cfg);
+    let seed_token = match crate::request_validation::query_param(req.query(), "seed") {
+        Some(token) if crate::request_validation::validate_seed_token(token.as_str()) => token,
+        _ => return challenge_forbidden_response(&messages),
+    };
+    let mut seed = match parse_seed_token(&seed_token) {
+        Ok(seed) => seed,
+        Err(_) => return challenge_forbidden_response(&messages),
+    };
+    if crate::admin::now_ts() > seed.expires_at {
+        return challenge_expired_response(&messages);
+    }
+    let ip = crate::extract_client_ip(req);
+    let ua = req
+        .header("user-agent")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    if crate::challenge::operation_envelope::validate_request_binding(
+        seed.ip_bucket.as_str(),
+        seed.ua_bucket.as_str(),
+        seed.path_class.as_str(),
+        ip.as_str(),
+        ua,
+        crate::challenge::operation_envelope::PATH_CLASS_CHALLENGE_PUZZLE_SUBMIT,
+    )
+    .is_err()
+    {
+        return challenge_forbidden_response(&messages);
+    }
+    seed.variant = ChallengeVariant::Text;
+    render_accessible_challenge(&seed, &messages)
+}
+
+fn render_accessible_challenge(seed: &ChallengeSeed, messages: &Messages) -> Response {
+    let question = build_question(seed);
+    let seed_token = make_seed_token(seed);
+    let html = format!(
+        r#"
+        <html lang="{lang}" dir="{dir}">
+        <head>
+          <meta charset="UTF-8" />
+          <meta name="viewport" content="width=device-width, initial-scale=1" />
+          <title>{title}</title>
+          <style>
+            body {{ font-family: sans-serif; font-size: 18px; line-height: 1.6; margin: 24px; color: #111; background: #fff; }}
+            .challenge {{ max-width: 520px; margin: 0 auto; padding: 24px; border: 1px solid #e2e8f0; }}
+            label {{ display: block; font-size: 1.2rem; margin-bottom: 12px; }}
+            input[type="text"] {{ width: 100%; box-sizing: border-box; padding: 10px; font-size: 1.1rem; border: 2px solid #111; }}
+            button {{ margin-top: 16px; width: 100%; padding: 10px 14px; font-size: 1rem; background: #111; color: #fff; border: 1px solid #111; }}
+          </style>
+        </head>
+        <body>
+          <main class="challenge">
+            <h1>{title}</h1>
+            <form method="POST" action="{accessible_path}">
+              <input type="hidden" name="seed" value="{seed_token}" />
+              <label for="challenge-answer">{prompt}</label>
+              <input type="text" id="challenge-answer" name="answer" autocomplete="off" autocapitalize="off" spellcheck="false" maxlength="64" required autofocus />
+              <button type="submit">{submit}</button>
+            </form>
+          </main>
+        </body>
+        </html>
+    "#,
+        accessible_path = PUZZLE_ACCESSIBLE_PATH,
+        seed_token = seed_token,
+        prompt = question.prompt(messages),
+        lang = messages.locale(),
+        dir = messages.dir(),
+        title = messages.text("accessible.title"),
+        submit = messages.text("accessible.submit"),
+    );
+    challenge_response(200, &html)
+}
+
+/// One key per IP bucket holding `<window>:<count>`; a new window overwrites the old one.
+pub(crate) fn accessible_solves_key(ip_bucket: &str) -> String {
+    format!("{}{}", ACCESSIBLE_SOLVES_KEY_PREFIX, ip_bucket)
+}
+
+fn accessible_solves_in_window<S: KeyValueStore>(store: &S, key: &str, window: u64) -> u32 {
+    store
+        .get(key)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| {
+            let (stored_window, count) = raw.split_once(':')?;
+            (stored_window.parse::<u64>().ok()? == window)
+                .then(|| count.parse::<u32>().ok())
+                .flatten()
+        })
+        .unwrap_or(0)
+}
+
+pub(crate) fn handle_accessible_submit_with_outcome<S: KeyValueStore>(
+    store: &S,
+    req: &Request,
+    cfg: &crate::config::Config,
+) -> (Response, ChallengeSubmitOutcome) {
+    let messages = Messages::for_request(req, cfg);
+    let (seed, answer_raw, now) =
+        match validate_submission(store, req, "answer", ChallengeVariant::Text, &messages) {
+            Ok(validated) => validated,
+            Err(rejection) => return rejection,
+        };
+    let solves_key = accessible_solves_key(seed.ip_bucket.as_str());
+    let window = now / ACCESSIBLE_SOLVE_WINDOW_SECONDS;
+    let solves = accessible_solves_in_window(store, solves_key.as_str(), window);
+    if solves >= ACCESSIBLE_SOLVES_PER_WINDOW {
+        return (
+            challenge_forbidden_response(&messages),
+            ChallengeSubmitOutcome::RateLimited,
+        );
+    }
+    let question = build_question(&seed);
+    if answer_matches(&question, &answer_raw, &messages) {
+        let _ = store.set(
+            solves_key.as_str(),
+            format!("{}:{}", window, solves + 1).as_bytes(),
+        );
+        crate::observability::metrics::observe(
+            store,
+            crate::observability::metrics::HistogramName::ChallengeSolveSeconds,
+            Some("puzzle_accessible"),
+            now.saturating_sub(seed.issued_at) as f64,
+        );
+        return (
+            challenge_solved_response(
+                &seed,
+                &messages,
+                crate::challenge::clearance::ClearanceLevel::ChallengeAccessible,
+                ACCESSIBLE_CLEARANCE_TTL_SECONDS,
+            ),
+            ChallengeSubmitOutcome::Solved,
+        );
+    }
+    (
+        challenge_incorrect_response(&messages),
+        ChallengeSubmitOutcome::Incorrect,
+    )
+}
@@ -1,3 +1,4 @@
+mod accessible;
 mod renders;
 mod submit;
 mod token;
@@ -8,6 +9,12 @@ use rand::{Rng, SeedableRng};
 
 use types::ChallengePuzzle;
 
+#[cfg(test)]
+pub(crate) use accessible::{
+    accessible_solves_key, answer_matches, build_question, AccessibleQuestion,
+    ACCESSIBLE_CLEARANCE_TTL_SECONDS, ACCESSIBLE_SOLVES_PER_WINDOW,
+};
+pub(crate) use accessible::{handle_accessible_submit_with_outcome, serve_accessible_challenge_page};
 pub(crate) use renders::render_challenge;
 #[cfg(test)]
 pub use submit::handle_challenge_submit;
@@ -16,6 +23,8 @@ pub(crate) use submit::{
 };
 #[cfg(test)]
 pub(crate) use token::make_seed_token;
+#[cfg(test)]
+pub(crate) use types::ChallengeVariant;
 pub(crate) use types::{ChallengeSeed, Transform};
 
 pub(crate) fn build_puzzle(seed: &ChallengeSeed) -> ChallengePuzzle {
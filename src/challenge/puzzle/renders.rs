@@ -1,12 +1,14 @@
 use rand::Rng;
 use spin_sdk::http::{Request, Response};
 
-use super::super::{challenge_response, PUZZLE_PATH};
+use super::super::{challenge_response, PUZZLE_ACCESSIBLE_PATH, PUZZLE_PATH};
 use super::token::make_seed_token;
-use super::types::{ChallengeSeed, Transform};
+use super::types::{ChallengeSeed, ChallengeVariant, Transform};
 use super::{build_puzzle, select_transform_pair, transforms_for_count};
+use crate::i18n::Messages;
 
-pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Response {
+pub(crate) fn render_challenge(req: &Request, cfg: &crate::config::Config) -> Response {
+    let messages = Messages::for_request(req, cfg);
     let ip = crate::extract_client_ip(req);
     let ua = req
         .header("user-agent")
@@ -18,7 +20,7 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
     let mut rng = rand::rng();
     let grid_size = 4u8;
     let active_cells = rng.random_range(7..=9);
-    let legend_transforms = transforms_for_count(transform_count);
+    let legend_transforms = transforms_for_count(cfg.challenge_puzzle_transform_count as usize);
     let transforms = select_transform_pair(&mut rng, &legend_transforms);
     let seed = ChallengeSeed {
         seed_id: format!("{:016x}", rng.random::<u64>()),
@@ -38,6 +40,7 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
         transforms,
         training_count: 1,
         seed: rng.random::<u64>(),
+        variant: ChallengeVariant::Grid,
     };
     let puzzle = build_puzzle(&seed);
     let seed_token = make_seed_token(&seed);
@@ -53,8 +56,10 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
                 "<div class=\"pair\">{}{}{}</div>",
                 "<div class=\"pair-grids\">",
                 format!(
-                    "<div><div class=\"grid-label\">Before</div>{}</div><div><div class=\"grid-label\">After</div>{}</div>",
+                    "<div><div class=\"grid-label\">{}</div>{}</div><div><div class=\"grid-label\">{}</div>{}</div>",
+                    messages.text("puzzle.before"),
                     render_grid(input, puzzle.grid_size, "grid-static", false),
+                    messages.text("puzzle.after"),
                     render_grid(output, puzzle.grid_size, "grid-static", false),
                 ),
                 "</div>"
@@ -62,11 +67,13 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
         })
         .collect();
 
-    let legend_html = render_transform_legend(&legend_transforms);
+    let legend_html = render_transform_legend(&legend_transforms, &messages);
     let html = format!(
         r#"
-        <html>
+        <html lang="{lang}" dir="{dir}">
         <head>
+          <meta charset="UTF-8" />
+          <title>{title}</title>
           <style>
             :root {{
               --color-black: #111;
@@ -90,7 +97,7 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
             body {{ font-family: sans-serif; font-size: 15px; line-height: 1.6; background: repeating-linear-gradient(-45deg,#fffafd,#fffafd 10px,#faf2fa 10px,#faf2fa 20px); margin: 24px; color: var(--color-black); }}
             .challenge {{ max-width: 520px; margin: 0 auto; background: var(--color-white); padding: 24px; border: 1px solid var(--color-border); }}
             .challenge h2 {{ width: var(--duo-grid-size); margin: 0 auto 0.6rem; font-size: var(--font-heading); line-height: 1.2; text-align: center; }}
-            .grid {{ display: grid; gap: var(--puzzle-gap); }}
+            .grid {{ display: grid; gap: var(--puzzle-gap); direction: ltr; }}
             .cell {{ width: var(--puzzle-cell); height: var(--puzzle-cell); border: 1px solid var(--color-border); background: var(--color-white); }}
             .cell.active {{ background: var(--color-black); }}
             .cell.active-alt {{ background: var(--color-pink); }}
@@ -102,6 +109,8 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
             .submit-row {{ grid-column: 1 / -1; margin-top: 12px; }}
             .submit-row button {{ width: 100%; }}
             button {{ padding: 8px 14px; font-size: var(--font-body); background: var(--color-black); color: var(--color-white); border: 1px solid var(--color-black); }}
+            .accessible-row {{ margin-top: 16px; text-align: center; }}
+            .link-button {{ background: none; border: 0; padding: 0; color: var(--color-dark-gray); text-decoration: underline; cursor: pointer; font-size: var(--font-small); }}
             .legend {{ margin: 12px 0 16px; padding: 12px; border: 1px solid var(--color-border); background: var(--color-legend-bg); }}
             .legend-fieldset {{ border: 0; margin: 0; padding: 0; min-width: 0; }}
             .legend-subtitle {{ font-size: var(--font-medium); color: var(--color-black); margin: 0 auto 10px; width: var(--duo-grid-size); text-align: center; }}
@@ -112,7 +121,7 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
             .legend-picks {{ display: flex; align-items: center; gap: 10px; }}
             .legend-pick-label {{ display: inline-flex; align-items: center; gap: 4px; font-size: var(--font-small); color: var(--color-dark-gray); cursor: pointer; }}
             .legend-pick-label input {{ width: 16px; height: 16px; margin: 0; accent-color: var(--color-black); cursor: pointer; }}
-            .legend-icon {{ position: relative; width: 100%; max-width: 100%; aspect-ratio: 1 / 1; flex: 0 0 auto; }}
+            .legend-icon {{ direction: ltr; position: relative; width: 100%; max-width: 100%; aspect-ratio: 1 / 1; flex: 0 0 auto; }}
             .legend-grid {{ position: absolute; inset: 0; display: grid; grid-template-columns: repeat(4, 1fr); gap: var(--legend-gap); z-index: 0; }}
             .legend-cell {{ border: 1px solid var(--color-border); background: var(--color-white); }}
             .legend-line {{ position: absolute; border-top: 2px dashed var(--color-pink); left: 0; right: 0; z-index: 1; }}
@@ -156,7 +165,7 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
         </head>
         <body>
           <div class="challenge">
-            <h2>Puzzle</h2>
+            <h2>{title}</h2>
             {training_html}
             <div class="legend">
               {legend_html}
@@ -173,11 +182,15 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
                   <form method="POST" action="{puzzle_path}" class="submit-row">
                     <input type="hidden" name="seed" value="{seed_token}" />
                     <input type="hidden" name="output" id="challenge-output" value="{empty_tritstring}" />
-                    <button type="submit">Submit</button>
+                    <button type="submit">{submit}</button>
                   </form>
                 </div>
               </div>
             </div>
+            <form method="GET" action="{accessible_path}" class="accessible-row">
+              <input type="hidden" name="seed" value="{seed_token}" />
+              <button type="submit" class="link-button">{accessible_link}</button>
+            </form>
           </div>
           <script>
             const size = {grid_size};
@@ -314,6 +327,12 @@ pub(crate) fn render_challenge(req: &Request, transform_count: usize) -> Respons
         grid_size = grid_size,
         empty_tritstring = grid_to_tritstring(&empty_output),
         puzzle_path = PUZZLE_PATH,
+        accessible_path = PUZZLE_ACCESSIBLE_PATH,
+        lang = messages.locale(),
+        dir = messages.dir(),
+        title = messages.text("puzzle.title"),
+        submit = messages.text("puzzle.submit"),
+        accessible_link = messages.text("puzzle.accessible_link"),
     );
     challenge_response(200, &html)
 }
@@ -335,54 +354,48 @@ fn transform_value(transform: Transform) -> &'static str {
     }
 }
 
-fn transform_option_label(transform: Transform) -> &'static str {
-    match transform {
-        Transform::ShiftUp => "Shift up",
-        Transform::ShiftDown => "Shift down",
-        Transform::ShiftLeft => "Shift left",
-        Transform::ShiftRight => "Shift right",
-        Transform::RotateCw90 => "90&#176; clockwise",
-        Transform::RotateCcw90 => "90&#176; anticlockwise",
-        Transform::MirrorHorizontal => "Mirror horizontally",
-        Transform::MirrorVertical => "Mirror vertically",
-        Transform::DropTop => "Drop top",
-        Transform::DropBottom => "Drop bottom",
-        Transform::DropLeft => "Drop left",
-        Transform::DropRight => "Drop right",
-    }
-}
-
-fn render_transform_legend(transforms: &[Transform]) -> String {
+fn render_transform_legend(transforms: &[Transform], messages: &Messages) -> String {
     let rows: String = transforms
         .iter()
         .map(|transform| {
-            let label = transform_legend_label(transform);
-            let icon = render_transform_icon(transform, label);
+            let label = transform_legend_label(transform, messages);
+            let icon = render_transform_icon(transform, label.as_str());
             let value = transform_value(*transform);
-            let aria_label = transform_option_label(*transform);
+            let option_label = messages.raw(format!("transform.{}", value).as_str());
             format!(
-                "<div class=\"legend-row\" data-transform=\"{}\"><div class=\"legend-item\">{}</div><div class=\"legend-picks\"><label class=\"legend-pick-label\"><input type=\"radio\" class=\"legend-radio legend-radio-1\" name=\"transform_1\" value=\"{}\" aria-label=\"First transform: {}\" /><span>1st</span></label><label class=\"legend-pick-label\"><input type=\"radio\" class=\"legend-radio legend-radio-2\" name=\"transform_2\" value=\"{}\" aria-label=\"Second transform: {}\" /><span>2nd</span></label></div></div>",
-                value, icon, value, aria_label, value, aria_label
+                "<div class=\"legend-row\" data-transform=\"{}\"><div class=\"legend-item\">{}</div><div class=\"legend-picks\"><label class=\"legend-pick-label\"><input type=\"radio\" class=\"legend-radio legend-radio-1\" name=\"transform_1\" value=\"{}\" aria-label=\"{}\" /><span>{}</span></label><label class=\"legend-pick-label\"><input type=\"radio\" class=\"legend-radio legend-radio-2\" name=\"transform_2\" value=\"{}\" aria-label=\"{}\" /><span>{}</span></label></div></div>",
+                value,
+                icon,
+                value,
+                messages.format("puzzle.pick_first_label", &[("transform", option_label.as_str())]),
+                messages.text("puzzle.pick_first"),
+                value,
+                messages.format("puzzle.pick_second_label", &[("transform", option_label.as_str())]),
+                messages.text("puzzle.pick_second"),
             )
         })
         .collect();
     format!(
-        "<fieldset class=\"legend-fieldset\"><div class=\"legend-subtitle\">Which 2 transforms were applied?</div><div class=\"legend-options\">{}</div></fieldset>",
+        "<fieldset class=\"legend-fieldset\"><div class=\"legend-subtitle\">{}</div><div class=\"legend-options\">{}</div></fieldset>",
+        messages.text("puzzle.prompt"),
         rows
     )
 }
 
-fn transform_legend_label(transform: &Transform) -> &'static str {
+fn transform_legend_label(transform: &Transform, messages: &Messages) -> String {
     match transform {
-        Transform::RotateCw90 | Transform::RotateCcw90 => "90&#176;",
-        Transform::MirrorHorizontal | Transform::MirrorVertical => "mirror",
+        Transform::RotateCw90 | Transform::RotateCcw90 => "90&#176;".to_string(),
+        Transform::MirrorHorizontal | Transform::MirrorVertical => {
+            messages.text("puzzle.legend_mirror")
+        }
         Transform::ShiftUp
         | Transform::ShiftDown
         | Transform::ShiftLeft
-        | Transform::ShiftRight => "shift",
-        Transform::DropTop | Transform::DropBottom | Transform::DropLeft | Transform::DropRight => {
-            "shift"
-        }
+        | Transform::ShiftRight
+        | Transform::DropTop
+        | Transform::DropBottom
+        | Transform::DropLeft
+        | Transform::DropRight => messages.text("puzzle.legend_shift"),
     }
 }
 
@@ -1,13 +1,11 @@
 use spin_sdk::http::{Request, Response};
 
-use super::super::{challenge_response, KeyValueStore};
+use super::super::{challenge_response, KeyValueStore, PUZZLE_PATH};
 use super::renders::render_challenge;
 use super::token::{parse_seed_token, SeedTokenError};
+use super::types::{ChallengeSeed, ChallengeVariant};
 use super::{build_puzzle, parse_submission};
-
-const CHALLENGE_FORBIDDEN_BODY: &str = "<html><body><h2 style='color:red;'>Forbidden. Please request a new challenge.</h2><a href='/challenge/puzzle'>Request new challenge.</a></body></html>";
-const CHALLENGE_EXPIRED_BODY: &str = "<html><body><h2 style='color:red;'>Expired</h2><a href='/challenge/puzzle'>Request new challenge.</a></body></html>";
-const CHALLENGE_INCORRECT_BODY: &str = "<html><body><h2 style='color:red;'>Incorrect.</h2><a href='/challenge/puzzle'>Request new challenge.</a></body></html>";
+use crate::i18n::Messages;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ChallengeSubmitOutcome {
@@ -25,114 +23,175 @@ pub(crate) enum ChallengeSubmitOutcome {
     SequenceTimingTooSlow,
     Forbidden,
     InvalidOutput,
+    RateLimited,
+}
+
+fn challenge_failure_body(messages: &Messages, heading_key: &str) -> String {
+    format!(
+        "<html lang=\"{lang}\" dir=\"{dir}\"><head><meta charset=\"UTF-8\"></head><body><h2 style='color:red;'>{heading}</h2><a href='{puzzle_path}'>{request_new}</a></body></html>",
+        lang = messages.locale(),
+        dir = messages.dir(),
+        heading = messages.text(heading_key),
+        puzzle_path = PUZZLE_PATH,
+        request_new = messages.text("challenge.request_new"),
+    )
+}
+
+pub(super) fn challenge_forbidden_response(messages: &Messages) -> Response {
+    challenge_response(
+        403,
+        &challenge_failure_body(messages, "challenge.forbidden"),
+    )
 }
 
-fn challenge_forbidden_response() -> Response {
-    challenge_response(403, CHALLENGE_FORBIDDEN_BODY)
+pub(super) fn challenge_expired_response(messages: &Messages) -> Response {
+    challenge_response(403, &challenge_failure_body(messages, "challenge.expired"))
 }
 
-fn challenge_expired_response() -> Response {
-    challenge_response(403, CHALLENGE_EXPIRED_BODY)
+pub(super) fn challenge_incorrect_response(messages: &Messages) -> Response {
+    challenge_response(
+        403,
+        &challenge_failure_body(messages, "challenge.incorrect"),
+    )
+}
+
+fn challenge_invalid_output_response(messages: &Messages) -> Response {
+    challenge_response(400, &messages.text("challenge.invalid_output"))
 }
 
-fn challenge_incorrect_response() -> Response {
-    challenge_response(403, CHALLENGE_INCORRECT_BODY)
+pub(super) fn challenge_solved_response(
+    seed: &ChallengeSeed,
+    messages: &Messages,
+    level: crate::challenge::clearance::ClearanceLevel,
+    clearance_ttl_seconds: u64,
+) -> Response {
+    let mut response = challenge_response(
+        200,
+        &format!(
+            "<html lang=\"{}\" dir=\"{}\"><head><meta charset=\"UTF-8\"></head><body><h2>{}</h2></body></html>",
+            messages.locale(),
+            messages.dir(),
+            messages.text("challenge.solved")
+        ),
+    );
+    response.set_header(
+        "Set-Cookie",
+        crate::challenge::clearance::issue_cookie(
+            seed.ip_bucket.as_str(),
+            seed.ua_bucket.as_str(),
+            level,
+            clearance_ttl_seconds,
+        ),
+    );
+    response
 }
 
 pub(crate) fn serve_challenge_page(
     req: &Request,
     test_mode: bool,
-    transform_count: usize,
+    cfg: &crate::config::Config,
 ) -> Response {
     if !test_mode {
         return challenge_response(404, "Not Found");
     }
-    render_challenge(req, transform_count)
+    render_challenge(req, cfg)
 }
 
-pub(crate) fn handle_challenge_submit_with_outcome<S: KeyValueStore>(
+/// Runs the checks shared by every puzzle variant: form shape, seed signature, operation
+/// envelope, request binding, timing and replay. Returns the seed, the raw answer field and
+/// the validation timestamp.
+pub(super) fn validate_submission<S: KeyValueStore + ?Sized>(
     store: &S,
     req: &Request,
-) -> (Response, ChallengeSubmitOutcome) {
+    answer_field: &str,
+    variant: ChallengeVariant,
+    messages: &Messages,
+) -> Result<(ChallengeSeed, String, u64), (Response, ChallengeSubmitOutcome)> {
     if crate::request_validation::enforce_body_size(
         req.body(),
         crate::request_validation::MAX_CHALLENGE_FORM_BYTES,
     )
     .is_err()
     {
-        return (
-            challenge_forbidden_response(),
+        return Err((
+            challenge_forbidden_response(messages),
             ChallengeSubmitOutcome::Forbidden,
-        );
+        ));
     }
     let form = match std::str::from_utf8(req.body()) {
         Ok(v) => v.to_string(),
         Err(_) => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::Forbidden,
-            )
+            ))
         }
     };
     let seed_token = match get_form_field(&form, "seed") {
         Some(v) => v,
         None => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::Forbidden,
-            )
+            ))
         }
     };
     if !crate::request_validation::validate_seed_token(seed_token.as_str()) {
-        return (
-            challenge_forbidden_response(),
+        return Err((
+            challenge_forbidden_response(messages),
             ChallengeSubmitOutcome::Forbidden,
-        );
+        ));
     }
-    let output_raw = match get_form_field(&form, "output") {
+    let answer_raw = match get_form_field(&form, answer_field) {
         Some(v) => v,
         None => {
-            return (
-                challenge_response(400, "Invalid output"),
+            return Err((
+                challenge_invalid_output_response(messages),
                 ChallengeSubmitOutcome::InvalidOutput,
-            )
+            ))
         }
     };
-    if output_raw.len() > 128 {
-        return (
-            challenge_response(400, "Invalid output"),
+    if answer_raw.len() > 128 {
+        return Err((
+            challenge_invalid_output_response(messages),
             ChallengeSubmitOutcome::InvalidOutput,
-        );
+        ));
     }
     let seed = match parse_seed_token(&seed_token) {
         Ok(s) => s,
         Err(SeedTokenError::InvalidOperationEnvelope(
             crate::challenge::operation_envelope::EnvelopeValidationError::MissingOperationId,
         )) => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::SequenceOpMissing,
-            )
+            ))
         }
         Err(SeedTokenError::InvalidOperationEnvelope(_)) => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::SequenceOpInvalid,
-            )
+            ))
         }
         Err(_) => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::Forbidden,
-            )
+            ))
         }
     };
+    if seed.variant != variant {
+        return Err((
+            challenge_forbidden_response(messages),
+            ChallengeSubmitOutcome::Forbidden,
+        ));
+    }
     let now = crate::admin::now_ts();
     if now > seed.expires_at {
-        return (
-            challenge_expired_response(),
+        return Err((
+            challenge_expired_response(messages),
             ChallengeSubmitOutcome::SequenceOpExpired,
-        );
+        ));
     }
     match crate::challenge::operation_envelope::validate_ordering_window(
         seed.flow_id.as_str(),
@@ -148,16 +207,16 @@ pub(crate) fn handle_challenge_submit_with_outcome<S: KeyValueStore>(
     ) {
         Ok(_) => {}
         Err(crate::challenge::operation_envelope::OrderingValidationError::OrderViolation) => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::SequenceOrderViolation,
-            )
+            ))
         }
         Err(crate::challenge::operation_envelope::OrderingValidationError::WindowExceeded) => {
-            return (
-                challenge_expired_response(),
+            return Err((
+                challenge_expired_response(messages),
                 ChallengeSubmitOutcome::SequenceWindowExceeded,
-            )
+            ))
         }
     }
     let ip = crate::extract_client_ip(req);
@@ -175,10 +234,10 @@ pub(crate) fn handle_challenge_submit_with_outcome<S: KeyValueStore>(
     )
     .is_err()
     {
-        return (
-            challenge_forbidden_response(),
+        return Err((
+            challenge_forbidden_response(messages),
             ChallengeSubmitOutcome::SequenceBindingMismatch,
-        );
+        ));
     }
     let timing_bucket = format!("{}:{}", seed.ip_bucket, seed.ua_bucket);
     match crate::challenge::operation_envelope::validate_timing_primitives(
@@ -196,22 +255,22 @@ pub(crate) fn handle_challenge_submit_with_outcome<S: KeyValueStore>(
     ) {
         Ok(_) => {}
         Err(crate::challenge::operation_envelope::TimingValidationError::TooFast) => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::SequenceTimingTooFast,
-            )
+            ))
         }
         Err(crate::challenge::operation_envelope::TimingValidationError::TooRegular) => {
-            return (
-                challenge_forbidden_response(),
+            return Err((
+                challenge_forbidden_response(messages),
                 ChallengeSubmitOutcome::SequenceTimingTooRegular,
-            )
+            ))
         }
         Err(crate::challenge::operation_envelope::TimingValidationError::TooSlow) => {
-            return (
-                challenge_expired_response(),
+            return Err((
+                challenge_expired_response(messages),
                 ChallengeSubmitOutcome::SequenceTimingTooSlow,
-            )
+            ))
         }
     }
     match crate::challenge::operation_envelope::validate_operation_replay(
@@ -224,46 +283,68 @@ pub(crate) fn handle_challenge_submit_with_outcome<S: KeyValueStore>(
     ) {
         Ok(_) => {}
         Err(crate::challenge::operation_envelope::ReplayValidationError::ReplayDetected) => {
-            return (
-                challenge_expired_response(),
+            return Err((
+                challenge_expired_response(messages),
                 ChallengeSubmitOutcome::SequenceOpReplay,
-            )
+            ))
         }
         Err(crate::challenge::operation_envelope::ReplayValidationError::ExpiredOperation) => {
-            return (
-                challenge_expired_response(),
+            return Err((
+                challenge_expired_response(messages),
                 ChallengeSubmitOutcome::SequenceOpExpired,
-            )
+            ))
         }
     }
+    Ok((seed, answer_raw, now))
+}
+
+pub(crate) fn handle_challenge_submit_with_outcome<S: KeyValueStore + ?Sized>(
+    store: &S,
+    req: &Request,
+    cfg: &crate::config::Config,
+) -> (Response, ChallengeSubmitOutcome) {
+    let messages = Messages::for_request(req, cfg);
+    let (seed, output_raw, now) =
+        match validate_submission(store, req, "output", ChallengeVariant::Grid, &messages) {
+            Ok(validated) => validated,
+            Err(rejection) => return rejection,
+        };
     let output = match parse_submission(&output_raw, seed.grid_size as usize) {
         Ok(v) => v,
         Err(_e) => {
             return (
-                challenge_response(400, "Invalid output"),
+                challenge_invalid_output_response(&messages),
                 ChallengeSubmitOutcome::InvalidOutput,
             )
         }
     };
     let puzzle = build_puzzle(&seed);
     if output == puzzle.test_output {
+        crate::observability::metrics::observe(
+            store,
+            crate::observability::metrics::HistogramName::ChallengeSolveSeconds,
+            Some("puzzle"),
+            now.saturating_sub(seed.issued_at) as f64,
+        );
         return (
-            challenge_response(
-                200,
-                "<html><body><h2>Thank you! Challenge complete.</h2></body></html>",
+            challenge_solved_response(
+                &seed,
+                &messages,
+                crate::challenge::clearance::ClearanceLevel::ChallengeStrong,
+                crate::challenge::clearance::CLEARANCE_TTL_SECONDS,
             ),
             ChallengeSubmitOutcome::Solved,
         );
     }
     (
-        challenge_incorrect_response(),
+        challenge_incorrect_response(&messages),
         ChallengeSubmitOutcome::Incorrect,
     )
 }
 
 #[cfg(test)]
 pub fn handle_challenge_submit<S: KeyValueStore>(store: &S, req: &Request) -> Response {
-    handle_challenge_submit_with_outcome(store, req).0
+    handle_challenge_submit_with_outcome(store, req, crate::config::defaults()).0
 }
 
 fn get_form_field(form: &str, name: &str) -> Option<String> {
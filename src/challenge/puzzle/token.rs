@@ -16,22 +16,15 @@ pub(crate) enum SeedTokenError {
     InvalidOperationEnvelope(crate::challenge::operation_envelope::EnvelopeValidationError),
 }
 
-fn get_challenge_secret() -> String {
-    match std::env::var("SHUMA_CHALLENGE_SECRET") {
-        Ok(secret) if !secret.trim().is_empty() => secret,
-        _ => crate::config::env_string_required("SHUMA_JS_SECRET"),
-    }
-}
-
 fn sign_payload(payload: &str) -> Vec<u8> {
-    let secret = get_challenge_secret();
+    let secret = crate::config::challenge_secret();
     let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
     mac.update(payload.as_bytes());
     mac.finalize().into_bytes().to_vec()
 }
 
 fn verify_signature(payload: &str, sig: &[u8]) -> bool {
-    let secret = get_challenge_secret();
+    let secret = crate::config::challenge_secret();
     let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
     mac.update(payload.as_bytes());
     mac.verify_slice(sig).is_ok()
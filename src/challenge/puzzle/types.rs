@@ -18,6 +18,18 @@ pub(crate) struct ChallengeSeed {
     pub transforms: Vec<Transform>,
     pub training_count: u8,
     pub seed: u64,
+    #[serde(default)]
+    pub variant: ChallengeVariant,
+}
+
+/// Presentation of a puzzle seed. Both variants share one operation envelope, so
+/// whichever is submitted first consumes the operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ChallengeVariant {
+    #[default]
+    Grid,
+    Text,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
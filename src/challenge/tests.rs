@@ -4,10 +4,12 @@
 #[cfg(test)]
 mod tests {
     use super::super::{
-        apply_transform, build_puzzle, generate_pair, handle_challenge_submit,
+        answer_matches, apply_transform, AccessibleQuestion, build_puzzle, build_question, generate_pair,
+        handle_accessible_submit_with_outcome, handle_challenge_submit,
         handle_challenge_submit_with_outcome, make_seed_token, parse_submission,
-        parse_transform_count, render_challenge, select_transform_pair, serve_challenge_page,
-        transforms_for_count, ChallengeSeed, ChallengeSubmitOutcome, Transform,
+        parse_transform_count, render_challenge, select_transform_pair,
+        serve_accessible_challenge_page, serve_challenge_page, transforms_for_count,
+        ChallengeSeed, ChallengeSubmitOutcome, ChallengeVariant, Transform,
     };
     use rand::rngs::StdRng;
     use rand::SeedableRng;
@@ -70,6 +72,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 12345,
+            variant: ChallengeVariant::Grid,
         };
         let a = build_puzzle(&seed);
         let b = build_puzzle(&seed);
@@ -96,6 +99,7 @@ mod tests {
             transforms: vec![Transform::ShiftLeft, Transform::ShiftDown],
             training_count: 2,
             seed: 6789,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let has_one = puzzle.test_input.iter().any(|v| *v == 1);
@@ -276,7 +280,7 @@ mod tests {
             .uri("/challenge/puzzle")
             .body(Vec::new())
             .build();
-        let resp = render_challenge(&req, 6);
+        let resp = render_challenge(&req, crate::config::defaults());
         let body = String::from_utf8(resp.into_body()).unwrap();
         assert!(body.contains("id=\"challenge-output-grid\""));
     }
@@ -288,7 +292,7 @@ mod tests {
             .uri("/challenge/puzzle")
             .body(Vec::new())
             .build();
-        let resp = render_challenge(&req, 6);
+        let resp = render_challenge(&req, crate::config::defaults());
         let body = String::from_utf8(resp.into_body()).unwrap();
         assert!(body.contains("id=\"challenge-output\""));
         assert!(body.contains("type=\"hidden\""));
@@ -301,7 +305,7 @@ mod tests {
             .uri("/challenge/puzzle")
             .body(Vec::new())
             .build();
-        let resp = render_challenge(&req, 6);
+        let resp = render_challenge(&req, crate::config::defaults());
         let body = String::from_utf8(resp.into_body()).unwrap();
         assert!(body.contains("Puzzle"));
         assert!(!body.contains("id=\"transform-1\""));
@@ -336,7 +340,7 @@ mod tests {
             .uri("/challenge/puzzle")
             .body(Vec::new())
             .build();
-        let resp = render_challenge(&req, 6);
+        let resp = render_challenge(&req, crate::config::defaults());
         let body = String::from_utf8(resp.into_body()).unwrap();
         assert!(!body.contains("Debug transforms:"));
     }
@@ -348,9 +352,9 @@ mod tests {
             .uri("/challenge/puzzle")
             .body(Vec::new())
             .build();
-        let resp = serve_challenge_page(&req, false, 6);
+        let resp = serve_challenge_page(&req, false, crate::config::defaults());
         assert_eq!(*resp.status(), 404u16);
-        let resp_ok = serve_challenge_page(&req, true, 6);
+        let resp_ok = serve_challenge_page(&req, true, crate::config::defaults());
         assert_eq!(*resp_ok.status(), 200u16);
     }
 
@@ -361,7 +365,7 @@ mod tests {
             .uri("/challenge/puzzle")
             .body(Vec::new())
             .build();
-        let resp = serve_challenge_page(&req, true, 6);
+        let resp = serve_challenge_page(&req, true, crate::config::defaults());
         assert_eq!(
             header_value(&resp, "Cache-Control").as_deref(),
             Some("no-store")
@@ -390,6 +394,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 9999,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let output = grid_to_tritstring(&puzzle.test_output);
@@ -431,6 +436,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 4242,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let mut output = grid_to_tritstring(&puzzle.test_output);
@@ -469,6 +475,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 6001,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let mut output = grid_to_tritstring(&puzzle.test_output);
@@ -480,7 +487,8 @@ mod tests {
             .header("content-type", "application/x-www-form-urlencoded")
             .body(body.as_bytes().to_vec())
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 403u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::Incorrect);
     }
@@ -507,6 +515,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 1,
             seed: 424242,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let mut wrong = grid_to_tritstring(&puzzle.test_output);
@@ -567,6 +576,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 1,
             seed: 424243,
+            variant: ChallengeVariant::Grid,
         };
         let seed_token = make_seed_token(&seed);
         let req = Request::builder()
@@ -579,7 +589,8 @@ mod tests {
                     .to_vec(),
             )
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 403u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceOpExpired);
         let body = String::from_utf8(resp.into_body()).unwrap();
@@ -629,6 +640,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 6002,
+            variant: ChallengeVariant::Grid,
         };
         let req = Request::builder()
             .method(Method::Post)
@@ -636,7 +648,8 @@ mod tests {
             .header("content-type", "application/x-www-form-urlencoded")
             .body(format!("seed={}", make_seed_token(&seed)).as_bytes().to_vec())
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 400u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::InvalidOutput);
         assert_eq!(String::from_utf8_lossy(resp.body()), "Invalid output");
@@ -664,6 +677,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 6003,
+            variant: ChallengeVariant::Grid,
         };
         let req = Request::builder()
             .method(Method::Post)
@@ -675,7 +689,8 @@ mod tests {
                     .to_vec(),
             )
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 400u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::InvalidOutput);
         assert_eq!(String::from_utf8_lossy(resp.body()), "Invalid output");
@@ -690,7 +705,8 @@ mod tests {
             .header("content-type", "application/x-www-form-urlencoded")
             .body(b"output=0000000000000000".to_vec())
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 403u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::Forbidden);
     }
@@ -704,7 +720,8 @@ mod tests {
             .header("content-type", "application/x-www-form-urlencoded")
             .body(vec![0xff, 0xfe, 0xfd])
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 403u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::Forbidden);
     }
@@ -731,6 +748,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 1111,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let output = grid_to_tritstring(&puzzle.test_output);
@@ -770,6 +788,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 2222,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let output = grid_to_tritstring(&puzzle.test_output);
@@ -781,7 +800,8 @@ mod tests {
             .header("content-type", "application/x-www-form-urlencoded")
             .body(body.as_bytes().to_vec())
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 403u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceBindingMismatch);
     }
@@ -808,6 +828,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 4444,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let output = grid_to_tritstring(&puzzle.test_output);
@@ -819,10 +840,18 @@ mod tests {
             .body(body.as_bytes().to_vec())
             .build();
 
-        let (_first_resp, first_outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (first_resp, first_outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(first_outcome, ChallengeSubmitOutcome::Solved);
+        let clearance_cookie = first_resp
+            .headers()
+            .find(|(k, _)| k.eq_ignore_ascii_case("set-cookie"))
+            .and_then(|(_, v)| v.as_str())
+            .unwrap_or("");
+        assert!(clearance_cookie.starts_with("shuma_clearance="));
 
-        let (second_resp, second_outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (second_resp, second_outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*second_resp.status(), 403u16);
         assert_eq!(second_outcome, ChallengeSubmitOutcome::SequenceOpReplay);
     }
@@ -849,6 +878,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 4545,
+            variant: ChallengeVariant::Grid,
         };
         let req = Request::builder()
             .method(Method::Post)
@@ -861,7 +891,8 @@ mod tests {
             )
             .build();
 
-        let (_resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (_resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceOpMissing);
     }
 
@@ -887,6 +918,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 4646,
+            variant: ChallengeVariant::Grid,
         };
         let req = Request::builder()
             .method(Method::Post)
@@ -899,7 +931,8 @@ mod tests {
             )
             .build();
 
-        let (_resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (_resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceOpInvalid);
     }
 
@@ -925,6 +958,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 4747,
+            variant: ChallengeVariant::Grid,
         };
         let req = Request::builder()
             .method(Method::Post)
@@ -937,7 +971,8 @@ mod tests {
             )
             .build();
 
-        let (_resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (_resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceTimingTooFast);
     }
 
@@ -963,6 +998,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 4848,
+            variant: ChallengeVariant::Grid,
         };
         let timing_bucket = format!("{}:{}", seed.ip_bucket, seed.ua_bucket);
         let cadence_key = format!(
@@ -989,7 +1025,8 @@ mod tests {
             )
             .build();
 
-        let (_resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (_resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceTimingTooRegular);
     }
 
@@ -1015,6 +1052,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 3333,
+            variant: ChallengeVariant::Grid,
         };
         let puzzle = build_puzzle(&seed);
         let output = grid_to_tritstring(&puzzle.test_output);
@@ -1026,7 +1064,8 @@ mod tests {
             .header("content-type", "application/x-www-form-urlencoded")
             .body(body.as_bytes().to_vec())
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 403u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceOrderViolation);
         let body = String::from_utf8(resp.into_body()).unwrap();
@@ -1057,6 +1096,7 @@ mod tests {
             transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
             training_count: 2,
             seed: 6004,
+            variant: ChallengeVariant::Grid,
         };
         let req = Request::builder()
             .method(Method::Post)
@@ -1068,7 +1108,8 @@ mod tests {
                     .to_vec(),
             )
             .build();
-        let (resp, outcome) = handle_challenge_submit_with_outcome(&store, &req);
+        let (resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
         assert_eq!(*resp.status(), 403u16);
         assert_eq!(outcome, ChallengeSubmitOutcome::SequenceWindowExceeded);
         let body = String::from_utf8(resp.into_body()).unwrap();
@@ -1098,4 +1139,279 @@ mod tests {
         let out = apply_transform(&grid, 4, Transform::ShiftLeft);
         assert_eq!(out[0], 2);
     }
+
+    fn accessible_test_seed(operation_id: &str, seed: u64, variant: ChallengeVariant) -> ChallengeSeed {
+        let now = crate::admin::now_ts();
+        ChallengeSeed {
+            seed_id: format!("seed-{}", operation_id),
+            operation_id: operation_id.to_string(),
+            flow_id: crate::challenge::operation_envelope::FLOW_CHALLENGE_PUZZLE.to_string(),
+            step_id: crate::challenge::operation_envelope::STEP_CHALLENGE_PUZZLE_SUBMIT.to_string(),
+            step_index: crate::challenge::operation_envelope::STEP_INDEX_CHALLENGE_PUZZLE_SUBMIT,
+            issued_at: now.saturating_sub(2),
+            expires_at: now + 300,
+            token_version: crate::challenge::operation_envelope::TOKEN_VERSION_V1,
+            ip_bucket: crate::signals::ip_identity::bucket_ip("unknown"),
+            ua_bucket: crate::challenge::operation_envelope::user_agent_bucket(""),
+            path_class: crate::challenge::operation_envelope::PATH_CLASS_CHALLENGE_PUZZLE_SUBMIT
+                .to_string(),
+            grid_size: 4,
+            active_cells: 7,
+            transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
+            training_count: 1,
+            seed,
+            variant,
+        }
+    }
+
+    fn form_post(path: &str, body: String) -> Request {
+        Request::builder()
+            .method(Method::Post)
+            .uri(path)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(body.into_bytes())
+            .build()
+    }
+
+    #[test]
+    fn accessible_question_bank_is_deterministic_and_rotates() {
+        let cfg = crate::config::defaults();
+        let messages = crate::i18n::Messages::new("en", &cfg.locale_overrides);
+        let mut prompts = std::collections::HashSet::new();
+        for value in 0..64u64 {
+            let seed = accessible_test_seed("ab01", value, ChallengeVariant::Text);
+            let question = build_question(&seed);
+            assert_eq!(question, build_question(&seed));
+            assert!(answer_matches(&question, question.answer.as_str(), &messages));
+            prompts.insert(question.prompt_key);
+        }
+        assert!(prompts.len() >= 4, "bank should rotate question kinds: {:?}", prompts);
+    }
+
+    #[test]
+    fn accessible_answers_tolerate_case_spacing_and_number_words() {
+        let cfg = crate::config::defaults();
+        let english = crate::i18n::Messages::new("en", &cfg.locale_overrides);
+        let question = AccessibleQuestion {
+            prompt_key: "accessible.question.add",
+            args: vec![("a", "4".to_string()), ("b", "3".to_string())],
+            answer: "7".to_string(),
+        };
+        assert_eq!(question.prompt(&english), "What is 4 plus 3?");
+        assert!(answer_matches(&question, " 7 ", &english));
+        assert!(answer_matches(&question, "Seven.", &english));
+        assert!(!answer_matches(&question, "8", &english));
+        let day = AccessibleQuestion {
+            prompt_key: "accessible.question.next_day",
+            args: vec![("day", "weekday.monday".to_string())],
+            answer: "tuesday".to_string(),
+        };
+        assert_eq!(
+            day.prompt(&english),
+            "Which day of the week comes after Monday?"
+        );
+        assert!(answer_matches(&day, "Tuesday+", &english));
+        let german = crate::i18n::Messages::new("de", &cfg.locale_overrides);
+        assert!(answer_matches(&day, "Dienstag", &german));
+        assert!(answer_matches(&day, "tuesday", &german));
+        assert!(!answer_matches(&day, "Dienstag", &english));
+    }
+
+    #[test]
+    fn puzzle_page_offers_accessible_alternative() {
+        let req = Request::builder()
+            .method(Method::Get)
+            .uri("/challenge/puzzle")
+            .body(Vec::new())
+            .build();
+        let body =
+            String::from_utf8(render_challenge(&req, crate::config::defaults()).into_body())
+                .unwrap();
+        assert!(body.contains("action=\"/challenge/puzzle/accessible\""));
+        assert!(body.contains("Answer a text question instead"));
+    }
+
+    #[test]
+    fn accessible_page_switches_grid_seed_to_text_variant() {
+        let seed = accessible_test_seed("ab02cd", 77, ChallengeVariant::Grid);
+        let encoded = percent_encoding::utf8_percent_encode(
+            make_seed_token(&seed).as_str(),
+            percent_encoding::NON_ALPHANUMERIC,
+        )
+        .to_string();
+        let req = Request::builder()
+            .method(Method::Get)
+            .uri(format!("/challenge/puzzle/accessible?seed={}", encoded))
+            .body(Vec::new())
+            .build();
+        let cfg = crate::config::defaults();
+        let resp = serve_accessible_challenge_page(&req, cfg);
+        assert_eq!(*resp.status(), 200u16);
+        let body = String::from_utf8(resp.into_body()).unwrap();
+        let mut text_seed = seed.clone();
+        text_seed.variant = ChallengeVariant::Text;
+        let english = crate::i18n::Messages::new("en", &cfg.locale_overrides);
+        assert!(body.contains(build_question(&text_seed).prompt(&english).as_str()));
+        assert!(body.contains(make_seed_token(&text_seed).as_str()));
+        assert!(body.contains("name=\"answer\""));
+
+        let missing = Request::builder()
+            .method(Method::Get)
+            .uri("/challenge/puzzle/accessible")
+            .body(Vec::new())
+            .build();
+        assert_eq!(
+            *serve_accessible_challenge_page(&missing, cfg).status(),
+            403u16
+        );
+    }
+
+    #[test]
+    fn accessible_submit_accepts_correct_answer_and_sets_clearance() {
+        let store = TestStore::default();
+        let seed = accessible_test_seed("ab03cd", 31337, ChallengeVariant::Text);
+        let answer = build_question(&seed).answer;
+        let req = form_post(
+            "/challenge/puzzle/accessible",
+            format!("seed={}&answer={}", make_seed_token(&seed), answer),
+        );
+        let (resp, outcome) =
+            handle_accessible_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::Solved);
+        assert_eq!(*resp.status(), 200u16);
+        assert!(header_value(&resp, "Set-Cookie").is_some());
+    }
+
+    #[test]
+    fn accessible_solves_get_short_clearance_and_are_capped_per_ip_bucket() {
+        let store = TestStore::default();
+        let seed = accessible_test_seed("ab08cd", 2718, ChallengeVariant::Text);
+        let answer = build_question(&seed).answer;
+        let req = form_post(
+            "/challenge/puzzle/accessible",
+            format!("seed={}&answer={}", make_seed_token(&seed), answer),
+        );
+        let (resp, outcome) =
+            handle_accessible_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::Solved);
+        let cookie = header_value(&resp, "Set-Cookie").unwrap();
+        assert!(cookie.contains(&format!(
+            "Max-Age={}",
+            crate::challenge::puzzle::ACCESSIBLE_CLEARANCE_TTL_SECONDS
+        )));
+        let token = cookie
+            .split(';')
+            .next()
+            .and_then(|pair| pair.split_once('='))
+            .map(|(_, token)| token.to_string())
+            .unwrap();
+        let cleared = Request::builder()
+            .method(Method::Get)
+            .uri("/")
+            .header(
+                "cookie",
+                format!(
+                    "{}={}",
+                    crate::challenge::clearance::CLEARANCE_COOKIE_NAME,
+                    token
+                ),
+            )
+            .body(Vec::new())
+            .build();
+        let level = crate::challenge::clearance::request_clearance_level(&cleared, "unknown", "");
+        assert_eq!(
+            level,
+            Some(crate::challenge::clearance::ClearanceLevel::ChallengeAccessible)
+        );
+        assert!(level > Some(crate::challenge::clearance::ClearanceLevel::NotABot));
+        assert!(level < Some(crate::challenge::clearance::ClearanceLevel::ChallengeStrong));
+
+        let window = crate::admin::now_ts() / 3_600;
+        super::super::KeyValueStore::set(
+            &store,
+            crate::challenge::puzzle::accessible_solves_key(seed.ip_bucket.as_str()).as_str(),
+            format!(
+                "{}:{}",
+                window,
+                crate::challenge::puzzle::ACCESSIBLE_SOLVES_PER_WINDOW
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+        let capped = accessible_test_seed("ab09cd", 2719, ChallengeVariant::Text);
+        let answer = build_question(&capped).answer;
+        let req = form_post(
+            "/challenge/puzzle/accessible",
+            format!("seed={}&answer={}", make_seed_token(&capped), answer),
+        );
+        let (resp, outcome) =
+            handle_accessible_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::RateLimited);
+        assert_eq!(*resp.status(), 403u16);
+        assert!(header_value(&resp, "Set-Cookie").is_none());
+    }
+
+    #[test]
+    fn accessible_submit_rejects_wrong_answer() {
+        let store = TestStore::default();
+        let seed = accessible_test_seed("ab04cd", 4242, ChallengeVariant::Text);
+        let req = form_post(
+            "/challenge/puzzle/accessible",
+            format!("seed={}&answer=not-the-answer", make_seed_token(&seed)),
+        );
+        let (resp, outcome) =
+            handle_accessible_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::Incorrect);
+        assert_eq!(*resp.status(), 403u16);
+    }
+
+    #[test]
+    fn submit_endpoints_reject_seeds_for_the_other_variant() {
+        let store = TestStore::default();
+        let grid_seed = accessible_test_seed("ab05cd", 11, ChallengeVariant::Grid);
+        let req = form_post(
+            "/challenge/puzzle/accessible",
+            format!("seed={}&answer=7", make_seed_token(&grid_seed)),
+        );
+        let (_resp, outcome) =
+            handle_accessible_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::Forbidden);
+
+        let text_seed = accessible_test_seed("ab06cd", 12, ChallengeVariant::Text);
+        let req = form_post(
+            "/challenge/puzzle",
+            format!(
+                "seed={}&output=0000000000000000",
+                make_seed_token(&text_seed)
+            ),
+        );
+        let (_resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::Forbidden);
+    }
+
+    #[test]
+    fn grid_and_text_variants_share_one_operation() {
+        let store = TestStore::default();
+        let grid_seed = accessible_test_seed("ab07cd", 909, ChallengeVariant::Grid);
+        let mut text_seed = grid_seed.clone();
+        text_seed.variant = ChallengeVariant::Text;
+        let answer = build_question(&text_seed).answer;
+        let req = form_post(
+            "/challenge/puzzle/accessible",
+            format!("seed={}&answer={}", make_seed_token(&text_seed), answer),
+        );
+        let (_resp, outcome) =
+            handle_accessible_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::Solved);
+
+        let output = grid_to_tritstring(&build_puzzle(&grid_seed).test_output);
+        let req = form_post(
+            "/challenge/puzzle",
+            format!("seed={}&output={}", make_seed_token(&grid_seed), output),
+        );
+        let (_resp, outcome) =
+            handle_challenge_submit_with_outcome(&store, &req, crate::config::defaults());
+        assert_eq!(outcome, ChallengeSubmitOutcome::SequenceOpReplay);
+    }
 }
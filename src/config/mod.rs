@@ -4,7 +4,11 @@
 
 #[cfg(not(test))]
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::{collections::HashMap, env, sync::Mutex};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    sync::Mutex,
+};
 
 use once_cell::sync::Lazy;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -17,6 +21,12 @@ pub const POW_DIFFICULTY_MIN: u8 = 12;
 pub const POW_DIFFICULTY_MAX: u8 = 20;
 pub const POW_TTL_MIN: u64 = 30;
 pub const POW_TTL_MAX: u64 = 300;
+pub const POW_SCRYPT_MEMORY_KIB_MIN: u32 = 1024;
+pub const POW_SCRYPT_MEMORY_KIB_MAX: u32 = 16384;
+pub const POW_SCRYPT_DIFFICULTY_MIN: u8 = 1;
+pub const POW_SCRYPT_DIFFICULTY_MAX: u8 = 10;
+pub const POW_ADAPTIVE_MAX_EXTRA_BITS_MAX: u8 = 8;
+pub const POW_ADAPTIVE_LOAD_THRESHOLD_MAX: u32 = 1_000_000;
 const MAZE_MICRO_POW_DIFFICULTY_MIN: u8 = 10;
 const MAZE_MICRO_POW_DIFFICULTY_MAX: u8 = 24;
 const CHALLENGE_THRESHOLD_MIN: u8 = 1;
@@ -37,6 +47,7 @@ const NOT_A_BOT_ATTEMPT_LIMIT_MIN: u32 = 1;
 const NOT_A_BOT_ATTEMPT_LIMIT_MAX: u32 = 100;
 const NOT_A_BOT_ATTEMPT_WINDOW_MIN: u64 = 30;
 const NOT_A_BOT_ATTEMPT_WINDOW_MAX: u64 = 3600;
+const ALERT_EVALUATION_INTERVAL_MIN_SECONDS: u64 = 30;
 #[cfg(not(test))]
 const CONFIG_CACHE_TTL_SECONDS: u64 = 2;
 
@@ -72,6 +83,20 @@ pub struct BotnessWeights {
     pub rate_high: u8,
     #[serde(default = "default_botness_weight_maze_behavior")]
     pub maze_behavior: u8,
+    #[serde(default = "default_botness_weight_behavior_timing_regular")]
+    pub behavior_timing_regular: u8,
+    #[serde(default = "default_botness_weight_behavior_asset_ratio")]
+    pub behavior_asset_ratio: u8,
+    #[serde(default = "default_botness_weight_behavior_referrer_chain")]
+    pub behavior_referrer_chain: u8,
+    #[serde(default = "default_botness_weight_behavior_breadth_first")]
+    pub behavior_breadth_first: u8,
+    #[serde(default = "default_botness_weight_behavior_sitemap_order")]
+    pub behavior_sitemap_order: u8,
+    #[serde(default = "default_botness_weight_privacy_pass_attested")]
+    pub privacy_pass_attested: u8,
+    #[serde(default = "default_botness_weight_robots_violation")]
+    pub robots_violation: u8,
 }
 
 impl Default for BotnessWeights {
@@ -82,6 +107,13 @@ impl Default for BotnessWeights {
             rate_medium: default_botness_weight_rate_medium(),
             rate_high: default_botness_weight_rate_high(),
             maze_behavior: default_botness_weight_maze_behavior(),
+            behavior_timing_regular: default_botness_weight_behavior_timing_regular(),
+            behavior_asset_ratio: default_botness_weight_behavior_asset_ratio(),
+            behavior_referrer_chain: default_botness_weight_behavior_referrer_chain(),
+            behavior_breadth_first: default_botness_weight_behavior_breadth_first(),
+            behavior_sitemap_order: default_botness_weight_behavior_sitemap_order(),
+            privacy_pass_attested: default_botness_weight_privacy_pass_attested(),
+            robots_violation: default_botness_weight_robots_violation(),
         }
     }
 }
@@ -193,6 +225,125 @@ impl CdpProbeFamily {
     }
 }
 
+/// What the declared-crawler stage does with an AI crawler whose category robots.txt disallows.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AiCrawlerAction {
+    #[default]
+    Allow,
+    Block,
+    Maze,
+    PaymentRequired,
+}
+
+impl AiCrawlerAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AiCrawlerAction::Allow => "allow",
+            AiCrawlerAction::Block => "block",
+            AiCrawlerAction::Maze => "maze",
+            AiCrawlerAction::PaymentRequired => "payment_required",
+        }
+    }
+}
+
+/// What happens to a request whose Web Bot Auth signature verifies for a configured agent.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifiedAgentAction {
+    #[default]
+    Allow,
+    RateLimit,
+    PaymentRequired,
+}
+
+impl VerifiedAgentAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VerifiedAgentAction::Allow => "allow",
+            VerifiedAgentAction::RateLimit => "rate_limit",
+            VerifiedAgentAction::PaymentRequired => "payment_required",
+        }
+    }
+}
+
+/// Crawler catalog category; decides which robots.txt stance applies to the crawler.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CrawlerCategory {
+    #[default]
+    AiTraining,
+    AiSearch,
+    SearchEngine,
+}
+
+impl CrawlerCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CrawlerCategory::AiTraining => "ai_training",
+            CrawlerCategory::AiSearch => "ai_search",
+            CrawlerCategory::SearchEngine => "search_engine",
+        }
+    }
+}
+
+/// How a `User-Agent` claim to be a catalogued crawler is checked before it is honoured.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CrawlerVerification {
+    /// The `User-Agent` match alone identifies the crawler.
+    #[default]
+    None,
+    /// The client IP must also fall in the managed IP range set named by `ip_range_set`.
+    IpRange,
+}
+
+/// A crawler known to robots.txt generation, decoy suppression and the declared-crawler stage.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct CrawlerCatalogEntry {
+    /// robots.txt `User-agent` token, also used as the metrics and event label.
+    #[serde(default)]
+    pub name: String,
+    /// Case-insensitive `User-Agent` substrings. When empty, `name` is matched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ua_patterns: Vec<String>,
+    #[serde(default)]
+    pub category: CrawlerCategory,
+    #[serde(default)]
+    pub verification: CrawlerVerification,
+    /// Managed IP range set id, required when `verification` is `ip_range`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub ip_range_set: String,
+    /// Replaces `ai_crawler_action` for this crawler when robots.txt disallows it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<AiCrawlerAction>,
+    /// Replaces `robots_crawl_delay` in this crawler's robots.txt group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crawl_delay: Option<u32>,
+}
+
+/// Hash function behind the JS-verification proof-of-work.
+///
+/// `sha256` is cheap per attempt and scales with difficulty alone; `scrypt` makes every
+/// attempt cost `pow_scrypt_memory_kib` of memory, which narrows the gap between browsers
+/// and GPU/ASIC farms.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PowAlgorithm {
+    #[default]
+    Sha256,
+    Scrypt,
+}
+
+impl PowAlgorithm {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PowAlgorithm::Sha256 => "sha256",
+            PowAlgorithm::Scrypt => "scrypt",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum MazeRolloutPhase {
@@ -323,6 +474,111 @@ pub struct IpRangeManagedPolicy {
     pub custom_message: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TlsFingerprintAction {
+    Allow,
+    #[default]
+    Score,
+    Block,
+}
+
+impl TlsFingerprintAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TlsFingerprintAction::Allow => "allow",
+            TlsFingerprintAction::Score => "score",
+            TlsFingerprintAction::Block => "block",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TlsFingerprintRule {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub ja3: Vec<String>,
+    #[serde(default)]
+    pub ja4: Vec<String>,
+    #[serde(default)]
+    pub action: TlsFingerprintAction,
+}
+
+/// A Privacy Pass issuer whose publicly verifiable (blind RSA) tokens we redeem.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrivacyPassIssuer {
+    /// Issuer host name as it appears in the TokenChallenge, e.g. `demo-pat.issuer.cloudflare.com`.
+    #[serde(default)]
+    pub name: String,
+    /// Base64url SubjectPublicKeyInfo of the issuer's 2048-bit token key.
+    #[serde(default)]
+    pub token_key: String,
+}
+
+/// A payment provider whose Ed25519-signed crawl-payment tokens we accept.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct CrawlPaymentIssuer {
+    /// Matched against the token's `kid` header.
+    #[serde(default)]
+    pub name: String,
+    /// Base64url raw Ed25519 public key.
+    #[serde(default)]
+    pub key: String,
+}
+
+/// A curated `llms.txt` section: a heading followed by a list of links.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct LlmsTxtSection {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub links: Vec<LlmsTxtLink>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct LlmsTxtLink {
+    #[serde(default)]
+    pub title: String,
+    /// Site path (`/docs/start.md`) or absolute http(s) URL.
+    #[serde(default)]
+    pub url: String,
+    /// Optional one-line description rendered after the link.
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// An agent whose RFC 9421 HTTP Message Signatures (Web Bot Auth) we verify.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct WebBotAuthAgent {
+    /// Label used in events and metrics.
+    #[serde(default)]
+    pub name: String,
+    /// Origin the agent sends in `Signature-Agent`, e.g. `https://chatgpt.com`.
+    #[serde(default)]
+    pub directory: String,
+    /// Pinned base64url Ed25519 public keys. When empty, the agent's key directory is fetched.
+    #[serde(default)]
+    pub keys: Vec<String>,
+    #[serde(default)]
+    pub action: VerifiedAgentAction,
+    /// Requests per minute across all of the agent's traffic when `action` is `rate_limit`.
+    #[serde(default)]
+    pub rate_limit: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct TlsFingerprintManagedPolicy {
+    #[serde(default)]
+    pub set_id: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub action: TlsFingerprintAction,
+}
+
 /// Per-capability provider backend selections.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ProviderBackends {
@@ -419,6 +675,18 @@ pub struct Config {
     pub honeypot_enabled: bool,
     #[serde(default = "default_honeypots")]
     pub honeypots: Vec<String>,
+    #[serde(default = "default_honeypot_rotation_enabled")]
+    pub honeypot_rotation_enabled: bool,
+    #[serde(default = "default_honeypot_rotation_interval_seconds")]
+    pub honeypot_rotation_interval_seconds: u64,
+    #[serde(default = "default_honeypot_rotation_grace_seconds")]
+    pub honeypot_rotation_grace_seconds: u64,
+    #[serde(default = "default_form_honeypot_enabled")]
+    pub form_honeypot_enabled: bool,
+    #[serde(default = "default_form_honeypot_min_submit_seconds")]
+    pub form_honeypot_min_submit_seconds: u64,
+    #[serde(default = "default_form_honeypot_auto_ban")]
+    pub form_honeypot_auto_ban: bool,
     #[serde(default = "default_browser_block")]
     pub browser_block: Vec<(String, u32)>,
     #[serde(default = "default_browser_whitelist")]
@@ -523,6 +791,24 @@ pub struct Config {
     pub robots_allow_search_engines: bool,
     #[serde(default = "default_robots_crawl_delay")]
     pub robots_crawl_delay: u32,
+    #[serde(default = "default_ai_crawler_action")]
+    pub ai_crawler_action: AiCrawlerAction,
+    #[serde(default = "default_crawl_payment_price_micros")]
+    pub crawl_payment_price_micros: u64,
+    #[serde(default = "default_crawl_payment_currency")]
+    pub crawl_payment_currency: String,
+    #[serde(default = "default_crawl_payment_issuers")]
+    pub crawl_payment_issuers: Vec<CrawlPaymentIssuer>,
+    #[serde(default = "default_crawler_catalog")]
+    pub crawler_catalog: Vec<CrawlerCatalogEntry>,
+    #[serde(default = "default_llms_txt_title")]
+    pub llms_txt_title: String,
+    #[serde(default = "default_llms_txt_summary")]
+    pub llms_txt_summary: String,
+    #[serde(default = "default_llms_txt_sections")]
+    pub llms_txt_sections: Vec<LlmsTxtSection>,
+    #[serde(default = "default_tdm_policy_url")]
+    pub tdm_policy_url: String,
     #[serde(default = "default_cdp_detection_enabled")]
     pub cdp_detection_enabled: bool,
     #[serde(default = "default_cdp_auto_ban")]
@@ -555,6 +841,22 @@ pub struct Config {
     pub fingerprint_family_cap_persistence: u8,
     #[serde(default = "default_fingerprint_family_cap_behavior")]
     pub fingerprint_family_cap_behavior: u8,
+    #[serde(default = "default_fingerprint_tls_custom_rules")]
+    pub fingerprint_tls_custom_rules: Vec<TlsFingerprintRule>,
+    #[serde(default = "default_fingerprint_tls_managed_policies")]
+    pub fingerprint_tls_managed_policies: Vec<TlsFingerprintManagedPolicy>,
+    #[serde(default = "default_behavior_signal_enabled")]
+    pub behavior_signal_enabled: bool,
+    #[serde(default = "default_behavior_family_cap")]
+    pub behavior_family_cap: u8,
+    #[serde(default = "default_privacy_pass_enabled")]
+    pub privacy_pass_enabled: bool,
+    #[serde(default = "default_privacy_pass_issuers")]
+    pub privacy_pass_issuers: Vec<PrivacyPassIssuer>,
+    #[serde(default = "default_web_bot_auth_enabled")]
+    pub web_bot_auth_enabled: bool,
+    #[serde(default = "default_web_bot_auth_agents")]
+    pub web_bot_auth_agents: Vec<WebBotAuthAgent>,
     #[serde(default = "default_js_required_enforced")]
     pub js_required_enforced: bool,
     #[serde(default = "default_pow_enabled")]
@@ -563,6 +865,18 @@ pub struct Config {
     pub pow_difficulty: u8,
     #[serde(default = "default_pow_ttl_seconds")]
     pub pow_ttl_seconds: u64,
+    #[serde(default = "default_pow_algorithm")]
+    pub pow_algorithm: PowAlgorithm,
+    #[serde(default = "default_pow_scrypt_memory_kib")]
+    pub pow_scrypt_memory_kib: u32,
+    #[serde(default = "default_pow_scrypt_difficulty")]
+    pub pow_scrypt_difficulty: u8,
+    #[serde(default = "default_pow_adaptive_enabled")]
+    pub pow_adaptive_enabled: bool,
+    #[serde(default = "default_pow_adaptive_max_extra_bits")]
+    pub pow_adaptive_max_extra_bits: u8,
+    #[serde(default = "default_pow_adaptive_load_threshold_per_minute")]
+    pub pow_adaptive_load_threshold_per_minute: u32,
     #[serde(default = "default_challenge_puzzle_enabled")]
     pub challenge_puzzle_enabled: bool,
     #[serde(default = "default_challenge_puzzle_transform_count")]
@@ -585,6 +899,8 @@ pub struct Config {
     pub not_a_bot_attempt_limit_per_window: u32,
     #[serde(default = "default_not_a_bot_attempt_window_seconds")]
     pub not_a_bot_attempt_window_seconds: u64,
+    #[serde(default = "default_locale_overrides")]
+    pub locale_overrides: BTreeMap<String, BTreeMap<String, String>>,
     #[serde(default = "default_maze_threshold")]
     pub botness_maze_threshold: u8,
     #[serde(default)]
@@ -605,7 +921,10 @@ struct CachedConfig {
 
 impl Config {
     /// Loads config for a site from KV only.
-    pub fn load(store: &impl KeyValueStore, site_id: &str) -> Result<Self, ConfigLoadError> {
+    pub fn load(
+        store: &(impl KeyValueStore + ?Sized),
+        site_id: &str,
+    ) -> Result<Self, ConfigLoadError> {
         let key = format!("config:{}", site_id);
         let val = store
             .get(&key)
@@ -765,7 +1084,7 @@ fn now_ts() -> u64 {
 }
 
 fn load_cached_with_now(
-    store: &impl KeyValueStore,
+    store: &(impl KeyValueStore + ?Sized),
     site_id: &str,
     now: u64,
     ttl_seconds: u64,
@@ -793,7 +1112,7 @@ fn load_cached_with_now(
 }
 
 pub fn load_runtime_cached(
-    store: &impl KeyValueStore,
+    store: &(impl KeyValueStore + ?Sized),
     site_id: &str,
 ) -> Result<Config, ConfigLoadError> {
     #[cfg(test)]
@@ -843,6 +1162,12 @@ static DEFAULT_CONFIG: Lazy<Config> = Lazy::new(|| {
         rate_limit: defaults_u32("SHUMA_RATE_LIMIT"),
         honeypot_enabled: defaults_bool("SHUMA_HONEYPOT_ENABLED"),
         honeypots: defaults_string_list("SHUMA_HONEYPOTS"),
+        honeypot_rotation_enabled: defaults_bool("SHUMA_HONEYPOT_ROTATION_ENABLED"),
+        honeypot_rotation_interval_seconds: defaults_u64("SHUMA_HONEYPOT_ROTATION_INTERVAL_SECONDS"),
+        honeypot_rotation_grace_seconds: defaults_u64("SHUMA_HONEYPOT_ROTATION_GRACE_SECONDS"),
+        form_honeypot_enabled: defaults_bool("SHUMA_FORM_HONEYPOT_ENABLED"),
+        form_honeypot_min_submit_seconds: defaults_u64("SHUMA_FORM_HONEYPOT_MIN_SUBMIT_SECONDS"),
+        form_honeypot_auto_ban: defaults_bool("SHUMA_FORM_HONEYPOT_AUTO_BAN"),
         browser_block: defaults_browser_rules("SHUMA_BROWSER_BLOCK"),
         browser_whitelist: defaults_browser_rules("SHUMA_BROWSER_WHITELIST"),
         geo_risk: defaults_country_list("SHUMA_GEO_RISK_COUNTRIES"),
@@ -899,6 +1224,15 @@ static DEFAULT_CONFIG: Lazy<Config> = Lazy::new(|| {
         robots_block_ai_search: defaults_bool("SHUMA_ROBOTS_BLOCK_AI_SEARCH"),
         robots_allow_search_engines: defaults_bool("SHUMA_ROBOTS_ALLOW_SEARCH_ENGINES"),
         robots_crawl_delay: defaults_u32("SHUMA_ROBOTS_CRAWL_DELAY"),
+        ai_crawler_action: default_ai_crawler_action(),
+        crawl_payment_price_micros: defaults_u64("SHUMA_CRAWL_PAYMENT_PRICE_MICROS"),
+        crawl_payment_currency: defaults_raw("SHUMA_CRAWL_PAYMENT_CURRENCY"),
+        crawl_payment_issuers: defaults_json("SHUMA_CRAWL_PAYMENT_ISSUERS"),
+        crawler_catalog: defaults_json("SHUMA_CRAWLER_CATALOG"),
+        llms_txt_title: defaults_raw("SHUMA_LLMS_TXT_TITLE"),
+        llms_txt_summary: defaults_raw("SHUMA_LLMS_TXT_SUMMARY"),
+        llms_txt_sections: defaults_json("SHUMA_LLMS_TXT_SECTIONS"),
+        tdm_policy_url: defaults_raw("SHUMA_TDM_POLICY_URL"),
         cdp_detection_enabled: defaults_bool("SHUMA_CDP_DETECTION_ENABLED"),
         cdp_auto_ban: defaults_bool("SHUMA_CDP_AUTO_BAN"),
         cdp_detection_threshold: defaults_f32("SHUMA_CDP_DETECTION_THRESHOLD"),
@@ -921,10 +1255,26 @@ static DEFAULT_CONFIG: Lazy<Config> = Lazy::new(|| {
             "SHUMA_FINGERPRINT_FAMILY_CAP_PERSISTENCE",
         ),
         fingerprint_family_cap_behavior: defaults_u8("SHUMA_FINGERPRINT_FAMILY_CAP_BEHAVIOR"),
+        fingerprint_tls_custom_rules: defaults_json("SHUMA_FINGERPRINT_TLS_CUSTOM_RULES"),
+        fingerprint_tls_managed_policies: defaults_json("SHUMA_FINGERPRINT_TLS_MANAGED_POLICIES"),
+        behavior_signal_enabled: defaults_bool("SHUMA_BEHAVIOR_SIGNAL_ENABLED"),
+        behavior_family_cap: defaults_u8("SHUMA_BEHAVIOR_FAMILY_CAP"),
+        privacy_pass_enabled: defaults_bool("SHUMA_PRIVACY_PASS_ENABLED"),
+        privacy_pass_issuers: defaults_json("SHUMA_PRIVACY_PASS_ISSUERS"),
+        web_bot_auth_enabled: defaults_bool("SHUMA_WEB_BOT_AUTH_ENABLED"),
+        web_bot_auth_agents: defaults_json("SHUMA_WEB_BOT_AUTH_AGENTS"),
         js_required_enforced: defaults_bool("SHUMA_JS_REQUIRED_ENFORCED"),
         pow_enabled: defaults_bool("SHUMA_POW_ENABLED"),
         pow_difficulty: defaults_u8("SHUMA_POW_DIFFICULTY"),
         pow_ttl_seconds: defaults_u64("SHUMA_POW_TTL_SECONDS"),
+        pow_algorithm: default_pow_algorithm(),
+        pow_scrypt_memory_kib: defaults_u32("SHUMA_POW_SCRYPT_MEMORY_KIB"),
+        pow_scrypt_difficulty: defaults_u8("SHUMA_POW_SCRYPT_DIFFICULTY"),
+        pow_adaptive_enabled: defaults_bool("SHUMA_POW_ADAPTIVE_ENABLED"),
+        pow_adaptive_max_extra_bits: defaults_u8("SHUMA_POW_ADAPTIVE_MAX_EXTRA_BITS"),
+        pow_adaptive_load_threshold_per_minute: defaults_u32(
+            "SHUMA_POW_ADAPTIVE_LOAD_THRESHOLD_PER_MINUTE",
+        ),
         challenge_puzzle_enabled: defaults_bool("SHUMA_CHALLENGE_PUZZLE_ENABLED"),
         challenge_puzzle_transform_count: defaults_u8("SHUMA_CHALLENGE_PUZZLE_TRANSFORM_COUNT"),
         challenge_puzzle_risk_threshold: defaults_u8("SHUMA_CHALLENGE_PUZZLE_RISK_THRESHOLD"),
@@ -936,6 +1286,7 @@ static DEFAULT_CONFIG: Lazy<Config> = Lazy::new(|| {
         not_a_bot_marker_ttl_seconds: defaults_u64("SHUMA_NOT_A_BOT_MARKER_TTL_SECONDS"),
         not_a_bot_attempt_limit_per_window: defaults_u32("SHUMA_NOT_A_BOT_ATTEMPT_LIMIT_PER_WINDOW"),
         not_a_bot_attempt_window_seconds: defaults_u64("SHUMA_NOT_A_BOT_ATTEMPT_WINDOW_SECONDS"),
+        locale_overrides: defaults_json("SHUMA_LOCALE_OVERRIDES"),
         botness_maze_threshold: defaults_u8("SHUMA_BOTNESS_MAZE_THRESHOLD"),
         botness_weights: BotnessWeights {
             js_required: defaults_u8("SHUMA_BOTNESS_WEIGHT_JS_REQUIRED"),
@@ -943,6 +1294,13 @@ static DEFAULT_CONFIG: Lazy<Config> = Lazy::new(|| {
             rate_medium: defaults_u8("SHUMA_BOTNESS_WEIGHT_RATE_MEDIUM"),
             rate_high: defaults_u8("SHUMA_BOTNESS_WEIGHT_RATE_HIGH"),
             maze_behavior: defaults_u8("SHUMA_BOTNESS_WEIGHT_MAZE_BEHAVIOR"),
+            behavior_timing_regular: defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_TIMING_REGULAR"),
+            behavior_asset_ratio: defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_ASSET_RATIO"),
+            behavior_referrer_chain: defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_REFERRER_CHAIN"),
+            behavior_breadth_first: defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_BREADTH_FIRST"),
+            behavior_sitemap_order: defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_SITEMAP_ORDER"),
+            privacy_pass_attested: defaults_u8("SHUMA_BOTNESS_WEIGHT_PRIVACY_PASS_ATTESTED"),
+            robots_violation: defaults_u8("SHUMA_BOTNESS_WEIGHT_ROBOTS_VIOLATION"),
         },
         defence_modes: DefenceModes::default(),
         provider_backends: ProviderBackends::default(),
@@ -991,10 +1349,37 @@ fn validate_env_only_impl() -> Result<(), String> {
     validate_optional_redis_url_var("SHUMA_BAN_STORE_REDIS_URL")?;
     validate_optional_rate_limiter_outage_mode_var("SHUMA_RATE_LIMITER_OUTAGE_MODE_MAIN")?;
     validate_optional_rate_limiter_outage_mode_var("SHUMA_RATE_LIMITER_OUTAGE_MODE_ADMIN_AUTH")?;
+    validate_optional_otlp_endpoint_var("SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT")?;
+    validate_optional_otlp_headers_var("SHUMA_OTEL_EXPORTER_OTLP_HEADERS")?;
+    validate_optional_ratio_var("SHUMA_OTEL_TRACES_SAMPLE_RATIO")?;
+    validate_optional_u64_var("SHUMA_OTEL_METRICS_EXPORT_INTERVAL_SECONDS")?;
+    validate_optional_alert_webhook_url_var("SHUMA_ALERT_WEBHOOK_URL")?;
+    validate_optional_u64_var("SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS")?;
 
     Ok(())
 }
 
+const REQUIRED_ENV_VARS: [&str; 8] = [
+    "SHUMA_API_KEY",
+    "SHUMA_JS_SECRET",
+    "SHUMA_FORWARDED_IP_SECRET",
+    "SHUMA_EVENT_LOG_RETENTION_HOURS",
+    "SHUMA_ADMIN_CONFIG_WRITE_ENABLED",
+    "SHUMA_KV_STORE_FAIL_OPEN",
+    "SHUMA_ENFORCE_HTTPS",
+    "SHUMA_DEBUG_HEADERS",
+];
+
+/// Fills unset required env vars from `config/defaults.env` so offline tools (the replay
+/// CLI) can run the request pipeline outside Spin. Never called from the Spin component.
+pub(crate) fn seed_required_env_from_defaults() {
+    for name in REQUIRED_ENV_VARS {
+        if env::var_os(name).is_none() {
+            env::set_var(name, defaults_raw(name));
+        }
+    }
+}
+
 fn validate_env_in_tests_enabled() -> bool {
     if !cfg!(test) {
         return false;
@@ -1063,6 +1448,80 @@ fn validate_optional_rate_limiter_outage_mode_var(name: &str) -> Result<(), Stri
     Ok(())
 }
 
+fn validate_optional_otlp_endpoint_var(name: &str) -> Result<(), String> {
+    let Some(value) = env::var(name).ok() else {
+        return Ok(());
+    };
+    if value.trim().is_empty() {
+        return Ok(());
+    }
+    if parse_otlp_endpoint(&value).is_none() {
+        return Err(format!(
+            "Invalid OTLP endpoint env var {}={} (expected http://... or https://...)",
+            name, value
+        ));
+    }
+    Ok(())
+}
+
+fn validate_optional_alert_webhook_url_var(name: &str) -> Result<(), String> {
+    let Some(value) = env::var(name).ok() else {
+        return Ok(());
+    };
+    if value.trim().is_empty() {
+        return Ok(());
+    }
+    if parse_alert_webhook_url(&value).is_none() {
+        return Err(format!(
+            "Invalid alert webhook URL env var {}={} (expected http://... or https://...)",
+            name, value
+        ));
+    }
+    Ok(())
+}
+
+fn validate_optional_otlp_headers_var(name: &str) -> Result<(), String> {
+    let Some(value) = env::var(name).ok() else {
+        return Ok(());
+    };
+    if parse_otlp_headers(&value).is_none() {
+        return Err(format!(
+            "Invalid OTLP headers env var {} (expected comma-separated name=value pairs)",
+            name
+        ));
+    }
+    Ok(())
+}
+
+fn validate_optional_ratio_var(name: &str) -> Result<(), String> {
+    let Some(value) = env::var(name).ok() else {
+        return Ok(());
+    };
+    if value.trim().is_empty() {
+        return Ok(());
+    }
+    if parse_ratio(&value).is_none() {
+        return Err(format!(
+            "Invalid ratio env var {}={} (expected a number between 0.0 and 1.0)",
+            name, value
+        ));
+    }
+    Ok(())
+}
+
+fn validate_optional_u64_var(name: &str) -> Result<(), String> {
+    let Some(value) = env::var(name).ok() else {
+        return Ok(());
+    };
+    if value.trim().is_empty() {
+        return Ok(());
+    }
+    if value.trim().parse::<u64>().is_err() {
+        return Err(format!("Invalid integer env var {}={}", name, value));
+    }
+    Ok(())
+}
+
 fn validate_u64_var(name: &str) -> Result<(), String> {
     let value = env::var(name).map_err(|_| format!("Missing required env var {}", name))?;
     if value.trim().parse::<u64>().is_err() {
@@ -1126,6 +1585,70 @@ pub fn ban_store_redis_url() -> Option<String> {
         .and_then(|value| parse_redis_url(&value))
 }
 
+pub fn otel_exporter_otlp_endpoint() -> Option<String> {
+    env::var("SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT")
+        .ok()
+        .and_then(|value| parse_otlp_endpoint(&value))
+}
+
+pub fn otel_exporter_otlp_headers() -> Vec<(String, String)> {
+    env::var("SHUMA_OTEL_EXPORTER_OTLP_HEADERS")
+        .ok()
+        .and_then(|value| parse_otlp_headers(&value))
+        .unwrap_or_default()
+}
+
+pub fn otel_traces_sample_ratio() -> f64 {
+    env::var("SHUMA_OTEL_TRACES_SAMPLE_RATIO")
+        .ok()
+        .and_then(|value| parse_ratio(&value))
+        .unwrap_or(1.0)
+}
+
+pub fn otel_metrics_export_interval_seconds() -> u64 {
+    env::var("SHUMA_OTEL_METRICS_EXPORT_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(60)
+}
+
+/// Shared secret for HS256 crawl-payment tokens; `None` when unset.
+pub fn crawl_payment_secret() -> Option<String> {
+    env::var("SHUMA_CRAWL_PAYMENT_SECRET")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Retired JS secrets still accepted when verifying clearance tokens during key rotation.
+pub fn js_secret_previous() -> Vec<String> {
+    env::var("SHUMA_JS_SECRET_PREVIOUS")
+        .ok()
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|secret| !secret.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn alert_webhook_url() -> Option<String> {
+    env::var("SHUMA_ALERT_WEBHOOK_URL")
+        .ok()
+        .and_then(|value| parse_alert_webhook_url(&value))
+}
+
+pub fn alert_evaluation_interval_seconds() -> u64 {
+    env::var("SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(60)
+        .max(ALERT_EVALUATION_INTERVAL_MIN_SECONDS)
+}
+
 fn env_rate_limiter_outage_mode(
     name: &str,
     default: RateLimiterOutageMode,
@@ -1171,6 +1694,62 @@ fn parse_redis_url(value: &str) -> Option<String> {
     }
 }
 
+/// Collector base URL; signal paths (`/v1/traces`, `/v1/metrics`) are appended at export time.
+pub(crate) fn parse_otlp_endpoint(value: &str) -> Option<String> {
+    let trimmed = value.trim().trim_end_matches('/');
+    let lower = trimmed.to_ascii_lowercase();
+    let host = lower
+        .strip_prefix("http://")
+        .or_else(|| lower.strip_prefix("https://"))?;
+    if host.is_empty() {
+        return None;
+    }
+    Some(trimmed.to_string())
+}
+
+/// Full webhook URL; unlike the OTLP endpoint it is posted to verbatim.
+pub(crate) fn parse_alert_webhook_url(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    let rest = lower
+        .strip_prefix("http://")
+        .or_else(|| lower.strip_prefix("https://"))?;
+    if rest.is_empty() || rest.starts_with('/') {
+        return None;
+    }
+    Some(trimmed.to_string())
+}
+
+/// Parses `OTEL_EXPORTER_OTLP_HEADERS`-style `name=value,name2=value2` lists.
+pub(crate) fn parse_otlp_headers(value: &str) -> Option<Vec<(String, String)>> {
+    let mut headers = Vec::new();
+    for pair in value.split(',') {
+        if pair.trim().is_empty() {
+            continue;
+        }
+        let (name, header_value) = pair.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty()
+            || !name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        {
+            return None;
+        }
+        headers.push((name.to_string(), header_value.trim().to_string()));
+    }
+    Some(headers)
+}
+
+fn parse_ratio(value: &str) -> Option<f64> {
+    let ratio = value.trim().parse::<f64>().ok()?;
+    if (0.0..=1.0).contains(&ratio) {
+        Some(ratio)
+    } else {
+        None
+    }
+}
+
 pub(crate) fn parse_composability_mode(value: &str) -> Option<ComposabilityMode> {
     match value.trim().to_ascii_lowercase().as_str() {
         "off" => Some(ComposabilityMode::Off),
@@ -1230,6 +1809,33 @@ pub(crate) fn parse_cdp_probe_family(value: &str) -> Option<CdpProbeFamily> {
     }
 }
 
+pub(crate) fn parse_pow_algorithm(value: &str) -> Option<PowAlgorithm> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "sha256" => Some(PowAlgorithm::Sha256),
+        "scrypt" => Some(PowAlgorithm::Scrypt),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_ai_crawler_action(value: &str) -> Option<AiCrawlerAction> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "allow" => Some(AiCrawlerAction::Allow),
+        "block" => Some(AiCrawlerAction::Block),
+        "maze" => Some(AiCrawlerAction::Maze),
+        "payment_required" => Some(AiCrawlerAction::PaymentRequired),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_verified_agent_action(value: &str) -> Option<VerifiedAgentAction> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "allow" => Some(VerifiedAgentAction::Allow),
+        "rate_limit" => Some(VerifiedAgentAction::RateLimit),
+        "payment_required" => Some(VerifiedAgentAction::PaymentRequired),
+        _ => None,
+    }
+}
+
 pub(crate) fn parse_maze_rollout_phase(value: &str) -> Option<MazeRolloutPhase> {
     match value.trim().to_ascii_lowercase().as_str() {
         "instrument" => Some(MazeRolloutPhase::Instrument),
@@ -1260,6 +1866,14 @@ pub fn event_log_retention_hours() -> u64 {
     env_u64_required("SHUMA_EVENT_LOG_RETENTION_HOURS")
 }
 
+/// HMAC key for challenge, report and trap tokens; falls back to `SHUMA_JS_SECRET`.
+pub fn challenge_secret() -> String {
+    match env::var("SHUMA_CHALLENGE_SECRET") {
+        Ok(secret) if !secret.trim().is_empty() => secret,
+        _ => env_string_required("SHUMA_JS_SECRET"),
+    }
+}
+
 pub fn env_string_required(name: &str) -> String {
     if cfg!(test) {
         return env::var(name).ok().unwrap_or_else(|| defaults_raw(name));
@@ -1304,6 +1918,25 @@ fn clamp_pow_ttl(value: u64) -> u64 {
     value.clamp(POW_TTL_MIN, POW_TTL_MAX)
 }
 
+/// Clamps to the supported range, then rounds down to a power of two because the scrypt
+/// cost parameter N is derived directly from it.
+pub(crate) fn clamp_pow_scrypt_memory_kib(value: u32) -> u32 {
+    let clamped = value.clamp(POW_SCRYPT_MEMORY_KIB_MIN, POW_SCRYPT_MEMORY_KIB_MAX);
+    1 << (31 - clamped.leading_zeros())
+}
+
+fn clamp_pow_scrypt_difficulty(value: u8) -> u8 {
+    value.clamp(POW_SCRYPT_DIFFICULTY_MIN, POW_SCRYPT_DIFFICULTY_MAX)
+}
+
+fn clamp_pow_adaptive_max_extra_bits(value: u8) -> u8 {
+    value.min(POW_ADAPTIVE_MAX_EXTRA_BITS_MAX)
+}
+
+fn clamp_pow_adaptive_load_threshold(value: u32) -> u32 {
+    value.min(POW_ADAPTIVE_LOAD_THRESHOLD_MAX)
+}
+
 fn clamp_challenge_threshold(value: u8) -> u8 {
     value.clamp(CHALLENGE_THRESHOLD_MIN, CHALLENGE_THRESHOLD_MAX)
 }
@@ -1343,6 +1976,12 @@ fn clamp_not_a_bot_attempt_window(value: u64) -> u64 {
 fn clamp_config_values(cfg: &mut Config) {
     cfg.pow_difficulty = clamp_pow_difficulty(cfg.pow_difficulty);
     cfg.pow_ttl_seconds = clamp_pow_ttl(cfg.pow_ttl_seconds);
+    cfg.pow_scrypt_memory_kib = clamp_pow_scrypt_memory_kib(cfg.pow_scrypt_memory_kib);
+    cfg.pow_scrypt_difficulty = clamp_pow_scrypt_difficulty(cfg.pow_scrypt_difficulty);
+    cfg.pow_adaptive_max_extra_bits =
+        clamp_pow_adaptive_max_extra_bits(cfg.pow_adaptive_max_extra_bits);
+    cfg.pow_adaptive_load_threshold_per_minute =
+        clamp_pow_adaptive_load_threshold(cfg.pow_adaptive_load_threshold_per_minute);
     cfg.challenge_puzzle_transform_count = clamp_challenge_puzzle_transform_count(cfg.challenge_puzzle_transform_count);
     cfg.challenge_puzzle_risk_threshold = clamp_challenge_threshold(cfg.challenge_puzzle_risk_threshold);
     cfg.not_a_bot_risk_threshold = clamp_challenge_threshold(cfg.not_a_bot_risk_threshold);
@@ -1368,6 +2007,14 @@ fn clamp_config_values(cfg: &mut Config) {
     cfg.botness_weights.rate_medium = clamp_botness_weight(cfg.botness_weights.rate_medium);
     cfg.botness_weights.rate_high = clamp_botness_weight(cfg.botness_weights.rate_high);
     cfg.botness_weights.maze_behavior = clamp_botness_weight(cfg.botness_weights.maze_behavior);
+    cfg.botness_weights.behavior_timing_regular = clamp_botness_weight(cfg.botness_weights.behavior_timing_regular);
+    cfg.botness_weights.behavior_asset_ratio = clamp_botness_weight(cfg.botness_weights.behavior_asset_ratio);
+    cfg.botness_weights.behavior_referrer_chain = clamp_botness_weight(cfg.botness_weights.behavior_referrer_chain);
+    cfg.botness_weights.behavior_breadth_first = clamp_botness_weight(cfg.botness_weights.behavior_breadth_first);
+    cfg.botness_weights.behavior_sitemap_order = clamp_botness_weight(cfg.botness_weights.behavior_sitemap_order);
+    cfg.botness_weights.privacy_pass_attested =
+        clamp_botness_weight(cfg.botness_weights.privacy_pass_attested);
+    cfg.botness_weights.robots_violation = clamp_botness_weight(cfg.botness_weights.robots_violation);
     cfg.maze_token_ttl_seconds = cfg.maze_token_ttl_seconds.clamp(30, 600);
     cfg.maze_token_max_depth = cfg.maze_token_max_depth.clamp(1, 32);
     cfg.maze_token_branch_budget = cfg.maze_token_branch_budget.clamp(1, 12);
@@ -1409,6 +2056,7 @@ fn clamp_config_values(cfg: &mut Config) {
     cfg.fingerprint_family_cap_persistence =
         clamp_botness_weight(cfg.fingerprint_family_cap_persistence);
     cfg.fingerprint_family_cap_behavior = clamp_botness_weight(cfg.fingerprint_family_cap_behavior);
+    cfg.behavior_family_cap = clamp_botness_weight(cfg.behavior_family_cap);
 }
 
 #[cfg(test)]
@@ -1641,6 +2289,30 @@ fn default_honeypots() -> Vec<String> {
     defaults_string_list("SHUMA_HONEYPOTS")
 }
 
+fn default_honeypot_rotation_enabled() -> bool {
+    defaults_bool("SHUMA_HONEYPOT_ROTATION_ENABLED")
+}
+
+fn default_honeypot_rotation_interval_seconds() -> u64 {
+    defaults_u64("SHUMA_HONEYPOT_ROTATION_INTERVAL_SECONDS")
+}
+
+fn default_honeypot_rotation_grace_seconds() -> u64 {
+    defaults_u64("SHUMA_HONEYPOT_ROTATION_GRACE_SECONDS")
+}
+
+fn default_form_honeypot_enabled() -> bool {
+    defaults_bool("SHUMA_FORM_HONEYPOT_ENABLED")
+}
+
+fn default_form_honeypot_min_submit_seconds() -> u64 {
+    defaults_u64("SHUMA_FORM_HONEYPOT_MIN_SUBMIT_SECONDS")
+}
+
+fn default_form_honeypot_auto_ban() -> bool {
+    defaults_bool("SHUMA_FORM_HONEYPOT_AUTO_BAN")
+}
+
 fn default_browser_block() -> Vec<(String, u32)> {
     defaults_browser_rules("SHUMA_BROWSER_BLOCK")
 }
@@ -1695,6 +2367,42 @@ fn default_ip_range_managed_policies() -> Vec<IpRangeManagedPolicy> {
     defaults_json("SHUMA_IP_RANGE_MANAGED_POLICIES")
 }
 
+fn default_locale_overrides() -> BTreeMap<String, BTreeMap<String, String>> {
+    defaults_json("SHUMA_LOCALE_OVERRIDES")
+}
+
+fn default_fingerprint_tls_custom_rules() -> Vec<TlsFingerprintRule> {
+    defaults_json("SHUMA_FINGERPRINT_TLS_CUSTOM_RULES")
+}
+
+fn default_fingerprint_tls_managed_policies() -> Vec<TlsFingerprintManagedPolicy> {
+    defaults_json("SHUMA_FINGERPRINT_TLS_MANAGED_POLICIES")
+}
+
+fn default_behavior_signal_enabled() -> bool {
+    defaults_bool("SHUMA_BEHAVIOR_SIGNAL_ENABLED")
+}
+
+fn default_behavior_family_cap() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BEHAVIOR_FAMILY_CAP"))
+}
+
+fn default_privacy_pass_enabled() -> bool {
+    defaults_bool("SHUMA_PRIVACY_PASS_ENABLED")
+}
+
+fn default_privacy_pass_issuers() -> Vec<PrivacyPassIssuer> {
+    defaults_json("SHUMA_PRIVACY_PASS_ISSUERS")
+}
+
+fn default_web_bot_auth_enabled() -> bool {
+    defaults_bool("SHUMA_WEB_BOT_AUTH_ENABLED")
+}
+
+fn default_web_bot_auth_agents() -> Vec<WebBotAuthAgent> {
+    defaults_json("SHUMA_WEB_BOT_AUTH_AGENTS")
+}
+
 fn default_ip_range_managed_max_staleness_hours() -> u64 {
     defaults_u64("SHUMA_IP_RANGE_MANAGED_MAX_STALENESS_HOURS")
 }
@@ -1863,6 +2571,16 @@ fn default_robots_crawl_delay() -> u32 {
     defaults_u32("SHUMA_ROBOTS_CRAWL_DELAY")
 }
 
+fn default_ai_crawler_action() -> AiCrawlerAction {
+    let raw = defaults_raw("SHUMA_AI_CRAWLER_ACTION");
+    parse_ai_crawler_action(raw.as_str()).unwrap_or_else(|| {
+        panic!(
+            "Invalid AI crawler action default for SHUMA_AI_CRAWLER_ACTION={}",
+            raw
+        )
+    })
+}
+
 fn default_cdp_detection_enabled() -> bool {
     defaults_bool("SHUMA_CDP_DETECTION_ENABLED")
 }
@@ -1875,6 +2593,38 @@ fn default_cdp_threshold() -> f32 {
     defaults_f32("SHUMA_CDP_DETECTION_THRESHOLD")
 }
 
+fn default_crawl_payment_price_micros() -> u64 {
+    defaults_u64("SHUMA_CRAWL_PAYMENT_PRICE_MICROS")
+}
+
+fn default_crawl_payment_currency() -> String {
+    defaults_raw("SHUMA_CRAWL_PAYMENT_CURRENCY")
+}
+
+fn default_crawl_payment_issuers() -> Vec<CrawlPaymentIssuer> {
+    defaults_json("SHUMA_CRAWL_PAYMENT_ISSUERS")
+}
+
+fn default_crawler_catalog() -> Vec<CrawlerCatalogEntry> {
+    defaults_json("SHUMA_CRAWLER_CATALOG")
+}
+
+fn default_llms_txt_title() -> String {
+    defaults_raw("SHUMA_LLMS_TXT_TITLE")
+}
+
+fn default_llms_txt_summary() -> String {
+    defaults_raw("SHUMA_LLMS_TXT_SUMMARY")
+}
+
+fn default_llms_txt_sections() -> Vec<LlmsTxtSection> {
+    defaults_json("SHUMA_LLMS_TXT_SECTIONS")
+}
+
+fn default_tdm_policy_url() -> String {
+    defaults_raw("SHUMA_TDM_POLICY_URL")
+}
+
 fn default_cdp_probe_family() -> CdpProbeFamily {
     let raw = defaults_raw("SHUMA_CDP_PROBE_FAMILY");
     parse_cdp_probe_family(raw.as_str()).unwrap_or_else(|| {
@@ -1949,6 +2699,38 @@ fn default_pow_ttl_seconds() -> u64 {
     clamp_pow_ttl(defaults_u64("SHUMA_POW_TTL_SECONDS"))
 }
 
+fn default_pow_algorithm() -> PowAlgorithm {
+    let raw = defaults_raw("SHUMA_POW_ALGORITHM");
+    parse_pow_algorithm(raw.as_str()).unwrap_or_else(|| {
+        panic!(
+            "Invalid PoW algorithm default for SHUMA_POW_ALGORITHM={}",
+            raw
+        )
+    })
+}
+
+fn default_pow_scrypt_memory_kib() -> u32 {
+    clamp_pow_scrypt_memory_kib(defaults_u32("SHUMA_POW_SCRYPT_MEMORY_KIB"))
+}
+
+fn default_pow_scrypt_difficulty() -> u8 {
+    clamp_pow_scrypt_difficulty(defaults_u8("SHUMA_POW_SCRYPT_DIFFICULTY"))
+}
+
+fn default_pow_adaptive_enabled() -> bool {
+    defaults_bool("SHUMA_POW_ADAPTIVE_ENABLED")
+}
+
+fn default_pow_adaptive_max_extra_bits() -> u8 {
+    clamp_pow_adaptive_max_extra_bits(defaults_u8("SHUMA_POW_ADAPTIVE_MAX_EXTRA_BITS"))
+}
+
+fn default_pow_adaptive_load_threshold_per_minute() -> u32 {
+    clamp_pow_adaptive_load_threshold(defaults_u32(
+        "SHUMA_POW_ADAPTIVE_LOAD_THRESHOLD_PER_MINUTE",
+    ))
+}
+
 fn default_challenge_puzzle_enabled() -> bool {
     defaults_bool("SHUMA_CHALLENGE_PUZZLE_ENABLED")
 }
@@ -2017,6 +2799,34 @@ fn default_botness_weight_maze_behavior() -> u8 {
     clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_MAZE_BEHAVIOR"))
 }
 
+fn default_botness_weight_behavior_timing_regular() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_TIMING_REGULAR"))
+}
+
+fn default_botness_weight_behavior_asset_ratio() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_ASSET_RATIO"))
+}
+
+fn default_botness_weight_behavior_referrer_chain() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_REFERRER_CHAIN"))
+}
+
+fn default_botness_weight_behavior_breadth_first() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_BREADTH_FIRST"))
+}
+
+fn default_botness_weight_behavior_sitemap_order() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_BEHAVIOR_SITEMAP_ORDER"))
+}
+
+fn default_botness_weight_privacy_pass_attested() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_PRIVACY_PASS_ATTESTED"))
+}
+
+fn default_botness_weight_robots_violation() -> u8 {
+    clamp_botness_weight(defaults_u8("SHUMA_BOTNESS_WEIGHT_ROBOTS_VIOLATION"))
+}
+
 fn defaults_composability_mode(key: &str) -> ComposabilityMode {
     let raw = defaults_raw(key);
     parse_composability_mode(raw.as_str())
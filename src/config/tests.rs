@@ -90,6 +90,32 @@ fn parse_botness_weight_clamps_range() {
     assert_eq!(parse_botness_weight(Some("junk"), 3), 3);
 }
 
+#[test]
+fn clamp_pow_scrypt_memory_kib_rounds_down_to_power_of_two() {
+    assert_eq!(clamp_pow_scrypt_memory_kib(0), 1024);
+    assert_eq!(clamp_pow_scrypt_memory_kib(6000), 4096);
+    assert_eq!(clamp_pow_scrypt_memory_kib(8192), 8192);
+    assert_eq!(clamp_pow_scrypt_memory_kib(1 << 20), 16384);
+}
+
+#[test]
+fn parse_pow_algorithm_accepts_expected_values() {
+    assert_eq!(parse_pow_algorithm("sha256"), Some(PowAlgorithm::Sha256));
+    assert_eq!(parse_pow_algorithm(" Scrypt "), Some(PowAlgorithm::Scrypt));
+    assert_eq!(parse_pow_algorithm("argon2id"), None);
+}
+
+#[test]
+fn parse_ai_crawler_action_accepts_expected_values() {
+    assert_eq!(parse_ai_crawler_action("allow"), Some(AiCrawlerAction::Allow));
+    assert_eq!(parse_ai_crawler_action(" MAZE "), Some(AiCrawlerAction::Maze));
+    assert_eq!(
+        parse_ai_crawler_action("payment_required"),
+        Some(AiCrawlerAction::PaymentRequired)
+    );
+    assert_eq!(parse_ai_crawler_action("tarpit"), None);
+}
+
 #[test]
 fn parse_composability_mode_accepts_expected_values() {
     assert_eq!(
@@ -264,6 +290,12 @@ fn defaults_enable_both_signal_and_action_paths() {
     assert_eq!(cfg.edge_integration_mode, EdgeIntegrationMode::Off);
     assert!(cfg.js_required_enforced);
     assert!(cfg.honeypot_enabled);
+    assert!(cfg.honeypot_rotation_enabled);
+    assert_eq!(cfg.honeypot_rotation_interval_seconds, 86_400);
+    assert_eq!(cfg.honeypot_rotation_grace_seconds, 86_400);
+    assert!(cfg.form_honeypot_enabled);
+    assert_eq!(cfg.form_honeypot_min_submit_seconds, 2);
+    assert!(!cfg.form_honeypot_auto_ban);
     assert!(cfg.challenge_puzzle_enabled);
     assert_eq!(cfg.defence_modes.js, ComposabilityMode::Both);
     assert_eq!(cfg.defence_modes.geo, ComposabilityMode::Both);
@@ -272,6 +304,34 @@ fn defaults_enable_both_signal_and_action_paths() {
     assert!(cfg.ip_range_emergency_allowlist.is_empty());
     assert!(cfg.ip_range_custom_rules.is_empty());
     assert!(cfg.ip_range_managed_policies.is_empty());
+    assert!(cfg.fingerprint_tls_custom_rules.is_empty());
+    assert!(cfg.fingerprint_tls_managed_policies.is_empty());
+    assert!(cfg.locale_overrides.is_empty());
+    assert_eq!(cfg.pow_algorithm, PowAlgorithm::Sha256);
+    assert_eq!(cfg.ai_crawler_action, AiCrawlerAction::Allow);
+    assert_eq!(cfg.crawl_payment_price_micros, 1000);
+    assert_eq!(cfg.crawl_payment_currency, "USD");
+    assert!(cfg.crawl_payment_issuers.is_empty());
+    assert_eq!(cfg.crawler_catalog.len(), 39);
+    assert!(cfg.llms_txt_title.is_empty());
+    assert!(cfg.llms_txt_summary.is_empty());
+    assert!(cfg.llms_txt_sections.is_empty());
+    assert!(cfg.tdm_policy_url.is_empty());
+    assert_eq!(cfg.pow_scrypt_memory_kib, 4096);
+    assert_eq!(cfg.pow_scrypt_difficulty, 4);
+    assert!(!cfg.pow_adaptive_enabled);
+    assert_eq!(cfg.pow_adaptive_max_extra_bits, 4);
+    assert_eq!(cfg.pow_adaptive_load_threshold_per_minute, 600);
+    assert!(cfg.behavior_signal_enabled);
+    assert_eq!(cfg.behavior_family_cap, 4);
+    assert_eq!(cfg.botness_weights.behavior_timing_regular, 2);
+    assert_eq!(cfg.botness_weights.behavior_asset_ratio, 1);
+    assert!(!cfg.privacy_pass_enabled);
+    assert!(cfg.privacy_pass_issuers.is_empty());
+    assert!(!cfg.web_bot_auth_enabled);
+    assert!(cfg.web_bot_auth_agents.is_empty());
+    assert_eq!(cfg.botness_weights.privacy_pass_attested, 4);
+    assert_eq!(cfg.botness_weights.robots_violation, 3);
     assert_eq!(cfg.ip_range_managed_max_staleness_hours, 168);
     assert!(!cfg.ip_range_allow_stale_managed_enforce);
     assert!(cfg.rate_signal_enabled());
@@ -764,6 +824,126 @@ fn validate_env_rejects_invalid_optional_rate_limiter_outage_mode() {
     ]);
 }
 
+#[test]
+fn validate_env_rejects_invalid_otel_exporter_settings() {
+    let _lock = crate::test_support::lock_env();
+    let keys = [
+        "SHUMA_VALIDATE_ENV_IN_TESTS",
+        "SHUMA_API_KEY",
+        "SHUMA_JS_SECRET",
+        "SHUMA_FORWARDED_IP_SECRET",
+        "SHUMA_EVENT_LOG_RETENTION_HOURS",
+        "SHUMA_ADMIN_CONFIG_WRITE_ENABLED",
+        "SHUMA_KV_STORE_FAIL_OPEN",
+        "SHUMA_ENFORCE_HTTPS",
+        "SHUMA_DEBUG_HEADERS",
+        "SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT",
+        "SHUMA_OTEL_EXPORTER_OTLP_HEADERS",
+        "SHUMA_OTEL_TRACES_SAMPLE_RATIO",
+        "SHUMA_OTEL_METRICS_EXPORT_INTERVAL_SECONDS",
+    ];
+    clear_env(&keys);
+
+    std::env::set_var("SHUMA_VALIDATE_ENV_IN_TESTS", "true");
+    std::env::set_var("SHUMA_API_KEY", "test-admin-key");
+    std::env::set_var("SHUMA_JS_SECRET", "test-js-secret");
+    std::env::set_var("SHUMA_FORWARDED_IP_SECRET", "test-forwarded-secret");
+    std::env::set_var("SHUMA_EVENT_LOG_RETENTION_HOURS", "168");
+    std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "false");
+    std::env::set_var("SHUMA_KV_STORE_FAIL_OPEN", "true");
+    std::env::set_var("SHUMA_ENFORCE_HTTPS", "false");
+    std::env::set_var("SHUMA_DEBUG_HEADERS", "false");
+
+    std::env::set_var("SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT", "http://otel-collector:4318/");
+    std::env::set_var(
+        "SHUMA_OTEL_EXPORTER_OTLP_HEADERS",
+        "authorization=Bearer abc,x-tenant=edge",
+    );
+    std::env::set_var("SHUMA_OTEL_TRACES_SAMPLE_RATIO", "0.25");
+    assert!(validate_env_only_once().is_ok());
+    assert_eq!(
+        otel_exporter_otlp_endpoint().as_deref(),
+        Some("http://otel-collector:4318")
+    );
+    assert_eq!(
+        otel_exporter_otlp_headers(),
+        vec![
+            ("authorization".to_string(), "Bearer abc".to_string()),
+            ("x-tenant".to_string(), "edge".to_string()),
+        ]
+    );
+    assert_eq!(otel_traces_sample_ratio(), 0.25);
+    assert_eq!(otel_metrics_export_interval_seconds(), 60);
+
+    std::env::set_var("SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT", "grpc://otel:4317");
+    let result = validate_env_only_once();
+    assert!(result.err().unwrap().contains("SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT"));
+    std::env::remove_var("SHUMA_OTEL_EXPORTER_OTLP_ENDPOINT");
+
+    std::env::set_var("SHUMA_OTEL_EXPORTER_OTLP_HEADERS", "missing-separator");
+    let result = validate_env_only_once();
+    assert!(result.err().unwrap().contains("SHUMA_OTEL_EXPORTER_OTLP_HEADERS"));
+    std::env::remove_var("SHUMA_OTEL_EXPORTER_OTLP_HEADERS");
+
+    std::env::set_var("SHUMA_OTEL_TRACES_SAMPLE_RATIO", "1.5");
+    let result = validate_env_only_once();
+    assert!(result.err().unwrap().contains("SHUMA_OTEL_TRACES_SAMPLE_RATIO"));
+
+    clear_env(&keys);
+}
+
+#[test]
+fn validate_env_rejects_invalid_alert_settings() {
+    let _lock = crate::test_support::lock_env();
+    let keys = [
+        "SHUMA_VALIDATE_ENV_IN_TESTS",
+        "SHUMA_API_KEY",
+        "SHUMA_JS_SECRET",
+        "SHUMA_FORWARDED_IP_SECRET",
+        "SHUMA_EVENT_LOG_RETENTION_HOURS",
+        "SHUMA_ADMIN_CONFIG_WRITE_ENABLED",
+        "SHUMA_KV_STORE_FAIL_OPEN",
+        "SHUMA_ENFORCE_HTTPS",
+        "SHUMA_DEBUG_HEADERS",
+        "SHUMA_ALERT_WEBHOOK_URL",
+        "SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS",
+    ];
+    clear_env(&keys);
+
+    std::env::set_var("SHUMA_VALIDATE_ENV_IN_TESTS", "true");
+    std::env::set_var("SHUMA_API_KEY", "test-admin-key");
+    std::env::set_var("SHUMA_JS_SECRET", "test-js-secret");
+    std::env::set_var("SHUMA_FORWARDED_IP_SECRET", "test-forwarded-secret");
+    std::env::set_var("SHUMA_EVENT_LOG_RETENTION_HOURS", "168");
+    std::env::set_var("SHUMA_ADMIN_CONFIG_WRITE_ENABLED", "false");
+    std::env::set_var("SHUMA_KV_STORE_FAIL_OPEN", "true");
+    std::env::set_var("SHUMA_ENFORCE_HTTPS", "false");
+    std::env::set_var("SHUMA_DEBUG_HEADERS", "false");
+
+    std::env::set_var("SHUMA_ALERT_WEBHOOK_URL", "https://hooks.example.com/T0/B0/");
+    std::env::set_var("SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS", "5");
+    assert!(validate_env_only_once().is_ok());
+    assert_eq!(
+        alert_webhook_url().as_deref(),
+        Some("https://hooks.example.com/T0/B0/")
+    );
+    assert_eq!(alert_evaluation_interval_seconds(), 30);
+
+    std::env::set_var("SHUMA_ALERT_WEBHOOK_URL", "ftp://hooks.example.com");
+    let result = validate_env_only_once();
+    assert!(result.err().unwrap().contains("SHUMA_ALERT_WEBHOOK_URL"));
+    std::env::remove_var("SHUMA_ALERT_WEBHOOK_URL");
+
+    std::env::set_var("SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS", "soon");
+    let result = validate_env_only_once();
+    assert!(result
+        .err()
+        .unwrap()
+        .contains("SHUMA_ALERT_EVALUATION_INTERVAL_SECONDS"));
+
+    clear_env(&keys);
+}
+
 #[test]
 fn validate_env_accepts_empty_optional_redis_url() {
     let _lock = crate::test_support::lock_env();
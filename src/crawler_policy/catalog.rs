@@ -0,0 +1,257 @@
+//! Crawler catalog lookups.
+//!
+//! `crawler_catalog` is seeded from the built-in list in `config/defaults.env` and edited
+//! through `POST /admin/config`. robots.txt groups, covert-decoy suppression, the
+//! declared-crawler stage and robots.txt compliance families all read it from here.
+
+use crate::config::{Config, CrawlerCatalogEntry, CrawlerCategory, CrawlerVerification};
+
+const MAX_ENTRIES: usize = 128;
+const MAX_PATTERNS_PER_ENTRY: usize = 8;
+const MAX_CRAWL_DELAY_SECONDS: u32 = 3600;
+const MAX_NAME_CHARS: usize = 64;
+const MIN_PATTERN_CHARS: usize = 3;
+const MAX_PATTERN_CHARS: usize = 64;
+
+/// `User-Agent` substrings for an entry; the name itself when none are configured.
+pub(crate) fn patterns(entry: &CrawlerCatalogEntry) -> Vec<&str> {
+    if entry.ua_patterns.is_empty() {
+        vec![entry.name.as_str()]
+    } else {
+        entry.ua_patterns.iter().map(String::as_str).collect()
+    }
+}
+
+pub(crate) fn entries_in(
+    cfg: &Config,
+    category: CrawlerCategory,
+) -> impl Iterator<Item = &CrawlerCatalogEntry> {
+    cfg.crawler_catalog
+        .iter()
+        .filter(move |entry| entry.category == category)
+}
+
+/// Case-insensitive pattern match over the given categories. The longest matching pattern
+/// wins so `Omgilibot` is not reported as `Omgili`.
+pub(crate) fn match_user_agent<'a>(
+    cfg: &'a Config,
+    user_agent: &str,
+    categories: &[CrawlerCategory],
+) -> Option<&'a CrawlerCatalogEntry> {
+    let ua = user_agent.to_ascii_lowercase();
+    cfg.crawler_catalog
+        .iter()
+        .filter(|entry| categories.contains(&entry.category))
+        .flat_map(|entry| {
+            patterns(entry)
+                .into_iter()
+                .map(move |pattern| (entry, pattern.to_ascii_lowercase()))
+        })
+        .filter(|(_, pattern)| !pattern.is_empty() && ua.contains(pattern.as_str()))
+        .max_by_key(|(_, pattern)| pattern.len())
+        .map(|(entry, _)| entry)
+}
+
+/// Whether a request from `ip` that matched `entry` by `User-Agent` really is that crawler.
+pub(crate) fn is_verified(entry: &CrawlerCatalogEntry, ip: &str) -> bool {
+    match entry.verification {
+        CrawlerVerification::None => true,
+        CrawlerVerification::IpRange => {
+            crate::signals::ip_range_policy::managed_set_contains(entry.ip_range_set.as_str(), ip)
+        }
+    }
+}
+
+/// Matched and verified; an unverified claim is treated as if the crawler were not named.
+pub(crate) fn identify<'a>(
+    cfg: &'a Config,
+    ip: &str,
+    user_agent: &str,
+    categories: &[CrawlerCategory],
+) -> Option<&'a CrawlerCatalogEntry> {
+    match_user_agent(cfg, user_agent, categories).filter(|entry| is_verified(entry, ip))
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    name.len() <= MAX_NAME_CHARS
+        && chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.'))
+}
+
+fn is_valid_pattern(pattern: &str) -> bool {
+    (MIN_PATTERN_CHARS..=MAX_PATTERN_CHARS).contains(&pattern.len())
+        && pattern.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+}
+
+/// Validate a `crawler_catalog` list, returning the first problem found
+pub(crate) fn validate_entries(entries: &[CrawlerCatalogEntry]) -> Result<(), String> {
+    if entries.len() > MAX_ENTRIES {
+        return Err(format!(
+            "crawler_catalog supports at most {} entries",
+            MAX_ENTRIES
+        ));
+    }
+    let mut seen = std::collections::HashSet::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if !is_valid_name(entry.name.as_str()) {
+            return Err(format!(
+                "crawler_catalog[{}].name must be 1-{} letters, digits, spaces, '-', '_' or '.'",
+                index, MAX_NAME_CHARS
+            ));
+        }
+        if !seen.insert(entry.name.to_ascii_lowercase()) {
+            return Err(format!(
+                "crawler_catalog contains duplicate name '{}'",
+                entry.name
+            ));
+        }
+        if entry.ua_patterns.len() > MAX_PATTERNS_PER_ENTRY
+            || !entry
+                .ua_patterns
+                .iter()
+                .all(|pattern| is_valid_pattern(pattern))
+        {
+            return Err(format!(
+                "crawler_catalog[{}].ua_patterns must hold at most {} printable patterns of {}-{} characters",
+                index, MAX_PATTERNS_PER_ENTRY, MIN_PATTERN_CHARS, MAX_PATTERN_CHARS
+            ));
+        }
+        if entry.ua_patterns.is_empty() && entry.name.len() < MIN_PATTERN_CHARS {
+            return Err(format!(
+                "crawler_catalog[{}] needs ua_patterns when name is shorter than {} characters",
+                index, MIN_PATTERN_CHARS
+            ));
+        }
+        match entry.verification {
+            CrawlerVerification::IpRange
+                if !crate::signals::ip_range_policy::has_managed_set(
+                    entry.ip_range_set.as_str(),
+                ) =>
+            {
+                return Err(format!(
+                    "crawler_catalog[{}].ip_range_set must name a managed IP range set",
+                    index
+                ));
+            }
+            CrawlerVerification::None if !entry.ip_range_set.is_empty() => {
+                return Err(format!(
+                    "crawler_catalog[{}].ip_range_set requires verification ip_range",
+                    index
+                ));
+            }
+            _ => {}
+        }
+        if entry
+            .crawl_delay
+            .is_some_and(|delay| delay > MAX_CRAWL_DELAY_SECONDS)
+        {
+            return Err(format!(
+                "crawler_catalog[{}].crawl_delay out of range (0-{})",
+                index, MAX_CRAWL_DELAY_SECONDS
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: &[CrawlerCategory] = &[
+        CrawlerCategory::AiTraining,
+        CrawlerCategory::AiSearch,
+        CrawlerCategory::SearchEngine,
+    ];
+
+    fn entry(name: &str, category: CrawlerCategory) -> CrawlerCatalogEntry {
+        CrawlerCatalogEntry {
+            name: name.to_string(),
+            category,
+            ..CrawlerCatalogEntry::default()
+        }
+    }
+
+    #[test]
+    fn built_in_catalog_is_valid_and_covers_every_category() {
+        let cfg = crate::config::defaults();
+        assert!(validate_entries(&cfg.crawler_catalog).is_ok());
+        for category in ALL {
+            assert!(entries_in(cfg, *category).next().is_some());
+        }
+        assert_eq!(
+            match_user_agent(cfg, "Mozilla/5.0 (compatible; Googlebot/2.1)", ALL)
+                .map(|entry| entry.category),
+            Some(CrawlerCategory::SearchEngine)
+        );
+    }
+
+    #[test]
+    fn custom_patterns_replace_the_name_and_longest_pattern_wins() {
+        let mut cfg = crate::config::defaults().clone();
+        let mut acme = entry("AcmeBot", CrawlerCategory::AiTraining);
+        acme.ua_patterns = vec!["acme-crawler".to_string(), "acmebot-ng".to_string()];
+        cfg.crawler_catalog = vec![acme, entry("Acme", CrawlerCategory::AiSearch)];
+
+        assert_eq!(
+            match_user_agent(&cfg, "ACMEBot-NG/3.0", ALL).map(|e| e.name.as_str()),
+            Some("AcmeBot")
+        );
+        assert_eq!(
+            match_user_agent(&cfg, "acme/1.0", ALL).map(|e| e.name.as_str()),
+            Some("Acme")
+        );
+        assert!(
+            match_user_agent(&cfg, "AcmeBot-NG/3.0", &[CrawlerCategory::SearchEngine]).is_none()
+        );
+    }
+
+    #[test]
+    fn ip_range_verification_requires_a_managed_set_match() {
+        let mut cfg = crate::config::defaults().clone();
+        let mut gptbot = entry("GPTBot", CrawlerCategory::AiTraining);
+        gptbot.verification = CrawlerVerification::IpRange;
+        gptbot.ip_range_set = "openai_gptbot".to_string();
+        cfg.crawler_catalog = vec![gptbot];
+
+        assert!(identify(&cfg, "20.171.206.9", "GPTBot/1.2", ALL).is_some());
+        assert!(identify(&cfg, "203.0.113.9", "GPTBot/1.2", ALL).is_none());
+        assert!(match_user_agent(&cfg, "GPTBot/1.2", ALL).is_some());
+    }
+
+    #[test]
+    fn validation_rejects_unsafe_or_ambiguous_entries() {
+        let valid = vec![entry("GPTBot", CrawlerCategory::AiTraining)];
+        assert!(validate_entries(&valid).is_ok());
+
+        let duplicate = vec![
+            entry("GPTBot", CrawlerCategory::AiTraining),
+            entry("gptbot", CrawlerCategory::AiSearch),
+        ];
+        assert!(validate_entries(&duplicate)
+            .unwrap_err()
+            .contains("duplicate"));
+
+        let quoted = vec![entry("Bad\"Bot", CrawlerCategory::AiTraining)];
+        assert!(validate_entries(&quoted).unwrap_err().contains(".name"));
+
+        let mut short = entry("X", CrawlerCategory::AiTraining);
+        assert!(validate_entries(&[short.clone()]).is_err());
+        short.ua_patterns = vec!["x".to_string()];
+        assert!(validate_entries(&[short]).is_err());
+
+        let mut unknown_set = entry("GPTBot", CrawlerCategory::AiTraining);
+        unknown_set.verification = CrawlerVerification::IpRange;
+        unknown_set.ip_range_set = "nope".to_string();
+        assert!(validate_entries(&[unknown_set])
+            .unwrap_err()
+            .contains("ip_range_set"));
+
+        let mut slow = entry("GPTBot", CrawlerCategory::AiTraining);
+        slow.crawl_delay = Some(MAX_CRAWL_DELAY_SECONDS + 1);
+        assert!(validate_entries(&[slow])
+            .unwrap_err()
+            .contains("crawl_delay"));
+    }
+}
@@ -0,0 +1,520 @@
+//! robots.txt compliance tracking.
+//!
+//! Serving robots.txt records, per client (IP bucket + UA family), the rules that copy
+//! applies to the client's user agent. A later request for a path those rules disallow
+//! raises the `robots_violation` botness signal; the first one after each fetch is logged.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::CrawlerCategory;
+use crate::signals::botness::{BotSignal, SignalFamily, SignalProvenance};
+
+const FETCH_KEY_PREFIX: &str = "robots:fetch:";
+/// RFC 9309 lets crawlers cache robots.txt for at most 24 hours.
+const FETCH_TTL_SECONDS: u64 = 86_400;
+const SWEEP_MARKER_KEY: &str = "robots:fetch_sweep:last";
+const SWEEP_INTERVAL_SECONDS: u64 = 3600;
+const MAX_TRACKED_RULES: usize = 32;
+const MAX_RULE_CHARS: usize = 128;
+const MAX_FAMILY_CHARS: usize = 32;
+const SIGNAL_KEY: &str = "robots_violation";
+const SIGNAL_LABEL: &str = "robots.txt disallow ignored";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct RobotsRules {
+    allow: Vec<String>,
+    disallow: Vec<String>,
+}
+
+impl RobotsRules {
+    /// The longest matching rule decides and `Allow` wins ties (RFC 9309). Rules are plain
+    /// prefixes because the generator never emits `*` or `$` patterns.
+    pub(crate) fn disallows(&self, path: &str) -> bool {
+        let longest = |rules: &[String]| {
+            rules
+                .iter()
+                .filter(|rule| path.starts_with(rule.as_str()))
+                .map(String::len)
+                .max()
+        };
+        match (longest(&self.disallow), longest(&self.allow)) {
+            (Some(disallow), Some(allow)) => disallow > allow,
+            (Some(_), None) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct FetchRecord {
+    fetched_at: u64,
+    rules: RobotsRules,
+    /// Stops at 1: only the first violation after a fetch needs the record rewritten.
+    violations: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Violation {
+    pub family: String,
+    /// True only for the first disallowed request after a fetch, so events are not
+    /// logged for every page a non-compliant crawler takes.
+    pub first: bool,
+    pub fetched_at: u64,
+}
+
+#[derive(Default)]
+struct Group {
+    agents: Vec<String>,
+    rules: RobotsRules,
+}
+
+fn parse_groups(robots_txt: &str) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut in_rules = false;
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match field.trim().to_ascii_lowercase().as_str() {
+            "user-agent" => {
+                if in_rules || groups.is_empty() {
+                    groups.push(Group::default());
+                    in_rules = false;
+                }
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+            }
+            "allow" | "disallow" if !value.is_empty() => {
+                in_rules = true;
+                let Some(group) = groups.last_mut() else {
+                    continue;
+                };
+                let rule = value.chars().take(MAX_RULE_CHARS).collect::<String>();
+                if field.trim().eq_ignore_ascii_case("allow") {
+                    group.rules.allow.push(rule);
+                } else {
+                    group.rules.disallow.push(rule);
+                }
+            }
+            "allow" | "disallow" => in_rules = true,
+            _ => {}
+        }
+    }
+    groups
+}
+
+/// Rules a served robots.txt applies to `user_agent`: every group naming one of its
+/// product tokens, or the `*` group when none does.
+pub(crate) fn rules_for_user_agent(robots_txt: &str, user_agent: &str) -> RobotsRules {
+    let ua = user_agent.to_ascii_lowercase();
+    let groups = parse_groups(robots_txt);
+    let named = groups
+        .iter()
+        .filter(|group| {
+            group
+                .agents
+                .iter()
+                .any(|agent| agent != "*" && ua.contains(agent.as_str()))
+        })
+        .collect::<Vec<_>>();
+    let selected = if named.is_empty() {
+        groups
+            .iter()
+            .filter(|group| group.agents.iter().any(|agent| agent == "*"))
+            .collect()
+    } else {
+        named
+    };
+
+    let mut rules = RobotsRules::default();
+    for group in selected {
+        rules.allow.extend(group.rules.allow.iter().cloned());
+        rules.disallow.extend(group.rules.disallow.iter().cloned());
+    }
+    rules.allow.truncate(MAX_TRACKED_RULES);
+    rules.disallow.truncate(MAX_TRACKED_RULES);
+    rules
+}
+
+/// Coarse client family: the catalogued crawler a UA names, otherwise `browser` for
+/// Mozilla-style UAs and the leading product token for everything else.
+pub(crate) fn ua_family(cfg: &crate::config::Config, user_agent: &str) -> String {
+    let ua = user_agent.trim().to_ascii_lowercase();
+    if let Some(bot) = super::catalog::match_user_agent(
+        cfg,
+        ua.as_str(),
+        &[
+            CrawlerCategory::AiTraining,
+            CrawlerCategory::AiSearch,
+            CrawlerCategory::SearchEngine,
+        ],
+    ) {
+        return bot.name.to_ascii_lowercase();
+    }
+    if ua.starts_with("mozilla/") {
+        return "browser".to_string();
+    }
+    let product = ua
+        .split(|c: char| c == '/' || c == ';' || c.is_whitespace())
+        .next()
+        .unwrap_or("")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        .take(MAX_FAMILY_CHARS)
+        .collect::<String>();
+    if product.is_empty() {
+        "none".to_string()
+    } else {
+        product
+    }
+}
+
+fn fetch_key(ip: &str, family: &str) -> String {
+    format!(
+        "{}{}:{}",
+        FETCH_KEY_PREFIX,
+        crate::signals::ip_identity::bucket_ip(ip),
+        family
+    )
+}
+
+/// The client's fetch record while it is fresh; a lapsed record is deleted on sight.
+fn load_record<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    key: &str,
+    now: u64,
+) -> Option<FetchRecord> {
+    let record = store
+        .get(key)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<FetchRecord>(&raw).ok())?;
+    if now.saturating_sub(record.fetched_at) > FETCH_TTL_SECONDS {
+        let _ = store.delete(key);
+        return None;
+    }
+    Some(record)
+}
+
+/// Deletes lapsed records of clients that never came back. Runs from the robots.txt path
+/// at most once per `SWEEP_INTERVAL_SECONDS`.
+fn maybe_sweep_expired<S: crate::challenge::KeyValueStore>(store: &S, now: u64) {
+    let last_sweep = store
+        .get(SWEEP_MARKER_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(0);
+    if now.saturating_sub(last_sweep) < SWEEP_INTERVAL_SECONDS {
+        return;
+    }
+    let _ = store.set(SWEEP_MARKER_KEY, now.to_string().as_bytes());
+    let Ok(keys) = store.get_keys() else {
+        return;
+    };
+    for key in keys {
+        if key.starts_with(FETCH_KEY_PREFIX) {
+            let _ = load_record(store, key.as_str(), now);
+        }
+    }
+}
+
+fn store_record<S: crate::challenge::KeyValueStore>(store: &S, key: &str, record: &FetchRecord) {
+    if let Ok(raw) = serde_json::to_vec(record) {
+        let _ = store.set(key, &raw);
+    }
+}
+
+/// Remembers what a robots.txt response told this client. Returns whether anything is
+/// tracked, i.e. the copy disallowed at least one path for it.
+pub(crate) fn record_fetch<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    cfg: &crate::config::Config,
+    ip: &str,
+    user_agent: &str,
+    robots_txt: &str,
+    now: u64,
+) -> bool {
+    maybe_sweep_expired(store, now);
+    let key = fetch_key(ip, ua_family(cfg, user_agent).as_str());
+    let mut rules = rules_for_user_agent(robots_txt, user_agent);
+    // Rotating honeypot paths are enforced by the honeypot stage, not tracked as violations.
+    let trap_paths = crate::enforcement::honeypot::live_rotating_paths(cfg, now);
+    rules.disallow.retain(|path| !trap_paths.contains(path));
+    if rules.disallow.is_empty() {
+        let _ = store.delete(key.as_str());
+        return false;
+    }
+    store_record(
+        store,
+        key.as_str(),
+        &FetchRecord {
+            fetched_at: now,
+            rules,
+            violations: 0,
+        },
+    );
+    true
+}
+
+/// Checks a request against the robots.txt copy its client last fetched.
+pub(crate) fn observe_request<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    cfg: &crate::config::Config,
+    ip: &str,
+    user_agent: &str,
+    path: &str,
+    now: u64,
+) -> Option<Violation> {
+    if !cfg.robots_enabled {
+        return None;
+    }
+    let family = ua_family(cfg, user_agent);
+    let key = fetch_key(ip, family.as_str());
+    let mut record = load_record(store, key.as_str(), now)?;
+    if !record.rules.disallows(path) {
+        return None;
+    }
+    let first = record.violations == 0;
+    if first {
+        record.violations = 1;
+        store_record(store, key.as_str(), &record);
+    }
+    Some(Violation {
+        family,
+        first,
+        fetched_at: record.fetched_at,
+    })
+}
+
+pub(crate) fn bot_signal(enabled: bool, violated: bool, weight: u8) -> BotSignal {
+    if !enabled {
+        return BotSignal::disabled_with_metadata(
+            SIGNAL_KEY,
+            SIGNAL_LABEL,
+            SignalProvenance::Derived,
+            9,
+            SignalFamily::Other,
+        );
+    }
+    BotSignal::scored_with_metadata(
+        SIGNAL_KEY,
+        SIGNAL_LABEL,
+        violated,
+        weight,
+        SignalProvenance::Derived,
+        9,
+        SignalFamily::Other,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::KeyValueStore;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockStore {
+        map: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    impl KeyValueStore for MockStore {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
+            Ok(self.map.lock().unwrap().get(key).cloned())
+        }
+
+        fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
+            self.map
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_vec());
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), ()> {
+            self.map.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        fn get_keys(&self) -> Result<Vec<String>, ()> {
+            Ok(self.map.lock().unwrap().keys().cloned().collect())
+        }
+    }
+
+    const GPTBOT_UA: &str = "Mozilla/5.0 (compatible; GPTBot/1.2; +https://openai.com/gptbot)";
+
+    fn robots_config() -> crate::config::Config {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.robots_enabled = true;
+        cfg.robots_block_ai_training = true;
+        cfg.robots_block_ai_search = false;
+        cfg.robots_allow_search_engines = true;
+        cfg
+    }
+
+    #[test]
+    fn served_copy_rules_follow_the_matching_group() {
+        let robots = super::super::robots::generate_robots_txt(&robots_config(), 1_700_000_000);
+
+        assert!(rules_for_user_agent(robots.as_str(), GPTBOT_UA).disallows("/articles/1"));
+        let googlebot = rules_for_user_agent(
+            robots.as_str(),
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        );
+        assert!(!googlebot.disallows("/articles/1"));
+        assert!(!rules_for_user_agent(robots.as_str(), "curl/8.5.0").disallows("/"));
+    }
+
+    #[test]
+    fn longest_rule_wins_and_allow_wins_ties() {
+        let robots =
+            "User-agent: *\nDisallow: /private\nAllow: /private/press\nDisallow: /x\nAllow: /x\n";
+        let rules = rules_for_user_agent(robots, "scrapy/2.11");
+
+        assert!(rules.disallows("/private/notes"));
+        assert!(!rules.disallows("/private/press/kit"));
+        assert!(!rules.disallows("/x"));
+        assert!(!rules.disallows("/public"));
+    }
+
+    #[test]
+    fn ua_family_prefers_catalogued_crawlers() {
+        let cfg = robots_config();
+        assert_eq!(ua_family(&cfg, GPTBOT_UA), "gptbot");
+        assert_eq!(
+            ua_family(&cfg, "Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0"),
+            "browser"
+        );
+        assert_eq!(ua_family(&cfg, "python-requests/2.31.0"), "python-requests");
+        assert_eq!(ua_family(&cfg, ""), "none");
+    }
+
+    #[test]
+    fn violation_is_raised_once_per_fetch_and_only_for_the_fetching_client() {
+        let cfg = robots_config();
+        let store = MockStore::default();
+        let now = 1_700_000_000;
+        let robots = super::super::robots::generate_robots_txt(&cfg, now);
+
+        assert!(observe_request(&store, &cfg, "198.51.100.7", GPTBOT_UA, "/a", now).is_none());
+        assert!(record_fetch(
+            &store,
+            &cfg,
+            "198.51.100.7",
+            GPTBOT_UA,
+            robots.as_str(),
+            now
+        ));
+
+        let first = observe_request(&store, &cfg, "198.51.100.9", GPTBOT_UA, "/a", now + 5)
+            .expect("same /24 bucket and family");
+        assert!(first.first);
+        assert_eq!(first.family, "gptbot");
+        let second =
+            observe_request(&store, &cfg, "198.51.100.7", GPTBOT_UA, "/b", now + 6).unwrap();
+        assert!(!second.first);
+
+        assert!(observe_request(&store, &cfg, "203.0.113.7", GPTBOT_UA, "/a", now).is_none());
+        assert!(observe_request(&store, &cfg, "198.51.100.7", "curl/8.5.0", "/a", now).is_none());
+        assert!(observe_request(
+            &store,
+            &cfg,
+            "198.51.100.7",
+            GPTBOT_UA,
+            "/a",
+            now + FETCH_TTL_SECONDS + 1
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn fetch_without_disallow_rules_clears_tracking() {
+        let mut cfg = robots_config();
+        let store = MockStore::default();
+        let blocking = super::super::robots::generate_robots_txt(&cfg, 10);
+        assert!(record_fetch(
+            &store,
+            &cfg,
+            "198.51.100.7",
+            GPTBOT_UA,
+            blocking.as_str(),
+            10
+        ));
+
+        cfg.robots_block_ai_training = false;
+        let permissive = super::super::robots::generate_robots_txt(&cfg, 20);
+        assert!(!record_fetch(
+            &store,
+            &cfg,
+            "198.51.100.7",
+            GPTBOT_UA,
+            permissive.as_str(),
+            20
+        ));
+        assert!(store.map.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn violations_write_once_and_lapsed_records_are_removed() {
+        let cfg = robots_config();
+        let store = MockStore::default();
+        let now = 1_700_000_000;
+        let robots = super::super::robots::generate_robots_txt(&cfg, now);
+        assert!(record_fetch(
+            &store,
+            &cfg,
+            "198.51.100.7",
+            GPTBOT_UA,
+            robots.as_str(),
+            now
+        ));
+        let key = fetch_key("198.51.100.7", "gptbot");
+
+        assert!(
+            observe_request(&store, &cfg, "198.51.100.7", GPTBOT_UA, "/a", now + 1)
+                .unwrap()
+                .first
+        );
+        let after_first = store.get(key.as_str()).unwrap();
+        assert!(
+            !observe_request(&store, &cfg, "198.51.100.7", GPTBOT_UA, "/b", now + 2)
+                .unwrap()
+                .first
+        );
+        assert_eq!(store.get(key.as_str()).unwrap(), after_first);
+
+        // A stale record is dropped when its client is next seen...
+        let lapsed = now + FETCH_TTL_SECONDS + 1;
+        assert!(observe_request(&store, &cfg, "198.51.100.7", GPTBOT_UA, "/a", lapsed).is_none());
+        assert!(store.get(key.as_str()).unwrap().is_none());
+
+        // ...and by the hourly sweep when it never is.
+        assert!(record_fetch(
+            &store,
+            &cfg,
+            "203.0.113.7",
+            GPTBOT_UA,
+            robots.as_str(),
+            now
+        ));
+        assert!(record_fetch(
+            &store,
+            &cfg,
+            "198.51.100.7",
+            GPTBOT_UA,
+            robots.as_str(),
+            lapsed
+        ));
+        assert!(store
+            .get(fetch_key("203.0.113.7", "gptbot").as_str())
+            .unwrap()
+            .is_none());
+        assert!(store.get(key.as_str()).unwrap().is_some());
+    }
+}
@@ -0,0 +1,170 @@
+//! Request-time enforcement of the AI crawler policy published in robots.txt.
+//!
+//! Only crawlers that name themselves in `User-Agent` are matched. The AI entries of
+//! `crawler_catalog` are the ones `robots::generate_robots_txt` writes `Disallow` rules for,
+//! so a crawler is only ever acted on for ignoring a rule we actually published.
+
+use super::catalog;
+pub(crate) use crate::config::CrawlerCategory;
+use crate::config::{AiCrawlerAction, Config};
+
+const AI_CATEGORIES: &[CrawlerCategory] = &[CrawlerCategory::AiTraining, CrawlerCategory::AiSearch];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DeclaredCrawler {
+    pub bot: String,
+    pub category: CrawlerCategory,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Decision {
+    pub crawler: DeclaredCrawler,
+    /// `Allow` whenever robots.txt does not disallow the crawler's category.
+    pub action: AiCrawlerAction,
+}
+
+/// Every catalogued AI crawler in catalog order, for per-bot metrics.
+pub(crate) fn catalog(cfg: &Config) -> impl Iterator<Item = &str> {
+    cfg.crawler_catalog
+        .iter()
+        .filter(|entry| AI_CATEGORIES.contains(&entry.category))
+        .map(|entry| entry.name.as_str())
+}
+
+/// Matches AI crawlers by `User-Agent` and drops claims that fail the entry's verification.
+pub(crate) fn match_user_agent(
+    cfg: &Config,
+    ip: &str,
+    user_agent: &str,
+) -> Option<DeclaredCrawler> {
+    catalog::identify(cfg, ip, user_agent, AI_CATEGORIES).map(|entry| DeclaredCrawler {
+        bot: entry.name.clone(),
+        category: entry.category,
+    })
+}
+
+fn is_disallowed(cfg: &Config, category: CrawlerCategory) -> bool {
+    cfg.robots_enabled
+        && match category {
+            CrawlerCategory::AiTraining => cfg.robots_block_ai_training,
+            CrawlerCategory::AiSearch => cfg.robots_block_ai_search,
+            CrawlerCategory::SearchEngine => false,
+        }
+}
+
+pub(crate) fn evaluate(cfg: &Config, ip: &str, user_agent: &str) -> Option<Decision> {
+    let crawler = match_user_agent(cfg, ip, user_agent)?;
+    let action = if is_disallowed(cfg, crawler.category) {
+        cfg.crawler_catalog
+            .iter()
+            .find(|entry| entry.name == crawler.bot)
+            .and_then(|entry| entry.action)
+            .unwrap_or(cfg.ai_crawler_action)
+    } else {
+        AiCrawlerAction::Allow
+    };
+    Some(Decision { crawler, action })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CrawlerCatalogEntry, CrawlerVerification};
+
+    const IP: &str = "198.51.100.7";
+
+    fn enforcing_config(action: AiCrawlerAction) -> Config {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.robots_enabled = true;
+        cfg.robots_block_ai_training = true;
+        cfg.robots_block_ai_search = false;
+        cfg.ai_crawler_action = action;
+        cfg
+    }
+
+    #[test]
+    fn matches_declared_crawlers_case_insensitively() {
+        let cfg = crate::config::defaults();
+        let gptbot = match_user_agent(
+            cfg,
+            IP,
+            "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; gptbot/1.2; +https://openai.com/gptbot)",
+        )
+        .unwrap();
+        assert_eq!(gptbot.bot, "GPTBot");
+        assert_eq!(gptbot.category, CrawlerCategory::AiTraining);
+
+        let perplexity =
+            match_user_agent(cfg, IP, "Mozilla/5.0 (compatible; PerplexityBot/1.0)").unwrap();
+        assert_eq!(perplexity.bot, "PerplexityBot");
+        assert_eq!(perplexity.category, CrawlerCategory::AiSearch);
+
+        assert!(
+            match_user_agent(cfg, IP, "Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0").is_none()
+        );
+        assert!(match_user_agent(cfg, IP, "Mozilla/5.0 (compatible; Googlebot/2.1)").is_none());
+    }
+
+    #[test]
+    fn longest_catalog_token_wins() {
+        let cfg = crate::config::defaults();
+        assert_eq!(
+            match_user_agent(cfg, IP, "Omgilibot/0.4").map(|c| c.bot),
+            Some("Omgilibot".to_string())
+        );
+        assert_eq!(
+            match_user_agent(cfg, IP, "cohere-training-data-crawler/1.0").map(|c| c.bot),
+            Some("cohere-training-data-crawler".to_string())
+        );
+    }
+
+    #[test]
+    fn action_applies_only_to_disallowed_categories() {
+        let cfg = enforcing_config(AiCrawlerAction::Block);
+        assert_eq!(
+            evaluate(&cfg, IP, "CCBot/2.0").map(|d| d.action),
+            Some(AiCrawlerAction::Block)
+        );
+        assert_eq!(
+            evaluate(&cfg, IP, "OAI-SearchBot/1.0").map(|d| d.action),
+            Some(AiCrawlerAction::Allow)
+        );
+
+        let mut robots_off = enforcing_config(AiCrawlerAction::Block);
+        robots_off.robots_enabled = false;
+        assert_eq!(
+            evaluate(&robots_off, IP, "CCBot/2.0").map(|d| d.action),
+            Some(AiCrawlerAction::Allow)
+        );
+        assert!(evaluate(&cfg, IP, "curl/8.5.0").is_none());
+    }
+
+    #[test]
+    fn per_bot_action_overrides_the_global_action() {
+        let mut cfg = enforcing_config(AiCrawlerAction::Block);
+        cfg.crawler_catalog = vec![
+            CrawlerCatalogEntry {
+                name: "CCBot".to_string(),
+                action: Some(AiCrawlerAction::PaymentRequired),
+                ..CrawlerCatalogEntry::default()
+            },
+            CrawlerCatalogEntry {
+                name: "GPTBot".to_string(),
+                verification: CrawlerVerification::IpRange,
+                ip_range_set: "openai_gptbot".to_string(),
+                ..CrawlerCatalogEntry::default()
+            },
+        ];
+
+        assert_eq!(
+            evaluate(&cfg, IP, "CCBot/2.0").map(|d| d.action),
+            Some(AiCrawlerAction::PaymentRequired)
+        );
+        assert_eq!(
+            evaluate(&cfg, "20.171.206.9", "GPTBot/1.2").map(|d| d.action),
+            Some(AiCrawlerAction::Block)
+        );
+        assert!(evaluate(&cfg, IP, "GPTBot/1.2").is_none());
+        assert_eq!(catalog(&cfg).collect::<Vec<_>>(), vec!["CCBot", "GPTBot"]);
+    }
+}
@@ -1 +1,6 @@
+pub(crate) mod catalog;
+pub(crate) mod compliance;
+pub(crate) mod declared;
+pub(crate) mod payment;
+pub(crate) mod policy_files;
 pub(crate) mod robots;
@@ -0,0 +1,759 @@
+//! Pay-per-crawl: `402 Payment Required` price offers and signed crawl-payment tokens.
+//!
+//! A token is a compact JWS in the `Crawl-Payment` request header, signed either with the
+//! env-only `SHUMA_CRAWL_PAYMENT_SECRET` (`HS256`) or by a configured issuer's Ed25519 key
+//! (`EdDSA`, issuer named in `kid`). Its `budget` is spent down by the configured price per
+//! request, with the running total kept in KV per token until the token expires.
+//!
+//! Charges to one token are serialised by a short KV lease. Spin KV has no compare-and-set,
+//! so the lease narrows the race rather than closing it: two charges only both proceed when
+//! each reads the lease key before the other's write lands, i.e. within one get/set/get of
+//! each other. A token can therefore overspend by one price per request racing inside that
+//! window, not by one price per concurrent request as with a bare get-then-set.
+
+use crate::challenge::KeyValueStore;
+use crate::config::Config;
+use base64::{engine::general_purpose, Engine as _};
+use ed25519_dalek::Signature;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use spin_sdk::http::{Request, Response};
+
+pub(crate) const MAX_ISSUERS: usize = 8;
+pub(crate) const PAYMENT_HEADER: &str = "crawl-payment";
+const SPENT_KEY_PREFIX: &str = "crawl_payment:spent:";
+const LEASE_KEY_PREFIX: &str = "crawl_payment:lease:";
+const SWEEP_MARKER_KEY: &str = "crawl_payment:sweep:last";
+const SWEEP_INTERVAL_SECONDS: u64 = 3600;
+/// Long enough to cover one charge; a holder that dies mid-charge blocks the token this long.
+const LEASE_TTL_SECONDS: u64 = 5;
+const MAX_TOKEN_CHARS: usize = 4096;
+const MAX_TOKEN_ID_CHARS: usize = 128;
+const MICROS_PER_UNIT: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentFailure {
+    Malformed,
+    UnknownIssuer,
+    InvalidSignature,
+    WrongCrawler,
+    Expired,
+    CurrencyMismatch,
+    Exhausted,
+    /// Another request is charging the same token; the crawler should retry.
+    Busy,
+}
+
+impl PaymentFailure {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PaymentFailure::Malformed => "malformed",
+            PaymentFailure::UnknownIssuer => "unknown_issuer",
+            PaymentFailure::InvalidSignature => "invalid_signature",
+            PaymentFailure::WrongCrawler => "wrong_crawler",
+            PaymentFailure::Expired => "expired",
+            PaymentFailure::CurrencyMismatch => "currency_mismatch",
+            PaymentFailure::Exhausted => "exhausted",
+            PaymentFailure::Busy => "busy",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Receipt {
+    pub token_id: String,
+    pub charged_micros: u64,
+    pub remaining_micros: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Redemption {
+    /// No `Crawl-Payment` header on the request.
+    Absent,
+    Paid(Receipt),
+    Rejected(PaymentFailure),
+}
+
+impl Redemption {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Redemption::Absent => "offered",
+            Redemption::Paid(_) => "paid",
+            Redemption::Rejected(failure) => failure.as_str(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenClaims {
+    /// Crawler the budget was bought for: a catalogued bot or a Web Bot Auth agent name.
+    sub: String,
+    jti: String,
+    exp: u64,
+    /// Total spend allowed, in millionths of `cur`.
+    budget: u64,
+    cur: String,
+}
+
+/// `1000` -> `0.001`; prices are held in millionths of the currency unit.
+pub(crate) fn format_price(micros: u64) -> String {
+    let whole = micros / MICROS_PER_UNIT;
+    let fraction = micros % MICROS_PER_UNIT;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{:06}", fraction);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+pub(crate) fn is_valid_currency(raw: &str) -> bool {
+    raw.len() == 3 && raw.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn decode_segment(raw: &str) -> Option<Vec<u8>> {
+    general_purpose::URL_SAFE_NO_PAD.decode(raw).ok()
+}
+
+fn verify_signature(
+    cfg: &Config,
+    secret: Option<&str>,
+    header: &TokenHeader,
+    signing_input: &str,
+    signature: &[u8],
+) -> Result<String, PaymentFailure> {
+    match header.alg.as_str() {
+        "HS256" => {
+            let secret = secret.ok_or(PaymentFailure::UnknownIssuer)?;
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                .map_err(|_| PaymentFailure::UnknownIssuer)?;
+            mac.update(signing_input.as_bytes());
+            mac.verify_slice(signature)
+                .map_err(|_| PaymentFailure::InvalidSignature)?;
+            Ok("hs256".to_string())
+        }
+        "EdDSA" => {
+            let kid = header.kid.as_deref().ok_or(PaymentFailure::UnknownIssuer)?;
+            let issuer = cfg
+                .crawl_payment_issuers
+                .iter()
+                .find(|issuer| issuer.name.eq_ignore_ascii_case(kid))
+                .ok_or(PaymentFailure::UnknownIssuer)?;
+            let key = crate::signals::web_bot_auth::decode_public_key(issuer.key.as_str())
+                .ok_or(PaymentFailure::UnknownIssuer)?;
+            let signature =
+                Signature::from_slice(signature).map_err(|_| PaymentFailure::Malformed)?;
+            key.verify_strict(signing_input.as_bytes(), &signature)
+                .map_err(|_| PaymentFailure::InvalidSignature)?;
+            Ok(format!("eddsa:{}", issuer.name))
+        }
+        _ => Err(PaymentFailure::Malformed),
+    }
+}
+
+/// Token ids are only unique per issuer, so the spend key is scoped to both.
+fn spent_key(issuer: &str, token_id: &str) -> String {
+    let digest = Sha256::digest(format!("{}\n{}", issuer, token_id).as_bytes());
+    format!(
+        "{}{}",
+        SPENT_KEY_PREFIX,
+        general_purpose::URL_SAFE_NO_PAD.encode(&digest[..16])
+    )
+}
+
+fn lease_key(spent_key: &str) -> String {
+    format!(
+        "{}{}",
+        LEASE_KEY_PREFIX,
+        &spent_key[SPENT_KEY_PREFIX.len()..]
+    )
+}
+
+/// Exclusive right to update one token's spend, released on drop.
+struct SpendLease<'a, S: KeyValueStore + ?Sized> {
+    store: &'a S,
+    key: String,
+    owner: String,
+}
+
+impl<S: KeyValueStore + ?Sized> SpendLease<'_, S> {
+    fn holder(store: &S, key: &str) -> Option<(String, u64)> {
+        let raw = String::from_utf8(store.get(key).ok()??).ok()?;
+        let (owner, expires) = raw.split_once(':')?;
+        Some((owner.to_string(), expires.parse().ok()?))
+    }
+}
+
+impl<S: KeyValueStore + ?Sized> Drop for SpendLease<'_, S> {
+    fn drop(&mut self) {
+        if Self::holder(self.store, self.key.as_str()).is_some_and(|(owner, _)| owner == self.owner)
+        {
+            let _ = self.store.delete(self.key.as_str());
+        }
+    }
+}
+
+/// Take the lease unless a live one is held, then read it back so a writer that landed
+/// in between wins and this request backs off.
+fn acquire_lease<'a, S: KeyValueStore + ?Sized>(
+    store: &'a S,
+    key: String,
+    now: u64,
+) -> Option<SpendLease<'a, S>> {
+    if SpendLease::holder(store, key.as_str()).is_some_and(|(_, expires)| expires > now) {
+        return None;
+    }
+    let owner = format!("{:016x}", rand::random::<u64>());
+    let value = format!("{}:{}", owner, now.saturating_add(LEASE_TTL_SECONDS));
+    store.set(key.as_str(), value.as_bytes()).ok()?;
+    match SpendLease::holder(store, key.as_str()) {
+        Some((holder, _)) if holder == owner => Some(SpendLease { store, key, owner }),
+        _ => None,
+    }
+}
+
+/// Running total and token expiry, stored as `<spent>:<exp>` so lapsed keys can be swept.
+fn load_spent<S: KeyValueStore + ?Sized>(store: &S, key: &str) -> Option<(u64, u64)> {
+    let raw = String::from_utf8(store.get(key).ok()??).ok()?;
+    let (spent, exp) = raw.split_once(':')?;
+    Some((spent.parse().ok()?, exp.parse().ok()?))
+}
+
+/// Deletes spend totals of expired tokens and leases left behind by dead holders. Runs
+/// from the charge path at most once per `SWEEP_INTERVAL_SECONDS`.
+fn maybe_sweep_expired<S: KeyValueStore + ?Sized>(store: &S, now: u64) {
+    let last_sweep = store
+        .get(SWEEP_MARKER_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(0);
+    if now.saturating_sub(last_sweep) < SWEEP_INTERVAL_SECONDS {
+        return;
+    }
+    let _ = store.set(SWEEP_MARKER_KEY, now.to_string().as_bytes());
+    let Ok(keys) = store.get_keys() else {
+        return;
+    };
+    for key in keys {
+        let expired = if key.starts_with(SPENT_KEY_PREFIX) {
+            load_spent(store, key.as_str()).is_none_or(|(_, exp)| exp < now)
+        } else if key.starts_with(LEASE_KEY_PREFIX) {
+            SpendLease::holder(store, key.as_str()).is_none_or(|(_, expires)| expires <= now)
+        } else {
+            continue;
+        };
+        if expired {
+            let _ = store.delete(key.as_str());
+        }
+    }
+}
+
+/// Checks the request's crawl-payment token for `crawler` and, if it still covers the
+/// price, charges it.
+pub(crate) fn redeem<S: KeyValueStore + ?Sized>(
+    store: &S,
+    cfg: &Config,
+    req: &Request,
+    crawler: &str,
+    secret: Option<&str>,
+    now: u64,
+) -> Redemption {
+    let Some(token) = req
+        .header(PAYMENT_HEADER)
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return Redemption::Absent;
+    };
+    match charge(store, cfg, token, crawler, secret, now) {
+        Ok(receipt) => Redemption::Paid(receipt),
+        Err(failure) => Redemption::Rejected(failure),
+    }
+}
+
+fn charge<S: KeyValueStore + ?Sized>(
+    store: &S,
+    cfg: &Config,
+    token: &str,
+    crawler: &str,
+    secret: Option<&str>,
+    now: u64,
+) -> Result<Receipt, PaymentFailure> {
+    if token.len() > MAX_TOKEN_CHARS {
+        return Err(PaymentFailure::Malformed);
+    }
+    let mut segments = token.split('.');
+    let (Some(header_raw), Some(claims_raw), Some(signature_raw), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return Err(PaymentFailure::Malformed);
+    };
+    let header = decode_segment(header_raw)
+        .and_then(|raw| serde_json::from_slice::<TokenHeader>(&raw).ok())
+        .ok_or(PaymentFailure::Malformed)?;
+    let signature = decode_segment(signature_raw).ok_or(PaymentFailure::Malformed)?;
+    let signing_input = &token[..header_raw.len() + 1 + claims_raw.len()];
+    let issuer = verify_signature(cfg, secret, &header, signing_input, &signature)?;
+
+    let claims = decode_segment(claims_raw)
+        .and_then(|raw| serde_json::from_slice::<TokenClaims>(&raw).ok())
+        .filter(|claims| {
+            claims.budget > 0 && !claims.jti.is_empty() && claims.jti.len() <= MAX_TOKEN_ID_CHARS
+        })
+        .ok_or(PaymentFailure::Malformed)?;
+    if !claims.sub.eq_ignore_ascii_case(crawler) {
+        return Err(PaymentFailure::WrongCrawler);
+    }
+    if claims.exp < now {
+        return Err(PaymentFailure::Expired);
+    }
+    if claims.cur != cfg.crawl_payment_currency {
+        return Err(PaymentFailure::CurrencyMismatch);
+    }
+
+    maybe_sweep_expired(store, now);
+    let key = spent_key(issuer.as_str(), claims.jti.as_str());
+    let _lease = acquire_lease(store, lease_key(key.as_str()), now).ok_or(PaymentFailure::Busy)?;
+    let spent = load_spent(store, key.as_str()).map_or(0, |(spent, _)| spent);
+    let price = cfg.crawl_payment_price_micros;
+    let total = spent.saturating_add(price);
+    if total > claims.budget {
+        return Err(PaymentFailure::Exhausted);
+    }
+    let _ = store.set(key.as_str(), format!("{}:{}", total, claims.exp).as_bytes());
+    Ok(Receipt {
+        token_id: claims.jti,
+        charged_micros: price,
+        remaining_micros: claims.budget - total,
+    })
+}
+
+/// The `402` a crawler gets instead of content: the price per request and how to pay it.
+pub(crate) fn offer_response(
+    cfg: &Config,
+    crawler: &str,
+    failure: Option<PaymentFailure>,
+) -> Response {
+    let mut algorithms = Vec::new();
+    if crate::config::crawl_payment_secret().is_some() {
+        algorithms.push("HS256");
+    }
+    if !cfg.crawl_payment_issuers.is_empty() {
+        algorithms.push("EdDSA");
+    }
+    let body = serde_json::json!({
+        "error": "payment_required",
+        "crawler": crawler,
+        "reason": failure.map(PaymentFailure::as_str),
+        "price": {
+            "amount": format_price(cfg.crawl_payment_price_micros),
+            "amount_micros": cfg.crawl_payment_price_micros,
+            "currency": cfg.crawl_payment_currency,
+            "per": "request"
+        },
+        "payment": {
+            "header": "Crawl-Payment",
+            "format": "jws",
+            "algorithms": algorithms,
+            "issuers": cfg
+                .crawl_payment_issuers
+                .iter()
+                .map(|issuer| issuer.name.as_str())
+                .collect::<Vec<_>>(),
+            "claims": ["sub", "jti", "exp", "budget", "cur"]
+        }
+    });
+    Response::builder()
+        .status(402)
+        .header("Content-Type", "application/json")
+        .header("Cache-Control", "no-store")
+        .header(
+            "Crawler-Price",
+            format!(
+                "{} {}",
+                cfg.crawl_payment_currency,
+                format_price(cfg.crawl_payment_price_micros)
+            ),
+        )
+        .body(body.to_string())
+        .build()
+}
+
+/// The pass-through response for a paid request.
+pub(crate) fn paid_response(cfg: &Config, receipt: &Receipt) -> Response {
+    Response::builder()
+        .status(200)
+        .header(
+            "Crawler-Charged",
+            format!(
+                "{} {}",
+                cfg.crawl_payment_currency,
+                format_price(receipt.charged_micros)
+            ),
+        )
+        .body("OK (paid crawl)")
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CrawlPaymentIssuer;
+    use ed25519_dalek::{Signer, SigningKey};
+    use spin_sdk::http::Method;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockStore {
+        map: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    impl KeyValueStore for MockStore {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
+            Ok(self.map.lock().unwrap().get(key).cloned())
+        }
+
+        fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
+            self.map
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_vec());
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), ()> {
+            self.map.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        fn get_keys(&self) -> Result<Vec<String>, ()> {
+            Ok(self.map.lock().unwrap().keys().cloned().collect())
+        }
+    }
+
+    const NOW: u64 = 1_735_689_600;
+    const SECRET: &str = "crawl-payment-secret";
+
+    fn encode(value: &serde_json::Value) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(value.to_string())
+    }
+
+    fn claims(sub: &str, budget: u64, exp: u64) -> serde_json::Value {
+        serde_json::json!({"sub": sub, "jti": "t-1", "exp": exp, "budget": budget, "cur": "USD"})
+    }
+
+    fn hs256_token(claims: &serde_json::Value) -> String {
+        let input = format!(
+            "{}.{}",
+            encode(&serde_json::json!({"alg": "HS256"})),
+            encode(claims)
+        );
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(input.as_bytes());
+        let signature = general_purpose::URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{}.{}", input, signature)
+    }
+
+    fn request(token: &str) -> Request {
+        Request::builder()
+            .method(Method::Get)
+            .uri("/articles/1")
+            .header(PAYMENT_HEADER, token)
+            .build()
+    }
+
+    fn config() -> Config {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.crawl_payment_price_micros = 1000;
+        cfg.crawl_payment_currency = "USD".to_string();
+        cfg
+    }
+
+    #[test]
+    fn formats_micro_prices() {
+        assert_eq!(format_price(1000), "0.001");
+        assert_eq!(format_price(2_500_000), "2.5");
+        assert_eq!(format_price(3_000_000), "3");
+    }
+
+    #[test]
+    fn hs256_token_is_charged_until_budget_runs_out() {
+        let cfg = config();
+        let store = MockStore::default();
+        let req = request(hs256_token(&claims("gptbot", 2500, NOW + 60)).as_str());
+
+        let first = redeem(&store, &cfg, &req, "GPTBot", Some(SECRET), NOW);
+        assert_eq!(
+            first,
+            Redemption::Paid(Receipt {
+                token_id: "t-1".to_string(),
+                charged_micros: 1000,
+                remaining_micros: 1500,
+            })
+        );
+        assert!(matches!(
+            redeem(&store, &cfg, &req, "GPTBot", Some(SECRET), NOW),
+            Redemption::Paid(_)
+        ));
+        assert_eq!(
+            redeem(&store, &cfg, &req, "GPTBot", Some(SECRET), NOW),
+            Redemption::Rejected(PaymentFailure::Exhausted)
+        );
+    }
+
+    #[test]
+    fn rejects_tokens_for_other_crawlers_or_expired_or_unsigned() {
+        let cfg = config();
+        let store = MockStore::default();
+        let token = hs256_token(&claims("GPTBot", 5000, NOW + 60));
+
+        assert_eq!(
+            redeem(
+                &store,
+                &cfg,
+                &request(token.as_str()),
+                "ClaudeBot",
+                Some(SECRET),
+                NOW
+            ),
+            Redemption::Rejected(PaymentFailure::WrongCrawler)
+        );
+        assert_eq!(
+            redeem(
+                &store,
+                &cfg,
+                &request(token.as_str()),
+                "GPTBot",
+                Some(SECRET),
+                NOW + 120
+            ),
+            Redemption::Rejected(PaymentFailure::Expired)
+        );
+        assert_eq!(
+            redeem(
+                &store,
+                &cfg,
+                &request(token.as_str()),
+                "GPTBot",
+                Some("other"),
+                NOW
+            ),
+            Redemption::Rejected(PaymentFailure::InvalidSignature)
+        );
+        assert_eq!(
+            redeem(&store, &cfg, &request(token.as_str()), "GPTBot", None, NOW),
+            Redemption::Rejected(PaymentFailure::UnknownIssuer)
+        );
+        assert_eq!(
+            redeem(
+                &store,
+                &cfg,
+                &request("not-a-token"),
+                "GPTBot",
+                Some(SECRET),
+                NOW
+            ),
+            Redemption::Rejected(PaymentFailure::Malformed)
+        );
+    }
+
+    #[test]
+    fn eddsa_token_verifies_against_configured_issuer() {
+        let signing_key = SigningKey::from_bytes(&[9u8; 32]);
+        let mut cfg = config();
+        cfg.crawl_payment_issuers = vec![CrawlPaymentIssuer {
+            name: "pay.example".to_string(),
+            key: general_purpose::URL_SAFE_NO_PAD.encode(signing_key.verifying_key().as_bytes()),
+        }];
+        let input = format!(
+            "{}.{}",
+            encode(&serde_json::json!({"alg": "EdDSA", "kid": "pay.example"})),
+            encode(&claims("agent", 1000, NOW + 60))
+        );
+        let signature =
+            general_purpose::URL_SAFE_NO_PAD.encode(signing_key.sign(input.as_bytes()).to_bytes());
+        let token = format!("{}.{}", input, signature);
+
+        let store = MockStore::default();
+        assert!(matches!(
+            redeem(&store, &cfg, &request(token.as_str()), "agent", None, NOW),
+            Redemption::Paid(_)
+        ));
+
+        cfg.crawl_payment_issuers[0].name = "other.example".to_string();
+        assert_eq!(
+            redeem(&store, &cfg, &request(token.as_str()), "agent", None, NOW),
+            Redemption::Rejected(PaymentFailure::UnknownIssuer)
+        );
+    }
+
+    #[test]
+    fn offer_names_price_and_reason() {
+        let response = offer_response(&config(), "GPTBot", Some(PaymentFailure::Exhausted));
+        assert_eq!(*response.status(), 402);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["crawler"], "GPTBot");
+        assert_eq!(body["reason"], "exhausted");
+        assert_eq!(body["price"]["amount"], "0.001");
+        assert_eq!(body["price"]["currency"], "USD");
+    }
+
+    #[test]
+    fn spend_of_expired_tokens_and_dead_leases_is_swept_hourly() {
+        let cfg = config();
+        let store = MockStore::default();
+        let short = hs256_token(&claims("GPTBot", 5000, NOW + 60));
+        assert!(matches!(
+            redeem(
+                &store,
+                &cfg,
+                &request(short.as_str()),
+                "GPTBot",
+                Some(SECRET),
+                NOW
+            ),
+            Redemption::Paid(_)
+        ));
+        let short_key = spent_key("hs256", "t-1");
+        assert_eq!(
+            load_spent(&store, short_key.as_str()),
+            Some((1000, NOW + 60))
+        );
+        let dead_lease = lease_key(spent_key("hs256", "t-dead").as_str());
+        store
+            .set(dead_lease.as_str(), format!("dead:{}", NOW + 1).as_bytes())
+            .unwrap();
+
+        let later = NOW + SWEEP_INTERVAL_SECONDS;
+        let mut long_claims = claims("GPTBot", 5000, later + 60);
+        long_claims["jti"] = "t-2".into();
+        let long = hs256_token(&long_claims);
+        assert!(matches!(
+            redeem(
+                &store,
+                &cfg,
+                &request(long.as_str()),
+                "GPTBot",
+                Some(SECRET),
+                later
+            ),
+            Redemption::Paid(_)
+        ));
+
+        assert!(store.get(short_key.as_str()).unwrap().is_none());
+        assert!(store.get(dead_lease.as_str()).unwrap().is_none());
+        assert_eq!(
+            load_spent(&store, spent_key("hs256", "t-2").as_str()),
+            Some((1000, later + 60))
+        );
+    }
+
+    #[test]
+    fn charges_to_one_token_are_serialised_by_a_lease() {
+        let cfg = config();
+        let store = MockStore::default();
+        let req = request(hs256_token(&claims("GPTBot", 1000, NOW + 60)).as_str());
+        let lease_key = lease_key(spent_key("hs256", "t-1").as_str());
+
+        let held = acquire_lease(&store, lease_key.clone(), NOW).unwrap();
+        assert!(acquire_lease(&store, lease_key.clone(), NOW).is_none());
+        assert_eq!(
+            redeem(&store, &cfg, &req, "GPTBot", Some(SECRET), NOW),
+            Redemption::Rejected(PaymentFailure::Busy)
+        );
+        drop(held);
+
+        assert!(matches!(
+            redeem(&store, &cfg, &req, "GPTBot", Some(SECRET), NOW),
+            Redemption::Paid(_)
+        ));
+        assert!(store.get(lease_key.as_str()).unwrap().is_none());
+        assert_eq!(
+            redeem(&store, &cfg, &req, "GPTBot", Some(SECRET), NOW),
+            Redemption::Rejected(PaymentFailure::Exhausted)
+        );
+    }
+
+    #[test]
+    fn stale_leases_expire_and_lost_lease_races_back_off() {
+        let store = MockStore::default();
+        let key = lease_key(spent_key("hs256", "t-1").as_str());
+
+        // A holder that died mid-charge stops blocking the token once its lease lapses.
+        store
+            .set(
+                key.as_str(),
+                format!("dead:{}", NOW + LEASE_TTL_SECONDS).as_bytes(),
+            )
+            .unwrap();
+        assert!(acquire_lease(&store, key.clone(), NOW).is_none());
+        let taken = acquire_lease(&store, key.clone(), NOW + LEASE_TTL_SECONDS).unwrap();
+
+        // The loser's release must not drop the winner's lease.
+        let loser = SpendLease {
+            store: &store,
+            key: key.clone(),
+            owner: "loser".to_string(),
+        };
+        drop(loser);
+        assert!(store.get(key.as_str()).unwrap().is_some());
+        drop(taken);
+        assert!(store.get(key.as_str()).unwrap().is_none());
+    }
+
+    /// Store whose lease writes are overwritten by a rival before they can be read back.
+    struct RacingStore {
+        inner: MockStore,
+    }
+
+    impl KeyValueStore for RacingStore {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
+            self.inner.get(key)
+        }
+
+        fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
+            self.inner.set(key, value)?;
+            if key.starts_with(LEASE_KEY_PREFIX) {
+                self.inner
+                    .set(key, format!("rival:{}", NOW + LEASE_TTL_SECONDS).as_bytes())?;
+            }
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), ()> {
+            self.inner.delete(key)
+        }
+    }
+
+    #[test]
+    fn a_charge_that_loses_the_lease_write_does_not_spend() {
+        let cfg = config();
+        let store = RacingStore {
+            inner: MockStore::default(),
+        };
+        let req = request(hs256_token(&claims("GPTBot", 5000, NOW + 60)).as_str());
+
+        assert_eq!(
+            redeem(&store, &cfg, &req, "GPTBot", Some(SECRET), NOW),
+            Redemption::Rejected(PaymentFailure::Busy)
+        );
+        assert!(store
+            .get(spent_key("hs256", "t-1").as_str())
+            .unwrap()
+            .is_none());
+    }
+}
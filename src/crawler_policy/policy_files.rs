@@ -0,0 +1,315 @@
+//! ai.txt, llms.txt and TDM Reservation Protocol output
+//!
+//! Expresses the same robots policy in the other formats crawlers read:
+//! - `/ai.txt` (Spawning) follows the AI training stance
+//! - `/llms.txt` lists operator-curated sections unless AI input is blocked
+//! - `tdm-reservation` / `tdm-policy` headers and `/.well-known/tdmrep.json` (W3C TDMRep)
+
+use crate::config::{Config, LlmsTxtSection};
+use serde_json::json;
+
+pub(crate) const MAX_LLMS_TXT_SECTIONS: usize = 16;
+pub(crate) const MAX_LLMS_TXT_LINKS_PER_SECTION: usize = 32;
+pub(crate) const MAX_LLMS_TXT_TEXT_CHARS: usize = 500;
+
+/// Generate Spawning-style ai.txt content
+pub fn generate_ai_txt(cfg: &Config) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    lines.push("# ai.txt - generated from the robots.txt AI policy".to_string());
+    lines.push(format!(
+        "# Content-Signal: {}",
+        super::robots::get_content_signal_header(cfg)
+    ));
+    lines.push("".to_string());
+    lines.push("User-Agent: *".to_string());
+    if cfg.robots_block_ai_training {
+        lines.push("Disallow: /".to_string());
+    } else {
+        lines.push("Allow: /".to_string());
+    }
+    lines.join("\n")
+}
+
+/// Generate llms.txt content. The title never comes from the request, since the file is
+/// cached publicly.
+pub fn generate_llms_txt(cfg: &Config) -> String {
+    let title = cfg.llms_txt_title.trim();
+    let mut lines: Vec<String> = Vec::new();
+    lines.push(format!(
+        "# {}",
+        if title.is_empty() { "llms.txt" } else { title }
+    ));
+    lines.push("".to_string());
+    if !cfg.llms_txt_summary.trim().is_empty() {
+        lines.push(format!("> {}", cfg.llms_txt_summary.trim()));
+        lines.push("".to_string());
+    }
+    lines.push(format!(
+        "Content-Signal: {}",
+        super::robots::get_content_signal_header(cfg)
+    ));
+
+    // Sections only advertise content for AI input; withhold them when that is reserved.
+    if cfg.robots_block_ai_search {
+        lines.push("".to_string());
+        lines.push(
+            "Use of this site as input to AI assistants and AI search is not permitted."
+                .to_string(),
+        );
+        return lines.join("\n");
+    }
+    for section in &cfg.llms_txt_sections {
+        lines.push("".to_string());
+        lines.push(format!("## {}", section.title.trim()));
+        lines.push("".to_string());
+        for link in &section.links {
+            let entry = format!("- [{}]({})", link.title.trim(), link.url.trim());
+            if link.notes.trim().is_empty() {
+                lines.push(entry);
+            } else {
+                lines.push(format!("{}: {}", entry, link.notes.trim()));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// `tdm-reservation` header value: `1` reserves text and data mining rights
+pub fn tdm_reservation_header(cfg: &Config) -> &'static str {
+    if cfg.robots_block_ai_training {
+        "1"
+    } else {
+        "0"
+    }
+}
+
+/// `tdm-policy` header value, only meaningful alongside a reservation
+pub fn tdm_policy_header(cfg: &Config) -> Option<&str> {
+    let url = cfg.tdm_policy_url.trim();
+    if cfg.robots_block_ai_training && !url.is_empty() {
+        Some(url)
+    } else {
+        None
+    }
+}
+
+/// Add `tdm-reservation` (and `tdm-policy`) to a response for protected content
+pub fn apply_tdm_headers(cfg: &Config, response: &mut spin_sdk::http::Response) {
+    if !cfg.robots_enabled {
+        return;
+    }
+    response.set_header("tdm-reservation", tdm_reservation_header(cfg));
+    if let Some(policy) = tdm_policy_header(cfg) {
+        response.set_header("tdm-policy", policy);
+    }
+}
+
+/// Generate `/.well-known/tdmrep.json` covering the whole site
+pub fn generate_tdmrep_json(cfg: &Config) -> String {
+    let mut rule = json!({
+        "location": "/*",
+        "tdm-reservation": if cfg.robots_block_ai_training { 1 } else { 0 },
+    });
+    if let Some(policy) = tdm_policy_header(cfg) {
+        rule["tdm-policy"] = json!(policy);
+    }
+    serde_json::to_string_pretty(&json!([rule])).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn is_single_line(value: &str) -> bool {
+    !value.chars().any(|c| c.is_control())
+}
+
+/// Validate a free-text llms.txt field (title, summary, notes)
+pub(crate) fn is_valid_llms_txt_text(value: &str) -> bool {
+    value.chars().count() <= MAX_LLMS_TXT_TEXT_CHARS && is_single_line(value)
+}
+
+/// Accepts site-relative paths and absolute http(s) URLs that are safe inside a Markdown link.
+pub(crate) fn is_valid_llms_txt_url(value: &str) -> bool {
+    let value = value.trim();
+    let scoped =
+        value.starts_with('/') || value.starts_with("https://") || value.starts_with("http://");
+    scoped
+        && value.len() <= 2048
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || c == '(' || c == ')')
+}
+
+/// Validate an `llms_txt_sections` list, returning the first problem found
+pub(crate) fn validate_llms_txt_sections(sections: &[LlmsTxtSection]) -> Result<(), String> {
+    if sections.len() > MAX_LLMS_TXT_SECTIONS {
+        return Err(format!(
+            "llms_txt_sections supports at most {} sections",
+            MAX_LLMS_TXT_SECTIONS
+        ));
+    }
+    for (index, section) in sections.iter().enumerate() {
+        if section.title.trim().is_empty() || !is_valid_llms_txt_text(&section.title) {
+            return Err(format!(
+                "llms_txt_sections[{}].title must be a non-empty single line",
+                index
+            ));
+        }
+        if section.links.len() > MAX_LLMS_TXT_LINKS_PER_SECTION {
+            return Err(format!(
+                "llms_txt_sections[{}].links supports at most {} links",
+                index, MAX_LLMS_TXT_LINKS_PER_SECTION
+            ));
+        }
+        for (link_index, link) in section.links.iter().enumerate() {
+            if link.title.trim().is_empty()
+                || !is_valid_llms_txt_text(&link.title)
+                || link.title.contains(['[', ']'])
+            {
+                return Err(format!(
+                    "llms_txt_sections[{}].links[{}].title must be a non-empty single line without brackets",
+                    index, link_index
+                ));
+            }
+            if !is_valid_llms_txt_url(&link.url) {
+                return Err(format!(
+                    "llms_txt_sections[{}].links[{}].url must be a site path or http(s) URL",
+                    index, link_index
+                ));
+            }
+            if !is_valid_llms_txt_text(&link.notes) {
+                return Err(format!(
+                    "llms_txt_sections[{}].links[{}].notes must be a single line",
+                    index, link_index
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LlmsTxtLink;
+
+    fn test_config() -> Config {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.robots_enabled = true;
+        cfg.robots_block_ai_training = true;
+        cfg.robots_block_ai_search = false;
+        cfg.robots_allow_search_engines = true;
+        cfg.llms_txt_title = "Example Docs".to_string();
+        cfg.llms_txt_summary = "Reference documentation for Example.".to_string();
+        cfg.llms_txt_sections = vec![LlmsTxtSection {
+            title: "Docs".to_string(),
+            links: vec![
+                LlmsTxtLink {
+                    title: "Quick start".to_string(),
+                    url: "/docs/start.md".to_string(),
+                    notes: "Install and first run".to_string(),
+                },
+                LlmsTxtLink {
+                    title: "API".to_string(),
+                    url: "https://example.com/api.md".to_string(),
+                    notes: String::new(),
+                },
+            ],
+        }];
+        cfg.tdm_policy_url = "https://example.com/tdm-policy.json".to_string();
+        cfg
+    }
+
+    #[test]
+    fn ai_txt_follows_training_stance() {
+        let mut cfg = test_config();
+        let blocked = generate_ai_txt(&cfg);
+        assert!(blocked.contains("User-Agent: *\nDisallow: /"));
+        assert!(blocked.contains("ai-train=no"));
+
+        cfg.robots_block_ai_training = false;
+        let allowed = generate_ai_txt(&cfg);
+        assert!(allowed.contains("User-Agent: *\nAllow: /"));
+    }
+
+    #[test]
+    fn llms_txt_lists_curated_sections() {
+        let cfg = test_config();
+        let llms = generate_llms_txt(&cfg);
+        assert!(llms.starts_with("# Example Docs\n\n> Reference documentation for Example."));
+        assert!(llms.contains("## Docs"));
+        assert!(llms.contains("- [Quick start](/docs/start.md): Install and first run"));
+        assert!(llms.ends_with("- [API](https://example.com/api.md)"));
+    }
+
+    #[test]
+    fn llms_txt_withholds_sections_when_ai_input_blocked() {
+        let mut cfg = test_config();
+        cfg.robots_block_ai_search = true;
+        cfg.llms_txt_title = String::new();
+        let llms = generate_llms_txt(&cfg);
+        assert!(llms.starts_with("# llms.txt\n"));
+        assert!(llms.contains("ai-input=no"));
+        assert!(!llms.contains("## Docs"));
+        assert!(llms.contains("not permitted"));
+    }
+
+    #[test]
+    fn tdmrep_reserves_rights_when_training_blocked() {
+        let mut cfg = test_config();
+        assert_eq!(tdm_reservation_header(&cfg), "1");
+        assert_eq!(
+            tdm_policy_header(&cfg),
+            Some("https://example.com/tdm-policy.json")
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&generate_tdmrep_json(&cfg)).unwrap();
+        assert_eq!(parsed[0]["location"], "/*");
+        assert_eq!(parsed[0]["tdm-reservation"], 1);
+        assert_eq!(
+            parsed[0]["tdm-policy"],
+            "https://example.com/tdm-policy.json"
+        );
+
+        cfg.robots_block_ai_training = false;
+        assert_eq!(tdm_reservation_header(&cfg), "0");
+        assert_eq!(tdm_policy_header(&cfg), None);
+        let parsed: serde_json::Value = serde_json::from_str(&generate_tdmrep_json(&cfg)).unwrap();
+        assert_eq!(parsed[0]["tdm-reservation"], 0);
+        assert!(parsed[0].get("tdm-policy").is_none());
+    }
+
+    #[test]
+    fn tdm_headers_are_added_to_protected_content_responses() {
+        let mut cfg = test_config();
+        let mut response = spin_sdk::http::Response::new(200, "page");
+        apply_tdm_headers(&cfg, &mut response);
+        assert_eq!(
+            response.header("tdm-reservation").and_then(|v| v.as_str()),
+            Some("1")
+        );
+        assert_eq!(
+            response.header("tdm-policy").and_then(|v| v.as_str()),
+            Some("https://example.com/tdm-policy.json")
+        );
+
+        cfg.robots_enabled = false;
+        let mut untouched = spin_sdk::http::Response::new(200, "page");
+        apply_tdm_headers(&cfg, &mut untouched);
+        assert!(untouched.header("tdm-reservation").is_none());
+    }
+
+    #[test]
+    fn llms_txt_sections_validation_rejects_markdown_breakouts() {
+        let cfg = test_config();
+        assert!(validate_llms_txt_sections(&cfg.llms_txt_sections).is_ok());
+
+        let mut bad = cfg.llms_txt_sections.clone();
+        bad[0].links[0].url = "javascript:alert(1)".to_string();
+        assert!(validate_llms_txt_sections(&bad).is_err());
+
+        let mut bad = cfg.llms_txt_sections.clone();
+        bad[0].links[0].url = "/docs/a) [x](/y".to_string();
+        assert!(validate_llms_txt_sections(&bad).is_err());
+
+        let mut bad = cfg.llms_txt_sections.clone();
+        bad[0].title = "Docs\n## Injected".to_string();
+        assert!(validate_llms_txt_sections(&bad).is_err());
+    }
+}
@@ -1,65 +1,17 @@
 //! robots.txt generation with AI crawler blocking and honeypot integration
 //!
 //! Generates configurable robots.txt that:
-//! - Blocks known AI training crawlers
+//! - Blocks the AI training and AI search crawlers in `crawler_catalog`
 //! - Supports Cloudflare Content-Signal directive
 //! - Allows legitimate search engine crawlers
+//! - Lists the live rotating honeypot paths for all other bots, which only a bot mining
+//!   robots.txt for targets will visit
 
-use crate::config::Config;
-
-/// Known AI training crawler user-agents
-pub const AI_TRAINING_BOTS: &[&str] = &[
-    "GPTBot",
-    "ChatGPT-User",
-    "CCBot",
-    "Google-Extended",
-    "Applebot-Extended",
-    "anthropic-ai",
-    "ClaudeBot",
-    "Claude-Web",
-    "Bytespider",
-    "FacebookBot",
-    "Meta-ExternalAgent",
-    "Meta-ExternalFetcher",
-    "Diffbot",
-    "Omgilibot",
-    "Omgili",
-    "cohere-ai",
-    "cohere-training-data-crawler",
-    "Timpibot",
-    "PanguBot",
-    "Kangaroo Bot",
-    "AI2Bot",
-    "Ai2Bot-Dolma",
-    "img2dataset",
-];
-
-/// AI search/assistant crawlers (real-time fetching)
-pub const AI_SEARCH_BOTS: &[&str] = &[
-    "PerplexityBot",
-    "Perplexity-User",
-    "YouBot",
-    "OAI-SearchBot",
-    "Claude-SearchBot",
-    "DuckAssistBot",
-    "Amazonbot",
-];
-
-/// Legitimate search engine crawlers to allow
-pub const SEARCH_ENGINE_BOTS: &[&str] = &[
-    "Googlebot",
-    "Bingbot",
-    "Slurp", // Yahoo
-    "DuckDuckBot",
-    "Baiduspider",
-    "YandexBot",
-    "facebot", // Facebook link preview (not training)
-    "Twitterbot",
-    "LinkedInBot",
-];
+use super::catalog::entries_in;
+use crate::config::{Config, CrawlerCategory};
 
 /// Generate robots.txt content based on configuration
-pub fn generate_robots_txt(cfg: &Config) -> String {
+pub fn generate_robots_txt(cfg: &Config, now: u64) -> String {
     let mut lines: Vec<String> = Vec::new();
 
     // Header comment with Content-Signal
@@ -96,8 +48,8 @@ pub fn generate_robots_txt(cfg: &Config) -> String {
     // Block AI training bots
     if cfg.robots_block_ai_training {
         lines.push("# AI Training Crawlers - BLOCKED".to_string());
-        for bot in AI_TRAINING_BOTS {
-            lines.push(format!("User-agent: {}", bot));
+        for bot in entries_in(cfg, CrawlerCategory::AiTraining) {
+            lines.push(format!("User-agent: {}", bot.name));
             lines.push("Disallow: /".to_string());
             lines.push("".to_string());
         }
@@ -106,22 +58,46 @@ pub fn generate_robots_txt(cfg: &Config) -> String {
     // Block AI search/assistant bots
     if cfg.robots_block_ai_search {
         lines.push("# AI Search/Assistant Crawlers - BLOCKED".to_string());
-        for bot in AI_SEARCH_BOTS {
-            lines.push(format!("User-agent: {}", bot));
+        for bot in entries_in(cfg, CrawlerCategory::AiSearch) {
+            lines.push(format!("User-agent: {}", bot.name));
             lines.push("Disallow: /".to_string());
             lines.push("".to_string());
         }
     }
 
+    // Allowed AI bots only need their own group when they carry a crawl-delay override
+    let paced_ai_bots: Vec<_> = [
+        (CrawlerCategory::AiTraining, cfg.robots_block_ai_training),
+        (CrawlerCategory::AiSearch, cfg.robots_block_ai_search),
+    ]
+    .into_iter()
+    .filter(|(_, blocked)| !blocked)
+    .flat_map(|(category, _)| entries_in(cfg, category))
+    .filter(|bot| bot.crawl_delay.is_some())
+    .collect();
+    if !paced_ai_bots.is_empty() {
+        lines.push("# AI Crawlers - ALLOWED".to_string());
+        for bot in paced_ai_bots {
+            lines.push(format!("User-agent: {}", bot.name));
+            lines.push("Allow: /".to_string());
+            push_crawl_delay(
+                &mut lines,
+                bot.crawl_delay.unwrap_or(cfg.robots_crawl_delay),
+            );
+            lines.push("".to_string());
+        }
+    }
+
     // Allow legitimate search engines with crawl delay
     if cfg.robots_allow_search_engines {
         lines.push("# Search Engine Crawlers - ALLOWED".to_string());
-        for bot in SEARCH_ENGINE_BOTS {
-            lines.push(format!("User-agent: {}", bot));
+        for bot in entries_in(cfg, CrawlerCategory::SearchEngine) {
+            lines.push(format!("User-agent: {}", bot.name));
             lines.push("Allow: /".to_string());
-            if cfg.robots_crawl_delay > 0 {
-                lines.push(format!("Crawl-delay: {}", cfg.robots_crawl_delay));
-            }
+            push_crawl_delay(
+                &mut lines,
+                bot.crawl_delay.unwrap_or(cfg.robots_crawl_delay),
+            );
             lines.push("".to_string());
         }
     }
@@ -131,8 +107,9 @@ pub fn generate_robots_txt(cfg: &Config) -> String {
     lines.push("User-agent: *".to_string());
     if cfg.robots_allow_search_engines {
         lines.push("Allow: /".to_string());
-        if cfg.robots_crawl_delay > 0 {
-            lines.push(format!("Crawl-delay: {}", cfg.robots_crawl_delay));
+        push_crawl_delay(&mut lines, cfg.robots_crawl_delay);
+        for path in crate::enforcement::honeypot::live_rotating_paths(cfg, now) {
+            lines.push(format!("Disallow: {}", path));
         }
     } else {
         lines.push("Disallow: /".to_string());
@@ -146,6 +123,12 @@ pub fn generate_robots_txt(cfg: &Config) -> String {
     lines.join("\n")
 }
 
+fn push_crawl_delay(lines: &mut Vec<String>, delay: u32) {
+    if delay > 0 {
+        lines.push(format!("Crawl-delay: {}", delay));
+    }
+}
+
 /// Get a human-readable policy name
 fn get_policy_name(cfg: &Config) -> &'static str {
     match (
@@ -192,6 +175,8 @@ mod tests {
     use super::*;
     use crate::config::BanDurations;
 
+    const NOW: u64 = 1_700_000_000;
+
     fn test_config() -> Config {
         let mut cfg = crate::config::defaults().clone();
         cfg.ban_duration = 21600;
@@ -236,7 +221,7 @@ mod tests {
     #[test]
     fn test_generate_robots_txt_blocks_ai_training() {
         let cfg = test_config();
-        let robots = generate_robots_txt(&cfg);
+        let robots = generate_robots_txt(&cfg, NOW);
 
         // Should block GPTBot
         assert!(robots.contains("User-agent: GPTBot"));
@@ -250,7 +235,7 @@ mod tests {
     #[test]
     fn test_generate_robots_txt_does_not_advertise_deception_paths() {
         let cfg = test_config();
-        let robots = generate_robots_txt(&cfg);
+        let robots = generate_robots_txt(&cfg, NOW);
 
         assert!(!robots.contains("Disallow: /maze/"));
         assert!(!robots.contains("Disallow: /trap/"));
@@ -267,10 +252,40 @@ mod tests {
         assert!(header.contains("search=yes"));
     }
 
+    #[test]
+    fn test_catalog_entries_drive_groups_and_crawl_delay() {
+        let mut cfg = test_config();
+        cfg.crawler_catalog = vec![
+            crate::config::CrawlerCatalogEntry {
+                name: "NewAIBot".to_string(),
+                category: CrawlerCategory::AiTraining,
+                ..Default::default()
+            },
+            crate::config::CrawlerCatalogEntry {
+                name: "PoliteSearchBot".to_string(),
+                category: CrawlerCategory::AiSearch,
+                crawl_delay: Some(30),
+                ..Default::default()
+            },
+            crate::config::CrawlerCatalogEntry {
+                name: "Googlebot".to_string(),
+                category: CrawlerCategory::SearchEngine,
+                crawl_delay: Some(0),
+                ..Default::default()
+            },
+        ];
+        let robots = generate_robots_txt(&cfg, NOW);
+
+        assert!(robots.contains("User-agent: NewAIBot\nDisallow: /"));
+        assert!(!robots.contains("User-agent: GPTBot"));
+        assert!(robots.contains("User-agent: PoliteSearchBot\nAllow: /\nCrawl-delay: 30"));
+        assert!(robots.contains("User-agent: Googlebot\nAllow: /\n\n"));
+    }
+
     #[test]
     fn test_crawl_delay() {
         let cfg = test_config();
-        let robots = generate_robots_txt(&cfg);
+        let robots = generate_robots_txt(&cfg, NOW);
 
         assert!(robots.contains("Crawl-delay: 2"));
     }
@@ -279,18 +294,36 @@ mod tests {
     fn test_honeypot_paths_remain_hidden_when_maze_disabled() {
         let mut cfg = test_config();
         cfg.maze_enabled = false;
-        let robots = generate_robots_txt(&cfg);
+        let robots = generate_robots_txt(&cfg, NOW);
 
         assert!(!robots.contains("Disallow: /instaban"));
         assert!(!robots.contains("Disallow: /maze/"));
         assert!(!robots.contains("Disallow: /trap/"));
     }
 
+    #[test]
+    fn test_rotating_honeypot_paths_are_disallowed_for_all_other_bots() {
+        let _lock = crate::test_support::lock_env();
+        let cfg = test_config();
+        let robots = generate_robots_txt(&cfg, NOW);
+        let (named_groups, default_group) = robots.split_once("User-agent: *").unwrap();
+
+        for path in crate::enforcement::honeypot::live_rotating_paths(&cfg, NOW) {
+            assert!(default_group.contains(format!("Disallow: {}\n", path).as_str()));
+            assert!(!named_groups.contains(path.as_str()));
+        }
+
+        let mut disabled = test_config();
+        disabled.honeypot_rotation_enabled = false;
+        let robots = generate_robots_txt(&disabled, NOW);
+        assert!(robots.contains("User-agent: *\nAllow: /\nCrawl-delay: 2\n\n"));
+    }
+
     #[test]
     fn test_honeypot_path_is_hidden_when_honeypot_disabled() {
         let mut cfg = test_config();
         cfg.honeypot_enabled = false;
-        let robots = generate_robots_txt(&cfg);
+        let robots = generate_robots_txt(&cfg, NOW);
 
         assert!(!robots.contains("Disallow: /instaban"));
     }
@@ -3,8 +3,6 @@
 // Handles persistent IP bans, expiry, and ban reasons using the Spin key-value store.
 
 use crate::challenge::KeyValueStore;
-use spin_sdk::key_value::Store;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
@@ -34,7 +32,7 @@ fn ban_index_key(site_id: &str) -> String {
     format!("ban_index:{}", site_id)
 }
 
-fn load_ban_index(store: &impl KeyValueStore, site_id: &str) -> Vec<String> {
+fn load_ban_index(store: &(impl KeyValueStore + ?Sized), site_id: &str) -> Vec<String> {
     let key = ban_index_key(site_id);
     store
         .get(&key)
@@ -44,7 +42,7 @@ fn load_ban_index(store: &impl KeyValueStore, site_id: &str) -> Vec<String> {
         .unwrap_or_default()
 }
 
-fn save_ban_index(store: &impl KeyValueStore, site_id: &str, index: &[String]) {
+fn save_ban_index(store: &(impl KeyValueStore + ?Sized), site_id: &str, index: &[String]) {
     if let Ok(val) = serde_json::to_vec(index) {
         let key = ban_index_key(site_id);
         if let Err(e) = store.set(&key, &val) {
@@ -53,7 +51,7 @@ fn save_ban_index(store: &impl KeyValueStore, site_id: &str, index: &[String]) {
     }
 }
 
-fn add_to_ban_index(store: &impl KeyValueStore, site_id: &str, ip: &str) {
+fn add_to_ban_index(store: &(impl KeyValueStore + ?Sized), site_id: &str, ip: &str) {
     let mut index = load_ban_index(store, site_id);
     if !index.iter().any(|v| v == ip) {
         index.push(ip.to_string());
@@ -61,7 +59,7 @@ fn add_to_ban_index(store: &impl KeyValueStore, site_id: &str, ip: &str) {
     }
 }
 
-fn remove_from_ban_index(store: &impl KeyValueStore, site_id: &str, ip: &str) {
+fn remove_from_ban_index(store: &(impl KeyValueStore + ?Sized), site_id: &str, ip: &str) {
     let mut index = load_ban_index(store, site_id);
     let before = index.len();
     index.retain(|v| v != ip);
@@ -71,7 +69,10 @@ fn remove_from_ban_index(store: &impl KeyValueStore, site_id: &str, ip: &str) {
 }
 
 /// Returns all active bans and prunes expired/missing entries from the index.
-pub fn list_active_bans(store: &impl KeyValueStore, site_id: &str) -> Vec<(String, BanEntry)> {
+pub fn list_active_bans(
+    store: &(impl KeyValueStore + ?Sized),
+    site_id: &str,
+) -> Vec<(String, BanEntry)> {
     let index = load_ban_index(store, site_id);
     let original_len = index.len();
     let now = now_ts();
@@ -114,7 +115,10 @@ pub fn list_active_bans(store: &impl KeyValueStore, site_id: &str) -> Vec<(Strin
 }
 
 /// Store-aware variant that can rebuild the index from existing ban keys when empty.
-pub fn list_active_bans_with_scan(store: &Store, site_id: &str) -> Vec<(String, BanEntry)> {
+pub fn list_active_bans_with_scan<S: KeyValueStore + ?Sized>(
+    store: &S,
+    site_id: &str,
+) -> Vec<(String, BanEntry)> {
     let mut active = list_active_bans(store, site_id);
     if !active.is_empty() {
         return active;
@@ -160,7 +164,7 @@ pub fn list_active_bans_with_scan(store: &Store, site_id: &str) -> Vec<(String,
 
 /// Checks if an IP is currently banned for a given site.
 /// Returns true if the ban is active, false otherwise. Cleans up expired/invalid bans.
-pub fn is_banned(store: &impl KeyValueStore, site_id: &str, ip: &str) -> bool {
+pub fn is_banned(store: &(impl KeyValueStore + ?Sized), site_id: &str, ip: &str) -> bool {
     let key = format!("ban:{}:{}", site_id, ip);
     match store.get(&key) {
         Ok(Some(val)) => {
@@ -202,7 +206,7 @@ pub fn ban_ip(
 }
 
 pub fn ban_ip_with_fingerprint(
-    store: &impl KeyValueStore,
+    store: &(impl KeyValueStore + ?Sized),
     site_id: &str,
     ip: &str,
     reason: &str,
@@ -236,7 +240,7 @@ pub fn ban_ip_with_fingerprint(
 }
 
 /// Unbans an IP for a given site by removing its ban entry from the key-value store.
-pub fn unban_ip(store: &impl KeyValueStore, site_id: &str, ip: &str) {
+pub fn unban_ip(store: &(impl KeyValueStore + ?Sized), site_id: &str, ip: &str) {
     let key = format!("ban:{}:{}", site_id, ip);
     if let Err(e) = store.delete(&key) {
         eprintln!("[ban] failed to delete ban {}: {:?}", key, e);
@@ -246,10 +250,7 @@ pub fn unban_ip(store: &impl KeyValueStore, site_id: &str, ip: &str) {
 
 /// Returns the current UNIX timestamp in seconds (used for ban expiry).
 fn now_ts() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
+    crate::runtime::clock::now_ts()
 }
 
 #[cfg(test)]
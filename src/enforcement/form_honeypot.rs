@@ -0,0 +1,333 @@
+// src/enforcement/form_honeypot.rs
+// Form-field honeypots for protected HTML.
+// POST forms in forwarded HTML get invisible decoy inputs and a signed time-to-submit token.
+// Submissions that fill a decoy, or arrive sooner than a person could fill the form, are bots.
+
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use spin_sdk::http::{Method, Request, Response};
+
+const FORM_MARKER: &str = "data-shuma-form-honeypot=\"1\"";
+/// Decoy inputs; names a form-filling bot will happily complete.
+pub(crate) const DECOY_FIELDS: [&str; 2] = ["url_homepage", "contact_fax"];
+/// Hidden input carrying the signed issue time of the page.
+pub(crate) const TOKEN_FIELD: &str = "shuma_ft";
+const MAX_TOKEN_LEN: usize = 256;
+const MAX_FORM_BODY_BYTES: usize = 64 * 1024;
+/// Tokens older than this are ignored, so a harvested token cannot vouch for submissions forever.
+const MAX_TOKEN_AGE_SECONDS: u64 = 24 * 60 * 60;
+
+/// Why a submission tripped the honeypot; `as_str` is the `form_honeypot_hits_total` label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FormHoneypotTrip {
+    DecoyFilled,
+    TooFast,
+}
+
+impl FormHoneypotTrip {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            FormHoneypotTrip::DecoyFilled => "decoy_filled",
+            FormHoneypotTrip::TooFast => "too_fast",
+        }
+    }
+}
+
+fn token_mac(ip_bucket: &str, issued_at: u64) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(crate::config::challenge_secret().as_bytes()).unwrap();
+    mac.update(format!("form_honeypot:{}:{}", ip_bucket, issued_at).as_bytes());
+    mac
+}
+
+/// Time-to-submit token bound to the client's IP bucket: `<issued_at>.<signature>`.
+pub(crate) fn issue_submit_token(ip: &str, now: u64) -> String {
+    let ip_bucket = crate::signals::ip_identity::bucket_ip(ip);
+    let sig = token_mac(ip_bucket.as_str(), now).finalize().into_bytes();
+    format!("{}.{}", now, general_purpose::URL_SAFE_NO_PAD.encode(sig))
+}
+
+/// Issue time of a valid token for this client; `None` when forged, foreign or malformed.
+fn verify_submit_token(token: &str, ip: &str) -> Option<u64> {
+    if token.len() > MAX_TOKEN_LEN {
+        return None;
+    }
+    let (issued_at, sig_b64) = token.split_once('.')?;
+    let issued_at = issued_at.parse::<u64>().ok()?;
+    let sig = general_purpose::URL_SAFE_NO_PAD.decode(sig_b64).ok()?;
+    let ip_bucket = crate::signals::ip_identity::bucket_ip(ip);
+    token_mac(ip_bucket.as_str(), issued_at)
+        .verify_slice(sig.as_slice())
+        .ok()?;
+    Some(issued_at)
+}
+
+fn is_post_form_tag(tag: &str) -> bool {
+    let lower = tag.to_ascii_lowercase();
+    lower.contains("method=\"post\"")
+        || lower.contains("method='post'")
+        || lower.contains("method=post")
+}
+
+fn honeypot_fields_html(token: &str) -> String {
+    let decoys: String = DECOY_FIELDS
+        .iter()
+        .map(|name| {
+            format!(
+                r#"<input type="text" name="{name}" value="" tabindex="-1" autocomplete="off">"#
+            )
+        })
+        .collect();
+    format!(
+        r#"<div aria-hidden="true" {FORM_MARKER} style="position:absolute;left:-10000px;top:auto;width:1px;height:1px;overflow:hidden;">{decoys}</div><input type="hidden" name="{TOKEN_FIELD}" value="{token}">"#
+    )
+}
+
+/// Insert decoy inputs and the submit token right after every `<form method="post">` opening tag.
+pub(crate) fn inject_form_honeypots_html(html: &str, token: &str) -> String {
+    if html.contains(FORM_MARKER) {
+        return html.to_string();
+    }
+    let fields = honeypot_fields_html(token);
+    let lower = html.to_ascii_lowercase();
+    let mut out = String::with_capacity(html.len() + fields.len());
+    let mut cursor = 0;
+    while let Some(offset) = lower[cursor..].find("<form") {
+        let start = cursor + offset;
+        let after_name = lower.as_bytes().get(start + 5).copied();
+        let Some(end) = lower[start..].find('>').map(|idx| start + idx + 1) else {
+            break;
+        };
+        out.push_str(&html[cursor..end]);
+        let is_form_tag = matches!(after_name, Some(b' ' | b'\t' | b'\n' | b'\r' | b'>'));
+        if is_form_tag && is_post_form_tag(&html[start..end]) {
+            out.push_str(fields.as_str());
+        }
+        cursor = end;
+    }
+    out.push_str(&html[cursor..]);
+    out
+}
+
+pub(crate) fn maybe_inject_form_honeypots(
+    req: &Request,
+    cfg: &crate::config::Config,
+    ip: &str,
+    response: Response,
+    now: u64,
+) -> Response {
+    if !cfg.form_honeypot_enabled || *req.method() != Method::Get {
+        return response;
+    }
+    if *response.status() != 200 || !crate::maze::covert_decoy::is_html_like_response(&response) {
+        return response;
+    }
+    let Ok(html) = std::str::from_utf8(response.body()) else {
+        return response;
+    };
+    let token = issue_submit_token(ip, now);
+    let updated = inject_form_honeypots_html(html, token.as_str());
+    if updated.len() == html.len() {
+        return response;
+    }
+    crate::maze::covert_decoy::clone_response_with_body(&response, updated)
+}
+
+fn form_field(form: &str, name: &str) -> Option<String> {
+    form.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        if key != name {
+            return None;
+        }
+        let value = value.replace('+', " ");
+        Some(
+            percent_encoding::percent_decode_str(value.as_str())
+                .decode_utf8_lossy()
+                .to_string(),
+        )
+    })
+}
+
+fn is_urlencoded_form(req: &Request) -> bool {
+    req.header("content-type")
+        .and_then(|value| value.as_str())
+        .map(|value| {
+            value
+                .to_ascii_lowercase()
+                .starts_with("application/x-www-form-urlencoded")
+        })
+        .unwrap_or(false)
+}
+
+/// Check a form POST for filled decoys or a submit faster than `form_honeypot_min_submit_seconds`.
+/// Submissions without a valid, unexpired token for this client are left alone, so forms we
+/// never served, and tokens replayed from elsewhere, are unaffected.
+pub(crate) fn evaluate_submission(
+    req: &Request,
+    cfg: &crate::config::Config,
+    ip: &str,
+    now: u64,
+) -> Option<FormHoneypotTrip> {
+    if *req.method() != Method::Post || !is_urlencoded_form(req) {
+        return None;
+    }
+    let body = req.body();
+    if body.len() > MAX_FORM_BODY_BYTES {
+        return None;
+    }
+    let form = std::str::from_utf8(body).ok()?;
+    let token = form_field(form, TOKEN_FIELD)?;
+    let issued_at = verify_submit_token(token.trim(), ip)?;
+    if now > issued_at.saturating_add(MAX_TOKEN_AGE_SECONDS) {
+        return None;
+    }
+    let decoy_filled = DECOY_FIELDS.iter().any(|name| {
+        form_field(form, name)
+            .map(|value| !value.trim().is_empty())
+            .unwrap_or(false)
+    });
+    if decoy_filled {
+        return Some(FormHoneypotTrip::DecoyFilled);
+    }
+    if now < issued_at.saturating_add(cfg.form_honeypot_min_submit_seconds) {
+        return Some(FormHoneypotTrip::TooFast);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        evaluate_submission, inject_form_honeypots_html, issue_submit_token,
+        maybe_inject_form_honeypots, FormHoneypotTrip, TOKEN_FIELD,
+    };
+    use spin_sdk::http::{Method, Request, Response};
+
+    const IP: &str = "198.51.100.9";
+
+    fn form_post(body: &str) -> Request {
+        Request::builder()
+            .method(Method::Post)
+            .uri("/contact")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(body.as_bytes().to_vec())
+            .build()
+    }
+
+    fn submission(token: &str, fax: &str) -> String {
+        format!(
+            "name=Ada&url_homepage=&contact_fax={}&{}={}",
+            fax, TOKEN_FIELD, token
+        )
+    }
+
+    #[test]
+    fn injects_decoys_into_post_forms_only() {
+        let html = r#"<html><body><FORM method="POST" action="/contact"><input name="name"></FORM><form action="/search"><input name="q"></form><formula></formula></body></html>"#;
+        let injected = inject_form_honeypots_html(html, "1000.sig");
+        assert_eq!(
+            injected.matches("data-shuma-form-honeypot=\"1\"").count(),
+            1
+        );
+        assert!(injected.contains(
+            r#"<FORM method="POST" action="/contact"><div aria-hidden="true" data-shuma-form-honeypot="1""#
+        ));
+        assert!(injected.contains(r#"name="shuma_ft" value="1000.sig""#));
+        assert!(injected.contains(r#"<form action="/search"><input name="q">"#));
+        assert_eq!(
+            inject_form_honeypots_html(injected.as_str(), "2000.sig"),
+            injected
+        );
+    }
+
+    #[test]
+    fn injects_only_into_html_get_responses() {
+        let _lock = crate::test_support::lock_env();
+        let cfg = crate::config::defaults().clone();
+        let page = r#"<html><body><form method="post"></form></body></html>"#;
+        let get = Request::builder()
+            .method(Method::Get)
+            .uri("/contact")
+            .body(Vec::<u8>::new())
+            .build();
+        let html = Response::builder()
+            .status(200)
+            .header("Content-Type", "text/html; charset=utf-8")
+            .body(page)
+            .build();
+        let injected = maybe_inject_form_honeypots(&get, &cfg, IP, html, 1_000);
+        assert!(String::from_utf8_lossy(injected.body()).contains(TOKEN_FIELD));
+
+        let not_found =
+            maybe_inject_form_honeypots(&get, &cfg, IP, Response::new(404, page), 1_000);
+        assert_eq!(String::from_utf8_lossy(not_found.body()), page);
+
+        let mut disabled = cfg.clone();
+        disabled.form_honeypot_enabled = false;
+        let html = Response::builder()
+            .status(200)
+            .header("Content-Type", "text/html")
+            .body(page)
+            .build();
+        let skipped = maybe_inject_form_honeypots(&get, &disabled, IP, html, 1_000);
+        assert_eq!(String::from_utf8_lossy(skipped.body()), page);
+    }
+
+    #[test]
+    fn flags_filled_decoys_and_fast_submits() {
+        let _lock = crate::test_support::lock_env();
+        let cfg = crate::config::defaults().clone();
+        let token = issue_submit_token(IP, 1_000);
+
+        let filled = form_post(submission(token.as_str(), "555-0100").as_str());
+        assert_eq!(
+            evaluate_submission(&filled, &cfg, IP, 1_060),
+            Some(FormHoneypotTrip::DecoyFilled)
+        );
+
+        let fast = form_post(submission(token.as_str(), "").as_str());
+        assert_eq!(
+            evaluate_submission(&fast, &cfg, IP, 1_001),
+            Some(FormHoneypotTrip::TooFast)
+        );
+        assert_eq!(evaluate_submission(&fast, &cfg, IP, 1_060), None);
+    }
+
+    #[test]
+    fn ignores_forms_without_a_valid_token_for_the_client() {
+        let _lock = crate::test_support::lock_env();
+        let cfg = crate::config::defaults().clone();
+        let token = issue_submit_token(IP, 1_000);
+
+        let untokened = form_post("name=Ada&contact_fax=555-0100");
+        assert_eq!(evaluate_submission(&untokened, &cfg, IP, 1_001), None);
+
+        let forged = form_post(submission("999.AAAA", "").as_str());
+        assert_eq!(evaluate_submission(&forged, &cfg, IP, 1_000), None);
+
+        let foreign = form_post(submission(token.as_str(), "555-0100").as_str());
+        assert_eq!(
+            evaluate_submission(&foreign, &cfg, "203.0.113.50", 1_060),
+            None
+        );
+
+        let forged_filled = form_post(submission("999.AAAA", "555-0100").as_str());
+        assert_eq!(evaluate_submission(&forged_filled, &cfg, IP, 1_060), None);
+    }
+
+    #[test]
+    fn ignores_tokens_older_than_the_maximum_age() {
+        let _lock = crate::test_support::lock_env();
+        let cfg = crate::config::defaults().clone();
+        let token = issue_submit_token(IP, 1_000);
+        let filled = form_post(submission(token.as_str(), "555-0100").as_str());
+
+        let last_valid = 1_000 + super::MAX_TOKEN_AGE_SECONDS;
+        assert_eq!(
+            evaluate_submission(&filled, &cfg, IP, last_valid),
+            Some(FormHoneypotTrip::DecoyFilled)
+        );
+        assert_eq!(evaluate_submission(&filled, &cfg, IP, last_valid + 1), None);
+    }
+}
@@ -1,6 +1,242 @@
 // src/honeypot.rs
 // Honeypot URL logic for WASM Bot Defence
+// Static trap paths come from `honeypots`. Rotating trap paths are derived per generation
+// from the challenge secret, so the paths a crawler learns stop working after the grace window.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use spin_sdk::http::{Method, Request, Response};
+
+const ROTATING_PATH_PREFIXES: [&str; 8] = [
+    "archive", "backup", "private", "staging", "export", "legacy", "drafts", "internal",
+];
+const ROTATING_PATH_HEX_LEN: usize = 12;
+/// Bound on generations honoured at once, whatever the grace window.
+const MAX_LIVE_GENERATIONS: u64 = 32;
+const LINK_MARKER: &str = "data-shuma-honeypot-link=\"1\"";
 
 pub fn is_honeypot(path: &str, honeypots: &[String]) -> bool {
     honeypots.iter().any(|h| h == path)
 }
+
+fn rotation_active(cfg: &crate::config::Config) -> bool {
+    cfg.honeypot_enabled && cfg.honeypot_rotation_enabled
+}
+
+/// Generations still honoured at `now`, newest first: the current one plus any that ended
+/// within `honeypot_rotation_grace_seconds`.
+pub(crate) fn live_generations(cfg: &crate::config::Config, now: u64) -> impl Iterator<Item = u64> {
+    let interval = cfg.honeypot_rotation_interval_seconds.max(1);
+    let current = now / interval;
+    let oldest = (now.saturating_sub(cfg.honeypot_rotation_grace_seconds) / interval)
+        .max(current.saturating_sub(MAX_LIVE_GENERATIONS - 1));
+    (oldest..=current).rev()
+}
+
+/// Trap path for one generation, e.g. `/archive/3fa9c0b1d2e4`.
+pub(crate) fn rotating_path(generation: u64) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(crate::config::challenge_secret().as_bytes()).unwrap();
+    mac.update(format!("honeypot_rotation:{}", generation).as_bytes());
+    let digest = mac.finalize().into_bytes();
+    let prefix = ROTATING_PATH_PREFIXES[usize::from(digest[0]) % ROTATING_PATH_PREFIXES.len()];
+    let suffix: String = digest[1..]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>()
+        .chars()
+        .take(ROTATING_PATH_HEX_LEN)
+        .collect();
+    format!("/{}/{}", prefix, suffix)
+}
+
+/// Rotating trap paths still honoured at `now`, newest first; empty when rotation is off.
+pub(crate) fn live_rotating_paths(cfg: &crate::config::Config, now: u64) -> Vec<String> {
+    if !rotation_active(cfg) {
+        return Vec::new();
+    }
+    live_generations(cfg, now).map(rotating_path).collect()
+}
+
+/// Generation of a live rotating trap path; `None` when `path` is not one.
+pub(crate) fn rotating_generation(
+    cfg: &crate::config::Config,
+    path: &str,
+    now: u64,
+) -> Option<u64> {
+    if !rotation_active(cfg) {
+        return None;
+    }
+    let prefix = path.trim_start_matches('/').split('/').next().unwrap_or("");
+    if !ROTATING_PATH_PREFIXES.contains(&prefix) {
+        return None;
+    }
+    live_generations(cfg, now).find(|generation| rotating_path(*generation) == path)
+}
+
+/// Static or live rotating trap path.
+pub(crate) fn is_trap_path(cfg: &crate::config::Config, path: &str, now: u64) -> bool {
+    is_honeypot(path, &cfg.honeypots) || rotating_generation(cfg, path, now).is_some()
+}
+
+fn inject_link_html(html: &str, href: &str) -> String {
+    if html.contains(LINK_MARKER) {
+        return html.to_string();
+    }
+    let link = format!(
+        r#"<div aria-hidden="true" {LINK_MARKER} style="position:absolute;left:-10000px;top:auto;width:1px;height:1px;overflow:hidden;">
+<a href="{href}" rel="nofollow" tabindex="-1">archive</a>
+</div>"#
+    );
+    let mut out = String::with_capacity(html.len() + link.len() + 1);
+    if let Some(idx) = html.rfind("</body>") {
+        out.push_str(&html[..idx]);
+        out.push_str(link.as_str());
+        out.push_str(&html[idx..]);
+    } else {
+        out.push_str(html);
+        out.push('\n');
+        out.push_str(link.as_str());
+    }
+    out
+}
+
+/// Add a hidden link to the current rotating trap path. Verified catalog crawlers are skipped,
+/// since they follow links without reading our robots.txt group for `*`.
+pub(crate) fn maybe_inject_rotating_link(
+    req: &Request,
+    cfg: &crate::config::Config,
+    ip: &str,
+    user_agent: &str,
+    response: Response,
+    now: u64,
+) -> Response {
+    if !rotation_active(cfg) || *req.method() != Method::Get {
+        return response;
+    }
+    if *response.status() != 200 || !crate::maze::covert_decoy::is_html_like_response(&response) {
+        return response;
+    }
+    if crate::crawler_policy::catalog::identify(
+        cfg,
+        ip,
+        user_agent,
+        &[
+            crate::config::CrawlerCategory::SearchEngine,
+            crate::config::CrawlerCategory::AiTraining,
+            crate::config::CrawlerCategory::AiSearch,
+        ],
+    )
+    .is_some()
+    {
+        return response;
+    }
+    let Ok(html) = std::str::from_utf8(response.body()) else {
+        return response;
+    };
+    let Some(href) = live_generations(cfg, now).next().map(rotating_path) else {
+        return response;
+    };
+    let updated = inject_link_html(html, href.as_str());
+    crate::maze::covert_decoy::clone_response_with_body(&response, updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_trap_path, live_rotating_paths, maybe_inject_rotating_link, rotating_generation,
+        rotating_path,
+    };
+    use spin_sdk::http::{Method, Request, Response};
+
+    const DAY: u64 = 86_400;
+
+    fn rotation_config() -> crate::config::Config {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.honeypot_enabled = true;
+        cfg.honeypot_rotation_enabled = true;
+        cfg.honeypot_rotation_interval_seconds = DAY;
+        cfg.honeypot_rotation_grace_seconds = DAY;
+        cfg
+    }
+
+    #[test]
+    fn rotating_paths_change_per_generation_and_honour_the_grace_window() {
+        let _lock = crate::test_support::lock_env();
+        let cfg = rotation_config();
+        let now = 100 * DAY + 10;
+        let current = rotating_path(100);
+        let previous = rotating_path(99);
+
+        assert_ne!(current, previous);
+        assert!(current.len() > 12 && current[1..].contains('/'));
+        assert_eq!(
+            live_rotating_paths(&cfg, now),
+            vec![current.clone(), previous.clone()]
+        );
+        assert_eq!(rotating_generation(&cfg, current.as_str(), now), Some(100));
+        assert_eq!(rotating_generation(&cfg, previous.as_str(), now), Some(99));
+        assert_eq!(
+            rotating_generation(&cfg, previous.as_str(), now + DAY),
+            None
+        );
+        assert_eq!(
+            rotating_generation(&cfg, rotating_path(98).as_str(), now),
+            None
+        );
+        assert!(is_trap_path(&cfg, "/instaban", now));
+        assert!(!is_trap_path(&cfg, "/archive/000000000000", now));
+    }
+
+    #[test]
+    fn rotation_is_inert_when_disabled() {
+        let _lock = crate::test_support::lock_env();
+        let mut cfg = rotation_config();
+        let now = 100 * DAY;
+        let current = rotating_path(100);
+
+        cfg.honeypot_rotation_enabled = false;
+        assert!(live_rotating_paths(&cfg, now).is_empty());
+        assert_eq!(rotating_generation(&cfg, current.as_str(), now), None);
+
+        cfg.honeypot_rotation_enabled = true;
+        cfg.honeypot_enabled = false;
+        assert_eq!(rotating_generation(&cfg, current.as_str(), now), None);
+    }
+
+    #[test]
+    fn injects_hidden_link_to_current_path_except_for_catalog_crawlers() {
+        let _lock = crate::test_support::lock_env();
+        let cfg = rotation_config();
+        let now = 100 * DAY;
+        let req = Request::builder()
+            .method(Method::Get)
+            .uri("/articles/1")
+            .body(Vec::<u8>::new())
+            .build();
+        let page = || {
+            Response::builder()
+                .status(200)
+                .header("Content-Type", "text/html")
+                .body("<html><body><p>Hello</p></body></html>")
+                .build()
+        };
+
+        let injected =
+            maybe_inject_rotating_link(&req, &cfg, "198.51.100.9", "curl/8.5.0", page(), now);
+        let body = String::from_utf8_lossy(injected.body()).to_string();
+        assert!(body.contains(format!("href=\"{}\"", rotating_path(100)).as_str()));
+        assert!(body.contains("rel=\"nofollow\""));
+        assert!(body.ends_with("</body></html>"));
+
+        let googlebot = maybe_inject_rotating_link(
+            &req,
+            &cfg,
+            "198.51.100.9",
+            "Mozilla/5.0 (compatible; Googlebot/2.1)",
+            page(),
+            now,
+        );
+        assert!(!String::from_utf8_lossy(googlebot.body()).contains("data-shuma-honeypot-link"));
+    }
+}
@@ -1,4 +1,5 @@
 pub(crate) mod ban;
 pub(crate) mod block_page;
+pub(crate) mod form_honeypot;
 pub(crate) mod honeypot;
 pub(crate) mod rate;
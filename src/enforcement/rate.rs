@@ -1,8 +1,12 @@
 use crate::challenge::KeyValueStore;
 use crate::signals::ip_identity;
-use std::time::{SystemTime, UNIX_EPOCH};
 
-pub fn check_rate_limit<S: KeyValueStore>(store: &S, site_id: &str, ip: &str, limit: u32) -> bool {
+pub fn check_rate_limit<S: KeyValueStore + ?Sized>(
+    store: &S,
+    site_id: &str,
+    ip: &str,
+    limit: u32,
+) -> bool {
     // Bucket the IP to limit distinct keys (reduces risk of KV cardinality explosion).
     let window_key = current_window_key(site_id, ip, now_ts() / 60);
     let count = store
@@ -79,8 +83,5 @@ mod tests {
 }
 
 fn now_ts() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
+    crate::runtime::clock::now_ts()
 }
@@ -0,0 +1,193 @@
+// src/i18n/mod.rs
+// Message catalogs and Accept-Language negotiation for visitor-facing pages
+// (block pages, puzzle and not-a-bot challenges, JS interstitial).
+
+use std::collections::{BTreeMap, HashMap};
+
+use once_cell::sync::Lazy;
+use spin_sdk::http::Request;
+
+pub(crate) const DEFAULT_LOCALE: &str = "en";
+pub(crate) const SUPPORTED_LOCALES: [&str; 8] = ["en", "ar", "de", "es", "fr", "ja", "pt", "zh"];
+const RTL_LOCALES: [&str; 1] = ["ar"];
+const MAX_ACCEPT_LANGUAGE_ENTRIES: usize = 16;
+pub(crate) const LOCALE_OVERRIDE_MAX_CHARS: usize = 512;
+
+const CATALOG_SOURCES: [(&str, &str); 8] = [
+    ("en", include_str!("../../config/locales/en.json")),
+    ("ar", include_str!("../../config/locales/ar.json")),
+    ("de", include_str!("../../config/locales/de.json")),
+    ("es", include_str!("../../config/locales/es.json")),
+    ("fr", include_str!("../../config/locales/fr.json")),
+    ("ja", include_str!("../../config/locales/ja.json")),
+    ("pt", include_str!("../../config/locales/pt.json")),
+    ("zh", include_str!("../../config/locales/zh.json")),
+];
+
+static CATALOGS: Lazy<HashMap<&'static str, HashMap<String, String>>> = Lazy::new(|| {
+    CATALOG_SOURCES
+        .iter()
+        .map(|(locale, text)| {
+            let entries = serde_json::from_str::<HashMap<String, String>>(text)
+                .unwrap_or_else(|err| panic!("Invalid {} message catalog: {}", locale, err));
+            (*locale, entries)
+        })
+        .collect()
+});
+
+fn catalog(locale: &str) -> &'static HashMap<String, String> {
+    CATALOGS
+        .get(locale)
+        .or_else(|| CATALOGS.get(DEFAULT_LOCALE))
+        .expect("default message catalog must be present")
+}
+
+pub(crate) fn is_supported_locale(locale: &str) -> bool {
+    SUPPORTED_LOCALES.contains(&locale)
+}
+
+/// Keys are defined by the English catalog; other locales and overrides may only fill them in.
+pub(crate) fn is_known_key(key: &str) -> bool {
+    catalog(DEFAULT_LOCALE).contains_key(key)
+}
+
+#[cfg(test)]
+pub(crate) fn catalog_keys(locale: &str) -> Vec<String> {
+    let mut keys: Vec<String> = catalog(locale).keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+fn supported_locale_for_tag(tag: &str) -> Option<&'static str> {
+    let primary = tag
+        .split(['-', '_'])
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    SUPPORTED_LOCALES
+        .iter()
+        .copied()
+        .find(|locale| *locale == primary)
+}
+
+/// Picks the best supported locale from an `Accept-Language` header by q-value, matching on
+/// the primary subtag. `*`, unknown tags and malformed input fall back to English.
+pub(crate) fn negotiate_locale(accept_language: Option<&str>) -> &'static str {
+    let Some(header) = accept_language else {
+        return DEFAULT_LOCALE;
+    };
+    let mut ranked: Vec<(u16, usize, &'static str)> = Vec::new();
+    for (position, entry) in header
+        .split(',')
+        .take(MAX_ACCEPT_LANGUAGE_ENTRIES)
+        .enumerate()
+    {
+        let mut parts = entry.split(';');
+        let tag = parts.next().unwrap_or("").trim();
+        let quality = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|value| value.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if !(quality > 0.0 && quality <= 1.0) {
+            continue;
+        }
+        if let Some(locale) = supported_locale_for_tag(tag) {
+            ranked.push(((quality * 1000.0) as u16, position, locale));
+        }
+    }
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    ranked
+        .first()
+        .map(|(_, _, locale)| *locale)
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+pub(crate) fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Strings for one rendered page: the negotiated locale plus any operator overrides for it.
+///
+/// Lookup order is override, locale catalog, English catalog. A key missing from all three
+/// renders as `[[key]]` so gaps are visible instead of silently blank.
+pub(crate) struct Messages<'a> {
+    locale: &'static str,
+    overrides: Option<&'a BTreeMap<String, String>>,
+}
+
+impl<'a> Messages<'a> {
+    pub(crate) fn new(
+        locale: &'static str,
+        overrides: &'a BTreeMap<String, BTreeMap<String, String>>,
+    ) -> Self {
+        Self {
+            locale,
+            overrides: overrides.get(locale),
+        }
+    }
+
+    pub(crate) fn for_request(req: &Request, cfg: &'a crate::config::Config) -> Self {
+        let accept_language = req
+            .header("accept-language")
+            .and_then(|value| value.as_str());
+        Self::new(negotiate_locale(accept_language), &cfg.locale_overrides)
+    }
+
+    pub(crate) fn locale(&self) -> &'static str {
+        self.locale
+    }
+
+    pub(crate) fn dir(&self) -> &'static str {
+        if RTL_LOCALES.contains(&self.locale) {
+            "rtl"
+        } else {
+            "ltr"
+        }
+    }
+
+    /// Unescaped message text.
+    pub(crate) fn raw(&self, key: &str) -> String {
+        self.overrides
+            .and_then(|overrides| overrides.get(key))
+            .or_else(|| catalog(self.locale).get(key))
+            .or_else(|| catalog(DEFAULT_LOCALE).get(key))
+            .cloned()
+            .unwrap_or_else(|| format!("[[{}]]", key))
+    }
+
+    /// Message text escaped for HTML element content and attribute values.
+    pub(crate) fn text(&self, key: &str) -> String {
+        escape_html(self.raw(key).as_str())
+    }
+
+    /// Escaped message with `{name}` placeholders replaced by escaped arguments.
+    pub(crate) fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.text(key), |acc, (name, value)| {
+            acc.replace(
+                format!("{{{}}}", name).as_str(),
+                escape_html(value).as_str(),
+            )
+        })
+    }
+
+    /// Message as a JavaScript string literal, safe inside an inline `<script>`.
+    pub(crate) fn js(&self, key: &str) -> String {
+        serde_json::to_string(self.raw(key).as_str())
+            .unwrap_or_else(|_| "\"\"".to_string())
+            .replace("</", "<\\/")
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,342 @@
+use super::*;
+use crate::challenge::{ChallengeSeed, ChallengeSubmitOutcome, ChallengeVariant, Transform};
+use crate::enforcement::block_page::{render_block_page, BlockReason};
+use spin_sdk::http::{Method, Request, Response};
+use std::collections::BTreeSet;
+
+fn placeholders(text: &str) -> BTreeSet<String> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+fn request_for(locale: &str, path: &str) -> Request {
+    Request::builder()
+        .method(Method::Get)
+        .uri(path)
+        .header("accept-language", locale)
+        .body(Vec::new())
+        .build()
+}
+
+fn form_post(locale: &str, path: &str, body: String) -> Request {
+    Request::builder()
+        .method(Method::Post)
+        .uri(path)
+        .header("accept-language", locale)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(body.into_bytes())
+        .build()
+}
+
+fn body_of(resp: Response) -> String {
+    String::from_utf8(resp.into_body()).unwrap()
+}
+
+fn test_seed(operation_id: &str, seed: u64, variant: ChallengeVariant) -> ChallengeSeed {
+    let now = crate::admin::now_ts();
+    ChallengeSeed {
+        seed_id: format!("seed-{}", operation_id),
+        operation_id: operation_id.to_string(),
+        flow_id: crate::challenge::operation_envelope::FLOW_CHALLENGE_PUZZLE.to_string(),
+        step_id: crate::challenge::operation_envelope::STEP_CHALLENGE_PUZZLE_SUBMIT.to_string(),
+        step_index: crate::challenge::operation_envelope::STEP_INDEX_CHALLENGE_PUZZLE_SUBMIT,
+        issued_at: now.saturating_sub(2),
+        expires_at: now + 300,
+        token_version: crate::challenge::operation_envelope::TOKEN_VERSION_V1,
+        ip_bucket: crate::signals::ip_identity::bucket_ip("unknown"),
+        ua_bucket: crate::challenge::operation_envelope::user_agent_bucket(""),
+        path_class: crate::challenge::operation_envelope::PATH_CLASS_CHALLENGE_PUZZLE_SUBMIT
+            .to_string(),
+        grid_size: 4,
+        active_cells: 7,
+        transforms: vec![Transform::RotateCw90, Transform::ShiftDown],
+        training_count: 1,
+        seed,
+        variant,
+    }
+}
+
+fn assert_localized_page(locale: &str, page: &str, body: &str) {
+    assert!(
+        !body.contains("[["),
+        "{} page for {} has an untranslated key: {}",
+        page,
+        locale,
+        body
+    );
+    let dir = if locale == "ar" { "rtl" } else { "ltr" };
+    assert!(
+        body.contains(format!("lang=\"{}\" dir=\"{}\"", locale, dir).as_str()),
+        "{} page for {} is missing lang/dir attributes",
+        page,
+        locale
+    );
+}
+
+/// Every page a visitor can land on, rendered for one locale.
+fn rendered_pages(locale: &str, cfg: &crate::config::Config) -> Vec<(&'static str, String)> {
+    let store = crate::test_support::InMemoryStore::default();
+    let messages = Messages::new(negotiate_locale(Some(locale)), &cfg.locale_overrides);
+    let mut pages = Vec::new();
+    for (name, reason) in [
+        ("block_honeypot", BlockReason::Honeypot),
+        ("block_rate_limit", BlockReason::RateLimit),
+        ("block_outdated_browser", BlockReason::OutdatedBrowser),
+        ("block_geo_policy", BlockReason::GeoPolicy),
+        ("block_ip_range_policy", BlockReason::IpRangePolicy),
+        ("block_client_fingerprint", BlockReason::ClientFingerprint),
+        ("block_ai_crawler", BlockReason::AiCrawler),
+    ] {
+        pages.push((name, render_block_page(reason, &messages)));
+    }
+    pages.push((
+        "puzzle",
+        body_of(crate::challenge::render_challenge(
+            &request_for(locale, "/challenge/puzzle"),
+            cfg,
+        )),
+    ));
+    pages.push((
+        "not_a_bot",
+        body_of(crate::challenge::render_not_a_bot(
+            &request_for(locale, "/challenge/not-a-bot-checkbox"),
+            cfg,
+        )),
+    ));
+    for pow_enabled in [true, false] {
+        pages.push((
+            if pow_enabled {
+                "js_pow"
+            } else {
+                "js_clearance"
+            },
+            body_of(crate::signals::js_verification::inject_js_challenge(
+                "198.51.100.7",
+                "Mozilla/5.0",
+                None,
+                pow_enabled,
+                crate::challenge::pow::PowParams::from_config(cfg),
+                cfg.pow_ttl_seconds,
+                cfg.cdp_probe_family,
+                cfg.cdp_probe_rollout_percent,
+                &messages,
+            )),
+        ));
+    }
+
+    let grid_seed = test_seed("ce01", 7, ChallengeVariant::Grid);
+    let encoded = percent_encoding::utf8_percent_encode(
+        crate::challenge::make_seed_token(&grid_seed).as_str(),
+        percent_encoding::NON_ALPHANUMERIC,
+    )
+    .to_string();
+    pages.push((
+        "accessible",
+        body_of(crate::challenge::serve_accessible_challenge_page(
+            &request_for(
+                locale,
+                format!("/challenge/puzzle/accessible?seed={}", encoded).as_str(),
+            ),
+            cfg,
+        )),
+    ));
+
+    let (forbidden, forbidden_outcome) = crate::challenge::handle_challenge_submit_with_outcome(
+        &store,
+        &form_post(locale, "/challenge/puzzle", String::new()),
+        cfg,
+    );
+    assert_eq!(forbidden_outcome, ChallengeSubmitOutcome::Forbidden);
+    pages.push(("result_forbidden", body_of(forbidden)));
+    let mut expired_seed = test_seed("ce02", 8, ChallengeVariant::Text);
+    expired_seed.expires_at = expired_seed.issued_at;
+    let (expired, expired_outcome) = crate::challenge::handle_accessible_submit_with_outcome(
+        &store,
+        &form_post(
+            locale,
+            "/challenge/puzzle/accessible",
+            format!(
+                "seed={}&answer=1",
+                crate::challenge::make_seed_token(&expired_seed)
+            ),
+        ),
+        cfg,
+    );
+    assert_eq!(expired_outcome, ChallengeSubmitOutcome::SequenceOpExpired);
+    pages.push(("result_expired", body_of(expired)));
+    let text_seed = test_seed("ce03", 9, ChallengeVariant::Text);
+    let (incorrect, incorrect_outcome) = crate::challenge::handle_accessible_submit_with_outcome(
+        &store,
+        &form_post(
+            locale,
+            "/challenge/puzzle/accessible",
+            format!(
+                "seed={}&answer=not-the-answer",
+                crate::challenge::make_seed_token(&text_seed)
+            ),
+        ),
+        cfg,
+    );
+    assert_eq!(incorrect_outcome, ChallengeSubmitOutcome::Incorrect);
+    pages.push(("result_incorrect", body_of(incorrect)));
+    let solved_seed = test_seed("ce04", 10, ChallengeVariant::Text);
+    let answer = crate::challenge::build_question(&solved_seed).answer;
+    let (solved, solved_outcome) = crate::challenge::handle_accessible_submit_with_outcome(
+        &store,
+        &form_post(
+            locale,
+            "/challenge/puzzle/accessible",
+            format!(
+                "seed={}&answer={}",
+                crate::challenge::make_seed_token(&solved_seed),
+                answer
+            ),
+        ),
+        cfg,
+    );
+    assert_eq!(solved_outcome, ChallengeSubmitOutcome::Solved);
+    pages.push(("result_solved", body_of(solved)));
+    pages
+}
+
+#[test]
+fn every_catalog_has_the_english_keys_with_matching_placeholders() {
+    let english = catalog_keys(DEFAULT_LOCALE);
+    assert!(!english.is_empty());
+    for locale in SUPPORTED_LOCALES {
+        assert_eq!(
+            catalog_keys(locale),
+            english,
+            "{} catalog key set differs from en",
+            locale
+        );
+        for key in &english {
+            let text = catalog(locale).get(key).unwrap();
+            assert!(!text.trim().is_empty(), "{}:{} is empty", locale, key);
+            assert_eq!(
+                placeholders(text),
+                placeholders(catalog(DEFAULT_LOCALE).get(key).unwrap()),
+                "{}:{} placeholders differ from en",
+                locale,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn negotiate_locale_prefers_highest_quality_supported_language() {
+    assert_eq!(negotiate_locale(None), "en");
+    assert_eq!(negotiate_locale(Some("")), "en");
+    assert_eq!(negotiate_locale(Some("de-DE,de;q=0.9,en;q=0.8")), "de");
+    assert_eq!(negotiate_locale(Some("nl-NL, fr;q=0.4, ja;q=0.6")), "ja");
+    assert_eq!(negotiate_locale(Some("pt-BR")), "pt");
+    assert_eq!(negotiate_locale(Some("zh_Hant_TW")), "zh");
+    assert_eq!(negotiate_locale(Some("AR")), "ar");
+    assert_eq!(negotiate_locale(Some("es;q=0.5, fr;q=0.5")), "es");
+    assert_eq!(negotiate_locale(Some("fr;q=0, de;q=0.1")), "de");
+    assert_eq!(negotiate_locale(Some("*")), "en");
+    assert_eq!(negotiate_locale(Some("ko, nl;q=0.9")), "en");
+    assert_eq!(negotiate_locale(Some("de;q=abc")), "de");
+    assert_eq!(negotiate_locale(Some("de;q=2")), "en");
+}
+
+#[test]
+fn messages_escape_html_and_javascript_contexts() {
+    let mut overrides = BTreeMap::new();
+    overrides.insert(
+        "en".to_string(),
+        BTreeMap::from([(
+            "js.verifying".to_string(),
+            "<b>\"Checking\"</script>".to_string(),
+        )]),
+    );
+    let messages = Messages::new("en", &overrides);
+    assert_eq!(
+        messages.text("js.verifying"),
+        "&lt;b&gt;&quot;Checking&quot;&lt;/script&gt;"
+    );
+    assert_eq!(
+        messages.js("js.verifying"),
+        "\"<b>\\\"Checking\\\"<\\/script>\""
+    );
+    assert_eq!(
+        messages.format("puzzle.pick_first_label", &[("transform", "<x>")]),
+        "First transform: &lt;x&gt;"
+    );
+    assert_eq!(messages.raw("missing.key"), "[[missing.key]]");
+}
+
+#[test]
+fn overrides_replace_only_their_locale_and_key() {
+    let mut cfg = crate::config::defaults().clone();
+    cfg.locale_overrides.insert(
+        "de".to_string(),
+        BTreeMap::from([(
+            "block.rate_limit.title".to_string(),
+            "Bitte langsamer".to_string(),
+        )]),
+    );
+    let german = Messages::for_request(&request_for("de", "/"), &cfg);
+    let page = render_block_page(BlockReason::RateLimit, &german);
+    assert!(page.contains("<h1>Bitte langsamer</h1>"));
+    assert!(page.contains(catalog("de").get("block.rate_limit.message").unwrap()));
+
+    let english = Messages::for_request(&request_for("en", "/"), &cfg);
+    let page = render_block_page(BlockReason::RateLimit, &english);
+    assert!(page.contains("<h1>Rate Limit Exceeded</h1>"));
+}
+
+#[test]
+fn every_page_renders_without_untranslated_keys_in_every_locale() {
+    let cfg = crate::config::defaults();
+    for locale in SUPPORTED_LOCALES {
+        let pages = rendered_pages(locale, cfg);
+        assert_eq!(pages.len(), 16);
+        for (page, body) in pages {
+            assert_localized_page(locale, page, body.as_str());
+        }
+    }
+}
+
+#[test]
+fn every_accessible_question_renders_in_every_locale() {
+    let cfg = crate::config::defaults();
+    for locale in SUPPORTED_LOCALES {
+        let messages = Messages::new(locale, &cfg.locale_overrides);
+        let mut kinds = BTreeSet::new();
+        for value in 0..256u64 {
+            let question =
+                crate::challenge::build_question(&test_seed("ce05", value, ChallengeVariant::Text));
+            let prompt = question.prompt(&messages);
+            assert!(
+                !prompt.contains('{'),
+                "{} prompt not filled: {}",
+                locale,
+                prompt
+            );
+            assert!(
+                !prompt.contains("[["),
+                "{} prompt untranslated: {}",
+                locale,
+                prompt
+            );
+            kinds.insert(question.prompt_key);
+        }
+        assert_eq!(kinds.len(), 11);
+    }
+}
+
+#[test]
+fn rtl_locale_keeps_puzzle_grids_left_to_right() {
+    let cfg = crate::config::defaults();
+    let body = body_of(crate::challenge::render_challenge(
+        &request_for("ar", "/challenge/puzzle"),
+        cfg,
+    ));
+    assert!(body.contains("<html lang=\"ar\" dir=\"rtl\">"));
+    assert!(body.contains("direction: ltr"));
+    assert!(body.contains(catalog("ar").get("puzzle.prompt").unwrap()));
+}
@@ -22,9 +22,11 @@ mod challenge; // Interactive math challenge for banned users
 mod config; // Config loading and defaults
 mod crawler_policy; // Crawler-facing policy surfaces (robots.txt)
 mod enforcement; // Enforcement actions (ban, block page, honeypot, rate limiting)
+mod i18n; // Visitor-facing message catalogs and Accept-Language negotiation
 mod maze; // maze crawler trap
 mod observability; // Metrics and monitoring surfaces
 mod providers; // Provider contracts for swappable implementations
+pub mod replay; // Offline traffic replay simulator for tuning
 mod request_validation; // Request validation/parsing helpers
 mod runtime; // request-time orchestration helpers
 mod signals; // Risk and identity signals (browser/CDP/GEO/IP/JS/whitelist)
@@ -158,6 +160,7 @@ pub(crate) fn should_bypass_expensive_bot_checks_for_static(req: &Request, path:
             | "/pow"
             | "/pow/verify"
             | "/challenge/puzzle"
+            | "/challenge/puzzle/accessible"
             | "/challenge/not-a-bot-checkbox"
     ) {
         return false;
@@ -385,6 +388,9 @@ pub struct BotnessSignalContext {
     pub rate_limit: u32,
     pub maze_behavior_score: u8,
     pub fingerprint_signals: Vec<BotnessContribution>,
+    pub behavior_signals: Vec<BotnessContribution>,
+    pub privacy_pass: crate::signals::privacy_pass::RedemptionOutcome,
+    pub robots_violation: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -395,8 +401,11 @@ pub struct GeoAssessment {
     pub scored_risk: bool,
 }
 
-pub(crate) fn assess_geo_request(req: &Request, cfg: &config::Config) -> GeoAssessment {
-    let headers_trusted = forwarded_ip_trusted(req);
+pub(crate) fn assess_geo_request(
+    req: &Request,
+    cfg: &config::Config,
+    headers_trusted: bool,
+) -> GeoAssessment {
     let country = geo::extract_geo_country(req, headers_trusted);
     let route = geo::evaluate_geo_policy(country.as_deref(), cfg);
     let scored_risk = if route == geo::GeoPolicyRoute::Allow {
@@ -419,7 +428,8 @@ pub(crate) fn collect_botness_contributions(
     context: BotnessSignalContext,
     cfg: &config::Config,
 ) -> Vec<BotnessContribution> {
-    let signal_capacity = 5 + context.fingerprint_signals.len();
+    let signal_capacity =
+        6 + context.fingerprint_signals.len() + context.behavior_signals.len();
     let mut accumulator = crate::signals::botness::SignalAccumulator::with_capacity_and_policy(
         signal_capacity,
         crate::signals::botness::SignalBudgetPolicy {
@@ -429,6 +439,7 @@ pub(crate) fn collect_botness_contributions(
             fingerprint_temporal_cap: cfg.fingerprint_family_cap_temporal,
             fingerprint_persistence_cap: cfg.fingerprint_family_cap_persistence,
             fingerprint_behavior_cap: cfg.fingerprint_family_cap_behavior,
+            behavior_cap: cfg.behavior_family_cap,
         },
     );
 
@@ -489,6 +500,22 @@ pub(crate) fn collect_botness_contributions(
         accumulator.push(fingerprint_signal);
     }
 
+    for behavior_signal in context.behavior_signals {
+        accumulator.push(behavior_signal);
+    }
+
+    accumulator.push(crate::signals::privacy_pass::bot_signal(
+        cfg.privacy_pass_enabled,
+        context.privacy_pass,
+        cfg.botness_weights.privacy_pass_attested,
+    ));
+
+    accumulator.push(crate::crawler_policy::compliance::bot_signal(
+        cfg.robots_enabled,
+        context.robots_violation,
+        cfg.botness_weights.robots_violation,
+    ));
+
     let (_score, contributions) = accumulator.finish();
     contributions
 }
@@ -521,7 +548,13 @@ pub(crate) fn botness_signals_summary(assessment: &BotnessAssessment) -> String
         .contributions
         .iter()
         .filter(|c| c.active)
-        .map(|c| format!("{}:{}", c.key, c.contribution))
+        .map(|c| {
+            if c.credit > 0 {
+                format!("{}:-{}", c.key, c.credit)
+            } else {
+                format!("{}:{}", c.key, c.contribution)
+            }
+        })
         .collect::<Vec<_>>();
     if active.is_empty() {
         "none".to_string()
@@ -604,9 +637,9 @@ fn log_line(msg: &str) {
     write_log_line(&mut out, msg);
 }
 
-pub(crate) fn serve_maze_with_tracking(
+pub(crate) fn serve_maze_with_tracking<S: crate::challenge::KeyValueStore + ?Sized>(
     req: &Request,
-    store: &Store,
+    store: &S,
     cfg: &config::Config,
     ip: &str,
     user_agent: &str,
@@ -700,7 +733,10 @@ pub(crate) fn serve_maze_with_tracking(
                     );
                     return Response::new(
                         403,
-                        block_page::render_block_page(block_page::BlockReason::Honeypot),
+                        block_page::render_block_page(
+                            block_page::BlockReason::Honeypot,
+                            &i18n::Messages::for_request(req, cfg),
+                        ),
                     );
                 }
                 crate::maze::runtime::MazeFallbackAction::Challenge => {
@@ -728,11 +764,18 @@ pub(crate) fn serve_maze_with_tracking(
                     return crate::signals::js_verification::inject_js_challenge(
                         ip,
                         user_agent,
+                        crate::challenge::clearance::request_clearance_level(req, ip, user_agent),
                         cfg.pow_enabled,
-                        cfg.pow_difficulty,
+                        crate::challenge::pow::pow_params_for_issue(
+                            store,
+                            cfg,
+                            ip,
+                            botness_hint.unwrap_or(0),
+                        ),
                         cfg.pow_ttl_seconds,
                         cfg.cdp_probe_family,
                         cfg.cdp_probe_rollout_percent,
+                        &crate::i18n::Messages::for_request(req, cfg),
                     );
                 }
             }
@@ -869,44 +912,121 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
         log_line(&format!("[ENV ERROR] {}", err));
         return Response::new(500, "Server configuration error");
     }
+    let otel_settings = observability::otel::OtelExportSettings::from_env();
+    let mut trace = match otel_settings.as_ref() {
+        Some(settings) => observability::otel::RequestTrace::start(
+            req.method().to_string().as_str(),
+            req.path(),
+            settings.traces_sample_ratio,
+        ),
+        None => observability::otel::RequestTrace::disabled(),
+    };
+    observability::decision_trace::begin();
+    let mut response = handle_bot_defence_pipeline(req, &mut trace);
+    observability::decision_trace::finish_request(
+        req.method().to_string().as_str(),
+        req.path(),
+        &mut response,
+    );
+    observability::otel::record_request(otel_settings.as_ref(), trace, *response.status());
+    response
+}
+
+fn handle_bot_defence_pipeline(
+    req: &Request,
+    trace: &mut observability::otel::RequestTrace,
+) -> Response {
+    let started = std::time::Instant::now();
     let path = req.path();
 
     if crate::config::https_enforced() && !request_is_https(req) {
         return Response::new(403, "HTTPS required");
     }
 
-    if let Some(response) = runtime::request_router::maybe_handle_early_route(req, path) {
+    if let Some(response) = trace.stage("early_route", || {
+        runtime::request_router::maybe_handle_early_route(req, path)
+    }) {
         return response;
     }
 
     if should_bypass_expensive_bot_checks_for_static(req, path) {
+        trace.set_attribute(
+            "shuma.static_bypass",
+            observability::otel::AttributeValue::Bool(true),
+        );
+        // Buffered in memory; the entrypoint writes it to KV once the response is sent.
+        signals::behavior::buffer_asset_fetch(extract_client_ip(req).as_str());
         return Response::new(200, "OK (passed bot defence)");
     }
 
-    let site_id = "default";
-    let ip = extract_client_ip(req);
-    let ua = req
-        .header("user-agent")
-        .map(|v| v.as_str().unwrap_or(""))
-        .unwrap_or("");
-
     let store = match runtime::kv_gate::open_store_or_fail_mode_response() {
         Ok(store) => store,
         Err(response) => return response,
     };
     let store = &store;
+    let _duration_timer = observability::metrics::RequestDurationTimer::new(store, started);
 
-    let cfg = match load_runtime_config(store, site_id, path) {
+    let cfg = match load_runtime_config(store, "default", path) {
         Ok(cfg) => cfg,
         Err(resp) => return resp,
     };
-    let provider_registry = providers::registry::ProviderRegistry::from_config(&cfg);
+    observability::alerts::maybe_evaluate_for_request(store, &cfg);
+    run_policy_pipeline(
+        req,
+        store,
+        &runtime::clock::SystemClock,
+        &signals::web_bot_auth::SpinKeyDirectoryTransport,
+        &cfg,
+        extract_client_ip(req).as_str(),
+        forwarded_ip_trusted(req),
+        trace,
+    )
+}
+
+/// Policy stages behind the KV gate, from the maze and report endpoints through to the
+/// final allow. Generic over store and clock so `replay` drives the same code as live traffic.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_policy_pipeline<S, C>(
+    req: &Request,
+    store: &S,
+    clock: &C,
+    key_directory: &dyn signals::web_bot_auth::KeyDirectoryTransport,
+    cfg: &config::Config,
+    ip: &str,
+    headers_trusted: bool,
+    trace: &mut observability::otel::RequestTrace,
+) -> Response
+where
+    S: crate::challenge::KeyValueStore,
+    C: runtime::clock::Clock + ?Sized,
+{
+    runtime::clock::with_clock(clock, || {
+        policy_stages(req, store, key_directory, cfg, ip, headers_trusted, trace)
+    })
+}
+
+fn policy_stages<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
+    key_directory: &dyn signals::web_bot_auth::KeyDirectoryTransport,
+    cfg: &config::Config,
+    ip: &str,
+    headers_trusted: bool,
+    trace: &mut observability::otel::RequestTrace,
+) -> Response {
+    let path = req.path();
+    let site_id = "default";
+    let ua = req
+        .header("user-agent")
+        .map(|v| v.as_str().unwrap_or(""))
+        .unwrap_or("");
+    let provider_registry = providers::registry::ProviderRegistry::from_config(cfg);
     observability::metrics::record_provider_backend_visibility(store, &provider_registry);
     observability::metrics::record_policy_signal(
         store,
         runtime::policy_taxonomy::SignalId::CtxPathClass,
     );
-    if forwarded_ip_trusted(req) {
+    if headers_trusted {
         observability::metrics::record_policy_signal(
             store,
             runtime::policy_taxonomy::SignalId::CtxIpTrusted,
@@ -918,7 +1038,7 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
             runtime::policy_taxonomy::SignalId::CtxUa,
         );
     }
-    let geo_assessment = assess_geo_request(req, &cfg);
+    let geo_assessment = assess_geo_request(req, cfg, headers_trusted);
 
     // CDP Report endpoint - receives automation detection reports from client-side JS
     if path
@@ -927,13 +1047,15 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
             .report_path()
         && *req.method() == spin_sdk::http::Method::Post
     {
+        observability::decision_trace::note_reason("cdp_report");
         return provider_registry
             .fingerprint_signal_provider()
             .handle_report(store, req);
     }
 
     if path == crate::maze::checkpoint_path() {
-        let response = crate::maze::runtime::handle_checkpoint(store, &cfg, req, &ip, ua);
+        observability::decision_trace::note_reason("maze_checkpoint");
+        let response = crate::maze::runtime::handle_checkpoint(store, cfg, req, ip, ua);
         let checkpoint_outcome = match *response.status() {
             204 => "accepted",
             405 => "method_not_allowed",
@@ -945,12 +1067,14 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
     }
 
     if path == crate::maze::issue_links_path() {
-        return crate::maze::runtime::handle_issue_links(store, &cfg, req, &ip, ua);
+        observability::decision_trace::note_reason("maze_issue_links");
+        return crate::maze::runtime::handle_issue_links(store, cfg, req, ip, ua);
     }
 
     // Maze - route suspicious crawlers into deception space (only if enabled)
     if provider_registry.maze_tarpit_provider().is_maze_path(path) {
         if !cfg.maze_enabled {
+            observability::decision_trace::note_reason("maze_disabled");
             return Response::new(404, "Not Found");
         }
         let policy_match = runtime::policy_taxonomy::resolve_policy_match(
@@ -963,8 +1087,8 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
             .serve_maze_with_tracking(
                 req,
                 store,
-                &cfg,
-                &ip,
+                cfg,
+                ip,
                 ua,
                 path,
                 "maze_trap",
@@ -987,94 +1111,161 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
             observability::metrics::MetricName::WhitelistedTotal,
             None,
         );
+        observability::decision_trace::note_reason("path_whitelisted");
         return Response::new(200, "OK (path whitelisted)");
     }
     // IP/CIDR whitelist
-    if whitelist::is_whitelisted(&ip, &cfg.whitelist) {
+    if whitelist::is_whitelisted(ip, &cfg.whitelist) {
         observability::metrics::increment(
             store,
             observability::metrics::MetricName::WhitelistedTotal,
             None,
         );
+        observability::decision_trace::note_reason("ip_whitelisted");
         return Response::new(200, "OK (whitelisted)");
     }
-    let ip_range_evaluation = crate::signals::ip_range_policy::evaluate(&cfg, &ip);
-    if let Some(response) = runtime::test_mode::maybe_handle_test_mode(
-        store,
-        &cfg,
-        site_id,
-        &ip,
-        ua,
-        path,
-        &ip_range_evaluation,
-        geo_assessment.route,
-        || js::needs_js_verification(req, store, site_id, &ip),
-        || {
-            observability::metrics::increment(
-                store,
-                observability::metrics::MetricName::TestModeActions,
-                None,
-            )
-        },
-    ) {
+    let ip_bucket = crate::signals::ip_identity::bucket_ip(ip);
+    observability::decision_trace::note_ip_bucket(ip_bucket.clone());
+    trace.set_attribute(
+        "shuma.ip_bucket",
+        observability::otel::AttributeValue::Str(ip_bucket),
+    );
+    let ip_range_evaluation = crate::signals::ip_range_policy::evaluate(cfg, ip);
+    if let Some(response) = trace.stage("test_mode", || {
+        runtime::test_mode::maybe_handle_test_mode(
+            store,
+            cfg,
+            site_id,
+            ip,
+            ua,
+            path,
+            &ip_range_evaluation,
+            geo_assessment.route,
+            || js::needs_js_verification(req, store, site_id, ip),
+            || {
+                observability::metrics::increment(
+                    store,
+                    observability::metrics::MetricName::TestModeActions,
+                    None,
+                )
+            },
+        )
+    }) {
         return response;
     }
-    if let Some(response) = runtime::policy_pipeline::maybe_handle_ip_range_policy(
-        req,
-        store,
-        &cfg,
-        &provider_registry,
-        site_id,
-        &ip,
-        path,
-        &ip_range_evaluation,
-    ) {
+    if let Some(response) = trace.stage("ip_range_policy", || {
+        runtime::policy_pipeline::maybe_handle_ip_range_policy(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            site_id,
+            ip,
+            path,
+            &ip_range_evaluation,
+        )
+    }) {
         return response;
     }
-    if let Some(response) = runtime::policy_pipeline::maybe_handle_honeypot(
-        store,
-        &cfg,
-        &provider_registry,
-        site_id,
-        &ip,
-        path,
-    ) {
+    if let Some(response) = trace.stage("honeypot", || {
+        runtime::policy_pipeline::maybe_handle_honeypot(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            site_id,
+            ip,
+            path,
+        )
+    }) {
         return response;
     }
-    if let Some(response) = runtime::policy_pipeline::maybe_handle_rate_limit(
-        store,
-        &cfg,
-        &provider_registry,
-        site_id,
-        &ip,
-        path,
-    ) {
+    if let Some(response) = trace.stage("form_honeypot", || {
+        runtime::policy_pipeline::maybe_handle_form_honeypot(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            site_id,
+            ip,
+            path,
+        )
+    }) {
         return response;
     }
-    if let Some(response) =
-        runtime::policy_pipeline::maybe_handle_existing_ban(store, &provider_registry, site_id, &ip)
-    {
+    if let Some(response) = trace.stage("rate_limit", || {
+        runtime::policy_pipeline::maybe_handle_rate_limit(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            site_id,
+            ip,
+            path,
+        )
+    }) {
+        return response;
+    }
+    if let Some(response) = trace.stage("existing_ban", || {
+        runtime::policy_pipeline::maybe_handle_existing_ban(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            site_id,
+            ip,
+        )
+    }) {
+        return response;
+    }
+    if let Some(response) = trace.stage("web_bot_auth", || {
+        runtime::policy_pipeline::maybe_handle_web_bot_auth(req, store, cfg, ip, key_directory)
+    }) {
+        return response;
+    }
+    if let Some(response) = trace.stage("ai_crawler_policy", || {
+        runtime::policy_pipeline::maybe_handle_ai_crawler_policy(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            ip,
+            req.header("user-agent")
+                .and_then(|v| v.as_str())
+                .unwrap_or(""),
+        )
+    }) {
         return response;
     }
     // PoW endpoints (public, before JS verification)
+    if path == "/pow" || path == "/pow/verify" {
+        observability::decision_trace::note_reason("pow_endpoint");
+    }
     if path == "/pow" {
         if *req.method() != spin_sdk::http::Method::Get {
             return Response::new(405, "Method Not Allowed");
         }
+        // Direct seed requests skip botness scoring, so price them as if the client had
+        // reached the puzzle tier rather than as clean traffic.
         return provider_registry
             .challenge_engine_provider()
             .handle_pow_challenge(
-                &ip,
+                ip,
                 ua,
                 cfg.pow_enabled,
-                cfg.pow_difficulty,
+                crate::challenge::pow::pow_params_for_issue(
+                    store,
+                    cfg,
+                    ip,
+                    cfg.challenge_puzzle_risk_threshold,
+                ),
                 cfg.pow_ttl_seconds,
             );
     }
     if path == "/pow/verify" {
         return provider_registry
             .challenge_engine_provider()
-            .handle_pow_verify(req, &ip, cfg.pow_enabled);
+            .handle_pow_verify(req, ip, cfg.pow_enabled);
     }
     // Outdated browser
     if browser::is_outdated_browser(ua, &cfg.browser_block) {
@@ -1087,7 +1278,7 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
             .ban_ip_with_fingerprint(
                 store,
                 site_id,
-                &ip,
+                ip,
                 "browser",
                 cfg.get_ban_duration("browser"),
                 Some(crate::enforcement::ban::BanFingerprint {
@@ -1112,7 +1303,7 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
             &crate::admin::EventLogEntry {
                 ts: crate::admin::now_ts(),
                 event: crate::admin::EventType::Ban,
-                ip: Some(ip.clone()),
+                ip: Some(ip.to_string()),
                 reason: Some("browser".to_string()),
                 outcome: Some(policy_match.annotate_outcome("banned")),
                 admin: None,
@@ -1120,38 +1311,78 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
         );
         return Response::new(
             403,
-            block_page::render_block_page(block_page::BlockReason::OutdatedBrowser),
+            block_page::render_block_page(
+                block_page::BlockReason::OutdatedBrowser,
+                &i18n::Messages::for_request(req, cfg),
+            ),
         );
     }
-    if let Some(response) = runtime::policy_pipeline::maybe_handle_geo_policy(
-        req,
-        store,
-        &cfg,
-        &provider_registry,
-        &ip,
-        &geo_assessment,
-    ) {
+    if let Some(response) = trace.stage("geo_policy", || {
+        runtime::policy_pipeline::maybe_handle_geo_policy(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            ip,
+            &geo_assessment,
+        )
+    }) {
         return response;
     }
 
-    let needs_js = runtime::policy_pipeline::compute_needs_js(req, store, &cfg, site_id, path, &ip);
+    if let Some(response) = trace.stage("tls_fingerprint", || {
+        runtime::policy_pipeline::maybe_handle_tls_fingerprint(
+            req,
+            store,
+            cfg,
+            ip,
+            geo_assessment.headers_trusted,
+        )
+    }) {
+        return response;
+    }
 
-    if let Some(response) = runtime::policy_pipeline::maybe_handle_botness(
-        req,
-        store,
-        &cfg,
-        &provider_registry,
-        site_id,
-        &ip,
-        needs_js,
-        &geo_assessment,
-    ) {
+    let needs_js = runtime::policy_pipeline::compute_needs_js(req, store, cfg, site_id, path, ip);
+
+    let mut botness_score = 0;
+    let mut privacy_pass_attested = false;
+    if let Some(response) = trace.stage("botness", || {
+        let botness = runtime::policy_pipeline::assess_botness(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            site_id,
+            ip,
+            needs_js,
+            &geo_assessment,
+        );
+        botness_score = botness.score;
+        privacy_pass_attested = crate::signals::privacy_pass::is_attested(&botness);
+        runtime::policy_pipeline::maybe_handle_botness(
+            req,
+            store,
+            cfg,
+            &provider_registry,
+            ip,
+            &botness,
+        )
+    }) {
         return response;
     }
 
-    if let Some(response) =
-        runtime::policy_pipeline::maybe_handle_js(store, &cfg, &ip, ua, needs_js)
-    {
+    if let Some(response) = trace.stage("js_verification", || {
+        runtime::policy_pipeline::maybe_handle_js(
+            req,
+            store,
+            cfg,
+            ip,
+            ua,
+            needs_js,
+            botness_score,
+            privacy_pass_attested,
+        )
+    }) {
         return response;
     }
 
@@ -1159,11 +1390,28 @@ pub fn handle_bot_defence_impl(req: &Request) -> Response {
         runtime::policy_taxonomy::PolicyTransition::AllowClean,
     );
     observability::metrics::record_policy_match(store, &policy_match);
-
-    Response::new(200, "OK (passed bot defence)")
+    observability::decision_trace::note_reason("allow_clean");
+
+    let now = crate::admin::now_ts();
+    let mut allowed = Response::new(200, "OK (passed bot defence)");
+    crawler_policy::policy_files::apply_tdm_headers(cfg, &mut allowed);
+    let response =
+        enforcement::form_honeypot::maybe_inject_form_honeypots(req, cfg, ip, allowed, now);
+    enforcement::honeypot::maybe_inject_rotating_link(req, cfg, ip, ua, response, now)
 }
 
+/// Sends the response before flushing buffered metrics, alert notifications and OTLP
+/// export, so neither KV writes nor webhook or collector latency delay the client.
 #[http_component]
-pub fn spin_entrypoint(req: Request) -> Response {
-    handle_bot_defence_impl(&req)
+pub async fn spin_entrypoint(req: Request, response_out: spin_sdk::http::ResponseOutparam) {
+    let mut response = handle_bot_defence_impl(&req);
+    let body = std::mem::take(response.body_mut());
+    if let Err(err) = response_out.set_with_body(response.into(), body).await {
+        log_line(&format!("[response] failed to send response: {}", err));
+    }
+    observability::metrics::flush_pending();
+    observability::alerts::flush_pending();
+    observability::otel::flush_pending(
+        observability::otel::OtelExportSettings::from_env().as_ref(),
+    );
 }
@@ -47,6 +47,9 @@ mod tests {
             rate_limit,
             maze_behavior_score,
             fingerprint_signals: Vec::new(),
+            behavior_signals: Vec::new(),
+            privacy_pass: crate::signals::privacy_pass::RedemptionOutcome::Absent,
+            robots_violation: false,
         }
     }
 
@@ -109,6 +112,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn botness_assessment_caps_behavior_family_contribution() {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.behavior_family_cap = 3;
+        let mut ctx = context(false, true, false, 0, 80);
+        ctx.behavior_signals = ["behavior_timing_regular", "behavior_sitemap_order"]
+            .into_iter()
+            .map(|key| {
+                crate::signals::botness::BotSignal::scored_with_metadata(
+                    key,
+                    "Behavior",
+                    true,
+                    2,
+                    crate::signals::botness::SignalProvenance::Derived,
+                    10,
+                    crate::signals::botness::SignalFamily::Behavior,
+                )
+            })
+            .collect();
+
+        let assessment = crate::compute_botness_assessment(ctx, &cfg);
+        assert_eq!(assessment.score, 3);
+        assert_eq!(contribution(&assessment, "behavior_timing_regular").contribution, 2);
+        assert_eq!(contribution(&assessment, "behavior_sitemap_order").contribution, 1);
+    }
+
     #[test]
     fn botness_assessment_includes_maze_behavior_signal() {
         let mut cfg = crate::config::defaults().clone();
@@ -127,6 +156,29 @@ mod tests {
         assert_eq!(maze_behavior.contribution, 3);
     }
 
+    #[test]
+    fn botness_assessment_credits_privacy_pass_attestation() {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.privacy_pass_enabled = true;
+        cfg.botness_weights.js_required = 3;
+        cfg.botness_weights.privacy_pass_attested = 4;
+
+        let mut attested = context(true, true, false, 0, 80);
+        attested.privacy_pass = crate::signals::privacy_pass::RedemptionOutcome::Attested;
+        let assessment = crate::compute_botness_assessment(attested, &cfg);
+        let signal = contribution(&assessment, "privacy_pass_attested");
+        assert!(signal.active);
+        assert_eq!(signal.credit, 4);
+        assert_eq!(assessment.score, 0);
+        assert!(crate::botness_signals_summary(&assessment).contains("privacy_pass_attested:-4"));
+
+        let mut replayed = context(true, true, false, 0, 80);
+        replayed.privacy_pass = crate::signals::privacy_pass::RedemptionOutcome::Replay;
+        let assessment = crate::compute_botness_assessment(replayed, &cfg);
+        assert!(!contribution(&assessment, "privacy_pass_attested").active);
+        assert_eq!(assessment.score, 3);
+    }
+
     #[test]
     fn botness_assessment_marks_disabled_and_unavailable_signals_explicitly() {
         let mut cfg = crate::config::defaults().clone();
@@ -259,7 +259,7 @@ fn geo_headers_are_ignored_when_forwarding_not_trusted() {
     let req = crate::test_support::request_with_headers("/health", &[("x-geo-country", "US")]);
 
     let cfg = crate::config::defaults().clone();
-    let assessment = crate::assess_geo_request(&req, &cfg);
+    let assessment = crate::assess_geo_request(&req, &cfg, crate::forwarded_ip_trusted(&req));
     assert!(!assessment.headers_trusted);
     assert_eq!(assessment.country, None);
     assert!(!assessment.scored_risk);
@@ -279,7 +279,7 @@ fn geo_headers_are_used_when_forwarding_is_trusted() {
 
     let mut cfg = crate::config::defaults().clone();
     cfg.geo_risk = vec!["US".to_string()];
-    let assessment = crate::assess_geo_request(&req, &cfg);
+    let assessment = crate::assess_geo_request(&req, &cfg, crate::forwarded_ip_trusted(&req));
     assert!(assessment.headers_trusted);
     assert_eq!(assessment.country.as_deref(), Some("US"));
     assert!(assessment.scored_risk);
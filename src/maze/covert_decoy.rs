@@ -1,43 +1,31 @@
 use spin_sdk::http::{Method, Request, Response};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::token;
 
 const DECOY_MARKER: &str = "data-shuma-covert-decoy=\"1\"";
-const SEARCH_ENGINE_UA_SUBSTRINGS: &[&str] = &[
-    "googlebot",
-    "bingbot",
-    "slurp",
-    "duckduckbot",
-    "baiduspider",
-    "yandexbot",
-    "facebot",
-    "twitterbot",
-    "linkedinbot",
-];
 
 fn now_secs() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0)
+    crate::runtime::clock::now_ts()
 }
 
-fn is_search_engine_user_agent(cfg: &crate::config::Config, user_agent: &str) -> bool {
+fn is_search_engine_user_agent(cfg: &crate::config::Config, ip: &str, user_agent: &str) -> bool {
     if !cfg.robots_allow_search_engines {
         return false;
     }
-    let normalized = user_agent.to_ascii_lowercase();
-    SEARCH_ENGINE_UA_SUBSTRINGS
-        .iter()
-        .any(|needle| normalized.contains(needle))
+    crate::crawler_policy::catalog::identify(
+        cfg,
+        ip,
+        user_agent,
+        &[crate::config::CrawlerCategory::SearchEngine],
+    )
+    .is_some()
 }
 
 fn medium_suspicion_score(cfg: &crate::config::Config, suspicion_score: u8) -> bool {
     suspicion_score >= cfg.challenge_puzzle_risk_threshold && suspicion_score < cfg.botness_maze_threshold
 }
 
-fn is_html_like_response(response: &Response) -> bool {
+pub(crate) fn is_html_like_response(response: &Response) -> bool {
     if let Some((_, value)) = response
         .headers()
         .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
@@ -109,7 +97,7 @@ fn inject_decoy_html(html: &str, href: &str) -> String {
     }
 }
 
-fn clone_response_with_body(original: &Response, body: String) -> Response {
+pub(crate) fn clone_response_with_body(original: &Response, body: String) -> Response {
     let mut builder = Response::builder();
     let builder = builder.status(*original.status());
     for (name, value) in original.headers() {
@@ -144,7 +132,7 @@ pub(crate) fn maybe_inject_non_maze_decoy(
     if matches!(path, "/health" | "/metrics" | "/robots.txt") {
         return response;
     }
-    if is_search_engine_user_agent(cfg, user_agent) {
+    if is_search_engine_user_agent(cfg, ip, user_agent) {
         return response;
     }
     if *response.status() != 200 || !is_html_like_response(&response) {
@@ -216,6 +204,29 @@ mod tests {
         assert!(!body.contains("data-shuma-covert-decoy=\"1\""));
     }
 
+    #[test]
+    fn unverified_search_engine_claim_still_gets_decoy() {
+        let mut cfg = crate::config::defaults().clone();
+        for entry in cfg.crawler_catalog.iter_mut() {
+            if entry.name == "Googlebot" {
+                entry.verification = crate::config::CrawlerVerification::IpRange;
+                entry.ip_range_set = "openai_gptbot".to_string();
+            }
+        }
+        let req = request("/challenge/puzzle");
+        let response = html_response("<html><body><h1>Challenge</h1></body></html>");
+        let injected = maybe_inject_non_maze_decoy(
+            &req,
+            &cfg,
+            "198.51.100.9",
+            "Mozilla/5.0 (compatible; Googlebot/2.1)",
+            response,
+            cfg.challenge_puzzle_risk_threshold,
+        );
+        let body = String::from_utf8_lossy(injected.body());
+        assert!(body.contains("data-shuma-covert-decoy=\"1\""));
+    }
+
     #[test]
     fn skips_decoy_for_high_suspicion_maze_tier() {
         let cfg = crate::config::defaults().clone();
@@ -1,6 +1,5 @@
 use serde::{Deserialize, Serialize};
 use spin_sdk::http::{Request, Response};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::content::{
     capitalize, generate_link_text, generate_paragraph, generate_title, DEPARTMENTS, NOUNS,
@@ -132,14 +131,14 @@ pub(crate) enum MazeServeDecision {
     Fallback(MazeFallbackDecision),
 }
 
-struct BudgetLease<'a, S: MazeStateStore> {
+struct BudgetLease<'a, S: MazeStateStore + ?Sized> {
     store: &'a S,
     global_key: String,
     bucket_key: String,
     active: bool,
 }
 
-impl<'a, S: MazeStateStore> BudgetLease<'a, S> {
+impl<'a, S: MazeStateStore + ?Sized> BudgetLease<'a, S> {
     fn release(&mut self) {
         if !self.active {
             return;
@@ -150,17 +149,14 @@ impl<'a, S: MazeStateStore> BudgetLease<'a, S> {
     }
 }
 
-impl<S: MazeStateStore> Drop for BudgetLease<'_, S> {
+impl<S: MazeStateStore + ?Sized> Drop for BudgetLease<'_, S> {
     fn drop(&mut self) {
         self.release();
     }
 }
 
 fn now_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_millis() as u64)
-        .unwrap_or(0)
+    crate::runtime::clock::now_ms()
 }
 
 fn read_counter(store: &(impl MazeStateStore + ?Sized), key: &str) -> u32 {
@@ -221,7 +217,7 @@ fn budget_bucket_key(ip_bucket: &str) -> String {
     format!("{}:{}", BUDGET_BUCKET_ACTIVE_PREFIX, ip_bucket)
 }
 
-fn try_acquire_budget<'a, S: MazeStateStore>(
+fn try_acquire_budget<'a, S: MazeStateStore + ?Sized>(
     store: &'a S,
     cfg: &crate::config::Config,
     ip_bucket: &str,
@@ -296,7 +292,7 @@ fn issue_seen(
 }
 
 fn mark_replay_seen(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     token: &MazeTraversalToken,
     replay_ttl: u64,
     now: u64,
@@ -385,7 +381,7 @@ fn fallback_action_for_reason(reason: MazeFallbackReason) -> MazeFallbackAction
 }
 
 fn high_confidence_violation_count(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     cfg: &crate::config::Config,
     ip_bucket: &str,
     reason: MazeFallbackReason,
@@ -439,7 +435,7 @@ fn current_violation_count(
 }
 
 fn load_checkpoint_state(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     flow_id: &str,
     ip_bucket: &str,
     now: u64,
@@ -458,7 +454,7 @@ fn checkpoint_is_required(cfg: &crate::config::Config, depth: u16) -> bool {
 }
 
 fn checkpoint_missing(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     cfg: &crate::config::Config,
     token: &MazeTraversalToken,
     ip_bucket: &str,
@@ -508,7 +504,7 @@ fn map_token_error(err: MazeTokenError) -> MazeFallbackReason {
 }
 
 fn parse_existing_token(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     cfg: &crate::config::Config,
     query: &str,
     path: &str,
@@ -889,7 +885,7 @@ fn make_breadcrumb(rng: &mut SeededRng) -> String {
 }
 
 pub(crate) fn serve(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     cfg: &crate::config::Config,
     req: &Request,
     ip: &str,
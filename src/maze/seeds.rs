@@ -101,7 +101,7 @@ fn parse_corpus(raw: &[u8]) -> Option<StoredSeedCorpus> {
     serde_json::from_slice::<StoredSeedCorpus>(raw).ok()
 }
 
-fn read_refresh_count(store: &impl MazeStateStore, hour_bucket: u64) -> u32 {
+fn read_refresh_count(store: &(impl MazeStateStore + ?Sized), hour_bucket: u64) -> u32 {
     let key = format!("{}:{}", OPERATOR_REFRESH_RATE_PREFIX, hour_bucket);
     store
         .get(key.as_str())
@@ -112,7 +112,7 @@ fn read_refresh_count(store: &impl MazeStateStore, hour_bucket: u64) -> u32 {
         .unwrap_or(0)
 }
 
-fn write_refresh_count(store: &impl MazeStateStore, hour_bucket: u64, value: u32) {
+fn write_refresh_count(store: &(impl MazeStateStore + ?Sized), hour_bucket: u64, value: u32) {
     let key = format!("{}:{}", OPERATOR_REFRESH_RATE_PREFIX, hour_bucket);
     if let Err(err) = store.set(key.as_str(), value.to_string().as_bytes()) {
         eprintln!(
@@ -217,7 +217,9 @@ fn extract_metadata_terms(source: &OperatorSeedSource) -> Vec<String> {
     tokens.into_iter().collect()
 }
 
-pub(crate) fn list_operator_sources(store: &impl MazeStateStore) -> Vec<OperatorSeedSource> {
+pub(crate) fn list_operator_sources(
+    store: &(impl MazeStateStore + ?Sized),
+) -> Vec<OperatorSeedSource> {
     let Some(raw) = store.get(OPERATOR_SOURCES_KEY).ok().flatten() else {
         return Vec::new();
     };
@@ -262,7 +264,7 @@ pub(crate) fn cached_seed_snapshot(store: &impl MazeStateStore) -> Option<SeedCo
 }
 
 fn refresh_operator_corpus_impl(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     cfg: &crate::config::Config,
     now_secs: u64,
     skip_rate_limit: bool,
@@ -346,7 +348,7 @@ pub(crate) fn manual_refresh_operator_corpus(
 }
 
 pub(crate) fn load_seed_corpus(
-    store: &impl MazeStateStore,
+    store: &(impl MazeStateStore + ?Sized),
     cfg: &crate::config::Config,
     now_secs: u64,
 ) -> MazeSeedCorpus {
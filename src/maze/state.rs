@@ -1,16 +1,14 @@
-use spin_sdk::key_value::Store;
-
 pub(crate) trait MazeStateStore {
     fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()>;
     fn set(&self, key: &str, value: &[u8]) -> Result<(), ()>;
 }
 
-impl MazeStateStore for Store {
+impl<T: crate::challenge::KeyValueStore + ?Sized> MazeStateStore for T {
     fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
-        Store::get(self, key).map_err(|_| ())
+        crate::challenge::KeyValueStore::get(self, key)
     }
 
     fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
-        Store::set(self, key, value).map_err(|_| ())
+        crate::challenge::KeyValueStore::set(self, key, value)
     }
 }
@@ -0,0 +1,683 @@
+// src/observability/alerts.rs
+// Operator-defined alert rules over monitoring counters.
+// Rules and alert state live in KV; evaluation is throttled on the request path and
+// state transitions (firing/resolved) are delivered to an optional webhook once the
+// response has been sent.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use spin_sdk::key_value::Store;
+use std::sync::Mutex;
+
+use crate::challenge::KeyValueStore;
+use crate::observability::outbound::{JsonPostTransport, SpinJsonPostTransport};
+
+const ALERT_RULES_KEY: &str = "alerts:v1:rules";
+const ALERT_STATE_KEY: &str = "alerts:v1:state";
+const ALERT_LAST_EVALUATED_KEY: &str = "alerts:v1:last_evaluated";
+const ALERT_RULES_MAX: usize = 32;
+const ALERT_RULE_ID_MAX_CHARS: usize = 64;
+const ALERT_WINDOW_MINUTES_MIN: u64 = 1;
+const ALERT_WINDOW_MINUTES_MAX: u64 = 60;
+const ALERT_WINDOW_MINUTES_DEFAULT: u64 = 60;
+
+/// Transitions waiting for webhook delivery after the response; one instance serves one
+/// request, so this holds at most one evaluation's worth.
+static PENDING_NOTIFICATIONS: Mutex<Vec<AlertTransition>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AlertMetric {
+    /// Bans issued within the rule window.
+    Bans,
+    /// Blocks served within the rule window.
+    Blocks,
+    /// Puzzle challenge failures (incorrect + expired/replay) within the rule window.
+    ChallengeFailures,
+    /// Puzzle challenge failures divided by puzzles served within the rule window.
+    ChallengeFailRatio,
+    /// External rate-limiter backend errors within the rule window.
+    RateLimiterBackendErrors,
+    /// `1` while the managed IP-range catalog is older than its staleness budget, else `0`.
+    ManagedIpCatalogStale,
+}
+
+impl AlertMetric {
+    fn is_windowed(self) -> bool {
+        !matches!(self, AlertMetric::ManagedIpCatalogStale)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AlertComparator {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl AlertComparator {
+    fn matches(self, value: f64, threshold: f64) -> bool {
+        match self {
+            AlertComparator::Gt => value > threshold,
+            AlertComparator::Gte => value >= threshold,
+            AlertComparator::Lt => value < threshold,
+            AlertComparator::Lte => value <= threshold,
+        }
+    }
+}
+
+fn default_window_minutes() -> u64 {
+    ALERT_WINDOW_MINUTES_DEFAULT
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AlertRule {
+    pub id: String,
+    pub metric: AlertMetric,
+    pub comparator: AlertComparator,
+    pub threshold: f64,
+    #[serde(default = "default_window_minutes")]
+    pub window_minutes: u64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// Cumulative counter readings captured at one evaluation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CounterSample {
+    pub ts: u64,
+    pub bans: u64,
+    pub blocks: u64,
+    pub challenge_failures: u64,
+    pub challenge_served: u64,
+    pub rate_limiter_backend_errors: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AlertInputs {
+    pub counters: CounterSample,
+    pub managed_ip_catalog_stale: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AlertStatus {
+    Ok,
+    Firing,
+}
+
+impl AlertStatus {
+    fn transition_label(self) -> &'static str {
+        match self {
+            AlertStatus::Ok => "resolved",
+            AlertStatus::Firing => "firing",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AlertRuleState {
+    pub rule_id: String,
+    pub status: AlertStatus,
+    pub value: Option<f64>,
+    pub since: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct NotificationRecord {
+    pub ts: u64,
+    pub transitions: usize,
+    pub result: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AlertState {
+    pub last_evaluated_at: u64,
+    pub samples: Vec<CounterSample>,
+    pub rules: Vec<AlertRuleState>,
+    pub last_notification: Option<NotificationRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AlertTransition {
+    pub rule: AlertRule,
+    pub status: AlertStatus,
+    pub value: Option<f64>,
+    pub ts: u64,
+}
+
+pub(crate) fn validate_rules(rules: &[AlertRule]) -> Result<(), String> {
+    if rules.len() > ALERT_RULES_MAX {
+        return Err(format!(
+            "at most {} alert rules are allowed",
+            ALERT_RULES_MAX
+        ));
+    }
+    let mut seen = std::collections::HashSet::new();
+    for rule in rules {
+        if rule.id.is_empty()
+            || rule.id.len() > ALERT_RULE_ID_MAX_CHARS
+            || !rule
+                .id
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'-')
+        {
+            return Err(format!(
+                "alert rule id must be 1-{} chars of [a-z0-9_-]",
+                ALERT_RULE_ID_MAX_CHARS
+            ));
+        }
+        if !seen.insert(rule.id.as_str()) {
+            return Err(format!("duplicate alert rule id {}", rule.id));
+        }
+        if !rule.threshold.is_finite() {
+            return Err(format!("alert rule {} threshold must be finite", rule.id));
+        }
+        if !(ALERT_WINDOW_MINUTES_MIN..=ALERT_WINDOW_MINUTES_MAX).contains(&rule.window_minutes) {
+            return Err(format!(
+                "alert rule {} window_minutes must be between {} and {}",
+                rule.id, ALERT_WINDOW_MINUTES_MIN, ALERT_WINDOW_MINUTES_MAX
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn load_rules<S: KeyValueStore>(store: &S) -> Vec<AlertRule> {
+    store
+        .get(ALERT_RULES_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<Vec<AlertRule>>(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn save_rules<S: KeyValueStore>(store: &S, rules: &[AlertRule]) -> Result<(), String> {
+    validate_rules(rules)?;
+    let payload = serde_json::to_vec(rules).map_err(|err| err.to_string())?;
+    store
+        .set(ALERT_RULES_KEY, &payload)
+        .map_err(|_| "Key-value store error".to_string())
+}
+
+pub(crate) fn load_state<S: KeyValueStore>(store: &S) -> AlertState {
+    store
+        .get(ALERT_STATE_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<AlertState>(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_state<S: KeyValueStore>(store: &S, state: &AlertState) {
+    if let Ok(payload) = serde_json::to_vec(state) {
+        if store.set(ALERT_STATE_KEY, &payload).is_err() {
+            eprintln!("[alerts] failed to persist alert state");
+        }
+    }
+}
+
+/// Newest sample taken at or before the window start; falls back to the oldest sample so a
+/// freshly configured rule can fire before a full window of history exists.
+fn window_baseline(samples: &[CounterSample], window_start: u64) -> Option<&CounterSample> {
+    samples
+        .iter()
+        .rev()
+        .find(|sample| sample.ts <= window_start)
+        .or_else(|| samples.first())
+}
+
+fn metric_value(
+    rule: &AlertRule,
+    inputs: &AlertInputs,
+    samples: &[CounterSample],
+    now: u64,
+) -> Option<f64> {
+    if !rule.metric.is_windowed() {
+        return Some(if inputs.managed_ip_catalog_stale {
+            1.0
+        } else {
+            0.0
+        });
+    }
+    let window_start = now.saturating_sub(rule.window_minutes.saturating_mul(60));
+    let baseline = window_baseline(samples, window_start)?;
+    let current = &inputs.counters;
+    let delta = |pick: fn(&CounterSample) -> u64| pick(current).saturating_sub(pick(baseline));
+    match rule.metric {
+        AlertMetric::Bans => Some(delta(|s| s.bans) as f64),
+        AlertMetric::Blocks => Some(delta(|s| s.blocks) as f64),
+        AlertMetric::ChallengeFailures => Some(delta(|s| s.challenge_failures) as f64),
+        AlertMetric::ChallengeFailRatio => {
+            let served = delta(|s| s.challenge_served);
+            if served == 0 {
+                return None;
+            }
+            Some(delta(|s| s.challenge_failures) as f64 / served as f64)
+        }
+        AlertMetric::RateLimiterBackendErrors => {
+            Some(delta(|s| s.rate_limiter_backend_errors) as f64)
+        }
+        AlertMetric::ManagedIpCatalogStale => None,
+    }
+}
+
+/// Evaluates all enabled rules against `inputs`, persists the updated state and returns the
+/// rules whose status changed. Rules without enough data keep their previous status.
+pub(crate) fn evaluate<S: KeyValueStore>(
+    store: &S,
+    rules: &[AlertRule],
+    inputs: &AlertInputs,
+    now: u64,
+) -> Vec<AlertTransition> {
+    let mut state = load_state(store);
+    let mut transitions = Vec::new();
+    let mut rule_states = Vec::with_capacity(rules.len());
+
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        let previous = state
+            .rules
+            .iter()
+            .find(|existing| existing.rule_id == rule.id)
+            .cloned()
+            .unwrap_or(AlertRuleState {
+                rule_id: rule.id.clone(),
+                status: AlertStatus::Ok,
+                value: None,
+                since: now,
+            });
+        let value = metric_value(rule, inputs, &state.samples, now);
+        let status = match value {
+            Some(value) if rule.comparator.matches(value, rule.threshold) => AlertStatus::Firing,
+            Some(_) => AlertStatus::Ok,
+            None => previous.status,
+        };
+        let since = if status == previous.status {
+            previous.since
+        } else {
+            transitions.push(AlertTransition {
+                rule: rule.clone(),
+                status,
+                value,
+                ts: now,
+            });
+            now
+        };
+        rule_states.push(AlertRuleState {
+            rule_id: rule.id.clone(),
+            status,
+            value,
+            since,
+        });
+    }
+
+    let retention_start = now.saturating_sub(ALERT_WINDOW_MINUTES_MAX.saturating_mul(60));
+    let keep_from = state
+        .samples
+        .iter()
+        .rposition(|sample| sample.ts <= retention_start)
+        .unwrap_or(0);
+    state.samples.drain(..keep_from);
+    let mut sample = inputs.counters.clone();
+    sample.ts = now;
+    state.samples.push(sample);
+    state.rules = rule_states;
+    state.last_evaluated_at = now;
+    save_state(store, &state);
+    transitions
+}
+
+fn notification_payload(transitions: &[AlertTransition]) -> serde_json::Value {
+    json!({
+        "source": "shuma-gorath",
+        "alerts": transitions
+            .iter()
+            .map(|transition| json!({
+                "rule_id": transition.rule.id,
+                "status": transition.status.transition_label(),
+                "metric": transition.rule.metric,
+                "comparator": transition.rule.comparator,
+                "threshold": transition.rule.threshold,
+                "window_minutes": transition.rule.window_minutes,
+                "value": transition.value,
+                "ts": transition.ts,
+            }))
+            .collect::<Vec<_>>()
+    })
+}
+
+pub(crate) fn notify(
+    transport: &impl JsonPostTransport,
+    webhook_url: &str,
+    transitions: &[AlertTransition],
+) -> Result<(), String> {
+    let body =
+        serde_json::to_vec(&notification_payload(transitions)).map_err(|err| err.to_string())?;
+    let status = transport.post_json(webhook_url, &[], body)?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("webhook responded with status {}", status))
+    }
+}
+
+fn evaluation_due<S: KeyValueStore>(store: &S, interval_seconds: u64, now: u64) -> bool {
+    let last = store
+        .get(ALERT_LAST_EVALUATED_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(0);
+    if now.saturating_sub(last) < interval_seconds {
+        return false;
+    }
+    store
+        .set(ALERT_LAST_EVALUATED_KEY, now.to_string().as_bytes())
+        .is_ok()
+}
+
+/// Throttled entry point: evaluates rules at most once per interval. Returns the
+/// transitions produced by this call; delivery is left to the caller.
+pub(crate) fn maybe_evaluate<S: KeyValueStore>(
+    store: &S,
+    interval_seconds: u64,
+    now: u64,
+    collect_inputs: impl FnOnce() -> AlertInputs,
+) -> Vec<AlertTransition> {
+    if !evaluation_due(store, interval_seconds, now) {
+        return Vec::new();
+    }
+    let rules = load_rules(store);
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    evaluate(store, &rules, &collect_inputs(), now)
+}
+
+/// Posts `transitions` to the webhook and records the outcome in alert state.
+fn deliver<S, T>(
+    store: &S,
+    transport: &T,
+    webhook_url: &str,
+    transitions: &[AlertTransition],
+    now: u64,
+) where
+    S: KeyValueStore,
+    T: JsonPostTransport,
+{
+    let result = notify(transport, webhook_url, transitions);
+    if let Err(err) = result.as_ref() {
+        eprintln!("[alerts] webhook notification failed: {}", err);
+    }
+    let mut state = load_state(store);
+    state.last_notification = Some(NotificationRecord {
+        ts: now,
+        transitions: transitions.len(),
+        result: match result {
+            Ok(()) => "delivered".to_string(),
+            Err(err) => err,
+        },
+    });
+    save_state(store, &state);
+}
+
+/// Delivers queued transitions in one webhook call; returns how many were sent.
+fn deliver_pending<S, T>(store: &S, transport: &T, webhook_url: &str, now: u64) -> usize
+where
+    S: KeyValueStore,
+    T: JsonPostTransport,
+{
+    let transitions = std::mem::take(&mut *PENDING_NOTIFICATIONS.lock().unwrap());
+    if transitions.is_empty() {
+        return 0;
+    }
+    deliver(store, transport, webhook_url, &transitions, now);
+    transitions.len()
+}
+
+fn collect_inputs(store: &Store, cfg: &crate::config::Config) -> AlertInputs {
+    use crate::observability::metrics::MetricName;
+    let [bans, blocks, incorrect, expired_replay, served, backend_errors] =
+        crate::observability::metrics::counter_totals(
+            store,
+            [
+                MetricName::BansTotal,
+                MetricName::BlocksTotal,
+                MetricName::ChallengeIncorrectTotal,
+                MetricName::ChallengeExpiredReplayTotal,
+                MetricName::ChallengeServedTotal,
+                MetricName::RateLimiterBackendErrors,
+            ],
+        );
+    AlertInputs {
+        counters: CounterSample {
+            ts: 0,
+            bans,
+            blocks,
+            challenge_failures: incorrect.saturating_add(expired_replay),
+            challenge_served: served,
+            rate_limiter_backend_errors: backend_errors,
+        },
+        managed_ip_catalog_stale: crate::signals::ip_range_policy::managed_catalog_stale(
+            cfg.ip_range_managed_max_staleness_hours,
+        ),
+    }
+}
+
+/// Evaluates rules when due and queues any transitions for `flush_pending`, so the
+/// webhook call never delays the response.
+pub(crate) fn maybe_evaluate_for_request(store: &Store, cfg: &crate::config::Config) {
+    let transitions = maybe_evaluate(
+        store,
+        crate::config::alert_evaluation_interval_seconds(),
+        crate::admin::now_ts(),
+        || collect_inputs(store, cfg),
+    );
+    if !transitions.is_empty() && crate::config::alert_webhook_url().is_some() {
+        PENDING_NOTIFICATIONS.lock().unwrap().extend(transitions);
+    }
+}
+
+/// Delivers queued transitions to the webhook. Called by the entrypoint after the
+/// response has been sent; failures are logged and recorded, never retried.
+pub(crate) fn flush_pending() {
+    if PENDING_NOTIFICATIONS.lock().unwrap().is_empty() {
+        return;
+    }
+    let Some(webhook_url) = crate::config::alert_webhook_url() else {
+        PENDING_NOTIFICATIONS.lock().unwrap().clear();
+        return;
+    };
+    if let Ok(store) = Store::open_default() {
+        deliver_pending(
+            &store,
+            &SpinJsonPostTransport,
+            webhook_url.as_str(),
+            crate::admin::now_ts(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryStore;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct StubWebhook {
+        status: u16,
+        bodies: RefCell<Vec<serde_json::Value>>,
+    }
+
+    impl JsonPostTransport for StubWebhook {
+        fn post_json(
+            &self,
+            _url: &str,
+            _headers: &[(String, String)],
+            body: Vec<u8>,
+        ) -> Result<u16, String> {
+            self.bodies
+                .borrow_mut()
+                .push(serde_json::from_slice(&body).unwrap());
+            Ok(self.status)
+        }
+    }
+
+    fn rule(
+        id: &str,
+        metric: AlertMetric,
+        comparator: AlertComparator,
+        threshold: f64,
+    ) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            metric,
+            comparator,
+            threshold,
+            window_minutes: 60,
+            enabled: true,
+        }
+    }
+
+    fn inputs(bans: u64, failures: u64, served: u64, stale: bool) -> AlertInputs {
+        AlertInputs {
+            counters: CounterSample {
+                ts: 0,
+                bans,
+                blocks: 0,
+                challenge_failures: failures,
+                challenge_served: served,
+                rate_limiter_backend_errors: 0,
+            },
+            managed_ip_catalog_stale: stale,
+        }
+    }
+
+    #[test]
+    fn validate_rules_rejects_bad_ids_duplicates_and_windows() {
+        let ok = rule(
+            "bans-per-hour",
+            AlertMetric::Bans,
+            AlertComparator::Gt,
+            10.0,
+        );
+        assert!(validate_rules(std::slice::from_ref(&ok)).is_ok());
+        assert!(
+            validate_rules(&[rule("Bad Id", AlertMetric::Bans, AlertComparator::Gt, 1.0)]).is_err()
+        );
+        assert!(validate_rules(&[ok.clone(), ok.clone()]).is_err());
+        let mut wide = ok.clone();
+        wide.window_minutes = 120;
+        assert!(validate_rules(&[wide]).is_err());
+        let mut nan = ok;
+        nan.threshold = f64::NAN;
+        assert!(validate_rules(&[nan]).is_err());
+    }
+
+    #[test]
+    fn counter_rules_fire_on_window_increase_and_resolve() {
+        let store = InMemoryStore::default();
+        let rules = vec![rule("bans", AlertMetric::Bans, AlertComparator::Gt, 5.0)];
+
+        // First evaluation only records a baseline sample.
+        assert!(evaluate(&store, &rules, &inputs(100, 0, 0, false), 1_000).is_empty());
+
+        let transitions = evaluate(&store, &rules, &inputs(110, 0, 0, false), 1_060);
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].status, AlertStatus::Firing);
+        assert_eq!(transitions[0].value, Some(10.0));
+
+        // Still firing: no new transition.
+        assert!(evaluate(&store, &rules, &inputs(111, 0, 0, false), 1_120).is_empty());
+
+        // An hour later the window baseline has moved past the burst.
+        let transitions = evaluate(&store, &rules, &inputs(112, 0, 0, false), 1_060 + 3_600);
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].status, AlertStatus::Ok);
+
+        let state = load_state(&store);
+        assert_eq!(state.rules[0].status, AlertStatus::Ok);
+        assert!(state.samples.len() <= 3);
+    }
+
+    #[test]
+    fn ratio_and_stale_catalog_rules_use_their_own_inputs() {
+        let store = InMemoryStore::default();
+        let rules = vec![
+            rule(
+                "fail-ratio",
+                AlertMetric::ChallengeFailRatio,
+                AlertComparator::Gte,
+                0.5,
+            ),
+            rule(
+                "catalog",
+                AlertMetric::ManagedIpCatalogStale,
+                AlertComparator::Gt,
+                0.0,
+            ),
+        ];
+        let first = evaluate(&store, &rules, &inputs(0, 0, 0, true), 1_000);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].rule.id, "catalog");
+
+        let second = evaluate(&store, &rules, &inputs(0, 6, 10, true), 1_060);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].rule.id, "fail-ratio");
+        assert_eq!(second[0].value, Some(0.6));
+    }
+
+    #[test]
+    fn maybe_evaluate_is_throttled_and_queued_transitions_are_delivered() {
+        let store = InMemoryStore::default();
+        save_rules(
+            &store,
+            &[rule(
+                "catalog",
+                AlertMetric::ManagedIpCatalogStale,
+                AlertComparator::Gt,
+                0.0,
+            )],
+        )
+        .unwrap();
+        let webhook = StubWebhook {
+            status: 204,
+            ..StubWebhook::default()
+        };
+        let url = "https://hooks.example.com/alerts";
+
+        let fired = maybe_evaluate(&store, 60, 1_000, || inputs(0, 0, 0, true));
+        assert_eq!(fired.len(), 1);
+        let throttled = maybe_evaluate(&store, 60, 1_030, || inputs(0, 0, 0, false));
+        assert!(throttled.is_empty());
+        let resolved = maybe_evaluate(&store, 60, 1_060, || inputs(0, 0, 0, false));
+        assert_eq!(resolved.len(), 1);
+        assert!(webhook.bodies.borrow().is_empty());
+        assert!(load_state(&store).last_notification.is_none());
+
+        PENDING_NOTIFICATIONS
+            .lock()
+            .unwrap()
+            .extend(fired.into_iter().chain(resolved));
+        assert_eq!(deliver_pending(&store, &webhook, url, 1_061), 2);
+        assert_eq!(deliver_pending(&store, &webhook, url, 1_062), 0);
+
+        let bodies = webhook.bodies.borrow();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0]["alerts"][0]["status"], "firing");
+        assert_eq!(bodies[0]["alerts"][1]["status"], "resolved");
+        assert_eq!(bodies[0]["alerts"][1]["metric"], "managed_ip_catalog_stale");
+        let state = load_state(&store);
+        let record = state.last_notification.unwrap();
+        assert_eq!(record.result, "delivered");
+        assert_eq!(record.transitions, 2);
+    }
+}
@@ -0,0 +1,374 @@
+// src/observability/decision_trace.rs
+// Compact per-request decision traces keyed by an incident reference ID.
+// Non-allow responses carry the ID so operators can look the decision up via /admin/decisions/{id}.
+
+use rand::random;
+use serde::{Deserialize, Serialize};
+use spin_sdk::http::Response;
+use std::cell::RefCell;
+
+const DECISION_TRACE_PREFIX: &str = "decision:v1:";
+const DECISION_SWEEP_MARKER_KEY: &str = "decision_sweep:v1:last";
+const DECISION_TRACE_TTL_SECONDS: u64 = 15 * 60;
+const MAX_RECORDED_TRANSITIONS: usize = 8;
+const INCIDENT_ID_RANDOM_HEX_LEN: usize = 8;
+const INCIDENT_ID_TS_HEX_MAX_LEN: usize = 16;
+pub(crate) const INCIDENT_ID_HEADER: &str = "x-shuma-incident-id";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TransitionRecord {
+    pub level: String,
+    pub action: String,
+    pub detection: String,
+    pub signals: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ContributionRecord {
+    pub key: String,
+    pub active: bool,
+    pub contribution: u8,
+    pub availability: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BotnessThresholds {
+    pub not_a_bot: u8,
+    pub challenge: u8,
+    pub maze: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct BotnessRecord {
+    pub score: u8,
+    pub contributions: Vec<ContributionRecord>,
+    pub thresholds: BotnessThresholds,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DecisionDraft {
+    ip_bucket: Option<String>,
+    transitions: Vec<TransitionRecord>,
+    allow_class: Vec<bool>,
+    botness: Option<BotnessRecord>,
+    reason: Option<String>,
+}
+
+impl DecisionDraft {
+    fn is_non_allow(&self) -> bool {
+        self.allow_class.iter().any(|allow| !allow)
+    }
+
+    /// Event reason for the outcome that followed the last recorded transition.
+    pub(crate) fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub(crate) fn last_transition(&self) -> Option<&TransitionRecord> {
+        self.transitions.last()
+    }
+
+    pub(crate) fn botness(&self) -> Option<&BotnessRecord> {
+        self.botness.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct DecisionTrace {
+    pub incident_id: String,
+    pub ts: u64,
+    pub expires_at: u64,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub ip_bucket: Option<String>,
+    pub transitions: Vec<TransitionRecord>,
+    pub botness: Option<BotnessRecord>,
+}
+
+thread_local! {
+    static DRAFT: RefCell<Option<DecisionDraft>> = const { RefCell::new(None) };
+}
+
+fn with_draft(update: impl FnOnce(&mut DecisionDraft)) {
+    DRAFT.with(|draft| {
+        if let Some(draft) = draft.borrow_mut().as_mut() {
+            update(draft);
+        }
+    });
+}
+
+/// Starts collecting decision context for the current request.
+pub(crate) fn begin() {
+    DRAFT.with(|draft| *draft.borrow_mut() = Some(DecisionDraft::default()));
+}
+
+pub(crate) fn take_draft() -> Option<DecisionDraft> {
+    DRAFT.with(|draft| draft.borrow_mut().take())
+}
+
+pub(crate) fn note_ip_bucket(bucket: String) {
+    with_draft(|draft| draft.ip_bucket = Some(bucket));
+}
+
+pub(crate) fn note_policy_match(policy_match: &crate::runtime::policy_taxonomy::PolicyMatch) {
+    with_draft(|draft| {
+        // A new transition starts a new outcome; its reason arrives with the event it logs.
+        draft.reason = None;
+        if draft.transitions.len() >= MAX_RECORDED_TRANSITIONS {
+            return;
+        }
+        draft.transitions.push(TransitionRecord {
+            level: policy_match.level_id().to_string(),
+            action: policy_match.action_id().to_string(),
+            detection: policy_match.detection_id().to_string(),
+            signals: policy_match
+                .signal_ids()
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        });
+        draft.allow_class.push(policy_match.is_allow_class());
+    });
+}
+
+/// Records the reason of the outcome being served; logged events note theirs automatically.
+pub(crate) fn note_reason(reason: &str) {
+    with_draft(|draft| draft.reason = Some(reason.to_string()));
+}
+
+pub(crate) fn note_botness(cfg: &crate::config::Config, assessment: &crate::BotnessAssessment) {
+    with_draft(|draft| {
+        draft.botness = Some(BotnessRecord {
+            score: assessment.score,
+            contributions: assessment
+                .contributions
+                .iter()
+                .map(|signal| ContributionRecord {
+                    key: signal.key.to_string(),
+                    active: signal.active,
+                    contribution: signal.contribution,
+                    availability: signal.availability.as_str().to_string(),
+                })
+                .collect(),
+            thresholds: BotnessThresholds {
+                not_a_bot: cfg.not_a_bot_risk_threshold,
+                challenge: cfg.challenge_puzzle_risk_threshold,
+                maze: cfg.botness_maze_threshold,
+            },
+        });
+    });
+}
+
+fn new_incident_id(now: u64) -> String {
+    format!("{:x}-{:08x}", now, random::<u32>())
+}
+
+fn incident_id_ts(id: &str) -> Option<u64> {
+    let (ts, suffix) = id.split_once('-')?;
+    if ts.is_empty()
+        || ts.len() > INCIDENT_ID_TS_HEX_MAX_LEN
+        || suffix.len() != INCIDENT_ID_RANDOM_HEX_LEN
+        || !ts
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase())
+        || !suffix
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase())
+    {
+        return None;
+    }
+    u64::from_str_radix(ts, 16).ok()
+}
+
+pub(crate) fn is_valid_incident_id(id: &str) -> bool {
+    incident_id_ts(id).is_some()
+}
+
+fn trace_key(id: &str) -> String {
+    format!("{}{}", DECISION_TRACE_PREFIX, id)
+}
+
+/// Persists the draft when any recorded transition was non-allow and returns its incident ID.
+pub(crate) fn persist<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    draft: DecisionDraft,
+    method: &str,
+    path: &str,
+    status: u16,
+    now: u64,
+) -> Option<String> {
+    if !draft.is_non_allow() {
+        return None;
+    }
+    let incident_id = new_incident_id(now);
+    let trace = DecisionTrace {
+        incident_id: incident_id.clone(),
+        ts: now,
+        expires_at: now.saturating_add(DECISION_TRACE_TTL_SECONDS),
+        method: method.to_string(),
+        path: path.to_string(),
+        status,
+        ip_bucket: draft.ip_bucket,
+        transitions: draft.transitions,
+        botness: draft.botness,
+    };
+    let payload = serde_json::to_vec(&trace).ok()?;
+    if store
+        .set(trace_key(&incident_id).as_str(), &payload)
+        .is_err()
+    {
+        crate::log_line(&format!(
+            "[decision_trace] failed to persist trace {}",
+            incident_id
+        ));
+        return None;
+    }
+    maybe_sweep_expired(store, now);
+    Some(incident_id)
+}
+
+/// Loads a trace by incident ID, deleting it when it has outlived its TTL.
+pub(crate) fn load<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    id: &str,
+    now: u64,
+) -> Option<DecisionTrace> {
+    if !is_valid_incident_id(id) {
+        return None;
+    }
+    let key = trace_key(id);
+    let raw = store.get(key.as_str()).ok().flatten()?;
+    let trace = serde_json::from_slice::<DecisionTrace>(&raw).ok()?;
+    if trace.expires_at <= now {
+        let _ = store.delete(key.as_str());
+        return None;
+    }
+    Some(trace)
+}
+
+fn maybe_sweep_expired<S: crate::challenge::KeyValueStore>(store: &S, now: u64) {
+    let last_sweep = store
+        .get(DECISION_SWEEP_MARKER_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(0);
+    if now.saturating_sub(last_sweep) < DECISION_TRACE_TTL_SECONDS {
+        return;
+    }
+    let _ = store.set(DECISION_SWEEP_MARKER_KEY, now.to_string().as_bytes());
+    if let Ok(keys) = store.get_keys() {
+        for key in keys {
+            let Some(id) = key.strip_prefix(DECISION_TRACE_PREFIX) else {
+                continue;
+            };
+            let expired = incident_id_ts(id)
+                .map(|ts| ts.saturating_add(DECISION_TRACE_TTL_SECONDS) <= now)
+                .unwrap_or(true);
+            if expired {
+                let _ = store.delete(key.as_str());
+            }
+        }
+    }
+}
+
+/// Persists the current request's decision trace and tags non-allow responses with its incident ID.
+pub(crate) fn finish_request(method: &str, path: &str, response: &mut Response) {
+    let Some(draft) = take_draft() else {
+        return;
+    };
+    if !draft.is_non_allow() {
+        return;
+    }
+    let Ok(store) = spin_sdk::key_value::Store::open_default() else {
+        return;
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    if let Some(incident_id) = persist(&store, draft, method, path, *response.status(), now) {
+        response.set_header(INCIDENT_ID_HEADER, incident_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::KeyValueStore;
+    use crate::runtime::policy_taxonomy::{resolve_policy_match, PolicyTransition};
+    use crate::test_support::InMemoryStore;
+
+    fn blocking_draft() -> DecisionDraft {
+        begin();
+        note_ip_bucket("198.51.100.0".to_string());
+        note_policy_match(&resolve_policy_match(PolicyTransition::RateLimitHit));
+        take_draft().expect("draft should exist after begin")
+    }
+
+    #[test]
+    fn allow_only_drafts_are_not_persisted() {
+        let store = InMemoryStore::default();
+        let draft = DecisionDraft::default();
+        assert_eq!(persist(&store, draft, "GET", "/", 200, 1_000), None);
+    }
+
+    #[test]
+    fn notes_are_ignored_without_an_active_draft() {
+        let _ = take_draft();
+        note_policy_match(&resolve_policy_match(PolicyTransition::RateLimitHit));
+        assert!(take_draft().is_none());
+    }
+
+    #[test]
+    fn non_allow_trace_round_trips_until_expiry() {
+        let store = InMemoryStore::default();
+        let now = 1_700_000_000;
+        let id = persist(&store, blocking_draft(), "GET", "/login", 429, now)
+            .expect("non-allow draft should be persisted");
+        assert!(is_valid_incident_id(&id));
+
+        let trace = load(&store, &id, now + 1).expect("trace should load");
+        assert_eq!(trace.status, 429);
+        assert_eq!(trace.path, "/login");
+        assert_eq!(trace.ip_bucket.as_deref(), Some("198.51.100.0"));
+        assert_eq!(trace.transitions.len(), 1);
+        assert!(trace.transitions[0]
+            .signals
+            .contains(&"S_RATE_LIMIT_HIT".to_string()));
+
+        assert!(load(&store, &id, now + DECISION_TRACE_TTL_SECONDS).is_none());
+        assert!(store.get(trace_key(&id).as_str()).unwrap().is_none());
+    }
+
+    #[test]
+    fn reason_tracks_the_outcome_after_the_last_transition() {
+        begin();
+        note_reason("robots_violation");
+        note_policy_match(&resolve_policy_match(PolicyTransition::AllowClean));
+        let draft = take_draft().expect("draft should exist after begin");
+        assert_eq!(draft.reason(), None);
+        assert_eq!(
+            draft
+                .last_transition()
+                .map(|transition| transition.action.as_str()),
+            Some("A_ALLOW")
+        );
+
+        begin();
+        note_policy_match(&resolve_policy_match(PolicyTransition::HoneypotHit));
+        note_reason("honeypot");
+        assert_eq!(take_draft().unwrap().reason(), Some("honeypot"));
+    }
+
+    #[test]
+    fn incident_id_validation_rejects_malformed_ids() {
+        assert!(is_valid_incident_id("6553f100-0a1b2c3d"));
+        assert!(!is_valid_incident_id("6553f100"));
+        assert!(!is_valid_incident_id("6553f100-0A1B2C3D"));
+        assert!(!is_valid_incident_id("../config-0a1b2c3d"));
+        assert!(!is_valid_incident_id("6553f100-0a1b2c"));
+    }
+}
@@ -3,17 +3,78 @@
 // Stores counters in KV store and exports in Prometheus text format
 
 use once_cell::sync::Lazy;
-use spin_sdk::key_value::Store;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Mutex;
 
 const METRICS_PREFIX: &str = "metrics:";
-const BOTNESS_SIGNAL_KEYS: [&str; 5] = [
+const HISTOGRAM_PREFIX: &str = "metrics:histogram:";
+const BOTNESS_SCORE_BUCKETS: [f64; 11] = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+const SOLVE_SECONDS_BUCKETS: [f64; 10] = [1.0, 2.0, 3.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0];
+const REQUEST_DURATION_SECONDS_BUCKETS: [f64; 11] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+const CHALLENGE_SOLVE_KINDS: [&str; 3] = ["puzzle", "puzzle_accessible", "not_a_bot"];
+// Every difficulty a PoW seed can carry: scrypt 1-10 and SHA-256 12-20.
+const POW_DIFFICULTY_LABELS: [&str; 19] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "12", "13", "14", "15", "16", "17", "18",
+    "19", "20",
+];
+const CHALLENGE_ACCESSIBLE_OUTCOMES: [&str; 7] = [
+    "solved",
+    "incorrect",
+    "expired_replay",
+    "sequence_violation",
+    "forbidden",
+    "invalid_output",
+    "rate_limited",
+];
+const PRIVACY_PASS_OUTCOMES: [&str; 3] = ["attested", "invalid", "replay"];
+const ROBOTS_TXT_COMPLIANCE_OUTCOMES: [&str; 2] = ["tracked", "violation"];
+const CRAWL_PAYMENT_OUTCOMES: [&str; 10] = [
+    "offered",
+    "paid",
+    "malformed",
+    "unknown_issuer",
+    "invalid_signature",
+    "wrong_crawler",
+    "expired",
+    "currency_mismatch",
+    "exhausted",
+    "busy",
+];
+const WEB_BOT_AUTH_OUTCOMES: [&str; 8] = [
+    "verified",
+    "malformed",
+    "expired",
+    "unknown_agent",
+    "unknown_key",
+    "directory_unavailable",
+    "invalid_signature",
+    "replay",
+];
+const CDP_REPORT_OUTCOMES: [&str; 6] = [
+    "accepted",
+    "missing_nonce",
+    "invalid_nonce",
+    "expired",
+    "binding_mismatch",
+    "replay",
+];
+const FORM_HONEYPOT_TRIPS: [&str; 2] = ["decoy_filled", "too_fast"];
+const BOTNESS_SIGNAL_KEYS: [&str; 12] = [
     "js_verification_required",
     "geo_risk",
     "rate_pressure_medium",
     "rate_pressure_high",
     "maze_behavior",
+    "behavior_timing_regular",
+    "behavior_asset_ratio",
+    "behavior_referrer_chain",
+    "behavior_breadth_first",
+    "behavior_sitemap_order",
+    "privacy_pass_attested",
+    "robots_violation",
 ];
 const SIGNAL_AVAILABILITY_STATES: [&str; 3] = ["active", "disabled", "unavailable"];
 const DEFENCE_MODE_MODULES: [&str; 3] = ["rate", "geo", "js"];
@@ -62,6 +123,8 @@ const MONITORING_POW_FAILURE_REASON_KEYS: [&str; 5] = [
 ];
 const MONITORING_RATE_OUTCOME_KEYS: [&str; 4] = ["limited", "banned", "fallback_allow", "fallback_deny"];
 const MONITORING_GEO_ACTION_KEYS: [&str; 3] = ["block", "challenge", "maze"];
+const MONITORING_TLS_FINGERPRINT_ACTION_KEYS: [&str; 3] = ["allow", "score", "block"];
+const MONITORING_AI_CRAWLER_ACTION_KEYS: [&str; 4] = ["allow", "block", "maze", "payment_required"];
 const PROVIDER_OBSERVED_COMBINATIONS: [(
     crate::providers::registry::ProviderCapability,
     crate::config::ProviderBackend,
@@ -135,6 +198,15 @@ pub enum MetricName {
     ChallengeSolvedTotal,
     ChallengeIncorrectTotal,
     ChallengeExpiredReplayTotal,
+    ChallengeAccessibleServedTotal,
+    ChallengeAccessibleOutcomes,
+    PrivacyPassRedemptions,
+    AiCrawlerHits,
+    RobotsTxtCompliance,
+    WebBotAuthVerifications,
+    CrawlPaymentOutcomes,
+    CrawlPaymentPaid,
+    CrawlPaymentChargedMicros,
     WhitelistedTotal,
     TestModeActions,
     MazeHits,
@@ -144,6 +216,9 @@ pub enum MetricName {
     MazeProofOutcomes,
     MazeEntropyVariants,
     CdpDetections,
+    CdpReportOutcomes,
+    FormHoneypotHits,
+    RotatingHoneypotHits,
     BotnessSignalState,
     DefenceModeEffective,
     EdgeIntegrationMode,
@@ -172,6 +247,15 @@ impl MetricName {
             MetricName::ChallengeSolvedTotal => "challenge_solved_total",
             MetricName::ChallengeIncorrectTotal => "challenge_incorrect_total",
             MetricName::ChallengeExpiredReplayTotal => "challenge_expired_replay_total",
+            MetricName::ChallengeAccessibleServedTotal => "challenge_accessible_served_total",
+            MetricName::ChallengeAccessibleOutcomes => "challenge_accessible_outcomes_total",
+            MetricName::PrivacyPassRedemptions => "privacy_pass_redemptions_total",
+            MetricName::AiCrawlerHits => "ai_crawler_hits_total",
+            MetricName::RobotsTxtCompliance => "robots_txt_compliance_total",
+            MetricName::WebBotAuthVerifications => "web_bot_auth_verifications_total",
+            MetricName::CrawlPaymentOutcomes => "crawl_payment_outcomes_total",
+            MetricName::CrawlPaymentPaid => "crawl_payment_paid_total",
+            MetricName::CrawlPaymentChargedMicros => "crawl_payment_charged_micros_total",
             MetricName::WhitelistedTotal => "whitelisted_total",
             MetricName::TestModeActions => "test_mode_actions_total",
             MetricName::MazeHits => "maze_hits_total",
@@ -181,6 +265,9 @@ impl MetricName {
             MetricName::MazeProofOutcomes => "maze_proof_outcomes_total",
             MetricName::MazeEntropyVariants => "maze_entropy_variants_total",
             MetricName::CdpDetections => "cdp_detections_total",
+            MetricName::CdpReportOutcomes => "cdp_report_outcomes_total",
+            MetricName::FormHoneypotHits => "form_honeypot_hits_total",
+            MetricName::RotatingHoneypotHits => "rotating_honeypot_hits_total",
             MetricName::BotnessSignalState => "botness_signal_state_total",
             MetricName::DefenceModeEffective => "defence_mode_effective_total",
             MetricName::EdgeIntegrationMode => "edge_integration_mode_total",
@@ -208,17 +295,40 @@ const FLUSH_VALUE_THRESHOLD: u64 = 10;
 
 /// Increment a counter metric, optionally with a label.
 /// This updates an in-memory buffer and flushes to KV on thresholds.
-pub fn increment(store: &Store, metric: MetricName, label: Option<&str>) {
+pub fn increment<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    metric: MetricName,
+    label: Option<&str>,
+) {
+    increment_by(store, metric, label, 1);
+}
+
+/// Add `amount` to a counter metric; same buffering as `increment`.
+pub fn increment_by<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    metric: MetricName,
+    label: Option<&str>,
+    amount: u64,
+) {
     let key = match label {
         Some(l) => format!("{}{}:{}", METRICS_PREFIX, metric.as_str(), l),
         None => format!("{}{}", METRICS_PREFIX, metric.as_str()),
     };
+    increment_counter(store, key.as_str(), amount);
+}
 
+/// Add `amount` to a raw counter key outside the `metrics:` namespace; same buffering
+/// as `increment`.
+pub(crate) fn increment_counter<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    key: &str,
+    amount: u64,
+) {
     // Update in-memory buffer
     {
         let mut buf = METRICS_BUFFER.lock().unwrap();
-        let v = buf.entry(key.clone()).or_insert(0);
-        *v = v.saturating_add(1);
+        let v = buf.entry(key.to_string()).or_insert(0);
+        *v = v.saturating_add(amount);
         // if this key reached threshold, flush
         if *v >= FLUSH_VALUE_THRESHOLD || buf.len() >= FLUSH_KEY_COUNT {
             // drop lock then flush below
@@ -227,36 +337,69 @@ pub fn increment(store: &Store, metric: MetricName, label: Option<&str>) {
         }
     }
 
-    // Flush buffer to KV
+    flush_buffers(store);
+}
+
+/// Write every buffered counter and histogram delta to KV.
+fn flush_buffers<S: crate::challenge::KeyValueStore + ?Sized>(store: &S) {
     let mut to_flush = HashMap::new();
     {
         let mut buf = METRICS_BUFFER.lock().unwrap();
         std::mem::swap(&mut to_flush, &mut *buf);
     }
-
-    // Apply buffered increments to KV
     for (k, v) in to_flush.into_iter() {
-        // read current
-        let current: u64 = store
-            .get(&k)
-            .ok()
-            .flatten()
-            .and_then(|val| String::from_utf8(val).ok())
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(0);
-        let new = current.saturating_add(v);
-        if let Err(e) = store.set(&k, new.to_string().as_bytes()) {
-            // If a write fails, log and re-insert the delta back into buffer for retry
-            eprintln!("[metrics] failed to write metric {} -> {}: {:?}", k, new, e);
-            let mut buf = METRICS_BUFFER.lock().unwrap();
-            let entry = buf.entry(k).or_insert(0);
-            *entry = entry.saturating_add(v);
-        }
+        write_buffered_delta(store, k, v);
+    }
+    let pending_histograms = std::mem::take(&mut *HISTOGRAM_BUFFER.lock().unwrap());
+    for (key, (histogram, delta)) in pending_histograms {
+        merge_histogram(store, histogram, key, delta);
+    }
+}
+
+/// Buffer `amount` for a raw counter key without touching KV, for callers that run before
+/// a store is open. It is written by the next flush that has one.
+pub(crate) fn buffer_counter(key: &str, amount: u64) {
+    let mut buf = METRICS_BUFFER.lock().unwrap();
+    let v = buf.entry(key.to_string()).or_insert(0);
+    *v = v.saturating_add(amount);
+}
+
+/// Write whatever this instance still has buffered. Spin builds an instance per request,
+/// so the entrypoint calls this once the response has been sent; otherwise counts below
+/// the flush thresholds would be lost with the instance.
+pub(crate) fn flush_pending() {
+    let has_pending =
+        !METRICS_BUFFER.lock().unwrap().is_empty() || !HISTOGRAM_BUFFER.lock().unwrap().is_empty();
+    if !has_pending {
+        return;
+    }
+    if let Ok(store) = spin_sdk::key_value::Store::open_default() {
+        flush_buffers(&store);
+    }
+}
+
+/// Write any buffered delta for `key` now, so a following read sees it.
+pub(crate) fn flush_counter<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, key: &str) {
+    let pending = METRICS_BUFFER.lock().unwrap().remove(key);
+    if let Some(v) = pending {
+        write_buffered_delta(store, key.to_string(), v);
     }
 }
 
-fn record_defence_mode_effective(
-    store: &Store,
+/// Apply one buffered increment to KV.
+fn write_buffered_delta<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, k: String, v: u64) {
+    let new = get_counter(store, &k).saturating_add(v);
+    if let Err(e) = store.set(&k, new.to_string().as_bytes()) {
+        // If a write fails, log and re-insert the delta back into buffer for retry
+        eprintln!("[metrics] failed to write metric {} -> {}: {:?}", k, new, e);
+        let mut buf = METRICS_BUFFER.lock().unwrap();
+        let entry = buf.entry(k).or_insert(0);
+        *entry = entry.saturating_add(v);
+    }
+}
+
+fn record_defence_mode_effective<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
     module: &str,
     effective: &crate::config::DefenceModeEffective,
 ) {
@@ -274,8 +417,8 @@ fn record_defence_mode_effective(
     );
 }
 
-pub fn record_botness_visibility(
-    store: &Store,
+pub fn record_botness_visibility<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
     cfg: &crate::config::Config,
     assessment: &crate::BotnessAssessment,
 ) {
@@ -283,6 +426,13 @@ pub fn record_botness_visibility(
         let label = format!("{}:{}", signal.key, signal.availability.as_str());
         increment(store, MetricName::BotnessSignalState, Some(label.as_str()));
     }
+    observe(
+        store,
+        HistogramName::BotnessScore,
+        None,
+        assessment.score as f64,
+    );
+    crate::observability::decision_trace::note_botness(cfg, assessment);
 
     let effective = cfg.defence_modes_effective();
     record_defence_mode_effective(store, "rate", &effective.rate);
@@ -295,8 +445,8 @@ pub fn record_botness_visibility(
     );
 }
 
-pub fn record_provider_backend_visibility(
-    store: &Store,
+pub fn record_provider_backend_visibility<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
     registry: &crate::providers::registry::ProviderRegistry,
 ) {
     let capabilities = [
@@ -324,12 +474,15 @@ pub fn record_provider_backend_visibility(
     }
 }
 
-pub fn record_policy_signal(store: &Store, signal_id: crate::runtime::policy_taxonomy::SignalId) {
+pub fn record_policy_signal<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    signal_id: crate::runtime::policy_taxonomy::SignalId,
+) {
     increment(store, MetricName::PolicySignals, Some(signal_id.as_str()));
 }
 
-pub fn record_policy_match(
-    store: &Store,
+pub fn record_policy_match<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
     policy_match: &crate::runtime::policy_taxonomy::PolicyMatch,
 ) {
     let label = format!(
@@ -342,26 +495,39 @@ pub fn record_policy_match(
     for signal in policy_match.signal_ids() {
         increment(store, MetricName::PolicySignals, Some(signal));
     }
+    crate::observability::decision_trace::note_policy_match(policy_match);
 }
 
-pub fn record_maze_token_outcome(store: &Store, outcome: &str) {
+pub fn record_maze_token_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    outcome: &str,
+) {
     increment(store, MetricName::MazeTokenOutcomes, Some(outcome));
 }
 
-pub fn record_maze_checkpoint_outcome(store: &Store, outcome: &str) {
+pub fn record_maze_checkpoint_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    outcome: &str,
+) {
     increment(store, MetricName::MazeCheckpointOutcomes, Some(outcome));
 }
 
-pub fn record_maze_budget_outcome(store: &Store, outcome: &str) {
+pub fn record_maze_budget_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    outcome: &str,
+) {
     increment(store, MetricName::MazeBudgetOutcomes, Some(outcome));
 }
 
-pub fn record_maze_proof_outcome(store: &Store, outcome: &str) {
+pub fn record_maze_proof_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    outcome: &str,
+) {
     increment(store, MetricName::MazeProofOutcomes, Some(outcome));
 }
 
-pub fn record_maze_entropy_variant(
-    store: &Store,
+pub fn record_maze_entropy_variant<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
     variant_family: &str,
     provider: &str,
     metadata_only: bool,
@@ -370,8 +536,440 @@ pub fn record_maze_entropy_variant(
     increment(store, MetricName::MazeEntropyVariants, Some(label.as_str()));
 }
 
+/// Fixed-bucket histograms. Each series (histogram plus optional label value)
+/// is one compact KV record, so an observation costs a single read and write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistogramName {
+    BotnessScore,
+    ChallengeSolveSeconds,
+    PowSolveSeconds,
+    PowSolveSecondsByDifficulty,
+    RequestDurationSeconds,
+}
+
+const HISTOGRAMS: [HistogramName; 5] = [
+    HistogramName::BotnessScore,
+    HistogramName::ChallengeSolveSeconds,
+    HistogramName::PowSolveSeconds,
+    HistogramName::PowSolveSecondsByDifficulty,
+    HistogramName::RequestDurationSeconds,
+];
+
+impl HistogramName {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HistogramName::BotnessScore => "botness_score",
+            HistogramName::ChallengeSolveSeconds => "challenge_solve_seconds",
+            HistogramName::PowSolveSeconds => "pow_solve_seconds",
+            HistogramName::PowSolveSecondsByDifficulty => "pow_solve_seconds_by_difficulty",
+            HistogramName::RequestDurationSeconds => "request_duration_seconds",
+        }
+    }
+
+    fn help(&self) -> &'static str {
+        match self {
+            HistogramName::BotnessScore => "Botness score of requests reaching the botness gate",
+            HistogramName::ChallengeSolveSeconds => {
+                "Seconds from challenge issue to successful submit"
+            }
+            HistogramName::PowSolveSeconds => "Seconds from PoW seed issue to verified proof",
+            HistogramName::PowSolveSecondsByDifficulty => {
+                "Seconds from PoW seed issue to verified proof, by issued difficulty"
+            }
+            HistogramName::RequestDurationSeconds => {
+                "Bot defence handling time for requests evaluated by the policy pipeline"
+            }
+        }
+    }
+
+    fn bounds(&self) -> &'static [f64] {
+        match self {
+            HistogramName::BotnessScore => &BOTNESS_SCORE_BUCKETS,
+            HistogramName::ChallengeSolveSeconds
+            | HistogramName::PowSolveSeconds
+            | HistogramName::PowSolveSecondsByDifficulty => &SOLVE_SECONDS_BUCKETS,
+            HistogramName::RequestDurationSeconds => &REQUEST_DURATION_SECONDS_BUCKETS,
+        }
+    }
+
+    /// Label name and the bounded set of values it may take.
+    fn label(&self) -> Option<(&'static str, &'static [&'static str])> {
+        match self {
+            HistogramName::ChallengeSolveSeconds => Some(("challenge", &CHALLENGE_SOLVE_KINDS)),
+            HistogramName::PowSolveSecondsByDifficulty => {
+                Some(("difficulty", &POW_DIFFICULTY_LABELS))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Stored per-bucket (non-cumulative) counts; the final slot is the `+Inf` overflow.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct HistogramRecord {
+    buckets: Vec<u64>,
+    count: u64,
+    sum: f64,
+}
+
+fn histogram_key(histogram: HistogramName, label: Option<&str>) -> String {
+    match label {
+        Some(l) => format!("{}{}:{}", HISTOGRAM_PREFIX, histogram.as_str(), l),
+        None => format!("{}{}", HISTOGRAM_PREFIX, histogram.as_str()),
+    }
+}
+
+fn load_histogram<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    histogram: HistogramName,
+    key: &str,
+) -> HistogramRecord {
+    let slots = histogram.bounds().len() + 1;
+    store
+        .get(key)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<HistogramRecord>(&raw).ok())
+        // A bucket layout change starts the series over rather than misattributing counts.
+        .filter(|record| record.buckets.len() == slots)
+        .unwrap_or_else(|| empty_histogram_record(histogram))
+}
+
+// Pending observations per histogram series, written by the same flush as `METRICS_BUFFER`.
+static HISTOGRAM_BUFFER: Lazy<Mutex<HashMap<String, (HistogramName, HistogramRecord)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn empty_histogram_record(histogram: HistogramName) -> HistogramRecord {
+    HistogramRecord {
+        buckets: vec![0; histogram.bounds().len() + 1],
+        ..HistogramRecord::default()
+    }
+}
+
+fn add_observation(record: &mut HistogramRecord, histogram: HistogramName, value: f64) {
+    let slot = histogram
+        .bounds()
+        .iter()
+        .position(|bound| value <= *bound)
+        .unwrap_or(histogram.bounds().len());
+    record.buckets[slot] = record.buckets[slot].saturating_add(1);
+    record.count = record.count.saturating_add(1);
+    record.sum += value;
+}
+
+fn merge_record(into: &mut HistogramRecord, delta: &HistogramRecord) {
+    for (slot, count) in into.buckets.iter_mut().zip(delta.buckets.iter()) {
+        *slot = slot.saturating_add(*count);
+    }
+    into.count = into.count.saturating_add(delta.count);
+    into.sum += delta.sum;
+}
+
+/// Add buffered observations to the stored record; a failed write goes back to the buffer.
+fn merge_histogram<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    histogram: HistogramName,
+    key: String,
+    delta: HistogramRecord,
+) {
+    let mut record = load_histogram(store, histogram, key.as_str());
+    merge_record(&mut record, &delta);
+    let Ok(encoded) = serde_json::to_vec(&record) else {
+        return;
+    };
+    if let Err(e) = store.set(&key, &encoded) {
+        eprintln!("[metrics] failed to write histogram {}: {:?}", key, e);
+        let mut buf = HISTOGRAM_BUFFER.lock().unwrap();
+        let (_, pending) = buf
+            .entry(key)
+            .or_insert_with(|| (histogram, empty_histogram_record(histogram)));
+        merge_record(pending, &delta);
+    }
+}
+
+/// Record one observation. Labels outside the histogram's allowed set are dropped
+/// to keep series cardinality bounded. Observations are buffered like counters and
+/// flushed on the same thresholds.
+pub fn observe<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    histogram: HistogramName,
+    label: Option<&str>,
+    value: f64,
+) {
+    if !value.is_finite() || value < 0.0 {
+        return;
+    }
+    match (histogram.label(), label) {
+        (Some((_, allowed)), Some(label)) if allowed.contains(&label) => {}
+        (None, None) => {}
+        _ => return,
+    }
+    let key = histogram_key(histogram, label);
+    buffer_observation(store, histogram, key, value);
+}
+
+/// Add one observation to the in-memory buffer; `true` when the buffer is due a flush.
+/// Whatever stays below the thresholds is written by `flush_pending` after the response.
+fn enqueue_observation(histogram: HistogramName, key: String, value: f64) -> bool {
+    let mut buf = HISTOGRAM_BUFFER.lock().unwrap();
+    let keys = buf.len();
+    let (_, pending) = buf
+        .entry(key)
+        .or_insert_with(|| (histogram, empty_histogram_record(histogram)));
+    add_observation(pending, histogram, value);
+    pending.count >= FLUSH_VALUE_THRESHOLD || keys >= FLUSH_KEY_COUNT
+}
+
+#[cfg(not(test))]
+fn buffer_observation<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    histogram: HistogramName,
+    key: String,
+    value: f64,
+) {
+    if enqueue_observation(histogram, key, value) {
+        flush_buffers(store);
+    }
+}
+
+// Tests share one process, so observations are written through rather than left in a
+// buffer another test's flush could pick up.
+#[cfg(test)]
+fn buffer_observation<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    histogram: HistogramName,
+    key: String,
+    value: f64,
+) {
+    let mut delta = empty_histogram_record(histogram);
+    add_observation(&mut delta, histogram, value);
+    merge_histogram(store, histogram, key, delta);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MetricValue {
+    Int(u64),
+    /// Per-bucket (non-cumulative) counts; the final slot is the `+Inf` overflow.
+    Histogram {
+        bounds: &'static [f64],
+        buckets: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MetricPoint {
+    pub labels: Vec<(&'static str, String)>,
+    pub value: MetricValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MetricFamily {
+    pub name: String,
+    pub kind: MetricKind,
+    pub help: Option<&'static str>,
+    pub points: Vec<MetricPoint>,
+}
+
+impl MetricFamily {
+    pub(crate) fn point(&mut self, labels: &[(&'static str, &dyn std::fmt::Display)], value: u64) {
+        self.points.push(MetricPoint {
+            labels: labels
+                .iter()
+                .map(|(name, value)| (*name, value.to_string()))
+                .collect(),
+            value: MetricValue::Int(value),
+        });
+    }
+}
+
+/// Metric families read from KV in exposition order. `/metrics` renders it as Prometheus
+/// text and the OTLP exporter encodes it directly, so both surfaces report the same values.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct MetricsSnapshot {
+    pub families: Vec<MetricFamily>,
+}
+
+impl MetricsSnapshot {
+    pub(crate) fn family(
+        &mut self,
+        name: &str,
+        kind: MetricKind,
+        help: Option<&'static str>,
+    ) -> &mut MetricFamily {
+        self.families.push(MetricFamily {
+            name: name.to_string(),
+            kind,
+            help,
+            points: Vec::new(),
+        });
+        self.families.last_mut().unwrap()
+    }
+}
+
+fn collect_histograms<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    snapshot: &mut MetricsSnapshot,
+) {
+    for histogram in HISTOGRAMS {
+        let family = snapshot.family(
+            format!("bot_defence_{}", histogram.as_str()).as_str(),
+            MetricKind::Histogram,
+            Some(histogram.help()),
+        );
+        let series: Vec<Option<(&'static str, &'static str)>> = match histogram.label() {
+            Some((label_name, values)) => values
+                .iter()
+                .map(|value| Some((label_name, *value)))
+                .collect(),
+            None => vec![None],
+        };
+        for label in series {
+            let key = histogram_key(histogram, label.map(|(_, value)| value));
+            let record = load_histogram(store, histogram, key.as_str());
+            family.points.push(MetricPoint {
+                labels: label
+                    .map(|(name, value)| vec![(name, value.to_string())])
+                    .unwrap_or_default(),
+                value: MetricValue::Histogram {
+                    bounds: histogram.bounds(),
+                    buckets: record.buckets,
+                    sum: record.sum,
+                    count: record.count,
+                },
+            });
+        }
+    }
+}
+
+fn render_labels(labels: &[(&'static str, String)], extra: Option<(&str, &str)>) -> String {
+    let rendered: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .chain(extra)
+        .map(|(name, value)| format!("{}=\"{}\"", name, value))
+        .collect();
+    if rendered.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", rendered.join(","))
+    }
+}
+
+fn render_point(output: &mut String, name: &str, point: &MetricPoint) {
+    match &point.value {
+        MetricValue::Int(value) => output.push_str(&format!(
+            "{}{} {}\n",
+            name,
+            render_labels(&point.labels, None),
+            value
+        )),
+        MetricValue::Histogram {
+            bounds,
+            buckets,
+            sum,
+            count,
+        } => {
+            let mut cumulative = 0u64;
+            for (bound, bucket) in bounds.iter().zip(buckets.iter()) {
+                cumulative = cumulative.saturating_add(*bucket);
+                output.push_str(&format!(
+                    "{}_bucket{} {}\n",
+                    name,
+                    render_labels(&point.labels, Some(("le", bound.to_string().as_str()))),
+                    cumulative
+                ));
+            }
+            output.push_str(&format!(
+                "{}_bucket{} {}\n",
+                name,
+                render_labels(&point.labels, Some(("le", "+Inf"))),
+                count
+            ));
+            let series_labels = render_labels(&point.labels, None);
+            output.push_str(&format!("{}_sum{} {}\n", name, series_labels, sum));
+            output.push_str(&format!("{}_count{} {}\n", name, series_labels, count));
+        }
+    }
+}
+
+/// Prometheus text exposition of a snapshot.
+fn render_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut output = String::from("# WASM Bot Defence Metrics\n");
+    for (index, family) in snapshot.families.iter().enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+        output.push_str(&format!(
+            "# TYPE {} {}\n",
+            family.name,
+            family.kind.as_str()
+        ));
+        if let Some(help) = family.help {
+            output.push_str(&format!("# HELP {} {}\n", family.name, help));
+        }
+        for point in &family.points {
+            render_point(&mut output, family.name.as_str(), point);
+        }
+    }
+    output
+}
+
+/// Generate Prometheus-format metrics output
+pub fn render_metrics<S: crate::challenge::KeyValueStore>(store: &S) -> String {
+    render_prometheus(&collect_metrics(store))
+}
+
+/// Times one policy-pipeline request and records it when dropped, so every
+/// early return is covered.
+pub struct RequestDurationTimer<'a> {
+    store: &'a dyn crate::challenge::KeyValueStore,
+    started: std::time::Instant,
+}
+
+impl<'a> RequestDurationTimer<'a> {
+    pub fn new(
+        store: &'a dyn crate::challenge::KeyValueStore,
+        started: std::time::Instant,
+    ) -> Self {
+        Self { store, started }
+    }
+}
+
+impl Drop for RequestDurationTimer<'_> {
+    fn drop(&mut self) {
+        observe(
+            self.store,
+            HistogramName::RequestDurationSeconds,
+            None,
+            self.started.elapsed().as_secs_f64(),
+        );
+    }
+}
+
 /// Get current value of a counter
-fn get_counter(store: &Store, key: &str) -> u64 {
+pub(crate) fn get_counter<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    key: &str,
+) -> u64 {
     store
         .get(key)
         .ok()
@@ -381,7 +979,10 @@ fn get_counter(store: &Store, key: &str) -> u64 {
         .unwrap_or(0)
 }
 
-fn collect_labeled_counters(store: &Store, metric: MetricName) -> Vec<(String, u64)> {
+fn collect_labeled_counters<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    metric: MetricName,
+) -> Vec<(String, u64)> {
     let mut rows = Vec::new();
     let prefix = format!("{}{}:", METRICS_PREFIX, metric.as_str());
 
@@ -398,26 +999,48 @@ fn collect_labeled_counters(store: &Store, metric: MetricName) -> Vec<(String, u
     rows
 }
 
+/// Sum each metric across its unlabeled and labeled counters in a single key scan.
+pub(crate) fn counter_totals<S: crate::challenge::KeyValueStore, const N: usize>(
+    store: &S,
+    metrics: [MetricName; N],
+) -> [u64; N] {
+    let mut totals = [0u64; N];
+    let prefixes = metrics.map(|metric| format!("{}{}", METRICS_PREFIX, metric.as_str()));
+    if let Ok(keys) = store.get_keys() {
+        for key in keys {
+            for (index, prefix) in prefixes.iter().enumerate() {
+                let Some(rest) = key.strip_prefix(prefix.as_str()) else {
+                    continue;
+                };
+                if rest.is_empty() || rest.starts_with(':') {
+                    totals[index] = totals[index].saturating_add(get_counter(store, &key));
+                }
+            }
+        }
+    }
+    totals
+}
+
 /// Count active bans (gauge)
-fn count_active_bans(store: &Store) -> u64 {
+fn count_active_bans<S: crate::challenge::KeyValueStore>(store: &S) -> u64 {
     crate::enforcement::ban::list_active_bans_with_scan(store, "default").len() as u64
 }
 
-/// Generate Prometheus-format metrics output
-pub fn render_metrics(store: &Store) -> String {
-    let mut output = String::new();
-
-    // Header
-    output.push_str("# WASM Bot Defence Metrics\n");
-    output.push_str("# TYPE bot_defence_requests_total counter\n");
+/// Read every exported metric family from KV.
+pub(crate) fn collect_metrics<S: crate::challenge::KeyValueStore>(store: &S) -> MetricsSnapshot {
+    let mut snapshot = MetricsSnapshot::default();
 
     // Requests total
+    let family = snapshot.family("bot_defence_requests_total", MetricKind::Counter, None);
     let requests = get_counter(store, &format!("{}requests_total", METRICS_PREFIX));
-    output.push_str(&format!("bot_defence_requests_total {}\n", requests));
+    family.point(&[], requests);
 
     // Bans by reason
-    output.push_str("\n# TYPE bot_defence_bans_total counter\n");
-    output.push_str("# HELP bot_defence_bans_total Total number of IP bans by reason\n");
+    let family = snapshot.family(
+        "bot_defence_bans_total",
+        MetricKind::Counter,
+        Some("Total number of IP bans by reason"),
+    );
     for reason in &[
         "honeypot",
         "rate_limit",
@@ -428,143 +1051,312 @@ pub fn render_metrics(store: &Store) -> String {
     ] {
         let key = format!("{}bans_total:{}", METRICS_PREFIX, reason);
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_bans_total{{reason=\"{}\"}} {}\n",
-            reason, count
-        ));
+        family.point(&[("reason", &reason)], count);
     }
 
     // Blocks total
-    output.push_str("\n# TYPE bot_defence_blocks_total counter\n");
+    let family = snapshot.family("bot_defence_blocks_total", MetricKind::Counter, None);
     let blocks = get_counter(store, &format!("{}blocks_total", METRICS_PREFIX));
-    output.push_str(&format!("bot_defence_blocks_total {}\n", blocks));
+    family.point(&[], blocks);
 
     // Challenges total
-    output.push_str("\n# TYPE bot_defence_challenges_total counter\n");
+    let family = snapshot.family("bot_defence_challenges_total", MetricKind::Counter, None);
     let challenges = get_counter(store, &format!("{}challenges_total", METRICS_PREFIX));
-    output.push_str(&format!("bot_defence_challenges_total {}\n", challenges));
+    family.point(&[], challenges);
 
-    output.push_str("\n# TYPE bot_defence_not_a_bot_served_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_not_a_bot_served_total",
+        MetricKind::Counter,
+        None,
+    );
     let not_a_bot_served = get_counter(store, &format!("{}not_a_bot_served_total", METRICS_PREFIX));
-    output.push_str(&format!(
-        "bot_defence_not_a_bot_served_total {}\n",
-        not_a_bot_served
-    ));
+    family.point(&[], not_a_bot_served);
 
-    output.push_str("\n# TYPE bot_defence_not_a_bot_pass_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_not_a_bot_pass_total",
+        MetricKind::Counter,
+        None,
+    );
     let not_a_bot_pass = get_counter(store, &format!("{}not_a_bot_pass_total", METRICS_PREFIX));
-    output.push_str(&format!(
-        "bot_defence_not_a_bot_pass_total {}\n",
-        not_a_bot_pass
-    ));
+    family.point(&[], not_a_bot_pass);
 
-    output.push_str("\n# TYPE bot_defence_not_a_bot_escalate_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_not_a_bot_escalate_total",
+        MetricKind::Counter,
+        None,
+    );
     let not_a_bot_escalate = get_counter(
         store,
         &format!("{}not_a_bot_escalate_total", METRICS_PREFIX),
     );
-    output.push_str(&format!(
-        "bot_defence_not_a_bot_escalate_total {}\n",
-        not_a_bot_escalate
-    ));
+    family.point(&[], not_a_bot_escalate);
 
-    output.push_str("\n# TYPE bot_defence_not_a_bot_fail_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_not_a_bot_fail_total",
+        MetricKind::Counter,
+        None,
+    );
     let not_a_bot_fail = get_counter(store, &format!("{}not_a_bot_fail_total", METRICS_PREFIX));
-    output.push_str(&format!(
-        "bot_defence_not_a_bot_fail_total {}\n",
-        not_a_bot_fail
-    ));
+    family.point(&[], not_a_bot_fail);
 
-    output.push_str("\n# TYPE bot_defence_not_a_bot_replay_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_not_a_bot_replay_total",
+        MetricKind::Counter,
+        None,
+    );
     let not_a_bot_replay = get_counter(
         store,
         &format!("{}not_a_bot_replay_total", METRICS_PREFIX),
     );
-    output.push_str(&format!(
-        "bot_defence_not_a_bot_replay_total {}\n",
-        not_a_bot_replay
-    ));
+    family.point(&[], not_a_bot_replay);
 
     // Challenge outcomes
-    output.push_str("\n# TYPE bot_defence_challenge_served_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_challenge_served_total",
+        MetricKind::Counter,
+        None,
+    );
     let challenge_served = get_counter(store, &format!("{}challenge_served_total", METRICS_PREFIX));
-    output.push_str(&format!(
-        "bot_defence_challenge_served_total {}\n",
-        challenge_served
-    ));
+    family.point(&[], challenge_served);
 
-    output.push_str("\n# TYPE bot_defence_challenge_solved_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_challenge_solved_total",
+        MetricKind::Counter,
+        None,
+    );
     let challenge_solved = get_counter(store, &format!("{}challenge_solved_total", METRICS_PREFIX));
-    output.push_str(&format!(
-        "bot_defence_challenge_solved_total {}\n",
-        challenge_solved
-    ));
+    family.point(&[], challenge_solved);
 
-    output.push_str("\n# TYPE bot_defence_challenge_incorrect_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_challenge_incorrect_total",
+        MetricKind::Counter,
+        None,
+    );
     let challenge_incorrect = get_counter(
         store,
         &format!("{}challenge_incorrect_total", METRICS_PREFIX),
     );
-    output.push_str(&format!(
-        "bot_defence_challenge_incorrect_total {}\n",
-        challenge_incorrect
-    ));
+    family.point(&[], challenge_incorrect);
 
-    output.push_str("\n# TYPE bot_defence_challenge_expired_replay_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_challenge_expired_replay_total",
+        MetricKind::Counter,
+        None,
+    );
     let challenge_expired_replay = get_counter(
         store,
         &format!("{}challenge_expired_replay_total", METRICS_PREFIX),
     );
-    output.push_str(&format!(
-        "bot_defence_challenge_expired_replay_total {}\n",
-        challenge_expired_replay
-    ));
+    family.point(&[], challenge_expired_replay);
 
-    output.push_str("\n# TYPE bot_defence_cdp_detections_total counter\n");
-    output.push_str("# HELP bot_defence_cdp_detections_total Total CDP detection reports processed\n");
+    let family = snapshot.family(
+        "bot_defence_challenge_accessible_served_total",
+        MetricKind::Counter,
+        Some("Text alternatives served in place of the puzzle grid"),
+    );
+    let challenge_accessible_served = get_counter(
+        store,
+        &format!("{}challenge_accessible_served_total", METRICS_PREFIX),
+    );
+    family.point(&[], challenge_accessible_served);
+
+    let family = snapshot.family(
+        "bot_defence_challenge_accessible_outcomes_total",
+        MetricKind::Counter,
+        Some("Text alternative submission outcomes"),
+    );
+    for outcome in CHALLENGE_ACCESSIBLE_OUTCOMES {
+        let key = format!(
+            "{}challenge_accessible_outcomes_total:{}",
+            METRICS_PREFIX, outcome
+        );
+        let count = get_counter(store, &key);
+        family.point(&[("outcome", &outcome)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_privacy_pass_redemptions_total",
+        MetricKind::Counter,
+        Some("PrivateToken redemptions by outcome"),
+    );
+    for outcome in PRIVACY_PASS_OUTCOMES {
+        let key = format!("{}privacy_pass_redemptions_total:{}", METRICS_PREFIX, outcome);
+        let count = get_counter(store, &key);
+        family.point(&[("outcome", &outcome)], count);
+    }
+
+    // Per-crawler series follow the runtime crawler catalog.
+    let runtime_cfg = crate::config::load_runtime_cached(store, "default").ok();
+    let catalog_cfg = match runtime_cfg.as_ref() {
+        Some(cfg) => cfg,
+        None => crate::config::defaults(),
+    };
+    let family = snapshot.family(
+        "bot_defence_ai_crawler_hits_total",
+        MetricKind::Counter,
+        Some("Requests from self-declared AI crawlers by catalog bot"),
+    );
+    for bot in crate::crawler_policy::declared::catalog(catalog_cfg) {
+        let key = format!("{}ai_crawler_hits_total:{}", METRICS_PREFIX, bot);
+        let count = get_counter(store, &key);
+        family.point(&[("bot", &bot)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_robots_txt_compliance_total",
+        MetricKind::Counter,
+        Some("robots.txt fetches tracked per client and later requests that ignored them"),
+    );
+    for outcome in ROBOTS_TXT_COMPLIANCE_OUTCOMES {
+        let key = format!("{}robots_txt_compliance_total:{}", METRICS_PREFIX, outcome);
+        let count = get_counter(store, &key);
+        family.point(&[("outcome", &outcome)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_web_bot_auth_verifications_total",
+        MetricKind::Counter,
+        Some("Web Bot Auth signature checks by outcome"),
+    );
+    for outcome in WEB_BOT_AUTH_OUTCOMES {
+        let key = format!(
+            "{}web_bot_auth_verifications_total:{}",
+            METRICS_PREFIX, outcome
+        );
+        let count = get_counter(store, &key);
+        family.point(&[("outcome", &outcome)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_crawl_payment_outcomes_total",
+        MetricKind::Counter,
+        Some("Pay-per-crawl offers and token redemptions by outcome"),
+    );
+    for outcome in CRAWL_PAYMENT_OUTCOMES {
+        let key = format!("{}crawl_payment_outcomes_total:{}", METRICS_PREFIX, outcome);
+        let count = get_counter(store, &key);
+        family.point(&[("outcome", &outcome)], count);
+    }
+
+    // Paid crawls are metered per crawler: catalogued bots plus configured signed agents.
+    let mut paying_crawlers: Vec<String> = crate::crawler_policy::declared::catalog(catalog_cfg)
+        .map(str::to_string)
+        .collect();
+    if let Some(cfg) = runtime_cfg.as_ref() {
+        paying_crawlers.extend(
+            cfg.web_bot_auth_agents
+                .iter()
+                .map(|agent| agent.name.clone()),
+        );
+    }
+    let family = snapshot.family(
+        "bot_defence_crawl_payment_paid_total",
+        MetricKind::Counter,
+        Some("Paid crawl requests per crawler"),
+    );
+    for crawler in &paying_crawlers {
+        let key = format!("{}crawl_payment_paid_total:{}", METRICS_PREFIX, crawler);
+        family.point(&[("crawler", &crawler)], get_counter(store, &key));
+    }
+    let family = snapshot.family(
+        "bot_defence_crawl_payment_charged_micros_total",
+        MetricKind::Counter,
+        Some("Amount charged per crawler in millionths of the configured currency"),
+    );
+    for crawler in &paying_crawlers {
+        let key = format!(
+            "{}crawl_payment_charged_micros_total:{}",
+            METRICS_PREFIX, crawler
+        );
+        family.point(&[("crawler", &crawler)], get_counter(store, &key));
+    }
+
+    let family = snapshot.family(
+        "bot_defence_cdp_detections_total",
+        MetricKind::Counter,
+        Some("Total CDP detection reports processed"),
+    );
     // Keep parity with `/admin/cdp` stats while preserving the buffered metrics key path.
     let cdp_detections = get_counter(store, "cdp:detections")
         .max(get_counter(store, &format!("{}cdp_detections_total", METRICS_PREFIX)));
-    output.push_str(&format!(
-        "bot_defence_cdp_detections_total {}\n",
-        cdp_detections
-    ));
+    family.point(&[], cdp_detections);
+
+    let family = snapshot.family(
+        "bot_defence_cdp_report_outcomes_total",
+        MetricKind::Counter,
+        Some("CDP reports accepted or rejected by report nonce checks"),
+    );
+    for outcome in CDP_REPORT_OUTCOMES {
+        let key = format!("{}cdp_report_outcomes_total:{}", METRICS_PREFIX, outcome);
+        let count = get_counter(store, &key);
+        family.point(&[("outcome", &outcome)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_form_honeypot_hits_total",
+        MetricKind::Counter,
+        Some("Form submissions that filled a decoy field or beat the minimum submit time"),
+    );
+    for trip in FORM_HONEYPOT_TRIPS {
+        let key = format!("{}form_honeypot_hits_total:{}", METRICS_PREFIX, trip);
+        let count = get_counter(store, &key);
+        family.point(&[("trip", &trip)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_rotating_honeypot_hits_total",
+        MetricKind::Counter,
+        Some("Hits on rotating honeypot paths by generation, for generations still honoured"),
+    );
+    for generation in
+        crate::enforcement::honeypot::live_generations(catalog_cfg, crate::admin::now_ts())
+    {
+        let key = format!(
+            "{}rotating_honeypot_hits_total:{}",
+            METRICS_PREFIX, generation
+        );
+        let count = get_counter(store, &key);
+        family.point(&[("generation", &generation)], count);
+    }
 
     // Whitelisted total
-    output.push_str("\n# TYPE bot_defence_whitelisted_total counter\n");
+    let family = snapshot.family("bot_defence_whitelisted_total", MetricKind::Counter, None);
     let whitelisted = get_counter(store, &format!("{}whitelisted_total", METRICS_PREFIX));
-    output.push_str(&format!("bot_defence_whitelisted_total {}\n", whitelisted));
+    family.point(&[], whitelisted);
 
     // Test mode actions
-    output.push_str("\n# TYPE bot_defence_test_mode_actions_total counter\n");
+    let family = snapshot.family(
+        "bot_defence_test_mode_actions_total",
+        MetricKind::Counter,
+        None,
+    );
     let test_mode = get_counter(store, &format!("{}test_mode_actions_total", METRICS_PREFIX));
-    output.push_str(&format!(
-        "bot_defence_test_mode_actions_total {}\n",
-        test_mode
-    ));
+    family.point(&[], test_mode);
 
     // Maze hits
-    output.push_str("\n# TYPE bot_defence_maze_hits_total counter\n");
-    output.push_str("# HELP bot_defence_maze_hits_total Total hits on maze pages\n");
+    let family = snapshot.family(
+        "bot_defence_maze_hits_total",
+        MetricKind::Counter,
+        Some("Total hits on maze pages"),
+    );
     let maze_hits = get_counter(store, &format!("{}maze_hits_total", METRICS_PREFIX));
-    output.push_str(&format!("bot_defence_maze_hits_total {}\n", maze_hits));
+    family.point(&[], maze_hits);
 
-    output.push_str("\n# TYPE bot_defence_maze_token_outcomes_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_maze_token_outcomes_total Maze traversal token outcomes by outcome label\n",
+    let family = snapshot.family(
+        "bot_defence_maze_token_outcomes_total",
+        MetricKind::Counter,
+        Some("Maze traversal token outcomes by outcome label"),
     );
     for outcome in MAZE_TOKEN_OUTCOMES {
         let key = format!("{}maze_token_outcomes_total:{}", METRICS_PREFIX, outcome);
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_maze_token_outcomes_total{{outcome=\"{}\"}} {}\n",
-            outcome, count
-        ));
+        family.point(&[("outcome", &outcome)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_maze_checkpoint_outcomes_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_maze_checkpoint_outcomes_total Maze checkpoint submission outcomes\n",
+    let family = snapshot.family(
+        "bot_defence_maze_checkpoint_outcomes_total",
+        MetricKind::Counter,
+        Some("Maze checkpoint submission outcomes"),
     );
     for outcome in MAZE_CHECKPOINT_OUTCOMES {
         let key = format!(
@@ -572,55 +1364,56 @@ pub fn render_metrics(store: &Store) -> String {
             METRICS_PREFIX, outcome
         );
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_maze_checkpoint_outcomes_total{{outcome=\"{}\"}} {}\n",
-            outcome, count
-        ));
+        family.point(&[("outcome", &outcome)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_maze_budget_outcomes_total counter\n");
-    output.push_str("# HELP bot_defence_maze_budget_outcomes_total Maze budget outcomes\n");
+    let family = snapshot.family(
+        "bot_defence_maze_budget_outcomes_total",
+        MetricKind::Counter,
+        Some("Maze budget outcomes"),
+    );
     for outcome in MAZE_BUDGET_OUTCOMES {
         let key = format!("{}maze_budget_outcomes_total:{}", METRICS_PREFIX, outcome);
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_maze_budget_outcomes_total{{outcome=\"{}\"}} {}\n",
-            outcome, count
-        ));
+        family.point(&[("outcome", &outcome)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_maze_proof_outcomes_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_maze_proof_outcomes_total Maze proof-of-work outcomes for deep traversal\n",
+    let family = snapshot.family(
+        "bot_defence_maze_proof_outcomes_total",
+        MetricKind::Counter,
+        Some("Maze proof-of-work outcomes for deep traversal"),
     );
     for outcome in MAZE_PROOF_OUTCOMES {
         let key = format!("{}maze_proof_outcomes_total:{}", METRICS_PREFIX, outcome);
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_maze_proof_outcomes_total{{outcome=\"{}\"}} {}\n",
-            outcome, count
-        ));
+        family.point(&[("outcome", &outcome)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_maze_entropy_variants_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_maze_entropy_variants_total Maze entropy variant families by provider and metadata posture\n",
+    let family = snapshot.family(
+        "bot_defence_maze_entropy_variants_total",
+        MetricKind::Counter,
+        Some("Maze entropy variant families by provider and metadata posture"),
     );
     for (label, count) in collect_labeled_counters(store, MetricName::MazeEntropyVariants) {
         let mut parts = label.splitn(3, ':');
         let variant = parts.next().unwrap_or("unknown");
         let provider = parts.next().unwrap_or("unknown");
         let metadata_only = parts.next().unwrap_or("unknown");
-        output.push_str(&format!(
-            "bot_defence_maze_entropy_variants_total{{variant=\"{}\",provider=\"{}\",metadata_only=\"{}\"}} {}\n",
-            variant, provider, metadata_only, count
-        ));
+        family.point(
+            &[
+                ("variant", &variant),
+                ("provider", &provider),
+                ("metadata_only", &metadata_only),
+            ],
+            count,
+        );
     }
 
     // Botness signal states
-    output.push_str("\n# TYPE bot_defence_botness_signal_state_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_botness_signal_state_total Botness signal state observations by signal key and availability\n",
+    let family = snapshot.family(
+        "bot_defence_botness_signal_state_total",
+        MetricKind::Counter,
+        Some("Botness signal state observations by signal key and availability"),
     );
     for signal_key in BOTNESS_SIGNAL_KEYS {
         for state in SIGNAL_AVAILABILITY_STATES {
@@ -629,17 +1422,15 @@ pub fn render_metrics(store: &Store) -> String {
                 METRICS_PREFIX, signal_key, state
             );
             let count = get_counter(store, &key);
-            output.push_str(&format!(
-                "bot_defence_botness_signal_state_total{{signal=\"{}\",state=\"{}\"}} {}\n",
-                signal_key, state, count
-            ));
+            family.point(&[("signal", &signal_key), ("state", &state)], count);
         }
     }
 
     // Effective defence modes (runtime-observed)
-    output.push_str("\n# TYPE bot_defence_defence_mode_effective_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_defence_mode_effective_total Observed effective defence mode combinations by module\n",
+    let family = snapshot.family(
+        "bot_defence_defence_mode_effective_total",
+        MetricKind::Counter,
+        Some("Observed effective defence mode combinations by module"),
     );
     for module in DEFENCE_MODE_MODULES {
         for configured_mode in DEFENCE_MODE_VALUES {
@@ -654,37 +1445,37 @@ pub fn render_metrics(store: &Store) -> String {
                         action_enabled as u8
                     );
                     let count = get_counter(store, &key);
-                    output.push_str(&format!(
-                        "bot_defence_defence_mode_effective_total{{module=\"{}\",configured=\"{}\",signal_enabled=\"{}\",action_enabled=\"{}\"}} {}\n",
-                        module,
-                        configured_mode,
-                        signal_enabled,
-                        action_enabled,
-                        count
-                    ));
+                    family.point(
+                        &[
+                            ("module", &module),
+                            ("configured", &configured_mode),
+                            ("signal_enabled", &signal_enabled),
+                            ("action_enabled", &action_enabled),
+                        ],
+                        count,
+                    );
                 }
             }
         }
     }
 
     // Edge integration mode observations
-    output.push_str("\n# TYPE bot_defence_edge_integration_mode_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_edge_integration_mode_total Observed configured edge integration mode\n",
+    let family = snapshot.family(
+        "bot_defence_edge_integration_mode_total",
+        MetricKind::Counter,
+        Some("Observed configured edge integration mode"),
     );
     for mode in EDGE_INTEGRATION_MODES {
         let key = format!("{}edge_integration_mode_total:{}", METRICS_PREFIX, mode);
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_edge_integration_mode_total{{mode=\"{}\"}} {}\n",
-            mode, count
-        ));
+        family.point(&[("mode", &mode)], count);
     }
 
     // Active provider implementation observations
-    output.push_str("\n# TYPE bot_defence_provider_implementation_effective_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_provider_implementation_effective_total Observed active provider backend and implementation by capability\n",
+    let family = snapshot.family(
+        "bot_defence_provider_implementation_effective_total",
+        MetricKind::Counter,
+        Some("Observed active provider backend and implementation by capability"),
     );
     for (capability, backend, implementation) in PROVIDER_OBSERVED_COMBINATIONS {
         let key = format!(
@@ -695,19 +1486,21 @@ pub fn render_metrics(store: &Store) -> String {
             implementation
         );
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_provider_implementation_effective_total{{capability=\"{}\",backend=\"{}\",implementation=\"{}\"}} {}\n",
-            capability.as_str(),
-            backend.as_str(),
-            implementation,
-            count
-        ));
+        family.point(
+            &[
+                ("capability", &capability.as_str()),
+                ("backend", &backend.as_str()),
+                ("implementation", &implementation),
+            ],
+            count,
+        );
     }
 
     // External rate-limiter backend errors
-    output.push_str("\n# TYPE bot_defence_rate_limiter_backend_errors_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_rate_limiter_backend_errors_total External rate-limiter backend errors by route class\n",
+    let family = snapshot.family(
+        "bot_defence_rate_limiter_backend_errors_total",
+        MetricKind::Counter,
+        Some("External rate-limiter backend errors by route class"),
     );
     for route_class in RATE_LIMITER_ROUTE_CLASSES {
         let key = format!(
@@ -715,16 +1508,14 @@ pub fn render_metrics(store: &Store) -> String {
             METRICS_PREFIX, route_class
         );
         let count = get_counter(store, &key);
-        output.push_str(&format!(
-            "bot_defence_rate_limiter_backend_errors_total{{route_class=\"{}\"}} {}\n",
-            route_class, count
-        ));
+        family.point(&[("route_class", &route_class)], count);
     }
 
     // External rate-limiter outage decisions
-    output.push_str("\n# TYPE bot_defence_rate_limiter_outage_decisions_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_rate_limiter_outage_decisions_total Degraded external rate-limiter decisions by route class, outage mode, action, and decision\n",
+    let family = snapshot.family(
+        "bot_defence_rate_limiter_outage_decisions_total",
+        MetricKind::Counter,
+        Some("Degraded external rate-limiter decisions by route class, outage mode, action, and decision"),
     );
     for route_class in RATE_LIMITER_ROUTE_CLASSES {
         for mode in RATE_LIMITER_OUTAGE_MODES {
@@ -735,19 +1526,25 @@ pub fn render_metrics(store: &Store) -> String {
                         METRICS_PREFIX, route_class, mode, action, decision
                     );
                     let count = get_counter(store, &key);
-                    output.push_str(&format!(
-                        "bot_defence_rate_limiter_outage_decisions_total{{route_class=\"{}\",mode=\"{}\",action=\"{}\",decision=\"{}\"}} {}\n",
-                        route_class, mode, action, decision, count
-                    ));
+                    family.point(
+                        &[
+                            ("route_class", &route_class),
+                            ("mode", &mode),
+                            ("action", &action),
+                            ("decision", &decision),
+                        ],
+                        count,
+                    );
                 }
             }
         }
     }
 
     // External rate-limiter usage fallback observations
-    output.push_str("\n# TYPE bot_defence_rate_limiter_usage_fallback_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_rate_limiter_usage_fallback_total External rate-limiter usage read fallback observations by route class and reason\n",
+    let family = snapshot.family(
+        "bot_defence_rate_limiter_usage_fallback_total",
+        MetricKind::Counter,
+        Some("External rate-limiter usage read fallback observations by route class and reason"),
     );
     for route_class in RATE_LIMITER_ROUTE_CLASSES {
         for reason in RATE_LIMITER_USAGE_FALLBACK_REASONS {
@@ -756,17 +1553,15 @@ pub fn render_metrics(store: &Store) -> String {
                 METRICS_PREFIX, route_class, reason
             );
             let count = get_counter(store, &key);
-            output.push_str(&format!(
-                "bot_defence_rate_limiter_usage_fallback_total{{route_class=\"{}\",reason=\"{}\"}} {}\n",
-                route_class, reason, count
-            ));
+            family.point(&[("route_class", &route_class), ("reason", &reason)], count);
         }
     }
 
     // External/local distributed state drift observations
-    output.push_str("\n# TYPE bot_defence_rate_limiter_state_drift_observations_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_rate_limiter_state_drift_observations_total Observed absolute drift bands between external distributed and local shadow rate counters\n",
+    let family = snapshot.family(
+        "bot_defence_rate_limiter_state_drift_observations_total",
+        MetricKind::Counter,
+        Some("Observed absolute drift bands between external distributed and local shadow rate counters"),
     );
     for route_class in RATE_LIMITER_ROUTE_CLASSES {
         for band in RATE_LIMITER_DRIFT_BANDS {
@@ -775,46 +1570,50 @@ pub fn render_metrics(store: &Store) -> String {
                 METRICS_PREFIX, route_class, band
             );
             let count = get_counter(store, &key);
-            output.push_str(&format!(
-                "bot_defence_rate_limiter_state_drift_observations_total{{route_class=\"{}\",delta_band=\"{}\"}} {}\n",
-                route_class, band, count
-            ));
+            family.point(
+                &[("route_class", &route_class), ("delta_band", &band)],
+                count,
+            );
         }
     }
 
     // Canonical policy matches
-    output.push_str("\n# TYPE bot_defence_policy_matches_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_policy_matches_total Canonical policy match observations by escalation level, action, and detection ID\n",
+    let family = snapshot.family(
+        "bot_defence_policy_matches_total",
+        MetricKind::Counter,
+        Some("Canonical policy match observations by escalation level, action, and detection ID"),
     );
     for (label, count) in collect_labeled_counters(store, MetricName::PolicyMatches) {
         let mut parts = label.splitn(3, ':');
         let level = parts.next().unwrap_or("unknown");
         let action = parts.next().unwrap_or("unknown");
         let detection = parts.next().unwrap_or("unknown");
-        output.push_str(&format!(
-            "bot_defence_policy_matches_total{{level=\"{}\",action=\"{}\",detection=\"{}\"}} {}\n",
-            level, action, detection, count
-        ));
+        family.point(
+            &[
+                ("level", &level),
+                ("action", &action),
+                ("detection", &detection),
+            ],
+            count,
+        );
     }
 
     // Canonical signal observations
-    output.push_str("\n# TYPE bot_defence_policy_signals_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_policy_signals_total Canonical signal ID observations across policy decisions\n",
+    let family = snapshot.family(
+        "bot_defence_policy_signals_total",
+        MetricKind::Counter,
+        Some("Canonical signal ID observations across policy decisions"),
     );
     for (signal, count) in collect_labeled_counters(store, MetricName::PolicySignals) {
-        output.push_str(&format!(
-            "bot_defence_policy_signals_total{{signal=\"{}\"}} {}\n",
-            signal, count
-        ));
+        family.point(&[("signal", &signal)], count);
     }
 
     let monitoring_summary = crate::observability::monitoring::summarize_metrics_window(store);
 
-    output.push_str("\n# TYPE bot_defence_monitoring_challenge_failures_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_monitoring_challenge_failures_total Monitoring challenge failures by normalized reason\n",
+    let family = snapshot.family(
+        "bot_defence_monitoring_challenge_failures_total",
+        MetricKind::Counter,
+        Some("Monitoring challenge failures by normalized reason"),
     );
     for reason in MONITORING_CHALLENGE_FAILURE_REASON_KEYS {
         let count = monitoring_summary
@@ -823,15 +1622,13 @@ pub fn render_metrics(store: &Store) -> String {
             .get(reason)
             .copied()
             .unwrap_or(0);
-        output.push_str(&format!(
-            "bot_defence_monitoring_challenge_failures_total{{reason=\"{}\"}} {}\n",
-            reason, count
-        ));
+        family.point(&[("reason", &reason)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_monitoring_pow_verifications_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_monitoring_pow_verifications_total Monitoring PoW verification outcomes\n",
+    let family = snapshot.family(
+        "bot_defence_monitoring_pow_verifications_total",
+        MetricKind::Counter,
+        Some("Monitoring PoW verification outcomes"),
     );
     for outcome in MONITORING_POW_OUTCOME_KEYS {
         let count = monitoring_summary
@@ -840,15 +1637,13 @@ pub fn render_metrics(store: &Store) -> String {
             .get(outcome)
             .copied()
             .unwrap_or(0);
-        output.push_str(&format!(
-            "bot_defence_monitoring_pow_verifications_total{{outcome=\"{}\"}} {}\n",
-            outcome, count
-        ));
+        family.point(&[("outcome", &outcome)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_monitoring_pow_failures_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_monitoring_pow_failures_total Monitoring PoW verification failures by normalized reason\n",
+    let family = snapshot.family(
+        "bot_defence_monitoring_pow_failures_total",
+        MetricKind::Counter,
+        Some("Monitoring PoW verification failures by normalized reason"),
     );
     for reason in MONITORING_POW_FAILURE_REASON_KEYS {
         let count = monitoring_summary
@@ -857,15 +1652,13 @@ pub fn render_metrics(store: &Store) -> String {
             .get(reason)
             .copied()
             .unwrap_or(0);
-        output.push_str(&format!(
-            "bot_defence_monitoring_pow_failures_total{{reason=\"{}\"}} {}\n",
-            reason, count
-        ));
+        family.point(&[("reason", &reason)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_monitoring_rate_violations_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_monitoring_rate_violations_total Monitoring rate-limit violations by normalized outcome\n",
+    let family = snapshot.family(
+        "bot_defence_monitoring_rate_violations_total",
+        MetricKind::Counter,
+        Some("Monitoring rate-limit violations by normalized outcome"),
     );
     for outcome in MONITORING_RATE_OUTCOME_KEYS {
         let count = monitoring_summary
@@ -874,48 +1667,76 @@ pub fn render_metrics(store: &Store) -> String {
             .get(outcome)
             .copied()
             .unwrap_or(0);
-        output.push_str(&format!(
-            "bot_defence_monitoring_rate_violations_total{{outcome=\"{}\"}} {}\n",
-            outcome, count
-        ));
+        family.point(&[("outcome", &outcome)], count);
     }
 
-    output.push_str("\n# TYPE bot_defence_monitoring_geo_violations_total counter\n");
-    output.push_str(
-        "# HELP bot_defence_monitoring_geo_violations_total Monitoring GEO policy violations by normalized action\n",
+    let family = snapshot.family(
+        "bot_defence_monitoring_geo_violations_total",
+        MetricKind::Counter,
+        Some("Monitoring GEO policy violations by normalized action"),
     );
     for action in MONITORING_GEO_ACTION_KEYS {
         let count = monitoring_summary.geo.actions.get(action).copied().unwrap_or(0);
-        output.push_str(&format!(
-            "bot_defence_monitoring_geo_violations_total{{action=\"{}\"}} {}\n",
-            action, count
-        ));
+        family.point(&[("action", &action)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_monitoring_tls_fingerprint_hits_total",
+        MetricKind::Counter,
+        Some("Monitoring JA3/JA4 reputation matches by configured action"),
+    );
+    for action in MONITORING_TLS_FINGERPRINT_ACTION_KEYS {
+        let count = monitoring_summary
+            .tls_fingerprint
+            .actions
+            .get(action)
+            .copied()
+            .unwrap_or(0);
+        family.point(&[("action", &action)], count);
+    }
+
+    let family = snapshot.family(
+        "bot_defence_monitoring_ai_crawler_hits_total",
+        MetricKind::Counter,
+        Some("Monitoring self-declared AI crawler requests by applied action"),
+    );
+    for action in MONITORING_AI_CRAWLER_ACTION_KEYS {
+        let count = monitoring_summary
+            .ai_crawler
+            .actions
+            .get(action)
+            .copied()
+            .unwrap_or(0);
+        family.point(&[("action", &action)], count);
     }
 
     // Active bans (gauge)
-    output.push_str("\n# TYPE bot_defence_active_bans gauge\n");
-    output.push_str("# HELP bot_defence_active_bans Current number of active (non-expired) bans\n");
+    let family = snapshot.family(
+        "bot_defence_active_bans",
+        MetricKind::Gauge,
+        Some("Current number of active (non-expired) bans"),
+    );
     let active_bans = count_active_bans(store);
-    output.push_str(&format!("bot_defence_active_bans {}\n", active_bans));
+    family.point(&[], active_bans);
+
+    collect_histograms(store, &mut snapshot);
 
     // Test mode enabled (gauge, 0 or 1)
-    output.push_str("\n# TYPE bot_defence_test_mode_enabled gauge\n");
+    let family = snapshot.family("bot_defence_test_mode_enabled", MetricKind::Gauge, None);
     let test_mode_enabled = crate::config::load_runtime_cached(store, "default")
         .map(|cfg| if cfg.test_mode { 1 } else { 0 })
         .unwrap_or(0);
-    output.push_str(&format!(
-        "bot_defence_test_mode_enabled {}\n",
-        test_mode_enabled
-    ));
+    family.point(&[], test_mode_enabled);
 
-    output
+    snapshot
 }
 
 /// Handle GET /metrics endpoint
-pub fn handle_metrics(store: &Store) -> spin_sdk::http::Response {
+pub fn handle_metrics<S: crate::challenge::KeyValueStore>(store: &S) -> spin_sdk::http::Response {
     if crate::config::load_runtime_cached(store, "default").is_err() {
         return spin_sdk::http::Response::new(500, "Configuration unavailable");
     }
+    flush_buffers(store);
     let body = render_metrics(store);
     spin_sdk::http::Response::builder()
         .status(200)
@@ -923,3 +1744,156 @@ pub fn handle_metrics(store: &Store) -> spin_sdk::http::Response {
         .body(body)
         .build()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::KeyValueStore;
+    use crate::test_support::InMemoryStore;
+
+    #[test]
+    fn observe_accumulates_into_a_single_record_per_series() {
+        let store = InMemoryStore::default();
+        observe(&store, HistogramName::BotnessScore, None, 0.0);
+        observe(&store, HistogramName::BotnessScore, None, 3.0);
+        observe(&store, HistogramName::BotnessScore, None, 3.0);
+        observe(&store, HistogramName::BotnessScore, None, 42.0);
+
+        let record = load_histogram(
+            &store,
+            HistogramName::BotnessScore,
+            "metrics:histogram:botness_score",
+        );
+        assert_eq!(record.count, 4);
+        assert_eq!(record.sum, 48.0);
+        assert_eq!(record.buckets.len(), BOTNESS_SCORE_BUCKETS.len() + 1);
+        assert_eq!(record.buckets[0], 1);
+        assert_eq!(record.buckets[3], 2);
+        assert_eq!(record.buckets[BOTNESS_SCORE_BUCKETS.len()], 1);
+    }
+
+    #[test]
+    fn observe_drops_unknown_labels_and_invalid_values() {
+        let store = InMemoryStore::default();
+        observe(&store, HistogramName::ChallengeSolveSeconds, Some("captcha"), 4.0);
+        observe(&store, HistogramName::ChallengeSolveSeconds, None, 4.0);
+        observe(&store, HistogramName::PowSolveSeconds, Some("puzzle"), 4.0);
+        observe(&store, HistogramName::PowSolveSeconds, None, f64::NAN);
+        observe(&store, HistogramName::PowSolveSeconds, None, -1.0);
+
+        assert!(store
+            .get("metrics:histogram:challenge_solve_seconds:captcha")
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get("metrics:histogram:challenge_solve_seconds")
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get("metrics:histogram:pow_solve_seconds")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn histograms_render_cumulative_buckets_sum_and_count() {
+        let store = InMemoryStore::default();
+        observe(&store, HistogramName::ChallengeSolveSeconds, Some("puzzle"), 4.0);
+        observe(&store, HistogramName::ChallengeSolveSeconds, Some("puzzle"), 12.5);
+        observe(&store, HistogramName::ChallengeSolveSeconds, Some("puzzle"), 900.0);
+        observe(&store, HistogramName::RequestDurationSeconds, None, 0.004);
+
+        let output = render_metrics(&store);
+
+        assert!(output.contains("# TYPE bot_defence_challenge_solve_seconds histogram\n"));
+        assert!(output.contains(
+            "bot_defence_challenge_solve_seconds_bucket{challenge=\"puzzle\",le=\"3\"} 0\n"
+        ));
+        assert!(output.contains(
+            "bot_defence_challenge_solve_seconds_bucket{challenge=\"puzzle\",le=\"5\"} 1\n"
+        ));
+        assert!(output.contains(
+            "bot_defence_challenge_solve_seconds_bucket{challenge=\"puzzle\",le=\"300\"} 2\n"
+        ));
+        assert!(output.contains(
+            "bot_defence_challenge_solve_seconds_bucket{challenge=\"puzzle\",le=\"+Inf\"} 3\n"
+        ));
+        assert!(output
+            .contains("bot_defence_challenge_solve_seconds_sum{challenge=\"puzzle\"} 916.5\n"));
+        assert!(output
+            .contains("bot_defence_challenge_solve_seconds_count{challenge=\"not_a_bot\"} 0\n"));
+        assert!(output.contains("bot_defence_request_duration_seconds_bucket{le=\"0.0025\"} 0\n"));
+        assert!(output.contains("bot_defence_request_duration_seconds_bucket{le=\"0.005\"} 1\n"));
+        assert!(output.contains("bot_defence_botness_score_count 0\n"));
+    }
+
+    #[test]
+    fn buffered_observations_merge_into_the_stored_record() {
+        let store = InMemoryStore::default();
+        observe(&store, HistogramName::PowSolveSeconds, None, 2.0);
+        let mut delta = empty_histogram_record(HistogramName::PowSolveSeconds);
+        add_observation(&mut delta, HistogramName::PowSolveSeconds, 2.0);
+        add_observation(&mut delta, HistogramName::PowSolveSeconds, 4.0);
+        merge_histogram(
+            &store,
+            HistogramName::PowSolveSeconds,
+            "metrics:histogram:pow_solve_seconds".to_string(),
+            delta,
+        );
+
+        let record = load_histogram(
+            &store,
+            HistogramName::PowSolveSeconds,
+            "metrics:histogram:pow_solve_seconds",
+        );
+        assert_eq!(record.count, 3);
+        assert_eq!(record.sum, 8.0);
+        assert_eq!(record.buckets[1], 2);
+        assert_eq!(record.buckets[3], 1);
+    }
+
+    #[test]
+    fn enqueued_observations_wait_for_the_flush_threshold() {
+        // A key no real histogram uses, so other tests' flushes never pick it up.
+        let key = "metrics:histogram:test_enqueue_threshold";
+        for _ in 1..FLUSH_VALUE_THRESHOLD {
+            assert!(!enqueue_observation(
+                HistogramName::PowSolveSeconds,
+                key.to_string(),
+                2.0
+            ));
+        }
+        assert!(enqueue_observation(
+            HistogramName::PowSolveSeconds,
+            key.to_string(),
+            2.0
+        ));
+
+        let (histogram, delta) = HISTOGRAM_BUFFER.lock().unwrap().remove(key).unwrap();
+        let store = InMemoryStore::default();
+        merge_histogram(&store, histogram, key.to_string(), delta);
+        let record = load_histogram(&store, HistogramName::PowSolveSeconds, key);
+        assert_eq!(record.count, FLUSH_VALUE_THRESHOLD);
+        assert_eq!(record.sum, 2.0 * FLUSH_VALUE_THRESHOLD as f64);
+    }
+
+    #[test]
+    fn bucket_layout_mismatch_resets_the_series() {
+        let store = InMemoryStore::default();
+        store
+            .set(
+                "metrics:histogram:pow_solve_seconds",
+                br#"{"buckets":[1,2],"count":3,"sum":9.0}"#,
+            )
+            .unwrap();
+        observe(&store, HistogramName::PowSolveSeconds, None, 2.0);
+
+        let record = load_histogram(
+            &store,
+            HistogramName::PowSolveSeconds,
+            "metrics:histogram:pow_solve_seconds",
+        );
+        assert_eq!(record.count, 1);
+        assert_eq!(record.buckets[1], 1);
+    }
+}
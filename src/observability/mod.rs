@@ -1,2 +1,6 @@
+pub(crate) mod alerts;
+pub(crate) mod decision_trace;
 pub(crate) mod metrics;
 pub(crate) mod monitoring;
+pub(crate) mod otel;
+pub(crate) mod outbound;
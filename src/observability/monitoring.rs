@@ -36,6 +36,8 @@ const NOT_A_BOT_OUTCOME_KEYS: [&str; 4] = ["pass", "escalate", "fail", "replay"]
 const NOT_A_BOT_SOLVE_MS_BUCKET_KEYS: [&str; 4] = ["lt_1s", "1_3s", "3_10s", "10s_plus"];
 const RATE_OUTCOME_KEYS: [&str; 4] = ["limited", "banned", "fallback_allow", "fallback_deny"];
 const GEO_ACTION_KEYS: [&str; 3] = ["block", "challenge", "maze"];
+const TLS_FINGERPRINT_ACTION_KEYS: [&str; 3] = ["allow", "score", "block"];
+const AI_CRAWLER_ACTION_KEYS: [&str; 4] = ["allow", "block", "maze", "payment_required"];
 
 #[cfg(not(test))]
 static LAST_MONITORING_CLEANUP_HOUR: Lazy<Mutex<u64>> = Lazy::new(|| Mutex::new(0));
@@ -109,6 +111,22 @@ pub(crate) struct GeoSummary {
     pub top_countries: Vec<CountEntry>,
 }
 
+#[derive(Debug, Clone, Serialize, Default)]
+pub(crate) struct TlsFingerprintSummary {
+    pub total_hits: u64,
+    pub actions: BTreeMap<String, u64>,
+    pub top_fingerprints: Vec<CountEntry>,
+    pub top_rules: Vec<CountEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub(crate) struct AiCrawlerSummary {
+    pub total_hits: u64,
+    pub actions: BTreeMap<String, u64>,
+    pub categories: BTreeMap<String, u64>,
+    pub top_bots: Vec<CountEntry>,
+}
+
 #[derive(Debug, Clone, Serialize, Default)]
 pub(crate) struct NotABotSummary {
     pub served: u64,
@@ -133,6 +151,8 @@ pub(crate) struct MonitoringSummary {
     pub pow: PowSummary,
     pub rate: RateSummary,
     pub geo: GeoSummary,
+    pub tls_fingerprint: TlsFingerprintSummary,
+    pub ai_crawler: AiCrawlerSummary,
 }
 
 fn now_ts() -> u64 {
@@ -272,6 +292,23 @@ fn normalize_geo_action(action: &str) -> &'static str {
     }
 }
 
+fn normalize_tls_fingerprint_action(action: &str) -> &'static str {
+    match action {
+        "allow" => "allow",
+        "block" => "block",
+        _ => "score",
+    }
+}
+
+fn normalize_ai_crawler_action(action: &str) -> &'static str {
+    match action {
+        "block" => "block",
+        "maze" => "maze",
+        "payment_required" => "payment_required",
+        _ => "allow",
+    }
+}
+
 fn normalize_not_a_bot_outcome(outcome: &str) -> &'static str {
     match outcome {
         "pass" => "pass",
@@ -306,7 +343,7 @@ fn parse_counter_bytes(bytes: Vec<u8>) -> u64 {
         .unwrap_or(0)
 }
 
-fn read_counter<S: crate::challenge::KeyValueStore>(store: &S, key: &str) -> u64 {
+fn read_counter<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, key: &str) -> u64 {
     store
         .get(key)
         .ok()
@@ -316,7 +353,7 @@ fn read_counter<S: crate::challenge::KeyValueStore>(store: &S, key: &str) -> u64
 }
 
 #[cfg(not(test))]
-fn flush_counter_deltas<S: crate::challenge::KeyValueStore>(
+fn flush_counter_deltas<S: crate::challenge::KeyValueStore + ?Sized>(
     store: &S,
     deltas: HashMap<String, u64>,
 ) {
@@ -333,7 +370,10 @@ fn flush_counter_deltas<S: crate::challenge::KeyValueStore>(
 }
 
 #[cfg(not(test))]
-fn maybe_flush_pending_counter_buffer<S: crate::challenge::KeyValueStore>(store: &S, force: bool) {
+fn maybe_flush_pending_counter_buffer<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    force: bool,
+) {
     let now = now_ts();
     let pending = {
         let mut buffer = PENDING_COUNTER_BUFFER.lock().unwrap();
@@ -356,7 +396,7 @@ fn maybe_flush_pending_counter_buffer<S: crate::challenge::KeyValueStore>(store:
 }
 
 #[cfg(test)]
-fn increment_counter<S: crate::challenge::KeyValueStore>(store: &S, key: &str) {
+fn increment_counter<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, key: &str) {
     let current = read_counter(store, key);
     let next = current.saturating_add(1);
     if let Err(err) = store.set(key, next.to_string().as_bytes()) {
@@ -365,7 +405,7 @@ fn increment_counter<S: crate::challenge::KeyValueStore>(store: &S, key: &str) {
 }
 
 #[cfg(not(test))]
-fn increment_counter<S: crate::challenge::KeyValueStore>(store: &S, key: &str) {
+fn increment_counter<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, key: &str) {
     let now = now_ts();
     {
         let mut buffer = PENDING_COUNTER_BUFFER.lock().unwrap();
@@ -456,7 +496,7 @@ fn maybe_cleanup_monitoring<S: crate::challenge::KeyValueStore>(store: &S, curre
     cleanup_monitoring_keys(store, cutoff);
 }
 
-fn record_with_dimension<S: crate::challenge::KeyValueStore>(
+fn record_with_dimension<S: crate::challenge::KeyValueStore + ?Sized>(
     store: &S,
     section: &str,
     metric: &str,
@@ -526,7 +566,10 @@ pub(crate) fn record_rate_violation_with_path<S: crate::challenge::KeyValueStore
     }
 }
 
-pub(crate) fn record_rate_outcome<S: crate::challenge::KeyValueStore>(store: &S, outcome: &str) {
+pub(crate) fn record_rate_outcome<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    outcome: &str,
+) {
     let normalized_outcome = normalize_rate_outcome(outcome);
     record_with_dimension(store, "rate", "outcome", Some(normalized_outcome));
 }
@@ -543,6 +586,42 @@ pub(crate) fn record_geo_violation<S: crate::challenge::KeyValueStore>(
     record_with_dimension(store, "geo", "country", Some(normalized_country.as_str()));
 }
 
+/// Records a JA3/JA4 reputation match keyed by the matched fingerprint and the rule or set that listed it.
+pub(crate) fn record_tls_fingerprint_hit<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    details: &crate::signals::tls_reputation::MatchDetails,
+) {
+    let normalized_action = normalize_tls_fingerprint_action(details.action.as_str());
+    let fingerprint = format!("{}:{}", details.kind.as_str(), details.fingerprint);
+    let rule = format!("{}:{}", details.source.as_str(), details.source_id);
+    record_with_dimension(store, "tls_fingerprint", "total", None);
+    record_with_dimension(store, "tls_fingerprint", "action", Some(normalized_action));
+    record_with_dimension(store, "tls_fingerprint", "fingerprint", Some(fingerprint.as_str()));
+    record_with_dimension(store, "tls_fingerprint", "rule", Some(rule.as_str()));
+}
+
+/// Records a request from a self-declared AI crawler with the action the policy applied.
+pub(crate) fn record_ai_crawler_hit<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    decision: &crate::crawler_policy::declared::Decision,
+) {
+    let normalized_action = normalize_ai_crawler_action(decision.action.as_str());
+    record_with_dimension(store, "ai_crawler", "total", None);
+    record_with_dimension(store, "ai_crawler", "action", Some(normalized_action));
+    record_with_dimension(
+        store,
+        "ai_crawler",
+        "category",
+        Some(decision.crawler.category.as_str()),
+    );
+    record_with_dimension(
+        store,
+        "ai_crawler",
+        "bot",
+        Some(decision.crawler.bot.as_str()),
+    );
+}
+
 pub(crate) fn record_not_a_bot_served<S: crate::challenge::KeyValueStore>(store: &S) {
     record_with_dimension(store, "not_a_bot", "served", None);
 }
@@ -662,6 +741,16 @@ pub(crate) fn summarize_with_store<S: crate::challenge::KeyValueStore>(
     let mut geo_actions: HashMap<String, u64> = HashMap::new();
     let mut geo_countries: HashMap<String, u64> = HashMap::new();
 
+    let mut tls_fingerprint_total = 0u64;
+    let mut tls_fingerprint_actions: HashMap<String, u64> = HashMap::new();
+    let mut tls_fingerprint_counts: HashMap<String, u64> = HashMap::new();
+    let mut tls_fingerprint_rules: HashMap<String, u64> = HashMap::new();
+
+    let mut ai_crawler_total = 0u64;
+    let mut ai_crawler_actions: HashMap<String, u64> = HashMap::new();
+    let mut ai_crawler_categories: BTreeMap<String, u64> = BTreeMap::new();
+    let mut ai_crawler_bots: HashMap<String, u64> = HashMap::new();
+
     if let Ok(keys) = store.get_keys() {
         for key in keys {
             if !key.starts_with(MONITORING_PREFIX) {
@@ -809,6 +898,50 @@ pub(crate) fn summarize_with_store<S: crate::challenge::KeyValueStore>(
                     }
                     _ => {}
                 },
+                "tls_fingerprint" => match metric.as_str() {
+                    "total" => tls_fingerprint_total = tls_fingerprint_total.saturating_add(count),
+                    "action" => {
+                        if let Some(dim) = dimension {
+                            let entry = tls_fingerprint_actions.entry(dim).or_insert(0);
+                            *entry = entry.saturating_add(count);
+                        }
+                    }
+                    "fingerprint" => {
+                        if let Some(dim) = dimension {
+                            let entry = tls_fingerprint_counts.entry(dim).or_insert(0);
+                            *entry = entry.saturating_add(count);
+                        }
+                    }
+                    "rule" => {
+                        if let Some(dim) = dimension {
+                            let entry = tls_fingerprint_rules.entry(dim).or_insert(0);
+                            *entry = entry.saturating_add(count);
+                        }
+                    }
+                    _ => {}
+                },
+                "ai_crawler" => match metric.as_str() {
+                    "total" => ai_crawler_total = ai_crawler_total.saturating_add(count),
+                    "action" => {
+                        if let Some(dim) = dimension {
+                            let entry = ai_crawler_actions.entry(dim).or_insert(0);
+                            *entry = entry.saturating_add(count);
+                        }
+                    }
+                    "category" => {
+                        if let Some(dim) = dimension {
+                            let entry = ai_crawler_categories.entry(dim).or_insert(0);
+                            *entry = entry.saturating_add(count);
+                        }
+                    }
+                    "bot" => {
+                        if let Some(dim) = dimension {
+                            let entry = ai_crawler_bots.entry(dim).or_insert(0);
+                            *entry = entry.saturating_add(count);
+                        }
+                    }
+                    _ => {}
+                },
                 _ => {}
             }
         }
@@ -873,6 +1006,18 @@ pub(crate) fn summarize_with_store<S: crate::challenge::KeyValueStore>(
         *entry = entry.saturating_add(value);
     }
 
+    let mut tls_fingerprint_action_map = build_seeded_map(&TLS_FINGERPRINT_ACTION_KEYS);
+    for (key, value) in tls_fingerprint_actions {
+        let entry = tls_fingerprint_action_map.entry(key).or_insert(0);
+        *entry = entry.saturating_add(value);
+    }
+
+    let mut ai_crawler_action_map = build_seeded_map(&AI_CRAWLER_ACTION_KEYS);
+    for (key, value) in ai_crawler_actions {
+        let entry = ai_crawler_action_map.entry(key).or_insert(0);
+        *entry = entry.saturating_add(value);
+    }
+
     MonitoringSummary {
         generated_at: now,
         hours,
@@ -924,6 +1069,18 @@ pub(crate) fn summarize_with_store<S: crate::challenge::KeyValueStore>(
             actions: geo_action_map,
             top_countries: top_entries(&geo_countries, top_limit),
         },
+        tls_fingerprint: TlsFingerprintSummary {
+            total_hits: tls_fingerprint_total,
+            actions: tls_fingerprint_action_map,
+            top_fingerprints: top_entries(&tls_fingerprint_counts, top_limit),
+            top_rules: top_entries(&tls_fingerprint_rules, top_limit),
+        },
+        ai_crawler: AiCrawlerSummary {
+            total_hits: ai_crawler_total,
+            actions: ai_crawler_action_map,
+            categories: ai_crawler_categories,
+            top_bots: top_entries(&ai_crawler_bots, top_limit),
+        },
     }
 }
 
@@ -1273,6 +1430,78 @@ mod tests {
         assert_eq!(summary.pow.outcomes.get("failure").copied().unwrap_or(0), 3);
     }
 
+    #[test]
+    fn summarize_aggregates_tls_fingerprint_hits() {
+        let store = MockStore::default();
+        let hit = crate::signals::tls_reputation::MatchDetails {
+            source: crate::signals::tls_reputation::MatchSource::ManagedSet,
+            source_id: "go_net_http".to_string(),
+            action: crate::config::TlsFingerprintAction::Block,
+            kind: crate::signals::tls_reputation::FingerprintKind::Ja4,
+            fingerprint: "t13d190900_9dc949149365_97f8aa674fd9".to_string(),
+        };
+        record_tls_fingerprint_hit(&store, &hit);
+        record_tls_fingerprint_hit(&store, &hit);
+
+        let summary = summarize_with_store(&store, 24, 10);
+        assert_eq!(summary.tls_fingerprint.total_hits, 2);
+        assert_eq!(summary.tls_fingerprint.actions.get("block").copied(), Some(2));
+        assert_eq!(summary.tls_fingerprint.actions.get("score").copied(), Some(0));
+        assert_eq!(
+            summary
+                .tls_fingerprint
+                .top_fingerprints
+                .first()
+                .map(|v| (v.label.as_str(), v.count)),
+            Some(("ja4:t13d190900_9dc949149365_97f8aa674fd9", 2))
+        );
+        assert_eq!(
+            summary
+                .tls_fingerprint
+                .top_rules
+                .first()
+                .map(|v| (v.label.as_str(), v.count)),
+            Some(("managed:go_net_http", 2))
+        );
+    }
+
+    #[test]
+    fn summarize_aggregates_ai_crawler_hits() {
+        let store = MockStore::default();
+        let gptbot = crate::crawler_policy::declared::Decision {
+            crawler: crate::crawler_policy::declared::DeclaredCrawler {
+                bot: "GPTBot".to_string(),
+                category: crate::crawler_policy::declared::CrawlerCategory::AiTraining,
+            },
+            action: crate::config::AiCrawlerAction::Block,
+        };
+        let perplexity = crate::crawler_policy::declared::Decision {
+            crawler: crate::crawler_policy::declared::DeclaredCrawler {
+                bot: "PerplexityBot".to_string(),
+                category: crate::crawler_policy::declared::CrawlerCategory::AiSearch,
+            },
+            action: crate::config::AiCrawlerAction::Allow,
+        };
+        record_ai_crawler_hit(&store, &gptbot);
+        record_ai_crawler_hit(&store, &gptbot);
+        record_ai_crawler_hit(&store, &perplexity);
+
+        let summary = summarize_with_store(&store, 24, 10);
+        assert_eq!(summary.ai_crawler.total_hits, 3);
+        assert_eq!(summary.ai_crawler.actions.get("block").copied(), Some(2));
+        assert_eq!(summary.ai_crawler.actions.get("allow").copied(), Some(1));
+        assert_eq!(summary.ai_crawler.actions.get("maze").copied(), Some(0));
+        assert_eq!(summary.ai_crawler.categories.get("ai_training").copied(), Some(2));
+        assert_eq!(
+            summary
+                .ai_crawler
+                .top_bots
+                .first()
+                .map(|v| (v.label.as_str(), v.count)),
+            Some(("GPTBot", 2))
+        );
+    }
+
     #[test]
     fn summarize_enforces_top_limit_and_window_bounds() {
         let store = MockStore::default();
@@ -0,0 +1,768 @@
+// src/observability/otel.rs
+// OpenTelemetry export over OTLP/HTTP (JSON encoding).
+// Spans cover each request pipeline stage and are buffered until the response has been
+// sent; metrics encode the same snapshot rendered at /metrics so both surfaces agree.
+
+use rand::random;
+use serde_json::{json, Value};
+use spin_sdk::http::Response;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::challenge::KeyValueStore;
+use crate::observability::metrics::{MetricKind, MetricPoint, MetricValue, MetricsSnapshot};
+use crate::observability::outbound::{JsonPostTransport, SpinJsonPostTransport};
+
+const SERVICE_NAME: &str = "shuma-gorath";
+const SCOPE_NAME: &str = "shuma_gorath.pipeline";
+const TRACES_PATH: &str = "/v1/traces";
+const METRICS_PATH: &str = "/v1/metrics";
+const METRICS_LAST_EXPORT_KEY: &str = "otel:metrics:last_export";
+// Finished traces held for the post-response flush; the oldest are dropped beyond this.
+const MAX_PENDING_TRACES: usize = 256;
+const SPAN_KIND_INTERNAL: u8 = 1;
+const SPAN_KIND_SERVER: u8 = 2;
+// OTLP AggregationTemporality::CUMULATIVE; KV counters never reset.
+const AGGREGATION_TEMPORALITY_CUMULATIVE: u8 = 2;
+
+/// Collector settings resolved from env-only `SHUMA_OTEL_*` variables.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OtelExportSettings {
+    pub endpoint: String,
+    pub headers: Vec<(String, String)>,
+    pub traces_sample_ratio: f64,
+    pub metrics_interval_seconds: u64,
+}
+
+impl OtelExportSettings {
+    /// Returns `None` when no collector endpoint is configured (export disabled).
+    pub(crate) fn from_env() -> Option<Self> {
+        Some(Self {
+            endpoint: crate::config::otel_exporter_otlp_endpoint()?,
+            headers: crate::config::otel_exporter_otlp_headers(),
+            traces_sample_ratio: crate::config::otel_traces_sample_ratio(),
+            metrics_interval_seconds: crate::config::otel_metrics_export_interval_seconds(),
+        })
+    }
+
+    fn url(&self, signal_path: &str) -> String {
+        format!("{}{}", self.endpoint, signal_path)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AttributeValue {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl AttributeValue {
+    fn to_otlp(&self) -> Value {
+        match self {
+            AttributeValue::Str(value) => json!({ "stringValue": value }),
+            AttributeValue::Int(value) => json!({ "intValue": value.to_string() }),
+            AttributeValue::Bool(value) => json!({ "boolValue": value }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SpanRecord {
+    pub span_id: [u8; 8],
+    pub name: String,
+    pub start_unix_nanos: u64,
+    pub end_unix_nanos: u64,
+    pub attributes: Vec<(&'static str, AttributeValue)>,
+}
+
+/// Per-request span collector. Unsampled traces record nothing, so the
+/// pipeline can call into it unconditionally.
+#[derive(Debug)]
+pub(crate) struct RequestTrace {
+    sampled: bool,
+    trace_id: [u8; 16],
+    root_span_id: [u8; 8],
+    start_unix_nanos: u64,
+    method: String,
+    path: String,
+    decided_by: Option<&'static str>,
+    attributes: Vec<(&'static str, AttributeValue)>,
+    stages: Vec<SpanRecord>,
+}
+
+impl RequestTrace {
+    pub(crate) fn start(method: &str, path: &str, sample_ratio: f64) -> Self {
+        let sampled = sample_ratio >= 1.0 || (sample_ratio > 0.0 && random::<f64>() < sample_ratio);
+        Self {
+            sampled,
+            trace_id: random(),
+            root_span_id: random(),
+            start_unix_nanos: now_unix_nanos(),
+            method: method.to_string(),
+            path: path.to_string(),
+            decided_by: None,
+            attributes: Vec::new(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// A trace that never records; used when export is disabled.
+    pub(crate) fn disabled() -> Self {
+        Self::start("", "", 0.0)
+    }
+
+    /// Attach a request-level attribute to the root span.
+    pub(crate) fn set_attribute(&mut self, key: &'static str, value: AttributeValue) {
+        if self.sampled {
+            self.attributes.push((key, value));
+        }
+    }
+
+    /// Run one `maybe_handle_*` stage inside a child span. The span records
+    /// whether the stage let the request continue or produced the response.
+    pub(crate) fn stage<F>(&mut self, name: &'static str, run: F) -> Option<Response>
+    where
+        F: FnOnce() -> Option<Response>,
+    {
+        if !self.sampled {
+            return run();
+        }
+        let start_unix_nanos = now_unix_nanos();
+        let response = run();
+        let end_unix_nanos = now_unix_nanos();
+        let mut attributes = vec![("shuma.stage", AttributeValue::Str(name.to_string()))];
+        match response.as_ref() {
+            Some(response) => {
+                attributes.push(("shuma.verdict", AttributeValue::Str("respond".to_string())));
+                attributes.push((
+                    "http.response.status_code",
+                    AttributeValue::Int(*response.status() as i64),
+                ));
+                self.decided_by = Some(name);
+            }
+            None => {
+                attributes.push(("shuma.verdict", AttributeValue::Str("continue".to_string())));
+            }
+        }
+        self.stages.push(SpanRecord {
+            span_id: random(),
+            name: format!("shuma.{}", name),
+            start_unix_nanos,
+            end_unix_nanos: end_unix_nanos.max(start_unix_nanos),
+            attributes,
+        });
+        response
+    }
+
+    /// Close the root span. Returns `None` for unsampled traces.
+    pub(crate) fn finish(self, status: u16) -> Option<FinishedTrace> {
+        if !self.sampled {
+            return None;
+        }
+        let end_unix_nanos = now_unix_nanos().max(self.start_unix_nanos);
+        let mut attributes = vec![
+            ("http.request.method", AttributeValue::Str(self.method)),
+            ("url.path", AttributeValue::Str(self.path)),
+            (
+                "http.response.status_code",
+                AttributeValue::Int(status as i64),
+            ),
+            (
+                "shuma.decided_by",
+                AttributeValue::Str(self.decided_by.unwrap_or("pipeline").to_string()),
+            ),
+        ];
+        attributes.extend(self.attributes);
+        Some(FinishedTrace {
+            trace_id: self.trace_id,
+            root: SpanRecord {
+                span_id: self.root_span_id,
+                name: "shuma.request".to_string(),
+                start_unix_nanos: self.start_unix_nanos,
+                end_unix_nanos,
+                attributes,
+            },
+            stages: self.stages,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FinishedTrace {
+    pub trace_id: [u8; 16],
+    pub root: SpanRecord,
+    pub stages: Vec<SpanRecord>,
+}
+
+static PENDING_TRACES: Mutex<Vec<FinishedTrace>> = Mutex::new(Vec::new());
+
+fn now_unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn attributes_to_otlp(attributes: &[(&str, AttributeValue)]) -> Value {
+    Value::Array(
+        attributes
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": value.to_otlp() }))
+            .collect(),
+    )
+}
+
+fn resource() -> Value {
+    json!({
+        "attributes": attributes_to_otlp(&[
+            ("service.name", AttributeValue::Str(SERVICE_NAME.to_string())),
+            (
+                "service.version",
+                AttributeValue::Str(env!("CARGO_PKG_VERSION").to_string()),
+            ),
+        ])
+    })
+}
+
+fn scope() -> Value {
+    json!({ "name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION") })
+}
+
+fn span_to_otlp(trace_id: &str, span: &SpanRecord, parent: Option<&[u8; 8]>, kind: u8) -> Value {
+    let mut value = json!({
+        "traceId": trace_id,
+        "spanId": hex(&span.span_id),
+        "name": span.name,
+        "kind": kind,
+        "startTimeUnixNano": span.start_unix_nanos.to_string(),
+        "endTimeUnixNano": span.end_unix_nanos.to_string(),
+        "attributes": attributes_to_otlp(&span.attributes),
+    });
+    if let Some(parent) = parent {
+        value["parentSpanId"] = Value::String(hex(parent));
+    }
+    value
+}
+
+/// Encode finished traces as one `ExportTraceServiceRequest` JSON body.
+pub(crate) fn encode_traces(traces: &[FinishedTrace]) -> Value {
+    let mut spans = Vec::new();
+    for trace in traces {
+        let trace_id = hex(&trace.trace_id);
+        spans.push(span_to_otlp(
+            trace_id.as_str(),
+            &trace.root,
+            None,
+            SPAN_KIND_SERVER,
+        ));
+        spans.extend(trace.stages.iter().map(|stage| {
+            span_to_otlp(
+                trace_id.as_str(),
+                stage,
+                Some(&trace.root.span_id),
+                SPAN_KIND_INTERNAL,
+            )
+        }));
+    }
+    json!({
+        "resourceSpans": [{
+            "resource": resource(),
+            "scopeSpans": [{ "scope": scope(), "spans": spans }]
+        }]
+    })
+}
+
+fn label_attributes(labels: &[(&'static str, String)]) -> Value {
+    let attributes: Vec<(&str, AttributeValue)> = labels
+        .iter()
+        .map(|(name, value)| (*name, AttributeValue::Str(value.clone())))
+        .collect();
+    attributes_to_otlp(&attributes)
+}
+
+fn data_point(point: &MetricPoint, time_unix_nanos: u64) -> Value {
+    let mut value = json!({
+        "attributes": label_attributes(&point.labels),
+        "timeUnixNano": time_unix_nanos.to_string(),
+    });
+    match &point.value {
+        MetricValue::Int(int) => value["asInt"] = Value::String(int.to_string()),
+        MetricValue::Histogram {
+            bounds,
+            buckets,
+            sum,
+            count,
+        } => {
+            value["count"] = Value::String(count.to_string());
+            value["sum"] = json!(sum);
+            value["bucketCounts"] = json!(buckets
+                .iter()
+                .map(|bucket| bucket.to_string())
+                .collect::<Vec<_>>());
+            value["explicitBounds"] = json!(bounds);
+        }
+    }
+    value
+}
+
+/// Encode a metrics snapshot as an `ExportMetricsServiceRequest` JSON body.
+/// Counters become cumulative monotonic sums, histograms become cumulative OTLP
+/// histograms, and gauges stay gauges.
+pub(crate) fn encode_metrics(snapshot: &MetricsSnapshot, time_unix_nanos: u64) -> Value {
+    let metrics: Vec<Value> = snapshot
+        .families
+        .iter()
+        .filter(|family| !family.points.is_empty())
+        .map(|family| {
+            let mut metric = json!({ "name": family.name });
+            if let Some(help) = family.help {
+                metric["description"] = Value::String(help.to_string());
+            }
+            let data_points: Vec<Value> = family
+                .points
+                .iter()
+                .map(|point| data_point(point, time_unix_nanos))
+                .collect();
+            match family.kind {
+                MetricKind::Counter => {
+                    metric["sum"] = json!({
+                        "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                        "isMonotonic": true,
+                        "dataPoints": data_points,
+                    });
+                }
+                MetricKind::Histogram => {
+                    metric["histogram"] = json!({
+                        "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                        "dataPoints": data_points,
+                    });
+                }
+                MetricKind::Gauge => metric["gauge"] = json!({ "dataPoints": data_points }),
+            }
+            metric
+        })
+        .collect();
+    json!({
+        "resourceMetrics": [{
+            "resource": resource(),
+            "scopeMetrics": [{ "scope": scope(), "metrics": metrics }]
+        }]
+    })
+}
+
+fn post(
+    transport: &impl JsonPostTransport,
+    settings: &OtelExportSettings,
+    signal_path: &str,
+    payload: &Value,
+) -> Result<(), String> {
+    let body = serde_json::to_vec(payload).map_err(|err| err.to_string())?;
+    let url = settings.url(signal_path);
+    let status = transport.post_json(url.as_str(), &settings.headers, body)?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("collector returned HTTP {} for {}", status, url))
+    }
+}
+
+pub(crate) fn export_traces(
+    transport: &impl JsonPostTransport,
+    settings: &OtelExportSettings,
+    traces: &[FinishedTrace],
+) -> Result<(), String> {
+    post(transport, settings, TRACES_PATH, &encode_traces(traces))
+}
+
+/// Push the counter snapshot at most once per `metrics_interval_seconds`.
+/// The last-export marker lives in KV so the throttle holds across instances.
+/// Returns `Ok(true)` when a snapshot was sent.
+pub(crate) fn maybe_export_metrics<S, T, F>(
+    store: &S,
+    transport: &T,
+    settings: &OtelExportSettings,
+    now_unix_seconds: u64,
+    collect: F,
+) -> Result<bool, String>
+where
+    S: KeyValueStore,
+    T: JsonPostTransport,
+    F: FnOnce() -> MetricsSnapshot,
+{
+    let last_export = store
+        .get(METRICS_LAST_EXPORT_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .unwrap_or(0);
+    if last_export > 0
+        && now_unix_seconds < last_export.saturating_add(settings.metrics_interval_seconds)
+    {
+        return Ok(false);
+    }
+    // Claim the window before sending so concurrent requests do not all export.
+    store
+        .set(
+            METRICS_LAST_EXPORT_KEY,
+            now_unix_seconds.to_string().as_bytes(),
+        )
+        .map_err(|_| "failed to record metrics export marker".to_string())?;
+    let payload = encode_metrics(&collect(), now_unix_seconds.saturating_mul(1_000_000_000));
+    post(transport, settings, METRICS_PATH, &payload)?;
+    Ok(true)
+}
+
+/// Close the request trace and hold it for `flush_pending`. No I/O happens here, so
+/// collector latency never reaches the request path.
+pub(crate) fn record_request(
+    settings: Option<&OtelExportSettings>,
+    trace: RequestTrace,
+    status: u16,
+) {
+    if settings.is_none() {
+        return;
+    }
+    let Some(finished) = trace.finish(status) else {
+        return;
+    };
+    let mut pending = PENDING_TRACES.lock().unwrap();
+    if pending.len() >= MAX_PENDING_TRACES {
+        pending.remove(0);
+    }
+    pending.push(finished);
+}
+
+/// Send every buffered trace in one collector request. Traces are dropped on failure
+/// rather than retried, so a down collector cannot grow the buffer.
+pub(crate) fn flush_pending_traces(
+    transport: &impl JsonPostTransport,
+    settings: &OtelExportSettings,
+) -> Result<usize, String> {
+    let traces = std::mem::take(&mut *PENDING_TRACES.lock().unwrap());
+    if traces.is_empty() {
+        return Ok(0);
+    }
+    export_traces(transport, settings, &traces)?;
+    Ok(traces.len())
+}
+
+/// Export buffered traces and, when due, a metrics snapshot. Called by the entrypoint
+/// after the response has been sent; failures are logged and never affect the response.
+pub(crate) fn flush_pending(settings: Option<&OtelExportSettings>) {
+    let Some(settings) = settings else {
+        return;
+    };
+    let transport = SpinJsonPostTransport;
+    if let Err(err) = flush_pending_traces(&transport, settings) {
+        eprintln!("[otel] trace export failed: {}", err);
+    }
+    let Ok(store) = spin_sdk::key_value::Store::open_default() else {
+        return;
+    };
+    let now = crate::admin::now_ts();
+    if let Err(err) = maybe_export_metrics(&store, &transport, settings, now, || {
+        crate::observability::metrics::collect_metrics(&store)
+    }) {
+        eprintln!("[otel] metrics export failed: {}", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::InMemoryStore;
+    use std::cell::RefCell;
+
+    type CollectedRequest = (String, Vec<(String, String)>, Value);
+
+    #[derive(Default)]
+    struct StubCollector {
+        status: u16,
+        requests: RefCell<Vec<CollectedRequest>>,
+    }
+
+    impl JsonPostTransport for StubCollector {
+        fn post_json(
+            &self,
+            url: &str,
+            headers: &[(String, String)],
+            body: Vec<u8>,
+        ) -> Result<u16, String> {
+            let payload = serde_json::from_slice(&body).map_err(|err| err.to_string())?;
+            self.requests
+                .borrow_mut()
+                .push((url.to_string(), headers.to_vec(), payload));
+            Ok(self.status)
+        }
+    }
+
+    fn settings() -> OtelExportSettings {
+        OtelExportSettings {
+            endpoint: "http://127.0.0.1:4318".to_string(),
+            headers: vec![("authorization".to_string(), "Bearer t".to_string())],
+            traces_sample_ratio: 1.0,
+            metrics_interval_seconds: 60,
+        }
+    }
+
+    fn attribute<'a>(span: &'a Value, key: &str) -> Option<&'a Value> {
+        span["attributes"]
+            .as_array()?
+            .iter()
+            .find(|attribute| attribute["key"] == key)
+            .map(|attribute| &attribute["value"])
+    }
+
+    #[test]
+    fn stage_spans_record_verdicts_under_the_request_span() {
+        let mut trace = RequestTrace::start("GET", "/login", 1.0);
+        assert!(trace.stage("ip_range_policy", || None).is_none());
+        let response = trace.stage("rate_limit", || Some(Response::new(429, "slow down")));
+        assert_eq!(response.map(|response| *response.status()), Some(429));
+
+        let finished = trace.finish(429).expect("sampled trace");
+        let payload = encode_traces(&[finished]);
+        let spans = payload["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0]["name"], "shuma.request");
+        assert_eq!(spans[0]["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(
+            attribute(&spans[0], "shuma.decided_by"),
+            Some(&json!({ "stringValue": "rate_limit" }))
+        );
+        assert_eq!(spans[1]["name"], "shuma.ip_range_policy");
+        assert_eq!(spans[1]["parentSpanId"], spans[0]["spanId"]);
+        assert_eq!(
+            attribute(&spans[1], "shuma.verdict"),
+            Some(&json!({ "stringValue": "continue" }))
+        );
+        assert_eq!(
+            attribute(&spans[2], "http.response.status_code"),
+            Some(&json!({ "intValue": "429" }))
+        );
+    }
+
+    #[test]
+    fn unsampled_traces_still_run_stages_but_export_nothing() {
+        let mut trace = RequestTrace::start("GET", "/", 0.0);
+        let response = trace.stage("honeypot", || Some(Response::new(403, "no")));
+        assert!(response.is_some());
+        assert!(trace.finish(403).is_none());
+        assert!(RequestTrace::disabled().finish(200).is_none());
+    }
+
+    #[test]
+    fn snapshot_counters_and_gauges_map_to_otlp_sums_and_gauges() {
+        let mut snapshot = MetricsSnapshot::default();
+        let bans = snapshot.family(
+            "bot_defence_bans_total",
+            MetricKind::Counter,
+            Some("Total number of IP bans by reason"),
+        );
+        bans.point(&[("reason", &"honeypot")], 3);
+        bans.point(&[("reason", &"rate_limit"), ("note", &"a \"q\", b")], 0);
+        snapshot
+            .family("bot_defence_active_bans", MetricKind::Gauge, None)
+            .point(&[], 2);
+        let payload = encode_metrics(&snapshot, 5_000_000_000);
+        let metrics = payload["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .unwrap();
+
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0]["name"], "bot_defence_bans_total");
+        assert_eq!(
+            metrics[0]["description"],
+            "Total number of IP bans by reason"
+        );
+        assert_eq!(metrics[0]["sum"]["isMonotonic"], true);
+        let points = metrics[0]["sum"]["dataPoints"].as_array().unwrap();
+        assert_eq!(points[0]["asInt"], "3");
+        assert_eq!(points[0]["timeUnixNano"], "5000000000");
+        assert_eq!(
+            points[1]["attributes"][1],
+            json!({ "key": "note", "value": { "stringValue": "a \"q\", b" } })
+        );
+        assert_eq!(metrics[1]["gauge"]["dataPoints"][0]["asInt"], "2");
+    }
+
+    #[test]
+    fn snapshot_histograms_map_to_otlp_histograms_per_label_set() {
+        let store = InMemoryStore::default();
+        for seconds in [0.5, 2.0, 4.0, 24.0] {
+            crate::observability::metrics::observe(
+                &store,
+                crate::observability::metrics::HistogramName::ChallengeSolveSeconds,
+                Some("puzzle"),
+                seconds,
+            );
+        }
+        let snapshot = crate::observability::metrics::collect_metrics(&store);
+        let payload = encode_metrics(&snapshot, 1);
+        let metrics = payload["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .unwrap();
+        let histogram = &metrics
+            .iter()
+            .find(|metric| metric["name"] == "bot_defence_challenge_solve_seconds")
+            .unwrap()["histogram"];
+
+        assert_eq!(histogram["aggregationTemporality"], 2);
+        let points = histogram["dataPoints"].as_array().unwrap();
+        let puzzle = points
+            .iter()
+            .find(|point| {
+                point["attributes"]
+                    == json!([{ "key": "challenge", "value": { "stringValue": "puzzle" } }])
+            })
+            .unwrap();
+        let bounds = puzzle["explicitBounds"].as_array().unwrap();
+        let buckets = puzzle["bucketCounts"].as_array().unwrap();
+        assert_eq!(buckets.len(), bounds.len() + 1);
+        assert_eq!(bounds[0], 1.0);
+        assert_eq!(buckets[0], "1");
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|bucket| bucket.as_str().unwrap().parse::<u64>().unwrap())
+                .sum::<u64>(),
+            4
+        );
+        assert_eq!(puzzle["count"], "4");
+        assert_eq!(puzzle["sum"], 30.5);
+    }
+
+    #[test]
+    fn exports_post_to_collector_signal_paths_with_headers() {
+        let collector = StubCollector {
+            status: 200,
+            ..StubCollector::default()
+        };
+        let mut trace = RequestTrace::start("GET", "/", 1.0);
+        trace.stage("botness", || None);
+        export_traces(&collector, &settings(), &[trace.finish(200).unwrap()]).unwrap();
+
+        let requests = collector.requests.borrow();
+        assert_eq!(requests[0].0, "http://127.0.0.1:4318/v1/traces");
+        assert_eq!(
+            requests[0].1,
+            vec![("authorization".to_string(), "Bearer t".to_string())]
+        );
+        assert_eq!(
+            requests[0].2["resourceSpans"][0]["resource"]["attributes"][0]["value"]["stringValue"],
+            SERVICE_NAME
+        );
+    }
+
+    #[test]
+    fn metrics_export_is_throttled_by_kv_marker() {
+        let store = InMemoryStore::default();
+        let collector = StubCollector {
+            status: 200,
+            ..StubCollector::default()
+        };
+        let render = || {
+            let mut snapshot = MetricsSnapshot::default();
+            snapshot
+                .family("bot_defence_requests_total", MetricKind::Counter, None)
+                .point(&[], 7);
+            snapshot
+        };
+
+        assert_eq!(
+            maybe_export_metrics(&store, &collector, &settings(), 1_000, render),
+            Ok(true)
+        );
+        assert_eq!(
+            maybe_export_metrics(&store, &collector, &settings(), 1_030, render),
+            Ok(false)
+        );
+        assert_eq!(
+            maybe_export_metrics(&store, &collector, &settings(), 1_060, render),
+            Ok(true)
+        );
+
+        let requests = collector.requests.borrow();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].0, "http://127.0.0.1:4318/v1/metrics");
+        assert_eq!(
+            requests[0].2["resourceMetrics"][0]["scopeMetrics"][0]["metrics"][0]["sum"]
+                ["dataPoints"][0]["asInt"],
+            "7"
+        );
+    }
+
+    #[test]
+    fn collector_errors_surface_as_export_failures() {
+        let collector = StubCollector {
+            status: 503,
+            ..StubCollector::default()
+        };
+        let store = InMemoryStore::default();
+        let result = maybe_export_metrics(
+            &store,
+            &collector,
+            &settings(),
+            1_000,
+            MetricsSnapshot::default,
+        );
+        assert!(result.unwrap_err().contains("HTTP 503"));
+    }
+
+    #[test]
+    fn request_traces_are_buffered_and_flushed_in_one_collector_request() {
+        let _lock = crate::test_support::lock_env();
+        PENDING_TRACES.lock().unwrap().clear();
+        let collector = StubCollector {
+            status: 200,
+            ..StubCollector::default()
+        };
+
+        record_request(None, RequestTrace::start("GET", "/skipped", 1.0), 200);
+        record_request(
+            Some(&settings()),
+            RequestTrace::start("GET", "/a", 1.0),
+            200,
+        );
+        record_request(
+            Some(&settings()),
+            RequestTrace::start("GET", "/b", 0.0),
+            200,
+        );
+        record_request(
+            Some(&settings()),
+            RequestTrace::start("POST", "/c", 1.0),
+            403,
+        );
+        assert!(collector.requests.borrow().is_empty());
+
+        assert_eq!(flush_pending_traces(&collector, &settings()), Ok(2));
+        assert_eq!(flush_pending_traces(&collector, &settings()), Ok(0));
+
+        let requests = collector.requests.borrow();
+        assert_eq!(requests.len(), 1);
+        let spans = requests[0].2["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        let paths: Vec<&Value> = spans
+            .iter()
+            .filter_map(|span| attribute(span, "url.path"))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                &json!({ "stringValue": "/a" }),
+                &json!({ "stringValue": "/c" })
+            ]
+        );
+    }
+}
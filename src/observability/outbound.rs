@@ -0,0 +1,41 @@
+// src/observability/outbound.rs
+// Outbound JSON delivery shared by the OTLP exporter and the alert webhook notifier.
+
+use spin_sdk::http::{Method, Request, Response};
+
+/// Delivery seam so exporters and notifiers can be exercised against a stub receiver.
+pub(crate) trait JsonPostTransport {
+    /// POST a JSON payload and return the receiver's HTTP status.
+    fn post_json(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: Vec<u8>,
+    ) -> Result<u16, String>;
+}
+
+/// Outbound transport via Spin HTTP. The receiver origin must be listed in
+/// `allowed_outbound_hosts` for the `bot-defence` component.
+pub(crate) struct SpinJsonPostTransport;
+
+impl JsonPostTransport for SpinJsonPostTransport {
+    fn post_json(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+        body: Vec<u8>,
+    ) -> Result<u16, String> {
+        let mut builder = Request::builder();
+        builder
+            .method(Method::Post)
+            .uri(url)
+            .header("content-type", "application/json");
+        for (name, value) in headers {
+            builder.header(name.as_str(), value.as_str());
+        }
+        let request = builder.body(body).build();
+        spin_sdk::http::run(spin_sdk::http::send::<_, Response>(request))
+            .map(|response| *response.status())
+            .map_err(|err| err.to_string())
+    }
+}
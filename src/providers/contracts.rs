@@ -1,5 +1,4 @@
 use spin_sdk::http::{Request, Response};
-use spin_sdk::key_value::Store;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum RateLimitDecision {
@@ -17,10 +16,15 @@ impl RateLimitDecision {
 }
 
 pub(crate) trait RateLimiterProvider {
-    fn current_rate_usage(&self, store: &Store, site_id: &str, ip: &str) -> u32;
+    fn current_rate_usage(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        site_id: &str,
+        ip: &str,
+    ) -> u32;
     fn check_rate_limit(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
         ip: &str,
         limit: u32,
@@ -45,22 +49,27 @@ impl BanSyncResult {
 }
 
 pub(crate) trait BanStoreProvider {
-    fn is_banned(&self, store: &Store, site_id: &str, ip: &str) -> bool;
+    fn is_banned(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        site_id: &str,
+        ip: &str,
+    ) -> bool;
     fn list_active_bans(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
     ) -> Vec<(String, crate::enforcement::ban::BanEntry)>;
     fn ban_ip_with_fingerprint(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
         ip: &str,
         reason: &str,
         duration_secs: u64,
         fingerprint: Option<crate::enforcement::ban::BanFingerprint>,
     );
-    fn unban_ip(&self, store: &Store, site_id: &str, ip: &str);
+    fn unban_ip(&self, store: &dyn crate::challenge::KeyValueStore, site_id: &str, ip: &str);
 
     fn sync_ban(&self, _site_id: &str, _ip: &str) -> BanSyncResult {
         BanSyncResult::Deferred
@@ -74,13 +83,13 @@ pub(crate) trait BanStoreProvider {
 pub(crate) trait ChallengeEngineProvider {
     fn puzzle_path(&self) -> &'static str;
     fn not_a_bot_path(&self) -> &'static str;
-    fn render_challenge(&self, req: &Request, transform_count: usize) -> Response;
+    fn render_challenge(&self, req: &Request, cfg: &crate::config::Config) -> Response;
     fn render_not_a_bot(&self, req: &Request, cfg: &crate::config::Config) -> Response;
     fn serve_challenge_page(
         &self,
         req: &Request,
         test_mode: bool,
-        transform_count: usize,
+        cfg: &crate::config::Config,
     ) -> Response;
     fn serve_not_a_bot_page(
         &self,
@@ -90,12 +99,13 @@ pub(crate) trait ChallengeEngineProvider {
     ) -> Response;
     fn handle_challenge_submit_with_outcome(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         req: &Request,
+        cfg: &crate::config::Config,
     ) -> (Response, crate::challenge::ChallengeSubmitOutcome);
     fn handle_not_a_bot_submit_with_outcome(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         req: &Request,
         cfg: &crate::config::Config,
     ) -> crate::challenge::NotABotSubmitResult;
@@ -104,7 +114,7 @@ pub(crate) trait ChallengeEngineProvider {
         ip: &str,
         user_agent: &str,
         enabled: bool,
-        difficulty: u8,
+        params: crate::challenge::pow::PowParams,
         ttl_seconds: u64,
     ) -> Response;
     fn handle_pow_verify(&self, req: &Request, ip: &str, enabled: bool) -> Response;
@@ -115,7 +125,7 @@ pub(crate) trait MazeTarpitProvider {
     fn serve_maze_with_tracking(
         &self,
         req: &Request,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         cfg: &crate::config::Config,
         ip: &str,
         user_agent: &str,
@@ -128,7 +138,7 @@ pub(crate) trait MazeTarpitProvider {
     fn maybe_handle_tarpit(
         &self,
         _req: &Request,
-        _store: &Store,
+        _store: &dyn crate::challenge::KeyValueStore,
         _cfg: &crate::config::Config,
         _site_id: &str,
         _ip: &str,
@@ -143,27 +153,37 @@ pub(crate) trait FingerprintSignalProvider {
         &self,
         cfg: &crate::config::Config,
     ) -> crate::signals::botness::SignalAvailability;
-    fn handle_report(&self, store: &Store, req: &Request) -> Response;
+    fn handle_report(&self, store: &dyn crate::challenge::KeyValueStore, req: &Request)
+        -> Response;
     fn detection_script(&self) -> &'static str;
-    fn report_script(&self, report_endpoint: &str) -> String;
-    fn inject_detection(&self, html: &str, report_endpoint: Option<&str>) -> String;
+    fn report_script(&self, report_endpoint: &str, report_nonce: &str) -> String;
+    fn inject_detection(
+        &self,
+        html: &str,
+        report_endpoint: Option<&str>,
+        report_nonce: &str,
+    ) -> String;
 }
 
 #[cfg(test)]
 mod tests {
     use super::{BanStoreProvider, BanSyncResult, RateLimitDecision};
-    use spin_sdk::key_value::Store;
 
     struct StubBanStoreProvider;
 
     impl BanStoreProvider for StubBanStoreProvider {
-        fn is_banned(&self, _store: &Store, _site_id: &str, _ip: &str) -> bool {
+        fn is_banned(
+            &self,
+            _store: &dyn crate::challenge::KeyValueStore,
+            _site_id: &str,
+            _ip: &str,
+        ) -> bool {
             false
         }
 
         fn list_active_bans(
             &self,
-            _store: &Store,
+            _store: &dyn crate::challenge::KeyValueStore,
             _site_id: &str,
         ) -> Vec<(String, crate::enforcement::ban::BanEntry)> {
             Vec::new()
@@ -171,7 +191,7 @@ mod tests {
 
         fn ban_ip_with_fingerprint(
             &self,
-            _store: &Store,
+            _store: &dyn crate::challenge::KeyValueStore,
             _site_id: &str,
             _ip: &str,
             _reason: &str,
@@ -180,7 +200,13 @@ mod tests {
         ) {
         }
 
-        fn unban_ip(&self, _store: &Store, _site_id: &str, _ip: &str) {}
+        fn unban_ip(
+            &self,
+            _store: &dyn crate::challenge::KeyValueStore,
+            _site_id: &str,
+            _ip: &str,
+        ) {
+        }
     }
 
     #[test]
@@ -1,7 +1,5 @@
 use serde::Deserialize;
 use spin_sdk::http::{Request, Response};
-use spin_sdk::key_value::Store;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::contracts::{
     BanStoreProvider, BanSyncResult, ChallengeEngineProvider, FingerprintSignalProvider,
@@ -285,10 +283,7 @@ impl DistributedRateCounter for RedisDistributedRateCounter {
 }
 
 fn now_ts() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
+    crate::runtime::clock::now_ts()
 }
 
 fn current_window_key(site_id: &str, ip: &str, window: u64) -> String {
@@ -342,7 +337,10 @@ fn rate_drift_band(delta: u32) -> &'static str {
     }
 }
 
-fn record_rate_backend_error_metric(store: &Store, route_class: &str) {
+fn record_rate_backend_error_metric(
+    store: &dyn crate::challenge::KeyValueStore,
+    route_class: &str,
+) {
     crate::observability::metrics::increment(
         store,
         crate::observability::metrics::MetricName::RateLimiterBackendErrors,
@@ -351,7 +349,7 @@ fn record_rate_backend_error_metric(store: &Store, route_class: &str) {
 }
 
 fn record_rate_outage_decision_metric(
-    store: &Store,
+    store: &dyn crate::challenge::KeyValueStore,
     route_class: &str,
     outage_mode: crate::config::RateLimiterOutageMode,
     action: RateLimiterOutageAction,
@@ -377,7 +375,11 @@ fn record_rate_outage_decision_metric(
     }
 }
 
-fn record_rate_usage_fallback_metric(store: &Store, route_class: &str, reason: &str) {
+fn record_rate_usage_fallback_metric(
+    store: &dyn crate::challenge::KeyValueStore,
+    route_class: &str,
+    reason: &str,
+) {
     let label = format!("{}:{}", route_class, reason);
     crate::observability::metrics::increment(
         store,
@@ -386,7 +388,11 @@ fn record_rate_usage_fallback_metric(store: &Store, route_class: &str, reason: &
     );
 }
 
-fn record_rate_drift_metric(store: &Store, route_class: &str, delta: u32) {
+fn record_rate_drift_metric(
+    store: &dyn crate::challenge::KeyValueStore,
+    route_class: &str,
+    delta: u32,
+) {
     let band = rate_drift_band(delta);
     let label = format!("{}:{}", route_class, band);
     crate::observability::metrics::increment(
@@ -453,7 +459,12 @@ fn check_rate_limit_with_backend<B: DistributedRateCounter>(
 }
 
 impl RateLimiterProvider for ExternalRateLimiterProvider {
-    fn current_rate_usage(&self, store: &Store, site_id: &str, ip: &str) -> u32 {
+    fn current_rate_usage(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        site_id: &str,
+        ip: &str,
+    ) -> u32 {
         let route_class = rate_route_class(site_id);
         let distributed_backend = RedisDistributedRateCounter::from_env();
         let Some(backend) = distributed_backend.as_ref() else {
@@ -478,7 +489,7 @@ impl RateLimiterProvider for ExternalRateLimiterProvider {
 
     fn check_rate_limit(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
         ip: &str,
         limit: u32,
@@ -807,7 +818,12 @@ fn unban_with_backend<B: DistributedBanStore>(
 }
 
 impl BanStoreProvider for ExternalBanStoreProvider {
-    fn is_banned(&self, store: &Store, site_id: &str, ip: &str) -> bool {
+    fn is_banned(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        site_id: &str,
+        ip: &str,
+    ) -> bool {
         let distributed_backend = RedisDistributedBanStore::from_env();
         is_banned_with_backend(distributed_backend.as_ref(), site_id, ip, || {
             internal::BAN_STORE.is_banned(store, site_id, ip)
@@ -816,7 +832,7 @@ impl BanStoreProvider for ExternalBanStoreProvider {
 
     fn list_active_bans(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
     ) -> Vec<(String, crate::enforcement::ban::BanEntry)> {
         let distributed_backend = RedisDistributedBanStore::from_env();
@@ -827,7 +843,7 @@ impl BanStoreProvider for ExternalBanStoreProvider {
 
     fn ban_ip_with_fingerprint(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
         ip: &str,
         reason: &str,
@@ -855,7 +871,7 @@ impl BanStoreProvider for ExternalBanStoreProvider {
         );
     }
 
-    fn unban_ip(&self, store: &Store, site_id: &str, ip: &str) {
+    fn unban_ip(&self, store: &dyn crate::challenge::KeyValueStore, site_id: &str, ip: &str) {
         let distributed_backend = RedisDistributedBanStore::from_env();
         unban_with_backend(distributed_backend.as_ref(), site_id, ip, || {
             internal::BAN_STORE.unban_ip(store, site_id, ip)
@@ -888,8 +904,8 @@ impl ChallengeEngineProvider for UnsupportedExternalChallengeEngineProvider {
         internal::CHALLENGE_ENGINE.not_a_bot_path()
     }
 
-    fn render_challenge(&self, req: &Request, transform_count: usize) -> Response {
-        internal::CHALLENGE_ENGINE.render_challenge(req, transform_count)
+    fn render_challenge(&self, req: &Request, cfg: &crate::config::Config) -> Response {
+        internal::CHALLENGE_ENGINE.render_challenge(req, cfg)
     }
 
     fn render_not_a_bot(&self, req: &Request, cfg: &crate::config::Config) -> Response {
@@ -900,9 +916,9 @@ impl ChallengeEngineProvider for UnsupportedExternalChallengeEngineProvider {
         &self,
         req: &Request,
         test_mode: bool,
-        transform_count: usize,
+        cfg: &crate::config::Config,
     ) -> Response {
-        internal::CHALLENGE_ENGINE.serve_challenge_page(req, test_mode, transform_count)
+        internal::CHALLENGE_ENGINE.serve_challenge_page(req, test_mode, cfg)
     }
 
     fn serve_not_a_bot_page(
@@ -916,15 +932,16 @@ impl ChallengeEngineProvider for UnsupportedExternalChallengeEngineProvider {
 
     fn handle_challenge_submit_with_outcome(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         req: &Request,
+        cfg: &crate::config::Config,
     ) -> (Response, crate::challenge::ChallengeSubmitOutcome) {
-        internal::CHALLENGE_ENGINE.handle_challenge_submit_with_outcome(store, req)
+        internal::CHALLENGE_ENGINE.handle_challenge_submit_with_outcome(store, req, cfg)
     }
 
     fn handle_not_a_bot_submit_with_outcome(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         req: &Request,
         cfg: &crate::config::Config,
     ) -> crate::challenge::NotABotSubmitResult {
@@ -936,14 +953,14 @@ impl ChallengeEngineProvider for UnsupportedExternalChallengeEngineProvider {
         ip: &str,
         user_agent: &str,
         enabled: bool,
-        difficulty: u8,
+        params: crate::challenge::pow::PowParams,
         ttl_seconds: u64,
     ) -> Response {
         internal::CHALLENGE_ENGINE.handle_pow_challenge(
             ip,
             user_agent,
             enabled,
-            difficulty,
+            params,
             ttl_seconds,
         )
     }
@@ -961,7 +978,7 @@ impl MazeTarpitProvider for UnsupportedExternalMazeTarpitProvider {
     fn serve_maze_with_tracking(
         &self,
         req: &Request,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         cfg: &crate::config::Config,
         ip: &str,
         user_agent: &str,
@@ -1000,7 +1017,11 @@ impl FingerprintSignalProvider for ExternalFingerprintSignalProvider {
         }
     }
 
-    fn handle_report(&self, store: &Store, req: &Request) -> Response {
+    fn handle_report(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        req: &Request,
+    ) -> Response {
         let cfg = match crate::config::load_runtime_cached(store, "default") {
             Ok(cfg) => cfg,
             Err(_) => return Response::new(500, "Configuration unavailable"),
@@ -1127,11 +1148,16 @@ impl FingerprintSignalProvider for ExternalFingerprintSignalProvider {
         ""
     }
 
-    fn report_script(&self, _report_endpoint: &str) -> String {
+    fn report_script(&self, _report_endpoint: &str, _report_nonce: &str) -> String {
         String::new()
     }
 
-    fn inject_detection(&self, html: &str, _report_endpoint: Option<&str>) -> String {
+    fn inject_detection(
+        &self,
+        html: &str,
+        _report_endpoint: Option<&str>,
+        _report_nonce: &str,
+    ) -> String {
         html.to_string()
     }
 }
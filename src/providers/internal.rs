@@ -1,5 +1,4 @@
 use spin_sdk::http::{Request, Response};
-use spin_sdk::key_value::Store;
 
 use super::contracts::{
     BanStoreProvider, ChallengeEngineProvider, FingerprintSignalProvider, MazeTarpitProvider,
@@ -21,13 +20,18 @@ pub(crate) const FINGERPRINT_SIGNAL: InternalFingerprintSignalProvider =
     InternalFingerprintSignalProvider;
 
 impl RateLimiterProvider for InternalRateLimiterProvider {
-    fn current_rate_usage(&self, store: &Store, site_id: &str, ip: &str) -> u32 {
+    fn current_rate_usage(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        site_id: &str,
+        ip: &str,
+    ) -> u32 {
         crate::signals::rate_pressure::current_rate_usage(store, site_id, ip)
     }
 
     fn check_rate_limit(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
         ip: &str,
         limit: u32,
@@ -41,13 +45,18 @@ impl RateLimiterProvider for InternalRateLimiterProvider {
 }
 
 impl BanStoreProvider for InternalBanStoreProvider {
-    fn is_banned(&self, store: &Store, site_id: &str, ip: &str) -> bool {
+    fn is_banned(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        site_id: &str,
+        ip: &str,
+    ) -> bool {
         crate::enforcement::ban::is_banned(store, site_id, ip)
     }
 
     fn list_active_bans(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
     ) -> Vec<(String, crate::enforcement::ban::BanEntry)> {
         crate::enforcement::ban::list_active_bans_with_scan(store, site_id)
@@ -55,7 +64,7 @@ impl BanStoreProvider for InternalBanStoreProvider {
 
     fn ban_ip_with_fingerprint(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         site_id: &str,
         ip: &str,
         reason: &str,
@@ -72,7 +81,7 @@ impl BanStoreProvider for InternalBanStoreProvider {
         );
     }
 
-    fn unban_ip(&self, store: &Store, site_id: &str, ip: &str) {
+    fn unban_ip(&self, store: &dyn crate::challenge::KeyValueStore, site_id: &str, ip: &str) {
         crate::enforcement::ban::unban_ip(store, site_id, ip);
     }
 }
@@ -86,8 +95,8 @@ impl ChallengeEngineProvider for InternalChallengeEngineProvider {
         crate::boundaries::challenge_not_a_bot_path()
     }
 
-    fn render_challenge(&self, req: &Request, transform_count: usize) -> Response {
-        crate::boundaries::render_challenge(req, transform_count)
+    fn render_challenge(&self, req: &Request, cfg: &crate::config::Config) -> Response {
+        crate::boundaries::render_challenge(req, cfg)
     }
 
     fn render_not_a_bot(&self, req: &Request, cfg: &crate::config::Config) -> Response {
@@ -98,9 +107,9 @@ impl ChallengeEngineProvider for InternalChallengeEngineProvider {
         &self,
         req: &Request,
         test_mode: bool,
-        transform_count: usize,
+        cfg: &crate::config::Config,
     ) -> Response {
-        crate::boundaries::serve_challenge_page(req, test_mode, transform_count)
+        crate::boundaries::serve_challenge_page(req, test_mode, cfg)
     }
 
     fn serve_not_a_bot_page(
@@ -114,15 +123,16 @@ impl ChallengeEngineProvider for InternalChallengeEngineProvider {
 
     fn handle_challenge_submit_with_outcome(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         req: &Request,
+        cfg: &crate::config::Config,
     ) -> (Response, crate::challenge::ChallengeSubmitOutcome) {
-        crate::boundaries::handle_challenge_submit_with_outcome(store, req)
+        crate::boundaries::handle_challenge_submit_with_outcome(store, req, cfg)
     }
 
     fn handle_not_a_bot_submit_with_outcome(
         &self,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         req: &Request,
         cfg: &crate::config::Config,
     ) -> crate::challenge::NotABotSubmitResult {
@@ -134,16 +144,10 @@ impl ChallengeEngineProvider for InternalChallengeEngineProvider {
         ip: &str,
         user_agent: &str,
         enabled: bool,
-        difficulty: u8,
+        params: crate::challenge::pow::PowParams,
         ttl_seconds: u64,
     ) -> Response {
-        crate::challenge::pow::handle_pow_challenge(
-            ip,
-            user_agent,
-            enabled,
-            difficulty,
-            ttl_seconds,
-        )
+        crate::challenge::pow::handle_pow_challenge(ip, user_agent, enabled, params, ttl_seconds)
     }
 
     fn handle_pow_verify(&self, req: &Request, ip: &str, enabled: bool) -> Response {
@@ -159,7 +163,7 @@ impl MazeTarpitProvider for InternalMazeTarpitProvider {
     fn serve_maze_with_tracking(
         &self,
         req: &Request,
-        store: &Store,
+        store: &dyn crate::challenge::KeyValueStore,
         cfg: &crate::config::Config,
         ip: &str,
         user_agent: &str,
@@ -198,7 +202,11 @@ impl FingerprintSignalProvider for InternalFingerprintSignalProvider {
         }
     }
 
-    fn handle_report(&self, store: &Store, req: &Request) -> Response {
+    fn handle_report(
+        &self,
+        store: &dyn crate::challenge::KeyValueStore,
+        req: &Request,
+    ) -> Response {
         crate::signals::cdp::handle_cdp_report(store, req)
     }
 
@@ -206,11 +214,16 @@ impl FingerprintSignalProvider for InternalFingerprintSignalProvider {
         crate::signals::cdp::get_cdp_detection_script()
     }
 
-    fn report_script(&self, report_endpoint: &str) -> String {
-        crate::signals::cdp::get_cdp_report_script(report_endpoint)
+    fn report_script(&self, report_endpoint: &str, report_nonce: &str) -> String {
+        crate::signals::cdp::get_cdp_report_script(report_endpoint, report_nonce)
     }
 
-    fn inject_detection(&self, html: &str, report_endpoint: Option<&str>) -> String {
-        crate::signals::cdp::inject_cdp_detection(html, report_endpoint)
+    fn inject_detection(
+        &self,
+        html: &str,
+        report_endpoint: Option<&str>,
+        report_nonce: &str,
+    ) -> String {
+        crate::signals::cdp::inject_cdp_detection(html, report_endpoint, report_nonce)
     }
 }
@@ -203,9 +203,13 @@ mod tests {
         assert_eq!(provider.report_path(), "/fingerprint-report");
         assert_eq!(provider.source_availability(&cfg).as_str(), "active");
         assert_eq!(provider.detection_script(), "");
-        assert_eq!(provider.report_script("/report-endpoint"), "");
+        assert_eq!(provider.report_script("/report-endpoint", "nonce"), "");
         assert_eq!(
-            provider.inject_detection("<html><body>ok</body></html>", Some("/report-endpoint")),
+            provider.inject_detection(
+                "<html><body>ok</body></html>",
+                Some("/report-endpoint"),
+                "nonce"
+            ),
             "<html><body>ok</body></html>"
         );
     }
@@ -0,0 +1,127 @@
+// Argument handling for the `shuma-replay` binary, kept in the library so it is
+// covered by the regular test run.
+
+use super::{compare, parse_log, render_text_report, LogFormat, ReplayError, ReplaySimulator};
+
+pub const USAGE: &str = "usage: shuma-replay --log <file> [--format auto|jsonl|clf] \
+[--config <baseline.json>] [--compare <candidate.json>] [--untrusted-headers] \
+[--flips <n>] [--json]";
+
+const DEFAULT_FLIP_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliArgs {
+    pub log_path: String,
+    pub format: LogFormat,
+    pub config_path: Option<String>,
+    pub compare_path: Option<String>,
+    pub trust_forwarded_headers: bool,
+    pub flip_limit: usize,
+    pub json: bool,
+}
+
+fn usage_error(message: &str) -> ReplayError {
+    ReplayError::Usage(format!("{}\n{}", message, USAGE))
+}
+
+pub fn parse_args(args: &[String]) -> Result<CliArgs, ReplayError> {
+    let mut log_path = None;
+    let mut format = LogFormat::Auto;
+    let mut config_path = None;
+    let mut compare_path = None;
+    let mut trust_forwarded_headers = true;
+    let mut flip_limit = DEFAULT_FLIP_LIMIT;
+    let mut json = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| usage_error(format!("{} requires a value", name).as_str()))
+        };
+        match arg.as_str() {
+            "--log" => log_path = Some(value("--log")?),
+            "--format" => {
+                let raw = value("--format")?;
+                format = LogFormat::parse(raw.as_str())
+                    .ok_or_else(|| usage_error(format!("unknown format '{}'", raw).as_str()))?;
+            }
+            "--config" => config_path = Some(value("--config")?),
+            "--compare" => compare_path = Some(value("--compare")?),
+            "--flips" => {
+                let raw = value("--flips")?;
+                flip_limit = raw
+                    .parse()
+                    .map_err(|_| usage_error(format!("invalid --flips '{}'", raw).as_str()))?;
+            }
+            "--untrusted-headers" => trust_forwarded_headers = false,
+            "--json" => json = true,
+            "-h" | "--help" => return Err(ReplayError::Usage(USAGE.to_string())),
+            other => {
+                return Err(usage_error(
+                    format!("unknown argument '{}'", other).as_str(),
+                ))
+            }
+        }
+    }
+
+    Ok(CliArgs {
+        log_path: log_path.ok_or_else(|| usage_error("--log is required"))?,
+        format,
+        config_path,
+        compare_path,
+        trust_forwarded_headers,
+        flip_limit,
+        json,
+    })
+}
+
+fn read_file(path: &str) -> Result<String, ReplayError> {
+    std::fs::read_to_string(path).map_err(|err| ReplayError::Io(format!("{}: {}", path, err)))
+}
+
+fn simulator(path: Option<&str>, args: &CliArgs) -> Result<ReplaySimulator, ReplayError> {
+    let simulator = match path {
+        Some(path) => ReplaySimulator::from_config_json(read_file(path)?.as_str())?,
+        None => ReplaySimulator::with_defaults(),
+    };
+    Ok(simulator.with_options(super::ReplayOptions {
+        trust_forwarded_headers: args.trust_forwarded_headers,
+    }))
+}
+
+/// Runs the replay described by `args` and returns the rendered report.
+pub fn run(args: &CliArgs) -> Result<String, ReplayError> {
+    // Replayed clearance cookies are signed with whatever SHUMA_JS_SECRET is set here.
+    crate::config::seed_required_env_from_defaults();
+    let records = parse_log(read_file(args.log_path.as_str())?.as_str(), args.format)?;
+    let baseline = simulator(args.config_path.as_deref(), args)?.replay(&records);
+    let baseline_summary = baseline.summary();
+
+    let candidate = match args.compare_path.as_deref() {
+        Some(path) => {
+            let run = simulator(Some(path), args)?.replay(&records);
+            let comparison = compare(&baseline, &run);
+            Some((run.summary(), comparison))
+        }
+        None => None,
+    };
+
+    if args.json {
+        let payload = serde_json::json!({
+            "baseline": baseline_summary,
+            "candidate": candidate.as_ref().map(|(summary, _)| summary),
+            "comparison": candidate.as_ref().map(|(_, comparison)| comparison),
+        });
+        return serde_json::to_string_pretty(&payload)
+            .map_err(|err| ReplayError::Io(err.to_string()));
+    }
+    Ok(render_text_report(
+        &baseline_summary,
+        candidate
+            .as_ref()
+            .map(|(summary, comparison)| (summary, comparison)),
+        args.flip_limit,
+    ))
+}
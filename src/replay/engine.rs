@@ -0,0 +1,119 @@
+use super::log::ReplayRecord;
+use super::store::ReplayStore;
+use super::{ReplayDecision, ReplayOptions};
+use crate::challenge::clearance::{self, ClearanceLevel};
+use crate::observability::decision_trace::DecisionDraft;
+use crate::runtime::clock::{with_clock, FixedClock};
+use crate::runtime::policy_taxonomy::{resolve_policy_match, PolicyTransition};
+
+fn passthrough(reason: &str, status: u16) -> ReplayDecision {
+    let policy_match = resolve_policy_match(PolicyTransition::AllowClean);
+    ReplayDecision {
+        reason: reason.to_string(),
+        status,
+        level: policy_match.level_id().to_string(),
+        action: policy_match.action_id().to_string(),
+        detection: policy_match.detection_id().to_string(),
+        botness_score: None,
+        signals: Vec::new(),
+    }
+}
+
+/// Logged clearance cookies were signed with the live keys, so any presented one is
+/// re-minted at the JS-verified level for the logged client. Higher levels are not assumed.
+fn remint_clearance_cookie(record: &ReplayRecord) -> ReplayRecord {
+    let mut record = record.clone();
+    let token = clearance::issue_token(
+        crate::signals::ip_identity::bucket_ip(record.ip.as_str()).as_str(),
+        crate::challenge::operation_envelope::user_agent_bucket(
+            record.header("user-agent").unwrap_or(""),
+        )
+        .as_str(),
+        ClearanceLevel::VerifyJs,
+        clearance::CLEARANCE_TTL_SECONDS,
+    );
+    for (name, value) in record.headers.iter_mut() {
+        if name != "cookie" {
+            continue;
+        }
+        *value = value
+            .split(';')
+            .map(|part| match part.trim().split_once('=') {
+                Some((cookie, _)) if cookie.trim() == clearance::CLEARANCE_COOKIE_NAME => {
+                    format!("{}={}", clearance::CLEARANCE_COOKIE_NAME, token)
+                }
+                _ => part.trim().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+    }
+    record
+}
+
+fn decision_from_draft(draft: Option<DecisionDraft>, status: u16) -> ReplayDecision {
+    let Some(draft) = draft else {
+        return passthrough("unclassified", status);
+    };
+    let mut decided = passthrough(draft.reason().unwrap_or("unclassified"), status);
+    if let Some(transition) = draft.last_transition() {
+        decided.level = transition.level.clone();
+        decided.action = transition.action.clone();
+        decided.detection = transition.detection.clone();
+    }
+    if let Some(botness) = draft.botness() {
+        decided.botness_score = Some(botness.score);
+        decided.signals = botness
+            .contributions
+            .iter()
+            .filter(|contribution| contribution.active)
+            .map(|contribution| format!("{}:{}", contribution.key, contribution.contribution))
+            .collect();
+    }
+    decided
+}
+
+/// Replays one request through `run_policy_pipeline` on the log clock.
+///
+/// Early routes and static assets stop before the pipeline, as they do live; only their
+/// side effects that feed later scoring (robots.txt fetches, asset fetches) are kept.
+pub(crate) fn evaluate(
+    store: &ReplayStore,
+    cfg: &crate::config::Config,
+    options: &ReplayOptions,
+    record: &ReplayRecord,
+) -> ReplayDecision {
+    let clock = FixedClock(record.ts);
+    with_clock(&clock, || {
+        let record = remint_clearance_cookie(record);
+        let req = record.to_request();
+        let path = req.path();
+        let ip = record.ip.as_str();
+
+        if crate::runtime::request_router::is_early_route(&req, path) {
+            if path == "/robots.txt" && cfg.robots_enabled {
+                crate::runtime::request_router::record_robots_txt_fetch(store, cfg, &req, ip);
+            }
+            return passthrough("early_route", 200);
+        }
+        if crate::should_bypass_expensive_bot_checks_for_static(&req, path) {
+            crate::signals::behavior::record_asset_fetch(store, cfg, ip);
+            return passthrough("static_bypass", 200);
+        }
+
+        crate::observability::decision_trace::begin();
+        let response = crate::run_policy_pipeline(
+            &req,
+            store,
+            &clock,
+            &crate::signals::web_bot_auth::OfflineKeyDirectoryTransport,
+            cfg,
+            ip,
+            options.trust_forwarded_headers,
+            &mut crate::observability::otel::RequestTrace::disabled(),
+        );
+        decision_from_draft(
+            crate::observability::decision_trace::take_draft(),
+            *response.status(),
+        )
+    })
+}
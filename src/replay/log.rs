@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+use spin_sdk::http::{Method, Request};
+
+use super::ReplayError;
+
+/// Supported request log formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Detect per line: `{` starts a JSON record, anything else is Combined Log Format.
+    Auto,
+    JsonLines,
+    Combined,
+}
+
+impl LogFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(LogFormat::Auto),
+            "jsonl" | "json" | "json_lines" => Some(LogFormat::JsonLines),
+            "clf" | "combined" => Some(LogFormat::Combined),
+            _ => None,
+        }
+    }
+}
+
+/// One request read from a traffic log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayRecord {
+    /// 1-based line number in the source log.
+    pub line: usize,
+    /// Request time in UNIX seconds; drives ban expiry and rate windows during replay.
+    pub ts: u64,
+    pub ip: String,
+    pub method: String,
+    /// Request target including any query string.
+    pub path: String,
+    /// Headers in log order; names are lowercased.
+    pub headers: Vec<(String, String)>,
+}
+
+impl ReplayRecord {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn to_request(&self) -> Request {
+        let mut builder = Request::builder();
+        builder
+            .method(parse_method(self.method.as_str()))
+            .uri(self.path.as_str());
+        for (name, value) in &self.headers {
+            builder.header(name.as_str(), value.as_str());
+        }
+        builder.body(Vec::new());
+        builder.build()
+    }
+}
+
+fn parse_method(value: &str) -> Method {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "GET" => Method::Get,
+        "HEAD" => Method::Head,
+        "POST" => Method::Post,
+        "PUT" => Method::Put,
+        "DELETE" => Method::Delete,
+        "PATCH" => Method::Patch,
+        "OPTIONS" => Method::Options,
+        "CONNECT" => Method::Connect,
+        "TRACE" => Method::Trace,
+        other => Method::Other(other.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonHeaders {
+    Pairs(Vec<(String, String)>),
+    Map(std::collections::BTreeMap<String, String>),
+}
+
+#[derive(Deserialize)]
+struct JsonRecord {
+    #[serde(default)]
+    ts: u64,
+    ip: String,
+    #[serde(default = "default_method")]
+    method: String,
+    path: String,
+    #[serde(default)]
+    headers: Option<JsonHeaders>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// Parse a whole request log. Blank lines and `#` comments are skipped.
+pub fn parse_log(input: &str, format: LogFormat) -> Result<Vec<ReplayRecord>, ReplayError> {
+    let mut records = Vec::new();
+    for (index, raw) in input.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let record = match format {
+            LogFormat::JsonLines => parse_json_line(line, line_no)?,
+            LogFormat::Combined => parse_combined_line(line, line_no)?,
+            LogFormat::Auto if line.starts_with('{') => parse_json_line(line, line_no)?,
+            LogFormat::Auto => parse_combined_line(line, line_no)?,
+        };
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> ReplayError {
+    ReplayError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+fn normalize_headers(headers: Vec<(String, String)>) -> Vec<(String, String)> {
+    headers
+        .into_iter()
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+pub(crate) fn parse_json_line(line: &str, line_no: usize) -> Result<ReplayRecord, ReplayError> {
+    let record: JsonRecord = serde_json::from_str(line)
+        .map_err(|err| parse_error(line_no, format!("invalid JSON record: {}", err)))?;
+    if record.ip.trim().is_empty() {
+        return Err(parse_error(line_no, "ip must not be empty"));
+    }
+    if !record.path.starts_with('/') {
+        return Err(parse_error(line_no, "path must start with '/'"));
+    }
+    let headers = match record.headers {
+        Some(JsonHeaders::Pairs(pairs)) => pairs,
+        Some(JsonHeaders::Map(map)) => map.into_iter().collect(),
+        None => Vec::new(),
+    };
+    Ok(ReplayRecord {
+        line: line_no,
+        ts: record.ts,
+        ip: record.ip.trim().to_string(),
+        method: record.method.trim().to_ascii_uppercase(),
+        path: record.path,
+        headers: normalize_headers(headers),
+    })
+}
+
+/// Parse one Combined Log Format line. Quoted fields after the user agent are
+/// treated as extra request headers in `name: value` form, for example:
+///
+/// `203.0.113.9 - - [10/Oct/2023:13:55:36 +0000] "GET / HTTP/1.1" 200 512 "-" "curl/8.0" "x-geo-country: RU"`
+pub(crate) fn parse_combined_line(line: &str, line_no: usize) -> Result<ReplayRecord, ReplayError> {
+    let mut rest = line;
+    let ip = take_token(&mut rest).ok_or_else(|| parse_error(line_no, "missing client address"))?;
+    take_token(&mut rest).ok_or_else(|| parse_error(line_no, "missing ident field"))?;
+    take_token(&mut rest).ok_or_else(|| parse_error(line_no, "missing user field"))?;
+
+    rest = rest.trim_start();
+    let Some(after_open) = rest.strip_prefix('[') else {
+        return Err(parse_error(line_no, "missing [timestamp]"));
+    };
+    let Some((timestamp, after_ts)) = after_open.split_once(']') else {
+        return Err(parse_error(line_no, "unterminated [timestamp]"));
+    };
+    let ts = parse_clf_timestamp(timestamp)
+        .ok_or_else(|| parse_error(line_no, format!("invalid timestamp '{}'", timestamp)))?;
+    rest = after_ts;
+
+    let request_line =
+        take_quoted(&mut rest).ok_or_else(|| parse_error(line_no, "missing \"request\" field"))?;
+    let mut request_parts = request_line.split_whitespace();
+    let method = request_parts
+        .next()
+        .ok_or_else(|| parse_error(line_no, "empty request line"))?;
+    let path = request_parts
+        .next()
+        .ok_or_else(|| parse_error(line_no, "request line has no target"))?;
+    if !path.starts_with('/') {
+        return Err(parse_error(line_no, "request target must start with '/'"));
+    }
+
+    // Status and byte count are informational only.
+    take_token(&mut rest);
+    take_token(&mut rest);
+
+    let mut headers = Vec::new();
+    if let Some(referer) = take_quoted(&mut rest) {
+        if referer != "-" && !referer.is_empty() {
+            headers.push(("referer".to_string(), referer));
+        }
+    }
+    if let Some(user_agent) = take_quoted(&mut rest) {
+        if user_agent != "-" && !user_agent.is_empty() {
+            headers.push(("user-agent".to_string(), user_agent));
+        }
+    }
+    while let Some(extra) = take_quoted(&mut rest) {
+        let Some((name, value)) = extra.split_once(':') else {
+            return Err(parse_error(
+                line_no,
+                format!("extra header '{}' is not in 'name: value' form", extra),
+            ));
+        };
+        headers.push((name.to_string(), value.to_string()));
+    }
+    if !rest.trim().is_empty() {
+        return Err(parse_error(line_no, "unexpected trailing data"));
+    }
+
+    Ok(ReplayRecord {
+        line: line_no,
+        ts,
+        ip,
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        headers: normalize_headers(headers),
+    })
+}
+
+fn take_token(rest: &mut &str) -> Option<String> {
+    let trimmed = rest.trim_start();
+    if trimmed.is_empty() {
+        *rest = trimmed;
+        return None;
+    }
+    let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+    let token = trimmed[..end].to_string();
+    *rest = &trimmed[end..];
+    Some(token)
+}
+
+fn take_quoted(rest: &mut &str) -> Option<String> {
+    let trimmed = rest.trim_start();
+    let body = trimmed.strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = body.char_indices();
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            '"' => {
+                *rest = &body[index + 1..];
+                return Some(value);
+            }
+            _ => value.push(ch),
+        }
+    }
+    None
+}
+
+/// Parse `10/Oct/2023:13:55:36 +0000` into UNIX seconds.
+pub(crate) fn parse_clf_timestamp(raw: &str) -> Option<u64> {
+    let (datetime, offset) = raw.trim().split_once(' ').unwrap_or((raw.trim(), "+0000"));
+    let (date, time) = datetime.split_once(':')?;
+
+    let mut date_parts = date.split('/');
+    let day: u32 = date_parts.next()?.parse().ok()?;
+    let month = month_number(date_parts.next()?)?;
+    let year: i64 = date_parts.next()?.parse().ok()?;
+
+    let mut time_parts = time.split(':');
+    let hour: i64 = time_parts.next()?.parse().ok()?;
+    let minute: i64 = time_parts.next()?.parse().ok()?;
+    let second: i64 = time_parts.next()?.parse().ok()?;
+    if !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let offset = offset.trim();
+    if offset.len() != 5 {
+        return None;
+    }
+    let sign = match &offset[..1] {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let offset_hours: i64 = offset[1..3].parse().ok()?;
+    let offset_minutes: i64 = offset[3..5].parse().ok()?;
+    let offset_seconds = sign * (offset_hours * 3600 + offset_minutes * 60);
+
+    let days = days_from_civil(year, month, day);
+    let local = days * 86_400 + hour * 3600 + minute * 60 + second;
+    u64::try_from(local - offset_seconds).ok()
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let lower = name.to_ascii_lowercase();
+    MONTHS
+        .iter()
+        .position(|month| *month == lower)
+        .map(|index| index as u32 + 1)
+}
+
+// Howard Hinnant's days-from-civil algorithm (proleptic Gregorian calendar).
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
@@ -0,0 +1,132 @@
+// src/replay/mod.rs
+// Offline traffic replay for tuning botness weights and thresholds against recorded logs.
+
+use serde::Serialize;
+use std::fmt;
+
+pub mod cli;
+mod engine;
+mod log;
+mod report;
+mod store;
+
+pub use log::{parse_log, LogFormat, ReplayRecord};
+pub use report::{compare, render_text_report, ReplayComparison, ReplayFlip, ReplaySummary};
+use store::ReplayStore;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    Parse { line: usize, message: String },
+    InvalidConfig(String),
+    Io(String),
+    Usage(String),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            ReplayError::InvalidConfig(message) => write!(f, "invalid config: {}", message),
+            ReplayError::Io(message) => write!(f, "io error: {}", message),
+            ReplayError::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayOptions {
+    /// Treat edge headers (`X-Geo-Country`, `X-Shuma-Edge-*`) in the log as trusted.
+    pub trust_forwarded_headers: bool,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            trust_forwarded_headers: true,
+        }
+    }
+}
+
+/// Policy outcome for one replayed request, labelled with the runtime taxonomy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayDecision {
+    /// Event reason the live pipeline would log (`honeypot`, `botness_gate_challenge`, ...).
+    pub reason: String,
+    pub status: u16,
+    pub level: String,
+    pub action: String,
+    pub detection: String,
+    /// Botness score, present only when the request reached the botness gate.
+    pub botness_score: Option<u8>,
+    pub signals: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayRun {
+    pub records: Vec<ReplayRecord>,
+    pub decisions: Vec<ReplayDecision>,
+}
+
+impl ReplayRun {
+    pub fn summary(&self) -> ReplaySummary {
+        report::summarize(&self.decisions)
+    }
+}
+
+/// Replays request logs through the policy pipeline against a fresh in-memory store.
+pub struct ReplaySimulator {
+    cfg: crate::config::Config,
+    options: ReplayOptions,
+}
+
+impl ReplaySimulator {
+    /// Simulator using the compiled-in defaults (`config/defaults.env`).
+    pub fn with_defaults() -> Self {
+        Self::from_config(crate::config::defaults().clone())
+    }
+
+    /// Simulator using a config JSON document in the same shape as the KV
+    /// `config:<site>` entry; omitted fields fall back to defaults.
+    pub fn from_config_json(raw: &str) -> Result<Self, ReplayError> {
+        let store = ReplayStore::new();
+        crate::challenge::KeyValueStore::set(&store, "config:default", raw.as_bytes())
+            .map_err(|_| ReplayError::InvalidConfig("store unavailable".to_string()))?;
+        let cfg = crate::config::Config::load(&store, "default")
+            .map_err(|err| ReplayError::InvalidConfig(err.user_message().to_string()))?;
+        Ok(Self::from_config(cfg))
+    }
+
+    fn from_config(mut cfg: crate::config::Config) -> Self {
+        // Flow identities only need to be stable within a run; skip secret-keyed hashing.
+        cfg.fingerprint_pseudonymize = false;
+        // Report what enforcement would do, not the test-mode log-only outcome.
+        cfg.test_mode = false;
+        Self {
+            cfg,
+            options: ReplayOptions::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: ReplayOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Replays `records` in order. State never leaks between runs.
+    pub fn replay(&self, records: &[ReplayRecord]) -> ReplayRun {
+        let store = ReplayStore::new();
+        let decisions = records
+            .iter()
+            .map(|record| engine::evaluate(&store, &self.cfg, &self.options, record))
+            .collect();
+        ReplayRun {
+            records: records.to_vec(),
+            decisions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
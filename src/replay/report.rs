@@ -0,0 +1,179 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::{ReplayDecision, ReplayRun};
+
+const SCORE_BUCKETS: usize = 11;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplaySummary {
+    pub total: u64,
+    /// Request count per taxonomy action (`A_ALLOW`, `A_NOT_A_BOT`, ...).
+    pub actions: BTreeMap<String, u64>,
+    /// Request count per decision reason.
+    pub reasons: BTreeMap<String, u64>,
+    /// Requests that reached the botness gate.
+    pub scored: u64,
+    /// `score_histogram[n]` counts scored requests with botness score `n` (0-10).
+    pub score_histogram: Vec<u64>,
+    pub mean_score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayFlip {
+    pub line: usize,
+    pub ip: String,
+    pub method: String,
+    pub path: String,
+    pub baseline_action: String,
+    pub baseline_reason: String,
+    pub baseline_score: Option<u8>,
+    pub candidate_action: String,
+    pub candidate_reason: String,
+    pub candidate_score: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayComparison {
+    pub total: u64,
+    /// Count per `baseline_action -> candidate_action` pair, for changed requests only.
+    pub transitions: BTreeMap<String, u64>,
+    pub flips: Vec<ReplayFlip>,
+}
+
+pub(crate) fn summarize(decisions: &[ReplayDecision]) -> ReplaySummary {
+    let mut actions = BTreeMap::new();
+    let mut reasons = BTreeMap::new();
+    let mut score_histogram = vec![0u64; SCORE_BUCKETS];
+    let mut scored = 0u64;
+    let mut score_sum = 0u64;
+    for decision in decisions {
+        *actions.entry(decision.action.clone()).or_insert(0) += 1;
+        *reasons.entry(decision.reason.clone()).or_insert(0) += 1;
+        if let Some(score) = decision.botness_score {
+            let bucket = (score as usize).min(SCORE_BUCKETS - 1);
+            score_histogram[bucket] += 1;
+            scored += 1;
+            score_sum += score as u64;
+        }
+    }
+    ReplaySummary {
+        total: decisions.len() as u64,
+        actions,
+        reasons,
+        scored,
+        score_histogram,
+        mean_score: if scored == 0 {
+            0.0
+        } else {
+            score_sum as f64 / scored as f64
+        },
+    }
+}
+
+/// Diff two runs over the same records. A request flips when its action or
+/// reason differs; score-only changes are visible in the histograms instead.
+pub fn compare(baseline: &ReplayRun, candidate: &ReplayRun) -> ReplayComparison {
+    let mut transitions = BTreeMap::new();
+    let mut flips = Vec::new();
+    for ((record, before), after) in baseline
+        .records
+        .iter()
+        .zip(baseline.decisions.iter())
+        .zip(candidate.decisions.iter())
+    {
+        if before.action == after.action && before.reason == after.reason {
+            continue;
+        }
+        *transitions
+            .entry(format!("{} -> {}", before.action, after.action))
+            .or_insert(0) += 1;
+        flips.push(ReplayFlip {
+            line: record.line,
+            ip: record.ip.clone(),
+            method: record.method.clone(),
+            path: record.path.clone(),
+            baseline_action: before.action.clone(),
+            baseline_reason: before.reason.clone(),
+            baseline_score: before.botness_score,
+            candidate_action: after.action.clone(),
+            candidate_reason: after.reason.clone(),
+            candidate_score: after.botness_score,
+        });
+    }
+    ReplayComparison {
+        total: baseline.decisions.len().min(candidate.decisions.len()) as u64,
+        transitions,
+        flips,
+    }
+}
+
+fn render_summary(out: &mut String, title: &str, summary: &ReplaySummary) {
+    out.push_str(&format!("== {} ({} requests) ==\n", title, summary.total));
+    out.push_str("actions:\n");
+    for (action, count) in &summary.actions {
+        out.push_str(&format!("  {:<24} {}\n", action, count));
+    }
+    out.push_str("reasons:\n");
+    for (reason, count) in &summary.reasons {
+        out.push_str(&format!("  {:<48} {}\n", reason, count));
+    }
+    out.push_str(&format!(
+        "botness scores ({} scored, mean {:.2}):\n",
+        summary.scored, summary.mean_score
+    ));
+    for (score, count) in summary.score_histogram.iter().enumerate() {
+        out.push_str(&format!("  {:>2} {}\n", score, count));
+    }
+}
+
+/// Plain-text report for terminals; `flip_limit` caps the per-request flip listing.
+pub fn render_text_report(
+    baseline: &ReplaySummary,
+    candidate: Option<(&ReplaySummary, &ReplayComparison)>,
+    flip_limit: usize,
+) -> String {
+    let mut out = String::new();
+    render_summary(&mut out, "baseline", baseline);
+    let Some((candidate_summary, comparison)) = candidate else {
+        return out;
+    };
+    out.push('\n');
+    render_summary(&mut out, "candidate", candidate_summary);
+    out.push_str(&format!(
+        "\n== flips ({} of {} requests changed) ==\n",
+        comparison.flips.len(),
+        comparison.total
+    ));
+    for (transition, count) in &comparison.transitions {
+        out.push_str(&format!("  {:<48} {}\n", transition, count));
+    }
+    for flip in comparison.flips.iter().take(flip_limit) {
+        out.push_str(&format!(
+            "  line {} {} {} {}: {}/{} score={} -> {}/{} score={}\n",
+            flip.line,
+            flip.ip,
+            flip.method,
+            flip.path,
+            flip.baseline_action,
+            flip.baseline_reason,
+            score_label(flip.baseline_score),
+            flip.candidate_action,
+            flip.candidate_reason,
+            score_label(flip.candidate_score)
+        ));
+    }
+    if comparison.flips.len() > flip_limit {
+        out.push_str(&format!(
+            "  ... {} more\n",
+            comparison.flips.len() - flip_limit
+        ));
+    }
+    out
+}
+
+fn score_label(score: Option<u8>) -> String {
+    score
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Process-local key-value store backing a single replay run.
+///
+/// Each run starts from an empty store so bans, rate windows and fingerprint
+/// state accumulate only from the replayed traffic itself. Event log writes are dropped.
+#[derive(Default)]
+pub(crate) struct ReplayStore {
+    map: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl ReplayStore {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl crate::challenge::KeyValueStore for ReplayStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
+        let map = self
+            .map
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(map.get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
+        // Nothing in the pipeline reads events back; keeping them would grow with the log.
+        if key.starts_with(crate::admin::EVENTLOG_V2_PREFIX) {
+            return Ok(());
+        }
+        let mut map = self
+            .map
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        map.insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), ()> {
+        let mut map = self
+            .map
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        map.remove(key);
+        Ok(())
+    }
+
+    fn get_keys(&self) -> Result<Vec<String>, ()> {
+        let map = self
+            .map
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(map.keys().cloned().collect())
+    }
+}
@@ -0,0 +1,354 @@
+use super::cli::parse_args;
+use super::log::{parse_clf_timestamp, parse_combined_line};
+use super::*;
+
+const BROWSER_UA: &str =
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36";
+const BROWSER_NAVIGATION_HEADERS: &str = r#""accept":"text/html,*/*","accept-language":"en-GB","accept-encoding":"gzip, br","sec-fetch-mode":"navigate","sec-fetch-dest":"document","sec-fetch-site":"none""#;
+
+fn json_line(ts: u64, ip: &str, path: &str, extra_headers: &str) -> String {
+    format!(
+        r#"{{"ts":{},"ip":"{}","method":"GET","path":"{}","headers":{{"user-agent":"{}","cookie":"shuma_clearance=x",{}{}}}}}"#,
+        ts, ip, path, BROWSER_UA, BROWSER_NAVIGATION_HEADERS, extra_headers
+    )
+}
+
+#[test]
+fn parses_json_lines_with_header_map_and_pairs() {
+    let input = format!(
+        "{}\n\n# comment\n{}\n",
+        json_line(1_700_000_000, "198.51.100.7", "/a?b=1", ""),
+        r#"{"ip":"198.51.100.8","path":"/b","headers":[["X-Geo-Country","ru"],["Accept","*/*"]]}"#
+    );
+    let records = parse_log(input.as_str(), LogFormat::JsonLines).expect("log parses");
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].line, 1);
+    assert_eq!(records[0].path, "/a?b=1");
+    assert_eq!(records[0].header("User-Agent"), Some(BROWSER_UA));
+    assert_eq!(records[1].line, 4);
+    assert_eq!(records[1].method, "GET");
+    assert_eq!(
+        records[1].headers,
+        vec![
+            ("x-geo-country".to_string(), "ru".to_string()),
+            ("accept".to_string(), "*/*".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn parses_combined_log_format_with_extra_headers() {
+    let line = r#"203.0.113.9 - - [10/Oct/2023:13:55:36 +0200] "POST /login?next=%2F HTTP/1.1" 200 512 "https://example.com/" "curl/8.4.0" "x-geo-country: RU" "accept-language: en""#;
+    let record = parse_combined_line(line, 3).expect("line parses");
+
+    assert_eq!(record.line, 3);
+    assert_eq!(record.ip, "203.0.113.9");
+    assert_eq!(record.method, "POST");
+    assert_eq!(record.path, "/login?next=%2F");
+    assert_eq!(record.ts, 1_696_938_936);
+    assert_eq!(record.header("referer"), Some("https://example.com/"));
+    assert_eq!(record.header("user-agent"), Some("curl/8.4.0"));
+    assert_eq!(record.header("x-geo-country"), Some("RU"));
+    assert_eq!(record.header("accept-language"), Some("en"));
+}
+
+#[test]
+fn clf_timestamp_applies_offsets_and_rejects_garbage() {
+    assert_eq!(parse_clf_timestamp("01/Jan/1970:00:00:00 +0000"), Some(0));
+    assert_eq!(parse_clf_timestamp("01/Jan/1970:01:00:00 +0100"), Some(0));
+    assert_eq!(
+        parse_clf_timestamp("29/Feb/2024:12:00:00 -0500"),
+        Some(1_709_226_000)
+    );
+    assert_eq!(parse_clf_timestamp("32/Jan/2024:00:00:00 +0000"), None);
+    assert_eq!(parse_clf_timestamp("01/Foo/2024:00:00:00 +0000"), None);
+}
+
+#[test]
+fn parse_errors_report_the_source_line() {
+    let input = format!(
+        "{}\n203.0.113.9 - - [10/Oct/2023:13:55:36 +0000] \"GET / HTTP/1.1\" 200 1 \"-\" \"ua\" \"not-a-header\"\n",
+        json_line(1, "198.51.100.7", "/", "")
+    );
+    let err = parse_log(input.as_str(), LogFormat::Auto).expect_err("bad extra header");
+    assert!(matches!(err, ReplayError::Parse { line: 2, .. }));
+}
+
+#[test]
+fn honeypot_hit_bans_the_ip_until_the_ban_expires_on_the_replay_clock() {
+    let input = [
+        json_line(1_000, "198.51.100.7", "/instaban", ""),
+        json_line(1_010, "198.51.100.7", "/", ""),
+        json_line(1_000_000_000, "198.51.100.7", "/", ""),
+    ]
+    .join("\n");
+    let records = parse_log(input.as_str(), LogFormat::JsonLines).unwrap();
+    let run = ReplaySimulator::with_defaults().replay(&records);
+
+    assert_eq!(run.decisions[0].reason, "honeypot");
+    assert_eq!(run.decisions[0].status, 403);
+    assert_eq!(run.decisions[1].reason, "banned");
+    assert_eq!(run.decisions[2].reason, "allow_clean");
+    assert_eq!(run.decisions[2].action, "A_ALLOW");
+}
+
+#[test]
+fn rate_limit_windows_follow_log_timestamps() {
+    // Three bare HTML fetches would also trip the behavioural asset-ratio signal.
+    let simulator =
+        ReplaySimulator::from_config_json(r#"{"rate_limit":2,"behavior_signal_enabled":false}"#)
+            .expect("config loads");
+    let input = [
+        json_line(60, "198.51.100.7", "/", ""),
+        json_line(61, "198.51.100.7", "/", ""),
+        json_line(62, "198.51.100.7", "/", ""),
+        json_line(62, "203.0.113.8", "/", ""),
+        json_line(62, "203.0.113.8", "/", ""),
+        json_line(120, "203.0.113.8", "/", ""),
+    ]
+    .join("\n");
+    let records = parse_log(input.as_str(), LogFormat::JsonLines).unwrap();
+    let run = simulator.replay(&records);
+
+    assert_eq!(run.decisions[2].reason, "rate");
+    assert_eq!(run.decisions[2].status, 429);
+    assert_eq!(run.decisions[3].reason, "allow_clean");
+    assert_eq!(run.decisions[4].reason, "botness_gate_challenge");
+    assert_eq!(run.decisions[5].reason, "allow_clean");
+}
+
+#[test]
+fn missing_js_cookie_routes_to_js_verification_with_a_score() {
+    let records = parse_log(
+        r#"{"ts":1,"ip":"198.51.100.7","path":"/","headers":{"user-agent":"curl/8.0"}}"#,
+        LogFormat::JsonLines,
+    )
+    .unwrap();
+    let run = ReplaySimulator::with_defaults().replay(&records);
+
+    assert_eq!(run.decisions[0].reason, "js_verification");
+    assert_eq!(run.decisions[0].action, "A_VERIFY_JS");
+    assert!(run.decisions[0].botness_score.is_some());
+}
+
+#[test]
+fn static_assets_and_early_routes_skip_policy() {
+    let input = [
+        json_line(1, "198.51.100.7", "/assets/app.css", ""),
+        json_line(1, "198.51.100.7", "/admin/config", ""),
+    ]
+    .join("\n");
+    let records = parse_log(input.as_str(), LogFormat::JsonLines).unwrap();
+    let run = ReplaySimulator::with_defaults().replay(&records);
+
+    assert_eq!(run.decisions[0].reason, "static_bypass");
+    assert_eq!(run.decisions[1].reason, "early_route");
+    assert_eq!(run.decisions[0].botness_score, None);
+}
+
+#[test]
+fn weight_change_flips_requests_between_configs() {
+    let baseline = ReplaySimulator::from_config_json(r#"{"geo_risk":["RU"]}"#).unwrap();
+    let candidate = ReplaySimulator::from_config_json(
+        r#"{"geo_risk":["RU"],"botness_weights":{"geo_risk":3}}"#,
+    )
+    .unwrap();
+    let input = [
+        json_line(1, "198.51.100.7", "/", r#","x-geo-country":"RU""#),
+        json_line(1, "198.51.100.8", "/", r#","x-geo-country":"FR""#),
+    ]
+    .join("\n");
+    let records = parse_log(input.as_str(), LogFormat::JsonLines).unwrap();
+
+    let before = baseline.replay(&records);
+    let after = candidate.replay(&records);
+    let comparison = compare(&before, &after);
+
+    assert_eq!(before.decisions[0].reason, "botness_gate_not_a_bot");
+    assert_eq!(after.decisions[0].reason, "botness_gate_challenge");
+    assert_eq!(comparison.total, 2);
+    assert_eq!(comparison.flips.len(), 1);
+    assert_eq!(comparison.flips[0].line, 1);
+    assert_eq!(comparison.flips[0].baseline_score, Some(2));
+    assert_eq!(comparison.flips[0].candidate_score, Some(3));
+    assert_eq!(
+        comparison
+            .transitions
+            .get("A_NOT_A_BOT -> A_CHALLENGE_STRONG"),
+        Some(&1)
+    );
+
+    let report = render_text_report(&before.summary(), Some((&after.summary(), &comparison)), 10);
+    assert!(report.contains("flips (1 of 2 requests changed)"));
+}
+
+#[test]
+fn untrusted_headers_ignore_geo_evidence() {
+    let simulator = ReplaySimulator::from_config_json(r#"{"geo_block":["RU"]}"#)
+        .unwrap()
+        .with_options(ReplayOptions {
+            trust_forwarded_headers: false,
+        });
+    let records = parse_log(
+        json_line(1, "198.51.100.7", "/", r#","x-geo-country":"RU""#).as_str(),
+        LogFormat::JsonLines,
+    )
+    .unwrap();
+
+    assert_eq!(
+        simulator.replay(&records).decisions[0].reason,
+        "allow_clean"
+    );
+}
+
+#[test]
+fn summary_counts_actions_and_builds_score_histogram() {
+    let input = [
+        json_line(1, "198.51.100.7", "/", ""),
+        json_line(2, "198.51.100.8", "/", ""),
+        json_line(3, "198.51.100.9", "/instaban", ""),
+    ]
+    .join("\n");
+    let records = parse_log(input.as_str(), LogFormat::JsonLines).unwrap();
+    let summary = ReplaySimulator::with_defaults().replay(&records).summary();
+
+    assert_eq!(summary.total, 3);
+    assert_eq!(summary.actions.get("A_ALLOW"), Some(&2));
+    assert_eq!(summary.reasons.get("honeypot"), Some(&1));
+    assert_eq!(summary.scored, 2);
+    assert_eq!(summary.score_histogram.len(), 11);
+    assert_eq!(summary.score_histogram[0], 2);
+}
+
+#[test]
+fn invalid_config_json_is_rejected() {
+    let err = ReplaySimulator::from_config_json("{not json").err();
+    assert!(matches!(err, Some(ReplayError::InvalidConfig(_))));
+}
+
+#[test]
+fn cli_args_parse_and_validate() {
+    let args = [
+        "--log",
+        "traffic.log",
+        "--format",
+        "clf",
+        "--compare",
+        "b.json",
+        "--json",
+        "--untrusted-headers",
+    ]
+    .iter()
+    .map(|value| value.to_string())
+    .collect::<Vec<_>>();
+    let parsed = parse_args(&args).expect("args parse");
+
+    assert_eq!(parsed.log_path, "traffic.log");
+    assert_eq!(parsed.format, LogFormat::Combined);
+    assert_eq!(parsed.config_path, None);
+    assert_eq!(parsed.compare_path.as_deref(), Some("b.json"));
+    assert!(parsed.json);
+    assert!(!parsed.trust_forwarded_headers);
+
+    assert!(matches!(parse_args(&[]), Err(ReplayError::Usage(_))));
+    assert!(matches!(
+        parse_args(&["--log".to_string()]),
+        Err(ReplayError::Usage(_))
+    ));
+}
+
+#[test]
+fn ai_crawler_policy_applies_to_disallowed_declared_crawlers() {
+    let simulator = ReplaySimulator::from_config_json(
+        r#"{"robots_enabled":true,"robots_block_ai_training":true,"ai_crawler_action":"payment_required"}"#,
+    )
+    .unwrap();
+    let input = [
+        r#"198.51.100.7 - - [10/Oct/2023:13:55:36 +0000] "GET /article HTTP/1.1" 200 512 "-" "Mozilla/5.0 (compatible; GPTBot/1.2; +https://openai.com/gptbot)""#.to_string(),
+        json_line(2, "198.51.100.8", "/article", ""),
+    ]
+    .join("\n");
+    let records = parse_log(input.as_str(), LogFormat::Auto).unwrap();
+    let decisions = simulator.replay(&records).decisions;
+
+    assert_eq!(decisions[0].reason, "ai_crawler_policy_payment_required");
+    assert_eq!(decisions[0].status, 402);
+    assert_eq!(decisions[0].detection, "D_AI_CRAWLER_PAYMENT_REQUIRED");
+    assert_eq!(decisions[1].reason, "allow_clean");
+}
+
+#[test]
+fn crawl_payment_token_is_spent_down_across_replayed_requests() {
+    use base64::{engine::general_purpose, Engine as _};
+    use hmac::{Hmac, Mac};
+
+    let _lock = crate::test_support::lock_env();
+    std::env::set_var("SHUMA_CRAWL_PAYMENT_SECRET", "replay-secret");
+    let simulator = ReplaySimulator::from_config_json(
+        r#"{"robots_enabled":true,"robots_block_ai_training":true,"ai_crawler_action":"payment_required","crawl_payment_price_micros":1000}"#,
+    )
+    .unwrap();
+    let encode =
+        |value: serde_json::Value| general_purpose::URL_SAFE_NO_PAD.encode(value.to_string());
+    let input = format!(
+        "{}.{}",
+        encode(serde_json::json!({"alg": "HS256"})),
+        encode(
+            serde_json::json!({"sub": "GPTBot", "jti": "r-1", "exp": 4_000_000_000u64, "budget": 1000, "cur": "USD"})
+        )
+    );
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"replay-secret").unwrap();
+    mac.update(input.as_bytes());
+    let token = format!(
+        "{}.{}",
+        input,
+        general_purpose::URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+    );
+    let line = |ts: u64| {
+        format!(
+            r#"{{"ts":{},"ip":"198.51.100.7","method":"GET","path":"/article","headers":{{"user-agent":"Mozilla/5.0 (compatible; GPTBot/1.2)","crawl-payment":"{}"}}}}"#,
+            ts, token
+        )
+    };
+    let records = parse_log([line(1), line(2)].join("\n").as_str(), LogFormat::Auto).unwrap();
+    let decisions = simulator.replay(&records).decisions;
+    std::env::remove_var("SHUMA_CRAWL_PAYMENT_SECRET");
+
+    assert_eq!(decisions[0].reason, "crawl_payment_paid");
+    assert_eq!(decisions[0].status, 200);
+    assert_eq!(decisions[0].detection, "D_CRAWL_PAYMENT_ACCEPTED");
+    assert_eq!(decisions[1].reason, "ai_crawler_policy_payment_required");
+    assert_eq!(decisions[1].status, 402);
+}
+
+#[test]
+fn robots_violation_signal_needs_a_prior_robots_fetch() {
+    let simulator = ReplaySimulator::from_config_json(
+        r#"{"robots_enabled":true,"robots_block_ai_training":true,"ai_crawler_action":"allow"}"#,
+    )
+    .unwrap();
+    let crawler = |path: &str, hour: u32| {
+        format!(
+            r#"198.51.100.7 - - [10/Oct/2023:{:02}:00:00 +0000] "GET {} HTTP/1.1" 200 512 "-" "Mozilla/5.0 (compatible; GPTBot/1.2; +https://openai.com/gptbot)""#,
+            hour, path
+        )
+    };
+    let input = [
+        crawler("/articles/1", 10),
+        crawler("/robots.txt", 11),
+        crawler("/articles/2", 12),
+    ]
+    .join("\n");
+    let records = parse_log(input.as_str(), LogFormat::Auto).unwrap();
+    let decisions = simulator.replay(&records).decisions;
+
+    assert!(!decisions[0]
+        .signals
+        .iter()
+        .any(|signal| signal.starts_with("robots_violation:")));
+    assert!(decisions[2]
+        .signals
+        .iter()
+        .any(|signal| signal.starts_with("robots_violation:")));
+}
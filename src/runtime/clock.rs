@@ -0,0 +1,107 @@
+// src/runtime/clock.rs
+// Request clock for the policy pipeline.
+// Live traffic reads wall time; replay pins each request to its log timestamp so bans,
+// rate windows and signed tokens age on the log clock instead.
+
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) trait Clock {
+    /// Current time in UNIX seconds.
+    fn now_ts(&self) -> u64;
+
+    /// Instant to hold for a whole request, or `None` to keep reading live time.
+    fn pinned_ts(&self) -> Option<u64> {
+        Some(self.now_ts())
+    }
+}
+
+/// Wall clock used for live requests.
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ts(&self) -> u64 {
+        system_now_ms() / 1000
+    }
+
+    fn pinned_ts(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Clock stopped at a fixed instant, e.g. a replayed request's log timestamp.
+pub(crate) struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now_ts(&self) -> u64 {
+        self.0
+    }
+}
+
+thread_local! {
+    static PINNED_TS: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+fn system_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+pub(crate) fn pinned_ts() -> Option<u64> {
+    PINNED_TS.with(Cell::get)
+}
+
+/// Current time in UNIX seconds, from the pinned clock when one is installed.
+pub(crate) fn now_ts() -> u64 {
+    pinned_ts().unwrap_or_else(|| system_now_ms() / 1000)
+}
+
+/// Current time in UNIX milliseconds, from the pinned clock when one is installed.
+pub(crate) fn now_ms() -> u64 {
+    pinned_ts()
+        .map(|ts| ts.saturating_mul(1000))
+        .unwrap_or_else(system_now_ms)
+}
+
+struct PinGuard(Option<u64>);
+
+impl Drop for PinGuard {
+    fn drop(&mut self) {
+        PINNED_TS.with(|pinned| pinned.set(self.0));
+    }
+}
+
+/// Runs `run` with `now_ts`/`now_ms` answering from `clock`.
+pub(crate) fn with_clock<C: Clock + ?Sized, T>(clock: &C, run: impl FnOnce() -> T) -> T {
+    let Some(ts) = clock.pinned_ts() else {
+        return run();
+    };
+    let _guard = PinGuard(PINNED_TS.with(|pinned| pinned.replace(Some(ts))));
+    run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{now_ms, now_ts, pinned_ts, with_clock, FixedClock, SystemClock};
+
+    #[test]
+    fn fixed_clock_pins_time_for_the_call_and_restores_after() {
+        assert_eq!(pinned_ts(), None);
+        let (ts, ms, nested) = with_clock(&FixedClock(1_000), || {
+            let nested = with_clock(&FixedClock(2_000), now_ts);
+            (now_ts(), now_ms(), nested)
+        });
+        assert_eq!((ts, ms, nested), (1_000, 1_000_000, 2_000));
+        assert_eq!(pinned_ts(), None);
+    }
+
+    #[test]
+    fn system_clock_keeps_reading_wall_time() {
+        with_clock(&FixedClock(1_000), || {
+            assert_eq!(with_clock(&SystemClock, now_ts), 1_000);
+        });
+        assert!(with_clock(&SystemClock, now_ts) > 1_000);
+    }
+}
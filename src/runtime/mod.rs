@@ -1,3 +1,4 @@
+pub(crate) mod clock;
 pub(crate) mod kv_gate;
 pub(crate) mod policy_pipeline;
 pub(crate) mod policy_taxonomy;
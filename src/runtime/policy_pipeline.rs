@@ -1,7 +1,6 @@
 use spin_sdk::http::{Request, Response};
-use spin_sdk::key_value::Store;
 
-fn active_botness_signal_ids(
+pub(crate) fn active_botness_signal_ids(
     assessment: &crate::BotnessAssessment,
 ) -> Vec<crate::runtime::policy_taxonomy::SignalId> {
     assessment
@@ -14,7 +13,7 @@ fn active_botness_signal_ids(
         .collect()
 }
 
-fn ip_range_signal_ids(source: &crate::signals::ip_range_policy::MatchSource) -> Vec<crate::runtime::policy_taxonomy::SignalId> {
+pub(crate) fn ip_range_signal_ids(source: &crate::signals::ip_range_policy::MatchSource) -> Vec<crate::runtime::policy_taxonomy::SignalId> {
     match source {
         crate::signals::ip_range_policy::MatchSource::CustomRule => {
             vec![crate::runtime::policy_taxonomy::SignalId::IpRangeCustom]
@@ -32,9 +31,9 @@ fn ip_range_source_label(source: &crate::signals::ip_range_policy::MatchSource)
     }
 }
 
-pub(crate) fn maybe_handle_ip_range_policy(
+pub(crate) fn maybe_handle_ip_range_policy<S: crate::challenge::KeyValueStore>(
     req: &Request,
-    store: &Store,
+    store: &S,
     cfg: &crate::config::Config,
     provider_registry: &crate::providers::registry::ProviderRegistry,
     site_id: &str,
@@ -121,6 +120,7 @@ pub(crate) fn maybe_handle_ip_range_policy(
                         403,
                         crate::enforcement::block_page::render_block_page(
                             crate::enforcement::block_page::BlockReason::IpRangePolicy,
+                            &crate::i18n::Messages::for_request(req, cfg),
                         ),
                     ))
                 }
@@ -224,6 +224,7 @@ pub(crate) fn maybe_handle_ip_range_policy(
                             403,
                             crate::enforcement::block_page::render_block_page(
                                 crate::enforcement::block_page::BlockReason::IpRangePolicy,
+                                &crate::i18n::Messages::for_request(req, cfg),
                             ),
                         ));
                     };
@@ -290,6 +291,7 @@ pub(crate) fn maybe_handle_ip_range_policy(
                         429,
                         crate::enforcement::block_page::render_block_page(
                             crate::enforcement::block_page::BlockReason::RateLimit,
+                            &crate::i18n::Messages::for_request(req, cfg),
                         ),
                     ))
                 }
@@ -343,6 +345,7 @@ pub(crate) fn maybe_handle_ip_range_policy(
                         403,
                         crate::enforcement::block_page::render_block_page(
                             crate::enforcement::block_page::BlockReason::Honeypot,
+                            &crate::i18n::Messages::for_request(req, cfg),
                         ),
                     ))
                 }
@@ -401,7 +404,7 @@ pub(crate) fn maybe_handle_ip_range_policy(
                         return Some(
                             provider_registry
                                 .challenge_engine_provider()
-                                .render_challenge(req, cfg.challenge_puzzle_transform_count as usize),
+                                .render_challenge(req, cfg),
                         );
                     }
                     crate::observability::metrics::increment(
@@ -429,6 +432,7 @@ pub(crate) fn maybe_handle_ip_range_policy(
                         403,
                         crate::enforcement::block_page::render_block_page(
                             crate::enforcement::block_page::BlockReason::IpRangePolicy,
+                            &crate::i18n::Messages::for_request(req, cfg),
                         ),
                     ))
                 }
@@ -501,6 +505,7 @@ pub(crate) fn maybe_handle_ip_range_policy(
                         403,
                         crate::enforcement::block_page::render_block_page(
                             crate::enforcement::block_page::BlockReason::IpRangePolicy,
+                            &crate::i18n::Messages::for_request(req, cfg),
                         ),
                     ))
                 }
@@ -509,8 +514,9 @@ pub(crate) fn maybe_handle_ip_range_policy(
     }
 }
 
-pub(crate) fn maybe_handle_honeypot(
-    store: &Store,
+pub(crate) fn maybe_handle_honeypot<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
     cfg: &crate::config::Config,
     provider_registry: &crate::providers::registry::ProviderRegistry,
     site_id: &str,
@@ -520,10 +526,21 @@ pub(crate) fn maybe_handle_honeypot(
     if !cfg.honeypot_enabled {
         return None;
     }
-    if !crate::enforcement::honeypot::is_honeypot(path, &cfg.honeypots) {
+    let rotating_generation =
+        crate::enforcement::honeypot::rotating_generation(cfg, path, crate::admin::now_ts());
+    if rotating_generation.is_none()
+        && !crate::enforcement::honeypot::is_honeypot(path, &cfg.honeypots)
+    {
         return None;
     }
     crate::observability::monitoring::record_honeypot_hit(store, ip, path);
+    if let Some(generation) = rotating_generation {
+        crate::observability::metrics::increment(
+            store,
+            crate::observability::metrics::MetricName::RotatingHoneypotHits,
+            Some(generation.to_string().as_str()),
+        );
+    }
     let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
         crate::runtime::policy_taxonomy::PolicyTransition::HoneypotHit,
     );
@@ -574,12 +591,103 @@ pub(crate) fn maybe_handle_honeypot(
         403,
         crate::enforcement::block_page::render_block_page(
             crate::enforcement::block_page::BlockReason::Honeypot,
+            &crate::i18n::Messages::for_request(req, cfg),
         ),
     ))
 }
 
-pub(crate) fn maybe_handle_rate_limit(
-    store: &Store,
+pub(crate) fn maybe_handle_form_honeypot<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
+    cfg: &crate::config::Config,
+    provider_registry: &crate::providers::registry::ProviderRegistry,
+    site_id: &str,
+    ip: &str,
+    path: &str,
+) -> Option<Response> {
+    if !cfg.form_honeypot_enabled {
+        return None;
+    }
+    let trip = crate::enforcement::form_honeypot::evaluate_submission(
+        req,
+        cfg,
+        ip,
+        crate::admin::now_ts(),
+    )?;
+    crate::observability::monitoring::record_honeypot_hit(store, ip, path);
+    let signal = match trip {
+        crate::enforcement::form_honeypot::FormHoneypotTrip::DecoyFilled => {
+            crate::runtime::policy_taxonomy::SignalId::FormHoneypotFilled
+        }
+        crate::enforcement::form_honeypot::FormHoneypotTrip::TooFast => {
+            crate::runtime::policy_taxonomy::SignalId::FormSubmitTooFast
+        }
+    };
+    let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
+        crate::runtime::policy_taxonomy::PolicyTransition::FormHoneypotHit(vec![signal]),
+    );
+    crate::observability::metrics::record_policy_match(store, &policy_match);
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::FormHoneypotHits,
+        Some(trip.as_str()),
+    );
+
+    let (event, outcome) = if cfg.form_honeypot_auto_ban {
+        provider_registry
+            .ban_store_provider()
+            .ban_ip_with_fingerprint(
+                store,
+                site_id,
+                ip,
+                "honeypot",
+                cfg.get_ban_duration("honeypot"),
+                Some(crate::enforcement::ban::BanFingerprint {
+                    score: None,
+                    signals: vec![format!("form_honeypot_{}", trip.as_str())],
+                    summary: Some(format!("path={} trip={}", path, trip.as_str())),
+                }),
+            );
+        crate::observability::metrics::increment(
+            store,
+            crate::observability::metrics::MetricName::BansTotal,
+            Some("honeypot"),
+        );
+        (crate::admin::EventType::Ban, "banned")
+    } else {
+        (crate::admin::EventType::Block, "blocked")
+    };
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::BlocksTotal,
+        None,
+    );
+    crate::admin::log_event(
+        store,
+        &crate::admin::EventLogEntry {
+            ts: crate::admin::now_ts(),
+            event,
+            ip: Some(ip.to_string()),
+            reason: Some("form_honeypot".to_string()),
+            outcome: Some(
+                policy_match
+                    .annotate_outcome(format!("{} trip={}", outcome, trip.as_str()).as_str()),
+            ),
+            admin: None,
+        },
+    );
+    Some(Response::new(
+        403,
+        crate::enforcement::block_page::render_block_page(
+            crate::enforcement::block_page::BlockReason::Honeypot,
+            &crate::i18n::Messages::for_request(req, cfg),
+        ),
+    ))
+}
+
+pub(crate) fn maybe_handle_rate_limit<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
     cfg: &crate::config::Config,
     provider_registry: &crate::providers::registry::ProviderRegistry,
     site_id: &str,
@@ -649,12 +757,15 @@ pub(crate) fn maybe_handle_rate_limit(
         429,
         crate::enforcement::block_page::render_block_page(
             crate::enforcement::block_page::BlockReason::RateLimit,
+            &crate::i18n::Messages::for_request(req, cfg),
         ),
     ))
 }
 
-pub(crate) fn maybe_handle_existing_ban(
-    store: &Store,
+pub(crate) fn maybe_handle_existing_ban<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
+    cfg: &crate::config::Config,
     provider_registry: &crate::providers::registry::ProviderRegistry,
     site_id: &str,
     ip: &str,
@@ -690,13 +801,278 @@ pub(crate) fn maybe_handle_existing_ban(
         403,
         crate::enforcement::block_page::render_block_page(
             crate::enforcement::block_page::BlockReason::Honeypot,
+            &crate::i18n::Messages::for_request(req, cfg),
         ),
     ))
 }
 
-pub(crate) fn maybe_handle_geo_policy(
+pub(crate) fn maybe_handle_web_bot_auth<S: crate::challenge::KeyValueStore>(
     req: &Request,
-    store: &Store,
+    store: &S,
+    cfg: &crate::config::Config,
+    ip: &str,
+    key_directory: &dyn crate::signals::web_bot_auth::KeyDirectoryTransport,
+) -> Option<Response> {
+    let now = crate::admin::now_ts();
+    let outcome = crate::signals::web_bot_auth::verify(store, req, cfg, key_directory, now);
+    if outcome == crate::signals::web_bot_auth::VerificationOutcome::Absent {
+        return None;
+    }
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::WebBotAuthVerifications,
+        Some(outcome.as_str()),
+    );
+    let verified = match outcome {
+        crate::signals::web_bot_auth::VerificationOutcome::Verified(verified) => verified,
+        failed => {
+            // A bad signature is not evidence of abuse on its own; the request continues
+            // through the pipeline as anonymous traffic.
+            crate::admin::log_event(
+                store,
+                &crate::admin::EventLogEntry {
+                    ts: now,
+                    event: crate::admin::EventType::Challenge,
+                    ip: Some(ip.to_string()),
+                    reason: Some("web_bot_auth_failed".to_string()),
+                    outcome: Some(format!("outcome={}", failed.as_str())),
+                    admin: None,
+                },
+            );
+            return None;
+        }
+    };
+    let summary = format!(
+        "agent={} keyid={} action={}",
+        verified.agent.name,
+        verified.key_id,
+        verified.agent.action.as_str()
+    );
+
+    match verified.agent.action {
+        crate::config::VerifiedAgentAction::RateLimit
+            if !crate::signals::web_bot_auth::within_rate_limit(store, &verified.agent, now) =>
+        {
+            let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
+                crate::runtime::policy_taxonomy::PolicyTransition::WebBotAuthRateLimit,
+            );
+            crate::observability::metrics::record_policy_match(store, &policy_match);
+            crate::admin::log_event(
+                store,
+                &crate::admin::EventLogEntry {
+                    ts: now,
+                    event: crate::admin::EventType::Block,
+                    ip: Some(ip.to_string()),
+                    reason: Some("web_bot_auth_rate_limit".to_string()),
+                    outcome: Some(policy_match.annotate_outcome(summary.as_str())),
+                    admin: None,
+                },
+            );
+            Some(
+                Response::builder()
+                    .status(429)
+                    .header("Content-Type", "text/plain; charset=utf-8")
+                    .header("Retry-After", (60 - now % 60).to_string())
+                    .body("Too Many Requests")
+                    .build(),
+            )
+        }
+        crate::config::VerifiedAgentAction::PaymentRequired => Some(settle_crawl_payment(
+            req,
+            store,
+            cfg,
+            ip,
+            verified.agent.name.as_str(),
+            crate::runtime::policy_taxonomy::SignalId::WebBotAuthVerified,
+            crate::runtime::policy_taxonomy::PolicyTransition::WebBotAuthPaymentRequired,
+            "web_bot_auth_payment_required",
+            summary.as_str(),
+        )),
+        crate::config::VerifiedAgentAction::Allow
+        | crate::config::VerifiedAgentAction::RateLimit => {
+            let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
+                crate::runtime::policy_taxonomy::PolicyTransition::WebBotAuthAllow,
+            );
+            crate::observability::metrics::record_policy_match(store, &policy_match);
+            crate::observability::decision_trace::note_reason("web_bot_auth_verified");
+            Some(Response::new(200, "OK (verified agent)"))
+        }
+    }
+}
+
+pub(crate) fn maybe_handle_ai_crawler_policy<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
+    cfg: &crate::config::Config,
+    provider_registry: &crate::providers::registry::ProviderRegistry,
+    ip: &str,
+    user_agent: &str,
+) -> Option<Response> {
+    let mut decision = crate::crawler_policy::declared::evaluate(cfg, ip, user_agent)?;
+    if decision.action == crate::config::AiCrawlerAction::Maze && !cfg.maze_enabled {
+        decision.action = crate::config::AiCrawlerAction::Block;
+    }
+    crate::observability::monitoring::record_ai_crawler_hit(store, &decision);
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::AiCrawlerHits,
+        Some(decision.crawler.bot.as_str()),
+    );
+    let summary = format!(
+        "bot={} category={} action={}",
+        decision.crawler.bot,
+        decision.crawler.category.as_str(),
+        decision.action.as_str()
+    );
+
+    match decision.action {
+        crate::config::AiCrawlerAction::Allow => None,
+        crate::config::AiCrawlerAction::Block => {
+            let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
+                crate::runtime::policy_taxonomy::PolicyTransition::AiCrawlerBlock,
+            );
+            crate::observability::metrics::record_policy_match(store, &policy_match);
+            crate::observability::metrics::increment(
+                store,
+                crate::observability::metrics::MetricName::BlocksTotal,
+                None,
+            );
+            crate::admin::log_event(
+                store,
+                &crate::admin::EventLogEntry {
+                    ts: crate::admin::now_ts(),
+                    event: crate::admin::EventType::Block,
+                    ip: Some(ip.to_string()),
+                    reason: Some("ai_crawler_policy_block".to_string()),
+                    outcome: Some(policy_match.annotate_outcome(summary.as_str())),
+                    admin: None,
+                },
+            );
+            Some(Response::new(
+                403,
+                crate::enforcement::block_page::render_block_page(
+                    crate::enforcement::block_page::BlockReason::AiCrawler,
+                    &crate::i18n::Messages::for_request(req, cfg),
+                ),
+            ))
+        }
+        crate::config::AiCrawlerAction::Maze => {
+            let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
+                crate::runtime::policy_taxonomy::PolicyTransition::AiCrawlerMaze,
+            );
+            crate::observability::metrics::record_policy_match(store, &policy_match);
+            let event_outcome = policy_match.annotate_outcome(summary.as_str());
+            Some(
+                provider_registry
+                    .maze_tarpit_provider()
+                    .serve_maze_with_tracking(
+                        req,
+                        store,
+                        cfg,
+                        ip,
+                        user_agent,
+                        crate::maze::entry_path("ai-crawler").as_str(),
+                        "ai_crawler_policy_maze",
+                        event_outcome.as_str(),
+                        None,
+                    ),
+            )
+        }
+        crate::config::AiCrawlerAction::PaymentRequired => Some(settle_crawl_payment(
+            req,
+            store,
+            cfg,
+            ip,
+            decision.crawler.bot.as_str(),
+            crate::runtime::policy_taxonomy::SignalId::AiCrawlerDeclared,
+            crate::runtime::policy_taxonomy::PolicyTransition::AiCrawlerPaymentRequired,
+            "ai_crawler_policy_payment_required",
+            summary.as_str(),
+        )),
+    }
+}
+
+/// Lets an identified crawler through if its `Crawl-Payment` token covers the price, and
+/// answers with the `402` price offer otherwise.
+#[allow(clippy::too_many_arguments)]
+fn settle_crawl_payment<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
+    cfg: &crate::config::Config,
+    ip: &str,
+    crawler: &str,
+    identified_by: crate::runtime::policy_taxonomy::SignalId,
+    unpaid_transition: crate::runtime::policy_taxonomy::PolicyTransition,
+    unpaid_reason: &str,
+    summary: &str,
+) -> Response {
+    let redemption = crate::crawler_policy::payment::redeem(
+        store,
+        cfg,
+        req,
+        crawler,
+        crate::config::crawl_payment_secret().as_deref(),
+        crate::admin::now_ts(),
+    );
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::CrawlPaymentOutcomes,
+        Some(redemption.as_str()),
+    );
+    let failure = match redemption {
+        crate::crawler_policy::payment::Redemption::Paid(receipt) => {
+            let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
+                crate::runtime::policy_taxonomy::PolicyTransition::CrawlPaymentAccepted(vec![
+                    identified_by,
+                ]),
+            );
+            crate::observability::metrics::record_policy_match(store, &policy_match);
+            crate::observability::metrics::increment(
+                store,
+                crate::observability::metrics::MetricName::CrawlPaymentPaid,
+                Some(crawler),
+            );
+            crate::observability::metrics::increment_by(
+                store,
+                crate::observability::metrics::MetricName::CrawlPaymentChargedMicros,
+                Some(crawler),
+                receipt.charged_micros,
+            );
+            crate::observability::decision_trace::note_reason("crawl_payment_paid");
+            return crate::crawler_policy::payment::paid_response(cfg, &receipt);
+        }
+        crate::crawler_policy::payment::Redemption::Rejected(failure) => Some(failure),
+        crate::crawler_policy::payment::Redemption::Absent => None,
+    };
+
+    let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(unpaid_transition);
+    crate::observability::metrics::record_policy_match(store, &policy_match);
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::BlocksTotal,
+        None,
+    );
+    let outcome = match failure {
+        Some(failure) => format!("{} payment={}", summary, failure.as_str()),
+        None => summary.to_string(),
+    };
+    crate::admin::log_event(
+        store,
+        &crate::admin::EventLogEntry {
+            ts: crate::admin::now_ts(),
+            event: crate::admin::EventType::Block,
+            ip: Some(ip.to_string()),
+            reason: Some(unpaid_reason.to_string()),
+            outcome: Some(policy_match.annotate_outcome(outcome.as_str())),
+            admin: None,
+        },
+    );
+    crate::crawler_policy::payment::offer_response(cfg, crawler, failure)
+}
+
+pub(crate) fn maybe_handle_geo_policy<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
     cfg: &crate::config::Config,
     provider_registry: &crate::providers::registry::ProviderRegistry,
     ip: &str,
@@ -745,6 +1121,7 @@ pub(crate) fn maybe_handle_geo_policy(
                 403,
                 crate::enforcement::block_page::render_block_page(
                     crate::enforcement::block_page::BlockReason::GeoPolicy,
+                    &crate::i18n::Messages::for_request(req, cfg),
                 ),
             ))
         }
@@ -816,7 +1193,7 @@ pub(crate) fn maybe_handle_geo_policy(
                 return Some(
                     provider_registry
                         .challenge_engine_provider()
-                        .render_challenge(req, cfg.challenge_puzzle_transform_count as usize),
+                        .render_challenge(req, cfg),
                 );
             }
             let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
@@ -850,6 +1227,7 @@ pub(crate) fn maybe_handle_geo_policy(
                 403,
                 crate::enforcement::block_page::render_block_page(
                     crate::enforcement::block_page::BlockReason::GeoPolicy,
+                    &crate::i18n::Messages::for_request(req, cfg),
                 ),
             ))
         }
@@ -892,7 +1270,7 @@ pub(crate) fn maybe_handle_geo_policy(
                 return Some(
                     provider_registry
                         .challenge_engine_provider()
-                        .render_challenge(req, cfg.challenge_puzzle_transform_count as usize),
+                        .render_challenge(req, cfg),
                 );
             }
             if cfg.maze_enabled {
@@ -959,6 +1337,7 @@ pub(crate) fn maybe_handle_geo_policy(
                 403,
                 crate::enforcement::block_page::render_block_page(
                     crate::enforcement::block_page::BlockReason::GeoPolicy,
+                    &crate::i18n::Messages::for_request(req, cfg),
                 ),
             ))
         }
@@ -968,9 +1347,68 @@ pub(crate) fn maybe_handle_geo_policy(
     }
 }
 
-pub(crate) fn compute_needs_js(
+/// Records JA3/JA4 reputation matches and blocks block-action matches before botness scoring.
+pub(crate) fn maybe_handle_tls_fingerprint<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
+    cfg: &crate::config::Config,
+    ip: &str,
+    headers_trusted: bool,
+) -> Option<Response> {
+    if !cfg.fingerprint_signal_enabled {
+        return None;
+    }
+    let (ja3, ja4) = crate::signals::fingerprint::trusted_tls_fingerprints(req, headers_trusted);
+    let details =
+        crate::signals::tls_reputation::evaluate(cfg, ja3.as_deref(), ja4.as_deref())?;
+    crate::observability::monitoring::record_tls_fingerprint_hit(store, &details);
+    if details.action != crate::config::TlsFingerprintAction::Block {
+        return None;
+    }
+
+    let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
+        crate::runtime::policy_taxonomy::PolicyTransition::TlsFingerprintBlock,
+    );
+    crate::observability::metrics::record_policy_match(store, &policy_match);
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::BlocksTotal,
+        None,
+    );
+    crate::admin::log_event(
+        store,
+        &crate::admin::EventLogEntry {
+            ts: crate::admin::now_ts(),
+            event: crate::admin::EventType::Block,
+            ip: Some(ip.to_string()),
+            reason: Some("tls_fingerprint_block".to_string()),
+            outcome: Some(
+                policy_match.annotate_outcome(
+                    format!(
+                        "source={} source_id={} {}={}",
+                        details.source.as_str(),
+                        details.source_id,
+                        details.kind.as_str(),
+                        details.fingerprint
+                    )
+                    .as_str(),
+                ),
+            ),
+            admin: None,
+        },
+    );
+    Some(Response::new(
+        403,
+        crate::enforcement::block_page::render_block_page(
+            crate::enforcement::block_page::BlockReason::ClientFingerprint,
+            &crate::i18n::Messages::for_request(req, cfg),
+        ),
+    ))
+}
+
+pub(crate) fn compute_needs_js<S: crate::challenge::KeyValueStore>(
     req: &Request,
-    store: &Store,
+    store: &S,
     cfg: &crate::config::Config,
     site_id: &str,
     path: &str,
@@ -991,16 +1429,16 @@ pub(crate) fn compute_needs_js(
     js_missing_verification
 }
 
-pub(crate) fn maybe_handle_botness(
+pub(crate) fn assess_botness<S: crate::challenge::KeyValueStore>(
     req: &Request,
-    store: &Store,
+    store: &S,
     cfg: &crate::config::Config,
     provider_registry: &crate::providers::registry::ProviderRegistry,
     site_id: &str,
     ip: &str,
     needs_js: bool,
     geo_assessment: &crate::GeoAssessment,
-) -> Option<Response> {
+) -> crate::BotnessAssessment {
     let geo_risk = geo_assessment.scored_risk;
     let geo_signal_available = geo_assessment.headers_trusted && geo_assessment.country.is_some();
     let rate_usage = provider_registry
@@ -1014,6 +1452,55 @@ pub(crate) fn maybe_handle_botness(
         ip,
         geo_assessment.headers_trusted,
     );
+    let behavior_signals = crate::signals::behavior::collect_bot_signals(
+        store,
+        req,
+        cfg,
+        ip,
+        crate::signals::behavior::now_ms(),
+    );
+    let privacy_pass =
+        crate::signals::privacy_pass::redeem(store, req, cfg, crate::admin::now_ts());
+    if privacy_pass != crate::signals::privacy_pass::RedemptionOutcome::Absent {
+        crate::observability::metrics::increment(
+            store,
+            crate::observability::metrics::MetricName::PrivacyPassRedemptions,
+            Some(privacy_pass.as_str()),
+        );
+    }
+    let robots_violation = crate::crawler_policy::compliance::observe_request(
+        store,
+        cfg,
+        ip,
+        req.header("user-agent")
+            .and_then(|v| v.as_str())
+            .unwrap_or(""),
+        req.path(),
+        crate::admin::now_ts(),
+    );
+    if let Some(violation) = robots_violation.as_ref().filter(|violation| violation.first) {
+        crate::observability::metrics::increment(
+            store,
+            crate::observability::metrics::MetricName::RobotsTxtCompliance,
+            Some("violation"),
+        );
+        crate::admin::log_event(
+            store,
+            &crate::admin::EventLogEntry {
+                ts: crate::admin::now_ts(),
+                event: crate::admin::EventType::Challenge,
+                ip: Some(ip.to_string()),
+                reason: Some("robots_violation".to_string()),
+                outcome: Some(format!(
+                    "family={} path={} robots_fetched_at={}",
+                    violation.family,
+                    req.path(),
+                    violation.fetched_at
+                )),
+                admin: None,
+            },
+        );
+    }
     let botness = crate::compute_botness_assessment(
         crate::BotnessSignalContext {
             js_needed: needs_js,
@@ -1023,12 +1510,26 @@ pub(crate) fn maybe_handle_botness(
             rate_limit: cfg.rate_limit,
             maze_behavior_score,
             fingerprint_signals,
+            behavior_signals,
+            privacy_pass,
+            robots_violation: robots_violation.is_some(),
         },
         cfg,
     );
     crate::observability::metrics::record_botness_visibility(store, cfg, &botness);
-    let botness_summary = crate::botness_signals_summary(&botness);
-    let botness_state_summary = crate::botness_signal_states_summary(&botness);
+    botness
+}
+
+pub(crate) fn maybe_handle_botness<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
+    cfg: &crate::config::Config,
+    provider_registry: &crate::providers::registry::ProviderRegistry,
+    ip: &str,
+    botness: &crate::BotnessAssessment,
+) -> Option<Response> {
+    let botness_summary = crate::botness_signals_summary(botness);
+    let botness_state_summary = crate::botness_signal_states_summary(botness);
     let runtime_metadata_summary = crate::defence_runtime_metadata_summary(cfg);
     let provider_summary = crate::provider_implementations_summary(provider_registry);
     let ua = req
@@ -1039,7 +1540,7 @@ pub(crate) fn maybe_handle_botness(
         "score={} signals={} signal_states={} {} providers={}",
         botness.score, botness_summary, botness_state_summary, runtime_metadata_summary, provider_summary
     );
-    let botness_signal_ids = active_botness_signal_ids(&botness);
+    let botness_signal_ids = active_botness_signal_ids(botness);
 
     if cfg.maze_enabled && botness.score >= cfg.botness_maze_threshold {
         let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
@@ -1085,7 +1586,12 @@ pub(crate) fn maybe_handle_botness(
         && botness.score >= not_a_bot_threshold
         && botness.score < cfg.challenge_puzzle_risk_threshold
     {
-        if crate::challenge::has_valid_not_a_bot_marker(req, ip, ua) {
+        if crate::challenge::clearance::has_clearance_at_least(
+            req,
+            ip,
+            ua,
+            crate::challenge::clearance::ClearanceLevel::NotABot,
+        ) {
             return None;
         }
         let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
@@ -1116,9 +1622,13 @@ pub(crate) fn maybe_handle_botness(
                 admin: None,
             },
         );
-        let not_a_bot_response = provider_registry
-            .challenge_engine_provider()
-            .render_not_a_bot(req, cfg);
+        let not_a_bot_response = crate::signals::privacy_pass::with_token_challenge(
+            req,
+            cfg,
+            provider_registry
+                .challenge_engine_provider()
+                .render_not_a_bot(req, cfg),
+        );
         let response = crate::maze::covert_decoy::maybe_inject_non_maze_decoy(
             req,
             cfg,
@@ -1132,6 +1642,14 @@ pub(crate) fn maybe_handle_botness(
 
     if botness.score >= cfg.challenge_puzzle_risk_threshold {
         if cfg.challenge_puzzle_enabled {
+            if crate::challenge::clearance::has_clearance_at_least(
+                req,
+                ip,
+                ua,
+                crate::challenge::clearance::ClearanceLevel::ChallengeStrong,
+            ) {
+                return None;
+            }
             let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
                 crate::runtime::policy_taxonomy::PolicyTransition::BotnessGateChallenge(
                     botness_signal_ids,
@@ -1159,9 +1677,13 @@ pub(crate) fn maybe_handle_botness(
                     admin: None,
                 },
             );
-            let challenge_response = provider_registry
-                .challenge_engine_provider()
-                .render_challenge(req, cfg.challenge_puzzle_transform_count as usize);
+            let challenge_response = crate::signals::privacy_pass::with_token_challenge(
+                req,
+                cfg,
+                provider_registry
+                    .challenge_engine_provider()
+                    .render_challenge(req, cfg),
+            );
             let response = crate::maze::covert_decoy::maybe_inject_non_maze_decoy(
                 req,
                 cfg,
@@ -1226,6 +1748,7 @@ pub(crate) fn maybe_handle_botness(
             403,
             crate::enforcement::block_page::render_block_page(
                 crate::enforcement::block_page::BlockReason::GeoPolicy,
+                &crate::i18n::Messages::for_request(req, cfg),
             ),
         ));
     }
@@ -1233,17 +1756,22 @@ pub(crate) fn maybe_handle_botness(
     None
 }
 
-pub(crate) fn maybe_handle_js(
-    store: &Store,
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn maybe_handle_js<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    store: &S,
     cfg: &crate::config::Config,
     ip: &str,
     user_agent: &str,
     needs_js: bool,
+    botness_score: u8,
+    privacy_pass_attested: bool,
 ) -> Option<Response> {
     if !cfg.js_action_enabled() {
         return None;
     }
-    if !needs_js {
+    // A redeemed Privacy Pass token is already proof of a real browser from its issuer.
+    if !needs_js || privacy_pass_attested {
         return None;
     }
     let policy_match = crate::runtime::policy_taxonomy::resolve_policy_match(
@@ -1266,13 +1794,20 @@ pub(crate) fn maybe_handle_js(
             admin: None,
         },
     );
-    Some(crate::signals::js_verification::inject_js_challenge(
+    let js_response = crate::signals::js_verification::inject_js_challenge(
         ip,
         user_agent,
+        crate::challenge::clearance::request_clearance_level(req, ip, user_agent),
         cfg.pow_enabled,
-        cfg.pow_difficulty,
+        crate::challenge::pow::pow_params_for_issue(store, cfg, ip, botness_score),
         cfg.pow_ttl_seconds,
         cfg.cdp_probe_family,
         cfg.cdp_probe_rollout_percent,
+        &crate::i18n::Messages::for_request(req, cfg),
+    );
+    Some(crate::signals::privacy_pass::with_token_challenge(
+        req,
+        cfg,
+        js_response,
     ))
 }
@@ -106,6 +106,8 @@ pub enum SignalId {
     RateUsageHigh,
     RateLimitHit,
     HoneypotHit,
+    FormHoneypotFilled,
+    FormSubmitTooFast,
     GeoRisk,
     GeoRouteChallenge,
     GeoRouteMaze,
@@ -116,11 +118,19 @@ pub enum SignalId {
     CdpReportMedium,
     CdpReportStrong,
     FingerprintUaHintMismatch,
+    FingerprintHeaderShapeMismatch,
     FingerprintUaTransportMismatch,
     FingerprintTemporalTransition,
     FingerprintFlowViolation,
     FingerprintPersistenceMissing,
     FingerprintUntrustedHeader,
+    FingerprintTlsReputation,
+    BehaviorTimingRegular,
+    BehaviorAssetRatio,
+    BehaviorReferrerChain,
+    BehaviorBreadthFirst,
+    BehaviorSitemapOrder,
+    RobotsViolation,
     EdgeFingerprintAdvisory,
     EdgeFingerprintStrong,
     EdgeFingerprintAuthoritativeBan,
@@ -138,6 +148,8 @@ pub enum SignalId {
     TarpitPersistence,
     IpRangeCustom,
     IpRangeManaged,
+    AiCrawlerDeclared,
+    WebBotAuthVerified,
 }
 
 impl SignalId {
@@ -160,6 +172,8 @@ impl SignalId {
             SignalId::RateUsageHigh => "S_RATE_USAGE_HIGH",
             SignalId::RateLimitHit => "S_RATE_LIMIT_HIT",
             SignalId::HoneypotHit => "S_HONEYPOT_HIT",
+            SignalId::FormHoneypotFilled => "S_FORM_HONEYPOT_FILLED",
+            SignalId::FormSubmitTooFast => "S_FORM_SUBMIT_TOO_FAST",
             SignalId::GeoRisk => "S_GEO_RISK",
             SignalId::GeoRouteChallenge => "S_GEO_ROUTE_CHALLENGE",
             SignalId::GeoRouteMaze => "S_GEO_ROUTE_MAZE",
@@ -170,11 +184,19 @@ impl SignalId {
             SignalId::CdpReportMedium => "S_CDP_REPORT_MEDIUM",
             SignalId::CdpReportStrong => "S_CDP_REPORT_STRONG",
             SignalId::FingerprintUaHintMismatch => "S_FP_UA_HINT_MISMATCH",
+            SignalId::FingerprintHeaderShapeMismatch => "S_FP_HEADER_SHAPE_MISMATCH",
             SignalId::FingerprintUaTransportMismatch => "S_FP_UA_TRANSPORT_MISMATCH",
             SignalId::FingerprintTemporalTransition => "S_FP_TEMPORAL_TRANSITION",
             SignalId::FingerprintFlowViolation => "S_FP_FLOW_VIOLATION",
             SignalId::FingerprintPersistenceMissing => "S_FP_PERSISTENCE_MISSING",
             SignalId::FingerprintUntrustedHeader => "S_FP_UNTRUSTED_HEADER",
+            SignalId::FingerprintTlsReputation => "S_FP_TLS_REPUTATION",
+            SignalId::BehaviorTimingRegular => "S_BEHAVIOR_TIMING_REGULAR",
+            SignalId::BehaviorAssetRatio => "S_BEHAVIOR_ASSET_RATIO",
+            SignalId::BehaviorReferrerChain => "S_BEHAVIOR_REFERRER_CHAIN",
+            SignalId::BehaviorBreadthFirst => "S_BEHAVIOR_BREADTH_FIRST",
+            SignalId::BehaviorSitemapOrder => "S_BEHAVIOR_SITEMAP_ORDER",
+            SignalId::RobotsViolation => "S_ROBOTS_VIOLATION",
             SignalId::EdgeFingerprintAdvisory => "S_FP_EDGE_ADVISORY",
             SignalId::EdgeFingerprintStrong => "S_FP_EDGE_STRONG",
             SignalId::EdgeFingerprintAuthoritativeBan => "S_FP_EDGE_AUTHORITATIVE_BAN",
@@ -192,6 +214,8 @@ impl SignalId {
             SignalId::TarpitPersistence => "S_TARPIT_PERSISTENCE",
             SignalId::IpRangeCustom => "S_IP_RANGE_CUSTOM",
             SignalId::IpRangeManaged => "S_IP_RANGE_MANAGED",
+            SignalId::AiCrawlerDeclared => "S_AI_CRAWLER_DECLARED",
+            SignalId::WebBotAuthVerified => "S_WEB_BOT_AUTH_VERIFIED",
         }
     }
 }
@@ -201,6 +225,7 @@ impl SignalId {
 pub enum DetectionId {
     AllowClean,
     HoneypotHit,
+    FormHoneypotHit,
     RateLimitHit,
     ExistingBan,
     BrowserOutdated,
@@ -234,6 +259,7 @@ pub enum DetectionId {
     FingerprintFlowViolation,
     FingerprintPersistenceMissing,
     FingerprintUntrustedHeader,
+    FingerprintTlsBlock,
     EdgeFingerprintAdvisory,
     EdgeFingerprintStrong,
     EdgeFingerprintAuthoritativeBan,
@@ -256,6 +282,13 @@ pub enum DetectionId {
     IpRangeHoneypot,
     IpRangeMaze,
     IpRangeTarpit,
+    AiCrawlerBlock,
+    AiCrawlerMaze,
+    AiCrawlerPaymentRequired,
+    WebBotAuthAllow,
+    WebBotAuthRateLimit,
+    WebBotAuthPaymentRequired,
+    CrawlPaymentAccepted,
 }
 
 impl DetectionId {
@@ -263,6 +296,7 @@ impl DetectionId {
         match self {
             DetectionId::AllowClean => "D_ALLOW_CLEAN",
             DetectionId::HoneypotHit => "D_HONEYPOT_HIT",
+            DetectionId::FormHoneypotHit => "D_FORM_HONEYPOT_HIT",
             DetectionId::RateLimitHit => "D_RATE_LIMIT_HIT",
             DetectionId::ExistingBan => "D_EXISTING_BAN",
             DetectionId::BrowserOutdated => "D_BROWSER_OUTDATED",
@@ -298,6 +332,7 @@ impl DetectionId {
             DetectionId::FingerprintFlowViolation => "D_FP_FLOW_VIOLATION",
             DetectionId::FingerprintPersistenceMissing => "D_FP_PERSISTENCE_MISSING",
             DetectionId::FingerprintUntrustedHeader => "D_FP_UNTRUSTED_HEADER",
+            DetectionId::FingerprintTlsBlock => "D_FP_TLS_BLOCK",
             DetectionId::EdgeFingerprintAdvisory => "D_EDGE_FP_ADVISORY",
             DetectionId::EdgeFingerprintStrong => "D_EDGE_FP_STRONG",
             DetectionId::EdgeFingerprintAuthoritativeBan => "D_EDGE_FP_AUTHORITATIVE_BAN",
@@ -320,6 +355,13 @@ impl DetectionId {
             DetectionId::IpRangeHoneypot => "D_IP_RANGE_HONEYPOT",
             DetectionId::IpRangeMaze => "D_IP_RANGE_MAZE",
             DetectionId::IpRangeTarpit => "D_IP_RANGE_TARPIT",
+            DetectionId::AiCrawlerBlock => "D_AI_CRAWLER_BLOCK",
+            DetectionId::AiCrawlerMaze => "D_AI_CRAWLER_MAZE",
+            DetectionId::AiCrawlerPaymentRequired => "D_AI_CRAWLER_PAYMENT_REQUIRED",
+            DetectionId::WebBotAuthAllow => "D_WEB_BOT_AUTH_ALLOW",
+            DetectionId::WebBotAuthRateLimit => "D_WEB_BOT_AUTH_RATE_LIMIT",
+            DetectionId::WebBotAuthPaymentRequired => "D_WEB_BOT_AUTH_PAYMENT_REQUIRED",
+            DetectionId::CrawlPaymentAccepted => "D_CRAWL_PAYMENT_ACCEPTED",
         }
     }
 }
@@ -360,6 +402,14 @@ impl PolicyMatch {
         self.detection.as_str()
     }
 
+    /// True when the matched action lets the request through (allow, tagged allow or monitor).
+    pub fn is_allow_class(&self) -> bool {
+        matches!(
+            self.action,
+            ActionId::Allow | ActionId::AllowTagged | ActionId::Monitor
+        )
+    }
+
     pub fn signal_ids(&self) -> Vec<&'static str> {
         self.signal_ids
             .iter()
@@ -386,6 +436,7 @@ impl PolicyMatch {
 pub enum PolicyTransition {
     AllowClean,
     HoneypotHit,
+    FormHoneypotHit(Vec<SignalId>),
     RateLimitHit,
     ExistingBan,
     BrowserOutdated,
@@ -413,6 +464,7 @@ pub enum PolicyTransition {
     EdgeFingerprintAdvisory,
     EdgeFingerprintStrong,
     EdgeFingerprintAuthoritativeBan,
+    TlsFingerprintBlock,
     SeqBindingMismatch,
     SeqOrderViolation,
     SeqWindowExceeded,
@@ -435,6 +487,13 @@ pub enum PolicyTransition {
     IpRangeHoneypot(Vec<SignalId>),
     IpRangeMaze(Vec<SignalId>),
     IpRangeTarpit(Vec<SignalId>),
+    AiCrawlerBlock,
+    AiCrawlerMaze,
+    AiCrawlerPaymentRequired,
+    WebBotAuthAllow,
+    WebBotAuthRateLimit,
+    WebBotAuthPaymentRequired,
+    CrawlPaymentAccepted(Vec<SignalId>),
 }
 
 pub fn resolve_policy_match(transition: PolicyTransition) -> PolicyMatch {
@@ -449,6 +508,11 @@ pub fn resolve_policy_match(transition: PolicyTransition) -> PolicyMatch {
             DetectionId::HoneypotHit,
             vec![SignalId::HoneypotHit],
         ),
+        PolicyTransition::FormHoneypotHit(signals) => PolicyMatch::new(
+            EscalationLevelId::L10DenyTemp,
+            DetectionId::FormHoneypotHit,
+            signals,
+        ),
         PolicyTransition::RateLimitHit => PolicyMatch::new(
             EscalationLevelId::L10DenyTemp,
             DetectionId::RateLimitHit,
@@ -649,6 +713,11 @@ pub fn resolve_policy_match(transition: PolicyTransition) -> PolicyMatch {
             DetectionId::MazeThresholdBan,
             vec![SignalId::MazeThreshold],
         ),
+        PolicyTransition::TlsFingerprintBlock => PolicyMatch::new(
+            EscalationLevelId::L10DenyTemp,
+            DetectionId::FingerprintTlsBlock,
+            vec![SignalId::FingerprintTlsReputation],
+        ),
         PolicyTransition::IpRangeAdvisory(signals) => PolicyMatch::new(
             EscalationLevelId::L2Monitor,
             DetectionId::IpRangeAdvisory,
@@ -694,6 +763,41 @@ pub fn resolve_policy_match(transition: PolicyTransition) -> PolicyMatch {
             DetectionId::IpRangeTarpit,
             signals,
         ),
+        PolicyTransition::AiCrawlerBlock => PolicyMatch::new(
+            EscalationLevelId::L10DenyTemp,
+            DetectionId::AiCrawlerBlock,
+            vec![SignalId::AiCrawlerDeclared],
+        ),
+        PolicyTransition::AiCrawlerMaze => PolicyMatch::new(
+            EscalationLevelId::L7DeceptionExplicit,
+            DetectionId::AiCrawlerMaze,
+            vec![SignalId::AiCrawlerDeclared],
+        ),
+        PolicyTransition::AiCrawlerPaymentRequired => PolicyMatch::new(
+            EscalationLevelId::L9CostImposition,
+            DetectionId::AiCrawlerPaymentRequired,
+            vec![SignalId::AiCrawlerDeclared],
+        ),
+        PolicyTransition::WebBotAuthAllow => PolicyMatch::new(
+            EscalationLevelId::L1AllowTagged,
+            DetectionId::WebBotAuthAllow,
+            vec![SignalId::WebBotAuthVerified],
+        ),
+        PolicyTransition::WebBotAuthRateLimit => PolicyMatch::new(
+            EscalationLevelId::L10DenyTemp,
+            DetectionId::WebBotAuthRateLimit,
+            vec![SignalId::WebBotAuthVerified],
+        ),
+        PolicyTransition::WebBotAuthPaymentRequired => PolicyMatch::new(
+            EscalationLevelId::L9CostImposition,
+            DetectionId::WebBotAuthPaymentRequired,
+            vec![SignalId::WebBotAuthVerified],
+        ),
+        PolicyTransition::CrawlPaymentAccepted(signals) => PolicyMatch::new(
+            EscalationLevelId::L1AllowTagged,
+            DetectionId::CrawlPaymentAccepted,
+            signals,
+        ),
     }
 }
 
@@ -714,11 +818,19 @@ pub fn signal_id_for_botness_key(key: &str) -> Option<SignalId> {
         "rate_pressure_high" => Some(SignalId::RateUsageHigh),
         "maze_behavior" => Some(SignalId::MazeTraversal),
         "fp_ua_ch_mismatch" => Some(SignalId::FingerprintUaHintMismatch),
+        "fp_header_shape_mismatch" => Some(SignalId::FingerprintHeaderShapeMismatch),
         "fp_ua_transport_mismatch" => Some(SignalId::FingerprintUaTransportMismatch),
         "fp_temporal_transition" => Some(SignalId::FingerprintTemporalTransition),
         "fp_flow_violation" => Some(SignalId::FingerprintFlowViolation),
         "fp_persistence_marker_missing" => Some(SignalId::FingerprintPersistenceMissing),
         "fp_untrusted_transport_header" => Some(SignalId::FingerprintUntrustedHeader),
+        "fp_tls_reputation" => Some(SignalId::FingerprintTlsReputation),
+        "behavior_timing_regular" => Some(SignalId::BehaviorTimingRegular),
+        "behavior_asset_ratio" => Some(SignalId::BehaviorAssetRatio),
+        "behavior_referrer_chain" => Some(SignalId::BehaviorReferrerChain),
+        "behavior_breadth_first" => Some(SignalId::BehaviorBreadthFirst),
+        "behavior_sitemap_order" => Some(SignalId::BehaviorSitemapOrder),
+        "robots_violation" => Some(SignalId::RobotsViolation),
         _ => None,
     }
 }
@@ -778,6 +890,18 @@ mod tests {
                 .as_str(),
             "S_FP_TEMPORAL_TRANSITION"
         );
+        assert_eq!(
+            signal_id_for_botness_key("fp_tls_reputation")
+                .expect("known signal")
+                .as_str(),
+            "S_FP_TLS_REPUTATION"
+        );
+        assert_eq!(
+            signal_id_for_botness_key("behavior_sitemap_order")
+                .expect("known signal")
+                .as_str(),
+            "S_BEHAVIOR_SITEMAP_ORDER"
+        );
         assert!(signal_id_for_botness_key("unknown").is_none());
     }
 
@@ -22,6 +22,22 @@ fn record_sequence_violation_for_challenge_submit(
     );
 }
 
+fn accessible_challenge_outcome_label(
+    outcome: crate::boundaries::ChallengeSubmitOutcome,
+) -> &'static str {
+    match outcome {
+        crate::boundaries::ChallengeSubmitOutcome::Solved => "solved",
+        crate::boundaries::ChallengeSubmitOutcome::Incorrect => "incorrect",
+        crate::boundaries::ChallengeSubmitOutcome::SequenceOpExpired
+        | crate::boundaries::ChallengeSubmitOutcome::SequenceOpReplay
+        | crate::boundaries::ChallengeSubmitOutcome::SequenceWindowExceeded => "expired_replay",
+        crate::boundaries::ChallengeSubmitOutcome::Forbidden => "forbidden",
+        crate::boundaries::ChallengeSubmitOutcome::InvalidOutput => "invalid_output",
+        crate::boundaries::ChallengeSubmitOutcome::RateLimited => "rate_limited",
+        _ => "sequence_violation",
+    }
+}
+
 fn handle_not_a_bot_submit(
     store: &Store,
     req: &Request,
@@ -45,6 +61,16 @@ fn handle_not_a_bot_submit(
         monitoring_outcome,
         submit_result.solve_ms,
     );
+    if let (crate::challenge::NotABotSubmitOutcome::Pass, Some(solve_ms)) =
+        (&submit_result.outcome, submit_result.solve_ms)
+    {
+        crate::observability::metrics::observe(
+            store,
+            crate::observability::metrics::HistogramName::ChallengeSolveSeconds,
+            Some("not_a_bot"),
+            solve_ms as f64 / 1000.0,
+        );
+    }
 
     match submit_result.decision {
         crate::challenge::NotABotDecision::Pass => {
@@ -72,8 +98,8 @@ fn handle_not_a_bot_submit(
             builder.status(303);
             builder.header("Location", submit_result.return_to.as_str());
             builder.header("Cache-Control", "no-store");
-            if let Some(marker_cookie) = submit_result.marker_cookie {
-                builder.header("Set-Cookie", marker_cookie.as_str());
+            if let Some(clearance_cookie) = submit_result.clearance_cookie {
+                builder.header("Set-Cookie", clearance_cookie.as_str());
             }
             builder.body(Vec::new()).build()
         }
@@ -107,7 +133,7 @@ fn handle_not_a_bot_submit(
                 );
                 return provider_registry
                     .challenge_engine_provider()
-                    .render_challenge(req, cfg.challenge_puzzle_transform_count as usize);
+                    .render_challenge(req, cfg);
             }
             if cfg.maze_enabled {
                 return provider_registry
@@ -128,6 +154,7 @@ fn handle_not_a_bot_submit(
                 403,
                 crate::enforcement::block_page::render_block_page(
                     crate::enforcement::block_page::BlockReason::GeoPolicy,
+                    &crate::i18n::Messages::for_request(req, cfg),
                 ),
             )
         }
@@ -174,13 +201,69 @@ fn handle_not_a_bot_submit(
                 403,
                 crate::enforcement::block_page::render_block_page(
                     crate::enforcement::block_page::BlockReason::GeoPolicy,
+                    &crate::i18n::Messages::for_request(req, cfg),
                 ),
             )
         }
     }
 }
 
+/// Requests answered by `maybe_handle_early_route`, before any policy runs. Replay uses this
+/// to skip the same routes without running their store-backed handlers.
+pub(crate) fn is_early_route(req: &Request, path: &str) -> bool {
+    let method = req.method();
+    let page_method = *method == Method::Get || *method == Method::Head;
+    let challenge_method = *method == Method::Get || *method == Method::Post;
+    crate::maze::assets::maybe_handle_asset(path, method).is_some()
+        || (path == "/dashboard" && page_method)
+        || matches!(
+            path,
+            "/health"
+                | "/metrics"
+                | "/robots.txt"
+                | "/ai.txt"
+                | "/llms.txt"
+                | "/.well-known/tdmrep.json"
+        )
+        || (challenge_method
+            && (path == crate::boundaries::challenge_not_a_bot_path()
+                || path == crate::boundaries::challenge_puzzle_path()
+                || path == crate::boundaries::challenge_puzzle_accessible_path()))
+        || path.starts_with("/admin")
+}
+
+/// Renders robots.txt and records the fetch for robots compliance scoring.
+pub(crate) fn record_robots_txt_fetch<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    cfg: &crate::config::Config,
+    req: &Request,
+    ip: &str,
+) -> String {
+    let now = crate::admin::now_ts();
+    let content = crate::crawler_policy::robots::generate_robots_txt(cfg, now);
+    if crate::crawler_policy::compliance::record_fetch(
+        store,
+        cfg,
+        ip,
+        req.header("user-agent")
+            .and_then(|v| v.as_str())
+            .unwrap_or(""),
+        content.as_str(),
+        now,
+    ) {
+        crate::observability::metrics::increment(
+            store,
+            crate::observability::metrics::MetricName::RobotsTxtCompliance,
+            Some("tracked"),
+        );
+    }
+    content
+}
+
 pub(crate) fn maybe_handle_early_route(req: &Request, path: &str) -> Option<Response> {
+    if !is_early_route(req, path) {
+        return None;
+    }
     if let Some(response) = crate::maze::assets::maybe_handle_asset(path, req.method()) {
         return Some(response);
     }
@@ -275,11 +358,28 @@ pub(crate) fn maybe_handle_early_route(req: &Request, path: &str) -> Option<Resp
         return Some(Response::new(500, "Key-value store error"));
     }
 
-    // Challenge POST handler
-    if path == crate::boundaries::challenge_puzzle_path() && *req.method() == Method::Post {
+    // Challenge POST handler; the accessible variant shares outcome handling with the grid.
+    let accessible_path = path == crate::boundaries::challenge_puzzle_accessible_path();
+    if (path == crate::boundaries::challenge_puzzle_path() || accessible_path)
+        && *req.method() == Method::Post
+    {
         if let Ok(store) = Store::open_default() {
-            let (response, outcome) =
-                crate::boundaries::handle_challenge_submit_with_outcome(&store, req);
+            let cfg = match crate::load_runtime_config(&store, "default", path) {
+                Ok(cfg) => cfg,
+                Err(resp) => return Some(resp),
+            };
+            let (response, outcome) = if accessible_path {
+                crate::boundaries::handle_accessible_submit_with_outcome(&store, req, &cfg)
+            } else {
+                crate::boundaries::handle_challenge_submit_with_outcome(&store, req, &cfg)
+            };
+            if accessible_path {
+                crate::observability::metrics::increment(
+                    &store,
+                    crate::observability::metrics::MetricName::ChallengeAccessibleOutcomes,
+                    Some(accessible_challenge_outcome_label(outcome)),
+                );
+            }
             let challenge_ip = crate::extract_client_ip(req);
             match outcome {
                 crate::boundaries::ChallengeSubmitOutcome::Solved => {
@@ -446,7 +546,8 @@ pub(crate) fn maybe_handle_early_route(req: &Request, path: &str) -> Option<Resp
                         "challenge_submit_timing_too_slow",
                     );
                 }
-                crate::boundaries::ChallengeSubmitOutcome::Forbidden => {
+                crate::boundaries::ChallengeSubmitOutcome::Forbidden
+                | crate::boundaries::ChallengeSubmitOutcome::RateLimited => {
                     crate::observability::monitoring::record_challenge_failure(
                         &store,
                         challenge_ip.as_str(),
@@ -465,17 +566,31 @@ pub(crate) fn maybe_handle_early_route(req: &Request, path: &str) -> Option<Resp
         }
         return Some(Response::new(500, "Key-value store error"));
     }
+    if accessible_path && *req.method() == Method::Get {
+        if let Ok(store) = Store::open_default() {
+            let cfg = match crate::load_runtime_config(&store, "default", path) {
+                Ok(cfg) => cfg,
+                Err(resp) => return Some(resp),
+            };
+            let response = crate::boundaries::serve_accessible_challenge_page(req, &cfg);
+            if *response.status() == 200 {
+                crate::observability::metrics::increment(
+                    &store,
+                    crate::observability::metrics::MetricName::ChallengeAccessibleServedTotal,
+                    None,
+                );
+            }
+            return Some(response);
+        }
+        return Some(Response::new(500, "Key-value store error"));
+    }
     if path == crate::boundaries::challenge_puzzle_path() && *req.method() == Method::Get {
         if let Ok(store) = Store::open_default() {
             let cfg = match crate::load_runtime_config(&store, "default", path) {
                 Ok(cfg) => cfg,
                 Err(resp) => return Some(resp),
             };
-            let response = crate::boundaries::serve_challenge_page(
-                req,
-                cfg.test_mode,
-                cfg.challenge_puzzle_transform_count as usize,
-            );
+            let response = crate::boundaries::serve_challenge_page(req, cfg.test_mode, &cfg);
             if *response.status() == 200 {
                 crate::observability::metrics::increment(
                     &store,
@@ -509,23 +624,59 @@ pub(crate) fn maybe_handle_early_route(req: &Request, path: &str) -> Option<Resp
                     crate::observability::metrics::MetricName::RequestsTotal,
                     Some("robots_txt"),
                 );
-                let content = crate::crawler_policy::robots::generate_robots_txt(&cfg);
-                let content_signal = crate::crawler_policy::robots::get_content_signal_header(&cfg);
-                return Some(
-                    Response::builder()
-                        .status(200)
-                        .header("Content-Type", "text/plain; charset=utf-8")
-                        .header("Content-Signal", content_signal)
-                        .header("Cache-Control", "public, max-age=3600")
-                        .body(content)
-                        .build(),
+                let content = record_robots_txt_fetch(
+                    &store,
+                    &cfg,
+                    req,
+                    crate::extract_client_ip(req).as_str(),
                 );
+                return Some(ai_policy_file_response(
+                    &cfg,
+                    "text/plain; charset=utf-8",
+                    content,
+                ));
             }
         }
         // If disabled or store error, return 404
         return Some(Response::new(404, "Not Found"));
     }
 
+    // ai.txt, llms.txt and TDMRep - same AI policy as robots.txt
+    if matches!(path, "/ai.txt" | "/llms.txt" | "/.well-known/tdmrep.json") {
+        if let Ok(store) = Store::open_default() {
+            let cfg = match crate::load_runtime_config(&store, "default", path) {
+                Ok(cfg) => cfg,
+                Err(resp) => return Some(resp),
+            };
+            if cfg.robots_enabled {
+                let (label, content_type, content) = match path {
+                    "/ai.txt" => (
+                        "ai_txt",
+                        "text/plain; charset=utf-8",
+                        crate::crawler_policy::policy_files::generate_ai_txt(&cfg),
+                    ),
+                    "/llms.txt" => (
+                        "llms_txt",
+                        "text/markdown; charset=utf-8",
+                        crate::crawler_policy::policy_files::generate_llms_txt(&cfg),
+                    ),
+                    _ => (
+                        "tdmrep_json",
+                        "application/json",
+                        crate::crawler_policy::policy_files::generate_tdmrep_json(&cfg),
+                    ),
+                };
+                crate::observability::metrics::increment(
+                    &store,
+                    crate::observability::metrics::MetricName::RequestsTotal,
+                    Some(label),
+                );
+                return Some(ai_policy_file_response(&cfg, content_type, content));
+            }
+        }
+        return Some(Response::new(404, "Not Found"));
+    }
+
     // Admin API
     if path.starts_with("/admin") {
         if req.method() == &Method::Options {
@@ -537,5 +688,29 @@ pub(crate) fn maybe_handle_early_route(req: &Request, path: &str) -> Option<Resp
     None
 }
 
+fn ai_policy_file_response(
+    cfg: &crate::config::Config,
+    content_type: &str,
+    content: String,
+) -> Response {
+    let mut builder = Response::builder();
+    builder
+        .status(200)
+        .header("Content-Type", content_type)
+        .header(
+            "Content-Signal",
+            crate::crawler_policy::robots::get_content_signal_header(cfg),
+        )
+        .header(
+            "tdm-reservation",
+            crate::crawler_policy::policy_files::tdm_reservation_header(cfg),
+        )
+        .header("Cache-Control", "public, max-age=3600");
+    if let Some(policy) = crate::crawler_policy::policy_files::tdm_policy_header(cfg) {
+        builder.header("tdm-policy", policy);
+    }
+    builder.body(content).build()
+}
+
 #[cfg(test)]
 mod tests;
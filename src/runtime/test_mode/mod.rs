@@ -115,7 +115,9 @@ where
         }
     }
 
-    if cfg.honeypot_enabled && crate::enforcement::honeypot::is_honeypot(path, &cfg.honeypots) {
+    if cfg.honeypot_enabled
+        && crate::enforcement::honeypot::is_trap_path(cfg, path, crate::admin::now_ts())
+    {
         crate::log_line(&format!("[TEST MODE] Would ban IP {ip} for honeypot"));
         log_test_mode_event(
             store,
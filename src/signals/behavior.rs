@@ -0,0 +1,852 @@
+use serde::{Deserialize, Serialize};
+use spin_sdk::http::{Method, Request};
+
+use crate::signals::botness::{BotSignal, SignalFamily, SignalProvenance};
+
+const BEHAVIOR_TIMING_REGULAR_KEY: &str = "behavior_timing_regular";
+const BEHAVIOR_ASSET_RATIO_KEY: &str = "behavior_asset_ratio";
+const BEHAVIOR_REFERRER_CHAIN_KEY: &str = "behavior_referrer_chain";
+const BEHAVIOR_BREADTH_FIRST_KEY: &str = "behavior_breadth_first";
+const BEHAVIOR_SITEMAP_ORDER_KEY: &str = "behavior_sitemap_order";
+
+const BEHAVIOR_KEY_PREFIX_STATE: &str = "behavior:state:";
+const BEHAVIOR_KEY_PREFIX_ASSETS: &str = "behavior:assets:";
+const BEHAVIOR_ASSET_SWEEP_MARKER_KEY: &str = "behavior:assets_sweep:last";
+/// Asset counters are bucketed by hour; a session credits the current and previous bucket.
+const ASSET_BUCKET_SECONDS: u64 = 3600;
+
+/// Page views needed in the current window before any behavioural feature is scored.
+const MIN_PAGES_FOR_SCORING: u32 = 8;
+const MAX_TRACKED_INTERVALS: usize = 16;
+const MAX_TRACKED_PATHS: usize = 16;
+const MAX_STORED_PATH_CHARS: usize = 128;
+/// Window counters are halved at this ceiling so old traffic decays out of the ratios.
+const COUNTER_DECAY_CEILING: u32 = 64;
+const TIMING_MAX_COEFFICIENT_OF_VARIATION: f64 = 0.1;
+const REFERRER_MISS_PERCENT: u32 = 75;
+const TRAVERSAL_RUN_THRESHOLD: u16 = 8;
+const BREADTH_LEVEL_MIN_WIDTH: u16 = 3;
+/// Pages a session needs, with zero subresource fetches, before the asset ratio is scored
+/// ahead of `MIN_PAGES_FOR_SCORING`.
+const ASSETLESS_MIN_PAGES: u32 = 3;
+/// Age a session needs before it can look asset-less, so fetches that trail its first
+/// page have been counted.
+const ASSET_SETTLE_SECONDS: u64 = 2;
+
+const SUBRESOURCE_FETCH_DESTS: [&str; 9] = [
+    "script", "style", "image", "font", "audio", "video", "track", "manifest", "worker",
+];
+
+/// Compact per-identity session model. Paths are kept as 32-bit hashes except the
+/// most recent one, which is needed for lexical (sitemap-order) comparison.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct BehaviorState {
+    last_seen: u64,
+    last_page_ms: u64,
+    intervals_ms: Vec<u32>,
+    pages: u32,
+    assets: u32,
+    referrer_checked: u32,
+    referrer_misses: u32,
+    recent_paths: Vec<u32>,
+    last_path: String,
+    last_depth: u16,
+    depth_run: u16,
+    level_width: u16,
+    prior_level_wide: bool,
+    lexical_run: u16,
+    #[serde(default)]
+    first_page_ms: u64,
+    /// Static asset counter value already credited to `assets`.
+    #[serde(default)]
+    assets_seen: u64,
+    /// Hour bucket `assets_seen` was read from.
+    #[serde(default)]
+    assets_bucket: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestKind {
+    Page,
+    Asset,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReferrerObservation {
+    Missing,
+    External,
+    SameSite(u32),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct BehaviorFeatures {
+    timing_regular: bool,
+    asset_ratio_low: bool,
+    referrer_chain_broken: bool,
+    breadth_first: bool,
+    sitemap_order: bool,
+}
+
+#[cfg(not(test))]
+pub(crate) fn now_ms() -> u64 {
+    crate::runtime::clock::now_ms()
+}
+
+#[cfg(test)]
+pub(crate) fn now_ms() -> u64 {
+    crate::runtime::clock::pinned_ts().map_or(1_700_000_000_000, |ts| ts.saturating_mul(1000))
+}
+
+fn header_value<'a>(req: &'a Request, name: &str) -> Option<&'a str> {
+    req.header(name)
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn normalize_path(raw: &str) -> &str {
+    let path = raw.split(['?', '#']).next().unwrap_or("/");
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/"
+    } else {
+        trimmed
+    }
+}
+
+fn path_depth(path: &str) -> u16 {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .count() as u16
+}
+
+/// FNV-1a; only used to recognise recently visited paths, never as an identifier.
+fn path_hash(path: &str) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in path.as_bytes() {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+fn classify_request(req: &Request) -> RequestKind {
+    if !matches!(req.method(), Method::Get | Method::Head) {
+        return RequestKind::Other;
+    }
+    match header_value(req, "sec-fetch-dest").map(|value| value.to_ascii_lowercase()) {
+        Some(dest) if SUBRESOURCE_FETCH_DESTS.contains(&dest.as_str()) => RequestKind::Asset,
+        Some(dest) if dest == "document" => RequestKind::Page,
+        Some(_) => RequestKind::Other,
+        None => RequestKind::Page,
+    }
+}
+
+fn observe_referrer(req: &Request) -> ReferrerObservation {
+    let Some(referer) = header_value(req, "referer") else {
+        return ReferrerObservation::Missing;
+    };
+    let without_scheme = referer
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(referer);
+    let (host, path) = match without_scheme.find('/') {
+        Some(index) => (&without_scheme[..index], &without_scheme[index..]),
+        None => (without_scheme, "/"),
+    };
+    let same_site = header_value(req, "host")
+        .map(|request_host| request_host.eq_ignore_ascii_case(host))
+        .unwrap_or(true);
+    if !same_site {
+        return ReferrerObservation::External;
+    }
+    ReferrerObservation::SameSite(path_hash(normalize_path(path)))
+}
+
+fn state_key(identity: &str) -> String {
+    format!("{}{}", BEHAVIOR_KEY_PREFIX_STATE, identity)
+}
+
+// Static asset hits skip the pipeline and the KV gate, so they bump a per-client, per-hour
+// counter in the metrics buffer; the client's next scored request credits new fetches.
+fn asset_counter_key(ip: &str, bucket: u64) -> String {
+    format!(
+        "{}{}:{}",
+        BEHAVIOR_KEY_PREFIX_ASSETS,
+        crate::signals::fingerprint::pseudonymous_ip(ip),
+        bucket
+    )
+}
+
+fn asset_bucket(now_ms: u64) -> u64 {
+    now_ms / 1000 / ASSET_BUCKET_SECONDS
+}
+
+fn load_state<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    identity: &str,
+    now: u64,
+    ttl_seconds: u64,
+) -> Option<BehaviorState> {
+    store
+        .get(state_key(identity).as_str())
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<BehaviorState>(&raw).ok())
+        .filter(|state| now.saturating_sub(state.last_seen) <= ttl_seconds)
+}
+
+fn store_state<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    identity: &str,
+    state: &BehaviorState,
+) {
+    let Ok(raw) = serde_json::to_vec(state) else {
+        return;
+    };
+    let _ = store.set(state_key(identity).as_str(), &raw);
+}
+
+fn decay_counters(state: &mut BehaviorState) {
+    if state.pages < COUNTER_DECAY_CEILING {
+        return;
+    }
+    state.pages /= 2;
+    state.assets /= 2;
+    state.referrer_checked /= 2;
+    state.referrer_misses /= 2;
+}
+
+fn push_bounded<T>(values: &mut Vec<T>, value: T, max: usize) {
+    values.push(value);
+    if values.len() > max {
+        let overflow = values.len() - max;
+        values.drain(..overflow);
+    }
+}
+
+fn observe_page(state: &mut BehaviorState, path: &str, referrer: ReferrerObservation, now_ms: u64) {
+    let hash = path_hash(path);
+    let depth = path_depth(path);
+    let revisit = state.recent_paths.contains(&hash);
+    let first_page = state.pages == 0;
+
+    if !first_page {
+        let interval = now_ms
+            .saturating_sub(state.last_page_ms)
+            .min(u32::MAX as u64) as u32;
+        push_bounded(&mut state.intervals_ms, interval, MAX_TRACKED_INTERVALS);
+
+        match referrer {
+            ReferrerObservation::Missing => {
+                state.referrer_checked = state.referrer_checked.saturating_add(1);
+                state.referrer_misses = state.referrer_misses.saturating_add(1);
+            }
+            ReferrerObservation::SameSite(referrer_hash) => {
+                state.referrer_checked = state.referrer_checked.saturating_add(1);
+                if !state.recent_paths.contains(&referrer_hash) {
+                    state.referrer_misses = state.referrer_misses.saturating_add(1);
+                }
+            }
+            ReferrerObservation::External => {}
+        }
+    }
+
+    // Breadth-first crawlers exhaust one depth level before descending and never
+    // climb back up; people bounce between levels and revisit hub pages.
+    if first_page || revisit || depth < state.last_depth {
+        state.depth_run = 1;
+        state.level_width = 1;
+        state.prior_level_wide = false;
+    } else if depth > state.last_depth {
+        state.depth_run = state.depth_run.saturating_add(1);
+        state.prior_level_wide = state.level_width >= BREADTH_LEVEL_MIN_WIDTH;
+        state.level_width = 1;
+    } else {
+        state.depth_run = state.depth_run.saturating_add(1);
+        state.level_width = state.level_width.saturating_add(1);
+    }
+
+    state.lexical_run = if !first_page && !revisit && path > state.last_path.as_str() {
+        state.lexical_run.saturating_add(1)
+    } else {
+        1
+    };
+
+    if first_page {
+        state.first_page_ms = now_ms;
+    }
+    state.pages = state.pages.saturating_add(1);
+    state.last_page_ms = now_ms;
+    state.last_depth = depth;
+    state.last_path = path.chars().take(MAX_STORED_PATH_CHARS).collect();
+    push_bounded(&mut state.recent_paths, hash, MAX_TRACKED_PATHS);
+    decay_counters(state);
+}
+
+fn timing_is_regular(intervals_ms: &[u32]) -> bool {
+    if intervals_ms.len() + 1 < MIN_PAGES_FOR_SCORING as usize {
+        return false;
+    }
+    let count = intervals_ms.len() as f64;
+    let mean = intervals_ms.iter().map(|value| *value as f64).sum::<f64>() / count;
+    if mean <= 0.0 {
+        return false;
+    }
+    let variance = intervals_ms
+        .iter()
+        .map(|value| {
+            let delta = *value as f64 - mean;
+            delta * delta
+        })
+        .sum::<f64>()
+        / count;
+    variance.sqrt() / mean < TIMING_MAX_COEFFICIENT_OF_VARIATION
+}
+
+/// Browsers fetch CSS/JS/images right after the first HTML page of a session, so a
+/// session with none at all is flagged before `MIN_PAGES_FOR_SCORING`.
+fn asset_ratio_low(state: &BehaviorState, now_ms: u64) -> bool {
+    if state.pages >= MIN_PAGES_FOR_SCORING {
+        return state.assets.saturating_mul(10) < state.pages;
+    }
+    state.pages >= ASSETLESS_MIN_PAGES
+        && state.assets == 0
+        && now_ms.saturating_sub(state.first_page_ms) >= ASSET_SETTLE_SECONDS * 1000
+}
+
+fn evaluate_features(state: &BehaviorState, now_ms: u64) -> BehaviorFeatures {
+    let asset_ratio_low = asset_ratio_low(state, now_ms);
+    if state.pages < MIN_PAGES_FOR_SCORING {
+        return BehaviorFeatures {
+            asset_ratio_low,
+            ..BehaviorFeatures::default()
+        };
+    }
+    BehaviorFeatures {
+        timing_regular: timing_is_regular(&state.intervals_ms),
+        asset_ratio_low,
+        referrer_chain_broken: state.referrer_checked + 1 >= MIN_PAGES_FOR_SCORING
+            && state.referrer_misses.saturating_mul(100)
+                >= state.referrer_checked.saturating_mul(REFERRER_MISS_PERCENT),
+        breadth_first: state.depth_run >= TRAVERSAL_RUN_THRESHOLD
+            && state.prior_level_wide
+            && state.level_width >= BREADTH_LEVEL_MIN_WIDTH,
+        sitemap_order: state.lexical_run >= TRAVERSAL_RUN_THRESHOLD,
+    }
+}
+
+fn behavior_signal_catalog() -> [(&'static str, &'static str); 5] {
+    [
+        (
+            BEHAVIOR_TIMING_REGULAR_KEY,
+            "Machine-regular request timing",
+        ),
+        (BEHAVIOR_ASSET_RATIO_KEY, "Pages fetched without assets"),
+        (BEHAVIOR_REFERRER_CHAIN_KEY, "Referrer chain inconsistent"),
+        (BEHAVIOR_BREADTH_FIRST_KEY, "Breadth-first crawl pattern"),
+        (BEHAVIOR_SITEMAP_ORDER_KEY, "Sitemap-order traversal"),
+    ]
+}
+
+fn disabled_behavior_signals() -> Vec<BotSignal> {
+    behavior_signal_catalog()
+        .iter()
+        .map(|(key, label)| {
+            BotSignal::disabled_with_metadata(
+                key,
+                label,
+                SignalProvenance::Derived,
+                10,
+                SignalFamily::Behavior,
+            )
+        })
+        .collect()
+}
+
+/// Counts a static asset fetch for `ip` in the metrics buffer. Needs neither store nor
+/// config, so the static bypass stays free of KV I/O; the entrypoint flushes the buffer
+/// to KV after the response has been sent.
+pub(crate) fn buffer_asset_fetch(ip: &str) {
+    crate::observability::metrics::buffer_counter(
+        asset_counter_key(ip, asset_bucket(now_ms())).as_str(),
+        1,
+    );
+}
+
+/// Like `buffer_asset_fetch` but writes through, so a following read sees it.
+fn record_static_asset_fetch<S: crate::challenge::KeyValueStore>(store: &S, ip: &str) {
+    let key = asset_counter_key(ip, asset_bucket(now_ms()));
+    crate::observability::metrics::increment_counter(store, key.as_str(), 1);
+    crate::observability::metrics::flush_counter(store, key.as_str());
+}
+
+/// `record_static_asset_fetch` behind the behaviour toggle; replay uses this so results
+/// do not depend on flush timing.
+pub(crate) fn record_asset_fetch<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    cfg: &crate::config::Config,
+    ip: &str,
+) {
+    if cfg.behavior_signal_enabled {
+        record_static_asset_fetch(store, ip);
+    }
+}
+
+/// Static asset fetches counted for `ip` since the session last looked.
+fn take_new_asset_fetches<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    ip: &str,
+    state: &mut BehaviorState,
+    now_ms: u64,
+) -> u32 {
+    let bucket = asset_bucket(now_ms);
+    let count = |bucket: u64| {
+        let key = asset_counter_key(ip, bucket);
+        crate::observability::metrics::flush_counter(store, key.as_str());
+        crate::observability::metrics::get_counter(store, key.as_str())
+    };
+    let total = count(bucket);
+    let fresh = if state.assets_bucket == bucket {
+        total.saturating_sub(state.assets_seen)
+    } else if state.assets_bucket.saturating_add(1) == bucket {
+        count(state.assets_bucket)
+            .saturating_sub(state.assets_seen)
+            .saturating_add(total)
+    } else {
+        total
+    };
+    state.assets_bucket = bucket;
+    state.assets_seen = total;
+    // Fetches from before the session started belong to no session (hotlinks, prefetchers).
+    if state.pages == 0 {
+        return 0;
+    }
+    fresh.min(u32::MAX as u64) as u32
+}
+
+/// Deletes asset counters older than the previous hour bucket. Runs at most once per
+/// bucket, throttled by a KV marker.
+fn maybe_sweep_asset_counters<S: crate::challenge::KeyValueStore>(store: &S, now_ms: u64) {
+    let bucket = asset_bucket(now_ms);
+    if crate::observability::metrics::get_counter(store, BEHAVIOR_ASSET_SWEEP_MARKER_KEY) >= bucket
+    {
+        return;
+    }
+    let _ = store.set(
+        BEHAVIOR_ASSET_SWEEP_MARKER_KEY,
+        bucket.to_string().as_bytes(),
+    );
+    let Ok(keys) = store.get_keys() else {
+        return;
+    };
+    for key in keys {
+        if !key.starts_with(BEHAVIOR_KEY_PREFIX_ASSETS) {
+            continue;
+        }
+        let stale = key
+            .rsplit(':')
+            .next()
+            .and_then(|raw| raw.parse::<u64>().ok())
+            .is_none_or(|key_bucket| key_bucket.saturating_add(1) < bucket);
+        if stale {
+            let _ = store.delete(key.as_str());
+        }
+    }
+}
+
+pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
+    store: &S,
+    req: &Request,
+    cfg: &crate::config::Config,
+    ip: &str,
+    now_ms: u64,
+) -> Vec<BotSignal> {
+    // The bypass counts asset fetches whatever the toggle, so old counters are swept either way.
+    maybe_sweep_asset_counters(store, now_ms);
+    if !cfg.behavior_signal_enabled {
+        return disabled_behavior_signals();
+    }
+
+    let identity = crate::signals::fingerprint::flow_identity(ip, cfg);
+    let now = now_ms / 1000;
+    let mut state = load_state(
+        store,
+        identity.as_str(),
+        now,
+        cfg.fingerprint_state_ttl_seconds,
+    )
+    .unwrap_or_default();
+    let new_assets = take_new_asset_fetches(store, ip, &mut state, now_ms);
+    state.assets = state.assets.saturating_add(new_assets);
+    match classify_request(req) {
+        RequestKind::Page => {
+            observe_page(
+                &mut state,
+                normalize_path(req.path()),
+                observe_referrer(req),
+                now_ms,
+            );
+            state.last_seen = now;
+            store_state(store, identity.as_str(), &state);
+        }
+        RequestKind::Asset if state.pages > 0 => {
+            state.assets = state.assets.saturating_add(1);
+            state.last_seen = now;
+            store_state(store, identity.as_str(), &state);
+        }
+        RequestKind::Asset | RequestKind::Other if new_assets > 0 => {
+            state.last_seen = now;
+            store_state(store, identity.as_str(), &state);
+        }
+        RequestKind::Asset | RequestKind::Other => {}
+    }
+
+    let features = evaluate_features(&state, now_ms);
+    let weights = &cfg.botness_weights;
+    vec![
+        BotSignal::scored_with_metadata(
+            BEHAVIOR_TIMING_REGULAR_KEY,
+            "Machine-regular request timing",
+            features.timing_regular,
+            weights.behavior_timing_regular,
+            SignalProvenance::Derived,
+            8,
+            SignalFamily::Behavior,
+        ),
+        // Browser caches mean repeat visitors legitimately fetch few assets.
+        BotSignal::scored_with_metadata(
+            BEHAVIOR_ASSET_RATIO_KEY,
+            "Pages fetched without assets",
+            features.asset_ratio_low,
+            weights.behavior_asset_ratio,
+            SignalProvenance::Derived,
+            6,
+            SignalFamily::Behavior,
+        ),
+        // Referrer-Policy: no-referrer and privacy extensions also strip the header.
+        BotSignal::scored_with_metadata(
+            BEHAVIOR_REFERRER_CHAIN_KEY,
+            "Referrer chain inconsistent",
+            features.referrer_chain_broken,
+            weights.behavior_referrer_chain,
+            SignalProvenance::Derived,
+            6,
+            SignalFamily::Behavior,
+        ),
+        BotSignal::scored_with_metadata(
+            BEHAVIOR_BREADTH_FIRST_KEY,
+            "Breadth-first crawl pattern",
+            features.breadth_first,
+            weights.behavior_breadth_first,
+            SignalProvenance::Derived,
+            7,
+            SignalFamily::Behavior,
+        ),
+        BotSignal::scored_with_metadata(
+            BEHAVIOR_SITEMAP_ORDER_KEY,
+            "Sitemap-order traversal",
+            features.sitemap_order,
+            weights.behavior_sitemap_order,
+            SignalProvenance::Derived,
+            7,
+            SignalFamily::Behavior,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockStore {
+        map: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    impl crate::challenge::KeyValueStore for MockStore {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
+            let map = self.map.lock().map_err(|_| ())?;
+            Ok(map.get(key).cloned())
+        }
+
+        fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
+            let mut map = self.map.lock().map_err(|_| ())?;
+            map.insert(key.to_string(), value.to_vec());
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), ()> {
+            let mut map = self.map.lock().map_err(|_| ())?;
+            map.remove(key);
+            Ok(())
+        }
+
+        fn get_keys(&self) -> Result<Vec<String>, ()> {
+            let map = self.map.lock().map_err(|_| ())?;
+            Ok(map.keys().cloned().collect())
+        }
+    }
+
+    fn page(path: &str, referer: Option<&str>) -> Request {
+        let mut builder = Request::builder();
+        builder
+            .method(Method::Get)
+            .uri(path)
+            .header("host", "example.com")
+            .header("sec-fetch-dest", "document");
+        if let Some(referer) = referer {
+            builder.header("referer", format!("https://example.com{}", referer));
+        }
+        builder.build()
+    }
+
+    fn asset(path: &str) -> Request {
+        let mut builder = Request::builder();
+        builder
+            .method(Method::Get)
+            .uri(path)
+            .header("host", "example.com")
+            .header("sec-fetch-dest", "image");
+        builder.build()
+    }
+
+    fn active(signals: &[BotSignal], key: &str) -> bool {
+        signals
+            .iter()
+            .find(|signal| signal.key == key)
+            .map(|signal| signal.active)
+            .unwrap_or(false)
+    }
+
+    fn replay(
+        store: &MockStore,
+        cfg: &crate::config::Config,
+        visits: &[(Request, u64)],
+    ) -> Vec<BotSignal> {
+        let mut signals = Vec::new();
+        for (req, at_ms) in visits {
+            signals = collect_bot_signals(store, req, cfg, "198.51.100.7", *at_ms);
+        }
+        signals
+    }
+
+    #[test]
+    fn disabled_behavior_signals_contribute_nothing() {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.behavior_signal_enabled = false;
+        let store = MockStore::default();
+        let signals = collect_bot_signals(&store, &page("/", None), &cfg, "198.51.100.7", now_ms());
+        assert_eq!(signals.len(), 5);
+        assert!(signals.iter().all(|signal| signal.contribution == 0));
+        let keys: Vec<String> = store.map.lock().unwrap().keys().cloned().collect();
+        assert_eq!(keys, vec![BEHAVIOR_ASSET_SWEEP_MARKER_KEY.to_string()]);
+    }
+
+    #[test]
+    fn scripted_sitemap_crawl_triggers_timing_order_and_referrer_features() {
+        let cfg = crate::config::defaults().clone();
+        let store = MockStore::default();
+        let start = now_ms();
+        let visits: Vec<(Request, u64)> = (0..10u64)
+            .map(|index| {
+                (
+                    page(format!("/docs/page-{:02}", index).as_str(), None),
+                    start + index * 1_000,
+                )
+            })
+            .collect();
+        let signals = replay(&store, &cfg, &visits);
+
+        assert!(active(&signals, BEHAVIOR_TIMING_REGULAR_KEY));
+        assert!(active(&signals, BEHAVIOR_ASSET_RATIO_KEY));
+        assert!(active(&signals, BEHAVIOR_REFERRER_CHAIN_KEY));
+        assert!(active(&signals, BEHAVIOR_SITEMAP_ORDER_KEY));
+        assert!(signals
+            .iter()
+            .all(|signal| signal.family == SignalFamily::Behavior));
+    }
+
+    #[test]
+    fn browsing_session_with_assets_and_referrers_stays_clean() {
+        let cfg = crate::config::defaults().clone();
+        let store = MockStore::default();
+        let start = now_ms();
+        let paths = [
+            ("/", None),
+            ("/blog", Some("/")),
+            ("/blog/zebra-care", Some("/blog")),
+            ("/blog", Some("/blog/zebra-care")),
+            ("/blog/apple-pie", Some("/blog")),
+            ("/", Some("/blog/apple-pie")),
+            ("/about", Some("/")),
+            ("/contact", Some("/about")),
+            ("/blog/markets", Some("/")),
+            ("/", Some("/blog/markets")),
+        ];
+        let gaps_ms = [
+            0u64, 4_200, 19_000, 7_300, 31_000, 2_900, 12_500, 8_800, 45_000, 6_100,
+        ];
+        let mut at = start;
+        let mut signals = Vec::new();
+        for (index, (path, referer)) in paths.iter().enumerate() {
+            at += gaps_ms[index];
+            signals = collect_bot_signals(&store, &page(path, *referer), &cfg, "198.51.100.7", at);
+            if index == 0 {
+                for asset_path in ["/app.css", "/app.js", "/logo.png"] {
+                    collect_bot_signals(
+                        &store,
+                        &asset(asset_path),
+                        &cfg,
+                        "198.51.100.7",
+                        at + 50,
+                    );
+                }
+            }
+        }
+
+        assert!(signals.iter().all(|signal| !signal.active), "{:?}", signals);
+    }
+
+    #[test]
+    fn breadth_first_crawl_requires_two_wide_levels() {
+        let cfg = crate::config::defaults().clone();
+        let store = MockStore::default();
+        let start = now_ms();
+        let paths = [
+            "/", "/docs", "/news", "/shop", "/team", "/docs/a", "/news/b", "/shop/c", "/team/d",
+        ];
+        let jitter = [0u64, 900, 2_400, 3_100, 5_600, 6_200, 8_900, 9_300, 12_000];
+        let visits: Vec<(Request, u64)> = paths
+            .iter()
+            .zip(jitter.iter())
+            .map(|(path, offset)| (page(path, Some("/")), start + offset))
+            .collect();
+        let signals = replay(&store, &cfg, &visits);
+        assert!(active(&signals, BEHAVIOR_BREADTH_FIRST_KEY));
+        assert!(!active(&signals, BEHAVIOR_TIMING_REGULAR_KEY));
+    }
+
+    #[test]
+    fn asset_fetches_only_count_once_a_session_exists() {
+        let cfg = crate::config::defaults().clone();
+        let store = MockStore::default();
+        let ip = "198.51.100.31";
+        let identity = crate::signals::fingerprint::flow_identity(ip, &cfg);
+        let start = now_ms();
+        record_asset_fetch(&store, &cfg, ip);
+        collect_bot_signals(&store, &page("/", None), &cfg, ip, start);
+        let state = load_state(&store, identity.as_str(), start / 1000, 60).unwrap();
+        assert_eq!(state.assets, 0);
+
+        record_asset_fetch(&store, &cfg, ip);
+        record_asset_fetch(&store, &cfg, ip);
+        collect_bot_signals(&store, &page("/a", Some("/")), &cfg, ip, start + 4_000);
+        collect_bot_signals(&store, &page("/b", Some("/a")), &cfg, ip, start + 9_000);
+        let state = load_state(&store, identity.as_str(), (start + 9_000) / 1000, 60).unwrap();
+        assert_eq!((state.pages, state.assets), (3, 2));
+    }
+
+    #[test]
+    fn asset_fetches_carry_across_an_hour_bucket_and_old_buckets_are_swept() {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.fingerprint_state_ttl_seconds = 7200;
+        let store = MockStore::default();
+        let ip = "198.51.100.32";
+        let identity = crate::signals::fingerprint::flow_identity(ip, &cfg);
+        let start = now_ms();
+        let next_bucket = (asset_bucket(start) + 1) * ASSET_BUCKET_SECONDS * 1000;
+
+        collect_bot_signals(&store, &page("/", None), &cfg, ip, start);
+        buffer_asset_fetch(ip);
+        let later = next_bucket + 1_000;
+        crate::runtime::clock::with_clock(&crate::runtime::clock::FixedClock(later / 1000), || {
+            record_static_asset_fetch(&store, ip)
+        });
+        collect_bot_signals(&store, &page("/a", Some("/")), &cfg, ip, later);
+        let state = load_state(&store, identity.as_str(), later / 1000, 7200).unwrap();
+        assert_eq!(state.assets, 2);
+
+        let first_key = asset_counter_key(ip, asset_bucket(start));
+        assert!(
+            crate::challenge::KeyValueStore::get(&store, first_key.as_str())
+                .unwrap()
+                .is_some()
+        );
+        let two_buckets_on = later + 2 * ASSET_BUCKET_SECONDS * 1000;
+        collect_bot_signals(&store, &page("/b", Some("/a")), &cfg, ip, two_buckets_on);
+        assert!(
+            crate::challenge::KeyValueStore::get(&store, first_key.as_str())
+                .unwrap()
+                .is_none()
+        );
+        assert!(!store
+            .map
+            .lock()
+            .unwrap()
+            .keys()
+            .any(|key| key.starts_with(BEHAVIOR_KEY_PREFIX_ASSETS)));
+    }
+
+    #[test]
+    fn assetless_session_is_flagged_early_once_assets_have_had_time_to_land() {
+        let cfg = crate::config::defaults().clone();
+        let store = MockStore::default();
+        let start = now_ms();
+        let early = replay(
+            &store,
+            &cfg,
+            &[
+                (page("/", None), start),
+                (page("/a", Some("/")), start + 300),
+                (page("/b", Some("/a")), start + 600),
+            ],
+        );
+        assert!(!active(&early, BEHAVIOR_ASSET_RATIO_KEY));
+
+        let later = collect_bot_signals(
+            &store,
+            &page("/c", Some("/b")),
+            &cfg,
+            "198.51.100.7",
+            start + ASSET_SETTLE_SECONDS * 1000,
+        );
+        assert!(active(&later, BEHAVIOR_ASSET_RATIO_KEY));
+    }
+
+    #[test]
+    fn state_expires_after_fingerprint_state_ttl() {
+        let cfg = crate::config::defaults().clone();
+        let store = MockStore::default();
+        let start = now_ms();
+        collect_bot_signals(&store, &page("/", None), &cfg, "198.51.100.7", start);
+        let identity = crate::signals::fingerprint::flow_identity("198.51.100.7", &cfg);
+        let later = start / 1000 + cfg.fingerprint_state_ttl_seconds + 1;
+        assert!(load_state(
+            &store,
+            identity.as_str(),
+            later,
+            cfg.fingerprint_state_ttl_seconds
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn counters_decay_at_ceiling_and_keep_tracked_lists_bounded() {
+        let mut state = BehaviorState::default();
+        for index in 0..(COUNTER_DECAY_CEILING as u64) {
+            observe_page(
+                &mut state,
+                format!("/p/{}", index).as_str(),
+                ReferrerObservation::Missing,
+                index * 1_000,
+            );
+        }
+        assert_eq!(state.pages, COUNTER_DECAY_CEILING / 2);
+        assert_eq!(state.intervals_ms.len(), MAX_TRACKED_INTERVALS);
+        assert_eq!(state.recent_paths.len(), MAX_TRACKED_PATHS);
+    }
+}
@@ -51,6 +51,8 @@ pub enum SignalFamily {
     FingerprintTemporal,
     FingerprintPersistence,
     FingerprintBehavior,
+    Behavior,
+    Attestation,
     Other,
 }
 
@@ -66,6 +68,8 @@ impl SignalFamily {
             SignalFamily::FingerprintTemporal => "fingerprint_temporal",
             SignalFamily::FingerprintPersistence => "fingerprint_persistence",
             SignalFamily::FingerprintBehavior => "fingerprint_behavior",
+            SignalFamily::Behavior => "behavior",
+            SignalFamily::Attestation => "attestation",
             SignalFamily::Other => "other",
         }
     }
@@ -90,6 +94,7 @@ pub struct SignalBudgetPolicy {
     pub fingerprint_temporal_cap: u8,
     pub fingerprint_persistence_cap: u8,
     pub fingerprint_behavior_cap: u8,
+    pub behavior_cap: u8,
 }
 
 impl SignalBudgetPolicy {
@@ -100,6 +105,7 @@ impl SignalBudgetPolicy {
             SignalFamily::FingerprintTemporal => Some(self.fingerprint_temporal_cap),
             SignalFamily::FingerprintPersistence => Some(self.fingerprint_persistence_cap),
             SignalFamily::FingerprintBehavior => Some(self.fingerprint_behavior_cap),
+            SignalFamily::Behavior => Some(self.behavior_cap),
             _ => None,
         }
     }
@@ -111,6 +117,8 @@ pub struct BotSignal {
     pub label: &'static str,
     pub active: bool,
     pub contribution: u8,
+    /// Points subtracted from the capped score; only attestation signals set this.
+    pub credit: u8,
     pub availability: SignalAvailability,
     pub provenance: SignalProvenance,
     pub confidence: u8,
@@ -155,6 +163,7 @@ impl BotSignal {
             label,
             active,
             contribution,
+            credit: 0,
             availability: SignalAvailability::Active,
             provenance,
             confidence,
@@ -162,6 +171,25 @@ impl BotSignal {
         }
     }
 
+    /// A trust signal: when active it lowers the final score instead of raising it, and is
+    /// exempt from family budgets.
+    pub fn credited_with_metadata(
+        key: &'static str,
+        label: &'static str,
+        active: bool,
+        weight: u8,
+        provenance: SignalProvenance,
+        confidence: u8,
+        family: SignalFamily,
+    ) -> Self {
+        let mut signal =
+            Self::scored_with_metadata(key, label, active, 0, provenance, confidence, family);
+        if active {
+            signal.credit = scale_weight_by_confidence(weight, signal.confidence);
+        }
+        signal
+    }
+
     pub fn disabled(key: &'static str, label: &'static str) -> Self {
         Self::disabled_with_metadata(
             key,
@@ -184,6 +212,7 @@ impl BotSignal {
             label,
             active: false,
             contribution: 0,
+            credit: 0,
             availability: SignalAvailability::Disabled,
             provenance,
             confidence: confidence.clamp(0, 10),
@@ -213,6 +242,7 @@ impl BotSignal {
             label,
             active: false,
             contribution: 0,
+            credit: 0,
             availability: SignalAvailability::Unavailable,
             provenance,
             confidence: confidence.clamp(0, 10),
@@ -224,6 +254,7 @@ impl BotSignal {
 #[derive(Debug, Default)]
 pub struct SignalAccumulator {
     score: u8,
+    credit: u8,
     signals: Vec<BotSignal>,
     budget_policy: Option<SignalBudgetPolicy>,
     fingerprint_total: u8,
@@ -234,6 +265,7 @@ impl SignalAccumulator {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             score: 0,
+            credit: 0,
             signals: Vec::with_capacity(capacity),
             budget_policy: None,
             fingerprint_total: 0,
@@ -244,6 +276,7 @@ impl SignalAccumulator {
     pub fn with_capacity_and_policy(capacity: usize, budget_policy: SignalBudgetPolicy) -> Self {
         Self {
             score: 0,
+            credit: 0,
             signals: Vec::with_capacity(capacity),
             budget_policy: Some(budget_policy),
             fingerprint_total: 0,
@@ -286,11 +319,15 @@ impl SignalAccumulator {
         let capped = self.apply_budget(signal.family, signal.contribution);
         signal.contribution = capped;
         self.score = self.score.saturating_add(capped);
+        self.credit = self.credit.saturating_add(signal.credit);
         self.signals.push(signal);
     }
 
     pub fn finish(self) -> (u8, Vec<BotSignal>) {
-        (self.score.clamp(0, 10), self.signals)
+        (
+            self.score.clamp(0, 10).saturating_sub(self.credit),
+            self.signals,
+        )
     }
 }
 
@@ -363,6 +400,7 @@ mod tests {
             fingerprint_temporal_cap: 3,
             fingerprint_persistence_cap: 3,
             fingerprint_behavior_cap: 3,
+            behavior_cap: 3,
         };
         let mut accumulator = SignalAccumulator::with_capacity_and_policy(2, policy);
         accumulator.push(BotSignal::scored_with_metadata(
@@ -390,6 +428,67 @@ mod tests {
         assert_eq!(signals[1].contribution, 1);
     }
 
+    #[test]
+    fn behavior_family_is_capped_separately_from_fingerprint_budget() {
+        let policy = SignalBudgetPolicy {
+            fingerprint_total_cap: 1,
+            fingerprint_header_runtime_cap: 1,
+            fingerprint_transport_cap: 1,
+            fingerprint_temporal_cap: 1,
+            fingerprint_persistence_cap: 1,
+            fingerprint_behavior_cap: 1,
+            behavior_cap: 3,
+        };
+        let mut accumulator = SignalAccumulator::with_capacity_and_policy(3, policy);
+        for key in ["behavior_a", "behavior_b"] {
+            accumulator.push(BotSignal::scored_with_metadata(
+                key,
+                "Behavior",
+                true,
+                2,
+                SignalProvenance::Derived,
+                10,
+                SignalFamily::Behavior,
+            ));
+        }
+        accumulator.push(BotSignal::scored_with_metadata(
+            "fp_hdr",
+            "FP header mismatch",
+            true,
+            2,
+            SignalProvenance::Internal,
+            10,
+            SignalFamily::FingerprintHeaderRuntime,
+        ));
+
+        let (score, signals) = accumulator.finish();
+        assert_eq!(score, 4);
+        assert_eq!(signals[0].contribution, 2);
+        assert_eq!(signals[1].contribution, 1);
+        assert_eq!(signals[2].contribution, 1);
+    }
+
+    #[test]
+    fn credited_signal_lowers_capped_score() {
+        let mut accumulator = SignalAccumulator::with_capacity(3);
+        accumulator.push(BotSignal::scored("a", "A", true, 9));
+        accumulator.push(BotSignal::scored("b", "B", true, 9));
+        accumulator.push(BotSignal::credited_with_metadata(
+            "attested",
+            "Attested",
+            true,
+            4,
+            SignalProvenance::ExternalTrusted,
+            10,
+            SignalFamily::Attestation,
+        ));
+
+        let (score, signals) = accumulator.finish();
+        assert_eq!(score, 6);
+        assert_eq!(signals[2].contribution, 0);
+        assert_eq!(signals[2].credit, 4);
+    }
+
     #[test]
     fn signal_availability_has_stable_labels() {
         assert_eq!(SignalAvailability::Active.as_str(), "active");
@@ -408,5 +507,6 @@ mod tests {
             SignalFamily::FingerprintHeaderRuntime.as_str(),
             "fingerprint_header_runtime"
         );
+        assert_eq!(SignalFamily::Behavior.as_str(), "behavior");
     }
 }
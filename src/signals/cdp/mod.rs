@@ -15,7 +15,11 @@
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use spin_sdk::http::{Request, Response};
-use spin_sdk::key_value::Store;
+
+mod nonce;
+pub(crate) mod probes;
+
+pub(crate) use nonce::verify_report_nonce;
 
 const MAX_CDP_CHECKS: usize = 32;
 
@@ -27,6 +31,15 @@ pub struct CdpReport {
     pub checks: Vec<String>,
 }
 
+/// `/cdp-report` body: the report plus the nonce issued with the detection script.
+#[derive(Debug, Deserialize)]
+struct SignedCdpReport {
+    #[serde(default)]
+    nonce: String,
+    #[serde(flatten)]
+    report: CdpReport,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CdpTier {
     Low,
@@ -40,16 +53,10 @@ fn has_check(checks: &[String], needle: &str) -> bool {
         .any(|check| check.eq_ignore_ascii_case(needle))
 }
 
-fn soft_signal_count(checks: &[String]) -> usize {
-    [
-        "cdp_timing",
-        "chrome_obj",
-        "plugins",
-        "storage_marker",
-        "micro_timing",
-    ]
+fn tier_count(checks: &[String], tier: probes::ProbeTier) -> usize {
+    checks
         .iter()
-        .filter(|name| has_check(checks, name))
+        .filter(|check| probes::tier_of(check) == Some(tier))
         .count()
 }
 
@@ -57,14 +64,13 @@ fn soft_signal_count(checks: &[String]) -> usize {
 ///
 /// Tiering intentionally treats hard automation checks as strongest evidence and
 /// only uses thresholded score as supporting evidence when hard checks are absent.
+/// Probe tiers come from the probe registry.
 pub fn classify_cdp_tier(report: &CdpReport, threshold: f32) -> CdpTier {
-    let has_hard_signal =
-        has_check(&report.checks, "webdriver") || has_check(&report.checks, "automation_props");
-    if has_hard_signal {
+    if tier_count(&report.checks, probes::ProbeTier::Hard) > 0 {
         return CdpTier::Strong;
     }
 
-    let soft_count = soft_signal_count(&report.checks);
+    let soft_count = tier_count(&report.checks, probes::ProbeTier::Soft);
     let threshold_clamped = threshold.clamp(0.0, 10.0);
     let strong_score_without_hard = report.score >= threshold_clamped + 0.4 && soft_count >= 2;
     if strong_score_without_hard {
@@ -88,7 +94,7 @@ fn cdp_tier_label(tier: CdpTier) -> &'static str {
     }
 }
 
-fn increment_kv_counter(store: &Store, key: &str) {
+fn increment_kv_counter<S: crate::challenge::KeyValueStore + ?Sized>(store: &S, key: &str) {
     let current: u64 = store
         .get(key)
         .ok()
@@ -103,7 +109,10 @@ fn increment_kv_counter(store: &Store, key: &str) {
 
 /// Handles incoming CDP detection reports from client-side JavaScript.
 /// Auto-bans are only applied for strong-tier automation detections.
-pub fn handle_cdp_report(store: &Store, req: &Request) -> Response {
+pub fn handle_cdp_report<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    req: &Request,
+) -> Response {
     let ip = crate::extract_client_ip(req);
     let cfg = match crate::config::load_runtime_cached(store, "default") {
         Ok(cfg) => cfg,
@@ -123,7 +132,7 @@ pub fn handle_cdp_report(store: &Store, req: &Request) -> Response {
     ) {
         return Response::new(400, e);
     }
-    let mut report: CdpReport = match serde_json::from_slice(body) {
+    let SignedCdpReport { nonce, mut report } = match serde_json::from_slice(body) {
         Ok(r) => r,
         Err(_) => return Response::new(400, "Invalid CDP report format"),
     };
@@ -144,6 +153,45 @@ pub fn handle_cdp_report(store: &Store, req: &Request) -> Response {
     }
     report.checks = sanitized_checks;
 
+    // Only reports carrying the nonce issued with this client's detection script count.
+    let user_agent = req
+        .header("user-agent")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+    let now = crate::admin::now_ts();
+    let issued = match verify_report_nonce(store, nonce.as_str(), &ip, user_agent, now) {
+        Ok(issued) => issued,
+        Err(rejection) => {
+            crate::observability::metrics::increment(
+                store,
+                crate::observability::metrics::MetricName::CdpReportOutcomes,
+                Some(rejection.as_str()),
+            );
+            crate::admin::log_event(
+                store,
+                &crate::admin::EventLogEntry {
+                    ts: now,
+                    event: crate::admin::EventType::Challenge,
+                    ip: Some(ip.clone()),
+                    reason: Some("cdp_report_rejected".to_string()),
+                    outcome: Some(format!("outcome={}", rejection.as_str())),
+                    admin: None,
+                },
+            );
+            return Response::new(403, "CDP report rejected");
+        }
+    };
+    crate::observability::metrics::increment(
+        store,
+        crate::observability::metrics::MetricName::CdpReportOutcomes,
+        Some("accepted"),
+    );
+
+    // Decode and score against the probe registry the client's script was assembled from.
+    let checks = probes::decode_checks(issued.version, issued.salt.as_str(), &report.checks);
+    report.score = probes::score(issued.version, &checks);
+    report.checks = checks.into_iter().map(str::to_string).collect();
+
     let cdp_tier = classify_cdp_tier(&report, cfg.cdp_detection_threshold);
     let tier_label = cdp_tier_label(cdp_tier);
     let detection_policy_match = match cdp_tier {
@@ -532,6 +580,36 @@ const CDP_PROBE_V2_APPEND_JS: &str = r#"
 })();
 "#;
 
+/// Per-request probe suite: assembled script, the global its reporter calls, and the report nonce.
+pub(crate) struct ProbeSuite {
+    pub script: String,
+    pub entry: String,
+    pub nonce: String,
+}
+
+/// Assemble the current probe registry for one client and sign the matching report nonce.
+pub(crate) fn issue_probe_suite(
+    configured: crate::config::CdpProbeFamily,
+    rollout_percent: u8,
+    ip: &str,
+    user_agent: &str,
+    now: u64,
+) -> ProbeSuite {
+    use rand::Rng;
+
+    let issued = nonce::IssuedProbes {
+        version: probes::CURRENT_PROBE_REGISTRY_VERSION,
+        salt: format!("{:016x}", rand::rng().random::<u64>()),
+    };
+    let base = get_cdp_detection_script_for_request(configured, rollout_percent, ip);
+    let assembled = probes::assemble(base.as_str(), issued.version, issued.salt.as_str());
+    ProbeSuite {
+        script: assembled.script,
+        entry: assembled.entry,
+        nonce: nonce::issue_report_nonce(ip, user_agent, &issued, now),
+    }
+}
+
 fn rollout_bucket_percent(request_key: &str) -> u8 {
     let mut hasher = Sha256::new();
     hasher.update(request_key.as_bytes());
@@ -580,8 +658,9 @@ pub fn get_cdp_detection_script() -> &'static str {
 
 /// JavaScript snippet to report CDP detection result back to the server.
 /// Used by inject_cdp_detection() and available for custom injection scenarios.
+/// `report_nonce` comes from `issue_report_nonce` for the client receiving the page.
 #[allow(dead_code)]
-pub fn get_cdp_report_script(report_endpoint: &str) -> String {
+pub fn get_cdp_report_script(report_endpoint: &str, report_nonce: &str) -> String {
     format!(
         r#"
 <script>
@@ -593,6 +672,7 @@ pub fn get_cdp_report_script(report_endpoint: &str) -> String {
                 method: 'POST',
                 headers: {{ 'Content-Type': 'application/json' }},
                 body: JSON.stringify({{
+                    nonce: '{}',
                     cdp_detected: true,
                     score: result.score,
                     checks: result.checks
@@ -603,7 +683,7 @@ pub fn get_cdp_report_script(report_endpoint: &str) -> String {
 }})();
 </script>
 "#,
-        report_endpoint
+        report_endpoint, report_nonce
     )
 }
 
@@ -611,11 +691,15 @@ pub fn get_cdp_report_script(report_endpoint: &str) -> String {
 /// Useful for injecting detection into external HTML content (e.g., proxy scenarios).
 /// Currently used by tests; main integration uses get_cdp_detection_script() directly.
 #[allow(dead_code)]
-pub fn inject_cdp_detection(html: &str, report_endpoint: Option<&str>) -> String {
+pub fn inject_cdp_detection(
+    html: &str,
+    report_endpoint: Option<&str>,
+    report_nonce: &str,
+) -> String {
     let detection_script = format!("<script>{}</script>", CDP_DETECTION_JS);
 
     let report_script = if let Some(endpoint) = report_endpoint {
-        get_cdp_report_script(endpoint)
+        get_cdp_report_script(endpoint, report_nonce)
     } else {
         String::new()
     };
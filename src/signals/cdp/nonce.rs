@@ -0,0 +1,195 @@
+// src/signals/cdp/nonce.rs
+// Signed, single-use report nonces for `/cdp-report`.
+// The nonce is embedded in the injected detection script and binds the report to the
+// IP bucket and user agent that received the script, so reports cannot be forged or replayed.
+
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::challenge::operation_envelope::{
+    FLOW_CDP_REPORT, MAX_OPERATION_REPLAY_TTL_SECONDS_CDP_REPORT,
+    MAX_STEP_WINDOW_SECONDS_CDP_REPORT, PATH_CLASS_CDP_REPORT_SUBMIT, STEP_CDP_REPORT_SUBMIT,
+    STEP_INDEX_CDP_REPORT_SUBMIT, TOKEN_VERSION_V1,
+};
+
+pub(crate) const CDP_REPORT_NONCE_TTL_SECONDS: u64 = 300;
+const MAX_NONCE_LEN: usize = 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CdpReportNonce {
+    operation_id: String,
+    flow_id: String,
+    step_id: String,
+    step_index: u8,
+    ip_bucket: String,
+    ua_bucket: String,
+    path_class: String,
+    issued_at: u64,
+    expires_at: u64,
+    token_version: u8,
+    #[serde(default)]
+    probe_version: u8,
+    #[serde(default)]
+    probe_salt: String,
+}
+
+/// Probe registry version and identifier salt the report's script was assembled with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IssuedProbes {
+    pub version: u8,
+    pub salt: String,
+}
+
+/// Why a report nonce was refused; `as_str` is the `cdp_report_outcomes_total` label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NonceRejection {
+    Missing,
+    Invalid,
+    Expired,
+    BindingMismatch,
+    Replay,
+}
+
+impl NonceRejection {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            NonceRejection::Missing => "missing_nonce",
+            NonceRejection::Invalid => "invalid_nonce",
+            NonceRejection::Expired => "expired",
+            NonceRejection::BindingMismatch => "binding_mismatch",
+            NonceRejection::Replay => "replay",
+        }
+    }
+}
+
+fn new_mac() -> Hmac<Sha256> {
+    Hmac::<Sha256>::new_from_slice(crate::config::challenge_secret().as_bytes()).unwrap()
+}
+
+/// Issue a nonce for the client that is about to receive the detection script.
+pub(crate) fn issue_report_nonce(
+    ip: &str,
+    user_agent: &str,
+    probes: &IssuedProbes,
+    now: u64,
+) -> String {
+    let mut rng = rand::rng();
+    let payload = CdpReportNonce {
+        operation_id: format!("{:016x}{:016x}", rng.random::<u64>(), rng.random::<u64>()),
+        flow_id: FLOW_CDP_REPORT.to_string(),
+        step_id: STEP_CDP_REPORT_SUBMIT.to_string(),
+        step_index: STEP_INDEX_CDP_REPORT_SUBMIT,
+        ip_bucket: crate::signals::ip_identity::bucket_ip(ip),
+        ua_bucket: crate::challenge::operation_envelope::user_agent_bucket(user_agent),
+        path_class: PATH_CLASS_CDP_REPORT_SUBMIT.to_string(),
+        issued_at: now,
+        expires_at: now.saturating_add(CDP_REPORT_NONCE_TTL_SECONDS),
+        token_version: TOKEN_VERSION_V1,
+        probe_version: probes.version,
+        probe_salt: probes.salt.clone(),
+    };
+    let payload_json = serde_json::to_string(&payload).unwrap();
+    let mut mac = new_mac();
+    mac.update(payload_json.as_bytes());
+    format!(
+        "{}.{}",
+        general_purpose::URL_SAFE_NO_PAD.encode(payload_json.as_bytes()),
+        general_purpose::URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+    )
+}
+
+fn parse_report_nonce(nonce: &str) -> Option<CdpReportNonce> {
+    let (payload_b64, sig_b64) = nonce.split_once('.')?;
+    let payload_bytes = general_purpose::URL_SAFE_NO_PAD.decode(payload_b64).ok()?;
+    let sig = general_purpose::URL_SAFE_NO_PAD.decode(sig_b64).ok()?;
+    let mut mac = new_mac();
+    mac.update(payload_bytes.as_slice());
+    mac.verify_slice(sig.as_slice()).ok()?;
+    let payload = serde_json::from_slice::<CdpReportNonce>(payload_bytes.as_slice()).ok()?;
+    crate::challenge::operation_envelope::validate_signed_operation_envelope(
+        payload.operation_id.as_str(),
+        payload.flow_id.as_str(),
+        payload.step_id.as_str(),
+        payload.issued_at,
+        payload.expires_at,
+        payload.token_version,
+        FLOW_CDP_REPORT,
+        STEP_CDP_REPORT_SUBMIT,
+    )
+    .ok()?;
+    Some(payload)
+}
+
+/// Verify signature, step window, client binding and single use, consuming the nonce on success.
+pub(crate) fn verify_report_nonce<S: crate::challenge::KeyValueStore + ?Sized>(
+    store: &S,
+    nonce: &str,
+    ip: &str,
+    user_agent: &str,
+    now: u64,
+) -> Result<IssuedProbes, NonceRejection> {
+    let nonce = nonce.trim();
+    if nonce.is_empty() {
+        return Err(NonceRejection::Missing);
+    }
+    if nonce.len() > MAX_NONCE_LEN {
+        return Err(NonceRejection::Invalid);
+    }
+    let payload = parse_report_nonce(nonce).ok_or(NonceRejection::Invalid)?;
+
+    crate::challenge::operation_envelope::validate_ordering_window(
+        payload.flow_id.as_str(),
+        payload.step_id.as_str(),
+        payload.step_index,
+        payload.issued_at,
+        payload.expires_at,
+        now,
+        FLOW_CDP_REPORT,
+        STEP_CDP_REPORT_SUBMIT,
+        STEP_INDEX_CDP_REPORT_SUBMIT,
+        MAX_STEP_WINDOW_SECONDS_CDP_REPORT,
+    )
+    .map_err(|err| match err {
+        crate::challenge::operation_envelope::OrderingValidationError::OrderViolation => {
+            NonceRejection::Invalid
+        }
+        crate::challenge::operation_envelope::OrderingValidationError::WindowExceeded => {
+            NonceRejection::Expired
+        }
+    })?;
+
+    crate::challenge::operation_envelope::validate_request_binding(
+        payload.ip_bucket.as_str(),
+        payload.ua_bucket.as_str(),
+        payload.path_class.as_str(),
+        ip,
+        user_agent,
+        PATH_CLASS_CDP_REPORT_SUBMIT,
+    )
+    .map_err(|_| NonceRejection::BindingMismatch)?;
+
+    crate::challenge::operation_envelope::validate_operation_replay(
+        store,
+        payload.flow_id.as_str(),
+        payload.operation_id.as_str(),
+        now,
+        payload.expires_at,
+        MAX_OPERATION_REPLAY_TTL_SECONDS_CDP_REPORT,
+    )
+    .map_err(|err| match err {
+        crate::challenge::operation_envelope::ReplayValidationError::ReplayDetected => {
+            NonceRejection::Replay
+        }
+        crate::challenge::operation_envelope::ReplayValidationError::ExpiredOperation => {
+            NonceRejection::Expired
+        }
+    })?;
+
+    Ok(IssuedProbes {
+        version: payload.probe_version,
+        salt: payload.probe_salt,
+    })
+}
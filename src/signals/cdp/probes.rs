@@ -0,0 +1,324 @@
+// src/signals/cdp/probes.rs
+// Versioned registry of client-side automation probes.
+// Each interstitial gets a probe suite assembled from the current registry with per-request
+// identifiers (global names, report codes and probe order) derived from a salt carried in the
+// signed report nonce, so reports are decoded and scored against the registry that was issued.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Registry version issued with new probe suites.
+pub(crate) const CURRENT_PROBE_REGISTRY_VERSION: u8 = 2;
+
+const PROBE_CODE_HEX_LEN: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProbeTier {
+    /// Conclusive on its own: a single hit classifies the report as strong.
+    Hard,
+    /// Supporting evidence that only counts towards score and soft-signal totals.
+    Soft,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ClientProbe {
+    pub id: &'static str,
+    pub weight: f32,
+    pub tier: ProbeTier,
+    /// Function expression resolving to a boolean; `None` when the base CDP detector reports it.
+    #[serde(skip)]
+    pub script: Option<&'static str>,
+}
+
+const fn detector(id: &'static str, weight: f32, tier: ProbeTier) -> ClientProbe {
+    ClientProbe {
+        id,
+        weight,
+        tier,
+        script: None,
+    }
+}
+
+const fn probe(
+    id: &'static str,
+    weight: f32,
+    tier: ProbeTier,
+    script: &'static str,
+) -> ClientProbe {
+    ClientProbe {
+        id,
+        weight,
+        tier,
+        script: Some(script),
+    }
+}
+
+const WEBGL_RENDERER_JS: &str = r#"function() {
+    var canvas = document.createElement('canvas');
+    var gl = canvas.getContext('webgl') || canvas.getContext('experimental-webgl');
+    if (!gl) { return /Chrome|Firefox|Safari/.test(navigator.userAgent); }
+    var info = gl.getExtension('WEBGL_debug_renderer_info');
+    if (!info) { return false; }
+    var renderer = String(gl.getParameter(info.UNMASKED_RENDERER_WEBGL) || '');
+    return /SwiftShader|llvmpipe|softpipe|Mesa OffScreen/i.test(renderer);
+}"#;
+
+const PERMISSIONS_QUIRK_JS: &str = r#"function() {
+    if (!navigator.permissions || !navigator.permissions.query || typeof Notification === 'undefined') {
+        return false;
+    }
+    return navigator.permissions.query({ name: 'notifications' }).then(function(status) {
+        return Notification.permission === 'denied' && status.state === 'prompt';
+    }, function() { return false; });
+}"#;
+
+const IFRAME_CONTENT_WINDOW_JS: &str = r#"function() {
+    var frame = document.createElement('iframe');
+    frame.srcdoc = '<!doctype html>';
+    frame.style.display = 'none';
+    (document.body || document.documentElement).appendChild(frame);
+    try {
+        var win = frame.contentWindow;
+        return !win || win === window || win.self !== win || typeof win.chrome !== typeof window.chrome;
+    } catch (e) {
+        return true;
+    } finally {
+        frame.remove();
+    }
+}"#;
+
+const TIMEZONE_LOCALE_JS: &str = r#"function() {
+    try {
+        var options = Intl.DateTimeFormat().resolvedOptions();
+        if (!options.timeZone) { return true; }
+        var language = String(navigator.language || '').split('-')[0].toLowerCase();
+        var locale = String(options.locale || '').split('-')[0].toLowerCase();
+        var languages = navigator.languages;
+        return (Array.isArray(languages) && languages.length === 0) ||
+            (language !== '' && locale !== '' && language !== locale);
+    } catch (e) {
+        return true;
+    }
+}"#;
+
+const WORKER_NAVIGATOR_JS: &str = r#"function() {
+    if (typeof Worker === 'undefined' || !window.Blob || !window.URL) { return false; }
+    return new Promise(function(resolve) {
+        var url, worker, timer;
+        function done(value) {
+            clearTimeout(timer);
+            try { worker.terminate(); } catch (e) {}
+            if (url) { URL.revokeObjectURL(url); }
+            resolve(value);
+        }
+        try {
+            url = URL.createObjectURL(new Blob(
+                ['postMessage({ ua: navigator.userAgent, hc: navigator.hardwareConcurrency });'],
+                { type: 'text/javascript' }
+            ));
+            worker = new Worker(url);
+        } catch (e) {
+            resolve(false);
+            return;
+        }
+        timer = setTimeout(function() { done(false); }, 1000);
+        worker.onmessage = function(ev) {
+            var data = ev.data || {};
+            done(data.ua !== navigator.userAgent || data.hc !== navigator.hardwareConcurrency);
+        };
+        worker.onerror = function() { done(false); };
+    });
+}"#;
+
+/// Checks reported by the base CDP detector (`v1`/`v2` probe families).
+const REGISTRY_V1: &[ClientProbe] = &[
+    detector("webdriver", 1.0, ProbeTier::Hard),
+    detector("automation_props", 0.9, ProbeTier::Hard),
+    detector("cdp_timing", 0.7, ProbeTier::Soft),
+    detector("chrome_obj", 0.3, ProbeTier::Soft),
+    detector("plugins", 0.4, ProbeTier::Soft),
+    detector("storage_marker", 0.25, ProbeTier::Soft),
+    detector("micro_timing", 0.2, ProbeTier::Soft),
+];
+
+const REGISTRY_V2: &[ClientProbe] = &[
+    detector("webdriver", 1.0, ProbeTier::Hard),
+    detector("automation_props", 0.9, ProbeTier::Hard),
+    detector("cdp_timing", 0.7, ProbeTier::Soft),
+    detector("chrome_obj", 0.3, ProbeTier::Soft),
+    detector("plugins", 0.4, ProbeTier::Soft),
+    detector("storage_marker", 0.25, ProbeTier::Soft),
+    detector("micro_timing", 0.2, ProbeTier::Soft),
+    probe("webgl_renderer", 0.5, ProbeTier::Soft, WEBGL_RENDERER_JS),
+    probe(
+        "permissions_quirk",
+        0.4,
+        ProbeTier::Soft,
+        PERMISSIONS_QUIRK_JS,
+    ),
+    probe(
+        "iframe_content_window",
+        0.6,
+        ProbeTier::Soft,
+        IFRAME_CONTENT_WINDOW_JS,
+    ),
+    probe("timezone_locale", 0.2, ProbeTier::Soft, TIMEZONE_LOCALE_JS),
+    probe(
+        "worker_navigator_mismatch",
+        1.0,
+        ProbeTier::Hard,
+        WORKER_NAVIGATOR_JS,
+    ),
+];
+
+/// Probes for a registry version; retired versions stay here until their nonces have expired.
+pub(crate) fn registry(version: u8) -> Option<&'static [ClientProbe]> {
+    match version {
+        1 => Some(REGISTRY_V1),
+        2 => Some(REGISTRY_V2),
+        _ => None,
+    }
+}
+
+pub(crate) fn current_registry() -> &'static [ClientProbe] {
+    registry(CURRENT_PROBE_REGISTRY_VERSION).unwrap_or(REGISTRY_V1)
+}
+
+/// Tier of a check by name, resolved against the current registry.
+pub(crate) fn tier_of(check: &str) -> Option<ProbeTier> {
+    current_registry()
+        .iter()
+        .find(|probe| probe.id.eq_ignore_ascii_case(check))
+        .map(|probe| probe.tier)
+}
+
+fn salted_hex(salt: &str, label: &str, len: usize) -> String {
+    let digest = Sha256::digest(format!("{}:{}", salt, label).as_bytes());
+    digest
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .take(len)
+        .map(|nibble| char::from_digit(u32::from(nibble), 16).unwrap_or('0'))
+        .collect()
+}
+
+/// Per-request report code for a probe; only the server and the issued script know the mapping.
+pub(crate) fn probe_code(salt: &str, probe_id: &str) -> String {
+    salted_hex(salt, probe_id, PROBE_CODE_HEX_LEN)
+}
+
+/// Randomized global identifier; the leading letter keeps it a valid JS name.
+fn identifier(salt: &str, role: &str) -> String {
+    format!("_{}", salted_hex(salt, role, 12))
+}
+
+/// Identifiers baked into the base detector that stealth plugins stub by name.
+const BASE_DETECTOR_GLOBALS: [&str; 4] = [
+    "_checkCDPAutomation",
+    "_shumaProbeFamily",
+    "_cdpScore",
+    "_cdpChecks",
+];
+
+pub(crate) struct AssembledProbes {
+    pub script: String,
+    /// Global function the reporter calls; resolves to `{ detected, score, checks }`.
+    pub entry: String,
+}
+
+/// Assemble the base detector plus registry probes under identifiers derived from `salt`.
+pub(crate) fn assemble(base_detector: &str, version: u8, salt: &str) -> AssembledProbes {
+    let mut script = base_detector.to_string();
+    for global in BASE_DETECTOR_GLOBALS {
+        script = script.replace(global, identifier(salt, global).as_str());
+    }
+    let base_entry = identifier(salt, BASE_DETECTOR_GLOBALS[0]);
+    let entry = identifier(salt, "entry");
+
+    let mut probes: Vec<&ClientProbe> = registry(version).unwrap_or(REGISTRY_V1).iter().collect();
+    // Vary probe order per request so the suite has no fixed layout to match against.
+    probes.sort_by_key(|probe| probe_code(salt, probe.id));
+
+    let detector_codes: Vec<String> = probes
+        .iter()
+        .filter(|probe| probe.script.is_none())
+        .map(|probe| format!("'{}': '{}'", probe.id, probe_code(salt, probe.id)))
+        .collect();
+    let client_probes: Vec<String> = probes
+        .iter()
+        .filter_map(|probe| {
+            probe.script.map(|body| {
+                format!(
+                    "{{ code: '{}', run: {} }}",
+                    probe_code(salt, probe.id),
+                    body
+                )
+            })
+        })
+        .collect();
+
+    script.push_str(&format!(
+        r#"
+(function() {{
+    var detectorCodes = {{ {detector_codes} }};
+    var probes = [{client_probes}];
+    window.{entry} = function() {{
+        var base = window.{base_entry};
+        return Promise.resolve(base ? base() : null).then(function(result) {{
+            result = result || {{ detected: false, score: 0, checks: [] }};
+            var checks = [];
+            var detected = !!result.detected;
+            (result.checks || []).forEach(function(name) {{
+                if (detectorCodes[name]) {{ checks.push(detectorCodes[name]); }}
+            }});
+            return Promise.all(probes.map(function(probe) {{
+                return Promise.resolve().then(probe.run).then(function(hit) {{
+                    if (hit) {{
+                        checks.push(probe.code);
+                        detected = true;
+                    }}
+                }}, function() {{}});
+            }})).then(function() {{
+                return {{ detected: detected, score: result.score || 0, checks: checks }};
+            }});
+        }});
+    }};
+}})();
+"#,
+        detector_codes = detector_codes.join(", "),
+        client_probes = client_probes.join(",\n"),
+        entry = entry,
+        base_entry = base_entry,
+    ));
+    AssembledProbes { script, entry }
+}
+
+/// Map reported codes back to probe IDs for the issued registry; unknown codes are dropped.
+pub(crate) fn decode_checks(version: u8, salt: &str, codes: &[String]) -> Vec<&'static str> {
+    let Some(probes) = registry(version) else {
+        return Vec::new();
+    };
+    let mut decoded = Vec::new();
+    for code in codes {
+        if let Some(probe) = probes
+            .iter()
+            .find(|probe| probe_code(salt, probe.id).eq_ignore_ascii_case(code))
+        {
+            if !decoded.contains(&probe.id) {
+                decoded.push(probe.id);
+            }
+        }
+    }
+    decoded
+}
+
+/// Server-side score for decoded checks, so the client-reported score is never trusted.
+pub(crate) fn score(version: u8, checks: &[&str]) -> f32 {
+    registry(version)
+        .unwrap_or(&[])
+        .iter()
+        .filter(|probe| checks.contains(&probe.id))
+        .map(|probe| probe.weight)
+        .sum()
+}
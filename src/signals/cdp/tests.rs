@@ -41,7 +41,7 @@ fn test_probe_rotation_selects_v1_and_v2_families() {
 #[test]
 fn test_get_cdp_report_script_with_endpoint() {
     let endpoint = "/cdp-report";
-    let script = get_cdp_report_script(endpoint);
+    let script = get_cdp_report_script(endpoint, "test-nonce");
 
     assert!(
         script.contains(endpoint),
@@ -67,7 +67,7 @@ fn test_inject_cdp_detection_into_head() {
 </body>
 </html>"#;
 
-    let result = inject_cdp_detection(html, None);
+    let result = inject_cdp_detection(html, None, "");
 
     assert!(
         result.contains("_checkCDPAutomation"),
@@ -92,7 +92,7 @@ fn test_inject_cdp_detection_into_body_fallback() {
 </body>
 </html>"#;
 
-    let result = inject_cdp_detection(html, None);
+    let result = inject_cdp_detection(html, None, "");
 
     assert!(
         result.contains("_checkCDPAutomation"),
@@ -106,7 +106,7 @@ fn test_inject_cdp_detection_with_report_endpoint() {
     let html = r#"<html><head></head><body></body></html>"#;
     let endpoint = "/api/cdp-report";
 
-    let result = inject_cdp_detection(html, Some(endpoint));
+    let result = inject_cdp_detection(html, Some(endpoint), "test-nonce");
 
     assert!(
         result.contains("_checkCDPAutomation"),
@@ -123,7 +123,7 @@ fn test_inject_cdp_detection_with_report_endpoint() {
 fn test_inject_cdp_detection_minimal_html() {
     let html = "<p>Just some text</p>";
 
-    let result = inject_cdp_detection(html, None);
+    let result = inject_cdp_detection(html, None, "");
 
     assert!(
         result.contains("_checkCDPAutomation"),
@@ -204,3 +204,171 @@ fn test_cdp_tier_low_when_only_weak_signal_present() {
     };
     assert_eq!(classify_cdp_tier(&report, 0.8), CdpTier::Low);
 }
+
+#[test]
+fn test_signed_cdp_report_carries_nonce_alongside_report() {
+    let json = r#"{"nonce":"abc.def","cdp_detected":true,"score":0.9,"checks":["webdriver"]}"#;
+    let signed: SignedCdpReport = serde_json::from_str(json).expect("Should deserialize");
+    assert_eq!(signed.nonce, "abc.def");
+    assert_eq!(signed.report.checks, vec!["webdriver".to_string()]);
+
+    let unsigned: SignedCdpReport =
+        serde_json::from_str(r#"{"cdp_detected":true,"score":0.9,"checks":[]}"#).unwrap();
+    assert!(unsigned.nonce.is_empty());
+}
+
+fn issued_probes() -> nonce::IssuedProbes {
+    nonce::IssuedProbes {
+        version: probes::CURRENT_PROBE_REGISTRY_VERSION,
+        salt: "0123456789abcdef".to_string(),
+    }
+}
+
+#[test]
+fn test_report_nonce_is_single_use_for_the_issued_client() {
+    let _lock = crate::test_support::lock_env();
+    let store = crate::test_support::InMemoryStore::default();
+    let token = nonce::issue_report_nonce("198.51.100.7", "Mozilla/5.0", &issued_probes(), 1_000);
+
+    assert_eq!(
+        nonce::verify_report_nonce(&store, token.as_str(), "198.51.100.7", "Mozilla/5.0", 1_001),
+        Ok(issued_probes())
+    );
+    assert_eq!(
+        nonce::verify_report_nonce(&store, token.as_str(), "198.51.100.7", "Mozilla/5.0", 1_002),
+        Err(nonce::NonceRejection::Replay)
+    );
+}
+
+#[test]
+fn test_report_nonce_rejects_forged_rebound_and_stale_reports() {
+    let _lock = crate::test_support::lock_env();
+    let store = crate::test_support::InMemoryStore::default();
+    let token = nonce::issue_report_nonce("198.51.100.7", "Mozilla/5.0", &issued_probes(), 1_000);
+
+    assert_eq!(
+        nonce::verify_report_nonce(&store, "", "198.51.100.7", "Mozilla/5.0", 1_001),
+        Err(nonce::NonceRejection::Missing)
+    );
+    let (payload, _) = token.split_once('.').unwrap();
+    let forged = format!("{}.{}", payload, "AAAA");
+    assert_eq!(
+        nonce::verify_report_nonce(
+            &store,
+            forged.as_str(),
+            "198.51.100.7",
+            "Mozilla/5.0",
+            1_001
+        ),
+        Err(nonce::NonceRejection::Invalid)
+    );
+    assert_eq!(
+        nonce::verify_report_nonce(&store, token.as_str(), "203.0.113.9", "Mozilla/5.0", 1_001),
+        Err(nonce::NonceRejection::BindingMismatch)
+    );
+    assert_eq!(
+        nonce::verify_report_nonce(&store, token.as_str(), "198.51.100.7", "curl/8.0", 1_001),
+        Err(nonce::NonceRejection::BindingMismatch)
+    );
+    assert_eq!(
+        nonce::verify_report_nonce(
+            &store,
+            token.as_str(),
+            "198.51.100.7",
+            "Mozilla/5.0",
+            1_000 + nonce::CDP_REPORT_NONCE_TTL_SECONDS + 1
+        ),
+        Err(nonce::NonceRejection::Expired)
+    );
+    // Rejected attempts do not consume the nonce.
+    assert_eq!(
+        nonce::verify_report_nonce(&store, token.as_str(), "198.51.100.7", "Mozilla/5.0", 1_001),
+        Ok(issued_probes())
+    );
+}
+
+#[test]
+fn test_report_script_embeds_nonce() {
+    let script = get_cdp_report_script("/cdp-report", "payload.sig");
+    assert!(script.contains("nonce: 'payload.sig'"));
+}
+
+#[test]
+fn test_probe_registries_have_unique_weighted_probes() {
+    for version in 1..=probes::CURRENT_PROBE_REGISTRY_VERSION {
+        let registry = probes::registry(version).expect("every issued version stays resolvable");
+        for (index, probe) in registry.iter().enumerate() {
+            assert!(probe.weight > 0.0, "{} needs a positive weight", probe.id);
+            assert!(registry[..index].iter().all(|other| other.id != probe.id));
+        }
+    }
+    assert!(probes::registry(0).is_none());
+    assert_eq!(
+        probes::tier_of("worker_navigator_mismatch"),
+        Some(probes::ProbeTier::Hard)
+    );
+}
+
+#[test]
+fn test_assembled_probe_suite_randomizes_identifiers_per_salt() {
+    let base = get_cdp_detection_script();
+    let a = probes::assemble(base, probes::CURRENT_PROBE_REGISTRY_VERSION, "salt-a");
+    let b = probes::assemble(base, probes::CURRENT_PROBE_REGISTRY_VERSION, "salt-b");
+
+    assert_ne!(a.entry, b.entry);
+    assert!(a
+        .script
+        .contains(&format!("window.{} = function()", a.entry)));
+    assert!(!a.script.contains("_checkCDPAutomation"));
+    assert!(!a.script.contains("_cdpScore"));
+    for probe in probes::current_registry() {
+        let code = probes::probe_code("salt-a", probe.id);
+        assert!(
+            a.script.contains(&format!("'{}'", code)),
+            "{} missing",
+            probe.id
+        );
+        assert!(!b.script.contains(&code));
+    }
+}
+
+#[test]
+fn test_report_codes_decode_and_score_against_the_issued_registry() {
+    let version = probes::CURRENT_PROBE_REGISTRY_VERSION;
+    let codes = vec![
+        probes::probe_code("salt-a", "webgl_renderer"),
+        probes::probe_code("salt-a", "cdp_timing"),
+        probes::probe_code("salt-b", "webdriver"),
+        "webdriver".to_string(),
+    ];
+    let checks = probes::decode_checks(version, "salt-a", &codes);
+    assert_eq!(checks, vec!["webgl_renderer", "cdp_timing"]);
+    assert!((probes::score(version, &checks) - 1.2).abs() < 0.001);
+
+    // Version 1 suites never carried the new probes.
+    assert_eq!(
+        probes::decode_checks(1, "salt-a", &codes),
+        vec!["cdp_timing"]
+    );
+    assert!(probes::decode_checks(99, "salt-a", &codes).is_empty());
+}
+
+#[test]
+fn test_cdp_tier_uses_registry_tiers_for_new_probes() {
+    let hard = CdpReport {
+        cdp_detected: true,
+        score: 1.0,
+        checks: vec!["worker_navigator_mismatch".to_string()],
+    };
+    assert_eq!(classify_cdp_tier(&hard, 0.8), CdpTier::Strong);
+
+    let soft = CdpReport {
+        cdp_detected: true,
+        score: 1.3,
+        checks: vec![
+            "webgl_renderer".to_string(),
+            "iframe_content_window".to_string(),
+        ],
+    };
+    assert_eq!(classify_cdp_tier(&soft, 0.8), CdpTier::Strong);
+}
@@ -2,8 +2,6 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use spin_sdk::http::Request;
 use std::env;
-#[cfg(not(test))]
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::signals::botness::{BotSignal, SignalFamily, SignalProvenance};
 
@@ -13,6 +11,8 @@ const FP_TEMPORAL_TRANSITION_KEY: &str = "fp_temporal_transition";
 const FP_FLOW_VIOLATION_KEY: &str = "fp_flow_violation";
 const FP_PERSISTENCE_MARKER_KEY: &str = "fp_persistence_marker_missing";
 const FP_UNTRUSTED_TRANSPORT_HEADER_KEY: &str = "fp_untrusted_transport_header";
+const FP_TLS_REPUTATION_KEY: &str = "fp_tls_reputation";
+const FP_HEADER_SHAPE_MISMATCH_KEY: &str = "fp_header_shape_mismatch";
 
 const FP_KEY_PREFIX_STATE: &str = "fp:state:";
 const FP_KEY_PREFIX_FLOW: &str = "fp:flow:";
@@ -24,6 +24,8 @@ const WEIGHT_TEMPORAL_TRANSITION: u8 = 2;
 const WEIGHT_FLOW_VIOLATION: u8 = 2;
 const WEIGHT_PERSISTENCE_MARKER_MISSING: u8 = 1;
 const WEIGHT_UNTRUSTED_TRANSPORT_HEADER: u8 = 3;
+const WEIGHT_TLS_REPUTATION: u8 = 3;
+const WEIGHT_HEADER_SHAPE_MISMATCH: u8 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct FingerprintState {
@@ -32,6 +34,26 @@ struct FingerprintState {
     ja4_hash: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeaderOrderClass {
+    Chromium,
+    Firefox,
+    Safari,
+    Library,
+    Unknown,
+}
+
+/// Compact header-shape fingerprint: which browser-default headers are present,
+/// whether Sec-Fetch metadata agrees with itself, and the edge-observed order class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HeaderShape {
+    accept_language: bool,
+    accept_encoding: bool,
+    sec_fetch_present: bool,
+    sec_fetch_consistent: bool,
+    order_class: HeaderOrderClass,
+}
+
 #[derive(Debug, Default)]
 struct TransportEvidence {
     ja3: Option<String>,
@@ -43,15 +65,12 @@ struct TransportEvidence {
 
 #[cfg(not(test))]
 fn now_ts() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
+    crate::runtime::clock::now_ts()
 }
 
 #[cfg(test)]
 fn now_ts() -> u64 {
-    1_700_000_000
+    crate::runtime::clock::pinned_ts().unwrap_or(1_700_000_000)
 }
 
 fn header_value(req: &Request, name: &str) -> Option<String> {
@@ -132,6 +151,105 @@ fn detect_ua_client_hint_mismatch(req: &Request) -> bool {
     family_mismatch || mobile_mismatch
 }
 
+fn sec_fetch_metadata_consistent(req: &Request) -> bool {
+    let mode = header_value(req, "sec-fetch-mode").map(|v| v.to_ascii_lowercase());
+    let dest = header_value(req, "sec-fetch-dest").map(|v| v.to_ascii_lowercase());
+    let user = header_value(req, "sec-fetch-user");
+    let Some(mode) = mode else {
+        // Sec-Fetch-Dest/User without Sec-Fetch-Mode never comes from a real browser.
+        return dest.is_none() && user.is_none();
+    };
+    if mode == "navigate" {
+        let dest_ok = matches!(
+            dest.as_deref(),
+            None | Some("document" | "iframe" | "frame" | "embed" | "object")
+        );
+        let accepts_html = header_value(req, "accept")
+            .map(|accept| {
+                let lower = accept.to_ascii_lowercase();
+                lower.contains("text/html") || lower.contains("*/*")
+            })
+            .unwrap_or(false);
+        return dest_ok && accepts_html;
+    }
+    // Sec-Fetch-User is only sent for user-activated navigations.
+    user.is_none() && !matches!(dest.as_deref(), Some("document"))
+}
+
+fn classify_header_order(raw: &str) -> HeaderOrderClass {
+    let names: Vec<String> = raw
+        .split(',')
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+    let position = |name: &str| names.iter().position(|candidate| candidate == name);
+    let before = |first: &str, second: &str| match (position(first), position(second)) {
+        (Some(a), Some(b)) => Some(a < b),
+        _ => None,
+    };
+
+    if before("accept-encoding", "accept") == Some(true) {
+        return HeaderOrderClass::Library;
+    }
+    if position("sec-fetch-mode").is_none() && position("accept-language").is_none() {
+        return HeaderOrderClass::Library;
+    }
+    if before("accept", "user-agent") == Some(true) {
+        return HeaderOrderClass::Safari;
+    }
+    if before("sec-fetch-dest", "sec-fetch-mode") == Some(true)
+        || before("accept-language", "accept-encoding") == Some(true)
+    {
+        return HeaderOrderClass::Firefox;
+    }
+    if before("sec-fetch-mode", "sec-fetch-dest") == Some(true)
+        || before("accept-encoding", "accept-language") == Some(true)
+    {
+        return HeaderOrderClass::Chromium;
+    }
+    HeaderOrderClass::Unknown
+}
+
+fn extract_header_shape(req: &Request, headers_trusted: bool) -> HeaderShape {
+    let order_class = if headers_trusted {
+        header_value(req, "x-shuma-edge-header-order")
+            .filter(|raw| raw.len() <= 2048)
+            .map(|raw| classify_header_order(raw.as_str()))
+            .unwrap_or(HeaderOrderClass::Unknown)
+    } else {
+        HeaderOrderClass::Unknown
+    };
+    HeaderShape {
+        accept_language: header_value(req, "accept-language").is_some(),
+        accept_encoding: header_value(req, "accept-encoding").is_some(),
+        sec_fetch_present: header_value(req, "sec-fetch-mode").is_some()
+            || header_value(req, "sec-fetch-site").is_some()
+            || header_value(req, "sec-fetch-dest").is_some(),
+        sec_fetch_consistent: sec_fetch_metadata_consistent(req),
+        order_class,
+    }
+}
+
+fn header_shape_mismatch(ua_family: &str, shape: &HeaderShape) -> bool {
+    if ua_family == "other" {
+        return false;
+    }
+    // Every modern engine sends Sec-Fetch metadata; Safari only from 16.4, so it is exempt.
+    let sec_fetch_expected = ua_family != "safari";
+    let order_consistent = match shape.order_class {
+        HeaderOrderClass::Unknown => true,
+        HeaderOrderClass::Library => false,
+        HeaderOrderClass::Chromium => matches!(ua_family, "chrome" | "edge"),
+        HeaderOrderClass::Firefox => ua_family == "firefox",
+        HeaderOrderClass::Safari => ua_family == "safari",
+    };
+    !shape.accept_language
+        || !shape.accept_encoding
+        || (sec_fetch_expected && !shape.sec_fetch_present)
+        || !shape.sec_fetch_consistent
+        || !order_consistent
+}
+
 fn sanitize_transport_token(value: Option<String>) -> Option<String> {
     let value = value?;
     let trimmed = value.trim();
@@ -187,6 +305,15 @@ fn extract_transport_evidence(req: &Request, headers_trusted: bool) -> Transport
     }
 }
 
+/// Returns the sanitized JA3/JA4 values, or `(None, None)` when edge headers are untrusted.
+pub(crate) fn trusted_tls_fingerprints(
+    req: &Request,
+    headers_trusted: bool,
+) -> (Option<String>, Option<String>) {
+    let transport = extract_transport_evidence(req, headers_trusted);
+    (transport.ja3, transport.ja4)
+}
+
 fn fingerprint_secret() -> String {
     env::var("SHUMA_JS_SECRET")
         .ok()
@@ -205,7 +332,15 @@ fn hash_prefix(input: &str, chars: usize) -> String {
     hex.chars().take(chars).collect()
 }
 
-fn flow_identity(ip: &str, cfg: &crate::config::Config) -> String {
+/// Keyed hash of `ip`, for keys that must not hold raw addresses whatever the config.
+pub(crate) fn pseudonymous_ip(ip: &str) -> String {
+    hash_prefix(
+        format!("{}|{}", fingerprint_secret(), ip.trim()).as_str(),
+        24,
+    )
+}
+
+pub(crate) fn flow_identity(ip: &str, cfg: &crate::config::Config) -> String {
     if !cfg.fingerprint_pseudonymize {
         return ip
             .chars()
@@ -218,10 +353,7 @@ fn flow_identity(ip: &str, cfg: &crate::config::Config) -> String {
             })
             .collect();
     }
-    hash_prefix(
-        format!("{}|{}", fingerprint_secret(), ip.trim()).as_str(),
-        24,
-    )
+    pseudonymous_ip(ip)
 }
 
 fn load_state<S: crate::challenge::KeyValueStore>(
@@ -348,13 +480,18 @@ fn ua_transport_family_mismatch(ua_family: &str, transport: &TransportEvidence)
     edge_family != "other" && ua_family != "other" && edge_family != ua_family
 }
 
-fn fingerprint_signal_catalog() -> [(&'static str, &'static str, SignalFamily); 6] {
+fn fingerprint_signal_catalog() -> [(&'static str, &'static str, SignalFamily); 8] {
     [
         (
             FP_UA_CH_MISMATCH_KEY,
             "UA and client-hint mismatch",
             SignalFamily::FingerprintHeaderRuntime,
         ),
+        (
+            FP_HEADER_SHAPE_MISMATCH_KEY,
+            "Header shape inconsistent with claimed browser",
+            SignalFamily::FingerprintHeaderRuntime,
+        ),
         (
             FP_UA_TRANSPORT_MISMATCH_KEY,
             "UA and transport/browser mismatch",
@@ -380,6 +517,11 @@ fn fingerprint_signal_catalog() -> [(&'static str, &'static str, SignalFamily);
             "Untrusted transport fingerprint headers present",
             SignalFamily::FingerprintTransport,
         ),
+        (
+            FP_TLS_REPUTATION_KEY,
+            "TLS fingerprint on reputation list",
+            SignalFamily::FingerprintTransport,
+        ),
     ]
 }
 
@@ -413,9 +555,20 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
     let ua_family = extract_ua_family(req);
     let transport = extract_transport_evidence(req, headers_trusted);
     let ua_ch_mismatch = detect_ua_client_hint_mismatch(req);
+    let header_shape = extract_header_shape(req, headers_trusted);
+    let header_shape_mismatch = header_shape_mismatch(ua_family, &header_shape);
     let ua_transport_mismatch = ua_transport_family_mismatch(ua_family, &transport);
     let now = now_ts();
 
+    // Allow and block matches are resolved by the pipeline; only score-action matches add botness.
+    let tls_reputation_match = crate::signals::tls_reputation::evaluate(
+        cfg,
+        transport.ja3.as_deref(),
+        transport.ja4.as_deref(),
+    )
+    .filter(|details| details.action == crate::config::TlsFingerprintAction::Score)
+    .is_some();
+
     let ja4_hash = transport.ja4.as_deref().map(|ja4| hash_prefix(ja4, 16));
     let previous_state = load_state(store, identity.as_str());
     let temporal_transition = temporal_transition_impossible(
@@ -426,8 +579,11 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
         ja4_hash.as_deref(),
     );
 
-    let mismatch_observed =
-        ua_ch_mismatch || ua_transport_mismatch || temporal_transition || transport.untrusted_headers_present;
+    let mismatch_observed = ua_ch_mismatch
+        || header_shape_mismatch
+        || ua_transport_mismatch
+        || temporal_transition
+        || transport.untrusted_headers_present;
     let mismatch_count = update_flow_mismatch_count(
         store,
         identity.as_str(),
@@ -438,7 +594,7 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
     let flow_violation = mismatch_count >= cfg.fingerprint_flow_violation_threshold as u32;
 
     let persistence_marker_missing =
-        has_cookie(req, "js_verified") && !has_cookie(req, "shuma_fp") && mismatch_observed;
+        has_cookie(req, crate::challenge::clearance::CLEARANCE_COOKIE_NAME) && !has_cookie(req, "shuma_fp") && mismatch_observed;
 
     store_state(
         store,
@@ -454,6 +610,9 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
     if ua_ch_mismatch {
         increment_counter(store, "fingerprint:ua_ch_mismatch");
     }
+    if header_shape_mismatch {
+        increment_counter(store, "fingerprint:header_shape_mismatch");
+    }
     if ua_transport_mismatch {
         increment_counter(store, "fingerprint:ua_transport_mismatch");
     }
@@ -469,6 +628,9 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
     if transport.untrusted_headers_present {
         increment_counter(store, "fingerprint:untrusted_transport_header");
     }
+    if tls_reputation_match {
+        increment_counter(store, "fingerprint:tls_reputation");
+    }
 
     let transport_identity_present = transport.ja3.is_some() || transport.ja4.is_some();
     let edge_confidence = if transport.edge_score.unwrap_or(0.0) >= 80.0 {
@@ -480,7 +642,7 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
     } else {
         7
     };
-    let mut signals = Vec::with_capacity(6);
+    let mut signals = Vec::with_capacity(8);
     signals.push(BotSignal::scored_with_metadata(
         FP_UA_CH_MISMATCH_KEY,
         "UA and client-hint mismatch",
@@ -490,6 +652,19 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
         8,
         SignalFamily::FingerprintHeaderRuntime,
     ));
+    signals.push(BotSignal::scored_with_metadata(
+        FP_HEADER_SHAPE_MISMATCH_KEY,
+        "Header shape inconsistent with claimed browser",
+        header_shape_mismatch,
+        WEIGHT_HEADER_SHAPE_MISMATCH,
+        SignalProvenance::Internal,
+        if header_shape.order_class == HeaderOrderClass::Unknown {
+            6
+        } else {
+            8
+        },
+        SignalFamily::FingerprintHeaderRuntime,
+    ));
     signals.push(BotSignal::scored_with_metadata(
         FP_UA_TRANSPORT_MISMATCH_KEY,
         "UA and transport/browser mismatch",
@@ -535,6 +710,15 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
         9,
         SignalFamily::FingerprintTransport,
     ));
+    signals.push(BotSignal::scored_with_metadata(
+        FP_TLS_REPUTATION_KEY,
+        "TLS fingerprint on reputation list",
+        tls_reputation_match,
+        WEIGHT_TLS_REPUTATION,
+        SignalProvenance::ExternalTrusted,
+        edge_confidence,
+        SignalFamily::FingerprintTransport,
+    ));
 
     // Keep transport signal availability explicit when trusted transport ingestion is unavailable.
     if !headers_trusted && !transport.untrusted_headers_present {
@@ -553,8 +737,9 @@ pub(crate) fn collect_bot_signals<S: crate::challenge::KeyValueStore>(
 #[cfg(test)]
 mod tests {
     use super::{
-        collect_bot_signals, FP_FLOW_VIOLATION_KEY, FP_TEMPORAL_TRANSITION_KEY, FP_UA_CH_MISMATCH_KEY,
-        FP_UA_TRANSPORT_MISMATCH_KEY, FP_UNTRUSTED_TRANSPORT_HEADER_KEY,
+        classify_header_order, collect_bot_signals, HeaderOrderClass, FP_FLOW_VIOLATION_KEY,
+        FP_HEADER_SHAPE_MISMATCH_KEY, FP_TEMPORAL_TRANSITION_KEY, FP_TLS_REPUTATION_KEY,
+        FP_UA_CH_MISMATCH_KEY, FP_UA_TRANSPORT_MISMATCH_KEY, FP_UNTRUSTED_TRANSPORT_HEADER_KEY,
     };
     use spin_sdk::http::Request;
     use std::collections::HashMap;
@@ -688,4 +873,124 @@ mod tests {
         let second = collect_bot_signals(&store, &req, &cfg, "203.0.113.13", true);
         assert!(signal_active(&second, FP_FLOW_VIOLATION_KEY));
     }
+
+    #[test]
+    fn tls_reputation_scores_only_trusted_score_action_matches() {
+        let store = MockStore::default();
+        let mut cfg = crate::config::defaults().clone();
+        cfg.fingerprint_signal_enabled = true;
+        cfg.fingerprint_tls_managed_policies = vec![crate::config::TlsFingerprintManagedPolicy {
+            set_id: "go_net_http".to_string(),
+            enabled: true,
+            action: crate::config::TlsFingerprintAction::Score,
+        }];
+        let req = request(
+            "/",
+            &[
+                ("user-agent", "Go-http-client/1.1"),
+                ("x-shuma-edge-ja4", "t13d190900_9dc949149365_97f8aa674fd9"),
+            ],
+        );
+
+        let trusted = collect_bot_signals(&store, &req, &cfg, "203.0.113.14", true);
+        assert!(signal_active(&trusted, FP_TLS_REPUTATION_KEY));
+        let untrusted = collect_bot_signals(&store, &req, &cfg, "203.0.113.15", false);
+        assert!(!signal_active(&untrusted, FP_TLS_REPUTATION_KEY));
+
+        cfg.fingerprint_tls_managed_policies[0].action = crate::config::TlsFingerprintAction::Allow;
+        let allowed = collect_bot_signals(&store, &req, &cfg, "203.0.113.16", true);
+        assert!(!signal_active(&allowed, FP_TLS_REPUTATION_KEY));
+    }
+
+    const CHROME_NAVIGATION_HEADERS: [(&str, &str); 7] = [
+        ("user-agent", "Mozilla/5.0 Chrome/120.0"),
+        ("accept", "text/html,application/xhtml+xml,*/*;q=0.8"),
+        ("accept-language", "en-GB,en;q=0.9"),
+        ("accept-encoding", "gzip, deflate, br"),
+        ("sec-fetch-mode", "navigate"),
+        ("sec-fetch-dest", "document"),
+        ("sec-fetch-site", "none"),
+    ];
+
+    #[test]
+    fn header_order_classes_follow_engine_defaults() {
+        assert_eq!(
+            classify_header_order(
+                "host,user-agent,accept,sec-fetch-site,sec-fetch-mode,sec-fetch-dest,accept-encoding,accept-language"
+            ),
+            HeaderOrderClass::Chromium
+        );
+        assert_eq!(
+            classify_header_order(
+                "host,user-agent,accept,accept-language,accept-encoding,sec-fetch-dest,sec-fetch-mode"
+            ),
+            HeaderOrderClass::Firefox
+        );
+        assert_eq!(
+            classify_header_order("host,accept,sec-fetch-site,accept-language,user-agent,accept-encoding"),
+            HeaderOrderClass::Safari
+        );
+        assert_eq!(
+            classify_header_order("Host, User-Agent, Accept-Encoding, Accept, Connection"),
+            HeaderOrderClass::Library
+        );
+        assert_eq!(classify_header_order("host"), HeaderOrderClass::Library);
+    }
+
+    #[test]
+    fn browser_shaped_headers_do_not_trigger_header_shape_mismatch() {
+        let store = MockStore::default();
+        let mut cfg = crate::config::defaults().clone();
+        cfg.fingerprint_signal_enabled = true;
+        let req = request("/", &CHROME_NAVIGATION_HEADERS);
+
+        let signals = collect_bot_signals(&store, &req, &cfg, "203.0.113.20", false);
+        assert!(!signal_active(&signals, FP_HEADER_SHAPE_MISMATCH_KEY));
+    }
+
+    #[test]
+    fn library_shaped_headers_under_browser_ua_trigger_header_shape_mismatch() {
+        let store = MockStore::default();
+        let mut cfg = crate::config::defaults().clone();
+        cfg.fingerprint_signal_enabled = true;
+        let req = request(
+            "/",
+            &[
+                ("user-agent", "Mozilla/5.0 Chrome/120.0"),
+                ("accept", "*/*"),
+                ("accept-encoding", "gzip, deflate"),
+            ],
+        );
+
+        let signals = collect_bot_signals(&store, &req, &cfg, "203.0.113.21", false);
+        assert!(signal_active(&signals, FP_HEADER_SHAPE_MISMATCH_KEY));
+
+        let curl = request("/", &[("user-agent", "curl/8.4.0"), ("accept", "*/*")]);
+        let signals = collect_bot_signals(&store, &curl, &cfg, "203.0.113.22", false);
+        assert!(!signal_active(&signals, FP_HEADER_SHAPE_MISMATCH_KEY));
+    }
+
+    #[test]
+    fn header_shape_flags_sec_fetch_inconsistency_and_trusted_library_order() {
+        let store = MockStore::default();
+        let mut cfg = crate::config::defaults().clone();
+        cfg.fingerprint_signal_enabled = true;
+
+        let mut headers = CHROME_NAVIGATION_HEADERS.to_vec();
+        headers[5] = ("sec-fetch-dest", "image");
+        let req = request("/", &headers);
+        let signals = collect_bot_signals(&store, &req, &cfg, "203.0.113.23", false);
+        assert!(signal_active(&signals, FP_HEADER_SHAPE_MISMATCH_KEY));
+
+        let mut headers = CHROME_NAVIGATION_HEADERS.to_vec();
+        headers.push((
+            "x-shuma-edge-header-order",
+            "host,user-agent,accept-encoding,accept,accept-language,sec-fetch-mode",
+        ));
+        let req = request("/", &headers);
+        let untrusted = collect_bot_signals(&store, &req, &cfg, "203.0.113.24", false);
+        assert!(!signal_active(&untrusted, FP_HEADER_SHAPE_MISMATCH_KEY));
+        let trusted = collect_bot_signals(&store, &req, &cfg, "203.0.113.25", true);
+        assert!(signal_active(&trusted, FP_HEADER_SHAPE_MISMATCH_KEY));
+    }
 }
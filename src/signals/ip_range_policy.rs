@@ -95,6 +95,14 @@ static MANAGED_CATALOG: Lazy<ManagedCatalog> = Lazy::new(|| {
         .unwrap_or_else(|err| panic!("Invalid managed IP range catalog: {}", err))
 });
 
+static MANAGED_SET_NETS: Lazy<HashMap<String, Vec<IpNet>>> = Lazy::new(|| {
+    MANAGED_CATALOG
+        .sets
+        .iter()
+        .map(|set| (set.id.to_ascii_lowercase(), parse_cidr_list(&set.cidrs)))
+        .collect()
+});
+
 static COMPILED_POLICY_CACHE: Lazy<Mutex<HashMap<u64, CompiledPolicy>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
@@ -116,6 +124,10 @@ fn managed_catalog_is_stale(max_staleness_hours: u64, now_unix: u64) -> bool {
     managed_catalog_age_hours(now_unix) > max_staleness_hours
 }
 
+pub(crate) fn managed_catalog_stale(managed_max_staleness_hours: u64) -> bool {
+    managed_catalog_is_stale(managed_max_staleness_hours, current_unix())
+}
+
 pub(crate) fn managed_set_metadata_with_staleness(
     managed_max_staleness_hours: u64,
 ) -> Vec<ManagedSetMetadata> {
@@ -161,6 +173,17 @@ pub(crate) fn has_managed_set(set_id: &str) -> bool {
         .any(|set| set.id.eq_ignore_ascii_case(normalized.as_str()))
 }
 
+/// Whether `ip` falls in the named managed set, independent of any configured policy.
+pub(crate) fn managed_set_contains(set_id: &str, ip: &str) -> bool {
+    let Ok(ip_addr) = ip.trim().parse::<IpAddr>() else {
+        return false;
+    };
+    MANAGED_SET_NETS
+        .get(set_id.trim().to_ascii_lowercase().as_str())
+        .map(|nets| nets.iter().any(|net| net.contains(&ip_addr)))
+        .unwrap_or(false)
+}
+
 fn policy_cache_key(cfg: &Config) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     cfg.ip_range_policy_mode.as_str().hash(&mut hasher);
@@ -408,8 +431,8 @@ pub(crate) fn evaluate(cfg: &Config, ip: &str) -> Evaluation {
 #[cfg(test)]
 mod tests {
     use super::{
-        evaluate, evaluate_with_now, has_managed_set, managed_set_metadata_with_staleness,
-        Evaluation, MatchSource, MANAGED_CATALOG,
+        evaluate, evaluate_with_now, has_managed_set, managed_set_contains,
+        managed_set_metadata_with_staleness, Evaluation, MatchSource, MANAGED_CATALOG,
     };
     use crate::config::{defaults, IpRangeManagedPolicy, IpRangePolicyAction, IpRangePolicyMode, IpRangePolicyRule};
 
@@ -421,6 +444,15 @@ mod tests {
         assert!(metadata.iter().any(|entry| entry.id == "openai_chatgpt_user"));
     }
 
+    #[test]
+    fn managed_set_membership_ignores_configured_policies() {
+        assert!(managed_set_contains("openai_gptbot", "20.171.206.9"));
+        assert!(managed_set_contains("OpenAI_GPTBot", "20.171.206.9"));
+        assert!(!managed_set_contains("openai_gptbot", "203.0.113.9"));
+        assert!(!managed_set_contains("unknown_set", "20.171.206.9"));
+        assert!(!managed_set_contains("openai_gptbot", "not-an-ip"));
+    }
+
     #[test]
     fn emergency_allowlist_short_circuits_matches() {
         let mut cfg = defaults().clone();
@@ -1,8 +1,8 @@
-/// Returns true if the request needs JS verification (no valid js_verified cookie),
+/// Returns true if the request needs JS verification (no valid clearance cookie),
 /// but bypasses challenge for whitelisted browsers.
-pub fn needs_js_verification_with_whitelist(
+pub fn needs_js_verification_with_whitelist<S: crate::challenge::KeyValueStore>(
     req: &Request,
-    _store: &Store,
+    _store: &S,
     _site_id: &str,
     ip: &str,
     browser_whitelist: &[(String, u32)],
@@ -26,55 +26,38 @@ pub fn needs_js_verification_with_whitelist(
 // JavaScript verification and challenge logic for WASM Bot Defence
 // Handles JS-based bot detection and challenge/response for suspicious clients.
 
-use base64::{engine::general_purpose, Engine as _};
-use hmac::{Hmac, Mac};
-use sha2::Sha256;
+use crate::challenge::clearance::{self, ClearanceLevel};
 use spin_sdk::http::{Request, Response};
-use spin_sdk::key_value::Store;
 
-/// Secret used for HMAC token generation for JS verification cookies.
-/// Pull from env to avoid a repo-known static secret in production.
-fn get_js_secret() -> String {
-    crate::config::env_string_required("SHUMA_JS_SECRET")
-}
-
-/// Generates a HMAC-based token for a given IP, used in the js_verified cookie.
-fn make_token(ip: &str) -> String {
-    let secret = get_js_secret();
-    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
-    mac.update(ip.as_bytes());
-    let result = mac.finalize().into_bytes();
-    general_purpose::STANDARD.encode(result)
-}
-
-/// Build the js_verified cookie value for a given IP.
-pub fn js_verified_cookie(ip: &str) -> String {
-    format!(
-        "js_verified={}; path=/; SameSite=Strict; Max-Age=86400",
-        make_token(ip)
-    )
+/// Build the clearance cookie the JS interstitial sets from script for a given client.
+/// Not HttpOnly because page script writes it; higher levels are always issued server-side.
+fn js_clearance_cookie(ip: &str, user_agent: &str) -> String {
+    let token = clearance::issue_token(
+        crate::signals::ip_identity::bucket_ip(ip).as_str(),
+        crate::challenge::operation_envelope::user_agent_bucket(user_agent).as_str(),
+        ClearanceLevel::VerifyJs,
+        clearance::CLEARANCE_TTL_SECONDS,
+    );
+    clearance::cookie_value(token.as_str(), clearance::CLEARANCE_TTL_SECONDS, false)
 }
 
 pub fn fingerprint_marker_cookie() -> &'static str {
     "shuma_fp=1; path=/; SameSite=Strict; Max-Age=1800"
 }
 
-/// Returns true if the request needs JS verification (no valid js_verified cookie).
-/// Checks for a valid js_verified cookie matching the HMAC token for the IP.
-pub fn needs_js_verification(req: &Request, _store: &Store, _site_id: &str, ip: &str) -> bool {
-    // Check for a valid js_verified cookie
-    if let Some(header) = req.header("cookie") {
-        let cookie = header.as_str().unwrap_or("");
-        for part in cookie.split(';') {
-            let trimmed = part.trim();
-            if let Some(val) = trimmed.strip_prefix("js_verified=") {
-                if val == make_token(ip) {
-                    return false;
-                }
-            }
-        }
-    }
-    true
+/// Returns true if the request needs JS verification (no valid clearance cookie).
+/// Any clearance level bound to this IP bucket and user agent satisfies JS verification.
+pub fn needs_js_verification<S: crate::challenge::KeyValueStore>(
+    req: &Request,
+    _store: &S,
+    _site_id: &str,
+    ip: &str,
+) -> bool {
+    let ua = req
+        .header("user-agent")
+        .map(|v| v.as_str().unwrap_or(""))
+        .unwrap_or("");
+    clearance::request_clearance_level(req, ip, ua).is_none()
 }
 
 pub fn bot_signal(
@@ -102,42 +85,120 @@ pub fn bot_signal(
     )
 }
 
-/// Returns a Response with a JS challenge page that sets the js_verified cookie for the client IP.
-/// Also injects CDP detection if enabled in the config.
+/// Web Worker body for `pow_algorithm=scrypt`: scrypt with r = 8, p = 1 (PBKDF2 from WebCrypto,
+/// Salsa20/8 ROMix in plain JS), matching `challenge::pow` verification. It runs off the main
+/// thread because each attempt blocks for tens of milliseconds and holds `memoryKib` of state.
+const SCRYPT_POW_WORKER_SCRIPT: &str = r#"'use strict';
+function R(a, b) { return (a << b) | (a >>> (32 - b)); }
+function salsa208(B, o, x) {
+  for (let i = 0; i < 16; i++) x[i] = B[o + i];
+  for (let i = 0; i < 8; i += 2) {
+    x[4] ^= R(x[0] + x[12], 7); x[8] ^= R(x[4] + x[0], 9); x[12] ^= R(x[8] + x[4], 13); x[0] ^= R(x[12] + x[8], 18);
+    x[9] ^= R(x[5] + x[1], 7); x[13] ^= R(x[9] + x[5], 9); x[1] ^= R(x[13] + x[9], 13); x[5] ^= R(x[1] + x[13], 18);
+    x[14] ^= R(x[10] + x[6], 7); x[2] ^= R(x[14] + x[10], 9); x[6] ^= R(x[2] + x[14], 13); x[10] ^= R(x[6] + x[2], 18);
+    x[3] ^= R(x[15] + x[11], 7); x[7] ^= R(x[3] + x[15], 9); x[11] ^= R(x[7] + x[3], 13); x[15] ^= R(x[11] + x[7], 18);
+    x[1] ^= R(x[0] + x[3], 7); x[2] ^= R(x[1] + x[0], 9); x[3] ^= R(x[2] + x[1], 13); x[0] ^= R(x[3] + x[2], 18);
+    x[6] ^= R(x[5] + x[4], 7); x[7] ^= R(x[6] + x[5], 9); x[4] ^= R(x[7] + x[6], 13); x[5] ^= R(x[4] + x[7], 18);
+    x[11] ^= R(x[10] + x[9], 7); x[8] ^= R(x[11] + x[10], 9); x[9] ^= R(x[8] + x[11], 13); x[10] ^= R(x[9] + x[8], 18);
+    x[12] ^= R(x[15] + x[14], 7); x[13] ^= R(x[12] + x[15], 9); x[14] ^= R(x[13] + x[12], 13); x[15] ^= R(x[14] + x[13], 18);
+  }
+  for (let i = 0; i < 16; i++) B[o + i] += x[i];
+}
+const R8 = 8, WORDS = 32 * R8;
+function blockMix(B, Y, x) {
+  const t = new Uint32Array(16);
+  for (let i = 0; i < 16; i++) t[i] = B[(2 * R8 - 1) * 16 + i];
+  for (let k = 0; k < 2 * R8; k++) {
+    for (let i = 0; i < 16; i++) t[i] ^= B[k * 16 + i];
+    salsa208(t, 0, x);
+    const dst = ((k & 1) ? R8 : 0) * 16 + (k >> 1) * 16;
+    for (let i = 0; i < 16; i++) Y[dst + i] = t[i];
+  }
+  B.set(Y);
+}
+function roMix(B, n, V, Y, x) {
+  for (let i = 0; i < n; i++) { V.set(B, i * WORDS); blockMix(B, Y, x); }
+  for (let i = 0; i < n; i++) {
+    const j = B[(2 * R8 - 1) * 16] & (n - 1);
+    for (let w = 0; w < WORDS; w++) B[w] ^= V[j * WORDS + w];
+    blockMix(B, Y, x);
+  }
+}
+function hasLeadingZeroBits(bytes, bits) {
+  let remaining = bits;
+  for (let i = 0; i < bytes.length; i++) {
+    if (remaining <= 0) return true;
+    if (remaining >= 8) { if (bytes[i] !== 0) return false; remaining -= 8; }
+    else return (bytes[i] & (0xFF << (8 - remaining))) === 0;
+  }
+  return true;
+}
+async function pbkdf2(key, salt, bytes) {
+  const bits = await crypto.subtle.deriveBits({ name: 'PBKDF2', salt: salt, iterations: 1, hash: 'SHA-256' }, key, bytes * 8);
+  return new Uint8Array(bits);
+}
+self.onmessage = async function (ev) {
+  const d = ev.data || {};
+  const n = d.memoryKib, enc = new TextEncoder();
+  const key = await crypto.subtle.importKey('raw', enc.encode(d.seed), 'PBKDF2', false, ['deriveBits']);
+  const V = new Uint32Array(n * WORDS), Y = new Uint32Array(WORDS), x = new Uint32Array(16);
+  for (let nonce = 0; ; nonce++) {
+    const salt = enc.encode(nonce.toString());
+    const B = new Uint32Array((await pbkdf2(key, salt, 4 * WORDS)).buffer);
+    roMix(B, n, V, Y, x);
+    const hash = await pbkdf2(key, new Uint8Array(B.buffer), 32);
+    if (hasLeadingZeroBits(hash, d.difficulty)) { self.postMessage({ nonce: nonce.toString() }); return; }
+  }
+};
+"#;
+
+/// Returns a Response with a JS challenge page that sets a JS-level clearance cookie for the client,
+/// unless `held_clearance` is already stronger. Also injects CDP detection if enabled in the config.
+#[allow(clippy::too_many_arguments)]
 pub fn inject_js_challenge(
     ip: &str,
     user_agent: &str,
+    held_clearance: Option<ClearanceLevel>,
     pow_enabled: bool,
-    pow_difficulty: u8,
+    pow_params: crate::challenge::pow::PowParams,
     pow_ttl_seconds: u64,
     cdp_probe_family: crate::config::CdpProbeFamily,
     cdp_probe_rollout_percent: u8,
+    messages: &crate::i18n::Messages,
 ) -> Response {
-    let cdp_script = crate::signals::cdp::get_cdp_detection_script_for_request(
+    let cdp_suite = crate::signals::cdp::issue_probe_suite(
         cdp_probe_family,
         cdp_probe_rollout_percent,
         ip,
+        user_agent,
+        crate::admin::now_ts(),
     );
+    let cdp_script = cdp_suite.script.as_str();
+    let cdp_entry = cdp_suite.entry.as_str();
+    let cdp_nonce = cdp_suite.nonce.as_str();
 
     if pow_enabled {
-        let challenge = crate::challenge::pow::issue_pow_challenge(
-            ip,
-            user_agent,
-            pow_difficulty,
-            pow_ttl_seconds,
-        );
+        let challenge =
+            crate::challenge::pow::issue_pow_challenge(ip, user_agent, pow_params, pow_ttl_seconds);
+        let scrypt_worker = match challenge.algorithm {
+            crate::config::PowAlgorithm::Scrypt => serde_json::to_string(SCRYPT_POW_WORKER_SCRIPT)
+                .unwrap_or_else(|_| "null".to_string())
+                .replace("</", "<\\/"),
+            crate::config::PowAlgorithm::Sha256 => "null".to_string(),
+        };
         let html = format!(
             r#"
-        <html><head><script>{cdp_script}</script></head><body>
+        <html lang="{lang}" dir="{dir}"><head><meta charset="UTF-8"><script>{cdp_script}</script></head><body>
         <script>
             // Run CDP detection before allowing access
-            if (window._checkCDPAutomation) {{
-                window._checkCDPAutomation().then(function(result) {{
+            if (window.{cdp_entry}) {{
+                window.{cdp_entry}().then(function(result) {{
                     if (result.detected) {{
                         fetch('/cdp-report', {{
                             method: 'POST',
                             headers: {{ 'Content-Type': 'application/json' }},
                             body: JSON.stringify({{
+                                nonce: "{cdp_nonce}",
                                 cdp_detected: true,
                                 score: result.score,
                                 checks: result.checks
@@ -149,6 +210,9 @@ pub fn inject_js_challenge(
 
             const POW_SEED = "{seed}";
             const SHUMA_POW_DIFFICULTY = {difficulty};
+            const SHUMA_POW_ALGORITHM = "{algorithm}";
+            const SHUMA_POW_MEMORY_KIB = {memory_kib};
+            const SCRYPT_WORKER_SOURCE = {scrypt_worker};
 
             function hasLeadingZeroBits(bytes, bits) {{
                 let remaining = bits;
@@ -186,13 +250,40 @@ pub fn inject_js_challenge(
                 }}
             }}
 
+            function solveScryptPow(seed, difficulty, memoryKib) {{
+                return new Promise(function(resolve, reject) {{
+                    const url = URL.createObjectURL(new Blob([SCRYPT_WORKER_SOURCE], {{ type: 'text/javascript' }}));
+                    const worker = new Worker(url);
+                    worker.onmessage = function(ev) {{
+                        worker.terminate();
+                        URL.revokeObjectURL(url);
+                        resolve(ev.data.nonce);
+                    }};
+                    worker.onerror = function(err) {{
+                        worker.terminate();
+                        URL.revokeObjectURL(url);
+                        reject(err);
+                    }};
+                    worker.postMessage({{ seed: seed, difficulty: difficulty, memoryKib: memoryKib }});
+                }});
+            }}
+
             async function runPow() {{
-                if (!window.crypto || !crypto.subtle) {{
-                    document.body.innerText = 'Proof-of-work requires a modern browser.';
+                const scrypt = SHUMA_POW_ALGORITHM === 'scrypt';
+                if (!window.crypto || !crypto.subtle || (scrypt && typeof Worker === 'undefined')) {{
+                    document.body.innerText = {pow_unsupported};
+                    return;
+                }}
+                document.body.innerText = {verifying};
+                let nonce;
+                try {{
+                    nonce = scrypt
+                        ? await solveScryptPow(POW_SEED, SHUMA_POW_DIFFICULTY, SHUMA_POW_MEMORY_KIB)
+                        : await solvePow(POW_SEED, SHUMA_POW_DIFFICULTY);
+                }} catch (_e) {{
+                    document.body.innerText = {pow_unsupported};
                     return;
                 }}
-                document.body.innerText = 'Verifying...';
-                const nonce = await solvePow(POW_SEED, SHUMA_POW_DIFFICULTY);
                 const resp = await fetch('/pow/verify', {{
                     method: 'POST',
                     headers: {{ 'Content-Type': 'application/json' }},
@@ -202,35 +293,53 @@ pub fn inject_js_challenge(
                     document.cookie = '{fp_marker_cookie}';
                     window.location.reload();
                 }} else {{
-                    document.body.innerText = 'Verification failed. Please refresh.';
+                    document.body.innerText = {verification_failed};
                 }}
             }}
 
             runPow();
     </script>
-    <noscript>Please enable JS to continue.</noscript>
+    <noscript>{enable_js}</noscript>
     </body></html>
     "#,
             seed = challenge.seed,
             difficulty = challenge.difficulty,
+            algorithm = challenge.algorithm.as_str(),
+            memory_kib = challenge.memory_kib,
+            scrypt_worker = scrypt_worker,
             fp_marker_cookie = fingerprint_marker_cookie(),
+            lang = messages.locale(),
+            dir = messages.dir(),
+            pow_unsupported = messages.js("js.pow_unsupported"),
+            verifying = messages.js("js.verifying"),
+            verification_failed = messages.js("js.verification_failed"),
+            enable_js = messages.text("js.enable_js"),
         );
         return Response::new(200, html);
     }
 
-    let token = make_token(ip);
+    let clearance_cookie_script =
+        if held_clearance.is_some_and(|held| held > ClearanceLevel::VerifyJs) {
+            String::new()
+        } else {
+            format!(
+                "document.cookie = '{}';",
+                js_clearance_cookie(ip, user_agent)
+            )
+        };
     let html = format!(
         r#"
-        <html><head><script>{cdp_script}</script></head><body>
+        <html lang="{lang}" dir="{dir}"><head><meta charset="UTF-8"><script>{cdp_script}</script></head><body>
         <script>
             // Run CDP detection before allowing access
-            if (window._checkCDPAutomation) {{
-                window._checkCDPAutomation().then(function(result) {{
+            if (window.{cdp_entry}) {{
+                window.{cdp_entry}().then(function(result) {{
                     if (result.detected) {{
                         fetch('/cdp-report', {{
                             method: 'POST',
                             headers: {{ 'Content-Type': 'application/json' }},
                             body: JSON.stringify({{
+                                nonce: "{cdp_nonce}",
                                 cdp_detected: true,
                                 score: result.score,
                                 checks: result.checks
@@ -239,14 +348,112 @@ pub fn inject_js_challenge(
                     }}
                 }});
             }}
-            document.cookie = 'js_verified={token}; path=/; SameSite=Strict; Max-Age=86400';
+            {clearance_cookie_script}
             document.cookie = '{fp_marker_cookie}';
             window.location.reload();
     </script>
-    <noscript>Please enable JS to continue.</noscript>
+    <noscript>{enable_js}</noscript>
     </body></html>
     "#,
-        fp_marker_cookie = fingerprint_marker_cookie()
+        fp_marker_cookie = fingerprint_marker_cookie(),
+        lang = messages.locale(),
+        dir = messages.dir(),
+        enable_js = messages.text("js.enable_js"),
     );
     Response::new(200, html)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::inject_js_challenge;
+
+    fn render(params: crate::challenge::pow::PowParams) -> String {
+        let cfg = crate::config::defaults();
+        let overrides = std::collections::BTreeMap::new();
+        let messages = crate::i18n::Messages::new("en", &overrides);
+        let resp = inject_js_challenge(
+            "198.51.100.7",
+            "Mozilla/5.0",
+            None,
+            true,
+            params,
+            cfg.pow_ttl_seconds,
+            cfg.cdp_probe_family,
+            cfg.cdp_probe_rollout_percent,
+            &messages,
+        );
+        String::from_utf8_lossy(resp.body()).to_string()
+    }
+
+    #[test]
+    fn scrypt_interstitial_embeds_worker_and_signed_params() {
+        let body = render(crate::challenge::pow::PowParams::scrypt(3, 2048));
+        assert!(body.contains("const SHUMA_POW_ALGORITHM = \"scrypt\";"));
+        assert!(body.contains("const SHUMA_POW_MEMORY_KIB = 2048;"));
+        assert!(body.contains("const SHUMA_POW_DIFFICULTY = 3;"));
+        assert!(body.contains("salsa208"));
+        // The worker source is a JS string literal and must not close the inline script.
+        assert_eq!(body.matches("</script>").count(), 2);
+    }
+
+    #[test]
+    fn sha256_interstitial_omits_scrypt_worker() {
+        let body = render(crate::challenge::pow::PowParams::sha256(15));
+        assert!(body.contains("const SHUMA_POW_ALGORITHM = \"sha256\";"));
+        assert!(body.contains("const SCRYPT_WORKER_SOURCE = null;"));
+        assert!(!body.contains("salsa208"));
+    }
+
+    #[test]
+    fn interstitial_reports_cdp_with_client_bound_nonce() {
+        let _lock = crate::test_support::lock_env();
+        let body = render(crate::challenge::pow::PowParams::sha256(15));
+        let nonce = body
+            .split("nonce: \"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .expect("interstitial should embed a CDP report nonce");
+        assert!(!body.contains("_checkCDPAutomation"));
+        let store = crate::test_support::InMemoryStore::default();
+        assert!(crate::signals::cdp::verify_report_nonce(
+            &store,
+            nonce,
+            "198.51.100.7",
+            "Mozilla/5.0",
+            crate::admin::now_ts()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn js_only_interstitial_does_not_overwrite_a_stronger_clearance() {
+        let cfg = crate::config::defaults();
+        let overrides = std::collections::BTreeMap::new();
+        let messages = crate::i18n::Messages::new("en", &overrides);
+        let render = |held| {
+            let resp = inject_js_challenge(
+                "198.51.100.7",
+                "Mozilla/5.0",
+                held,
+                false,
+                crate::challenge::pow::PowParams::from_config(cfg),
+                cfg.pow_ttl_seconds,
+                cfg.cdp_probe_family,
+                cfg.cdp_probe_rollout_percent,
+                &messages,
+            );
+            String::from_utf8_lossy(resp.body()).to_string()
+        };
+
+        assert!(render(None).contains("document.cookie = 'shuma_clearance="));
+        assert!(
+            render(Some(crate::challenge::clearance::ClearanceLevel::VerifyJs))
+                .contains("document.cookie = 'shuma_clearance=")
+        );
+        let strong = render(Some(
+            crate::challenge::clearance::ClearanceLevel::ChallengeStrong,
+        ));
+        assert!(!strong.contains("shuma_clearance="));
+        assert!(strong.contains("document.cookie = 'shuma_fp=1"));
+    }
+}
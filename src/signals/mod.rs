@@ -1,3 +1,4 @@
+pub(crate) mod behavior;
 pub(crate) mod botness;
 pub(crate) mod browser_user_agent;
 pub(crate) mod cdp;
@@ -6,5 +7,8 @@ pub(crate) mod geo;
 pub(crate) mod ip_identity;
 pub(crate) mod ip_range_policy;
 pub(crate) mod js_verification;
+pub(crate) mod privacy_pass;
+pub(crate) mod web_bot_auth;
 pub(crate) mod rate_pressure;
+pub(crate) mod tls_reputation;
 pub(crate) mod whitelist;
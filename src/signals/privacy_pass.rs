@@ -0,0 +1,596 @@
+//! Privacy Pass (`PrivateToken` HTTP authentication) redemption for publicly verifiable
+//! blind-RSA tokens (token type 0x0002, RFC 9577/9578).
+//!
+//! We only redeem: issuance happens between the client and an issuer we trust (Apple,
+//! Cloudflare, ...). A valid token proves the client passed that issuer's attestation
+//! without telling us who they are, so it feeds a negative-botness signal.
+
+use crate::challenge::KeyValueStore;
+use crate::config::{Config, PrivacyPassIssuer};
+use base64::{engine::general_purpose, Engine as _};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs8::der::Decode;
+use rsa::pkcs8::{DecodePublicKey, ObjectIdentifier, SubjectPublicKeyInfoRef};
+use rsa::traits::PublicKeyParts;
+use rsa::{Pss, RsaPublicKey};
+use sha2::{Digest, Sha256, Sha384};
+use spin_sdk::http::{Request, Response};
+
+pub(crate) const TOKEN_TYPE_BLIND_RSA: u16 = 0x0002;
+pub(crate) const MAX_ISSUERS: usize = 8;
+const MODULUS_BYTES: usize = 256;
+const DIGEST_BYTES: usize = 32;
+const TOKEN_BYTES: usize = 2 + DIGEST_BYTES * 3 + MODULUS_BYTES;
+const PSS_SALT_BYTES: usize = 48;
+// Challenges are bound to an hour-long window; tokens minted for the current or previous
+// window are accepted, which is also how long a redeemed nonce has to be remembered.
+const REDEMPTION_WINDOW_SECONDS: u64 = 3600;
+const REDEMPTION_CONTEXT_LABEL: &str = "shuma-privacy-pass-v1";
+const NONCE_KEY_PREFIX: &str = "privacy_pass:nonce";
+const NONCE_SWEEP_MARKER_KEY: &str = "privacy_pass:nonce_sweep:last";
+const RSASSA_PSS_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.10");
+
+pub(crate) const SIGNAL_KEY: &str = "privacy_pass_attested";
+const SIGNAL_LABEL: &str = "Privacy Pass attested client";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedemptionOutcome {
+    Absent,
+    Attested,
+    Invalid,
+    Replay,
+}
+
+impl RedemptionOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RedemptionOutcome::Absent => "absent",
+            RedemptionOutcome::Attested => "attested",
+            RedemptionOutcome::Invalid => "invalid",
+            RedemptionOutcome::Replay => "replay",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    nonce: [u8; DIGEST_BYTES],
+    challenge_digest: [u8; DIGEST_BYTES],
+    token_key_id: [u8; DIGEST_BYTES],
+    authenticator: Vec<u8>,
+}
+
+impl Token {
+    fn parse(raw: &[u8]) -> Option<Self> {
+        if raw.len() != TOKEN_BYTES {
+            return None;
+        }
+        if u16::from_be_bytes([raw[0], raw[1]]) != TOKEN_TYPE_BLIND_RSA {
+            return None;
+        }
+        let field = |index: usize| -> [u8; DIGEST_BYTES] {
+            let start = 2 + index * DIGEST_BYTES;
+            raw[start..start + DIGEST_BYTES]
+                .try_into()
+                .expect("token length already checked")
+        };
+        Some(Self {
+            nonce: field(0),
+            challenge_digest: field(1),
+            token_key_id: field(2),
+            authenticator: raw[2 + DIGEST_BYTES * 3..].to_vec(),
+        })
+    }
+
+    /// The message the issuer signed: every token field except the authenticator.
+    fn token_input(&self) -> Vec<u8> {
+        let mut input = Vec::with_capacity(2 + DIGEST_BYTES * 3);
+        input.extend_from_slice(&TOKEN_TYPE_BLIND_RSA.to_be_bytes());
+        input.extend_from_slice(&self.nonce);
+        input.extend_from_slice(&self.challenge_digest);
+        input.extend_from_slice(&self.token_key_id);
+        input
+    }
+}
+
+fn decode_base64url(raw: &str) -> Option<Vec<u8>> {
+    let trimmed = raw.trim().trim_end_matches('=');
+    general_purpose::URL_SAFE_NO_PAD.decode(trimmed).ok()
+}
+
+/// Issuer keys are published as SPKI with either the rsaEncryption or the RSASSA-PSS
+/// algorithm identifier; the PSS parameters are fixed by the token type, so we ignore them.
+fn decode_token_key(token_key: &str) -> Option<(RsaPublicKey, [u8; DIGEST_BYTES])> {
+    let der = decode_base64url(token_key)?;
+    let key = match RsaPublicKey::from_public_key_der(der.as_slice()) {
+        Ok(key) => key,
+        Err(_) => {
+            let spki = SubjectPublicKeyInfoRef::from_der(der.as_slice()).ok()?;
+            if spki.algorithm.oid != RSASSA_PSS_OID {
+                return None;
+            }
+            RsaPublicKey::from_pkcs1_der(spki.subject_public_key.as_bytes()?).ok()?
+        }
+    };
+    if key.size() != MODULUS_BYTES {
+        return None;
+    }
+    Some((key, Sha256::digest(der.as_slice()).into()))
+}
+
+pub(crate) fn is_valid_token_key(token_key: &str) -> bool {
+    decode_token_key(token_key).is_some()
+}
+
+/// Issuer names travel in a u16-length field and name a host, so keep them to host characters.
+pub(crate) fn is_valid_issuer_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 255
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == ':')
+}
+
+fn redemption_context(window: u64) -> [u8; DIGEST_BYTES] {
+    Sha256::digest(format!("{}:{}", REDEMPTION_CONTEXT_LABEL, window).as_bytes()).into()
+}
+
+fn encode_token_challenge(issuer_name: &str, context: &[u8], origin_info: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(7 + issuer_name.len() + context.len() + origin_info.len());
+    out.extend_from_slice(&TOKEN_TYPE_BLIND_RSA.to_be_bytes());
+    out.extend_from_slice(&(issuer_name.len() as u16).to_be_bytes());
+    out.extend_from_slice(issuer_name.as_bytes());
+    out.push(context.len() as u8);
+    out.extend_from_slice(context);
+    out.extend_from_slice(&(origin_info.len() as u16).to_be_bytes());
+    out.extend_from_slice(origin_info.as_bytes());
+    out
+}
+
+fn request_origin(req: &Request) -> String {
+    req.header("host")
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+fn configured_issuers(cfg: &Config) -> impl Iterator<Item = &PrivacyPassIssuer> {
+    cfg.privacy_pass_issuers
+        .iter()
+        .filter(|issuer| is_valid_issuer_name(issuer.name.as_str()))
+}
+
+/// One `PrivateToken` challenge per configured issuer, comma-separated as RFC 9110 allows.
+pub(crate) fn challenge_header_value(cfg: &Config, origin: &str, now: u64) -> Option<String> {
+    let context = redemption_context(now / REDEMPTION_WINDOW_SECONDS);
+    let challenges = configured_issuers(cfg)
+        .filter(|issuer| is_valid_token_key(issuer.token_key.as_str()))
+        .map(|issuer| {
+            let challenge = encode_token_challenge(issuer.name.as_str(), &context, origin);
+            format!(
+                "PrivateToken challenge=\"{}\", token-key=\"{}\", max-age=\"{}\"",
+                general_purpose::URL_SAFE_NO_PAD.encode(challenge),
+                issuer.token_key.trim().trim_end_matches('='),
+                REDEMPTION_WINDOW_SECONDS
+            )
+        })
+        .collect::<Vec<_>>();
+    if challenges.is_empty() {
+        None
+    } else {
+        Some(challenges.join(", "))
+    }
+}
+
+/// Turns a challenge page into a `401` carrying `WWW-Authenticate: PrivateToken`. Clients
+/// holding tokens (Safari, Cloudflare-attested browsers) retry transparently with
+/// `Authorization: PrivateToken`; everyone else still renders the page body.
+pub(crate) fn with_token_challenge(req: &Request, cfg: &Config, response: Response) -> Response {
+    if !cfg.privacy_pass_enabled {
+        return response;
+    }
+    let origin = request_origin(req);
+    let Some(header) = challenge_header_value(cfg, origin.as_str(), crate::admin::now_ts()) else {
+        return response;
+    };
+    response
+        .into_builder()
+        .status(401)
+        .header("WWW-Authenticate", header)
+        .build()
+}
+
+fn parse_authorization(value: &str) -> Option<Vec<u8>> {
+    let (scheme, params) = value.trim().split_once(char::is_whitespace)?;
+    if !scheme.eq_ignore_ascii_case("PrivateToken") {
+        return None;
+    }
+    params.split(',').find_map(|param| {
+        let (name, raw) = param.trim().split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("token") {
+            return None;
+        }
+        decode_base64url(raw.trim().trim_matches('"'))
+    })
+}
+
+fn verify_token(cfg: &Config, token: &Token, origin: &str, now: u64) -> bool {
+    let Some((issuer, key)) = configured_issuers(cfg).find_map(|issuer| {
+        let (key, key_id) = decode_token_key(issuer.token_key.as_str())?;
+        (key_id == token.token_key_id).then_some((issuer, key))
+    }) else {
+        return false;
+    };
+    let window = now / REDEMPTION_WINDOW_SECONDS;
+    let challenge_matches = [window, window.saturating_sub(1)].iter().any(|window| {
+        let challenge =
+            encode_token_challenge(issuer.name.as_str(), &redemption_context(*window), origin);
+        Sha256::digest(challenge.as_slice()).as_slice() == token.challenge_digest
+    });
+    if !challenge_matches {
+        return false;
+    }
+    let hashed = Sha384::digest(token.token_input().as_slice());
+    key.verify(
+        Pss::new_with_salt::<Sha384>(PSS_SALT_BYTES),
+        hashed.as_slice(),
+        token.authenticator.as_slice(),
+    )
+    .is_ok()
+}
+
+fn nonce_key(nonce: &[u8]) -> String {
+    let hex: String = nonce.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}:{}", NONCE_KEY_PREFIX, hex)
+}
+
+fn nonce_seen_until<S: KeyValueStore + ?Sized>(store: &S, key: &str) -> Option<u64> {
+    store
+        .get(key)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u64>().ok())
+}
+
+/// Deletes nonces whose replay window has passed. Runs at most once per window, throttled by
+/// a KV marker, so redeemed tokens do not accumulate in KV.
+fn maybe_sweep_expired_nonces<S: KeyValueStore + ?Sized>(store: &S, now: u64) {
+    let last_sweep = nonce_seen_until(store, NONCE_SWEEP_MARKER_KEY).unwrap_or(0);
+    if now.saturating_sub(last_sweep) < REDEMPTION_WINDOW_SECONDS {
+        return;
+    }
+    let _ = store.set(NONCE_SWEEP_MARKER_KEY, now.to_string().as_bytes());
+    let Ok(keys) = store.get_keys() else {
+        return;
+    };
+    let prefix = format!("{}:", NONCE_KEY_PREFIX);
+    for key in keys {
+        if !key.starts_with(prefix.as_str()) {
+            continue;
+        }
+        let expired = nonce_seen_until(store, key.as_str())
+            .map(|until| until < now)
+            .unwrap_or(true);
+        if expired {
+            let _ = store.delete(key.as_str());
+        }
+    }
+}
+
+/// Reads and redeems the request's `Authorization: PrivateToken` header. A token's nonce is
+/// remembered for as long as its challenge window is accepted, so each token counts once.
+pub(crate) fn redeem<S: KeyValueStore + ?Sized>(
+    store: &S,
+    req: &Request,
+    cfg: &Config,
+    now: u64,
+) -> RedemptionOutcome {
+    if !cfg.privacy_pass_enabled {
+        return RedemptionOutcome::Absent;
+    }
+    let Some(raw) = req
+        .header("authorization")
+        .and_then(|value| value.as_str())
+        .filter(|value| {
+            value
+                .trim_start()
+                .get(..12)
+                .is_some_and(|scheme| scheme.eq_ignore_ascii_case("PrivateToken"))
+        })
+    else {
+        return RedemptionOutcome::Absent;
+    };
+    let Some(token) = parse_authorization(raw).and_then(|bytes| Token::parse(bytes.as_slice()))
+    else {
+        return RedemptionOutcome::Invalid;
+    };
+    if !verify_token(cfg, &token, request_origin(req).as_str(), now) {
+        return RedemptionOutcome::Invalid;
+    }
+
+    let key = nonce_key(&token.nonce);
+    if nonce_seen_until(store, key.as_str()).is_some_and(|until| now <= until) {
+        return RedemptionOutcome::Replay;
+    }
+    maybe_sweep_expired_nonces(store, now);
+    let track_until = now.saturating_add(REDEMPTION_WINDOW_SECONDS * 2);
+    if let Err(err) = store.set(key.as_str(), track_until.to_string().as_bytes()) {
+        eprintln!("[privacy_pass] failed to persist nonce {}: {:?}", key, err);
+    }
+    RedemptionOutcome::Attested
+}
+
+pub(crate) fn bot_signal(
+    enabled: bool,
+    outcome: RedemptionOutcome,
+    weight: u8,
+) -> crate::signals::botness::BotSignal {
+    if !enabled {
+        return crate::signals::botness::BotSignal::disabled_with_metadata(
+            SIGNAL_KEY,
+            SIGNAL_LABEL,
+            crate::signals::botness::SignalProvenance::ExternalTrusted,
+            10,
+            crate::signals::botness::SignalFamily::Attestation,
+        );
+    }
+    crate::signals::botness::BotSignal::credited_with_metadata(
+        SIGNAL_KEY,
+        SIGNAL_LABEL,
+        outcome == RedemptionOutcome::Attested,
+        weight,
+        crate::signals::botness::SignalProvenance::ExternalTrusted,
+        10,
+        crate::signals::botness::SignalFamily::Attestation,
+    )
+}
+
+pub(crate) fn is_attested(assessment: &crate::BotnessAssessment) -> bool {
+    assessment
+        .contributions
+        .iter()
+        .any(|signal| signal.key == SIGNAL_KEY && signal.active)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{request_with_headers, InMemoryStore};
+
+    // Minted offline with the throwaway key behind `PRIVACY_PASS_TOKEN_KEY`: the token
+    // answers the challenge for `issuer.example` on origin `example.com` in the window
+    // containing `FIXTURE_NOW`.
+    const FIXTURE_NOW: u64 = 1_800_000_000;
+    const FIXTURE_ISSUER: &str = "issuer.example";
+    const FIXTURE_TOKEN_KEY: &str = crate::test_support::PRIVACY_PASS_TOKEN_KEY;
+    const FIXTURE_TOKEN: &str = concat!(
+        "AAKoq9Vrfeas37ZfJjGaY6X_tSstF3RW8owK-cfZMr6R9LtDYDh5i9fBNz-57lMMz_3vsVRfNq85vf16duy2_YUW",
+        "A5Uvh0zkOY028zGO7z4Vz47MzEElHqUNsuxN9JIncvaS5V4H3d-M9QWlNBk67P6eGp_jW6Mnr6VtR5nCpWPYaXBl",
+        "39bUIVAVig8NHPqWiexWvuB-eJ6atvXBwznvRh2LeW7ZNu_nWjccXeaHTSENbLpUkEw_Gjp8HQM1HnWw7OSlTWcC",
+        "kgdhewxCdRgpw9W7rjmQJQdplQe5rG8U_U9Hkdg3IXxsfJ7Yzer6lGVc0oOMTYhHJkW88-E188MToF_vSZRJZ1i8",
+        "Ti8DEae-r8OUT_9VkPswLz_kLh2L9AoQCf72jrp4dU2pXzG2zQCYBxrhOL6LsLcQtJEOlYjz4A7D-BM951ncpDrL",
+        "fJEhW4XCWFo6AqWrFFcvWMFPFQxiXT-3",
+    );
+
+    fn fixture_config() -> Config {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.privacy_pass_enabled = true;
+        cfg.privacy_pass_issuers = vec![PrivacyPassIssuer {
+            name: FIXTURE_ISSUER.to_string(),
+            token_key: FIXTURE_TOKEN_KEY.to_string(),
+        }];
+        cfg
+    }
+
+    fn token_request(host: &str, token: &str) -> Request {
+        let authorization = format!("PrivateToken token=\"{}\"", token);
+        request_with_headers(
+            "/",
+            &[("host", host), ("authorization", authorization.as_str())],
+        )
+    }
+
+    #[test]
+    fn fixture_token_key_decodes_as_pss_spki() {
+        assert!(is_valid_token_key(FIXTURE_TOKEN_KEY));
+        assert!(!is_valid_token_key("not-a-key"));
+        let token = decode_base64url(FIXTURE_TOKEN)
+            .and_then(|raw| Token::parse(raw.as_slice()))
+            .expect("fixture token parses");
+        let (_, key_id) = decode_token_key(FIXTURE_TOKEN_KEY).unwrap();
+        assert_eq!(token.token_key_id, key_id);
+    }
+
+    #[test]
+    fn valid_token_is_attested_once_then_replayed() {
+        let cfg = fixture_config();
+        let store = InMemoryStore::default();
+        let req = token_request("example.com", FIXTURE_TOKEN);
+        assert_eq!(
+            redeem(&store, &req, &cfg, FIXTURE_NOW),
+            RedemptionOutcome::Attested
+        );
+        assert_eq!(
+            redeem(&store, &req, &cfg, FIXTURE_NOW + 5),
+            RedemptionOutcome::Replay
+        );
+    }
+
+    #[test]
+    fn expired_nonces_are_swept_once_per_window() {
+        let cfg = fixture_config();
+        let store = InMemoryStore::default();
+        let stale = format!("{}:{}", NONCE_KEY_PREFIX, "00".repeat(DIGEST_BYTES));
+        let live = format!("{}:{}", NONCE_KEY_PREFIX, "11".repeat(DIGEST_BYTES));
+        store
+            .set(stale.as_str(), (FIXTURE_NOW - 1).to_string().as_bytes())
+            .unwrap();
+        store
+            .set(live.as_str(), (FIXTURE_NOW + 60).to_string().as_bytes())
+            .unwrap();
+
+        let req = token_request("example.com", FIXTURE_TOKEN);
+        assert_eq!(
+            redeem(&store, &req, &cfg, FIXTURE_NOW),
+            RedemptionOutcome::Attested
+        );
+        assert!(store.get(stale.as_str()).unwrap().is_none());
+        assert!(store.get(live.as_str()).unwrap().is_some());
+        let redeemed = nonce_key(
+            &Token::parse(decode_base64url(FIXTURE_TOKEN).unwrap().as_slice())
+                .unwrap()
+                .nonce,
+        );
+        assert!(store.get(redeemed.as_str()).unwrap().is_some());
+
+        // Within the same window the sweep is skipped; after it, the lapsed nonce goes.
+        maybe_sweep_expired_nonces(&store, FIXTURE_NOW + 120);
+        assert!(store.get(live.as_str()).unwrap().is_some());
+        maybe_sweep_expired_nonces(&store, FIXTURE_NOW + REDEMPTION_WINDOW_SECONDS);
+        assert!(store.get(live.as_str()).unwrap().is_none());
+        assert!(store.get(redeemed.as_str()).unwrap().is_some());
+    }
+
+    #[test]
+    fn token_is_accepted_in_the_following_window_only() {
+        let cfg = fixture_config();
+        let req = token_request("example.com", FIXTURE_TOKEN);
+        assert_eq!(
+            redeem(
+                &InMemoryStore::default(),
+                &req,
+                &cfg,
+                FIXTURE_NOW + REDEMPTION_WINDOW_SECONDS
+            ),
+            RedemptionOutcome::Attested
+        );
+        assert_eq!(
+            redeem(
+                &InMemoryStore::default(),
+                &req,
+                &cfg,
+                FIXTURE_NOW + REDEMPTION_WINDOW_SECONDS * 2
+            ),
+            RedemptionOutcome::Invalid
+        );
+    }
+
+    #[test]
+    fn token_for_another_origin_or_issuer_is_invalid() {
+        let store = InMemoryStore::default();
+        let cfg = fixture_config();
+        let req = token_request("other.example", FIXTURE_TOKEN);
+        assert_eq!(
+            redeem(&store, &req, &cfg, FIXTURE_NOW),
+            RedemptionOutcome::Invalid
+        );
+
+        let mut renamed = fixture_config();
+        renamed.privacy_pass_issuers[0].name = "other-issuer.example".to_string();
+        let req = token_request("example.com", FIXTURE_TOKEN);
+        assert_eq!(
+            redeem(&store, &req, &renamed, FIXTURE_NOW),
+            RedemptionOutcome::Invalid
+        );
+    }
+
+    #[test]
+    fn tampered_authenticator_is_invalid() {
+        let mut raw = decode_base64url(FIXTURE_TOKEN).unwrap();
+        let last = raw.len() - 1;
+        raw[last] ^= 0x01;
+        let tampered = general_purpose::URL_SAFE_NO_PAD.encode(raw);
+        let req = token_request("example.com", tampered.as_str());
+        assert_eq!(
+            redeem(
+                &InMemoryStore::default(),
+                &req,
+                &fixture_config(),
+                FIXTURE_NOW
+            ),
+            RedemptionOutcome::Invalid
+        );
+    }
+
+    #[test]
+    fn missing_or_foreign_authorization_is_absent() {
+        let cfg = fixture_config();
+        let store = InMemoryStore::default();
+        let bare = request_with_headers("/", &[("host", "example.com")]);
+        assert_eq!(
+            redeem(&store, &bare, &cfg, FIXTURE_NOW),
+            RedemptionOutcome::Absent
+        );
+        let basic = request_with_headers("/", &[("authorization", "Basic dXNlcjpwdw==")]);
+        assert_eq!(
+            redeem(&store, &basic, &cfg, FIXTURE_NOW),
+            RedemptionOutcome::Absent
+        );
+
+        let mut disabled = fixture_config();
+        disabled.privacy_pass_enabled = false;
+        let req = token_request("example.com", FIXTURE_TOKEN);
+        assert_eq!(
+            redeem(&store, &req, &disabled, FIXTURE_NOW),
+            RedemptionOutcome::Absent
+        );
+    }
+
+    #[test]
+    fn challenge_header_encodes_token_challenge_for_origin() {
+        let cfg = fixture_config();
+        let header = challenge_header_value(&cfg, "example.com", FIXTURE_NOW).unwrap();
+        assert!(header.starts_with("PrivateToken challenge=\""));
+        assert!(header.contains(format!("token-key=\"{}\"", FIXTURE_TOKEN_KEY).as_str()));
+
+        let encoded = header
+            .split("challenge=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+        let challenge = decode_base64url(encoded).unwrap();
+        let expected = encode_token_challenge(
+            FIXTURE_ISSUER,
+            &redemption_context(FIXTURE_NOW / REDEMPTION_WINDOW_SECONDS),
+            "example.com",
+        );
+        assert_eq!(challenge, expected);
+        assert_eq!(&challenge[..2], &[0x00, 0x02]);
+        assert_eq!(challenge[2..4], (FIXTURE_ISSUER.len() as u16).to_be_bytes());
+
+        let mut no_issuers = fixture_config();
+        no_issuers.privacy_pass_issuers.clear();
+        assert!(challenge_header_value(&no_issuers, "example.com", FIXTURE_NOW).is_none());
+    }
+
+    #[test]
+    fn challenge_response_becomes_401_only_when_enabled() {
+        let req = request_with_headers("/", &[("host", "example.com")]);
+        let challenged = with_token_challenge(&req, &fixture_config(), Response::new(200, "page"));
+        assert_eq!(*challenged.status(), 401u16);
+        assert!(crate::test_support::has_header(
+            &challenged,
+            "www-authenticate"
+        ));
+        assert_eq!(challenged.body(), b"page");
+
+        let mut disabled = fixture_config();
+        disabled.privacy_pass_enabled = false;
+        let untouched = with_token_challenge(&req, &disabled, Response::new(200, "page"));
+        assert_eq!(*untouched.status(), 200u16);
+    }
+
+    #[test]
+    fn attested_signal_is_a_credit() {
+        let signal = bot_signal(true, RedemptionOutcome::Attested, 4);
+        assert!(signal.active);
+        assert_eq!(signal.contribution, 0);
+        assert_eq!(signal.credit, 4);
+        assert!(!bot_signal(true, RedemptionOutcome::Replay, 4).active);
+        assert_eq!(
+            bot_signal(false, RedemptionOutcome::Attested, 4).availability,
+            crate::signals::botness::SignalAvailability::Disabled
+        );
+    }
+}
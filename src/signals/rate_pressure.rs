@@ -1,13 +1,12 @@
 use crate::challenge::KeyValueStore;
 use crate::signals::ip_identity;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 const RATE_MEDIUM_KEY: &str = "rate_pressure_medium";
 const RATE_MEDIUM_LABEL: &str = "Rate pressure (>=50%)";
 const RATE_HIGH_KEY: &str = "rate_pressure_high";
 const RATE_HIGH_LABEL: &str = "Rate pressure (>=80%)";
 
-pub fn current_rate_usage<S: KeyValueStore>(store: &S, site_id: &str, ip: &str) -> u32 {
+pub fn current_rate_usage<S: KeyValueStore + ?Sized>(store: &S, site_id: &str, ip: &str) -> u32 {
     let key = current_window_key(site_id, ip, now_ts() / 60);
     store
         .get(&key)
@@ -112,10 +111,7 @@ fn current_window_key(site_id: &str, ip: &str, window: u64) -> String {
 }
 
 fn now_ts() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
+    crate::runtime::clock::now_ts()
 }
 
 #[cfg(test)]
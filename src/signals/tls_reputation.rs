@@ -0,0 +1,271 @@
+use crate::config::{Config, TlsFingerprintAction, TlsFingerprintManagedPolicy, TlsFingerprintRule};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+const MANAGED_TLS_FINGERPRINTS_TEXT: &str =
+    include_str!("../../config/managed_tls_fingerprints.json");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MatchSource {
+    CustomRule,
+    ManagedSet,
+}
+
+impl MatchSource {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            MatchSource::CustomRule => "custom",
+            MatchSource::ManagedSet => "managed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FingerprintKind {
+    Ja3,
+    Ja4,
+}
+
+impl FingerprintKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            FingerprintKind::Ja3 => "ja3",
+            FingerprintKind::Ja4 => "ja4",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MatchDetails {
+    pub source: MatchSource,
+    pub source_id: String,
+    pub action: TlsFingerprintAction,
+    pub kind: FingerprintKind,
+    pub fingerprint: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub(crate) struct ManagedSetMetadata {
+    pub id: String,
+    pub label: String,
+    pub category: String,
+    pub source: String,
+    pub ja3_count: usize,
+    pub ja4_count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ManagedCatalog {
+    catalog_version: String,
+    generated_at: String,
+    sets: Vec<ManagedSet>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ManagedSet {
+    id: String,
+    label: String,
+    category: String,
+    source: String,
+    #[serde(default)]
+    ja3: Vec<String>,
+    #[serde(default)]
+    ja4: Vec<String>,
+}
+
+static MANAGED_CATALOG: Lazy<ManagedCatalog> = Lazy::new(|| {
+    serde_json::from_str::<ManagedCatalog>(MANAGED_TLS_FINGERPRINTS_TEXT)
+        .unwrap_or_else(|err| panic!("Invalid managed TLS fingerprint catalog: {}", err))
+});
+
+pub(crate) fn managed_catalog_version() -> String {
+    MANAGED_CATALOG.catalog_version.clone()
+}
+
+pub(crate) fn managed_catalog_generated_at() -> String {
+    MANAGED_CATALOG.generated_at.clone()
+}
+
+pub(crate) fn managed_set_metadata() -> Vec<ManagedSetMetadata> {
+    MANAGED_CATALOG
+        .sets
+        .iter()
+        .map(|set| ManagedSetMetadata {
+            id: set.id.clone(),
+            label: set.label.clone(),
+            category: set.category.clone(),
+            source: set.source.clone(),
+            ja3_count: set.ja3.len(),
+            ja4_count: set.ja4.len(),
+        })
+        .collect()
+}
+
+pub(crate) fn has_managed_set(set_id: &str) -> bool {
+    let normalized = set_id.trim().to_ascii_lowercase();
+    MANAGED_CATALOG
+        .sets
+        .iter()
+        .any(|set| set.id.eq_ignore_ascii_case(normalized.as_str()))
+}
+
+/// Accepts the same token shape the fingerprint collector keeps from trusted edge headers.
+pub(crate) fn is_valid_fingerprint(raw: &str) -> bool {
+    let trimmed = raw.trim();
+    !trimmed.is_empty()
+        && trimmed.len() <= 256
+        && trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':')
+}
+
+fn list_contains(list: &[String], fingerprint: &str) -> bool {
+    list.iter()
+        .any(|entry| entry.trim().eq_ignore_ascii_case(fingerprint))
+}
+
+fn match_lists(
+    ja3_list: &[String],
+    ja4_list: &[String],
+    ja3: Option<&str>,
+    ja4: Option<&str>,
+) -> Option<(FingerprintKind, String)> {
+    if let Some(ja4) = ja4.filter(|value| list_contains(ja4_list, value)) {
+        return Some((FingerprintKind::Ja4, ja4.to_string()));
+    }
+    if let Some(ja3) = ja3.filter(|value| list_contains(ja3_list, value)) {
+        return Some((FingerprintKind::Ja3, ja3.to_string()));
+    }
+    None
+}
+
+fn match_custom_rule(
+    rule: &TlsFingerprintRule,
+    ja3: Option<&str>,
+    ja4: Option<&str>,
+) -> Option<MatchDetails> {
+    if !rule.enabled {
+        return None;
+    }
+    let (kind, fingerprint) = match_lists(&rule.ja3, &rule.ja4, ja3, ja4)?;
+    Some(MatchDetails {
+        source: MatchSource::CustomRule,
+        source_id: rule.id.clone(),
+        action: rule.action,
+        kind,
+        fingerprint,
+    })
+}
+
+fn match_managed_policy(
+    policy: &TlsFingerprintManagedPolicy,
+    ja3: Option<&str>,
+    ja4: Option<&str>,
+) -> Option<MatchDetails> {
+    if !policy.enabled {
+        return None;
+    }
+    let set = MANAGED_CATALOG
+        .sets
+        .iter()
+        .find(|set| set.id.eq_ignore_ascii_case(policy.set_id.trim()))?;
+    let (kind, fingerprint) = match_lists(&set.ja3, &set.ja4, ja3, ja4)?;
+    Some(MatchDetails {
+        source: MatchSource::ManagedSet,
+        source_id: set.id.clone(),
+        action: policy.action,
+        kind,
+        fingerprint,
+    })
+}
+
+/// Resolves trusted JA3/JA4 values against custom rules (first match) and then managed sets.
+///
+/// JA4 is preferred over JA3 within a rule because it is stable across TLS extension shuffling.
+pub(crate) fn evaluate(cfg: &Config, ja3: Option<&str>, ja4: Option<&str>) -> Option<MatchDetails> {
+    if ja3.is_none() && ja4.is_none() {
+        return None;
+    }
+    cfg.fingerprint_tls_custom_rules
+        .iter()
+        .find_map(|rule| match_custom_rule(rule, ja3, ja4))
+        .or_else(|| {
+            cfg.fingerprint_tls_managed_policies
+                .iter()
+                .find_map(|policy| match_managed_policy(policy, ja3, ja4))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GO_JA4: &str = "t13d190900_9dc949149365_97f8aa674fd9";
+    const PYTHON_JA3: &str = "b32309a26951912be7dba376398abc3b";
+
+    fn managed(set_id: &str, action: TlsFingerprintAction) -> TlsFingerprintManagedPolicy {
+        TlsFingerprintManagedPolicy {
+            set_id: set_id.to_string(),
+            enabled: true,
+            action,
+        }
+    }
+
+    #[test]
+    fn managed_catalog_parses_and_lists_expected_sets() {
+        assert!(!managed_catalog_version().is_empty());
+        assert!(has_managed_set("python_requests"));
+        assert!(has_managed_set("GO_NET_HTTP"));
+        assert!(!has_managed_set("unknown_set"));
+        for set in managed_set_metadata() {
+            assert!(set.ja3_count + set.ja4_count > 0, "{} has no fingerprints", set.id);
+        }
+    }
+
+    #[test]
+    fn no_policies_never_match() {
+        let cfg = crate::config::defaults().clone();
+        assert_eq!(evaluate(&cfg, Some(PYTHON_JA3), Some(GO_JA4)), None);
+    }
+
+    #[test]
+    fn managed_set_matches_only_when_enabled() {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.fingerprint_tls_managed_policies = vec![managed("go_net_http", TlsFingerprintAction::Block)];
+        let matched = evaluate(&cfg, None, Some(GO_JA4)).expect("go fingerprint should match");
+        assert_eq!(matched.source, MatchSource::ManagedSet);
+        assert_eq!(matched.source_id, "go_net_http");
+        assert_eq!(matched.kind, FingerprintKind::Ja4);
+        assert_eq!(matched.action, TlsFingerprintAction::Block);
+
+        cfg.fingerprint_tls_managed_policies[0].enabled = false;
+        assert_eq!(evaluate(&cfg, None, Some(GO_JA4)), None);
+    }
+
+    #[test]
+    fn custom_rules_take_precedence_over_managed_sets() {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.fingerprint_tls_managed_policies =
+            vec![managed("python_requests", TlsFingerprintAction::Block)];
+        cfg.fingerprint_tls_custom_rules = vec![TlsFingerprintRule {
+            id: "internal_monitor".to_string(),
+            enabled: true,
+            ja3: vec![PYTHON_JA3.to_ascii_uppercase()],
+            ja4: vec![],
+            action: TlsFingerprintAction::Allow,
+        }];
+        let matched = evaluate(&cfg, Some(PYTHON_JA3), None).expect("custom rule should match");
+        assert_eq!(matched.source, MatchSource::CustomRule);
+        assert_eq!(matched.source_id, "internal_monitor");
+        assert_eq!(matched.action, TlsFingerprintAction::Allow);
+    }
+
+    #[test]
+    fn fingerprint_shape_validation_matches_collector_tokens() {
+        assert!(is_valid_fingerprint(GO_JA4));
+        assert!(is_valid_fingerprint(PYTHON_JA3));
+        assert!(!is_valid_fingerprint(""));
+        assert!(!is_valid_fingerprint("ja3 with spaces"));
+        assert!(!is_valid_fingerprint(&"a".repeat(257)));
+    }
+}
@@ -0,0 +1,920 @@
+//! Web Bot Auth: RFC 9421 HTTP Message Signatures from self-identifying agents.
+//!
+//! Agents sign each request with an Ed25519 key, name their key directory in
+//! `Signature-Agent`, and tag the signature `web-bot-auth`. We only trust directories that
+//! are configured as agents; keys are either pinned in config or fetched from the
+//! directory's `/.well-known/http-message-signatures-directory` and cached in KV.
+
+use crate::challenge::KeyValueStore;
+use crate::config::{Config, WebBotAuthAgent};
+use base64::{engine::general_purpose, Engine as _};
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use spin_sdk::http::{Method, Request, Response};
+
+pub(crate) const MAX_AGENTS: usize = 16;
+pub(crate) const MAX_KEYS_PER_AGENT: usize = 8;
+const SIGNATURE_TAG: &str = "web-bot-auth";
+const DIRECTORY_PATH: &str = "/.well-known/http-message-signatures-directory";
+const DIRECTORY_KEY_PREFIX: &str = "web_bot_auth:directory:";
+const NONCE_KEY_PREFIX: &str = "web_bot_auth:nonce:";
+const RATE_KEY_PREFIX: &str = "web_bot_auth:rate:";
+const SWEEP_MARKER_KEY: &str = "web_bot_auth:sweep:last";
+const SWEEP_INTERVAL_SECONDS: u64 = 3600;
+const DIRECTORY_CACHE_SECONDS: u64 = 3600;
+// Failed fetches are retried sooner so a directory outage does not outlast itself by an hour.
+const DIRECTORY_RETRY_SECONDS: u64 = 300;
+const MAX_CLOCK_SKEW_SECONDS: u64 = 60;
+// Signatures without a nonce can be replayed until they expire, so keep that window short.
+const MAX_VALIDITY_SECONDS: u64 = 300;
+const MAX_COVERED_COMPONENTS: usize = 16;
+const MAX_NONCE_CHARS: usize = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationFailure {
+    Malformed,
+    Expired,
+    UnknownAgent,
+    UnknownKey,
+    DirectoryUnavailable,
+    InvalidSignature,
+    Replay,
+}
+
+impl VerificationFailure {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VerificationFailure::Malformed => "malformed",
+            VerificationFailure::Expired => "expired",
+            VerificationFailure::UnknownAgent => "unknown_agent",
+            VerificationFailure::UnknownKey => "unknown_key",
+            VerificationFailure::DirectoryUnavailable => "directory_unavailable",
+            VerificationFailure::InvalidSignature => "invalid_signature",
+            VerificationFailure::Replay => "replay",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VerifiedAgent {
+    pub agent: WebBotAuthAgent,
+    pub key_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum VerificationOutcome {
+    /// No `web-bot-auth` signature on the request.
+    Absent,
+    Verified(VerifiedAgent),
+    Failed(VerificationFailure),
+}
+
+impl VerificationOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationOutcome::Absent => "absent",
+            VerificationOutcome::Verified(_) => "verified",
+            VerificationOutcome::Failed(failure) => failure.as_str(),
+        }
+    }
+}
+
+/// Key directory fetch seam so verification can be exercised without outbound HTTP.
+pub(crate) trait KeyDirectoryTransport {
+    /// GET `url` and return the response status and body.
+    fn get(&self, url: &str) -> Result<(u16, Vec<u8>), String>;
+}
+
+/// Outbound transport via Spin HTTP. Directory origins must be listed in
+/// `allowed_outbound_hosts` for the `bot-defence` component.
+pub(crate) struct SpinKeyDirectoryTransport;
+
+impl KeyDirectoryTransport for SpinKeyDirectoryTransport {
+    fn get(&self, url: &str) -> Result<(u16, Vec<u8>), String> {
+        let request = Request::builder()
+            .method(Method::Get)
+            .uri(url)
+            .header(
+                "accept",
+                "application/http-message-signatures-directory+json",
+            )
+            .build();
+        spin_sdk::http::run(spin_sdk::http::send::<_, Response>(request))
+            .map(|response| (*response.status(), response.body().to_vec()))
+            .map_err(|err| err.to_string())
+    }
+}
+
+/// Verification without outbound HTTP; only pinned keys can verify.
+pub(crate) struct OfflineKeyDirectoryTransport;
+
+impl KeyDirectoryTransport for OfflineKeyDirectoryTransport {
+    fn get(&self, _url: &str) -> Result<(u16, Vec<u8>), String> {
+        Err("key directory fetches are disabled".to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParamValue {
+    Integer(u64),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SignatureInput {
+    components: Vec<String>,
+    params: Vec<(String, ParamValue)>,
+    /// Serialized member value exactly as received; it is the `@signature-params` line.
+    raw: String,
+}
+
+impl SignatureInput {
+    fn param(&self, name: &str) -> Option<&ParamValue> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    fn integer(&self, name: &str) -> Option<u64> {
+        match self.param(name) {
+            Some(ParamValue::Integer(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn text(&self, name: &str) -> Option<&str> {
+        match self.param(name) {
+            Some(ParamValue::Text(value)) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Splits an RFC 8941 dictionary into `(key, raw value)` members, honouring strings,
+/// inner lists and byte sequences.
+fn split_dictionary(input: &str) -> Vec<(String, String)> {
+    let mut members = Vec::new();
+    let mut current = String::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut depth = 0usize;
+    for c in input.chars() {
+        if in_string {
+            current.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                members.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    members.push(current);
+    members
+        .into_iter()
+        .filter_map(|member| {
+            let (key, value) = member.split_once('=')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Reads an sf-string starting at `input[0] == '"'`; returns the value and the rest.
+fn take_string(input: &str) -> Option<(String, &str)> {
+    let mut chars = input.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    let mut value = String::new();
+    let mut escaped = false;
+    for (index, c) in chars {
+        if escaped {
+            value.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            return Some((value, &input[index + 1..]));
+        } else {
+            value.push(c);
+        }
+    }
+    None
+}
+
+fn parse_params(mut rest: &str) -> Option<Vec<(String, ParamValue)>> {
+    let mut params = Vec::new();
+    while let Some(after) = rest.trim_start().strip_prefix(';') {
+        let after = after.trim_start();
+        let name_len = after
+            .find(|c: char| !(c.is_ascii_lowercase() || c.is_ascii_digit() || "_-.*".contains(c)))
+            .unwrap_or(after.len());
+        let name = after[..name_len].to_string();
+        let after = after[name_len..].strip_prefix('=')?;
+        let (value, remaining) = if after.starts_with('"') {
+            let (value, remaining) = take_string(after)?;
+            (ParamValue::Text(value), remaining)
+        } else {
+            let end = after.find(';').unwrap_or(after.len());
+            let raw = after[..end].trim();
+            let value = match raw.parse::<u64>() {
+                Ok(number) => ParamValue::Integer(number),
+                Err(_) => ParamValue::Text(raw.to_string()),
+            };
+            (value, &after[end..])
+        };
+        params.push((name, value));
+        rest = remaining;
+    }
+    rest.trim().is_empty().then_some(params)
+}
+
+fn parse_signature_input(raw: &str) -> Option<SignatureInput> {
+    let mut rest = raw.strip_prefix('(')?;
+    let mut components = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix(')') {
+            rest = after;
+            break;
+        }
+        let (component, after) = take_string(rest)?;
+        // Component parameters (`;sf`, `;key`, `;req`) change the canonical value; none of
+        // the agents we verify use them.
+        if after.starts_with(';') {
+            return None;
+        }
+        components.push(component.to_ascii_lowercase());
+        if components.len() > MAX_COVERED_COMPONENTS {
+            return None;
+        }
+        rest = after;
+    }
+    Some(SignatureInput {
+        components,
+        params: parse_params(rest)?,
+        raw: raw.to_string(),
+    })
+}
+
+fn parse_byte_sequence(raw: &str) -> Option<Vec<u8>> {
+    let inner = raw.strip_prefix(':')?.strip_suffix(':')?;
+    general_purpose::STANDARD.decode(inner).ok()
+}
+
+fn decode_base64url(raw: &str) -> Option<Vec<u8>> {
+    general_purpose::URL_SAFE_NO_PAD
+        .decode(raw.trim().trim_end_matches('='))
+        .ok()
+}
+
+pub(crate) fn decode_public_key(raw: &str) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = decode_base64url(raw)?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+pub(crate) fn is_valid_public_key(raw: &str) -> bool {
+    decode_public_key(raw).is_some()
+}
+
+/// RFC 7638 JWK thumbprint of an Ed25519 key, which agents send as `keyid`.
+pub(crate) fn key_thumbprint(x: &str) -> String {
+    let canonical = format!(r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#, x.trim());
+    general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()))
+}
+
+/// Lowercased origin with any trailing slash removed, as configured and as sent.
+pub(crate) fn normalize_directory(raw: &str) -> String {
+    raw.trim()
+        .trim_matches('"')
+        .trim_end_matches('/')
+        .to_ascii_lowercase()
+}
+
+pub(crate) fn is_valid_directory(raw: &str) -> bool {
+    let normalized = normalize_directory(raw);
+    normalized
+        .strip_prefix("https://")
+        .is_some_and(|host| !host.is_empty() && !host.contains(['/', '?', '#', '@', ' ']))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CachedDirectory {
+    fetched_at: u64,
+    keys: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    #[serde(default)]
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    #[serde(default)]
+    kty: String,
+    #[serde(default)]
+    crv: String,
+    #[serde(default)]
+    x: String,
+}
+
+fn parse_directory(body: &[u8]) -> Option<Vec<String>> {
+    let jwks = serde_json::from_slice::<Jwks>(body).ok()?;
+    Some(
+        jwks.keys
+            .into_iter()
+            .filter(|jwk| jwk.kty == "OKP" && jwk.crv == "Ed25519" && is_valid_public_key(&jwk.x))
+            .map(|jwk| jwk.x)
+            .take(MAX_KEYS_PER_AGENT)
+            .collect(),
+    )
+}
+
+fn directory_keys<S: KeyValueStore + ?Sized, T: KeyDirectoryTransport + ?Sized>(
+    store: &S,
+    transport: &T,
+    agent: &WebBotAuthAgent,
+    now: u64,
+) -> Option<Vec<String>> {
+    if !agent.keys.is_empty() {
+        return Some(agent.keys.clone());
+    }
+    let cache_key = format!("{}{}", DIRECTORY_KEY_PREFIX, agent.name);
+    let cached = store
+        .get(cache_key.as_str())
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_slice::<CachedDirectory>(&raw).ok());
+    if let Some(cached) = cached {
+        let max_age = if cached.keys.is_empty() {
+            DIRECTORY_RETRY_SECONDS
+        } else {
+            DIRECTORY_CACHE_SECONDS
+        };
+        if now.saturating_sub(cached.fetched_at) < max_age {
+            return (!cached.keys.is_empty()).then_some(cached.keys);
+        }
+    }
+
+    let url = format!(
+        "{}{}",
+        normalize_directory(&agent.directory),
+        DIRECTORY_PATH
+    );
+    let keys = match transport.get(url.as_str()) {
+        Ok((200, body)) => parse_directory(&body).unwrap_or_default(),
+        Ok(_) | Err(_) => Vec::new(),
+    };
+    let entry = CachedDirectory {
+        fetched_at: now,
+        keys,
+    };
+    if let Ok(raw) = serde_json::to_vec(&entry) {
+        let _ = store.set(cache_key.as_str(), &raw);
+    }
+    (!entry.keys.is_empty()).then_some(entry.keys)
+}
+
+fn request_authority(req: &Request) -> Option<String> {
+    let host = req.header("host")?.as_str()?.trim().to_ascii_lowercase();
+    let host = host
+        .strip_suffix(":443")
+        .map(str::to_string)
+        .unwrap_or(host);
+    (!host.is_empty()).then_some(host)
+}
+
+fn component_value(req: &Request, component: &str) -> Option<String> {
+    let path = if req.path().is_empty() {
+        "/"
+    } else {
+        req.path()
+    };
+    let query = req.query();
+    match component {
+        "@method" => Some(req.method().to_string().to_ascii_uppercase()),
+        "@authority" => request_authority(req),
+        "@scheme" => Some("https".to_string()),
+        "@path" => Some(path.to_string()),
+        "@query" => Some(format!("?{}", query)),
+        "@target-uri" => Some(format!(
+            "https://{}{}{}",
+            request_authority(req)?,
+            path,
+            if query.is_empty() {
+                String::new()
+            } else {
+                format!("?{}", query)
+            }
+        )),
+        derived if derived.starts_with('@') => None,
+        field => req
+            .header(field)
+            .and_then(|value| value.as_str())
+            .map(|value| value.trim().to_string()),
+    }
+}
+
+/// RFC 9421 §2.5 signature base.
+fn signature_base(req: &Request, input: &SignatureInput) -> Option<String> {
+    let mut lines = Vec::with_capacity(input.components.len() + 1);
+    for component in &input.components {
+        lines.push(format!(
+            "\"{}\": {}",
+            component,
+            component_value(req, component)?
+        ));
+    }
+    lines.push(format!("\"@signature-params\": {}", input.raw));
+    Some(lines.join("\n"))
+}
+
+fn find_agent<'a>(cfg: &'a Config, signature_agent: Option<&str>) -> Option<&'a WebBotAuthAgent> {
+    let wanted = normalize_directory(signature_agent?);
+    cfg.web_bot_auth_agents
+        .iter()
+        .find(|agent| normalize_directory(&agent.directory) == wanted)
+}
+
+/// Remembers a nonce until its signature expires; false if it was already seen.
+fn claim_nonce<S: KeyValueStore + ?Sized>(store: &S, nonce: &str, expires: u64, now: u64) -> bool {
+    let digest = Sha256::digest(nonce.as_bytes());
+    let key = format!(
+        "{}{}",
+        NONCE_KEY_PREFIX,
+        general_purpose::URL_SAFE_NO_PAD.encode(&digest[..16])
+    );
+    let seen = stored_u64(store, key.as_str())
+        .is_some_and(|until| until.saturating_add(MAX_CLOCK_SKEW_SECONDS) >= now);
+    if seen {
+        return false;
+    }
+    let _ = store.set(key.as_str(), expires.to_string().as_bytes());
+    true
+}
+
+fn stored_u64<S: KeyValueStore + ?Sized>(store: &S, key: &str) -> Option<u64> {
+    store
+        .get(key)
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u64>().ok())
+}
+
+/// Deletes nonces whose signatures have lapsed and rate windows that have closed. Runs at
+/// most once per `SWEEP_INTERVAL_SECONDS`, so only one request per interval pays for the scan.
+fn maybe_sweep_expired<S: KeyValueStore + ?Sized>(store: &S, now: u64) {
+    let last_sweep = stored_u64(store, SWEEP_MARKER_KEY).unwrap_or(0);
+    if now.saturating_sub(last_sweep) < SWEEP_INTERVAL_SECONDS {
+        return;
+    }
+    let _ = store.set(SWEEP_MARKER_KEY, now.to_string().as_bytes());
+    let Ok(keys) = store.get_keys() else {
+        return;
+    };
+    for key in keys {
+        let expired = if key.starts_with(NONCE_KEY_PREFIX) {
+            stored_u64(store, key.as_str())
+                .map(|until| until.saturating_add(MAX_CLOCK_SKEW_SECONDS) < now)
+                .unwrap_or(true)
+        } else if let Some(rest) = key.strip_prefix(RATE_KEY_PREFIX) {
+            rest.rsplit(':')
+                .next()
+                .and_then(|minute| minute.parse::<u64>().ok())
+                .map(|minute| minute < now / 60)
+                .unwrap_or(true)
+        } else {
+            continue;
+        };
+        if expired {
+            let _ = store.delete(key.as_str());
+        }
+    }
+}
+
+pub(crate) fn verify<S: KeyValueStore + ?Sized, T: KeyDirectoryTransport + ?Sized>(
+    store: &S,
+    req: &Request,
+    cfg: &Config,
+    transport: &T,
+    now: u64,
+) -> VerificationOutcome {
+    if !cfg.web_bot_auth_enabled {
+        return VerificationOutcome::Absent;
+    }
+    let header = |name: &str| req.header(name).and_then(|value| value.as_str());
+    let (Some(signature_input), Some(signature)) = (header("signature-input"), header("signature"))
+    else {
+        return VerificationOutcome::Absent;
+    };
+
+    let inputs = split_dictionary(signature_input);
+    let tagged = inputs.iter().find_map(|(label, raw)| {
+        let input = parse_signature_input(raw)?;
+        (input.text("tag") == Some(SIGNATURE_TAG)).then(|| (label.clone(), input))
+    });
+    let Some((label, input)) = tagged else {
+        return VerificationOutcome::Absent;
+    };
+    let failed = VerificationOutcome::Failed;
+
+    let Some(signature_bytes) = split_dictionary(signature)
+        .into_iter()
+        .find(|(member, _)| *member == label)
+        .and_then(|(_, raw)| parse_byte_sequence(raw.as_str()))
+    else {
+        return failed(VerificationFailure::Malformed);
+    };
+    let Ok(signature) = Signature::from_slice(&signature_bytes) else {
+        return failed(VerificationFailure::Malformed);
+    };
+    let signature_agent = header("signature-agent");
+    let covers = |component: &str| input.components.iter().any(|c| c == component);
+    if input
+        .text("alg")
+        .is_some_and(|alg| !alg.eq_ignore_ascii_case("ed25519"))
+        || !covers("@authority")
+        || (signature_agent.is_some() && !covers("signature-agent"))
+    {
+        return failed(VerificationFailure::Malformed);
+    }
+    let (Some(created), Some(expires), Some(key_id)) = (
+        input.integer("created"),
+        input.integer("expires"),
+        input.text("keyid"),
+    ) else {
+        return failed(VerificationFailure::Malformed);
+    };
+    if expires <= created || expires - created > MAX_VALIDITY_SECONDS {
+        return failed(VerificationFailure::Malformed);
+    }
+    if created > now.saturating_add(MAX_CLOCK_SKEW_SECONDS)
+        || expires.saturating_add(MAX_CLOCK_SKEW_SECONDS) < now
+    {
+        return failed(VerificationFailure::Expired);
+    }
+
+    let Some(agent) = find_agent(cfg, signature_agent) else {
+        return failed(VerificationFailure::UnknownAgent);
+    };
+    let Some(keys) = directory_keys(store, transport, agent, now) else {
+        return failed(VerificationFailure::DirectoryUnavailable);
+    };
+    let Some(key) = keys
+        .iter()
+        .find(|x| key_thumbprint(x) == key_id)
+        .and_then(|x| decode_public_key(x))
+    else {
+        return failed(VerificationFailure::UnknownKey);
+    };
+    let Some(base) = signature_base(req, &input) else {
+        return failed(VerificationFailure::Malformed);
+    };
+    if key.verify_strict(base.as_bytes(), &signature).is_err() {
+        return failed(VerificationFailure::InvalidSignature);
+    }
+    maybe_sweep_expired(store, now);
+    if let Some(nonce) = input.text("nonce") {
+        if nonce.len() > MAX_NONCE_CHARS || !claim_nonce(store, nonce, expires, now) {
+            return failed(VerificationFailure::Replay);
+        }
+    }
+
+    VerificationOutcome::Verified(VerifiedAgent {
+        agent: agent.clone(),
+        key_id: key_id.to_string(),
+    })
+}
+
+/// Counts a request against the agent's per-minute budget; false once it is spent.
+pub(crate) fn within_rate_limit<S: KeyValueStore + ?Sized>(
+    store: &S,
+    agent: &WebBotAuthAgent,
+    now: u64,
+) -> bool {
+    let key = format!("{}{}:{}", RATE_KEY_PREFIX, agent.name, now / 60);
+    let count = store
+        .get(key.as_str())
+        .ok()
+        .flatten()
+        .and_then(|raw| String::from_utf8(raw).ok())
+        .and_then(|raw| raw.parse::<u32>().ok())
+        .unwrap_or(0);
+    if count >= agent.rate_limit {
+        return false;
+    }
+    let _ = store.set(key.as_str(), count.saturating_add(1).to_string().as_bytes());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VerifiedAgentAction;
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockStore {
+        map: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    impl KeyValueStore for MockStore {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, ()> {
+            Ok(self.map.lock().unwrap().get(key).cloned())
+        }
+
+        fn set(&self, key: &str, value: &[u8]) -> Result<(), ()> {
+            self.map
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_vec());
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), ()> {
+            self.map.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        fn get_keys(&self) -> Result<Vec<String>, ()> {
+            Ok(self.map.lock().unwrap().keys().cloned().collect())
+        }
+    }
+
+    struct StubDirectory {
+        body: Vec<u8>,
+        calls: Cell<u32>,
+    }
+
+    impl KeyDirectoryTransport for StubDirectory {
+        fn get(&self, url: &str) -> Result<(u16, Vec<u8>), String> {
+            assert_eq!(
+                url,
+                "https://agent.example/.well-known/http-message-signatures-directory"
+            );
+            self.calls.set(self.calls.get() + 1);
+            Ok((200, self.body.clone()))
+        }
+    }
+
+    const NOW: u64 = 1_735_689_600;
+    const AGENT_ORIGIN: &str = "https://agent.example";
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn public_x() -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(signing_key().verifying_key().as_bytes())
+    }
+
+    fn config(pinned: bool) -> Config {
+        let mut cfg = crate::config::defaults().clone();
+        cfg.web_bot_auth_enabled = true;
+        cfg.web_bot_auth_agents = vec![WebBotAuthAgent {
+            name: "agent".to_string(),
+            directory: AGENT_ORIGIN.to_string(),
+            keys: if pinned { vec![public_x()] } else { Vec::new() },
+            action: VerifiedAgentAction::Allow,
+            rate_limit: 0,
+        }];
+        cfg
+    }
+
+    fn signed_request(params: &str, tamper_path: bool) -> Request {
+        let input = format!(r#"("@authority" "@path" "signature-agent"){}"#, params);
+        let base = format!(
+            "\"@authority\": example.com\n\"@path\": /articles/1\n\"signature-agent\": \"{}\"\n\"@signature-params\": {}",
+            AGENT_ORIGIN, input
+        );
+        let signature =
+            general_purpose::STANDARD.encode(signing_key().sign(base.as_bytes()).to_bytes());
+        Request::builder()
+            .method(Method::Get)
+            .uri(if tamper_path {
+                "/articles/2"
+            } else {
+                "/articles/1"
+            })
+            .header("host", "Example.com")
+            .header("signature-agent", format!("\"{}\"", AGENT_ORIGIN))
+            .header("signature-input", format!("sig1={}", input))
+            .header("signature", format!("sig1=:{}:", signature))
+            .build()
+    }
+
+    fn params(created: u64, expires: u64, extra: &str) -> String {
+        format!(
+            r#";created={};expires={};keyid="{}";alg="ed25519";tag="web-bot-auth"{}"#,
+            created,
+            expires,
+            key_thumbprint(public_x().as_str()),
+            extra
+        )
+    }
+
+    #[test]
+    fn thumbprint_matches_rfc_8037_example() {
+        assert_eq!(
+            key_thumbprint("11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"),
+            "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+        );
+    }
+
+    #[test]
+    fn pinned_key_verifies_and_tampering_fails() {
+        let cfg = config(true);
+        let store = MockStore::default();
+        let transport = OfflineKeyDirectoryTransport;
+        let valid = signed_request(params(NOW - 10, NOW + 60, "").as_str(), false);
+        match verify(&store, &valid, &cfg, &transport, NOW) {
+            VerificationOutcome::Verified(verified) => {
+                assert_eq!(verified.agent.name, "agent");
+                assert_eq!(verified.key_id, key_thumbprint(public_x().as_str()));
+            }
+            other => panic!("expected verified, got {:?}", other),
+        }
+
+        let tampered = signed_request(params(NOW - 10, NOW + 60, "").as_str(), true);
+        assert_eq!(
+            verify(&store, &tampered, &cfg, &transport, NOW),
+            VerificationOutcome::Failed(VerificationFailure::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn rejects_expired_unknown_and_replayed_signatures() {
+        let cfg = config(true);
+        let store = MockStore::default();
+        let transport = OfflineKeyDirectoryTransport;
+
+        let expired = signed_request(params(NOW - 600, NOW - 300, "").as_str(), false);
+        assert_eq!(
+            verify(&store, &expired, &cfg, &transport, NOW),
+            VerificationOutcome::Failed(VerificationFailure::Expired)
+        );
+
+        let mut other_agent = cfg.clone();
+        other_agent.web_bot_auth_agents[0].directory = "https://other.example".to_string();
+        let valid = signed_request(params(NOW, NOW + 60, "").as_str(), false);
+        assert_eq!(
+            verify(&store, &valid, &other_agent, &transport, NOW),
+            VerificationOutcome::Failed(VerificationFailure::UnknownAgent)
+        );
+
+        let with_nonce =
+            signed_request(params(NOW, NOW + 60, r#";nonce="abc123""#).as_str(), false);
+        assert!(matches!(
+            verify(&store, &with_nonce, &cfg, &transport, NOW),
+            VerificationOutcome::Verified(_)
+        ));
+        assert_eq!(
+            verify(&store, &with_nonce, &cfg, &transport, NOW + 1),
+            VerificationOutcome::Failed(VerificationFailure::Replay)
+        );
+    }
+
+    #[test]
+    fn untagged_or_disabled_signatures_are_absent() {
+        let store = MockStore::default();
+        let transport = OfflineKeyDirectoryTransport;
+        let untagged = signed_request(r#";created=1;expires=2;keyid="k""#, false);
+        assert_eq!(
+            verify(&store, &untagged, &config(true), &transport, NOW),
+            VerificationOutcome::Absent
+        );
+
+        let mut disabled = config(true);
+        disabled.web_bot_auth_enabled = false;
+        let valid = signed_request(params(NOW, NOW + 60, "").as_str(), false);
+        assert_eq!(
+            verify(&store, &valid, &disabled, &transport, NOW),
+            VerificationOutcome::Absent
+        );
+    }
+
+    #[test]
+    fn directory_keys_are_fetched_once_and_cached() {
+        let cfg = config(false);
+        let store = MockStore::default();
+        let transport = StubDirectory {
+            body: format!(
+                r#"{{"keys":[{{"kty":"OKP","crv":"Ed25519","x":"{}"}},{{"kty":"RSA","n":"AQAB"}}]}}"#,
+                public_x()
+            )
+            .into_bytes(),
+            calls: Cell::new(0),
+        };
+        let valid = signed_request(params(NOW, NOW + 60, "").as_str(), false);
+
+        assert!(matches!(
+            verify(&store, &valid, &cfg, &transport, NOW),
+            VerificationOutcome::Verified(_)
+        ));
+        assert!(matches!(
+            verify(&store, &valid, &cfg, &transport, NOW + 30),
+            VerificationOutcome::Verified(_)
+        ));
+        assert_eq!(transport.calls.get(), 1);
+
+        assert_eq!(
+            verify(
+                &MockStore::default(),
+                &valid,
+                &cfg,
+                &OfflineKeyDirectoryTransport,
+                NOW
+            ),
+            VerificationOutcome::Failed(VerificationFailure::DirectoryUnavailable)
+        );
+    }
+
+    #[test]
+    fn agent_rate_limit_spans_the_minute_window() {
+        let mut agent = config(true).web_bot_auth_agents[0].clone();
+        agent.rate_limit = 2;
+        let store = MockStore::default();
+        assert!(within_rate_limit(&store, &agent, NOW));
+        assert!(within_rate_limit(&store, &agent, NOW + 1));
+        assert!(!within_rate_limit(&store, &agent, NOW + 2));
+        assert!(within_rate_limit(&store, &agent, NOW + 60));
+    }
+
+    #[test]
+    fn lapsed_nonces_and_closed_rate_windows_are_swept_hourly() {
+        let mut cfg = config(true);
+        cfg.web_bot_auth_agents[0].rate_limit = 10;
+        let store = MockStore::default();
+        let transport = OfflineKeyDirectoryTransport;
+        let agent = &cfg.web_bot_auth_agents[0];
+        assert!(within_rate_limit(&store, agent, NOW));
+        let first = signed_request(params(NOW, NOW + 60, r#";nonce="first""#).as_str(), false);
+        assert!(matches!(
+            verify(&store, &first, &cfg, &transport, NOW),
+            VerificationOutcome::Verified(_)
+        ));
+
+        let later = NOW + SWEEP_INTERVAL_SECONDS;
+        assert!(within_rate_limit(&store, agent, later));
+        let second = signed_request(
+            params(later, later + 60, r#";nonce="second""#).as_str(),
+            false,
+        );
+        assert!(matches!(
+            verify(&store, &second, &cfg, &transport, later),
+            VerificationOutcome::Verified(_)
+        ));
+
+        let mut keys = store.get_keys().unwrap();
+        keys.sort();
+        assert_eq!(keys.len(), 3);
+        assert!(keys[0].starts_with(NONCE_KEY_PREFIX));
+        assert_eq!(
+            keys[1],
+            format!("{}{}:{}", RATE_KEY_PREFIX, agent.name, later / 60)
+        );
+        assert_eq!(keys[2], SWEEP_MARKER_KEY);
+        assert_eq!(stored_u64(&store, keys[0].as_str()), Some(later + 60));
+    }
+
+    #[test]
+    fn validity_longer_than_the_cap_is_malformed() {
+        let cfg = config(true);
+        let store = MockStore::default();
+        let long_lived = signed_request(
+            params(NOW, NOW + MAX_VALIDITY_SECONDS + 1, "").as_str(),
+            false,
+        );
+        assert_eq!(
+            verify(
+                &store,
+                &long_lived,
+                &cfg,
+                &OfflineKeyDirectoryTransport,
+                NOW
+            ),
+            VerificationOutcome::Failed(VerificationFailure::Malformed)
+        );
+    }
+}
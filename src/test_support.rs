@@ -34,8 +34,26 @@ impl crate::challenge::KeyValueStore for InMemoryStore {
         map.remove(key);
         Ok(())
     }
+
+    fn get_keys(&self) -> Result<Vec<String>, ()> {
+        let map = self
+            .map
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(map.keys().cloned().collect())
+    }
 }
 
+// Throwaway 2048-bit issuer key, published the way Privacy Pass issuers do it (RSASSA-PSS SPKI).
+pub(crate) const PRIVACY_PASS_TOKEN_KEY: &str = concat!(
+    "MIIBUjA9BgkqhkiG9w0BAQowMKANMAsGCWCGSAFlAwQCAqEaMBgGCSqGSIb3DQEBCDALBglghkgBZQMEAgKiAwIB",
+    "MAOCAQ8AMIIBCgKCAQEAnmiiJXamR7b9IWeLdAxBJuromTV57yYCxceOiDgB1lp42F73ooWqCuQwRc3Z7U24cxrw",
+    "W9na54BKzO5BDZlBBeOE2ks0RQLVEKZrplGzQQPK4pwPrxPg6YJpSIsj-d1mAANyG2NnJDCcwRqWoIH8IOn9nBJN",
+    "qfKfOBndOAPzVSaQR75Ff4P-1NIS7sXVc7Xbw7WNfwmTVSPls0KHv7AHR6SRbZn_iEGQoyJErXZv7KJ8PYLnC6Zr",
+    "qTsygiv5SKqUdG6nc0fp42lZxW-fgW19_WY98Tk8rCHYpajVFHMnK4MK3BxENWJgwSgo9F6sUzG1oGghqBpa_uZD",
+    "M8AsreVZCQIDAQAB",
+);
+
 static ENV_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 pub(crate) fn lock_env() -> MutexGuard<'static, ()> {